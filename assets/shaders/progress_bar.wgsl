@@ -0,0 +1,19 @@
+#import bevy_ui::ui_vertex_output
+
+struct ProgressBarMaterial {
+    color: vec4<f32>,
+    background_color: vec4<f32>,
+    progress: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: ProgressBarMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = select(material.background_color, material.color, in.uv.x < material.progress);
+    // Anti-alias the rounded corners of the node, and cut the parts clipped by its ancestors
+    let rounded = coverage(sd_rounded_box(in.node_position, in.size, in.border_radius));
+    let clip = clip_coverage(in.mode, in.clip_point, in.clip_size, in.clip_radius);
+    return vec4<f32>(color.rgb, color.a * rounded * clip);
+}
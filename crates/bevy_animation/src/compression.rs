@@ -0,0 +1,275 @@
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::{AnimationClip, Keyframe, Keyframes, VariableCurve};
+
+/// Keyframes of a [`Vec3`] attribute quantized to 16 bits per component, relative to the bounds
+/// of the keyframes.
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct QuantizedVec3Keyframes {
+    /// The minimum of the keyframes.
+    pub min: Vec3,
+    /// The size of the bounds of the keyframes.
+    pub extent: Vec3,
+    /// The keyframes, with each component mapped from the bounds to `0..=u16::MAX`.
+    pub keyframes: Vec<[u16; 3]>,
+}
+
+impl QuantizedVec3Keyframes {
+    /// Quantize the keyframes.
+    pub fn new(keyframes: &[Vec3]) -> Self {
+        let min = keyframes
+            .iter()
+            .copied()
+            .reduce(Vec3::min)
+            .unwrap_or(Vec3::ZERO);
+        let max = keyframes
+            .iter()
+            .copied()
+            .reduce(Vec3::max)
+            .unwrap_or(Vec3::ZERO);
+        let extent = max - min;
+        let quantize = |value: f32, min: f32, extent: f32| {
+            if extent > 0.0 {
+                ((value - min) / extent * u16::MAX as f32).round() as u16
+            } else {
+                0
+            }
+        };
+        Self {
+            min,
+            extent,
+            keyframes: keyframes
+                .iter()
+                .map(|keyframe| {
+                    [
+                        quantize(keyframe.x, min.x, extent.x),
+                        quantize(keyframe.y, min.y, extent.y),
+                        quantize(keyframe.z, min.z, extent.z),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Gets a keyframe.
+    pub fn get(&self, index: usize) -> Option<Vec3> {
+        self.keyframes.get(index).map(|[x, y, z]| {
+            self.min + Vec3::new(*x as f32, *y as f32, *z as f32) / u16::MAX as f32 * self.extent
+        })
+    }
+}
+
+/// Quantize a rotation to 16 bits per component.
+pub(crate) fn quantize_rotation(rotation: Quat) -> [i16; 4] {
+    rotation
+        .normalize()
+        .to_array()
+        .map(|component| (component * i16::MAX as f32).round() as i16)
+}
+
+/// Restore a rotation quantized with [`quantize_rotation`].
+pub(crate) fn dequantize_rotation(rotation: [i16; 4]) -> Quat {
+    Quat::from_array(rotation.map(|component| component as f32 / i16::MAX as f32)).normalize()
+}
+
+/// Settings of the compression of an [`AnimationClip`], see [`AnimationClip::compress`].
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationCompression {
+    /// The maximum error, in radians, of the rotations sampled from the compressed clip.
+    pub rotation_tolerance: f32,
+    /// The maximum error, in distance, of the translations sampled from the compressed clip.
+    pub translation_tolerance: f32,
+    /// The maximum error of the scales sampled from the compressed clip.
+    pub scale_tolerance: f32,
+    /// Whether the keyframes are quantized to 16 bits per component, halving their size.
+    ///
+    /// The quantization error is not included in the tolerances.
+    pub quantize: bool,
+}
+
+impl Default for AnimationCompression {
+    fn default() -> Self {
+        Self {
+            rotation_tolerance: 0.0005,
+            translation_tolerance: 0.0001,
+            scale_tolerance: 0.0001,
+            quantize: true,
+        }
+    }
+}
+
+impl AnimationClip {
+    /// Compress the curves of the clip, removing the keyframes that can be interpolated from
+    /// their neighbours and quantizing the remaining ones.
+    ///
+    /// This reduces the memory used by clips with many keyframes, such as motion capture clips,
+    /// and speeds up their sampling. It can be done when importing them, see the
+    /// `with_animation_compression` setting of the `GltfPlugin`.
+    pub fn compress(&mut self, settings: &AnimationCompression) {
+        for curve in self.curves.iter_mut().flatten() {
+            let tolerance = match curve.keyframes {
+                Keyframes::Rotation(_) | Keyframes::QuantizedRotation(_) => {
+                    settings.rotation_tolerance
+                }
+                Keyframes::Translation(_) | Keyframes::QuantizedTranslation(_) => {
+                    settings.translation_tolerance
+                }
+                Keyframes::Scale(_) | Keyframes::QuantizedScale(_) => settings.scale_tolerance,
+            };
+            curve.reduce_keyframes(tolerance);
+            if settings.quantize {
+                curve.keyframes = curve.keyframes.quantized();
+            }
+        }
+    }
+}
+
+impl VariableCurve {
+    /// Remove the keyframes that can be linearly interpolated from the remaining ones with an
+    /// error of at most `tolerance`.
+    ///
+    /// The error is an angle in radians for rotations, and a distance for translations and
+    /// scales. The first and last keyframes are always kept.
+    pub fn reduce_keyframes(&mut self, tolerance: f32) {
+        let len = self.keyframe_timestamps.len().min(self.keyframes.len());
+        if len <= 2 {
+            return;
+        }
+        let keyframes = (0..len)
+            .filter_map(|index| self.keyframes.get(index))
+            .collect::<Vec<_>>();
+        let timestamps = &self.keyframe_timestamps;
+
+        // Greedily extend the interpolated segment from the last kept keyframe as long as the
+        // keyframes it covers stay within the tolerance
+        let mut kept = vec![0];
+        for index in 1..len - 1 {
+            let start = *kept.last().unwrap();
+            let end = index + 1;
+            let removable = (start + 1..end).all(|covered| {
+                let t = (timestamps[covered] - timestamps[start])
+                    / (timestamps[end] - timestamps[start]);
+                let interpolated = keyframes[start].interpolate(keyframes[end], t);
+                keyframe_error(interpolated, keyframes[covered]) <= tolerance
+            });
+            if !removable {
+                kept.push(index);
+            }
+        }
+        kept.push(len - 1);
+
+        if kept.len() == len {
+            return;
+        }
+        self.keyframe_timestamps = kept.iter().map(|&index| timestamps[index]).collect();
+        match &mut self.keyframes {
+            Keyframes::Rotation(keyframes) => *keyframes = select_keyframes(keyframes, &kept),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+                *keyframes = select_keyframes(keyframes, &kept);
+            }
+            Keyframes::QuantizedRotation(keyframes) => {
+                *keyframes = select_keyframes(keyframes, &kept);
+            }
+            Keyframes::QuantizedTranslation(quantized) | Keyframes::QuantizedScale(quantized) => {
+                quantized.keyframes = select_keyframes(&quantized.keyframes, &kept);
+            }
+        }
+    }
+}
+
+fn select_keyframes<T: Copy>(keyframes: &[T], kept: &[usize]) -> Vec<T> {
+    kept.iter().map(|&index| keyframes[index]).collect()
+}
+
+/// The difference between two keyframes of the same attribute.
+fn keyframe_error(a: Keyframe, b: Keyframe) -> f32 {
+    match (a, b) {
+        (Keyframe::Rotation(a), Keyframe::Rotation(b)) => a.angle_between(b),
+        (Keyframe::Translation(a), Keyframe::Translation(b))
+        | (Keyframe::Scale(a), Keyframe::Scale(b)) => a.distance(b),
+        _ => f32::INFINITY,
+    }
+}
+
+impl Keyframes {
+    /// The keyframes quantized to 16 bits per component.
+    pub fn quantized(&self) -> Keyframes {
+        match self {
+            Keyframes::Rotation(keyframes) => Keyframes::QuantizedRotation(
+                keyframes.iter().copied().map(quantize_rotation).collect(),
+            ),
+            Keyframes::Translation(keyframes) => {
+                Keyframes::QuantizedTranslation(QuantizedVec3Keyframes::new(keyframes))
+            }
+            Keyframes::Scale(keyframes) => {
+                Keyframes::QuantizedScale(QuantizedVec3Keyframes::new(keyframes))
+            }
+            quantized => quantized.clone(),
+        }
+    }
+
+    /// The keyframes with full precision.
+    pub fn decompressed(&self) -> Keyframes {
+        match self {
+            Keyframes::QuantizedRotation(keyframes) => {
+                Keyframes::Rotation(keyframes.iter().copied().map(dequantize_rotation).collect())
+            }
+            Keyframes::QuantizedTranslation(quantized) => Keyframes::Translation(
+                (0..quantized.keyframes.len())
+                    .filter_map(|index| quantized.get(index))
+                    .collect(),
+            ),
+            Keyframes::QuantizedScale(quantized) => Keyframes::Scale(
+                (0..quantized.keyframes.len())
+                    .filter_map(|index| quantized.get(index))
+                    .collect(),
+            ),
+            keyframes => keyframes.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_linear_keyframes() {
+        let mut curve = VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            keyframes: Keyframes::Translation(vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::X * 2.0,
+                Vec3::new(2.0, 1.0, 0.0),
+                Vec3::new(2.0, 2.0, 0.0),
+            ]),
+        };
+        curve.reduce_keyframes(0.001);
+        assert_eq!(vec![0.0, 2.0, 4.0], curve.keyframe_timestamps);
+        assert_eq!(
+            Some(Keyframe::Translation(Vec3::X * 2.0)),
+            curve.keyframes.get(1)
+        );
+    }
+
+    #[test]
+    fn quantize_keyframes() {
+        let rotation = Quat::from_rotation_y(1.0);
+        let keyframes = Keyframes::Rotation(vec![Quat::IDENTITY, rotation]).quantized();
+        let Some(Keyframe::Rotation(quantized)) = keyframes.get(1) else {
+            panic!("expected a rotation keyframe");
+        };
+        assert!(quantized.angle_between(rotation) < 0.001);
+
+        let translations = vec![Vec3::new(-1.0, 0.0, 5.0), Vec3::new(3.0, 0.0, 2.0)];
+        let keyframes = Keyframes::Translation(translations.clone()).quantized();
+        let Keyframes::Translation(decompressed) = keyframes.decompressed() else {
+            panic!("expected translation keyframes");
+        };
+        for (decompressed, translation) in decompressed.iter().zip(&translations) {
+            assert!(decompressed.distance(*translation) < 0.001);
+        }
+    }
+}
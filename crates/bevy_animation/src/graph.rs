@@ -0,0 +1,519 @@
+use std::time::Duration;
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::Vec2;
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
+use bevy_time::Time;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{AnimationClip, AnimationPlayer};
+
+/// Identifier of a state of an [`AnimationGraph`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub struct AnimationStateId(pub usize);
+
+/// Describes which [`AnimationClip`]s are played in a state of an [`AnimationGraph`], and how
+/// they are blended depending on the parameters of the [`AnimationGraphPlayer`].
+#[derive(Clone, Debug)]
+pub enum BlendTree {
+    /// Play a single clip.
+    Clip(Handle<AnimationClip>),
+    /// Blend between the two children whose thresholds surround the value of a float parameter.
+    ///
+    /// Values outside of the thresholds use the closest child.
+    Blend1d {
+        /// The name of the float parameter.
+        parameter: String,
+        /// The children and their thresholds.
+        children: Vec<(f32, BlendTree)>,
+    },
+    /// Blend the children by their distance to the position given by two float parameters.
+    ///
+    /// The children are weighted by their inverse squared distance to the position.
+    Blend2d {
+        /// The names of the float parameters used as the x and y coordinates of the position.
+        parameters: [String; 2],
+        /// The children and their positions.
+        children: Vec<(Vec2, BlendTree)>,
+    },
+}
+
+impl BlendTree {
+    /// Adds the weight of each clip of the tree to `weights`, with a total weight of `weight`.
+    fn evaluate(
+        &self,
+        parameters: &AnimationGraphPlayer,
+        weight: f32,
+        weights: &mut HashMap<Handle<AnimationClip>, f32>,
+    ) {
+        match self {
+            BlendTree::Clip(handle) => {
+                *weights.entry(handle.clone_weak()).or_insert(0.0) += weight;
+            }
+            BlendTree::Blend1d {
+                parameter,
+                children,
+            } => {
+                let value = parameters.float(parameter);
+                let lower = children
+                    .iter()
+                    .filter(|(threshold, _)| *threshold <= value)
+                    .max_by(|(a, _), (b, _)| a.total_cmp(b));
+                let upper = children
+                    .iter()
+                    .filter(|(threshold, _)| *threshold >= value)
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b));
+                match (lower, upper) {
+                    (Some((lower_threshold, lower)), Some((upper_threshold, upper)))
+                        if lower_threshold < upper_threshold =>
+                    {
+                        let t = (value - lower_threshold) / (upper_threshold - lower_threshold);
+                        lower.evaluate(parameters, weight * (1.0 - t), weights);
+                        upper.evaluate(parameters, weight * t, weights);
+                    }
+                    (Some((_, child)), _) | (None, Some((_, child))) => {
+                        child.evaluate(parameters, weight, weights);
+                    }
+                    (None, None) => {}
+                }
+            }
+            BlendTree::Blend2d {
+                parameters: [x, y],
+                children,
+            } => {
+                let position = Vec2::new(parameters.float(x), parameters.float(y));
+                // A child at the exact position takes the whole weight
+                if let Some((_, child)) = children
+                    .iter()
+                    .find(|(child_position, _)| child_position.distance_squared(position) == 0.0)
+                {
+                    child.evaluate(parameters, weight, weights);
+                    return;
+                }
+                let total_inverse_distance: f32 = children
+                    .iter()
+                    .map(|(child_position, _)| 1.0 / child_position.distance_squared(position))
+                    .sum();
+                for (child_position, child) in children {
+                    let inverse_distance = 1.0 / child_position.distance_squared(position);
+                    child.evaluate(
+                        parameters,
+                        weight * inverse_distance / total_inverse_distance,
+                        weights,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A state of an [`AnimationGraph`].
+#[derive(Clone, Debug)]
+pub struct AnimationGraphState {
+    /// The name of the state.
+    pub name: String,
+    /// The clips played in this state.
+    pub blend_tree: BlendTree,
+}
+
+/// A condition on a parameter of an [`AnimationGraphPlayer`] for a transition to happen.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransitionCondition {
+    /// The float parameter is greater than the value.
+    Greater(String, f32),
+    /// The float parameter is less than the value.
+    Less(String, f32),
+    /// The bool parameter has the value.
+    Bool(String, bool),
+    /// The trigger is set. It is reset when the transition happens.
+    Trigger(String),
+}
+
+impl TransitionCondition {
+    fn is_met(&self, parameters: &AnimationGraphPlayer) -> bool {
+        match self {
+            TransitionCondition::Greater(parameter, value) => parameters.float(parameter) > *value,
+            TransitionCondition::Less(parameter, value) => parameters.float(parameter) < *value,
+            TransitionCondition::Bool(parameter, value) => parameters.bool(parameter) == *value,
+            TransitionCondition::Trigger(trigger) => parameters.triggers.contains(trigger),
+        }
+    }
+}
+
+/// A transition between two states of an [`AnimationGraph`].
+#[derive(Clone, Debug)]
+pub struct AnimationGraphTransition {
+    /// The state the transition starts from, or `None` to start from any other state.
+    pub from: Option<AnimationStateId>,
+    /// The state the transition goes to.
+    pub to: AnimationStateId,
+    /// The conditions that must all be met for the transition to happen.
+    pub conditions: Vec<TransitionCondition>,
+    /// The duration of the cross-fade between the clips of the two states.
+    pub duration: Duration,
+}
+
+impl AnimationGraphTransition {
+    /// Create an instant transition from `from` to `to`, without conditions.
+    pub fn new(from: AnimationStateId, to: AnimationStateId) -> Self {
+        Self {
+            from: Some(from),
+            to,
+            conditions: Vec::new(),
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Create an instant transition from any other state to `to`, without conditions.
+    pub fn from_any(to: AnimationStateId) -> Self {
+        Self {
+            from: None,
+            to,
+            conditions: Vec::new(),
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Add a condition to the transition.
+    pub fn with_condition(mut self, condition: TransitionCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Set the duration of the cross-fade of the transition.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+/// A state machine of animations, played by an [`AnimationGraphPlayer`].
+///
+/// Each state plays a [`BlendTree`] of clips. The first transition whose conditions are met
+/// moves the graph to another state, cross-fading the clips of the two states.
+#[derive(Clone, Debug, Default, TypeUuid)]
+#[uuid = "4a5c0f3e-3c3b-4d0a-8d3c-6b8f2f5f7c21"]
+pub struct AnimationGraph {
+    states: Vec<AnimationGraphState>,
+    transitions: Vec<AnimationGraphTransition>,
+    initial_state: AnimationStateId,
+}
+
+impl AnimationGraph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a state to the graph. The first state added is the initial state.
+    pub fn add_state(
+        &mut self,
+        name: impl Into<String>,
+        blend_tree: BlendTree,
+    ) -> AnimationStateId {
+        self.states.push(AnimationGraphState {
+            name: name.into(),
+            blend_tree,
+        });
+        AnimationStateId(self.states.len() - 1)
+    }
+
+    /// Add a transition to the graph. Transitions are checked in the order they were added.
+    pub fn add_transition(&mut self, transition: AnimationGraphTransition) -> &mut Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Set the state the graph starts in.
+    pub fn set_initial_state(&mut self, state: AnimationStateId) -> &mut Self {
+        self.initial_state = state;
+        self
+    }
+
+    /// The state the graph starts in.
+    pub fn initial_state(&self) -> AnimationStateId {
+        self.initial_state
+    }
+
+    /// Gets a state of the graph.
+    pub fn state(&self, state: AnimationStateId) -> Option<&AnimationGraphState> {
+        self.states.get(state.0)
+    }
+
+    /// Gets a state of the graph by its name.
+    pub fn state_by_name(&self, name: &str) -> Option<AnimationStateId> {
+        self.states
+            .iter()
+            .position(|state| state.name == name)
+            .map(AnimationStateId)
+    }
+
+    /// The transitions of the graph.
+    pub fn transitions(&self) -> &[AnimationGraphTransition] {
+        &self.transitions
+    }
+}
+
+/// A cross-fade between two states in progress.
+#[derive(Clone, Debug)]
+struct ActiveTransition {
+    from: AnimationStateId,
+    elapsed: f32,
+    duration: f32,
+}
+
+/// Plays an [`AnimationGraph`] with the [`AnimationPlayer`] of the same entity.
+///
+/// The player drives the blended animations of the [`AnimationPlayer`], and sets the weight of
+/// its main animation to 0.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct AnimationGraphPlayer {
+    graph: Handle<AnimationGraph>,
+    state: Option<AnimationStateId>,
+    floats: HashMap<String, f32>,
+    bools: HashMap<String, bool>,
+    triggers: HashSet<String>,
+    #[reflect(ignore)]
+    transition: Option<ActiveTransition>,
+}
+
+impl AnimationGraphPlayer {
+    /// Create a player for the given graph, starting in its initial state.
+    pub fn new(graph: Handle<AnimationGraph>) -> Self {
+        Self {
+            graph,
+            ..Default::default()
+        }
+    }
+
+    /// The graph being played.
+    pub fn graph(&self) -> &Handle<AnimationGraph> {
+        &self.graph
+    }
+
+    /// The current state of the graph, once it started playing.
+    pub fn state(&self) -> Option<AnimationStateId> {
+        self.state
+    }
+
+    /// Is a transition between two states in progress
+    pub fn is_in_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Move the graph to a state, ignoring the transitions of the graph.
+    pub fn set_state(&mut self, state: AnimationStateId, transition_duration: Duration) {
+        self.transition = self
+            .state
+            .filter(|_| !transition_duration.is_zero())
+            .map(|from| ActiveTransition {
+                from,
+                elapsed: 0.0,
+                duration: transition_duration.as_secs_f32(),
+            });
+        self.state = Some(state);
+    }
+
+    /// Value of a float parameter, 0 if it wasn't set.
+    pub fn float(&self, parameter: &str) -> f32 {
+        self.floats.get(parameter).copied().unwrap_or(0.0)
+    }
+
+    /// Set the value of a float parameter.
+    pub fn set_float(&mut self, parameter: impl Into<String>, value: f32) -> &mut Self {
+        self.floats.insert(parameter.into(), value);
+        self
+    }
+
+    /// Value of a bool parameter, `false` if it wasn't set.
+    pub fn bool(&self, parameter: &str) -> bool {
+        self.bools.get(parameter).copied().unwrap_or(false)
+    }
+
+    /// Set the value of a bool parameter.
+    pub fn set_bool(&mut self, parameter: impl Into<String>, value: bool) -> &mut Self {
+        self.bools.insert(parameter.into(), value);
+        self
+    }
+
+    /// Set a trigger. It stays set until a transition with a condition on it happens.
+    pub fn set_trigger(&mut self, trigger: impl Into<String>) -> &mut Self {
+        self.triggers.insert(trigger.into());
+        self
+    }
+
+    /// Reset a trigger.
+    pub fn reset_trigger(&mut self, trigger: &str) -> &mut Self {
+        self.triggers.remove(trigger);
+        self
+    }
+
+    /// Take the first transition of the graph whose conditions are met from the current state.
+    fn update_state(&mut self, graph: &AnimationGraph) {
+        let state = match self.state {
+            Some(state) => state,
+            None => {
+                self.state = Some(graph.initial_state);
+                return;
+            }
+        };
+        if self.transition.is_some() {
+            return;
+        }
+        let transition = graph.transitions.iter().find(|transition| {
+            transition
+                .from
+                .map_or(transition.to != state, |from| from == state)
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.is_met(self))
+        });
+        if let Some(transition) = transition {
+            for condition in &transition.conditions {
+                if let TransitionCondition::Trigger(trigger) = condition {
+                    self.triggers.remove(trigger);
+                }
+            }
+            self.set_state(transition.to, transition.duration);
+        }
+    }
+
+    /// Compute the weight of each clip of the current and previous states.
+    fn clip_weights(&self, graph: &AnimationGraph) -> HashMap<Handle<AnimationClip>, f32> {
+        let mut weights = HashMap::default();
+        let mut state_weight = 1.0;
+        if let Some(transition) = &self.transition {
+            state_weight = (transition.elapsed / transition.duration).clamp(0.0, 1.0);
+            if let Some(from) = graph.state(transition.from) {
+                from.blend_tree
+                    .evaluate(self, 1.0 - state_weight, &mut weights);
+            }
+        }
+        if let Some(state) = self.state.and_then(|state| graph.state(state)) {
+            state.blend_tree.evaluate(self, state_weight, &mut weights);
+        }
+        weights
+    }
+}
+
+/// System that plays the [`AnimationGraph`] of each [`AnimationGraphPlayer`] with the
+/// [`AnimationPlayer`] of its entity.
+pub fn animation_graph_player(
+    time: Res<Time>,
+    graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(&mut AnimationGraphPlayer, &mut AnimationPlayer)>,
+) {
+    for (mut graph_player, mut player) in &mut players {
+        let Some(graph) = graphs.get(&graph_player.graph) else {
+            continue;
+        };
+        if graph.states.is_empty() {
+            continue;
+        }
+
+        let graph_player = &mut *graph_player;
+        if let Some(transition) = &mut graph_player.transition {
+            transition.elapsed += time.delta_seconds();
+            if transition.elapsed >= transition.duration {
+                graph_player.transition = None;
+            }
+        }
+        graph_player.update_state(graph);
+
+        let weights = graph_player.clip_weights(graph);
+        if player.weight() != 0.0 {
+            player.set_weight(0.0);
+        }
+        player
+            .blended
+            .retain(|blended| weights.contains_key(&blended.animation.animation_clip));
+        for (clip, weight) in weights {
+            player.blend(clip, weight);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::HandleId;
+
+    fn clip(id: u64) -> Handle<AnimationClip> {
+        Handle::weak(HandleId::new(AnimationClip::TYPE_UUID, id))
+    }
+
+    #[test]
+    fn blend_1d_weights() {
+        let tree = BlendTree::Blend1d {
+            parameter: "speed".to_string(),
+            children: vec![
+                (0.0, BlendTree::Clip(clip(0))),
+                (1.0, BlendTree::Clip(clip(1))),
+                (3.0, BlendTree::Clip(clip(2))),
+            ],
+        };
+        let mut player = AnimationGraphPlayer::default();
+        let weights = |player: &AnimationGraphPlayer| {
+            let mut weights = HashMap::default();
+            tree.evaluate(player, 1.0, &mut weights);
+            weights
+        };
+
+        player.set_float("speed", 2.0);
+        let blended = weights(&player);
+        assert_eq!(2, blended.len());
+        assert_eq!(0.5, blended[&clip(1)]);
+        assert_eq!(0.5, blended[&clip(2)]);
+
+        player.set_float("speed", 5.0);
+        assert_eq!(1.0, weights(&player)[&clip(2)]);
+        player.set_float("speed", 1.0);
+        assert_eq!(1.0, weights(&player)[&clip(1)]);
+    }
+
+    #[test]
+    fn transition_on_conditions() {
+        let mut graph = AnimationGraph::new();
+        let idle = graph.add_state("idle", BlendTree::Clip(clip(0)));
+        let run = graph.add_state("run", BlendTree::Clip(clip(1)));
+        let jump = graph.add_state("jump", BlendTree::Clip(clip(2)));
+        graph
+            .add_transition(
+                AnimationGraphTransition::new(idle, run)
+                    .with_condition(TransitionCondition::Greater("speed".to_string(), 0.5))
+                    .with_duration(Duration::from_secs(1)),
+            )
+            .add_transition(
+                AnimationGraphTransition::from_any(jump)
+                    .with_condition(TransitionCondition::Trigger("jump".to_string())),
+            );
+
+        let mut player = AnimationGraphPlayer::default();
+        player.update_state(&graph);
+        assert_eq!(Some(idle), player.state());
+        player.update_state(&graph);
+        assert_eq!(Some(idle), player.state());
+
+        player.set_float("speed", 1.0);
+        player.update_state(&graph);
+        assert_eq!(Some(run), player.state());
+        assert!(player.is_in_transition());
+        player.transition.as_mut().unwrap().elapsed = 0.25;
+        let weights = player.clip_weights(&graph);
+        assert_eq!(0.75, weights[&clip(0)]);
+        assert_eq!(0.25, weights[&clip(1)]);
+
+        player.transition = None;
+        player.set_trigger("jump");
+        player.update_state(&graph);
+        assert_eq!(Some(jump), player.state());
+        assert!(player.triggers.is_empty());
+    }
+}
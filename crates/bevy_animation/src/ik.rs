@@ -0,0 +1,318 @@
+use bevy_ecs::{entity::Entity, prelude::Component, system::Query};
+use bevy_hierarchy::Parent;
+use bevy_math::{Quat, Vec3};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+
+/// Inverse kinematics constraint solving a chain of two bones, such as a leg or an arm, so that
+/// the entity with this component reaches the target.
+///
+/// The entity is the end of the chain, such as a foot or a hand. Its parent is the middle joint,
+/// and its grandparent the root joint of the chain. Only the rotations of the two joints are
+/// changed. The constraint is solved after the animations are applied, and before the
+/// [`GlobalTransform`]s are propagated.
+#[derive(Component, Clone, Debug)]
+pub struct TwoBoneIk {
+    /// The entity to reach.
+    pub target: Entity,
+    /// An entity the middle joint bends towards, such as a point in front of a knee.
+    ///
+    /// Without a pole, the chain keeps the bending plane it has before solving.
+    pub pole: Option<Entity>,
+    /// How much the solved rotations replace the animated rotations, from 0 to 1.
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Create a constraint reaching `target`, with a weight of 1.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Inverse kinematics constraint solving a chain of bones of any length with the FABRIK
+/// algorithm, so that the entity with this component reaches the target.
+///
+/// The entity is the end of the chain, which is made of its `chain_length` closest ancestors.
+/// Only the rotations of the ancestors are changed. The constraint is solved after the animations
+/// are applied, and before the [`GlobalTransform`]s are propagated.
+#[derive(Component, Clone, Debug)]
+pub struct FabrikIk {
+    /// The entity to reach.
+    pub target: Entity,
+    /// The number of ancestors of the entity that are rotated.
+    pub chain_length: usize,
+    /// The maximum number of iterations of the algorithm.
+    pub iterations: usize,
+    /// The distance to the target under which the chain is considered solved.
+    pub tolerance: f32,
+    /// How much the solved rotations replace the animated rotations, from 0 to 1.
+    pub weight: f32,
+}
+
+impl FabrikIk {
+    /// Create a constraint reaching `target` with a chain of `chain_length` ancestors.
+    pub fn new(target: Entity, chain_length: usize) -> Self {
+        Self {
+            target,
+            chain_length,
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Computes the global transform of an entity from the [`Transform`]s of its ancestors, as the
+/// [`GlobalTransform`]s of this frame are not propagated yet.
+fn compute_global_transform(
+    entity: Entity,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&Parent>,
+) -> Option<GlobalTransform> {
+    let transform = *transforms.get(entity).ok()?;
+    Some(match parents.get(entity) {
+        Ok(parent) => {
+            compute_global_transform(parent.get(), transforms, parents)?.mul_transform(transform)
+        }
+        Err(_) => GlobalTransform::from(transform),
+    })
+}
+
+/// Rotates the joint by the global `rotation`, given its global rotation before the change.
+fn rotate_joint(transform: &mut Transform, global_rotation: Quat, rotation: Quat, weight: f32) {
+    let local_rotation = global_rotation.inverse() * rotation * global_rotation;
+    transform.rotation =
+        (transform.rotation * Quat::IDENTITY.slerp(local_rotation, weight)).normalize();
+}
+
+/// The angle between two vectors, robust to rounding errors.
+fn angle_between(a: Vec3, b: Vec3) -> f32 {
+    a.normalize().dot(b.normalize()).clamp(-1.0, 1.0).acos()
+}
+
+/// System that solves the [`TwoBoneIk`] and [`FabrikIk`] constraints.
+pub fn inverse_kinematics(
+    two_bone_constraints: Query<(Entity, &TwoBoneIk)>,
+    fabrik_constraints: Query<(Entity, &FabrikIk)>,
+    parents: Query<&Parent>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (end, constraint) in &two_bone_constraints {
+        solve_two_bone(end, constraint, &parents, &mut transforms);
+    }
+    for (end, constraint) in &fabrik_constraints {
+        solve_fabrik(end, constraint, &parents, &mut transforms);
+    }
+}
+
+fn solve_two_bone(
+    end: Entity,
+    constraint: &TwoBoneIk,
+    parents: &Query<&Parent>,
+    transforms: &mut Query<&mut Transform>,
+) -> Option<()> {
+    let mid = parents.get(end).ok()?.get();
+    let root = parents.get(mid).ok()?.get();
+    let global = |entity| compute_global_transform(entity, transforms, parents);
+    let (_, root_rotation, a) = global(root)?.to_scale_rotation_translation();
+    let (_, mid_rotation, b) = global(mid)?.to_scale_rotation_translation();
+    let c = global(end)?.translation();
+    let target = global(constraint.target)?.translation();
+
+    let length_ab = (b - a).length();
+    let length_bc = (c - b).length();
+    if length_ab == 0.0 || length_bc == 0.0 {
+        return None;
+    }
+    let length_at = (target - a)
+        .length()
+        .clamp((length_ab - length_bc).abs(), length_ab + length_bc);
+
+    // Bend the middle joint for the chain to span the distance to the target
+    let angle_ba_bc = angle_between(a - b, c - b);
+    let desired_angle_ba_bc = ((length_ab * length_ab + length_bc * length_bc
+        - length_at * length_at)
+        / (2.0 * length_ab * length_bc))
+        .clamp(-1.0, 1.0)
+        .acos();
+    // Rotating around this axis opens the angle of the middle joint. A straight chain can bend in
+    // any plane, the pole orients it afterwards.
+    let bend_axis = (a - b)
+        .cross(c - b)
+        .try_normalize()
+        .unwrap_or_else(|| (c - b).any_orthonormal_vector());
+    let mid_world_rotation = Quat::from_axis_angle(bend_axis, desired_angle_ba_bc - angle_ba_bc);
+
+    // Rotate the root joint for the end of the bent chain to point at the target
+    let bent_c = b + mid_world_rotation * (c - b);
+    let mut root_world_rotation =
+        Quat::from_rotation_arc((bent_c - a).try_normalize()?, (target - a).try_normalize()?);
+
+    // Twist the chain around the direction of the target for the middle joint to face the pole
+    if let Some(pole) = constraint.pole.and_then(global) {
+        let axis = (target - a).normalize();
+        let to_plane = |v: Vec3| v - axis * v.dot(axis);
+        let mid_direction = to_plane(root_world_rotation * (b - a));
+        let pole_direction = to_plane(pole.translation() - a);
+        if let (Some(mid_direction), Some(pole_direction)) = (
+            mid_direction.try_normalize(),
+            pole_direction.try_normalize(),
+        ) {
+            let angle = mid_direction
+                .dot(pole_direction)
+                .clamp(-1.0, 1.0)
+                .acos()
+                .copysign(mid_direction.cross(pole_direction).dot(axis));
+            root_world_rotation = Quat::from_axis_angle(axis, angle) * root_world_rotation;
+        }
+    }
+
+    let weight = constraint.weight.clamp(0.0, 1.0);
+    rotate_joint(
+        transforms.get_mut(root).ok()?.as_mut(),
+        root_rotation,
+        root_world_rotation,
+        weight,
+    );
+    rotate_joint(
+        transforms.get_mut(mid).ok()?.as_mut(),
+        mid_rotation,
+        mid_world_rotation,
+        weight,
+    );
+    Some(())
+}
+
+fn solve_fabrik(
+    end: Entity,
+    constraint: &FabrikIk,
+    parents: &Query<&Parent>,
+    transforms: &mut Query<&mut Transform>,
+) -> Option<()> {
+    // The joints of the chain, from the root to the end
+    let mut joints = vec![end];
+    for _ in 0..constraint.chain_length {
+        joints.push(parents.get(*joints.last().unwrap()).ok()?.get());
+    }
+    joints.reverse();
+
+    let mut rotations = Vec::with_capacity(joints.len());
+    let mut positions = Vec::with_capacity(joints.len());
+    for joint in &joints {
+        let (_, rotation, translation) =
+            compute_global_transform(*joint, transforms, parents)?.to_scale_rotation_translation();
+        rotations.push(rotation);
+        positions.push(translation);
+    }
+    let target = compute_global_transform(constraint.target, transforms, parents)?.translation();
+    let lengths = positions
+        .windows(2)
+        .map(|bone| (bone[1] - bone[0]).length())
+        .collect::<Vec<_>>();
+
+    // Move the joints to reach the target while keeping the length of the bones
+    let mut solved = positions.clone();
+    let root = solved[0];
+    for _ in 0..constraint.iterations {
+        if (*solved.last().unwrap() - target).length() <= constraint.tolerance {
+            break;
+        }
+        // Backward pass, from the target to the root
+        *solved.last_mut().unwrap() = target;
+        for i in (0..solved.len() - 1).rev() {
+            let direction = (solved[i] - solved[i + 1]).normalize_or_zero();
+            solved[i] = solved[i + 1] + direction * lengths[i];
+        }
+        // Forward pass, from the root to the target
+        solved[0] = root;
+        for i in 0..solved.len() - 1 {
+            let direction = (solved[i + 1] - solved[i]).normalize_or_zero();
+            solved[i + 1] = solved[i] + direction * lengths[i];
+        }
+    }
+
+    // Rotate each joint to point its bone at the solved position of the next joint, moving the
+    // following joints with it
+    let weight = constraint.weight.clamp(0.0, 1.0);
+    for i in 0..joints.len() - 1 {
+        let (Some(current), Some(solved_direction)) = (
+            (positions[i + 1] - positions[i]).try_normalize(),
+            (solved[i + 1] - solved[i]).try_normalize(),
+        ) else {
+            continue;
+        };
+        let rotation =
+            Quat::IDENTITY.slerp(Quat::from_rotation_arc(current, solved_direction), weight);
+        rotate_joint(
+            transforms.get_mut(joints[i]).ok()?.as_mut(),
+            rotations[i],
+            rotation,
+            1.0,
+        );
+        for j in i + 1..joints.len() {
+            positions[j] = positions[i] + rotation * (positions[j] - positions[i]);
+            rotations[j] = rotation * rotations[j];
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+    use bevy_hierarchy::BuildWorldChildren;
+
+    /// Spawns a chain of joints one unit apart along the y axis, returning the end of the chain.
+    fn spawn_chain(world: &mut World, length: usize) -> Entity {
+        let mut joint = world.spawn(Transform::default()).id();
+        for _ in 0..length {
+            let child = world.spawn(Transform::from_xyz(0.0, 1.0, 0.0)).id();
+            world.entity_mut(joint).push_children(&[child]);
+            joint = child;
+        }
+        joint
+    }
+
+    fn end_position(world: &mut World, end: Entity) -> Vec3 {
+        let mut state =
+            bevy_ecs::system::SystemState::<(Query<&mut Transform>, Query<&Parent>)>::new(world);
+        let (transforms, parents) = state.get_mut(world);
+        compute_global_transform(end, &transforms, &parents)
+            .unwrap()
+            .translation()
+    }
+
+    #[test]
+    fn solve_two_bone_chain() {
+        let mut world = World::new();
+        let end = spawn_chain(&mut world, 2);
+        let target = world.spawn(Transform::from_xyz(1.0, 1.0, 0.5)).id();
+        world.entity_mut(end).insert(TwoBoneIk::new(target));
+
+        SystemStage::single(inverse_kinematics).run(&mut world);
+
+        let position = end_position(&mut world, end);
+        assert!((position - Vec3::new(1.0, 1.0, 0.5)).length() < 0.01);
+    }
+
+    #[test]
+    fn solve_fabrik_chain() {
+        let mut world = World::new();
+        let end = spawn_chain(&mut world, 4);
+        let target = world.spawn(Transform::from_xyz(2.0, 1.0, 1.0)).id();
+        world.entity_mut(end).insert(FabrikIk::new(target, 4));
+
+        SystemStage::single(inverse_kinematics).run(&mut world);
+
+        let position = end_position(&mut world, end);
+        assert!((position - Vec3::new(2.0, 1.0, 1.0)).length() < 0.01);
+    }
+}
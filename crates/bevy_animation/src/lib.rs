@@ -2,6 +2,14 @@
 
 #![warn(missing_docs)]
 
+mod compression;
+mod graph;
+mod ik;
+mod mask;
+mod morph;
+mod property;
+mod retarget;
+
 use std::ops::Deref;
 use std::time::Duration;
 
@@ -9,8 +17,9 @@ use bevy_app::{App, CoreStage, Plugin};
 use bevy_asset::{AddAsset, Assets, Handle};
 use bevy_core::Name;
 use bevy_ecs::{
-    change_detection::{DetectChanges, Mut},
+    change_detection::{DetectChanges, DetectChangesMut, Mut},
     entity::Entity,
+    event::EventWriter,
     prelude::Component,
     query::With,
     reflect::ReflectComponent,
@@ -24,11 +33,21 @@ use bevy_time::Time;
 use bevy_transform::{prelude::Transform, TransformSystem};
 use bevy_utils::{tracing::warn, HashMap};
 
+pub use compression::*;
+pub use graph::*;
+pub use ik::*;
+pub use mask::*;
+pub use morph::*;
+pub use property::*;
+pub use retarget::*;
+
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AnimationClip, AnimationPlayer, AnimationPlugin, EntityPath, Keyframes, VariableCurve,
+        AnimationClip, AnimationEvent, AnimationGraph, AnimationGraphPlayer, AnimationMask,
+        AnimationPlayer, AnimationPlugin, AnimationRetargeting, BlendTree, EntityPath, FabrikIk,
+        Keyframes, MorphWeights, PropertyCurve, PropertyKeyframes, TwoBoneIk, VariableCurve,
     };
 }
 
@@ -41,6 +60,120 @@ pub enum Keyframes {
     Translation(Vec<Vec3>),
     /// Keyframes for scale.
     Scale(Vec<Vec3>),
+    /// Keyframes for rotation, quantized to 16 bits per component.
+    QuantizedRotation(Vec<[i16; 4]>),
+    /// Keyframes for translation, quantized to 16 bits per component.
+    QuantizedTranslation(QuantizedVec3Keyframes),
+    /// Keyframes for scale, quantized to 16 bits per component.
+    QuantizedScale(QuantizedVec3Keyframes),
+}
+
+impl Keyframes {
+    /// Number of keyframes.
+    pub fn len(&self) -> usize {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.len(),
+            Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => keyframes.len(),
+            Keyframes::QuantizedRotation(keyframes) => keyframes.len(),
+            Keyframes::QuantizedTranslation(quantized) | Keyframes::QuantizedScale(quantized) => {
+                quantized.keyframes.len()
+            }
+        }
+    }
+
+    /// Returns `true` if there are no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a keyframe.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Keyframe> {
+        match self {
+            Keyframes::Rotation(keyframes) => keyframes.get(index).copied().map(Keyframe::Rotation),
+            Keyframes::Translation(keyframes) => {
+                keyframes.get(index).copied().map(Keyframe::Translation)
+            }
+            Keyframes::Scale(keyframes) => keyframes.get(index).copied().map(Keyframe::Scale),
+            Keyframes::QuantizedRotation(keyframes) => keyframes
+                .get(index)
+                .map(|keyframe| Keyframe::Rotation(dequantize_rotation(*keyframe))),
+            Keyframes::QuantizedTranslation(quantized) => {
+                quantized.get(index).map(Keyframe::Translation)
+            }
+            Keyframes::QuantizedScale(quantized) => quantized.get(index).map(Keyframe::Scale),
+        }
+    }
+}
+
+/// A single keyframe of one of the attributes of a [`Transform`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Keyframe {
+    /// Keyframe for rotation.
+    Rotation(Quat),
+    /// Keyframe for translation.
+    Translation(Vec3),
+    /// Keyframe for scale.
+    Scale(Vec3),
+}
+
+impl Keyframe {
+    /// Interpolate between this keyframe and the next one of the same attribute.
+    pub fn interpolate(self, end: Keyframe, t: f32) -> Keyframe {
+        match (self, end) {
+            (Keyframe::Rotation(rot_start), Keyframe::Rotation(mut rot_end)) => {
+                // Choose the smallest angle for the rotation
+                if rot_end.dot(rot_start) < 0.0 {
+                    rot_end = -rot_end;
+                }
+                // Rotations are using a spherical linear interpolation
+                Keyframe::Rotation(rot_start.normalize().slerp(rot_end.normalize(), t))
+            }
+            (Keyframe::Translation(start), Keyframe::Translation(end)) => {
+                Keyframe::Translation(start.lerp(end, t))
+            }
+            (Keyframe::Scale(start), Keyframe::Scale(end)) => Keyframe::Scale(start.lerp(end, t)),
+            (start, _) => start,
+        }
+    }
+
+    /// Interpolate the attribute of the transform towards the keyframe by `weight`.
+    fn apply(self, transform: &mut Transform, weight: f32) {
+        match self {
+            Keyframe::Rotation(rotation) => {
+                transform.rotation = transform.rotation.slerp(rotation, weight);
+            }
+            Keyframe::Translation(translation) => {
+                transform.translation = transform.translation.lerp(translation, weight);
+            }
+            Keyframe::Scale(scale) => {
+                transform.scale = transform.scale.lerp(scale, weight);
+            }
+        }
+    }
+
+    /// Add the difference between the keyframe and the `reference` keyframe, scaled by `weight`,
+    /// to the attribute of the transform.
+    fn apply_additive(self, reference: Keyframe, transform: &mut Transform, weight: f32) {
+        match (self, reference) {
+            (Keyframe::Rotation(rotation), Keyframe::Rotation(reference)) => {
+                let mut difference = reference.normalize().inverse() * rotation;
+                // Choose the smallest angle for the difference
+                if difference.w < 0.0 {
+                    difference = -difference;
+                }
+                transform.rotation *= Quat::IDENTITY.slerp(difference, weight);
+            }
+            (Keyframe::Translation(translation), Keyframe::Translation(reference)) => {
+                transform.translation += (translation - reference) * weight;
+            }
+            (Keyframe::Scale(scale), Keyframe::Scale(reference)) => {
+                transform.scale *= Vec3::ONE.lerp(scale / reference, weight);
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Describes how an attribute of a [`Transform`] should be animated.
@@ -61,13 +194,25 @@ pub struct EntityPath {
     pub parts: Vec<Name>,
 }
 
-/// A list of [`VariableCurve`], and the [`EntityPath`] to which they apply.
+/// A named event at a timestamp of an [`AnimationClip`].
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct AnimationClipEvent {
+    /// Timestamp of the event, in seconds.
+    pub time: f32,
+    /// Name of the event.
+    pub name: String,
+}
+
+/// A list of [`VariableCurve`] and [`PropertyCurve`], and the [`EntityPath`] to which they apply.
 #[derive(Reflect, FromReflect, Clone, TypeUuid, Debug, Default)]
 #[uuid = "d81b7179-0448-4eb0-89fe-c067222725bf"]
 pub struct AnimationClip {
     curves: Vec<Vec<VariableCurve>>,
     paths: HashMap<EntityPath, usize>,
+    property_curves: Vec<Vec<PropertyCurve>>,
+    property_paths: HashMap<EntityPath, usize>,
     duration: f32,
+    events: Vec<AnimationClipEvent>,
 }
 
 impl AnimationClip {
@@ -113,6 +258,37 @@ impl AnimationClip {
             self.paths.insert(path, idx);
         }
     }
+
+    /// Events of the clip, sent as [`AnimationEvent`]s when the playback crosses their timestamp.
+    #[inline]
+    pub fn events(&self) -> &[AnimationClipEvent] {
+        &self.events
+    }
+
+    /// Add a named event at `time`, in seconds.
+    pub fn add_event(&mut self, time: f32, name: impl Into<String>) {
+        // Update the duration of the animation by this event time if it's later
+        self.duration = self.duration.max(time);
+        self.events.push(AnimationClipEvent {
+            time,
+            name: name.into(),
+        });
+    }
+}
+
+/// Event sent when the playback of an [`AnimationClip`] by an [`AnimationPlayer`] crosses the
+/// timestamp of one of its [`AnimationClipEvent`]s.
+///
+/// Events are sent for every animation of the player with a non-zero weight, including
+/// blended animations and animations being faded out by a transition.
+#[derive(Clone, Debug)]
+pub struct AnimationEvent {
+    /// The entity with the [`AnimationPlayer`] playing the clip.
+    pub entity: Entity,
+    /// The clip the event belongs to.
+    pub animation_clip: Handle<AnimationClip>,
+    /// Name of the event.
+    pub name: String,
 }
 
 #[derive(Reflect)]
@@ -120,8 +296,20 @@ struct PlayingAnimation {
     repeat: bool,
     speed: f32,
     elapsed: f32,
+    /// The weight of the animation relative to the other animations playing at the same time.
+    weight: f32,
+    /// The weight `weight` is moving towards.
+    target_weight: f32,
+    /// How much `weight` moves towards `target_weight` per second
+    weight_change_per_sec: f32,
     animation_clip: Handle<AnimationClip>,
     path_cache: Vec<Vec<Option<Entity>>>,
+    property_path_cache: Vec<Vec<Option<Entity>>>,
+    /// The bones the animation is restricted to, or `None` to animate every bone.
+    mask: Option<AnimationMask>,
+    /// Whether the difference between the animation and its first keyframes is added to the
+    /// transforms, instead of the transforms being interpolated towards the animation.
+    additive: bool,
 }
 
 impl Default for PlayingAnimation {
@@ -130,8 +318,39 @@ impl Default for PlayingAnimation {
             repeat: false,
             speed: 1.0,
             elapsed: 0.0,
+            weight: 1.0,
+            target_weight: 1.0,
+            weight_change_per_sec: 0.0,
             animation_clip: Default::default(),
             path_cache: Vec::new(),
+            property_path_cache: Vec::new(),
+            mask: None,
+            additive: false,
+        }
+    }
+}
+
+impl PlayingAnimation {
+    /// Set the weight of the animation, linearly moving to it over `transition_duration`.
+    fn set_weight(&mut self, weight: f32, transition_duration: Duration) {
+        self.target_weight = weight;
+        if transition_duration.is_zero() {
+            self.weight = weight;
+        } else {
+            self.weight_change_per_sec =
+                (weight - self.weight).abs() / transition_duration.as_secs_f32();
+        }
+    }
+
+    /// Move the weight towards its target
+    fn update_weight(&mut self, time: &Time) {
+        if self.weight != self.target_weight {
+            let step = self.weight_change_per_sec * time.delta_seconds();
+            if (self.target_weight - self.weight).abs() <= step {
+                self.weight = self.target_weight;
+            } else {
+                self.weight += step.copysign(self.target_weight - self.weight);
+            }
         }
     }
 }
@@ -146,6 +365,14 @@ struct AnimationTransition {
     animation: PlayingAnimation,
 }
 
+/// An animation that is played at the same time as the main animation
+struct BlendedAnimation {
+    /// Whether the animation is removed once its weight reaches 0
+    stopping: bool,
+    /// The animation that is being blended
+    animation: PlayingAnimation,
+}
+
 /// Animation controls
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
@@ -161,6 +388,23 @@ pub struct AnimationPlayer {
     // Once a transition is finished, it will be automatically removed from the list
     #[reflect(ignore)]
     transitions: Vec<AnimationTransition>,
+
+    // List of animations played at the same time as the main animation, blended with it by weight.
+    // Animations being stopped with a transition are removed once their weight reaches 0.
+    #[reflect(ignore)]
+    blended: Vec<BlendedAnimation>,
+
+    // List of additive animations, applied on top of all the other animations.
+    #[reflect(ignore)]
+    additive_layers: Vec<PlayingAnimation>,
+
+    // Events crossed by the playback during this frame, sent by `send_animation_events`.
+    #[reflect(ignore)]
+    events: Vec<(Handle<AnimationClip>, String)>,
+
+    // Properties sampled during this frame, applied by `animate_properties`.
+    #[reflect(ignore)]
+    properties: Vec<AnimatedProperty>,
 }
 
 impl AnimationPlayer {
@@ -173,8 +417,9 @@ impl AnimationPlayer {
         };
 
         // We want a hard transition.
-        // In case any previous transitions are still playing, stop them
+        // In case any previous transitions or blended animations are still playing, stop them
         self.transitions.clear();
+        self.blended.clear();
 
         self
     }
@@ -191,6 +436,10 @@ impl AnimationPlayer {
             ..Default::default()
         };
         std::mem::swap(&mut animation, &mut self.animation);
+        for blended in &mut self.blended {
+            blended.stopping = true;
+            blended.animation.set_weight(0.0, transition_duration);
+        }
 
         // Add the current transition. If other transitions are still ongoing,
         // this will keep those transitions running and cause a transition between
@@ -227,15 +476,213 @@ impl AnimationPlayer {
         self
     }
 
-    /// Set the animation to repeat
+    /// Play an animation at the same time as the main animation, blending them by their weight.
+    ///
+    /// Weights are relative: a blended animation with a weight of 1.0 has as much influence as
+    /// the main animation with its default weight of 1.0. The blended animation starts in sync
+    /// with the main animation, with its repeat mode and speed. If the animation is already
+    /// blended, only its weight is updated.
+    pub fn blend(&mut self, handle: Handle<AnimationClip>, weight: f32) -> &mut Self {
+        self.blend_with_transition(handle, weight, Duration::ZERO)
+    }
+
+    /// Play an animation at the same time as the main animation, see [`Self::blend`].
+    ///
+    /// The weight of the animation linearly moves to `weight` over `transition_duration`,
+    /// starting from 0 if the animation wasn't blended yet.
+    pub fn blend_with_transition(
+        &mut self,
+        handle: Handle<AnimationClip>,
+        weight: f32,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        let index = match self
+            .blended
+            .iter()
+            .position(|blended| blended.animation.animation_clip == handle)
+        {
+            Some(index) => index,
+            None => {
+                self.blended.push(BlendedAnimation {
+                    stopping: false,
+                    animation: PlayingAnimation {
+                        repeat: self.animation.repeat,
+                        speed: self.animation.speed,
+                        elapsed: self.animation.elapsed,
+                        weight: 0.0,
+                        animation_clip: handle,
+                        ..Default::default()
+                    },
+                });
+                self.blended.len() - 1
+            }
+        };
+        let blended = &mut self.blended[index];
+        blended.stopping = false;
+        blended.animation.set_weight(weight, transition_duration);
+        self
+    }
+
+    /// Stop playing a blended animation.
+    pub fn stop_blending(&mut self, handle: &Handle<AnimationClip>) -> &mut Self {
+        self.blended
+            .retain(|blended| &blended.animation.animation_clip != handle);
+        self
+    }
+
+    /// Stop playing a blended animation, linearly fading out its weight over `transition_duration`.
+    pub fn stop_blending_with_transition(
+        &mut self,
+        handle: &Handle<AnimationClip>,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        for blended in &mut self.blended {
+            if &blended.animation.animation_clip == handle {
+                blended.stopping = true;
+                blended.animation.set_weight(0.0, transition_duration);
+            }
+        }
+        self
+    }
+
+    /// Weight of a blended animation, or `None` if the animation isn't blended.
+    pub fn blend_weight(&self, handle: &Handle<AnimationClip>) -> Option<f32> {
+        self.blended
+            .iter()
+            .find(|blended| &blended.animation.animation_clip == handle)
+            .map(|blended| blended.animation.weight)
+    }
+
+    /// Restrict a blended animation to the bones of `mask`, or animate every bone with `None`.
+    pub fn set_blend_mask(
+        &mut self,
+        handle: &Handle<AnimationClip>,
+        mask: Option<AnimationMask>,
+    ) -> &mut Self {
+        if let Some(blended) = self
+            .blended
+            .iter_mut()
+            .find(|blended| &blended.animation.animation_clip == handle)
+        {
+            blended.animation.mask = mask;
+        }
+        self
+    }
+
+    /// The bones the main animation is restricted to, if any
+    pub fn mask(&self) -> Option<&AnimationMask> {
+        self.animation.mask.as_ref()
+    }
+
+    /// Restrict the main animation to the bones of `mask`, or animate every bone with `None`
+    ///
+    /// The mask is cleared when another animation is started
+    pub fn set_mask(&mut self, mask: Option<AnimationMask>) -> &mut Self {
+        self.animation.mask = mask;
+        self
+    }
+
+    /// Weight of the main animation, relative to the blended animations
+    pub fn weight(&self) -> f32 {
+        self.animation.weight
+    }
+
+    /// Set the weight of the main animation, relative to the blended animations
+    pub fn set_weight(&mut self, weight: f32) -> &mut Self {
+        self.animation.set_weight(weight, Duration::ZERO);
+        self
+    }
+
+    /// Set the weight of the main animation, linearly moving to it over `transition_duration`
+    pub fn set_weight_with_transition(
+        &mut self,
+        weight: f32,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        self.animation.set_weight(weight, transition_duration);
+        self
+    }
+
+    /// Play an additive animation on top of the other animations, restarting it if it's already
+    /// playing.
+    ///
+    /// An additive animation adds the difference between its current pose and its reference pose,
+    /// made of the first keyframe of each of its curves, to the result of the other animations,
+    /// scaled by `weight`. This layers poses such as recoil, breathing or leaning over any base
+    /// animation. Additive animations don't repeat unless [`Self::repeat_additive`] is used, and
+    /// are not affected by [`Self::repeat`] and [`Self::set_speed`].
+    pub fn play_additive(&mut self, handle: Handle<AnimationClip>, weight: f32) -> &mut Self {
+        self.stop_additive(&handle);
+        self.additive_layers.push(PlayingAnimation {
+            weight,
+            target_weight: weight,
+            additive: true,
+            animation_clip: handle,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Set an additive animation to repeat
+    pub fn repeat_additive(&mut self, handle: &Handle<AnimationClip>) -> &mut Self {
+        if let Some(layer) = self.additive_layer_mut(handle) {
+            layer.repeat = true;
+        }
+        self
+    }
+
+    /// Stop playing an additive animation
+    pub fn stop_additive(&mut self, handle: &Handle<AnimationClip>) -> &mut Self {
+        self.additive_layers
+            .retain(|layer| &layer.animation_clip != handle);
+        self
+    }
+
+    /// Weight of an additive animation, or `None` if the animation isn't playing
+    pub fn additive_weight(&self, handle: &Handle<AnimationClip>) -> Option<f32> {
+        self.additive_layers
+            .iter()
+            .find(|layer| &layer.animation_clip == handle)
+            .map(|layer| layer.weight)
+    }
+
+    /// Set the weight of an additive animation, linearly moving to it over `transition_duration`
+    pub fn set_additive_weight(
+        &mut self,
+        handle: &Handle<AnimationClip>,
+        weight: f32,
+        transition_duration: Duration,
+    ) -> &mut Self {
+        if let Some(layer) = self.additive_layer_mut(handle) {
+            layer.set_weight(weight, transition_duration);
+        }
+        self
+    }
+
+    fn additive_layer_mut(
+        &mut self,
+        handle: &Handle<AnimationClip>,
+    ) -> Option<&mut PlayingAnimation> {
+        self.additive_layers
+            .iter_mut()
+            .find(|layer| &layer.animation_clip == handle)
+    }
+
+    /// Set the animation and the blended animations to repeat
     pub fn repeat(&mut self) -> &mut Self {
         self.animation.repeat = true;
+        for blended in &mut self.blended {
+            blended.animation.repeat = true;
+        }
         self
     }
 
-    /// Stop the animation from repeating
+    /// Stop the animation and the blended animations from repeating
     pub fn stop_repeating(&mut self) -> &mut Self {
         self.animation.repeat = false;
+        for blended in &mut self.blended {
+            blended.animation.repeat = false;
+        }
         self
     }
 
@@ -259,9 +706,12 @@ impl AnimationPlayer {
         self.animation.speed
     }
 
-    /// Set the speed of the animation playback
+    /// Set the speed of the playback of the animation and the blended animations
     pub fn set_speed(&mut self, speed: f32) -> &mut Self {
         self.animation.speed = speed;
+        for blended in &mut self.blended {
+            blended.animation.speed = speed;
+        }
         self
     }
 
@@ -387,20 +837,38 @@ fn run_animation_player(
         return;
     }
 
-    // Apply the main animation
-    apply_animation(
-        1.0,
-        &mut player.animation,
-        paused,
-        root,
-        time,
-        animations,
-        names,
-        transforms,
-        maybe_parent,
-        parents,
-        children,
-    );
+    // Apply the main animation and the blended animations. Each animation is interpolated with
+    // the result of the previous ones by its share of the total weight so far, which results in
+    // the weighted average of the animations.
+    let player = &mut *player;
+    let mut total_weight = 0.0;
+    let blended = player
+        .blended
+        .iter_mut()
+        .map(|blended| &mut blended.animation);
+    for animation in std::iter::once(&mut player.animation).chain(blended) {
+        let weight = animation.weight.max(0.0);
+        total_weight += weight;
+        apply_animation(
+            if total_weight > 0.0 {
+                weight / total_weight
+            } else {
+                0.0
+            },
+            animation,
+            paused,
+            &mut player.events,
+            &mut player.properties,
+            root,
+            time,
+            animations,
+            names,
+            transforms,
+            maybe_parent,
+            parents,
+            children,
+        );
+    }
 
     // Apply any potential fade-out transitions from previous animations
     for AnimationTransition {
@@ -413,6 +881,27 @@ fn run_animation_player(
             *current_weight,
             animation,
             paused,
+            &mut player.events,
+            &mut player.properties,
+            root,
+            time,
+            animations,
+            names,
+            transforms,
+            maybe_parent,
+            parents,
+            children,
+        );
+    }
+
+    // Add the additive animations on top of the result
+    for animation in &mut player.additive_layers {
+        apply_animation(
+            animation.weight,
+            animation,
+            paused,
+            &mut player.events,
+            &mut player.properties,
             root,
             time,
             animations,
@@ -430,6 +919,8 @@ fn apply_animation(
     weight: f32,
     animation: &mut PlayingAnimation,
     paused: bool,
+    events: &mut Vec<(Handle<AnimationClip>, String)>,
+    properties: &mut Vec<AnimatedProperty>,
     root: Entity,
     time: &Time,
     animations: &Assets<AnimationClip>,
@@ -441,7 +932,21 @@ fn apply_animation(
 ) {
     if let Some(animation_clip) = animations.get(&animation.animation_clip) {
         if !paused {
+            let previous_elapsed = animation.elapsed;
             animation.elapsed += time.delta_seconds() * animation.speed;
+            if weight > 0.0 {
+                for event in &animation_clip.events {
+                    if is_event_crossed(
+                        event.time,
+                        previous_elapsed,
+                        animation.elapsed,
+                        animation_clip.duration,
+                        animation.repeat,
+                    ) {
+                        events.push((animation.animation_clip.clone_weak(), event.name.clone()));
+                    }
+                }
+            }
         }
         let mut elapsed = animation.elapsed;
         if animation.repeat {
@@ -453,12 +958,20 @@ fn apply_animation(
         if animation.path_cache.len() != animation_clip.paths.len() {
             animation.path_cache = vec![Vec::new(); animation_clip.paths.len()];
         }
+        if animation.property_path_cache.len() != animation_clip.property_paths.len() {
+            animation.property_path_cache = vec![Vec::new(); animation_clip.property_paths.len()];
+        }
         if !verify_no_ancestor_player(maybe_parent, parents) {
             warn!("Animation player on {:?} has a conflicting animation player on an ancestor. Cannot safely animate.", root);
             return;
         }
 
         for (path, bone_id) in &animation_clip.paths {
+            if let Some(mask) = &animation.mask {
+                if !mask.contains(path) {
+                    continue;
+                }
+            }
             let cached_path = &mut animation.path_cache[*bone_id];
             let curves = animation_clip.get_curves(*bone_id).unwrap();
             let Some(target) = find_bone(root, path, children, names, cached_path) else { continue };
@@ -476,75 +989,163 @@ fn apply_animation(
             // and do nothing.
             let Ok(mut transform) = (unsafe { transforms.get_unchecked(target) }) else { continue };
             for curve in curves {
-                // Some curves have only one keyframe used to set a transform
+                // Some curves have only one keyframe used to set a transform. It is the reference
+                // pose of additive animations, so they don't change the transform.
                 if curve.keyframe_timestamps.len() == 1 {
-                    match &curve.keyframes {
-                        Keyframes::Rotation(keyframes) => {
-                            transform.rotation = transform.rotation.slerp(keyframes[0], weight);
-                        }
-                        Keyframes::Translation(keyframes) => {
-                            transform.translation =
-                                transform.translation.lerp(keyframes[0], weight);
-                        }
-                        Keyframes::Scale(keyframes) => {
-                            transform.scale = transform.scale.lerp(keyframes[0], weight);
-                        }
+                    if animation.additive {
+                        continue;
+                    }
+                    if let Some(keyframe) = curve.keyframes.get(0) {
+                        keyframe.apply(&mut transform, weight);
                     }
                     continue;
                 }
 
-                // Find the current keyframe
-                // PERF: finding the current keyframe can be optimised
-                let step_start = match curve
-                    .keyframe_timestamps
-                    .binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap())
-                {
-                    Ok(n) if n >= curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                    Ok(i) => i,
-                    Err(0) => continue, // this curve isn't started yet
-                    Err(n) if n > curve.keyframe_timestamps.len() - 1 => continue, // this curve is finished
-                    Err(i) => i - 1,
+                let Some((step_start, lerp)) = keyframe_step(&curve.keyframe_timestamps, elapsed)
+                else {
+                    continue;
                 };
-                let ts_start = curve.keyframe_timestamps[step_start];
-                let ts_end = curve.keyframe_timestamps[step_start + 1];
-                let lerp = (elapsed - ts_start) / (ts_end - ts_start);
 
                 // Apply the keyframe
-                match &curve.keyframes {
-                    Keyframes::Rotation(keyframes) => {
-                        let rot_start = keyframes[step_start];
-                        let mut rot_end = keyframes[step_start + 1];
-                        // Choose the smallest angle for the rotation
-                        if rot_end.dot(rot_start) < 0.0 {
-                            rot_end = -rot_end;
-                        }
-                        // Rotations are using a spherical linear interpolation
-                        let rot = rot_start.normalize().slerp(rot_end.normalize(), lerp);
-                        transform.rotation = transform.rotation.slerp(rot, weight);
-                    }
-                    Keyframes::Translation(keyframes) => {
-                        let translation_start = keyframes[step_start];
-                        let translation_end = keyframes[step_start + 1];
-                        let result = translation_start.lerp(translation_end, lerp);
-                        transform.translation = transform.translation.lerp(result, weight);
-                    }
-                    Keyframes::Scale(keyframes) => {
-                        let scale_start = keyframes[step_start];
-                        let scale_end = keyframes[step_start + 1];
-                        let result = scale_start.lerp(scale_end, lerp);
-                        transform.scale = transform.scale.lerp(result, weight);
-                    }
+                let (Some(start), Some(end)) = (
+                    curve.keyframes.get(step_start),
+                    curve.keyframes.get(step_start + 1),
+                ) else {
+                    continue;
+                };
+                let keyframe = start.interpolate(end, lerp);
+                if animation.additive {
+                    let Some(reference) = curve.keyframes.get(0) else { continue };
+                    keyframe.apply_additive(reference, &mut transform, weight);
+                } else {
+                    keyframe.apply(&mut transform, weight);
+                }
+            }
+        }
+
+        // Sample the property curves, they are applied to the reflected components later by
+        // `animate_properties`
+        if weight <= 0.0 {
+            return;
+        }
+        for (path, id) in &animation_clip.property_paths {
+            if let Some(mask) = &animation.mask {
+                if !mask.contains(path) {
+                    continue;
+                }
+            }
+            let cached_path = &mut animation.property_path_cache[*id];
+            let Some(target) = find_bone(root, path, children, names, cached_path) else { continue };
+            for curve in &animation_clip.property_curves[*id] {
+                // Curves with only one keyframe are the reference of additive animations
+                if animation.additive && curve.keyframe_timestamps.len() == 1 {
+                    continue;
                 }
+                let Some(value) = curve.sample(elapsed) else { continue };
+                let reference = if animation.additive {
+                    let Some(reference) = curve.keyframes.get(0) else { continue };
+                    Some(reference)
+                } else {
+                    None
+                };
+                properties.push(AnimatedProperty {
+                    entity: target,
+                    component: curve.component.clone(),
+                    field_path: curve.field_path.clone(),
+                    value,
+                    reference,
+                    weight,
+                });
             }
         }
     }
 }
 
+/// Find the keyframe `elapsed` is after in `keyframe_timestamps`, and how far it is towards the
+/// next keyframe, from 0 to 1.
+///
+/// Returns `None` if the curve isn't started yet or is finished.
+fn keyframe_step(keyframe_timestamps: &[f32], elapsed: f32) -> Option<(usize, f32)> {
+    // PERF: finding the current keyframe can be optimised
+    let step_start =
+        match keyframe_timestamps.binary_search_by(|probe| probe.partial_cmp(&elapsed).unwrap()) {
+            Ok(n) if n >= keyframe_timestamps.len() - 1 => return None, // this curve is finished
+            Ok(i) => i,
+            Err(0) => return None, // this curve isn't started yet
+            Err(n) if n > keyframe_timestamps.len() - 1 => return None, // this curve is finished
+            Err(i) => i - 1,
+        };
+    let ts_start = keyframe_timestamps[step_start];
+    let ts_end = keyframe_timestamps[step_start + 1];
+    Some((step_start, (elapsed - ts_start) / (ts_end - ts_start)))
+}
+
+/// Whether the playback from `previous_elapsed` to `elapsed` crosses an event at `time`.
+///
+/// The playback covers `[previous_elapsed, elapsed)` when playing forward and
+/// `(elapsed, previous_elapsed]` when playing backward, repeated over each loop of a clip of
+/// `duration` seconds if `repeat` is set.
+fn is_event_crossed(
+    time: f32,
+    previous_elapsed: f32,
+    elapsed: f32,
+    duration: f32,
+    repeat: bool,
+) -> bool {
+    let repeat = repeat && duration > 0.0;
+    if elapsed >= previous_elapsed {
+        // The first occurrence of the event at or after the previous time
+        let time = if repeat {
+            time + ((previous_elapsed - time) / duration).ceil() * duration
+        } else {
+            time
+        };
+        previous_elapsed <= time && time < elapsed
+    } else {
+        // The last occurrence of the event at or before the previous time
+        let time = if repeat {
+            time + ((previous_elapsed - time) / duration).floor() * duration
+        } else {
+            time
+        };
+        elapsed < time && time <= previous_elapsed
+    }
+}
+
+/// System that sends the [`AnimationEvent`]s crossed by the playback of the [`AnimationPlayer`]s
+pub fn send_animation_events(
+    mut animation_players: Query<(Entity, &mut AnimationPlayer)>,
+    mut animation_events: EventWriter<AnimationEvent>,
+) {
+    for (entity, mut player) in &mut animation_players {
+        if player.events.is_empty() {
+            continue;
+        }
+        // Don't mark the player as changed, that would resume the animation when paused
+        let player = player.bypass_change_detection();
+        animation_events.send_batch(player.events.drain(..).map(|(animation_clip, name)| {
+            AnimationEvent {
+                entity,
+                animation_clip,
+                name,
+            }
+        }));
+    }
+}
+
 fn update_transitions(player: &mut AnimationPlayer, time: &Time) {
     player.transitions.retain_mut(|animation| {
         animation.current_weight -= animation.weight_decline_per_sec * time.delta_seconds();
         animation.current_weight > 0.0
     });
+    player.animation.update_weight(time);
+    for layer in &mut player.additive_layers {
+        layer.update_weight(time);
+    }
+    player.blended.retain_mut(|blended| {
+        blended.animation.update_weight(time);
+        !blended.stopping || blended.animation.weight > 0.0
+    });
 }
 
 /// Adds animation support to an app
@@ -555,10 +1156,59 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<AnimationClip>()
             .register_asset_reflect::<AnimationClip>()
+            .add_asset::<AnimationGraph>()
+            .add_asset::<AnimationRetargeting>()
+            .add_event::<AnimationEvent>()
             .register_type::<AnimationPlayer>()
+            .register_type::<AnimationGraphPlayer>()
+            .register_type::<MorphWeights>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animation_graph_player.before(animation_player),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 animation_player.before(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animate_properties
+                    .after(animation_player)
+                    .before(inverse_kinematics),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                inverse_kinematics
+                    .after(animation_player)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                send_animation_events.after(animation_player),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_event_crossed;
+
+    #[test]
+    fn crossed_events() {
+        // forward
+        assert!(is_event_crossed(0.0, 0.0, 0.1, 1.0, false));
+        assert!(is_event_crossed(0.5, 0.4, 0.6, 1.0, false));
+        assert!(!is_event_crossed(0.5, 0.5 - 1.0, 0.4, 1.0, false));
+        assert!(!is_event_crossed(0.5, 0.6, 0.7, 1.0, false));
+        // forward, repeating
+        assert!(is_event_crossed(0.5, 2.4, 2.6, 1.0, true));
+        assert!(is_event_crossed(0.1, 0.9, 1.2, 1.0, true));
+        assert!(!is_event_crossed(0.1, 0.2, 0.9, 1.0, false));
+        assert!(!is_event_crossed(0.5, 1.6, 1.9, 1.0, true));
+        // backward
+        assert!(is_event_crossed(0.5, 0.6, 0.4, 1.0, false));
+        assert!(!is_event_crossed(0.5, 0.4, 0.3, 1.0, false));
+        assert!(is_event_crossed(0.9, 1.1, 0.8, 1.0, true));
+        assert!(is_event_crossed(0.9, 0.1, -0.2, 1.0, true));
+    }
+}
@@ -0,0 +1,73 @@
+use bevy_reflect::{FromReflect, Reflect};
+
+use crate::EntityPath;
+
+/// Restricts an animation to a subset of the bones it animates, such as the upper body of a
+/// character.
+///
+/// Bones are included or excluded with their descendants, by their [`EntityPath`]. When several
+/// paths of the mask apply to a bone, the longest one wins, so a subtree can be excluded from an
+/// included subtree. Bones that no path applies to are excluded.
+#[derive(Reflect, FromReflect, Clone, Debug, Default, PartialEq)]
+pub struct AnimationMask {
+    paths: Vec<(EntityPath, bool)>,
+}
+
+impl AnimationMask {
+    /// Create a mask that excludes every bone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include the bone at `path` and its descendants.
+    pub fn include(mut self, path: EntityPath) -> Self {
+        self.set(path, true);
+        self
+    }
+
+    /// Exclude the bone at `path` and its descendants.
+    pub fn exclude(mut self, path: EntityPath) -> Self {
+        self.set(path, false);
+        self
+    }
+
+    fn set(&mut self, path: EntityPath, included: bool) {
+        self.paths.retain(|(other, _)| other != &path);
+        self.paths.push((path, included));
+    }
+
+    /// Returns `true` if the bone at `path` is animated.
+    pub fn contains(&self, path: &EntityPath) -> bool {
+        self.paths
+            .iter()
+            .filter(|(mask_path, _)| path.parts.starts_with(&mask_path.parts))
+            .max_by_key(|(mask_path, _)| mask_path.parts.len())
+            .is_some_and(|(_, included)| *included)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AnimationMask;
+    use crate::EntityPath;
+    use bevy_core::Name;
+
+    fn path(parts: &[&'static str]) -> EntityPath {
+        EntityPath {
+            parts: parts.iter().map(|part| Name::new(*part)).collect(),
+        }
+    }
+
+    #[test]
+    fn mask_subtrees() {
+        let mask = AnimationMask::new()
+            .include(path(&["root", "spine"]))
+            .exclude(path(&["root", "spine", "neck"]));
+
+        assert!(!mask.contains(&path(&["root"])));
+        assert!(!mask.contains(&path(&["root", "leg"])));
+        assert!(mask.contains(&path(&["root", "spine"])));
+        assert!(mask.contains(&path(&["root", "spine", "arm"])));
+        assert!(!mask.contains(&path(&["root", "spine", "neck", "head"])));
+    }
+}
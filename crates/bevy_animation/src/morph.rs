@@ -0,0 +1,70 @@
+use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_reflect::Reflect;
+
+use crate::{PropertyCurve, PropertyKeyframes};
+
+/// The weights of the morph targets of a mesh, animated by the weight channels of glTF
+/// animations.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct MorphWeights {
+    /// The weight of each morph target.
+    pub weights: Vec<f32>,
+}
+
+impl MorphWeights {
+    /// Create the weights of the morph targets of a mesh.
+    pub fn new(weights: Vec<f32>) -> Self {
+        Self { weights }
+    }
+
+    /// Create a [`PropertyCurve`] for each morph target from interleaved keyframes, with the
+    /// weights of all the targets for the first timestamp, then for the second timestamp...
+    ///
+    /// This is the layout of the weight channels of glTF animations.
+    pub fn curves(keyframe_timestamps: &[f32], keyframes: &[f32]) -> Vec<PropertyCurve> {
+        if keyframe_timestamps.is_empty() {
+            return Vec::new();
+        }
+        let target_count = keyframes.len() / keyframe_timestamps.len();
+        (0..target_count)
+            .map(|target| {
+                PropertyCurve::new::<MorphWeights>(
+                    format!("weights[{target}]"),
+                    keyframe_timestamps.to_vec(),
+                    PropertyKeyframes::F32(
+                        keyframes
+                            .iter()
+                            .skip(target)
+                            .step_by(target_count)
+                            .copied()
+                            .collect(),
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PropertyValue;
+    use bevy_reflect::GetPath;
+
+    #[test]
+    fn morph_weight_curves() {
+        let curves = MorphWeights::curves(&[0.0, 1.0], &[0.0, 1.0, 0.5, 0.0]);
+        assert_eq!(2, curves.len());
+        assert_eq!(Some(PropertyValue::F32(0.25)), curves[0].sample(0.5));
+        assert_eq!(Some(PropertyValue::F32(0.5)), curves[1].sample(0.5));
+
+        let mut morph_weights = MorphWeights::new(vec![0.0, 0.0]);
+        *morph_weights
+            .path_mut(&curves[1].field_path)
+            .unwrap()
+            .downcast_mut::<f32>()
+            .unwrap() = 1.0;
+        assert_eq!(vec![0.0, 1.0], morph_weights.weights);
+    }
+}
@@ -0,0 +1,367 @@
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    change_detection::DetectChangesMut, entity::Entity, prelude::Component,
+    reflect::ReflectComponent, world::World,
+};
+use bevy_math::{Quat, Vec2, Vec3, Vec4};
+use bevy_reflect::{FromReflect, GetPath, Reflect};
+use bevy_utils::tracing::warn;
+
+use crate::{keyframe_step, AnimationClip, AnimationPlayer, EntityPath};
+
+/// List of keyframes for a reflected property of a component.
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub enum PropertyKeyframes {
+    /// Keyframes for a `f32` property.
+    F32(Vec<f32>),
+    /// Keyframes for a [`Vec2`] property.
+    Vec2(Vec<Vec2>),
+    /// Keyframes for a [`Vec3`] property.
+    Vec3(Vec<Vec3>),
+    /// Keyframes for a [`Vec4`] property.
+    Vec4(Vec<Vec4>),
+    /// Keyframes for a [`Quat`] property.
+    Quat(Vec<Quat>),
+}
+
+impl PropertyKeyframes {
+    /// Number of keyframes.
+    pub fn len(&self) -> usize {
+        match self {
+            PropertyKeyframes::F32(keyframes) => keyframes.len(),
+            PropertyKeyframes::Vec2(keyframes) => keyframes.len(),
+            PropertyKeyframes::Vec3(keyframes) => keyframes.len(),
+            PropertyKeyframes::Vec4(keyframes) => keyframes.len(),
+            PropertyKeyframes::Quat(keyframes) => keyframes.len(),
+        }
+    }
+
+    /// Returns `true` if there are no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a keyframe.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<PropertyValue> {
+        match self {
+            PropertyKeyframes::F32(keyframes) => {
+                keyframes.get(index).copied().map(PropertyValue::F32)
+            }
+            PropertyKeyframes::Vec2(keyframes) => {
+                keyframes.get(index).copied().map(PropertyValue::Vec2)
+            }
+            PropertyKeyframes::Vec3(keyframes) => {
+                keyframes.get(index).copied().map(PropertyValue::Vec3)
+            }
+            PropertyKeyframes::Vec4(keyframes) => {
+                keyframes.get(index).copied().map(PropertyValue::Vec4)
+            }
+            PropertyKeyframes::Quat(keyframes) => {
+                keyframes.get(index).copied().map(PropertyValue::Quat)
+            }
+        }
+    }
+}
+
+/// A value of an animated property.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// A `f32` value.
+    F32(f32),
+    /// A [`Vec2`] value.
+    Vec2(Vec2),
+    /// A [`Vec3`] value.
+    Vec3(Vec3),
+    /// A [`Vec4`] value.
+    Vec4(Vec4),
+    /// A [`Quat`] value.
+    Quat(Quat),
+}
+
+impl PropertyValue {
+    /// Interpolate between this value and the next keyframe of the same property.
+    pub fn interpolate(self, end: PropertyValue, t: f32) -> PropertyValue {
+        match (self, end) {
+            (PropertyValue::F32(start), PropertyValue::F32(end)) => {
+                PropertyValue::F32(start + (end - start) * t)
+            }
+            (PropertyValue::Vec2(start), PropertyValue::Vec2(end)) => {
+                PropertyValue::Vec2(start.lerp(end, t))
+            }
+            (PropertyValue::Vec3(start), PropertyValue::Vec3(end)) => {
+                PropertyValue::Vec3(start.lerp(end, t))
+            }
+            (PropertyValue::Vec4(start), PropertyValue::Vec4(end)) => {
+                PropertyValue::Vec4(start.lerp(end, t))
+            }
+            (PropertyValue::Quat(start), PropertyValue::Quat(mut end)) => {
+                // Choose the smallest angle for the rotation
+                if end.dot(start) < 0.0 {
+                    end = -end;
+                }
+                PropertyValue::Quat(start.normalize().slerp(end.normalize(), t))
+            }
+            (start, _) => start,
+        }
+    }
+
+    /// Interpolate the reflected `field` towards the value by `weight`.
+    ///
+    /// Returns `false` if the field doesn't have the type of the value.
+    fn apply(self, field: &mut dyn Reflect, weight: f32) -> bool {
+        match self {
+            PropertyValue::F32(value) => {
+                update_field(field, |field: &mut f32| *field += (value - *field) * weight)
+            }
+            PropertyValue::Vec2(value) => {
+                update_field(field, |field: &mut Vec2| *field = field.lerp(value, weight))
+            }
+            PropertyValue::Vec3(value) => {
+                update_field(field, |field: &mut Vec3| *field = field.lerp(value, weight))
+            }
+            PropertyValue::Vec4(value) => {
+                update_field(field, |field: &mut Vec4| *field = field.lerp(value, weight))
+            }
+            PropertyValue::Quat(value) => update_field(field, |field: &mut Quat| {
+                *field = field.slerp(value, weight)
+            }),
+        }
+    }
+
+    /// Add the difference between the value and the `reference` value, scaled by `weight`, to
+    /// the reflected `field`.
+    ///
+    /// Returns `false` if the field doesn't have the type of the value.
+    fn apply_additive(
+        self,
+        reference: PropertyValue,
+        field: &mut dyn Reflect,
+        weight: f32,
+    ) -> bool {
+        match (self, reference) {
+            (PropertyValue::F32(value), PropertyValue::F32(reference)) => {
+                update_field(field, |field: &mut f32| {
+                    *field += (value - reference) * weight
+                })
+            }
+            (PropertyValue::Vec2(value), PropertyValue::Vec2(reference)) => {
+                update_field(field, |field: &mut Vec2| {
+                    *field += (value - reference) * weight
+                })
+            }
+            (PropertyValue::Vec3(value), PropertyValue::Vec3(reference)) => {
+                update_field(field, |field: &mut Vec3| {
+                    *field += (value - reference) * weight
+                })
+            }
+            (PropertyValue::Vec4(value), PropertyValue::Vec4(reference)) => {
+                update_field(field, |field: &mut Vec4| {
+                    *field += (value - reference) * weight
+                })
+            }
+            (PropertyValue::Quat(value), PropertyValue::Quat(reference)) => {
+                let mut difference = reference.normalize().inverse() * value;
+                // Choose the smallest angle for the difference
+                if difference.w < 0.0 {
+                    difference = -difference;
+                }
+                update_field(field, |field: &mut Quat| {
+                    *field *= Quat::IDENTITY.slerp(difference, weight);
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+fn update_field<T: Reflect>(field: &mut dyn Reflect, update: impl FnOnce(&mut T)) -> bool {
+    match field.downcast_mut::<T>() {
+        Some(field) => {
+            update(field);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Describes how a reflected property of a component should be animated, such as the
+/// `intensity` of a `PointLight` or the `scale.x` of a [`Transform`](bevy_transform::prelude::Transform).
+///
+/// The component must be registered in the `AppTypeRegistry` with `#[reflect(Component)]`.
+/// `keyframe_timestamps` and `keyframes` should have the same length.
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct PropertyCurve {
+    /// The type name of the animated component, as returned by [`std::any::type_name`].
+    pub component: String,
+    /// The path of the animated field in the component, see [`GetPath`].
+    pub field_path: String,
+    /// Timestamp for each of the keyframes.
+    pub keyframe_timestamps: Vec<f32>,
+    /// List of the keyframes.
+    pub keyframes: PropertyKeyframes,
+}
+
+impl PropertyCurve {
+    /// Create a curve animating the field at `field_path` in the component `C`.
+    pub fn new<C: Component>(
+        field_path: impl Into<String>,
+        keyframe_timestamps: Vec<f32>,
+        keyframes: PropertyKeyframes,
+    ) -> Self {
+        Self {
+            component: std::any::type_name::<C>().to_string(),
+            field_path: field_path.into(),
+            keyframe_timestamps,
+            keyframes,
+        }
+    }
+
+    /// The value of the property at `elapsed` seconds, or `None` if the curve isn't started yet
+    /// or is finished.
+    pub fn sample(&self, elapsed: f32) -> Option<PropertyValue> {
+        if self.keyframe_timestamps.len() == 1 {
+            return self.keyframes.get(0);
+        }
+        let (step_start, lerp) = keyframe_step(&self.keyframe_timestamps, elapsed)?;
+        let start = self.keyframes.get(step_start)?;
+        let end = self.keyframes.get(step_start + 1)?;
+        Some(start.interpolate(end, lerp))
+    }
+}
+
+impl AnimationClip {
+    /// [`PropertyCurve`]s for each entity. Indexed by the entity ID of the property paths.
+    #[inline]
+    pub fn property_curves(&self) -> &Vec<Vec<PropertyCurve>> {
+        &self.property_curves
+    }
+
+    /// Gets the property curves by the [`EntityPath`] of the entity they animate.
+    ///
+    /// Returns `None` if no property of the entity is animated.
+    #[inline]
+    pub fn get_property_curves_by_path(&self, path: &EntityPath) -> Option<&'_ Vec<PropertyCurve>> {
+        self.property_paths
+            .get(path)
+            .and_then(|id| self.property_curves.get(*id))
+    }
+
+    /// Add a [`PropertyCurve`] to an [`EntityPath`].
+    pub fn add_property_curve_to_path(&mut self, path: EntityPath, curve: PropertyCurve) {
+        // Update the duration of the animation by this curve duration if it's longer
+        self.duration = self
+            .duration
+            .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
+        if let Some(id) = self.property_paths.get(&path) {
+            self.property_curves[*id].push(curve);
+        } else {
+            let idx = self.property_curves.len();
+            self.property_curves.push(vec![curve]);
+            self.property_paths.insert(path, idx);
+        }
+    }
+}
+
+/// A property sampled by an [`AnimationPlayer`] during this frame, applied by
+/// [`animate_properties`].
+pub(crate) struct AnimatedProperty {
+    pub(crate) entity: Entity,
+    pub(crate) component: String,
+    pub(crate) field_path: String,
+    pub(crate) value: PropertyValue,
+    /// The reference value of additive animations.
+    pub(crate) reference: Option<PropertyValue>,
+    pub(crate) weight: f32,
+}
+
+/// System that applies the [`PropertyCurve`]s sampled by the [`AnimationPlayer`]s to the
+/// reflected components.
+pub fn animate_properties(world: &mut World) {
+    let mut properties = Vec::new();
+    for mut player in world.query::<&mut AnimationPlayer>().iter_mut(world) {
+        if !player.properties.is_empty() {
+            // Don't mark the player as changed, that would resume the animation when paused
+            properties.append(&mut player.bypass_change_detection().properties);
+        }
+    }
+    if properties.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    for property in properties {
+        let Some(reflect_component) = type_registry
+            .get_with_name(&property.component)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            warn!(
+                "Cannot animate the component {}, it is not registered as a reflected component",
+                property.component
+            );
+            continue;
+        };
+        let Some(mut component) = reflect_component.reflect_mut(world, property.entity) else {
+            continue;
+        };
+        let Ok(field) = (*component).path_mut(&property.field_path) else {
+            warn!(
+                "Cannot animate the field {} of the component {}, it does not exist",
+                property.field_path, property.component
+            );
+            continue;
+        };
+        let applied = match property.reference {
+            Some(reference) => property
+                .value
+                .apply_additive(reference, field, property.weight),
+            None => property.value.apply(field, property.weight),
+        };
+        if !applied {
+            warn!(
+                "Cannot animate the field {} of the component {}, its type is not {:?}",
+                property.field_path, property.component, property.value
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn sample_property_curve() {
+        let curve = PropertyCurve::new::<Transform>(
+            "scale.x",
+            vec![0.0, 1.0, 3.0],
+            PropertyKeyframes::F32(vec![1.0, 2.0, 0.0]),
+        );
+        assert_eq!(
+            "bevy_transform::components::transform::Transform",
+            curve.component
+        );
+        assert_eq!(Some(PropertyValue::F32(1.5)), curve.sample(0.5));
+        assert_eq!(Some(PropertyValue::F32(1.0)), curve.sample(2.0));
+        assert_eq!(None, curve.sample(3.5));
+    }
+
+    #[test]
+    fn apply_property_values() {
+        let mut transform = Transform::default();
+        assert!(PropertyValue::F32(3.0).apply(transform.path_mut("scale.x").unwrap(), 0.5));
+        assert_eq!(2.0, transform.scale.x);
+        assert!(PropertyValue::Vec3(Vec3::X).apply_additive(
+            PropertyValue::Vec3(Vec3::ZERO),
+            transform.path_mut("translation").unwrap(),
+            1.0,
+        ));
+        assert_eq!(Vec3::X, transform.translation);
+        assert!(
+            !PropertyValue::Quat(Quat::IDENTITY).apply(transform.path_mut("scale").unwrap(), 1.0)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use bevy_reflect::TypeUuid;
+use bevy_transform::prelude::Transform;
+use bevy_utils::HashMap;
+
+use crate::{AnimationClip, EntityPath, Keyframes, VariableCurve};
+
+/// The bones of a humanoid skeleton, used to map the bones of two humanoid rigs whose bones are
+/// named differently.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HumanoidBone {
+    Hips,
+    Spine,
+    Chest,
+    UpperChest,
+    Neck,
+    Head,
+    LeftShoulder,
+    LeftUpperArm,
+    LeftLowerArm,
+    LeftHand,
+    RightShoulder,
+    RightUpperArm,
+    RightLowerArm,
+    RightHand,
+    LeftUpperLeg,
+    LeftLowerLeg,
+    LeftFoot,
+    LeftToes,
+    RightUpperLeg,
+    RightLowerLeg,
+    RightFoot,
+    RightToes,
+}
+
+/// The path and rest pose of the [`HumanoidBone`]s of a rig.
+#[derive(Clone, Debug, Default)]
+pub struct HumanoidRig {
+    /// The path of each bone of the rig, and its [`Transform`] in the rest pose.
+    pub bones: HashMap<HumanoidBone, (EntityPath, Transform)>,
+}
+
+impl HumanoidRig {
+    /// Create a rig without bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the path and the rest pose of a bone of the rig.
+    pub fn with_bone(mut self, bone: HumanoidBone, path: EntityPath, rest_pose: Transform) -> Self {
+        self.bones.insert(bone, (path, rest_pose));
+        self
+    }
+}
+
+/// A bone of the rig a clip was authored for, mapped to a bone of another rig.
+#[derive(Clone, Debug)]
+pub struct BoneMapping {
+    /// The path of the bone in the source rig.
+    pub source: EntityPath,
+    /// The path of the bone in the target rig.
+    pub target: EntityPath,
+    /// The [`Transform`] of the bone in the rest pose of the source rig.
+    pub source_rest_pose: Transform,
+    /// The [`Transform`] of the bone in the rest pose of the target rig.
+    pub target_rest_pose: Transform,
+}
+
+/// Maps the bones of a source rig to the bones of a differently-proportioned target rig, to play
+/// the [`AnimationClip`]s authored for the source rig on the target rig.
+///
+/// Each animated bone keeps its difference to the rest pose:
+/// - rotations are applied relative to the rest rotation of the bone, in its local space,
+/// - translations are offset from the rest translation of the bone, and scaled by
+///   `translation_scale` to account for the proportions of the rigs,
+/// - scales are relative to the rest scale of the bone.
+#[derive(Clone, Debug, TypeUuid)]
+#[uuid = "6c1c4b1b-9b8e-4d5e-a8a7-2b4f6a2f0e8d"]
+pub struct AnimationRetargeting {
+    /// The mapped bones. The curves of the bones that aren't mapped are dropped.
+    pub bones: Vec<BoneMapping>,
+    /// The scale of the translations of the source rig on the target rig.
+    pub translation_scale: f32,
+}
+
+impl Default for AnimationRetargeting {
+    fn default() -> Self {
+        Self {
+            bones: Vec::new(),
+            translation_scale: 1.0,
+        }
+    }
+}
+
+impl AnimationRetargeting {
+    /// Create a retargeting without bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a bone of the source rig to a bone of the target rig.
+    pub fn add_bone(&mut self, mapping: BoneMapping) -> &mut Self {
+        self.bones.push(mapping);
+        self
+    }
+
+    /// Map the bones two humanoid rigs have in common.
+    ///
+    /// The translations are scaled by the ratio of the heights of the hips of the rigs in their
+    /// rest pose.
+    pub fn from_humanoid_rigs(source: &HumanoidRig, target: &HumanoidRig) -> Self {
+        let mut retargeting = Self::new();
+        for (bone, (source_path, source_rest_pose)) in &source.bones {
+            if let Some((target_path, target_rest_pose)) = target.bones.get(bone) {
+                retargeting.add_bone(BoneMapping {
+                    source: source_path.clone(),
+                    target: target_path.clone(),
+                    source_rest_pose: *source_rest_pose,
+                    target_rest_pose: *target_rest_pose,
+                });
+            }
+        }
+        if let (Some((_, source_hips)), Some((_, target_hips))) = (
+            source.bones.get(&HumanoidBone::Hips),
+            target.bones.get(&HumanoidBone::Hips),
+        ) {
+            if source_hips.translation.y != 0.0 {
+                retargeting.translation_scale =
+                    target_hips.translation.y / source_hips.translation.y;
+            }
+        }
+        retargeting
+    }
+
+    /// Create a clip animating the target rig like `clip` animates the source rig.
+    ///
+    /// The [`PropertyCurve`](crate::PropertyCurve)s of the mapped bones are copied unchanged.
+    pub fn retarget(&self, clip: &AnimationClip) -> AnimationClip {
+        let mut retargeted = AnimationClip::default();
+        for mapping in &self.bones {
+            let Some(curves) = clip.get_curves_by_path(&mapping.source) else {
+                continue;
+            };
+            for curve in curves {
+                retargeted
+                    .add_curve_to_path(mapping.target.clone(), self.retarget_curve(mapping, curve));
+            }
+        }
+        for mapping in &self.bones {
+            let Some(curves) = clip.get_property_curves_by_path(&mapping.source) else {
+                continue;
+            };
+            for curve in curves {
+                retargeted.add_property_curve_to_path(mapping.target.clone(), curve.clone());
+            }
+        }
+        for event in clip.events() {
+            retargeted.add_event(event.time, event.name.clone());
+        }
+        retargeted
+    }
+
+    fn retarget_curve(&self, mapping: &BoneMapping, curve: &VariableCurve) -> VariableCurve {
+        let source = &mapping.source_rest_pose;
+        let target = &mapping.target_rest_pose;
+        let keyframes = match curve.keyframes.decompressed() {
+            Keyframes::Rotation(keyframes) => {
+                let correction = source.rotation.inverse();
+                Keyframes::Rotation(
+                    keyframes
+                        .into_iter()
+                        .map(|rotation| (target.rotation * (correction * rotation)).normalize())
+                        .collect(),
+                )
+            }
+            Keyframes::Translation(keyframes) => Keyframes::Translation(
+                keyframes
+                    .into_iter()
+                    .map(|translation| {
+                        target.translation
+                            + (translation - source.translation) * self.translation_scale
+                    })
+                    .collect(),
+            ),
+            Keyframes::Scale(keyframes) => Keyframes::Scale(
+                keyframes
+                    .into_iter()
+                    .map(|scale| target.scale * scale / source.scale)
+                    .collect(),
+            ),
+            quantized => quantized,
+        };
+        VariableCurve {
+            keyframe_timestamps: curve.keyframe_timestamps.clone(),
+            keyframes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_core::Name;
+    use bevy_math::{Quat, Vec3};
+
+    fn path(name: &'static str) -> EntityPath {
+        EntityPath {
+            parts: vec![Name::new(name)],
+        }
+    }
+
+    #[test]
+    fn retarget_humanoid_clip() {
+        let source = HumanoidRig::new()
+            .with_bone(
+                HumanoidBone::Hips,
+                path("pelvis"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+            )
+            .with_bone(
+                HumanoidBone::Head,
+                path("head"),
+                Transform::from_rotation(Quat::from_rotation_x(0.5)),
+            );
+        let target = HumanoidRig::new()
+            .with_bone(
+                HumanoidBone::Hips,
+                path("Hips"),
+                Transform::from_xyz(0.0, 2.0, 0.0),
+            )
+            .with_bone(HumanoidBone::Head, path("Head"), Transform::IDENTITY);
+        let retargeting = AnimationRetargeting::from_humanoid_rigs(&source, &target);
+        assert_eq!(2.0, retargeting.translation_scale);
+
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_path(
+            path("pelvis"),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Translation(vec![
+                    Vec3::new(0.0, 1.0, 0.0),
+                    Vec3::new(1.0, 1.0, 0.0),
+                ]),
+            },
+        );
+        clip.add_curve_to_path(
+            path("head"),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0],
+                keyframes: Keyframes::Rotation(vec![Quat::from_rotation_x(0.5)]),
+            },
+        );
+        clip.add_curve_to_path(
+            path("tail"),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0],
+                keyframes: Keyframes::Scale(vec![Vec3::ONE]),
+            },
+        );
+
+        let retargeted = retargeting.retarget(&clip);
+        assert!(retargeted.get_curves_by_path(&path("tail")).is_none());
+        let Keyframes::Translation(hips) =
+            &retargeted.get_curves_by_path(&path("Hips")).unwrap()[0].keyframes
+        else {
+            panic!("expected translation keyframes");
+        };
+        assert_eq!(
+            &[Vec3::new(0.0, 2.0, 0.0), Vec3::new(2.0, 2.0, 0.0)],
+            &hips[..]
+        );
+        let Keyframes::Rotation(head) =
+            &retargeted.get_curves_by_path(&path("Head")).unwrap()[0].keyframes
+        else {
+            panic!("expected rotation keyframes");
+        };
+        assert!(head[0].angle_between(Quat::IDENTITY) < 1e-5);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{CoreStage, Plugin, PluginGroup, StartupSchedule, StartupStage};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    event::{Event, Events},
+    event::{Event, EventSettings, Events},
     prelude::FromWorld,
     schedule::{
         IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State, StateData, SystemSet,
         SystemStage,
     },
+    schedule_v3::{self, OnEnter, OnExit, ScheduleLabel, Schedules, States, SubStates},
     system::Resource,
     world::World,
 };
@@ -652,6 +653,67 @@ impl App {
             .add_system_set_to_stage(stage, State::<T>::get_driver())
     }
 
+    /// Initializes the resource `R` each time the stageless
+    /// [`State<S>`](schedule_v3::State) enters `state`, and removes it when the state is exited,
+    /// so that it only exists in that state.
+    ///
+    /// The resource is created from scratch each time the state is entered.
+    pub fn init_resource_on_enter<S: States, R: Resource + FromWorld>(
+        &mut self,
+        state: S,
+    ) -> &mut Self {
+        self.edit_stageless_schedule(OnEnter(state.clone()), |schedule| {
+            schedule.add_system(schedule_v3::init_state_scoped_resource::<R>);
+        })
+        .edit_stageless_schedule(OnExit(state), |schedule| {
+            schedule.add_system(schedule_v3::remove_state_scoped_resource::<R>);
+        })
+    }
+
+    /// Despawns the entities marked with [`StateScoped(state)`](schedule_v3::StateScoped) when
+    /// the stageless [`State<S>`](schedule_v3::State) exits `state`, for each variant of `S`.
+    pub fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self {
+        for state in S::states() {
+            self.edit_stageless_schedule(OnExit(state.clone()), |schedule| {
+                schedule.add_system(schedule_v3::clear_state_scoped_entities(state));
+            });
+        }
+        self
+    }
+
+    /// Adds [`OnEnter`] and [`OnExit`] schedules for each variant of the stageless
+    /// [`SubStates`] `S` and registers it with its parent state, so that the transitions of the
+    /// parent state enter and exit `S`. The [`State<S>`](schedule_v3::State) and
+    /// [`NextState<S>`](schedule_v3::NextState) resources only exist while the parent state
+    /// matches.
+    ///
+    /// Transitions queued in [`NextState<S>`](schedule_v3::NextState) are applied by
+    /// [`apply_sub_state_transition::<S>`](schedule_v3::apply_sub_state_transition), which should
+    /// run after the transitions of the parent state.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        for state in S::states() {
+            self.edit_stageless_schedule(OnEnter(state.clone()), |_| {})
+                .edit_stageless_schedule(OnExit(state), |_| {});
+        }
+        schedule_v3::register_sub_state::<S>(&mut self.world);
+        self
+    }
+
+    /// Applies the function to the stageless [`Schedule`](schedule_v3::Schedule) of the
+    /// [`Schedules`] resource associated with `label`, creating both if they don't exist.
+    fn edit_stageless_schedule(
+        &mut self,
+        label: impl ScheduleLabel,
+        f: impl FnOnce(&mut schedule_v3::Schedule),
+    ) -> &mut Self {
+        let mut schedules = self.world.get_resource_or_insert_with(Schedules::new);
+        if schedules.get(&label).is_none() {
+            schedules.insert_boxed(label.dyn_clone(), schedule_v3::Schedule::new());
+        }
+        f(schedules.get_mut(&label).unwrap());
+        self
+    }
+
     /// Adds utility stages to the [`Schedule`], giving it a standardized structure.
     ///
     /// Adding those stages is necessary to make some core engine features work, like
@@ -732,6 +794,36 @@ impl App {
         self
     }
 
+    /// Setup the application to manage events of type `T`, using the given [`EventSettings`].
+    ///
+    /// Works like [`add_event`](Self::add_event), but allows keeping events around until they
+    /// are cleared manually and limiting how many events are stored at once.
+    /// If the events were already added, only their settings are replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{prelude::*, event::EventSettings};
+    /// #
+    /// # struct NetworkMessage;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_event_with_settings::<NetworkMessage>(EventSettings {
+    ///     manual_clearing: true,
+    ///     capacity: Some(1024),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn add_event_with_settings<T>(&mut self, settings: EventSettings) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        *self.world.resource_mut::<Events<T>>().settings_mut() = settings;
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -1114,6 +1206,13 @@ pub struct AppExit;
 #[cfg(test)]
 mod tests {
     use crate::{App, Plugin};
+    use bevy_ecs::{
+        prelude::{Component, Resource},
+        schedule_v3::{
+            apply_state_transition, apply_sub_state_transition, NextState, State, StateScoped,
+            States, SubStates,
+        },
+    };
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1167,4 +1266,125 @@ mod tests {
         }
         App::new().add_plugin(PluginRun);
     }
+
+    #[test]
+    fn state_scoped_resources_and_entities() {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        enum AppState {
+            Menu,
+            InGame,
+        }
+
+        impl States for AppState {
+            type Iter = std::array::IntoIter<AppState, 2>;
+
+            fn states() -> Self::Iter {
+                [AppState::Menu, AppState::InGame].into_iter()
+            }
+        }
+
+        #[derive(Component)]
+        struct Marker;
+
+        #[derive(Resource, Default)]
+        struct MenuData;
+
+        let mut app = App::new();
+        app.insert_resource(State(AppState::InGame))
+            .insert_resource(NextState(Some(AppState::Menu)))
+            .init_resource_on_enter::<AppState, MenuData>(AppState::Menu)
+            .enable_state_scoped_entities::<AppState>();
+        let game = app
+            .world
+            .spawn((Marker, StateScoped(AppState::InGame)))
+            .id();
+
+        apply_state_transition::<AppState>(&mut app.world);
+        assert!(app.world.get_entity(game).is_none());
+        assert!(app.world.contains_resource::<MenuData>());
+        let menu = app.world.spawn((Marker, StateScoped(AppState::Menu))).id();
+
+        app.world.resource_mut::<NextState<AppState>>().0 = Some(AppState::InGame);
+        apply_state_transition::<AppState>(&mut app.world);
+        assert!(app.world.get_entity(menu).is_none());
+        assert!(!app.world.contains_resource::<MenuData>());
+    }
+
+    #[test]
+    fn sub_states() {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        enum AppState {
+            Menu,
+            InGame,
+        }
+
+        impl States for AppState {
+            type Iter = std::array::IntoIter<AppState, 2>;
+
+            fn states() -> Self::Iter {
+                [AppState::Menu, AppState::InGame].into_iter()
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        enum GamePhase {
+            #[default]
+            Running,
+            Paused,
+        }
+
+        impl States for GamePhase {
+            type Iter = std::array::IntoIter<GamePhase, 2>;
+
+            fn states() -> Self::Iter {
+                [GamePhase::Running, GamePhase::Paused].into_iter()
+            }
+        }
+
+        impl SubStates for GamePhase {
+            type Parent = AppState;
+
+            fn exists_in(parent: &AppState) -> bool {
+                *parent == AppState::InGame
+            }
+        }
+
+        #[derive(Component)]
+        struct Marker;
+
+        let mut app = App::new();
+        app.insert_resource(State(AppState::Menu))
+            .insert_resource(NextState::<AppState>(None))
+            .add_sub_state::<GamePhase>()
+            .enable_state_scoped_entities::<GamePhase>();
+        let transition = |app: &mut App| {
+            apply_state_transition::<AppState>(&mut app.world);
+            apply_sub_state_transition::<GamePhase>(&mut app.world);
+        };
+
+        app.world.resource_mut::<NextState<AppState>>().0 = Some(AppState::InGame);
+        transition(&mut app);
+        assert_eq!(
+            app.world.resource::<State<GamePhase>>().0,
+            GamePhase::Running
+        );
+
+        app.world.resource_mut::<NextState<GamePhase>>().0 = Some(GamePhase::Paused);
+        transition(&mut app);
+        assert_eq!(
+            app.world.resource::<State<GamePhase>>().0,
+            GamePhase::Paused
+        );
+        let paused = app
+            .world
+            .spawn((Marker, StateScoped(GamePhase::Paused)))
+            .id();
+
+        // leaving the parent state exits the sub-state
+        app.world.resource_mut::<NextState<AppState>>().0 = Some(AppState::Menu);
+        transition(&mut app);
+        assert!(!app.world.contains_resource::<State<GamePhase>>());
+        assert!(!app.world.contains_resource::<NextState<GamePhase>>());
+        assert!(app.world.get_entity(paused).is_none());
+    }
 }
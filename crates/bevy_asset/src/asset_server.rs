@@ -1,17 +1,24 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    settings_path, Asset, AssetEvent, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, AssetRetentionPolicy, Assets, EmbeddedAssetIo, Handle,
+    HandleId, HandleUntyped, LabelId, LabeledAsset, LoadContext, LoadProgress, LoadState,
+    LoadedWithDependencies, RefChange, RefChangeChannel, RoutedAssetIo, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
-use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    system::{Local, Res, ResMut, Resource},
+};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Entry, HashMap, HashSet, Instant, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -55,6 +62,13 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+struct RetainedAsset {
+    id: HandleId,
+    type_uuid: Uuid,
+    unused_since: Instant,
+    size: u64,
+}
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
@@ -66,10 +80,12 @@ pub(crate) struct AssetRefCounter {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: RoutedAssetIo,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
+    retention_policies: RwLock<HashMap<Uuid, AssetRetentionPolicy>>,
+    retained_assets: Mutex<Vec<RetainedAsset>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
@@ -132,14 +148,27 @@ impl AssetServer {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                retention_policies: Default::default(),
+                retained_assets: Default::default(),
+                asset_io: RoutedAssetIo::new(asset_io),
             }),
         }
     }
 
     /// Returns the associated asset I/O.
     pub fn asset_io(&self) -> &dyn AssetIo {
-        &*self.server.asset_io
+        &*self.server.asset_io.default
+    }
+
+    /// Returns the asset I/O of the assets embedded in the application, which are loaded from
+    /// `embedded://` paths.
+    pub fn embedded_assets(&self) -> &EmbeddedAssetIo {
+        &self.server.asset_io.embedded
+    }
+
+    // the asset I/O used for loading, which also handles `embedded://` paths
+    pub(crate) fn source_asset_io(&self) -> &dyn AssetIo {
+        &self.server.asset_io
     }
 
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
@@ -239,6 +268,93 @@ impl AssetServer {
             .cloned()
     }
 
+    /// Gets the labeled assets loaded from the same source as an asset, sorted by label.
+    ///
+    /// This lists every asset a loader produced with
+    /// [`LoadContext::set_labeled_asset`], such as the meshes, materials and animations of a glTF
+    /// file, without having to know their labels. Returns an empty list if the source of the
+    /// asset isn't loaded yet.
+    pub fn get_labeled_assets<H: Into<HandleId>>(&self, handle: H) -> Vec<LabeledAsset> {
+        let source_path_id = match handle.into() {
+            HandleId::AssetPathId(id) => id.source_path_id(),
+            HandleId::Id(_, _) => return Vec::new(),
+        };
+        let asset_sources = self.server.asset_sources.read();
+        let (source_path, meta) = match asset_sources.get(&source_path_id) {
+            Some(SourceInfo {
+                path,
+                meta: Some(meta),
+                ..
+            }) => (path, meta),
+            _ => return Vec::new(),
+        };
+        let mut labeled_assets = meta
+            .assets
+            .iter()
+            .filter_map(|asset_meta| {
+                let label = asset_meta.label.as_ref()?;
+                let id = AssetPath::new_ref(source_path, Some(label)).get_id();
+                Some(LabeledAsset {
+                    label: label.clone(),
+                    type_uuid: asset_meta.type_uuid,
+                    handle: self.get_handle_untyped(id),
+                })
+            })
+            .collect::<Vec<_>>();
+        labeled_assets.sort_by(|a, b| a.label.cmp(&b.label));
+        labeled_assets
+    }
+
+    /// Gets the paths of the asset sources that depend on the asset source at `path`,
+    /// recursively, sorted by path.
+    ///
+    /// An asset source depends on another one if one of its assets was declared to depend on
+    /// an asset of the other with [`LoadedAsset::add_dependency`](crate::LoadedAsset::add_dependency),
+    /// such as a shader importing another shader, or if its loader read the other with
+    /// [`LoadContext::read_asset_bytes`], such as a glTF file reading a texture. When watching
+    /// for changes, the dependents of a changed file are reloaded along with it.
+    ///
+    /// Sources whose assets were unloaded are not included.
+    pub fn get_dependents<P: AsRef<Path>>(&self, path: P) -> Vec<PathBuf> {
+        let asset_sources = self.server.asset_sources.read();
+        let mut dependents_of = HashMap::<&Path, Vec<&Path>>::default();
+        for source_info in asset_sources.values() {
+            let meta = match &source_info.meta {
+                Some(meta) if source_info.load_state != LoadState::Unloaded => meta,
+                _ => continue,
+            };
+            let dependencies = meta
+                .assets
+                .iter()
+                .flat_map(|asset_meta| asset_meta.dependencies.iter())
+                .map(|dependency| dependency.path())
+                .chain(source_info.read_paths.iter().map(PathBuf::as_path));
+            for dependency in dependencies {
+                dependents_of
+                    .entry(dependency)
+                    .or_default()
+                    .push(&source_info.path);
+            }
+        }
+
+        let path = path.as_ref();
+        let mut dependents = HashSet::default();
+        let mut stack = vec![path];
+        while let Some(dependency) = stack.pop() {
+            for &dependent in dependents_of.get(dependency).into_iter().flatten() {
+                if dependent != path && dependents.insert(dependent) {
+                    stack.push(dependent);
+                }
+            }
+        }
+        let mut dependents = dependents
+            .into_iter()
+            .map(Path::to_owned)
+            .collect::<Vec<_>>();
+        dependents.sort();
+        dependents
+    }
+
     /// Gets the load state of an asset from the provided handle.
     pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
         match handle.into() {
@@ -276,6 +392,110 @@ impl AssetServer {
         load_state
     }
 
+    /// Gets the load state of an asset and of everything it depends on, recursively.
+    ///
+    /// The dependencies of an asset are the other assets of its source, such as the meshes and
+    /// textures of a glTF scene, and the assets they were declared to depend on with
+    /// [`LoadedAsset::add_dependency`](crate::LoadedAsset::add_dependency). Unlike
+    /// [`AssetServer::get_load_state`], this only returns [`LoadState::Loaded`] once all of them
+    /// are loaded, and returns [`LoadState::Failed`] if any of them failed to load.
+    pub fn get_load_state_with_dependencies<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        let handle = handle.into();
+        let id = match handle {
+            HandleId::AssetPathId(id) => id.source_path_id(),
+            HandleId::Id(_, _) => return LoadState::NotLoaded,
+        };
+        if self.get_load_state(handle) == LoadState::NotLoaded {
+            return LoadState::NotLoaded;
+        }
+
+        let mut load_state = LoadState::Loaded;
+        self.visit_sources_with_dependencies([id], |source_info| {
+            let source_load_state = source_info.map_or(LoadState::Loading, |info| info.load_state);
+            load_state = match (load_state, source_load_state) {
+                (LoadState::Failed, _) | (_, LoadState::Failed) => LoadState::Failed,
+                (LoadState::Unloaded, _) | (_, LoadState::Unloaded) => LoadState::Unloaded,
+                (LoadState::Loaded, LoadState::Loaded) => LoadState::Loaded,
+                _ => LoadState::Loading,
+            };
+        });
+        load_state
+    }
+
+    /// Returns `true` if an asset and everything it depends on are loaded.
+    ///
+    /// See [`AssetServer::get_load_state_with_dependencies`].
+    pub fn is_loaded_with_dependencies<H: Into<HandleId>>(&self, handle: H) -> bool {
+        self.get_load_state_with_dependencies(handle) == LoadState::Loaded
+    }
+
+    /// Gets the loading progress of an asset and of everything it depends on, recursively.
+    ///
+    /// See [`AssetServer::get_load_state_with_dependencies`] for what the dependencies of an asset
+    /// are. As dependencies are only known once the asset that depends on them is loaded, the
+    /// number of queued sources can grow while the asset loads.
+    pub fn get_load_progress<H: Into<HandleId>>(&self, handle: H) -> LoadProgress {
+        self.get_group_load_progress(std::iter::once(handle.into()))
+    }
+
+    /// Gets the combined loading progress of a group of assets and of everything they depend on,
+    /// recursively.
+    ///
+    /// Sources shared by several assets of the group are only counted once.
+    pub fn get_group_load_progress(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> LoadProgress {
+        let roots = handles.into_iter().filter_map(|handle| match handle {
+            HandleId::AssetPathId(id) => Some(id.source_path_id()),
+            HandleId::Id(_, _) => None,
+        });
+        let mut progress = LoadProgress::default();
+        self.visit_sources_with_dependencies(roots, |source_info| match source_info {
+            Some(source_info) => progress.add_source(source_info),
+            None => progress.queued += 1,
+        });
+        progress
+    }
+
+    /// Gets the combined loading progress of every asset source known to the asset server.
+    ///
+    /// Sources whose assets were unloaded are not counted.
+    pub fn load_progress(&self) -> LoadProgress {
+        let mut progress = LoadProgress::default();
+        for source_info in self.server.asset_sources.read().values() {
+            progress.add_source(source_info);
+        }
+        progress
+    }
+
+    // Visits the sources of `roots` and the sources they depend on, recursively. Dependencies are
+    // queued before their source info is created, in which case `visit` gets `None`.
+    fn visit_sources_with_dependencies(
+        &self,
+        roots: impl IntoIterator<Item = SourcePathId>,
+        mut visit: impl FnMut(Option<&SourceInfo>),
+    ) {
+        let asset_sources = self.server.asset_sources.read();
+        let mut visited = HashSet::default();
+        let mut stack = roots.into_iter().collect::<Vec<_>>();
+        while let Some(source_path_id) = stack.pop() {
+            if !visited.insert(source_path_id) {
+                continue;
+            }
+            let source_info = asset_sources.get(&source_path_id);
+            visit(source_info);
+            if let Some(meta) = source_info.and_then(|source_info| source_info.meta.as_ref()) {
+                stack.extend(meta.assets.iter().flat_map(|asset_meta| {
+                    asset_meta
+                        .dependencies
+                        .iter()
+                        .map(|dependency| dependency.get_id().source_path_id())
+                }));
+            }
+        }
+    }
+
     /// Queues an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -324,6 +544,9 @@ impl AssetServer {
                     meta: None,
                     path: asset_path.path().to_owned(),
                     version: 0,
+                    bytes_read: 0,
+                    total_bytes: None,
+                    read_paths: Vec::new(),
                 }),
             };
 
@@ -341,9 +564,23 @@ impl AssetServer {
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
+            source_info.read_paths.clear();
+            source_info.bytes_read = 0;
+            source_info.total_bytes = None;
             source_info.version
         };
 
+        let update_source_bytes = |bytes_read: u64, total_bytes: Option<u64>| {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .get_mut(&asset_path_id.source_path_id())
+                .expect("`AssetSource` should exist at this point.");
+            if source_info.version == version {
+                source_info.bytes_read = bytes_read;
+                source_info.total_bytes = total_bytes;
+            }
+        };
+
         let set_asset_failed = || {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
@@ -361,9 +598,36 @@ impl AssetServer {
             }
         };
 
+        // the size is only used to report progress, so asset I/Os are free not to know it
+        if let Some(size) = self
+            .source_asset_io()
+            .get_metadata(asset_path.path())
+            .ok()
+            .and_then(|metadata| metadata.size())
+        {
+            update_source_bytes(0, Some(size));
+        }
+
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
+        let bytes = match self.source_asset_io().load_path(asset_path.path()).await {
+            Ok(bytes) => {
+                update_source_bytes(bytes.len() as u64, Some(bytes.len() as u64));
+                bytes
+            }
+            Err(err) => {
+                set_asset_failed();
+                return Err(AssetServerError::AssetIoError(err));
+            }
+        };
+
+        // load the settings of the asset source, if it has any
+        let settings = match self
+            .source_asset_io()
+            .load_path(&settings_path(asset_path.path()))
+            .await
+        {
+            Ok(settings) => Some(settings),
+            Err(AssetIoError::NotFound(_)) => None,
             Err(err) => {
                 set_asset_failed();
                 return Err(AssetServerError::AssetIoError(err));
@@ -374,9 +638,10 @@ impl AssetServer {
         let mut load_context = LoadContext::new(
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            self.source_asset_io(),
             version,
         );
+        load_context.settings = settings;
 
         if let Err(err) = asset_loader
             .load(&bytes, &mut load_context)
@@ -410,6 +675,7 @@ impl AssetServer {
         source_info.meta = Some(SourceMeta {
             assets: load_context.get_asset_metas(),
         });
+        source_info.read_paths = std::mem::take(load_context.read_paths.get_mut());
 
         // load asset dependencies and prepare asset type hashmap
         for (label, loaded_asset) in &mut load_context.labeled_assets {
@@ -421,9 +687,14 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
+        self.source_asset_io()
             .watch_path_for_changes(asset_path.path())
             .unwrap();
+        for read_path in &source_info.read_paths {
+            if let Err(err) = self.source_asset_io().watch_path_for_changes(read_path) {
+                warn!("{}", err);
+            }
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
@@ -480,15 +751,15 @@ impl AssetServer {
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
         let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        if !self.source_asset_io().is_dir(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
+        for child_path in self.source_asset_io().read_directory(path.as_ref())? {
+            if self.source_asset_io().is_dir(&child_path) {
                 handles.extend(self.load_folder(&child_path)?);
             } else {
                 if self.get_path_asset_loader(&child_path).is_err() {
@@ -503,31 +774,137 @@ impl AssetServer {
         Ok(handles)
     }
 
-    /// Frees unused assets, unloading them from memory.
+    /// Sets how long unused assets of type `T` stay loaded.
+    ///
+    /// See [`AssetRetentionPolicy`].
+    pub fn set_retention_policy<T: Asset>(&self, policy: AssetRetentionPolicy) {
+        self.server
+            .retention_policies
+            .write()
+            .insert(T::TYPE_UUID, policy);
+    }
+
+    /// Gets how long unused assets of type `T` stay loaded.
+    pub fn get_retention_policy<T: Asset>(&self) -> AssetRetentionPolicy {
+        self.server
+            .retention_policies
+            .read()
+            .get(&T::TYPE_UUID)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Frees unused assets, unloading them from memory according to the
+    /// [retention policy](AssetRetentionPolicy) of their type.
     pub fn free_unused_assets(&self) {
+        self.free_unused_assets_impl(false);
+    }
+
+    /// Frees every asset that has no active handles, ignoring the
+    /// [retention policies](AssetRetentionPolicy) of their types.
+    ///
+    /// This is useful to release the assets kept loaded by a retention policy at a convenient
+    /// time, for example when switching levels. As handles dropped during the current frame are
+    /// only noticed by [`AssetServer::mark_unused_assets`], this also marks unused assets first.
+    pub fn free_unused(&self) {
+        self.mark_unused_assets();
+        self.free_unused_assets_impl(true);
+    }
+
+    fn free_unused_assets_impl(&self, ignore_retention: bool) {
         let mut potential_frees = self.server.asset_ref_counter.mark_unused_assets.lock();
+        let mut retained_assets = self.server.retained_assets.lock();
+        if potential_frees.is_empty() && retained_assets.is_empty() {
+            return;
+        }
 
-        if !potential_frees.is_empty() {
-            let ref_counts = self.server.asset_ref_counter.ref_counts.read();
-            let asset_sources = self.server.asset_sources.read();
-            let asset_lifecycles = self.server.asset_lifecycles.read();
-            for potential_free in potential_frees.drain(..) {
-                if let Some(&0) = ref_counts.get(&potential_free) {
-                    let type_uuid = match potential_free {
-                        HandleId::Id(type_uuid, _) => Some(type_uuid),
-                        HandleId::AssetPathId(id) => asset_sources
-                            .get(&id.source_path_id())
-                            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
-                    };
-
-                    if let Some(type_uuid) = type_uuid {
-                        if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
-                            asset_lifecycle.free_asset(potential_free);
+        let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+        let asset_sources = self.server.asset_sources.read();
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        let retention_policies = self.server.retention_policies.read();
+        let free_asset = |type_uuid: &Uuid, handle_id: HandleId| {
+            if let Some(asset_lifecycle) = asset_lifecycles.get(type_uuid) {
+                asset_lifecycle.free_asset(handle_id);
+            }
+        };
+        let now = Instant::now();
+
+        // retained assets that were handed out again are in use
+        retained_assets.retain(|asset| ref_counts.get(&asset.id) == Some(&0));
+
+        for potential_free in potential_frees.drain(..) {
+            if let Some(&0) = ref_counts.get(&potential_free) {
+                let type_uuid = match potential_free {
+                    HandleId::Id(type_uuid, _) => Some(type_uuid),
+                    HandleId::AssetPathId(id) => asset_sources
+                        .get(&id.source_path_id())
+                        .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+                };
+
+                if let Some(type_uuid) = type_uuid {
+                    let policy = retention_policies
+                        .get(&type_uuid)
+                        .copied()
+                        .unwrap_or_default();
+                    match potential_free {
+                        // only assets loaded from a path can be handed out again
+                        HandleId::AssetPathId(id) if policy != AssetRetentionPolicy::Immediate => {
+                            let size =
+                                asset_sources
+                                    .get(&id.source_path_id())
+                                    .map_or(0, |source_info| {
+                                        let assets = source_info
+                                            .meta
+                                            .as_ref()
+                                            .map_or(1, |meta| meta.assets.len().max(1));
+                                        source_info.total_bytes.unwrap_or(source_info.bytes_read)
+                                            / assets as u64
+                                    });
+                            retained_assets.retain(|asset| asset.id != potential_free);
+                            retained_assets.push(RetainedAsset {
+                                id: potential_free,
+                                type_uuid,
+                                unused_since: now,
+                                size,
+                            });
                         }
+                        _ => free_asset(&type_uuid, potential_free),
                     }
                 }
             }
         }
+
+        let mut unused_bytes = HashMap::<Uuid, u64>::default();
+        for asset in retained_assets.iter() {
+            *unused_bytes.entry(asset.type_uuid).or_default() += asset.size;
+        }
+        // retained assets are ordered from the least recently used
+        retained_assets.retain(|asset| {
+            let policy = retention_policies
+                .get(&asset.type_uuid)
+                .copied()
+                .unwrap_or_default();
+            let retain = !ignore_retention
+                && match policy {
+                    AssetRetentionPolicy::Immediate => false,
+                    AssetRetentionPolicy::KeepFor(duration) => {
+                        now.duration_since(asset.unused_since) < duration
+                    }
+                    AssetRetentionPolicy::MemoryBudget(budget) => {
+                        let unused_bytes = unused_bytes.get_mut(&asset.type_uuid).unwrap();
+                        if *unused_bytes > budget {
+                            *unused_bytes -= asset.size;
+                            false
+                        } else {
+                            true
+                        }
+                    }
+                };
+            if !retain {
+                free_asset(&asset.type_uuid, asset.id);
+            }
+            retain
+        });
     }
 
     /// Iterates through asset references and marks assets with no active handles as unused.
@@ -640,14 +1017,50 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     free_unused_assets_system_impl(&asset_server);
 }
 
+/// A system that sends a [`LoadedWithDependencies`] event for each asset of type `T` created by
+/// the asset server, once it and all of its dependencies are loaded.
+pub fn loaded_with_dependencies_system<T: Asset>(
+    asset_server: Res<AssetServer>,
+    mut asset_events: EventReader<AssetEvent<T>>,
+    mut events: EventWriter<LoadedWithDependencies<T>>,
+    mut pending: Local<HashSet<HandleId>>,
+) {
+    for event in asset_events.iter() {
+        match event {
+            AssetEvent::Created { handle } => {
+                if let HandleId::AssetPathId(_) = handle.id() {
+                    pending.insert(handle.id());
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                pending.remove(&handle.id());
+            }
+            AssetEvent::Modified { .. } => {}
+        }
+    }
+
+    pending.retain(
+        |&id| match asset_server.get_load_state_with_dependencies(id) {
+            LoadState::Loaded => {
+                events.send(LoadedWithDependencies {
+                    handle: Handle::weak(id),
+                });
+                false
+            }
+            LoadState::NotLoaded | LoadState::Loading => true,
+            LoadState::Failed | LoadState::Unloaded => false,
+        },
+    );
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{loader::LoadedAsset, update_asset_storage_system};
-    use bevy_app::App;
-    use bevy_ecs::prelude::*;
+    use bevy_app::{App, CoreStage};
+    use bevy_ecs::{event::Events, prelude::*};
     use bevy_reflect::TypeUuid;
-    use bevy_utils::BoxedFuture;
+    use bevy_utils::{BoxedFuture, Duration};
 
     #[derive(Debug, TypeUuid)]
     #[uuid = "a5189b72-0572-4290-a2e0-96f73a491c44"]
@@ -669,6 +1082,107 @@ mod test {
         }
     }
 
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "2c4e7a1d-51c5-4a6b-9d4f-0c3bd0d7e1a3"]
+    struct SceneAsset;
+
+    struct FakeSceneLoader;
+    impl AssetLoader for FakeSceneLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(
+                LoadedAsset::new(SceneAsset).with_dependency("texture.png".into()),
+            );
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scene"]
+        }
+    }
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "6f1c2a8e-96a4-4b7e-8a4f-3f1d9e1f5a27"]
+    struct TextAsset(String);
+
+    #[derive(serde::Deserialize, Default)]
+    #[serde(default)]
+    struct TextSettings {
+        uppercase: bool,
+    }
+
+    struct FakeTextLoader;
+    impl AssetLoader for FakeTextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let settings: TextSettings = ctx.settings()?;
+                let mut text = String::from_utf8(bytes.to_vec())?;
+                if settings.uppercase {
+                    text = text.to_uppercase();
+                }
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    struct FakeModelLoader;
+    impl AssetLoader for FakeModelLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let texture = ctx.set_labeled_asset("Texture1", LoadedAsset::new(PngAsset));
+            ctx.set_labeled_asset("Texture0", LoadedAsset::new(PngAsset));
+            let scene = ctx.set_labeled_asset("Scene0", LoadedAsset::new(SceneAsset));
+            ctx.set_default_asset(LoadedAsset::new(SceneAsset));
+            // sub-assets can be inspected before the load finishes
+            assert!(ctx.get_asset(&texture).is_some());
+            assert!(ctx.get_asset(&scene).is_some());
+            assert!(ctx
+                .get_asset(&Handle::<PngAsset>::weak(scene.id()))
+                .is_none());
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["model"]
+        }
+    }
+
+    struct FakeAtlasLoader;
+    impl AssetLoader for FakeAtlasLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                // the atlas contains the path of the image it packs
+                let image_path = std::str::from_utf8(bytes)?;
+                ctx.read_asset_bytes(image_path).await?;
+                ctx.set_default_asset(LoadedAsset::new(PngAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["atlas"]
+        }
+    }
+
     struct FailingLoader;
     impl AssetLoader for FailingLoader {
         fn load<'a>(
@@ -917,6 +1431,299 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_loaded_with_dependencies() {
+        let dir = create_dir_and_file("level.scene");
+        std::fs::write(dir.path().join("texture.png"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeSceneLoader);
+        asset_server.add_loader(FakePngLoader);
+        let scenes = asset_server.register_asset_type::<SceneAsset>();
+        let pngs = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(scenes)
+            .insert_resource(pngs)
+            .insert_resource(asset_server.clone())
+            .add_event::<AssetEvent<SceneAsset>>()
+            .add_event::<LoadedWithDependencies<SceneAsset>>()
+            .add_system(update_asset_storage_system::<SceneAsset>)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                Assets::<SceneAsset>::asset_event_system,
+            )
+            .add_system_to_stage(
+                CoreStage::Last,
+                loaded_with_dependencies_system::<SceneAsset>,
+            );
+
+        let path: AssetPath = "level.scene".into();
+        let id = futures_lite::future::block_on(asset_server.load_async(path, false)).unwrap();
+        let handle = asset_server.get_handle_untyped(id);
+
+        // the texture can't be stored yet, as its asset storage isn't updated
+        app.update();
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+        assert!(!asset_server.is_loaded_with_dependencies(&handle));
+        let events = app
+            .world
+            .resource::<Events<LoadedWithDependencies<SceneAsset>>>();
+        assert!(events.is_empty());
+
+        app.add_system(update_asset_storage_system::<PngAsset>);
+        for _ in 0..100 {
+            app.update();
+            if asset_server.is_loaded_with_dependencies(&handle) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            asset_server.get_load_state_with_dependencies(&handle),
+            LoadState::Loaded
+        );
+
+        let events = app
+            .world
+            .resource::<Events<LoadedWithDependencies<SceneAsset>>>();
+        let mut reader = events.get_reader();
+        let loaded = reader
+            .iter(events)
+            .map(|event| event.handle.id())
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, vec![handle.id()]);
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("level.scene"), [0; 10]).unwrap();
+        std::fs::write(dir.path().join("texture.png"), [0; 30]).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeSceneLoader);
+        asset_server.add_loader(FakePngLoader);
+        let scenes = asset_server.register_asset_type::<SceneAsset>();
+        let pngs = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(scenes)
+            .insert_resource(pngs)
+            .insert_resource(asset_server.clone())
+            .add_system(update_asset_storage_system::<SceneAsset>)
+            .add_system(update_asset_storage_system::<PngAsset>);
+
+        assert_eq!(asset_server.load_progress(), LoadProgress::default());
+
+        let path: AssetPath = "level.scene".into();
+        let id = futures_lite::future::block_on(asset_server.load_async(path, false)).unwrap();
+        let handle = asset_server.get_handle_untyped(id);
+
+        // the scene was read and its texture is queued, but nothing is stored yet
+        let progress = asset_server.get_load_progress(&handle);
+        assert_eq!(progress.queued, 2);
+        assert_eq!(progress.finished, 0);
+        assert!(progress.bytes_read >= 10);
+        assert!(!progress.is_finished());
+
+        for _ in 0..100 {
+            app.update();
+            if asset_server.get_load_progress(&handle).is_finished() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let progress = asset_server.get_load_progress(&handle);
+        assert_eq!(
+            progress,
+            LoadProgress {
+                finished: 2,
+                failed: 0,
+                queued: 2,
+                bytes_read: 40,
+                total_bytes: 40,
+            }
+        );
+        assert_eq!(progress.fraction(), 1.0);
+        assert_eq!(asset_server.load_progress(), progress);
+
+        let missing: AssetPath = "missing.png".into();
+        let _ = futures_lite::future::block_on(asset_server.load_async(missing.clone(), false));
+        let progress = asset_server.get_group_load_progress([id.into(), missing.get_id().into()]);
+        assert_eq!(
+            (progress.finished, progress.failed, progress.queued),
+            (3, 1, 3)
+        );
+    }
+
+    #[test]
+    fn test_retention_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["a.png", "b.png", "c.png"] {
+            std::fs::write(dir.path().join(file), [0; 10]).unwrap();
+        }
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_system(free_unused_assets_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_asset_storage_system::<PngAsset>,
+            );
+
+        let load = |path: &str| {
+            let path: AssetPath = path.into();
+            let id = futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+            asset_server.get_handle::<PngAsset, _>(id)
+        };
+        let is_loaded = |app: &App, handle: &Handle<PngAsset>| {
+            app.world.resource::<Assets<PngAsset>>().contains(handle)
+        };
+
+        // unused assets are kept until they exceed the budget, least recently used first
+        asset_server.set_retention_policy::<PngAsset>(AssetRetentionPolicy::MemoryBudget(25));
+        let handles = [load("a.png"), load("b.png"), load("c.png")];
+        app.update();
+        let weak_handles = handles.clone().map(|handle| handle.clone_weak());
+        drop(handles);
+        app.update();
+        app.update();
+        app.update();
+        assert!(!is_loaded(&app, &weak_handles[0]));
+        assert!(is_loaded(&app, &weak_handles[1]));
+        assert!(is_loaded(&app, &weak_handles[2]));
+
+        // getting a strong handle again keeps the asset loaded
+        let handle = asset_server.get_handle::<PngAsset, _>(&weak_handles[1]);
+        asset_server.set_retention_policy::<PngAsset>(AssetRetentionPolicy::KeepFor(
+            Duration::from_secs(3600),
+        ));
+        asset_server.free_unused();
+        app.update();
+        assert!(is_loaded(&app, &handle));
+        assert!(!is_loaded(&app, &weak_handles[2]));
+
+        drop(handle);
+        app.update();
+        app.update();
+        app.update();
+        assert!(is_loaded(&app, &weak_handles[1]));
+
+        asset_server.set_retention_policy::<PngAsset>(AssetRetentionPolicy::Immediate);
+        app.update();
+        app.update();
+        assert!(!is_loaded(&app, &weak_handles[1]));
+    }
+
+    #[test]
+    fn test_loader_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["default.txt", "upper.txt", "invalid.txt"] {
+            std::fs::write(dir.path().join(file), "hello").unwrap();
+        }
+        std::fs::write(dir.path().join("upper.txt.meta"), "(uppercase: true)").unwrap();
+        std::fs::write(dir.path().join("invalid.txt.meta"), "(uppercase: ").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeTextLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .add_system(update_asset_storage_system::<TextAsset>)
+            .insert_resource(asset_server.clone());
+
+        let load = |path: &str| {
+            let path: AssetPath = path.into();
+            futures_lite::future::block_on(asset_server.load_async(path.clone(), false))
+                .map(|id| asset_server.get_handle::<TextAsset, _>(id))
+        };
+        let default = load("default.txt").unwrap();
+        let upper = load("upper.txt").unwrap();
+        assert!(matches!(
+            load("invalid.txt"),
+            Err(AssetServerError::AssetLoaderError(_))
+        ));
+
+        app.update();
+        let assets = app.world.resource::<Assets<TextAsset>>();
+        assert_eq!(assets.get(&default).unwrap().0, "hello");
+        assert_eq!(assets.get(&upper).unwrap().0, "HELLO");
+    }
+
+    #[test]
+    fn test_get_labeled_assets() {
+        let dir = create_dir_and_file("cube.model");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeModelLoader);
+        asset_server.register_asset_type::<SceneAsset>();
+        asset_server.register_asset_type::<PngAsset>();
+
+        let path: AssetPath = "cube.model".into();
+        assert!(asset_server.get_labeled_assets(path.clone()).is_empty());
+
+        let id =
+            futures_lite::future::block_on(asset_server.load_async(path.clone(), false)).unwrap();
+        let handle = asset_server.get_handle_untyped(id);
+        let labeled_assets = asset_server.get_labeled_assets(&handle);
+        let labels = labeled_assets
+            .iter()
+            .map(|labeled_asset| labeled_asset.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["Scene0", "Texture0", "Texture1"]);
+
+        assert!(labeled_assets[0].is::<SceneAsset>());
+        assert!(labeled_assets[0].typed::<PngAsset>().is_none());
+        let texture = labeled_assets[1].typed::<PngAsset>().unwrap();
+        assert_eq!(
+            texture.id(),
+            AssetPath::new_ref(path.path(), Some("Texture0"))
+                .get_id()
+                .into()
+        );
+
+        // labeled assets of the same source are listed from any of them
+        assert_eq!(asset_server.get_labeled_assets(&texture).len(), 3);
+        assert!(asset_server
+            .get_labeled_assets(HandleId::random::<PngAsset>())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_dependents() {
+        let dir = create_dir_and_file("level.scene");
+        std::fs::write(dir.path().join("texture.png"), []).unwrap();
+        std::fs::write(dir.path().join("ui.atlas"), "texture.png").unwrap();
+        std::fs::write(dir.path().join("menu.atlas"), "ui.atlas").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeSceneLoader);
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeAtlasLoader);
+        asset_server.register_asset_type::<SceneAsset>();
+        asset_server.register_asset_type::<PngAsset>();
+
+        for path in ["level.scene", "ui.atlas", "menu.atlas"] {
+            futures_lite::future::block_on(asset_server.load_async(path.into(), false)).unwrap();
+        }
+
+        assert_eq!(
+            asset_server.get_dependents("texture.png"),
+            vec![
+                PathBuf::from("level.scene"),
+                PathBuf::from("menu.atlas"),
+                PathBuf::from("ui.atlas")
+            ]
+        );
+        assert_eq!(
+            asset_server.get_dependents("ui.atlas"),
+            vec![PathBuf::from("menu.atlas")]
+        );
+        assert!(asset_server.get_dependents("menu.atlas").is_empty());
+        assert!(asset_server.get_dependents("unknown.png").is_empty());
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,10 +1,12 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange, ReflectAsset, ReflectHandle,
+    loaded_with_dependencies_system, update_asset_storage_system, Asset, AssetLoader,
+    AssetProcessor, AssetProcessors, AssetRetentionPolicy, AssetServer, AssetStage, Handle,
+    HandleId, RefChange, ReflectAsset, ReflectHandle,
 };
 use bevy_app::{App, AppTypeRegistry};
 use bevy_ecs::{
     event::{EventWriter, Events},
+    schedule::IntoSystemDescriptor,
     system::{ResMut, Resource},
     world::FromWorld,
 };
@@ -54,6 +56,31 @@ impl<T: Asset> Debug for AssetEvent<T> {
     }
 }
 
+/// An event sent once an asset of type `T` loaded by the [`AssetServer`] and everything it
+/// depends on are loaded.
+///
+/// Unlike [`AssetEvent::Created`], which is sent as soon as the asset itself is added to its
+/// [`Assets`] collection, this waits for the whole dependency graph: a glTF scene is only
+/// reported once its meshes, materials and textures are loaded as well. See
+/// [`AssetServer::get_load_state_with_dependencies`].
+///
+/// The event holds a _Weak_ handle, like [`AssetEvent`].
+pub struct LoadedWithDependencies<T: Asset> {
+    /// The asset that was loaded with its dependencies.
+    pub handle: Handle<T>,
+}
+
+impl<T: Asset> Debug for LoadedWithDependencies<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!(
+            "LoadedWithDependencies<{}>",
+            std::any::type_name::<T>()
+        ))
+        .field("handle", &self.handle.id())
+        .finish()
+    }
+}
+
 /// Stores Assets of a given type and tracks changes to them.
 ///
 /// Each asset is mapped by a unique [`HandleId`], allowing any [`Handle`] with the same
@@ -157,6 +184,15 @@ impl<T: Asset> Assets<T> {
         self.assets.get_mut(&id)
     }
 
+    /// Get mutable access to the asset for the given handle, without sending an
+    /// [`AssetEvent::Modified`].
+    ///
+    /// This is meant for bookkeeping that the systems reacting to the modifications of the asset
+    /// don't need to see, such as clearing the state of its previous modification.
+    pub fn get_mut_without_event(&mut self, handle: &Handle<T>) -> Option<&mut T> {
+        self.assets.get_mut(&handle.into())
+    }
+
     /// Gets a _Strong_ handle pointing to the same asset as the given one.
     pub fn get_handle<H: Into<HandleId>>(&self, handle: H) -> Handle<T> {
         Handle::strong(handle.into(), self.ref_change_sender.clone())
@@ -319,6 +355,21 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Sets how long unused assets of type `T` stay loaded.
+    ///
+    /// See [`AssetRetentionPolicy`].
+    fn set_asset_retention_policy<T>(&mut self, policy: AssetRetentionPolicy) -> &mut Self
+    where
+        T: Asset;
+
+    /// Adds the provided asset processor to the application.
+    ///
+    /// Processors only run if the [`AssetPlugin`](crate::AssetPlugin) is in
+    /// [`AssetMode::Processing`](crate::AssetMode::Processing).
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -336,9 +387,14 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_system_to_stage(AssetStage::AssetEvents, Assets::<T>::asset_event_system)
+            .add_system_to_stage(
+                AssetStage::AssetEvents,
+                loaded_with_dependencies_system::<T>.after(Assets::<T>::asset_event_system),
+            )
             .add_system_to_stage(AssetStage::LoadAssets, update_asset_storage_system::<T>)
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
+            .add_event::<LoadedWithDependencies<T>>()
     }
 
     fn register_asset_reflect<T>(&mut self) -> &mut Self
@@ -403,12 +459,35 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn set_asset_retention_policy<T>(&mut self, policy: AssetRetentionPolicy) -> &mut Self
+    where
+        T: Asset,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .set_retention_policy::<T>(policy);
+        self
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .get_resource_or_insert_with(AssetProcessors::default)
+            .add(processor);
+        self
+    }
 }
 
 /// Loads an internal asset.
 ///
 /// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
 /// using the conventional API. See `DebugAssetServerPlugin`.
+///
+/// Consider [`embedded_asset`](crate::embedded_asset) instead, which gives the asset a path that
+/// is loaded and hot reloaded like any other asset, rather than a handle constant.
 #[cfg(feature = "debug_asset_server")]
 #[macro_export]
 macro_rules! load_internal_asset {
@@ -434,6 +513,9 @@ macro_rules! load_internal_asset {
 ///
 /// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
 /// using the conventional API. See `DebugAssetServerPlugin`.
+///
+/// Consider [`embedded_asset`](crate::embedded_asset) instead, which gives the asset a path that
+/// is loaded and hot reloaded like any other asset, rather than a handle constant.
 #[cfg(not(feature = "debug_asset_server"))]
 #[macro_export]
 macro_rules! load_internal_asset {
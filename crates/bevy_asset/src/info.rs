@@ -1,5 +1,5 @@
-use crate::{path::AssetPath, LabelId};
-use bevy_utils::{HashMap, HashSet, Uuid};
+use crate::{path::AssetPath, Asset, Handle, HandleUntyped, LabelId};
+use bevy_utils::{Duration, HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -36,6 +36,13 @@ pub struct SourceInfo {
     pub committed_assets: HashSet<LabelId>,
     /// Current version of the source.
     pub version: usize,
+    /// The number of bytes of the source that were read.
+    pub bytes_read: u64,
+    /// The size of the source in bytes, if known.
+    pub total_bytes: Option<u64>,
+    /// The paths of the other files read by the loader of the source, see
+    /// [`LoadContext::read_asset_bytes`](crate::LoadContext::read_asset_bytes).
+    pub read_paths: Vec<PathBuf>,
 }
 
 impl SourceInfo {
@@ -52,6 +59,30 @@ impl SourceInfo {
     }
 }
 
+/// A labeled asset loaded from the same source as another asset, see
+/// [`AssetServer::get_labeled_assets`](crate::AssetServer::get_labeled_assets).
+#[derive(Debug)]
+pub struct LabeledAsset {
+    /// The label of the asset, such as `Mesh0/Primitive0` for a glTF primitive.
+    pub label: String,
+    /// The type identifier of the asset.
+    pub type_uuid: Uuid,
+    /// A strong handle to the asset.
+    pub handle: HandleUntyped,
+}
+
+impl LabeledAsset {
+    /// Returns `true` if the asset is of type `T`.
+    pub fn is<T: Asset>(&self) -> bool {
+        self.type_uuid == T::TYPE_UUID
+    }
+
+    /// Returns a strong typed handle to the asset, or `None` if the asset is not of type `T`.
+    pub fn typed<T: Asset>(&self) -> Option<Handle<T>> {
+        self.is::<T>().then(|| self.handle.clone().typed())
+    }
+}
+
 /// The load state of an asset.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum LoadState {
@@ -67,3 +98,93 @@ pub enum LoadState {
     /// from the [`Assets`](crate::Assets) collection.
     Unloaded,
 }
+
+/// How long assets of a given type stay loaded once they have no active handles, see
+/// [`AssetServer::set_retention_policy`](crate::AssetServer::set_retention_policy).
+///
+/// Keeping unused assets loaded avoids reloading them when they are needed again shortly after,
+/// for example when the same enemy is spawned again. Only assets loaded from a path by the
+/// [`AssetServer`](crate::AssetServer) are kept, as the others can't be handed out again.
+/// [`AssetServer::free_unused`](crate::AssetServer::free_unused) frees all unused assets
+/// regardless of their policy.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AssetRetentionPolicy {
+    /// Unused assets are freed as soon as their last strong handle is dropped.
+    #[default]
+    Immediate,
+    /// Unused assets are freed once they have been unused for the given duration.
+    KeepFor(Duration),
+    /// Unused assets are kept until their total size in bytes exceeds the given budget, at which
+    /// point the least recently used ones are freed.
+    ///
+    /// The size of an asset is estimated from the size of the source it was loaded from, shared
+    /// evenly between the assets of that source.
+    MemoryBudget(u64),
+}
+
+/// The loading progress of a group of asset sources, see
+/// [`AssetServer::get_load_progress`](crate::AssetServer::get_load_progress).
+///
+/// This can be used to display a progress bar on a loading screen.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of sources that finished loading, either successfully or not.
+    pub finished: usize,
+    /// The number of sources that failed to load. These are included in `finished`.
+    pub failed: usize,
+    /// The number of sources queued for loading, including the finished ones.
+    pub queued: usize,
+    /// The number of bytes read from the sources.
+    pub bytes_read: u64,
+    /// The total size of the sources in bytes.
+    ///
+    /// Sources of unknown size only count once they are read, so this can grow while loading.
+    pub total_bytes: u64,
+}
+
+impl LoadProgress {
+    /// Returns `true` if every queued source finished loading.
+    pub fn is_finished(&self) -> bool {
+        self.finished == self.queued
+    }
+
+    /// Returns the fraction of the queued sources that finished loading, between `0.0` and `1.0`.
+    ///
+    /// Returns `1.0` if no source is queued.
+    pub fn fraction(&self) -> f32 {
+        if self.queued == 0 {
+            1.0
+        } else {
+            self.finished as f32 / self.queued as f32
+        }
+    }
+
+    /// Returns the fraction of the bytes of the sources that were read, between `0.0` and `1.0`.
+    ///
+    /// Returns `1.0` if the size of the sources is unknown.
+    pub fn bytes_fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_read as f32 / self.total_bytes as f32
+        }
+    }
+
+    pub(crate) fn add_source(&mut self, source_info: &SourceInfo) {
+        match source_info.load_state {
+            LoadState::Unloaded => return,
+            LoadState::Loaded => self.finished += 1,
+            LoadState::Failed => {
+                self.finished += 1;
+                self.failed += 1;
+            }
+            LoadState::NotLoaded | LoadState::Loading => {}
+        }
+        self.queued += 1;
+        self.bytes_read += source_info.bytes_read;
+        self.total_bytes += source_info
+            .total_bytes
+            .unwrap_or(source_info.bytes_read)
+            .max(source_info.bytes_read);
+    }
+}
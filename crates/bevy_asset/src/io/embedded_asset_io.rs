@@ -0,0 +1,394 @@
+#[cfg(feature = "filesystem_watcher")]
+use crate::filesystem_watcher::FilesystemWatcher;
+use crate::{AssetIo, AssetIoError, AssetStream, FileType, Metadata};
+use bevy_utils::{BoxedFuture, HashMap};
+#[cfg(feature = "filesystem_watcher")]
+use crossbeam_channel::TryRecvError;
+use parking_lot::RwLock;
+use std::path::{Component, Path, PathBuf};
+
+/// The prefix of the asset paths of embedded assets, see [`EmbeddedAssetIo`].
+pub const EMBEDDED_ASSET_PREFIX: &str = "embedded://";
+
+struct EmbeddedAsset {
+    bytes: &'static [u8],
+    #[cfg_attr(not(feature = "filesystem_watcher"), allow(dead_code))]
+    source_path: Option<PathBuf>,
+}
+
+/// An asset I/O for assets compiled into the application, such as the shaders of a plugin.
+///
+/// Every [`AssetServer`](crate::AssetServer) has one, which loads the asset paths starting with
+/// [`embedded://`](EMBEDDED_ASSET_PREFIX). Assets are usually added with the
+/// [`embedded_asset`](crate::embedded_asset) macro, which embeds the file at
+/// `src/shaders/pbr.wgsl` in the `my_plugin` crate as `embedded://my_plugin/shaders/pbr.wgsl`.
+///
+/// When watching for changes, assets whose source file exists on disk, as when running from the
+/// workspace of the crate, are read from that file instead and reloaded when it changes.
+#[derive(Default)]
+pub struct EmbeddedAssetIo {
+    assets: RwLock<HashMap<PathBuf, EmbeddedAsset>>,
+    #[cfg(feature = "filesystem_watcher")]
+    filesystem_watcher: RwLock<Option<FilesystemWatcher>>,
+}
+
+impl EmbeddedAssetIo {
+    /// Adds an embedded asset at `path`, relative to `embedded://`.
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: &'static [u8]) {
+        self.assets.write().insert(
+            path.into(),
+            EmbeddedAsset {
+                bytes,
+                source_path: None,
+            },
+        );
+    }
+
+    /// Adds an embedded asset at `path`, relative to `embedded://`, that was embedded from the
+    /// file at `source_path`.
+    ///
+    /// The source file is read instead of `bytes` when watching for changes.
+    pub fn insert_with_source(
+        &self,
+        path: impl Into<PathBuf>,
+        bytes: &'static [u8],
+        source_path: impl Into<PathBuf>,
+    ) {
+        self.assets.write().insert(
+            path.into(),
+            EmbeddedAsset {
+                bytes,
+                source_path: Some(source_path.into()),
+            },
+        );
+    }
+
+    /// Removes the embedded asset at `path`, relative to `embedded://`. Returns `false` if there
+    /// was no such asset.
+    pub fn remove(&self, path: &Path) -> bool {
+        self.assets.write().remove(path).is_some()
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn watched_source_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.filesystem_watcher.read().is_none() {
+            return None;
+        }
+        self.assets
+            .read()
+            .get(path)
+            .and_then(|asset| asset.source_path.clone())
+            .filter(|source_path| source_path.is_file())
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            #[cfg(feature = "filesystem_watcher")]
+            if let Some(source_path) = self.watched_source_path(path) {
+                return Ok(std::fs::read(source_path)?);
+            }
+            self.assets
+                .read()
+                .get(path)
+                .map(|asset| asset.bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut entries = self
+            .assets
+            .read()
+            .keys()
+            .filter_map(|asset_path| {
+                let child = asset_path.strip_prefix(path).ok()?.components().next()?;
+                Some(path.join(child))
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        entries.sort();
+        entries.dedup();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let assets = self.assets.read();
+        if let Some(asset) = assets.get(path) {
+            Ok(Metadata::new(FileType::File).with_size(asset.bytes.len() as u64))
+        } else if assets
+            .keys()
+            .any(|asset_path| asset_path != path && asset_path.starts_with(path))
+        {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        #[cfg(feature = "filesystem_watcher")]
+        if let Some(source_path) = self.watched_source_path(_path) {
+            if let Some(watcher) = self.filesystem_watcher.write().as_mut() {
+                watcher
+                    .watch(&source_path)
+                    .map_err(|_error| AssetIoError::PathWatchError(source_path))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            *self.filesystem_watcher.write() = Some(FilesystemWatcher::default());
+        }
+        #[cfg(not(feature = "filesystem_watcher"))]
+        bevy_log::warn!("Watching for changes is not supported when the `filesystem_watcher` feature is disabled");
+
+        Ok(())
+    }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            let mut changed = Vec::new();
+            if let Some(watcher) = self.filesystem_watcher.read().as_ref() {
+                let assets = self.assets.read();
+                loop {
+                    let event = match watcher.receiver.try_recv() {
+                        Ok(result) => result.unwrap(),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            panic!("FilesystemWatcher disconnected.")
+                        }
+                    };
+                    if let notify::event::EventKind::Modify(_) = event.kind {
+                        changed.extend(assets.iter().filter_map(|(path, asset)| {
+                            asset
+                                .source_path
+                                .as_ref()
+                                .filter(|source_path| event.paths.contains(source_path))
+                                .map(|_| path.clone())
+                        }));
+                    }
+                }
+            }
+            changed
+        }
+        #[cfg(not(feature = "filesystem_watcher"))]
+        Vec::new()
+    }
+}
+
+/// The asset I/O of an [`AssetServer`](crate::AssetServer), which loads `embedded://` paths from
+/// its [`EmbeddedAssetIo`] and all other paths from the asset I/O it was created with.
+pub(crate) struct RoutedAssetIo {
+    pub(crate) default: Box<dyn AssetIo>,
+    pub(crate) embedded: EmbeddedAssetIo,
+}
+
+impl RoutedAssetIo {
+    pub(crate) fn new(default: Box<dyn AssetIo>) -> Self {
+        Self {
+            default,
+            embedded: Default::default(),
+        }
+    }
+
+    fn route<'a>(&self, path: &'a Path) -> (&dyn AssetIo, &'a Path) {
+        match path
+            .to_str()
+            .and_then(|path| path.strip_prefix(EMBEDDED_ASSET_PREFIX))
+        {
+            Some(embedded_path) => (&self.embedded, Path::new(embedded_path)),
+            None => (&*self.default, path),
+        }
+    }
+}
+
+fn embedded(path: PathBuf) -> PathBuf {
+    let mut embedded_path = PathBuf::from(EMBEDDED_ASSET_PREFIX).into_os_string();
+    embedded_path.push(path.into_os_string());
+    embedded_path.into()
+}
+
+impl AssetIo for RoutedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        let (asset_io, path) = self.route(path);
+        asset_io.load_path(path)
+    }
+
+    fn open_stream(&self, path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        let (asset_io, path) = self.route(path);
+        asset_io.open_stream(path)
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let (asset_io, routed_path) = self.route(path);
+        let entries = asset_io.read_directory(routed_path)?;
+        if routed_path == path {
+            Ok(entries)
+        } else {
+            Ok(Box::new(entries.map(embedded)))
+        }
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let (asset_io, path) = self.route(path);
+        asset_io.get_metadata(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        let (asset_io, path) = self.route(path);
+        asset_io.watch_path_for_changes(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.default.watch_for_changes()?;
+        self.embedded.watch_for_changes()
+    }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = self.default.take_changed_paths();
+        changed.extend(self.embedded.take_changed_paths().into_iter().map(embedded));
+        changed
+    }
+}
+
+/// Returns the asset path of an asset embedded with the [`embedded_asset`](crate::embedded_asset)
+/// macro, along with the path of its source file.
+#[doc(hidden)]
+pub fn embedded_asset_paths(
+    crate_name: &str,
+    manifest_dir: &str,
+    file_path: &str,
+    asset_path: &str,
+) -> (PathBuf, PathBuf) {
+    // the path of the asset relative to the `src` folder of its crate
+    let directory = Path::new(file_path)
+        .parent()
+        .expect("file path must have a parent");
+    let src_position = directory
+        .components()
+        .position(|component| component == Component::Normal("src".as_ref()));
+    let relative_path = match src_position {
+        Some(position) => directory.components().skip(position + 1).collect(),
+        None => directory.to_owned(),
+    }
+    .join(asset_path);
+
+    (
+        Path::new(crate_name).join(&relative_path),
+        Path::new(manifest_dir).join("src").join(relative_path),
+    )
+}
+
+/// Embeds the file at `path`, relative to the current source file, in the application as an
+/// asset of the [`EmbeddedAssetIo`] of the app's [`AssetServer`](crate::AssetServer).
+///
+/// The asset path of the file is `embedded://`, followed by the name of the current crate and the
+/// path of the file relative to the `src` folder of the crate. This path is returned as an
+/// [`AssetPath`](crate::AssetPath). Unlike assets loaded with
+/// [`load_internal_asset`](crate::load_internal_asset), embedded assets are loaded like any other
+/// asset, and are hot reloaded when watching for changes while their source file exists.
+///
+/// ```ignore
+/// # use bevy_app::App;
+/// # use bevy_asset::{embedded_asset, AssetServer, Handle, HandleUntyped};
+/// # let mut app = App::new();
+/// // in `src/render/mod.rs` of the `my_plugin` crate
+/// embedded_asset!(app, "outline.wgsl");
+///
+/// let asset_server = app.world.resource::<AssetServer>();
+/// let shader: HandleUntyped = asset_server.load_untyped("embedded://my_plugin/render/outline.wgsl");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path: expr) => {{
+        let (embedded_path, source_path) = $crate::embedded_asset_paths(
+            env!("CARGO_CRATE_NAME"),
+            env!("CARGO_MANIFEST_DIR"),
+            file!(),
+            $path,
+        );
+        let asset_path = $crate::AssetPath::from(
+            format!(
+                "{}{}",
+                $crate::EMBEDDED_ASSET_PREFIX,
+                embedded_path.display()
+            )
+            .as_str(),
+        )
+        .to_owned();
+        $app.world
+            .resource::<$crate::AssetServer>()
+            .embedded_assets()
+            .insert_with_source(embedded_path, include_bytes!($path), source_path);
+        asset_path
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future;
+
+    #[test]
+    fn embedded_paths() {
+        let (embedded_path, source_path) = embedded_asset_paths(
+            "my_plugin",
+            "/work/my_plugin",
+            "crates/my_plugin/src/render/mod.rs",
+            "shaders/outline.wgsl",
+        );
+        assert_eq!(
+            embedded_path,
+            Path::new("my_plugin/render/shaders/outline.wgsl")
+        );
+        assert_eq!(
+            source_path,
+            Path::new("/work/my_plugin/src/render/shaders/outline.wgsl")
+        );
+    }
+
+    #[test]
+    fn route_embedded_paths() {
+        let asset_io = RoutedAssetIo::new(Box::<EmbeddedAssetIo>::default());
+        asset_io.embedded.insert("my_plugin/a.txt", b"embedded a");
+        asset_io
+            .embedded
+            .insert("my_plugin/shaders/b.wgsl", b"embedded b");
+
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("embedded://my_plugin/a.txt").unwrap(), b"embedded a");
+        assert!(matches!(
+            load("my_plugin/a.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("embedded://my_plugin/shaders")));
+        assert!(asset_io.is_file(Path::new("embedded://my_plugin/shaders/b.wgsl")));
+        let entries = asset_io
+            .read_directory(Path::new("embedded://my_plugin"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("embedded://my_plugin/a.txt"),
+                PathBuf::from("embedded://my_plugin/shaders")
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer, LoadState};
+use crate::{AssetIo, AssetIoError, AssetStream, AssetWriter, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::Res;
@@ -109,6 +109,17 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn open_stream(&self, path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        let full_path = self.root_path.join(path);
+        match File::open(&full_path) {
+            Ok(file) => Ok(Box::new(file)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(AssetIoError::NotFound(full_path))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -161,6 +172,70 @@ impl AssetIo for FileAssetIo {
                 }
             })
     }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            let mut changed = Vec::new();
+            let watcher = self.filesystem_watcher.read();
+            if let Some(ref watcher) = *watcher {
+                loop {
+                    let event = match watcher.receiver.try_recv() {
+                        Ok(result) => result.unwrap(),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            panic!("FilesystemWatcher disconnected.")
+                        }
+                    };
+                    if let notify::event::Event {
+                        kind: notify::event::EventKind::Modify(_),
+                        paths,
+                        ..
+                    } = event
+                    {
+                        for path in &paths {
+                            if let Ok(relative_path) = path.strip_prefix(&self.root_path) {
+                                changed.push(relative_path.to_owned());
+                            }
+                        }
+                    }
+                }
+            }
+            changed
+        }
+        #[cfg(not(feature = "filesystem_watcher"))]
+        Vec::new()
+    }
+}
+
+impl AssetWriter for FileAssetIo {
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            fs::remove_file(&full_path).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    AssetIoError::NotFound(full_path)
+                } else {
+                    e.into()
+                }
+            })
+        })
+    }
 }
 
 /// Watches for file changes in the local file system.
@@ -170,33 +245,26 @@ impl AssetIo for FileAssetIo {
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
-    let watcher = asset_io.filesystem_watcher.read();
-    if let Some(ref watcher) = *watcher {
-        loop {
-            let event = match watcher.receiver.try_recv() {
-                Ok(result) => result.unwrap(),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
-            };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in &paths {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
-                    }
-                }
-                changed.extend(paths);
+    for mut path in asset_server.source_asset_io().take_changed_paths() {
+        // a changed settings file reloads the asset source it belongs to
+        if path.extension() == Some("meta".as_ref()) {
+            path.set_extension("");
+        }
+        if !changed.insert(path.clone()) {
+            continue;
+        }
+
+        // files that were only read by the loaders of other asset sources, and aren't asset
+        // sources themselves, only reload their dependents
+        let dependents = asset_server.get_dependents(&path);
+        let is_source =
+            asset_server.get_load_state(AssetPath::new_ref(&path, None)) != LoadState::NotLoaded;
+        if is_source || dependents.is_empty() {
+            let _ = asset_server.load_untracked(path.into(), true);
+        }
+        for dependent in dependents {
+            if changed.insert(dependent.clone()) {
+                let _ = asset_server.load_untracked(dependent.into(), true);
             }
         }
     }
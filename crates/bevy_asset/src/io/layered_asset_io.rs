@@ -0,0 +1,335 @@
+use crate::{AssetIo, AssetIoError, AssetStream, AssetWriter, Metadata};
+use bevy_utils::{BoxedFuture, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+struct AssetLayer {
+    name: String,
+    priority: i32,
+    reader: Arc<dyn AssetIo>,
+    writer: Option<Arc<dyn AssetWriter>>,
+}
+
+/// An [`AssetIo`] made of several asset I/Os mounted as layers with priorities.
+///
+/// Each path is read from the layer with the highest priority that contains it, so a mod folder
+/// can override assets of a base archive, which itself overrides assets built into the game.
+/// Layers with equal priority are searched in the order they were mounted. Directories list the
+/// entries of all layers.
+///
+/// Layers can be mounted and unmounted while the [`AssetServer`](crate::AssetServer) is running,
+/// for example when a mod is enabled. Change watching is forwarded to every layer, and the
+/// changes reported by each layer trigger reloads.
+///
+/// ```
+/// # use bevy_asset::{LayeredAssetIo, PakAssetIo, PakCompression, PakWriter};
+/// # let mut writer = PakWriter::default();
+/// # writer.add_file("a.png", vec![], PakCompression::None).unwrap();
+/// # let mut archive = Vec::new();
+/// # writer.write_to(&mut archive).unwrap();
+/// # let base_archive = PakAssetIo::from_bytes(archive.clone()).unwrap();
+/// # let mod_archive = PakAssetIo::from_bytes(archive).unwrap();
+/// let asset_io = LayeredAssetIo::default();
+/// asset_io.mount("base", 0, base_archive);
+/// // assets of the mod replace the base assets at the same paths
+/// asset_io.mount("my_mod", 10, mod_archive);
+/// ```
+#[derive(Default)]
+pub struct LayeredAssetIo {
+    layers: RwLock<Vec<AssetLayer>>,
+}
+
+impl LayeredAssetIo {
+    /// Mounts `asset_io` as a layer named `name`, replacing any layer with the same name.
+    pub fn mount(&self, name: impl Into<String>, priority: i32, asset_io: impl AssetIo) {
+        self.insert_layer(AssetLayer {
+            name: name.into(),
+            priority,
+            reader: Arc::new(asset_io),
+            writer: None,
+        });
+    }
+
+    /// Mounts `asset_io` as a layer named `name` that can be written to, replacing any layer with
+    /// the same name.
+    ///
+    /// Writes go to the writable layer with the highest priority.
+    pub fn mount_writable<T: AssetIo + AssetWriter>(
+        &self,
+        name: impl Into<String>,
+        priority: i32,
+        asset_io: T,
+    ) {
+        let asset_io = Arc::new(asset_io);
+        self.insert_layer(AssetLayer {
+            name: name.into(),
+            priority,
+            reader: asset_io.clone(),
+            writer: Some(asset_io),
+        });
+    }
+
+    fn insert_layer(&self, layer: AssetLayer) {
+        let mut layers = self.layers.write();
+        layers.retain(|existing| existing.name != layer.name);
+        let index = layers.partition_point(|existing| existing.priority >= layer.priority);
+        layers.insert(index, layer);
+    }
+
+    /// Unmounts the layer named `name`. Returns `false` if there was no such layer.
+    pub fn unmount(&self, name: &str) -> bool {
+        let mut layers = self.layers.write();
+        let len = layers.len();
+        layers.retain(|layer| layer.name != name);
+        layers.len() != len
+    }
+
+    /// Returns the names of the mounted layers, from the highest priority to the lowest.
+    pub fn layer_names(&self) -> Vec<String> {
+        self.layers
+            .read()
+            .iter()
+            .map(|layer| layer.name.clone())
+            .collect()
+    }
+
+    /// Returns the name of the layer the file at `path` is read from.
+    pub fn find_layer(&self, path: &Path) -> Option<String> {
+        self.layers
+            .read()
+            .iter()
+            .find(|layer| layer.reader.is_file(path))
+            .map(|layer| layer.name.clone())
+    }
+
+    fn readers(&self) -> Vec<Arc<dyn AssetIo>> {
+        self.layers
+            .read()
+            .iter()
+            .map(|layer| layer.reader.clone())
+            .collect()
+    }
+
+    fn writer(&self) -> Option<Arc<dyn AssetWriter>> {
+        self.layers
+            .read()
+            .iter()
+            .find_map(|layer| layer.writer.clone())
+    }
+}
+
+impl AssetIo for LayeredAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            for reader in self.readers() {
+                match reader.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn open_stream(&self, path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        for reader in self.readers() {
+            match reader.open_stream(path) {
+                Err(AssetIoError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut found = false;
+        let mut entries = Vec::new();
+        let mut seen = HashSet::default();
+        for reader in self.readers() {
+            match reader.read_directory(path) {
+                Ok(layer_entries) => {
+                    found = true;
+                    entries.extend(layer_entries.filter(|entry| seen.insert(entry.clone())));
+                }
+                Err(AssetIoError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if found {
+            Ok(Box::new(entries.into_iter()))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        for reader in self.readers() {
+            match reader.get_metadata(path) {
+                Err(AssetIoError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        for reader in self.readers() {
+            reader.watch_path_for_changes(path)?;
+        }
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        for reader in self.readers() {
+            reader.watch_for_changes()?;
+        }
+        Ok(())
+    }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        self.readers()
+            .iter()
+            .flat_map(|reader| reader.take_changed_paths())
+            .collect()
+    }
+}
+
+impl AssetWriter for LayeredAssetIo {
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            match self.writer() {
+                Some(writer) => writer.write_path(path, bytes).await,
+                None => Err(no_writable_layer()),
+            }
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            match self.writer() {
+                Some(writer) => writer.remove_path(path).await,
+                None => Err(no_writable_layer()),
+            }
+        })
+    }
+}
+
+fn no_writable_layer() -> AssetIoError {
+    AssetIoError::Io(std::io::Error::new(
+        std::io::ErrorKind::PermissionDenied,
+        "no writable layer is mounted",
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PakAssetIo, PakCompression, PakWriter};
+    use futures_lite::future;
+    use std::io::Read;
+
+    fn archive(files: &[(&str, &str)]) -> PakAssetIo {
+        let mut writer = PakWriter::default();
+        for (path, contents) in files {
+            writer
+                .add_file(path, contents.as_bytes().to_vec(), PakCompression::None)
+                .unwrap();
+        }
+        let mut archive = Vec::new();
+        writer.write_to(&mut archive).unwrap();
+        PakAssetIo::from_bytes(archive).unwrap()
+    }
+
+    fn load(asset_io: &LayeredAssetIo, path: &str) -> Result<String, AssetIoError> {
+        future::block_on(asset_io.load_path(Path::new(path)))
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+    }
+
+    #[test]
+    fn layer_priorities() {
+        let asset_io = LayeredAssetIo::default();
+        asset_io.mount(
+            "base",
+            0,
+            archive(&[("a.txt", "base a"), ("textures/b.txt", "base b")]),
+        );
+        asset_io.mount("builtin", -10, archive(&[("c.txt", "builtin c")]));
+        asset_io.mount("mod", 10, archive(&[("textures/b.txt", "mod b")]));
+
+        assert_eq!(asset_io.layer_names(), vec!["mod", "base", "builtin"]);
+        assert_eq!(load(&asset_io, "a.txt").unwrap(), "base a");
+        assert_eq!(load(&asset_io, "textures/b.txt").unwrap(), "mod b");
+        assert_eq!(load(&asset_io, "c.txt").unwrap(), "builtin c");
+        assert!(matches!(
+            load(&asset_io, "d.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert_eq!(
+            asset_io.find_layer(Path::new("textures/b.txt")).as_deref(),
+            Some("mod")
+        );
+
+        let mut root = asset_io
+            .read_directory(Path::new(""))
+            .unwrap()
+            .collect::<Vec<_>>();
+        root.sort();
+        assert_eq!(
+            root,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("c.txt"),
+                PathBuf::from("textures")
+            ]
+        );
+
+        assert!(asset_io.unmount("mod"));
+        assert!(!asset_io.unmount("mod"));
+        assert_eq!(load(&asset_io, "textures/b.txt").unwrap(), "base b");
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    #[test]
+    fn write_to_writable_layer() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_io = LayeredAssetIo::default();
+        asset_io.mount("base", 0, archive(&[("a.txt", "base a")]));
+        assert!(future::block_on(asset_io.write_path(Path::new("a.txt"), b"")).is_err());
+
+        asset_io.mount_writable("user", 10, crate::FileAssetIo::new(dir.path(), false));
+        future::block_on(asset_io.write_path(Path::new("saves/a.txt"), b"saved a")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("saves/a.txt")).unwrap(),
+            "saved a"
+        );
+        assert_eq!(load(&asset_io, "saves/a.txt").unwrap(), "saved a");
+
+        // The file of the writable layer is streamed, and the one of the archive is read from memory
+        for (path, contents) in [("saves/a.txt", "saved a"), ("a.txt", "base a")] {
+            let mut streamed = String::new();
+            let mut stream = asset_io.open_stream(Path::new(path)).unwrap();
+            stream.read_to_string(&mut streamed).unwrap();
+            assert_eq!(streamed, contents);
+        }
+        assert!(matches!(
+            asset_io.open_stream(Path::new("b.txt")),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        future::block_on(asset_io.remove_path(Path::new("saves/a.txt"))).unwrap();
+        assert!(matches!(
+            load(&asset_io, "saves/a.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+}
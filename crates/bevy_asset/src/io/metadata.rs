@@ -47,12 +47,31 @@ impl TryFrom<std::fs::FileType> for FileType {
 #[derive(Debug, Clone)]
 pub struct Metadata {
     file_type: FileType,
+    size: Option<u64>,
 }
 
 impl Metadata {
     /// Creates new metadata information.
     pub fn new(file_type: FileType) -> Self {
-        Self { file_type }
+        Self {
+            file_type,
+            size: None,
+        }
+    }
+
+    /// Sets the size of the file in bytes.
+    #[must_use]
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Returns the size of the file in bytes, if known.
+    ///
+    /// The asset server uses it to report the loading progress of assets.
+    #[inline]
+    pub const fn size(&self) -> Option<u64> {
+        self.size
     }
 
     /// Returns the file type.
@@ -78,8 +97,10 @@ impl TryFrom<std::fs::Metadata> for Metadata {
     type Error = std::io::Error;
 
     fn try_from(metadata: std::fs::Metadata) -> Result<Self, Self::Error> {
+        let file_type: FileType = metadata.file_type().try_into()?;
         Ok(Self {
-            file_type: metadata.file_type().try_into()?,
+            file_type,
+            size: file_type.is_file().then_some(metadata.len()),
         })
     }
 }
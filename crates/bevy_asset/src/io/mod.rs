@@ -5,7 +5,10 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod embedded_asset_io;
+mod layered_asset_io;
 mod metadata;
+mod pak_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -14,13 +17,16 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use embedded_asset_io::*;
+pub use layered_asset_io::*;
 pub use metadata::*;
+pub use pak_asset_io::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
 use std::{
-    io,
+    io::{self, Read, Seek},
     path::{Path, PathBuf},
 };
 use thiserror::Error;
@@ -41,6 +47,11 @@ pub enum AssetIoError {
     PathWatchError(PathBuf),
 }
 
+/// A stream reading a file progressively, opened with [`AssetIo::open_stream`].
+pub trait AssetStream: Read + Seek + Send + Sync {}
+
+impl<T: Read + Seek + Send + Sync> AssetStream for T {}
+
 /// A storage provider for an [`AssetServer`].
 ///
 /// An asset I/O is the backend actually providing data for the asset loaders managed by the asset
@@ -56,6 +67,15 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Opens a stream reading the file at the provided path progressively, for large files which
+    /// shouldn't be loaded at once, like long music tracks.
+    ///
+    /// By default, the full file data is loaded and read from memory.
+    fn open_stream(&self, path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        let bytes = futures_lite::future::block_on(self.load_path(path))?;
+        Ok(Box::new(io::Cursor::new(bytes)))
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
@@ -71,6 +91,15 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Enables change tracking in this asset I/O.
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
 
+    /// Returns the paths of the files that were modified since the last call, relative to the
+    /// root of this asset I/O.
+    ///
+    /// Only asset I/Os that [watch for changes](Self::watch_for_changes) return any paths. The
+    /// [`AssetServer`](crate::AssetServer) reloads the assets at these paths.
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Returns `true` if the path is a directory.
     fn is_dir(&self, path: &Path) -> bool {
         self.get_metadata(path)
@@ -89,3 +118,19 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
 }
 
 impl_downcast!(AssetIo);
+
+/// A storage provider that assets can be written to, such as the processed asset folder.
+///
+/// This is the writing counterpart of [`AssetIo`].
+pub trait AssetWriter: Send + Sync + 'static {
+    /// Returns a future to write `bytes` to the file at the provided path, creating its parent
+    /// directories and replacing any existing file.
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>>;
+
+    /// Returns a future to remove the file at the provided path.
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>>;
+}
@@ -0,0 +1,562 @@
+use crate::{processor::hash_bytes, AssetIo, AssetIoError, FileType, Metadata};
+use bevy_utils::{BoxedFuture, HashSet};
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use parking_lot::Mutex;
+use std::{
+    convert::TryInto,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const PAK_MAGIC: &[u8; 4] = b"BPAK";
+const PAK_VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 20;
+
+/// How the data of a file in a pak archive is stored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PakCompression {
+    /// The data is stored as is.
+    None,
+    /// The data is compressed with zlib. Requires the `zlib` feature.
+    Zlib,
+}
+
+impl PakCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PakCompression::None => 0,
+            PakCompression::Zlib => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(PakCompression::None),
+            1 => Ok(PakCompression::Zlib),
+            _ => Err(invalid_data(format!("unknown pak compression {byte}"))),
+        }
+    }
+
+    fn compress(self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            PakCompression::None => Ok(bytes),
+            #[cfg(feature = "zlib")]
+            PakCompression::Zlib => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&bytes)?;
+                encoder.finish()
+            }
+            #[cfg(not(feature = "zlib"))]
+            PakCompression::Zlib => Err(zlib_disabled()),
+        }
+    }
+
+    fn decompress(self, bytes: Vec<u8>, size: usize) -> io::Result<Vec<u8>> {
+        match self {
+            PakCompression::None => Ok(bytes),
+            #[cfg(feature = "zlib")]
+            PakCompression::Zlib => {
+                let mut decompressed = Vec::with_capacity(size);
+                flate2::read::ZlibDecoder::new(&bytes[..]).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            #[cfg(not(feature = "zlib"))]
+            PakCompression::Zlib => {
+                let _ = size;
+                Err(zlib_disabled())
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "zlib"))]
+fn zlib_disabled() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "zlib compressed pak files require the `zlib` feature",
+    )
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Returns the path of an archive entry, with `/` separators and without `.` components.
+fn normalize_path(path: &Path) -> String {
+    let mut normalized = String::new();
+    for component in path.components() {
+        if let Component::Normal(name) = component {
+            if !normalized.is_empty() {
+                normalized.push('/');
+            }
+            normalized.push_str(&name.to_string_lossy());
+        }
+    }
+    normalized
+}
+
+#[derive(Debug, Clone)]
+struct PakEntry {
+    path_hash: u64,
+    path: String,
+    offset: u64,
+    stored_size: u64,
+    size: u64,
+    compression: PakCompression,
+}
+
+/// Builds a pak archive to be read by a [`PakAssetIo`].
+///
+/// A pak archive is a header, followed by the data of every file, followed by an index of the
+/// files sorted by the hash of their path.
+///
+/// ```
+/// # use bevy_asset::{PakCompression, PakWriter};
+/// let mut writer = PakWriter::default();
+/// writer
+///     .add_file("textures/player.png", vec![1, 2, 3], PakCompression::None)
+///     .unwrap();
+/// let mut archive = Vec::new();
+/// writer.write_to(&mut archive).unwrap();
+/// ```
+#[derive(Default)]
+pub struct PakWriter {
+    files: Vec<(String, Vec<u8>, u64, PakCompression)>,
+}
+
+impl PakWriter {
+    /// Adds a file at `path` in the archive, compressing `bytes` with `compression`.
+    ///
+    /// A file previously added at the same path is replaced.
+    pub fn add_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: Vec<u8>,
+        compression: PakCompression,
+    ) -> io::Result<&mut Self> {
+        let path = normalize_path(path.as_ref());
+        let size = bytes.len() as u64;
+        let stored = compression.compress(bytes)?;
+        self.files.retain(|(existing, ..)| *existing != path);
+        self.files.push((path, stored, size, compression));
+        Ok(self)
+    }
+
+    /// Adds every file in the `root` directory and its subdirectories, at their path relative to
+    /// `root`.
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    pub fn add_directory(
+        &mut self,
+        root: impl AsRef<Path>,
+        compression: PakCompression,
+    ) -> io::Result<&mut Self> {
+        let root = root.as_ref();
+        let mut directories = vec![root.to_owned()];
+        while let Some(directory) = directories.pop() {
+            for entry in std::fs::read_dir(directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else {
+                    let bytes = std::fs::read(&path)?;
+                    let relative_path = path.strip_prefix(root).unwrap();
+                    self.add_file(relative_path, bytes, compression)?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Writes the archive.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut entries = Vec::with_capacity(self.files.len());
+        let mut offset = HEADER_SIZE;
+        for (path, stored, size, compression) in &self.files {
+            entries.push(PakEntry {
+                path_hash: hash_bytes(path.as_bytes()),
+                path: path.clone(),
+                offset,
+                stored_size: stored.len() as u64,
+                size: *size,
+                compression: *compression,
+            });
+            offset += stored.len() as u64;
+        }
+
+        writer.write_all(PAK_MAGIC)?;
+        writer.write_all(&PAK_VERSION.to_le_bytes())?;
+        writer.write_all(&(entries.len() as u32).to_le_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        for (_, stored, ..) in &self.files {
+            writer.write_all(stored)?;
+        }
+
+        entries.sort_by(|a, b| (a.path_hash, &a.path).cmp(&(b.path_hash, &b.path)));
+        for entry in &entries {
+            writer.write_all(&entry.path_hash.to_le_bytes())?;
+            writer.write_all(&(entry.path.len() as u32).to_le_bytes())?;
+            writer.write_all(entry.path.as_bytes())?;
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.stored_size.to_le_bytes())?;
+            writer.write_all(&entry.size.to_le_bytes())?;
+            writer.write_all(&[entry.compression.to_byte()])?;
+        }
+        writer.flush()
+    }
+}
+
+enum PakData {
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    File(Mutex<std::fs::File>),
+    Memory(Vec<u8>),
+}
+
+/// I/O implementation for assets packed in a single archive written by a [`PakWriter`].
+///
+/// Shipping assets in an archive avoids exposing a loose asset folder, and loading them from a
+/// single file is usually faster than opening many small files. Only the index of the archive
+/// is kept in memory when it is [opened](Self::open) from a file; the data of each asset is read
+/// when the asset is loaded.
+pub struct PakAssetIo {
+    data: PakData,
+    entries: Vec<PakEntry>,
+    directories: HashSet<String>,
+}
+
+impl PakAssetIo {
+    /// Opens the archive at a path relative to the executable's directory.
+    ///
+    /// See [`FileAssetIo::get_base_path`](crate::FileAssetIo::get_base_path).
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let full_path = crate::FileAssetIo::get_base_path().join(path.as_ref());
+        let mut file = std::fs::File::open(&full_path).map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                AssetIoError::NotFound(full_path)
+            } else {
+                e.into()
+            }
+        })?;
+        let entries = read_index(&mut file)?;
+        Ok(Self::with_entries(PakData::File(Mutex::new(file)), entries))
+    }
+
+    /// Creates a `PakAssetIo` from an archive loaded in memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        let entries = read_index(&mut io::Cursor::new(&bytes))?;
+        Ok(Self::with_entries(PakData::Memory(bytes), entries))
+    }
+
+    fn with_entries(data: PakData, entries: Vec<PakEntry>) -> Self {
+        let mut directories = HashSet::default();
+        directories.insert(String::new());
+        for entry in &entries {
+            let mut path = entry.path.as_str();
+            while let Some(index) = path.rfind('/') {
+                path = &path[..index];
+                directories.insert(path.to_string());
+            }
+        }
+        Self {
+            data,
+            entries,
+            directories,
+        }
+    }
+
+    fn get_entry(&self, path: &str) -> Option<&PakEntry> {
+        let path_hash = hash_bytes(path.as_bytes());
+        let start = self
+            .entries
+            .partition_point(|entry| entry.path_hash < path_hash);
+        self.entries[start..]
+            .iter()
+            .take_while(|entry| entry.path_hash == path_hash)
+            .find(|entry| entry.path == path)
+    }
+
+    fn read_entry(&self, entry: &PakEntry) -> io::Result<Vec<u8>> {
+        let stored = match &self.data {
+            #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+            PakData::File(file) => {
+                let mut file = file.lock();
+                file.seek(SeekFrom::Start(entry.offset))?;
+                let mut stored = vec![0; entry.stored_size as usize];
+                file.read_exact(&mut stored)?;
+                stored
+            }
+            PakData::Memory(bytes) => bytes
+                // the entry was checked to lie within the archive when reading the index
+                .get(entry.offset as usize..(entry.offset + entry.stored_size) as usize)
+                .ok_or_else(|| invalid_data(format!("pak entry {} is truncated", entry.path)))?
+                .to_vec(),
+        };
+        entry.compression.decompress(stored, entry.size as usize)
+    }
+}
+
+/// The size of an index entry without its path.
+const ENTRY_SIZE: u64 = 37;
+/// The maximum compression ratio of zlib, used to bound the decompressed size of an entry.
+const MAX_ZLIB_RATIO: u64 = 1032;
+
+/// Reads the index of an archive, checking that every entry lies within the archive so that
+/// corrupt size fields can't cause huge allocations or out of bounds reads.
+fn read_index(reader: &mut (impl Read + Seek)) -> io::Result<Vec<PakEntry>> {
+    let archive_len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut header = [0; HEADER_SIZE as usize];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != PAK_MAGIC {
+        return Err(invalid_data("not a pak archive".to_string()));
+    }
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version != PAK_VERSION {
+        return Err(invalid_data(format!("unsupported pak version {version}")));
+    }
+    let entry_count = u32::from_le_bytes(header[8..12].try_into().unwrap());
+    let index_offset = u64::from_le_bytes(header[12..20].try_into().unwrap());
+    if !(HEADER_SIZE..=archive_len).contains(&index_offset)
+        || u64::from(entry_count) * ENTRY_SIZE > archive_len - index_offset
+    {
+        return Err(invalid_data("pak index is out of bounds".to_string()));
+    }
+
+    fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+        let mut bytes = [0; 8];
+        reader.read_exact(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    reader.seek(SeekFrom::Start(index_offset))?;
+    let mut entries = Vec::with_capacity(entry_count as usize);
+    for _ in 0..entry_count {
+        let path_hash = read_u64(reader)?;
+        let mut path_len = [0; 4];
+        reader.read_exact(&mut path_len)?;
+        let path_len = u64::from(u32::from_le_bytes(path_len));
+        if path_len > archive_len - reader.stream_position()? {
+            return Err(invalid_data("pak entry path is out of bounds".to_string()));
+        }
+        let mut path = vec![0; path_len as usize];
+        reader.read_exact(&mut path)?;
+        let path = String::from_utf8(path)
+            .map_err(|_| invalid_data("pak entry path is not valid UTF-8".to_string()))?;
+        let offset = read_u64(reader)?;
+        let stored_size = read_u64(reader)?;
+        let size = read_u64(reader)?;
+        let mut compression = [0; 1];
+        reader.read_exact(&mut compression)?;
+        let compression = PakCompression::from_byte(compression[0])?;
+
+        let in_bounds = offset >= HEADER_SIZE
+            && matches!(offset.checked_add(stored_size), Some(end) if end <= index_offset);
+        let valid_size = match compression {
+            PakCompression::None => size == stored_size,
+            PakCompression::Zlib => match stored_size.checked_mul(MAX_ZLIB_RATIO) {
+                Some(max_size) => size <= max_size,
+                None => true,
+            },
+        };
+        if !in_bounds || !valid_size {
+            return Err(invalid_data(format!("pak entry {path} is out of bounds")));
+        }
+
+        entries.push(PakEntry {
+            path_hash,
+            path,
+            offset,
+            stored_size,
+            size,
+            compression,
+        });
+    }
+    Ok(entries)
+}
+
+impl AssetIo for PakAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self
+                .get_entry(&normalize_path(path))
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            Ok(self.read_entry(entry)?)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let directory = normalize_path(path);
+        if !self.directories.contains(&directory) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        let is_child = |child: &str| match child.rsplit_once('/') {
+            Some((parent, _)) => parent == directory,
+            None => directory.is_empty(),
+        };
+        let children = self
+            .entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .chain(self.directories.iter().map(String::as_str))
+            .filter(|child| !child.is_empty() && is_child(child))
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let normalized = normalize_path(path);
+        if self.directories.contains(&normalized) {
+            Ok(Metadata::new(FileType::Directory))
+        } else if let Some(entry) = self.get_entry(&normalized) {
+            Ok(Metadata::new(FileType::File).with_size(entry.size))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        bevy_log::warn!("Watching for changes is not supported for pak archives");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future;
+
+    fn create_archive() -> PakAssetIo {
+        let mut writer = PakWriter::default();
+        writer
+            .add_file(
+                "textures/player.png",
+                b"player".to_vec(),
+                PakCompression::None,
+            )
+            .unwrap()
+            .add_file(
+                "textures/ui/button.png",
+                b"button".to_vec(),
+                PakCompression::None,
+            )
+            .unwrap()
+            .add_file(
+                "./config.ron",
+                b"(volume: 1.0)".to_vec(),
+                PakCompression::None,
+            )
+            .unwrap();
+        let mut archive = Vec::new();
+        writer.write_to(&mut archive).unwrap();
+        PakAssetIo::from_bytes(archive).unwrap()
+    }
+
+    #[test]
+    fn load_files() {
+        let asset_io = create_archive();
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path)));
+
+        assert_eq!(load("textures/player.png").unwrap(), b"player");
+        assert_eq!(load("textures/ui/button.png").unwrap(), b"button");
+        assert_eq!(load("config.ron").unwrap(), b"(volume: 1.0)");
+        assert!(matches!(
+            load("textures/enemy.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn directories() {
+        let asset_io = create_archive();
+        let read_directory = |path: &str| {
+            let mut children = asset_io
+                .read_directory(Path::new(path))
+                .unwrap()
+                .collect::<Vec<_>>();
+            children.sort();
+            children
+        };
+
+        assert_eq!(
+            read_directory(""),
+            vec![PathBuf::from("config.ron"), PathBuf::from("textures")]
+        );
+        assert_eq!(
+            read_directory("textures"),
+            vec![
+                PathBuf::from("textures/player.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+        assert!(asset_io.is_dir(Path::new("textures/ui")));
+        assert!(asset_io.is_file(Path::new("textures/ui/button.png")));
+        assert!(asset_io.read_directory(Path::new("sounds")).is_err());
+    }
+
+    #[test]
+    fn invalid_archive() {
+        assert!(PakAssetIo::from_bytes(b"not an archive".to_vec()).is_err());
+        assert!(
+            PakAssetIo::from_bytes(b"BPAK\x02\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".to_vec()).is_err()
+        );
+    }
+
+    #[test]
+    fn corrupt_index() {
+        let mut writer = PakWriter::default();
+        writer
+            .add_file("data.bin", vec![7; 16], PakCompression::None)
+            .unwrap();
+        let mut archive = Vec::new();
+        writer.write_to(&mut archive).unwrap();
+        // the index starts with the path hash and path of the only entry
+        let entry_fields = HEADER_SIZE as usize + 16 + 8 + 4 + "data.bin".len();
+        let corrupt = |field: usize, value: u64| {
+            let mut archive = archive.clone();
+            let start = entry_fields + field * 8;
+            archive[start..start + 8].copy_from_slice(&value.to_le_bytes());
+            PakAssetIo::from_bytes(archive)
+        };
+
+        assert!(corrupt(0, 0).is_err());
+        assert!(corrupt(0, u64::MAX).is_err());
+        assert!(corrupt(1, u64::MAX).is_err());
+        assert!(corrupt(1, 17).is_err());
+        assert!(corrupt(2, u64::MAX).is_err());
+        assert!(corrupt(1, 16).is_ok());
+
+        let mut huge_index = archive.clone();
+        huge_index[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PakAssetIo::from_bytes(huge_index).is_err());
+        let mut huge_path = archive.clone();
+        huge_path[entry_fields - "data.bin".len() - 4..entry_fields - "data.bin".len()]
+            .copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PakAssetIo::from_bytes(huge_path).is_err());
+        assert!(PakAssetIo::from_bytes(archive[..archive.len() - 1].to_vec()).is_err());
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn zlib_compression() {
+        let mut writer = PakWriter::default();
+        writer
+            .add_file("data.bin", vec![7; 4096], PakCompression::Zlib)
+            .unwrap();
+        let mut archive = Vec::new();
+        writer.write_to(&mut archive).unwrap();
+        assert!(archive.len() < 4096);
+
+        let asset_io = PakAssetIo::from_bytes(archive).unwrap();
+        let bytes = future::block_on(asset_io.load_path(Path::new("data.bin"))).unwrap();
+        assert_eq!(bytes, vec![7; 4096]);
+    }
+}
@@ -1,9 +1,10 @@
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{AssetIo, AssetIoError, AssetStream, Metadata};
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use js_sys::Uint8Array;
 use std::{
     convert::TryFrom,
+    io,
     path::{Path, PathBuf},
 };
 use wasm_bindgen::JsCast;
@@ -15,6 +16,7 @@ use web_sys::Response;
 /// Implementation details:
 ///
 /// - `load_path` makes [fetch()] requests.
+/// - `open_stream` always returns an error, as the requests can't be awaited synchronously.
 /// - `read_directory` always returns an empty iterator.
 /// - `get_metadata` will always return an error.
 /// - Watching for changes is not supported. The watcher methods will do nothing.
@@ -48,6 +50,14 @@ impl AssetIo for WasmAssetIo {
         })
     }
 
+    fn open_stream(&self, _path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "streaming is not supported in WASM",
+        )
+        .into())
+    }
+
     fn read_directory(
         &self,
         _path: &Path,
@@ -25,6 +25,7 @@ mod info;
 mod io;
 mod loader;
 mod path;
+mod processor;
 mod reflect;
 
 /// The `bevy_asset` prelude.
@@ -44,10 +45,13 @@ pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use bevy_ecs::system::Res;
 
 /// The names of asset stages in an [`App`] schedule.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
@@ -58,6 +62,22 @@ pub enum AssetStage {
     AssetEvents,
 }
 
+/// How an [`AssetPlugin`] treats the [asset processors](AssetProcessor) of an [`App`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AssetMode {
+    /// Assets are loaded from their sources, processors are ignored.
+    #[default]
+    Unprocessed,
+    /// Assets are loaded from the processed asset folder, falling back to their sources when no
+    /// processed version exists. Use this when shipping processed assets.
+    Processed,
+    /// Like [`AssetMode::Processed`], but outdated sources are processed first during
+    /// [`StartupStage::PreStartup`](bevy_app::StartupStage::PreStartup).
+    ///
+    /// Processing is not supported on the wasm32 arch nor android os.
+    Processing,
+}
+
 /// Adds support for Assets to an App.
 ///
 /// Assets are typed collections with change tracking, which are added as App Resources. Examples of
@@ -66,16 +86,23 @@ pub enum AssetStage {
 pub struct AssetPlugin {
     /// The base folder where assets are loaded from, relative to the executable.
     pub asset_folder: String,
+    /// The folder where processed assets are written to and loaded from, relative to the
+    /// executable. Only used if `mode` isn't [`AssetMode::Unprocessed`].
+    pub processed_asset_folder: String,
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: bool,
+    /// Whether assets are processed and loaded from the processed asset folder.
+    pub mode: AssetMode,
 }
 
 impl Default for AssetPlugin {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            processed_asset_folder: "imported_assets".to_string(),
             watch_for_changes: false,
+            mode: AssetMode::Unprocessed,
         }
     }
 }
@@ -85,23 +112,66 @@ impl AssetPlugin {
     ///
     /// This is useful when providing a custom `AssetIo` instance that needs to
     /// delegate to the default `AssetIo` for the platform.
+    ///
+    /// Unless `mode` is [`AssetMode::Unprocessed`], this is a [`ProcessedAssetIo`] reading from
+    /// the processed asset folder and the asset folder.
     pub fn create_platform_default_asset_io(&self) -> Box<dyn AssetIo> {
+        let source = self.create_folder_asset_io(&self.asset_folder, self.watch_for_changes);
+        match self.mode {
+            AssetMode::Unprocessed => source,
+            AssetMode::Processed | AssetMode::Processing => Box::new(ProcessedAssetIo::new(
+                self.create_folder_asset_io(&self.processed_asset_folder, false),
+                source,
+            )),
+        }
+    }
+
+    #[cfg_attr(
+        any(target_arch = "wasm32", target_os = "android"),
+        allow(unused_variables)
+    )]
+    fn create_folder_asset_io(&self, folder: &str, watch_for_changes: bool) -> Box<dyn AssetIo> {
         #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-        let source = FileAssetIo::new(&self.asset_folder, self.watch_for_changes);
+        let source = FileAssetIo::new(folder, watch_for_changes);
         #[cfg(target_arch = "wasm32")]
-        let source = WasmAssetIo::new(&self.asset_folder);
+        let source = WasmAssetIo::new(folder);
         #[cfg(target_os = "android")]
-        let source = AndroidAssetIo::new(&self.asset_folder);
+        let source = AndroidAssetIo::new(folder);
 
         Box::new(source)
     }
 }
 
+/// Processes the sources of the asset folder with the [`AssetProcessors`] of the app, see
+/// [`AssetMode::Processing`].
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+fn process_assets_system(
+    asset_folder: &str,
+    processed_asset_folder: &str,
+) -> impl FnMut(Option<Res<AssetProcessors>>) {
+    let source = FileAssetIo::new(asset_folder, false);
+    let destination = FileAssetIo::get_base_path().join(processed_asset_folder);
+    move |processors: Option<Res<AssetProcessors>>| {
+        let processors = match processors {
+            Some(processors) => processors,
+            None => return,
+        };
+        match processors.process_folder(&source, &destination) {
+            Ok(count) => bevy_log::info!("Processed {} assets", count),
+            Err(error) => bevy_log::error!("Failed to process assets: {}", error),
+        }
+    }
+}
+
 impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
         if !app.world.contains_resource::<AssetServer>() {
             let source = self.create_platform_default_asset_io();
             let asset_server = AssetServer::with_boxed_io(source);
+            if self.watch_for_changes {
+                // the default asset I/O already watches for changes
+                asset_server.embedded_assets().watch_for_changes().unwrap();
+            }
             app.insert_resource(asset_server);
         }
 
@@ -126,5 +196,15 @@ impl Plugin for AssetPlugin {
             all(not(target_arch = "wasm32"), not(target_os = "android"))
         ))]
         app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
+
+        if self.mode == AssetMode::Processing {
+            #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+            panic!("Processing assets cannot be used on wasm32 / android targets");
+            #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+            app.add_startup_system_to_stage(
+                bevy_app::StartupStage::PreStartup,
+                process_assets_system(&self.asset_folder, &self.processed_asset_folder),
+            );
+        }
     }
 }
@@ -9,7 +9,10 @@ use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 
 /// A loader for an asset source.
 ///
@@ -125,6 +128,8 @@ pub struct LoadContext<'a> {
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<Vec<u8>>,
+    pub(crate) read_paths: Mutex<Vec<PathBuf>>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -140,6 +145,8 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             version,
             path,
+            settings: None,
+            read_paths: Default::default(),
         }
     }
 
@@ -148,6 +155,60 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the settings of the asset source being loaded, read from its settings file.
+    ///
+    /// The settings file of `path/to/image.png` is `path/to/image.png.meta`, see
+    /// [`settings_path`]. Settings are stored in the RON format, and `S::default()` is returned
+    /// if the asset source has no settings file.
+    ///
+    /// ```
+    /// # use bevy_asset::{AssetLoader, BoxedFuture, LoadContext};
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize, Default)]
+    /// #[serde(default)]
+    /// struct TextSettings {
+    ///     uppercase: bool,
+    /// }
+    ///
+    /// struct TextLoader;
+    ///
+    /// impl AssetLoader for TextLoader {
+    ///     fn load<'a>(
+    ///         &'a self,
+    ///         bytes: &'a [u8],
+    ///         load_context: &'a mut LoadContext,
+    ///     ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+    ///         Box::pin(async move {
+    ///             // `text.txt.meta` may contain `(uppercase: true)`
+    ///             let settings: TextSettings = load_context.settings()?;
+    ///             // ...
+    ///             Ok(())
+    ///         })
+    ///     }
+    ///
+    ///     fn extensions(&self) -> &[&str] {
+    ///         &["txt"]
+    ///     }
+    /// }
+    /// ```
+    pub fn settings<S: DeserializeOwned + Default>(&self) -> Result<S, AssetSettingsError> {
+        match &self.settings {
+            Some(settings) => ron::de::from_bytes(settings).map_err(|error| AssetSettingsError {
+                path: settings_path(self.path),
+                error,
+            }),
+            None => Ok(S::default()),
+        }
+    }
+
+    /// Gets the raw contents of the settings file of the asset source being loaded, if it has
+    /// one.
+    ///
+    /// This can be used by loaders storing their settings in another format than RON.
+    pub fn settings_bytes(&self) -> Option<&[u8]> {
+        self.settings.as_deref()
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -166,6 +227,19 @@ impl<'a> LoadContext<'a> {
         self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
     }
 
+    /// Gets an asset that was already set in this load context.
+    ///
+    /// This lets a loader inspect the sub-assets referred to by another asset before it is added,
+    /// such as the meshes of a scene.
+    pub fn get_asset<T: Asset>(&self, handle: &Handle<T>) -> Option<&T> {
+        self.labeled_assets.iter().find_map(|(label, asset)| {
+            if HandleId::from(AssetPath::new_ref(self.path, label.as_deref())) != handle.id() {
+                return None;
+            }
+            asset.value.as_ref()?.downcast_ref::<T>()
+        })
+    }
+
     /// Gets a handle to an asset of type `T` from its id.
     pub fn get_handle<I: Into<HandleId>, T: Asset>(&self, id: I) -> Handle<T> {
         Handle::strong(id.into(), self.ref_change_channel.sender.clone())
@@ -173,8 +247,13 @@ impl<'a> LoadContext<'a> {
 
     /// Reads the contents of the file at the specified path through the [`AssetIo`] associated
     /// with this context.
+    ///
+    /// The asset source being loaded is reloaded when the file changes, if the
+    /// [`AssetServer`] watches for changes.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
-        self.asset_io.load_path(path.as_ref()).await
+        let path = path.as_ref();
+        self.read_paths.lock().push(path.to_owned());
+        self.asset_io.load_path(path).await
     }
 
     /// Generates metadata for the assets managed by this load context.
@@ -196,6 +275,25 @@ impl<'a> LoadContext<'a> {
     }
 }
 
+/// Returns the path of the settings file of the asset source at `path`, which is `path` with a
+/// `.meta` extension appended.
+///
+/// Settings files are read by the [`AssetServer`] and passed to the [`AssetLoader`] through the
+/// [`LoadContext`], see [`LoadContext::settings`].
+pub fn settings_path(path: &Path) -> PathBuf {
+    let mut settings_path = path.to_owned().into_os_string();
+    settings_path.push(".meta");
+    PathBuf::from(settings_path)
+}
+
+/// An error that occurs when the settings file of an asset source can't be parsed.
+#[derive(Error, Debug)]
+#[error("failed to parse asset settings {path}: {error}")]
+pub struct AssetSettingsError {
+    path: PathBuf,
+    error: ron::error::SpannedError,
+}
+
 /// The result of loading an asset of type `T`.
 #[derive(Debug)]
 pub struct AssetResult<T> {
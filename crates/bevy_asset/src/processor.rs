@@ -0,0 +1,399 @@
+use crate::{AssetIo, AssetIoError, AssetStream, Metadata};
+use anyhow::Error;
+use bevy_ecs::system::Resource;
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// A processor for asset sources.
+///
+/// Processors run ahead of time, for example while developing the game, and transform the bytes
+/// of asset sources into a form that is faster to load or better suited to the target platform,
+/// such as compressed textures or optimized meshes. The processed bytes are written to the
+/// processed asset folder along with a [`ProcessedAssetMeta`] file, and are loaded in place of
+/// the sources by a [`ProcessedAssetIo`].
+///
+/// The processed bytes are still loaded by the [`AssetLoader`](crate::AssetLoader) matching the
+/// extension of the source path, so that loader must support the processed format.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the bytes of an asset source in an asynchronous closure.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this asset processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the version of this processor's output.
+    ///
+    /// Sources processed by a different version are processed again, so this should be bumped
+    /// whenever the output of the processor changes.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// An asynchronous context where an asset source is processed.
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    asset_io: &'a dyn AssetIo,
+}
+
+impl<'a> ProcessContext<'a> {
+    /// Gets the source path for this process context.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the contents of the source file at the specified path through the [`AssetIo`]
+    /// associated with this context.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.asset_io.load_path(path.as_ref()).await
+    }
+
+    /// Gets the source asset I/O associated with this process context.
+    pub fn asset_io(&self) -> &dyn AssetIo {
+        self.asset_io
+    }
+}
+
+/// Metadata written next to each processed asset, used to skip sources that are up to date.
+///
+/// The metadata of `path/to/asset.png` is stored in `path/to/asset.png.processed.meta`, to keep
+/// the [settings file](crate::settings_path) of the asset free for its loader.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedAssetMeta {
+    /// The type name of the processor that produced the asset.
+    pub processor: String,
+    /// The [version](AssetProcessor::version) of the processor that produced the asset.
+    pub processor_version: u32,
+    /// A hash of the source bytes the asset was produced from.
+    pub source_hash: u64,
+}
+
+/// Errors that occur while processing assets.
+#[derive(Error, Debug)]
+pub enum AssetProcessError {
+    /// Encountered an error while reading an asset source.
+    #[error("encountered an error while reading an asset source: {0}")]
+    AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while writing a processed asset.
+    #[error("encountered an error while writing processed asset {path}: {error}")]
+    WriteError {
+        /// The path of the processed asset.
+        path: PathBuf,
+        /// The underlying error.
+        error: std::io::Error,
+    },
+
+    /// An asset processor failed.
+    #[error("encountered an error while processing asset {path}: {error}")]
+    ProcessorError {
+        /// The source path of the asset.
+        path: PathBuf,
+        /// The error returned by the processor.
+        error: Error,
+    },
+}
+
+#[cfg_attr(any(target_arch = "wasm32", target_os = "android"), allow(dead_code))]
+struct ProcessorEntry {
+    type_name: &'static str,
+    processor: Arc<dyn AssetProcessor>,
+}
+
+/// The [`AssetProcessor`]s of an app, see
+/// [`AddAsset::add_asset_processor`](crate::AddAsset::add_asset_processor).
+#[derive(Resource, Default)]
+pub struct AssetProcessors {
+    processors: Vec<ProcessorEntry>,
+    extension_to_processor_index: HashMap<String, usize>,
+}
+
+impl AssetProcessors {
+    /// Adds the provided asset processor.
+    ///
+    /// If `processor` has one or more supported extensions in conflict with processors that came
+    /// before it, it will replace them.
+    pub fn add<T: AssetProcessor>(&mut self, processor: T) {
+        let processor_index = self.processors.len();
+        for extension in processor.extensions() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), processor_index);
+        }
+        self.processors.push(ProcessorEntry {
+            type_name: std::any::type_name::<T>(),
+            processor: Arc::new(processor),
+        });
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn get_path_processor(&self, path: &Path) -> Option<&ProcessorEntry> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(&index) = self.extension_to_processor_index.get(ext) {
+                return Some(&self.processors[index]);
+            }
+        }
+        None
+    }
+
+    /// Processes every source in `source` that has a matching processor, writing the results and
+    /// their [`ProcessedAssetMeta`] files to the `destination` directory.
+    ///
+    /// Sources whose processed version is up to date are skipped. Returns the number of sources
+    /// that were processed.
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    pub fn process_folder(
+        &self,
+        source: &dyn AssetIo,
+        destination: &Path,
+    ) -> Result<usize, AssetProcessError> {
+        let mut processed = 0;
+        let mut directories = vec![PathBuf::new()];
+        while let Some(directory) = directories.pop() {
+            for path in source.read_directory(&directory)? {
+                if source.is_dir(&path) {
+                    directories.push(path);
+                } else if let Some(entry) = self.get_path_processor(&path) {
+                    if self.process_file(entry, source, &path, destination)? {
+                        processed += 1;
+                    }
+                }
+            }
+        }
+        Ok(processed)
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn process_file(
+        &self,
+        entry: &ProcessorEntry,
+        source: &dyn AssetIo,
+        path: &Path,
+        destination: &Path,
+    ) -> Result<bool, AssetProcessError> {
+        use futures_lite::future;
+        use std::fs;
+
+        let bytes = future::block_on(source.load_path(path))?;
+        let meta = ProcessedAssetMeta {
+            processor: entry.type_name.to_string(),
+            processor_version: entry.processor.version(),
+            source_hash: hash_bytes(&bytes),
+        };
+
+        let output_path = destination.join(path);
+        let mut meta_path = output_path.clone().into_os_string();
+        meta_path.push(".processed.meta");
+        let meta_path = PathBuf::from(meta_path);
+        // unreadable or outdated metadata just means the asset is processed again
+        let existing_meta = fs::read_to_string(&meta_path)
+            .ok()
+            .and_then(|meta| ron::from_str::<ProcessedAssetMeta>(&meta).ok());
+        if existing_meta.as_ref() == Some(&meta) && output_path.is_file() {
+            return Ok(false);
+        }
+
+        let mut process_context = ProcessContext {
+            path,
+            asset_io: source,
+        };
+        let output = future::block_on(entry.processor.process(&bytes, &mut process_context))
+            .map_err(|error| AssetProcessError::ProcessorError {
+                path: path.to_owned(),
+                error,
+            })?;
+
+        let write = |path: &Path, contents: &[u8]| {
+            path.parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(path, contents))
+                .map_err(|error| AssetProcessError::WriteError {
+                    path: path.to_owned(),
+                    error,
+                })
+        };
+        let meta = ron::ser::to_string_pretty(&meta, Default::default())
+            .expect("ProcessedAssetMeta should be serializable");
+        write(&output_path, &output)?;
+        write(&meta_path, meta.as_bytes())?;
+        Ok(true)
+    }
+}
+
+// FNV-1a, which unlike the std hashers is stable across Rust versions and platforms
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// An [`AssetIo`] that loads processed assets, falling back to their sources.
+///
+/// Files are read from the `processed` asset I/O when they exist there, and from the `source`
+/// asset I/O otherwise, so sources without an [`AssetProcessor`] are loaded as is. Directories,
+/// metadata and change watching all come from the `source` asset I/O.
+pub struct ProcessedAssetIo {
+    processed: Box<dyn AssetIo>,
+    source: Box<dyn AssetIo>,
+}
+
+impl ProcessedAssetIo {
+    /// Creates a new `ProcessedAssetIo` from the asset I/Os of the processed assets and their
+    /// sources.
+    pub fn new(processed: Box<dyn AssetIo>, source: Box<dyn AssetIo>) -> Self {
+        Self { processed, source }
+    }
+
+    /// Returns the asset I/O of the processed assets.
+    pub fn processed(&self) -> &dyn AssetIo {
+        &*self.processed
+    }
+
+    /// Returns the asset I/O of the asset sources.
+    pub fn source(&self) -> &dyn AssetIo {
+        &*self.source
+    }
+}
+
+impl AssetIo for ProcessedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            match self.processed.load_path(path).await {
+                Err(AssetIoError::NotFound(_)) => self.source.load_path(path).await,
+                result => result,
+            }
+        })
+    }
+
+    fn open_stream(&self, path: &Path) -> Result<Box<dyn AssetStream>, AssetIoError> {
+        match self.processed.open_stream(path) {
+            Err(AssetIoError::NotFound(_)) => self.source.open_stream(path),
+            result => result,
+        }
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.source.read_directory(path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        self.source.get_metadata(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.source.watch_path_for_changes(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.source.watch_for_changes()
+    }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        self.source.take_changed_paths()
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod test {
+    use super::*;
+    use crate::FileAssetIo;
+    use futures_lite::future;
+    use std::fs;
+
+    struct UppercaseProcessor(u32);
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn version(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn process_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("assets");
+        let processed_path = dir.path().join("imported_assets");
+        fs::create_dir_all(source_path.join("text")).unwrap();
+        fs::write(source_path.join("text/hello.txt"), "hello").unwrap();
+        fs::write(source_path.join("data.bin"), "data").unwrap();
+        let source = FileAssetIo::new(&source_path, false);
+
+        let mut processors = AssetProcessors::default();
+        processors.add(UppercaseProcessor(0));
+
+        assert_eq!(
+            processors.process_folder(&source, &processed_path).unwrap(),
+            1
+        );
+        assert_eq!(
+            fs::read_to_string(processed_path.join("text/hello.txt")).unwrap(),
+            "HELLO"
+        );
+        assert!(processed_path
+            .join("text/hello.txt.processed.meta")
+            .is_file());
+        assert!(!processed_path.join("data.bin").exists());
+
+        // up to date sources are skipped
+        assert_eq!(
+            processors.process_folder(&source, &processed_path).unwrap(),
+            0
+        );
+
+        fs::write(source_path.join("text/hello.txt"), "hello again").unwrap();
+        assert_eq!(
+            processors.process_folder(&source, &processed_path).unwrap(),
+            1
+        );
+        assert_eq!(
+            fs::read_to_string(processed_path.join("text/hello.txt")).unwrap(),
+            "HELLO AGAIN"
+        );
+
+        // a new processor version processes everything again
+        let mut processors = AssetProcessors::default();
+        processors.add(UppercaseProcessor(1));
+        assert_eq!(
+            processors.process_folder(&source, &processed_path).unwrap(),
+            1
+        );
+
+        let asset_io = ProcessedAssetIo::new(
+            Box::new(FileAssetIo::new(&processed_path, false)),
+            Box::new(source),
+        );
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path))).unwrap();
+        assert_eq!(load("text/hello.txt"), b"HELLO AGAIN");
+        assert_eq!(load("data.bin"), b"data");
+    }
+}
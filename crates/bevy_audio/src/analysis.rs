@@ -0,0 +1,292 @@
+use crate::{AudioBus, AudioBuses, AudioSink};
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_ecs::system::{Local, Res, ResMut, Resource};
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use std::{f32::consts::TAU, sync::Arc};
+
+/// The number of samples analyzed by the FFT of the spectrum, a power of two.
+const SPECTRUM_SAMPLES: usize = 1024;
+
+/// The analysis of the sound of an [`AudioBus`] or an [`AudioSink`] over the last frame.
+#[derive(Clone, Debug, Default)]
+pub struct SoundAnalysis {
+    /// The root mean square of the samples played since the last frame
+    pub rms: f32,
+    /// The highest absolute value of the samples played since the last frame
+    pub peak: f32,
+    /// The amplitude of the frequencies of the last samples played, in bands of equal width from
+    /// 0 Hz to half the sample rate. A sine wave of amplitude `1.0` has an amplitude close to
+    /// `1.0` in its band.
+    pub spectrum: Vec<f32>,
+    /// The sample rate of the sound
+    pub sample_rate: u32,
+}
+
+impl SoundAnalysis {
+    /// The width of the bands of the [`spectrum`](Self::spectrum), in hertz.
+    pub fn band_width(&self) -> f32 {
+        self.sample_rate as f32 / (2 * self.spectrum.len()).max(1) as f32
+    }
+
+    /// The amplitude of the band of the [`spectrum`](Self::spectrum) containing the frequency, in
+    /// hertz.
+    pub fn amplitude(&self, frequency: f32) -> f32 {
+        let band = (frequency / self.band_width()) as usize;
+        self.spectrum.get(band).copied().unwrap_or(0.0)
+    }
+
+    /// The mean amplitude of the bands of the [`spectrum`](Self::spectrum) between two
+    /// frequencies, in hertz, like the bass of a song.
+    pub fn mean_amplitude(&self, low: f32, high: f32) -> f32 {
+        let band_width = self.band_width();
+        let low = ((low / band_width) as usize).min(self.spectrum.len());
+        let high = ((high / band_width) as usize + 1).clamp(low, self.spectrum.len());
+        let bands = &self.spectrum[low..high];
+        if bands.is_empty() {
+            0.0
+        } else {
+            bands.iter().sum::<f32>() / bands.len() as f32
+        }
+    }
+}
+
+/// Use this [`Resource`] to visualize the sound of the [`AudioBus`]es and of the analyzed
+/// [`AudioSink`]s, or to react to it, from their [`SoundAnalysis`] updated every frame.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_audio::{AudioAnalysis, AudioBus};
+/// fn pulse_on_bass_system(audio_analysis: Res<AudioAnalysis>) {
+///     let music = audio_analysis.bus(AudioBus::Music);
+///     if music.mean_amplitude(20.0, 150.0) > 0.1 {
+///         println!("Boom");
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct AudioAnalysis {
+    /// The analysis of the buses, in the order of [`AudioBus::ALL`]
+    buses: Vec<SoundAnalysis>,
+    sinks: HashMap<HandleId, SoundAnalysis>,
+}
+
+impl Default for AudioAnalysis {
+    fn default() -> Self {
+        Self {
+            buses: vec![SoundAnalysis::default(); AudioBus::ALL.len()],
+            sinks: HashMap::default(),
+        }
+    }
+}
+
+impl AudioAnalysis {
+    /// Gets the analysis of the sound of the bus, after its effects and its volume.
+    pub fn bus(&self, bus: AudioBus) -> &SoundAnalysis {
+        &self.buses[bus as usize]
+    }
+
+    /// Gets the analysis of the sound of the sink, after its effects, if it is
+    /// [analyzed](AudioSink::set_analyzed).
+    pub fn sink(&self, sink: &Handle<AudioSink>) -> Option<&SoundAnalysis> {
+        self.sinks.get(&sink.id())
+    }
+}
+
+/// The samples of a sound kept for its analysis, written by the audio thread.
+struct TapBuffer {
+    enabled: bool,
+    sample_rate: u32,
+    /// The last samples, downmixed to mono, in a ring buffer
+    samples: Vec<f32>,
+    next: usize,
+    sum_squares: f32,
+    count: usize,
+    peak: f32,
+}
+
+/// A tap on the sound of a bus or a sink, collecting its samples for its [`SoundAnalysis`].
+#[derive(Clone)]
+pub(crate) struct AnalysisTap {
+    buffer: Arc<Mutex<TapBuffer>>,
+}
+
+impl AnalysisTap {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(TapBuffer {
+                enabled,
+                sample_rate: 0,
+                samples: vec![0.0; SPECTRUM_SAMPLES],
+                next: 0,
+                sum_squares: 0.0,
+                count: 0,
+                peak: 0.0,
+            })),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.buffer.lock().enabled
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.buffer.lock().enabled = enabled;
+    }
+
+    /// Collects a block of interleaved samples, on the audio thread.
+    pub(crate) fn write(&self, samples: &[f32], channels: u16, sample_rate: u32) {
+        let mut buffer = self.buffer.lock();
+        if !buffer.enabled {
+            return;
+        }
+        buffer.sample_rate = sample_rate;
+        for frame in samples.chunks(channels.max(1) as usize) {
+            let mut mono = 0.0;
+            for &sample in frame {
+                mono += sample;
+                buffer.sum_squares += sample * sample;
+                buffer.peak = buffer.peak.max(sample.abs());
+            }
+            buffer.count += frame.len();
+            let next = buffer.next;
+            buffer.samples[next] = mono / frame.len() as f32;
+            buffer.next = (next + 1) % SPECTRUM_SAMPLES;
+        }
+    }
+
+    /// Analyzes the samples collected since the last call.
+    fn read(&self, analysis: &mut SoundAnalysis, scratch: &mut Vec<f32>) {
+        {
+            let mut buffer = self.buffer.lock();
+            analysis.rms = if buffer.count > 0 {
+                (buffer.sum_squares / buffer.count as f32).sqrt()
+            } else {
+                0.0
+            };
+            analysis.peak = buffer.peak;
+            analysis.sample_rate = buffer.sample_rate;
+            buffer.sum_squares = 0.0;
+            buffer.count = 0;
+            buffer.peak = 0.0;
+
+            scratch.clear();
+            scratch.extend_from_slice(&buffer.samples[buffer.next..]);
+            scratch.extend_from_slice(&buffer.samples[..buffer.next]);
+        }
+        spectrum(scratch, &mut analysis.spectrum);
+    }
+}
+
+/// Computes the amplitude of the frequencies of the samples, with a Hann window.
+fn spectrum(samples: &[f32], spectrum: &mut Vec<f32>) {
+    let len = samples.len();
+    let mut real: Vec<f32> = samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| sample * (0.5 - 0.5 * (TAU * index as f32 / len as f32).cos()))
+        .collect();
+    let mut imaginary = vec![0.0; len];
+    fft(&mut real, &mut imaginary);
+
+    // Normalized by the length, and by the coherent gain of the window, of 0.5
+    let scale = 4.0 / len as f32;
+    spectrum.clear();
+    spectrum.extend(
+        real.iter()
+            .zip(&imaginary)
+            .take(len / 2)
+            .map(|(real, imaginary)| (real * real + imaginary * imaginary).sqrt() * scale),
+    );
+}
+
+/// An in-place radix-2 Cooley-Tukey FFT, of a length that is a power of two.
+fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let len = real.len();
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            real.swap(i, j);
+            imaginary.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let angle = -TAU / size as f32;
+        for start in (0..len).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + size / 2);
+                let twiddled_real = real[b] * cos - imaginary[b] * sin;
+                let twiddled_imaginary = real[b] * sin + imaginary[b] * cos;
+                real[b] = real[a] - twiddled_real;
+                imaginary[b] = imaginary[a] - twiddled_imaginary;
+                real[a] += twiddled_real;
+                imaginary[a] += twiddled_imaginary;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+/// Updates the [`AudioAnalysis`] resource with the sound played since the last frame.
+pub fn update_audio_analysis_system(
+    audio_buses: Res<AudioBuses>,
+    sinks: Res<Assets<AudioSink>>,
+    mut audio_analysis: ResMut<AudioAnalysis>,
+    mut scratch: Local<Vec<f32>>,
+) {
+    for (bus, analysis) in AudioBus::ALL.iter().zip(&mut audio_analysis.buses) {
+        audio_buses.analysis_tap(*bus).read(analysis, &mut scratch);
+    }
+
+    // Forget the sinks that were dropped or stopped being analyzed
+    audio_analysis
+        .sinks
+        .retain(|id, _| matches!(sinks.get(&Handle::weak(*id)), Some(sink) if sink.is_analyzed()));
+    for (id, sink) in sinks.iter() {
+        if sink.is_analyzed() {
+            let analysis = audio_analysis.sinks.entry(id).or_default();
+            sink.analysis.read(analysis, &mut scratch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_analysis() {
+        let sample_rate = 1024;
+        let frequency = 64.0;
+        let samples: Vec<f32> = (0..2 * sample_rate)
+            .flat_map(|index| {
+                let sample = (TAU * frequency * index as f32 / sample_rate as f32).sin();
+                [sample, sample]
+            })
+            .collect();
+        let tap = AnalysisTap::new(true);
+        tap.write(&samples, 2, sample_rate);
+
+        let mut analysis = SoundAnalysis::default();
+        tap.read(&mut analysis, &mut Vec::new());
+        assert!((analysis.rms - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!((analysis.peak - 1.0).abs() < 1e-3);
+        assert_eq!(analysis.spectrum.len(), SPECTRUM_SAMPLES / 2);
+        assert!((analysis.amplitude(frequency) - 1.0).abs() < 1e-3);
+        assert!(analysis.amplitude(2.0 * frequency) < 1e-3);
+        assert!(analysis.mean_amplitude(0.0, 32.0) < 1e-3);
+
+        // The levels are reset once read
+        tap.read(&mut analysis, &mut Vec::new());
+        assert_eq!(analysis.peak, 0.0);
+    }
+}
@@ -1,8 +1,12 @@
-use crate::{AudioSink, AudioSource, Decodable};
+use crate::{
+    AudioBus, AudioGenerator, AudioGeneratorSender, AudioSink, AudioSource, Decodable, Generated,
+    PlaybackDecoder,
+};
 use bevy_asset::{Asset, Handle, HandleId};
-use bevy_ecs::system::Resource;
+use bevy_ecs::{entity::Entity, system::Resource};
+use bevy_utils::synccell::SyncCell;
 use parking_lot::RwLock;
-use std::{collections::VecDeque, fmt};
+use std::{collections::VecDeque, fmt, time::Duration};
 
 /// Use this [`Resource`] to play audio.
 ///
@@ -21,6 +25,8 @@ where
 {
     /// Queue for playing audio from asset handles
     pub(crate) queue: RwLock<VecDeque<AudioToPlay<Source>>>,
+    /// Queue for playing audio from generators
+    pub(crate) generators: RwLock<VecDeque<GeneratorToPlay>>,
 }
 
 impl<Source: Asset> fmt::Debug for Audio<Source>
@@ -39,6 +45,7 @@ where
     fn default() -> Self {
         Self {
             queue: Default::default(),
+            generators: Default::default(),
         }
     }
 }
@@ -119,6 +126,46 @@ where
         self.queue.write().push_back(config);
         Handle::<AudioSink>::weak(id)
     }
+
+    /// Play a sound synthesized on the audio thread by an [`AudioGenerator`], with
+    /// [`PlaybackSettings`].
+    ///
+    /// Returns a weak [`Handle`] to the [`AudioSink`] like [`Self::play`], and the sender of the
+    /// messages controlling the generator. Generated sounds can't repeat, seek or loop.
+    ///
+    /// ```
+    /// # use bevy_ecs::system::{Commands, Res, Resource};
+    /// # use bevy_audio::{Audio, AudioGenerator, AudioGeneratorSender, PlaybackSettings};
+    /// # struct Noise;
+    /// # impl AudioGenerator for Noise {
+    /// #     type Message = f32;
+    /// #     fn channels(&self) -> u16 { 1 }
+    /// #     fn sample_rate(&self) -> u32 { 44_100 }
+    /// #     fn generate(&mut self, _samples: &mut [f32]) -> bool { true }
+    /// # }
+    /// #[derive(Resource)]
+    /// struct NoiseController(AudioGeneratorSender<f32>);
+    ///
+    /// fn play_noise_system(mut commands: Commands, audio: Res<Audio>) {
+    ///     let (_sink, sender) = audio.play_generator(Noise, PlaybackSettings::ONCE);
+    ///     commands.insert_resource(NoiseController(sender));
+    /// }
+    /// ```
+    pub fn play_generator<G: AudioGenerator>(
+        &self,
+        generator: G,
+        settings: PlaybackSettings,
+    ) -> (Handle<AudioSink>, AudioGeneratorSender<G::Message>) {
+        let id = HandleId::random::<AudioSink>();
+        let (generated, sender) = Generated::new(generator);
+        let config = GeneratorToPlay {
+            settings,
+            sink_handle: id,
+            source: SyncCell::new(Box::new(generated)),
+        };
+        self.generators.write().push_back(config);
+        (Handle::<AudioSink>::weak(id), sender)
+    }
 }
 
 /// Settings to control playback from the start.
@@ -130,6 +177,13 @@ pub struct PlaybackSettings {
     pub volume: f32,
     /// Speed to play at.
     pub speed: f32,
+    /// The entity to play from, as a spatial sound heard by the
+    /// [`AudioListener`](crate::AudioListener), instead of directly.
+    pub emitter: Option<Entity>,
+    /// The bus the sound is mixed in.
+    pub bus: AudioBus,
+    /// The time of the audio clock the sound starts at, or `None` to start it as soon as possible.
+    pub start_time: Option<Duration>,
 }
 
 impl Default for PlaybackSettings {
@@ -144,6 +198,9 @@ impl PlaybackSettings {
         repeat: false,
         volume: 1.0,
         speed: 1.0,
+        emitter: None,
+        bus: AudioBus::Master,
+        start_time: None,
     };
 
     /// Will play the associate audio source in a loop.
@@ -151,6 +208,9 @@ impl PlaybackSettings {
         repeat: true,
         volume: 1.0,
         speed: 1.0,
+        emitter: None,
+        bus: AudioBus::Master,
+        start_time: None,
     };
 
     /// Helper to set the volume from start of playback.
@@ -164,6 +224,82 @@ impl PlaybackSettings {
         self.speed = speed;
         self
     }
+
+    /// Helper to play from an entity with an [`AudioEmitter`](crate::AudioEmitter), as a spatial
+    /// sound attenuated and panned from its position relative to the
+    /// [`AudioListener`](crate::AudioListener).
+    ///
+    /// ```
+    /// # use bevy_ecs::system::{Commands, Res};
+    /// # use bevy_asset::AssetServer;
+    /// # use bevy_audio::{Audio, AudioEmitter, PlaybackSettings};
+    /// # use bevy_transform::components::{GlobalTransform, Transform};
+    /// fn play_audio_system(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    ///     let emitter = commands
+    ///         .spawn((
+    ///             AudioEmitter::default(),
+    ///             Transform::from_xyz(2.0, 0.0, 0.0),
+    ///             GlobalTransform::default(),
+    ///         ))
+    ///         .id();
+    ///     audio.play_with_settings(
+    ///         asset_server.load("my_sound.ogg"),
+    ///         PlaybackSettings::ONCE.with_emitter(emitter),
+    ///     );
+    /// }
+    /// ```
+    pub const fn with_emitter(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    /// Helper to mix the sound in an [`AudioBus`], with the volume and the effects of the bus.
+    ///
+    /// ```
+    /// # use bevy_ecs::system::Res;
+    /// # use bevy_asset::AssetServer;
+    /// # use bevy_audio::{Audio, AudioBus, PlaybackSettings};
+    /// fn play_music_system(asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    ///     audio.play_with_settings(
+    ///         asset_server.load("my_music.ogg"),
+    ///         PlaybackSettings::LOOP.with_bus(AudioBus::Music),
+    ///     );
+    /// }
+    /// ```
+    pub const fn with_bus(mut self, bus: AudioBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Helper to start the sound exactly at a time of the audio clock, given by
+    /// [`AudioBuses::clock`](crate::AudioBuses::clock), regardless of the frame rate.
+    ///
+    /// The sound must be played ahead of its start time, as it starts as soon as possible when the
+    /// time has passed.
+    ///
+    /// ```
+    /// # use bevy_ecs::system::Res;
+    /// # use bevy_asset::AssetServer;
+    /// # use bevy_audio::{Audio, AudioBuses, PlaybackSettings};
+    /// # use std::time::Duration;
+    /// fn play_on_next_beat_system(
+    ///     asset_server: Res<AssetServer>,
+    ///     audio: Res<Audio>,
+    ///     audio_buses: Res<AudioBuses>,
+    /// ) {
+    ///     // The beats of a song at 120 beats per minute, which started at the time 0
+    ///     let beat = 0.5;
+    ///     let next_beat = (audio_buses.clock().as_secs_f64() / beat).floor() + 1.0;
+    ///     audio.play_with_settings(
+    ///         asset_server.load("drum.ogg"),
+    ///         PlaybackSettings::ONCE.with_start_time(Duration::from_secs_f64(next_beat * beat)),
+    ///     );
+    /// }
+    /// ```
+    pub const fn with_start_time(mut self, start_time: Duration) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -188,3 +324,9 @@ where
             .finish()
     }
 }
+
+pub(crate) struct GeneratorToPlay {
+    pub(crate) sink_handle: HandleId,
+    pub(crate) source: SyncCell<PlaybackDecoder>,
+    pub(crate) settings: PlaybackSettings,
+}
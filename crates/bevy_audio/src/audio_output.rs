@@ -1,50 +1,43 @@
-use crate::{Audio, AudioSource, Decodable};
-use bevy_asset::{Asset, Assets};
-use bevy_ecs::system::{Res, ResMut, Resource};
+use crate::{
+    decoder_at, effect::EffectChain, AnalysisTap, Audio, AudioBuses, AudioEffect, AudioSource,
+    Decodable, Playback, PlaybackControls, PlaybackDecoder, PlaybackFinished, PlaybackSettings,
+    SpatialSounds,
+};
+use bevy_asset::{Asset, Assets, Handle, HandleId};
+use bevy_ecs::{
+    event::EventWriter,
+    system::{Res, ResMut, Resource},
+};
 use bevy_reflect::TypeUuid;
-use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
-use std::marker::PhantomData;
+use bevy_utils::synccell::SyncCell;
+use rodio::Sink;
+use std::{sync::Arc, time::Duration};
 
-/// Used internally to play audio on the current "audio device"
-///
-/// ## Note
-///
-/// Initializing this resource will leak [`rodio::OutputStream`](rodio::OutputStream)
-/// using [`std::mem::forget`].
-/// This is done to avoid storing this in the struct (and making this `!Send`)
-/// while preventing it from dropping (to avoid halting of audio).
-///
-/// This is fine when initializing this once (as is default when adding this plugin),
-/// since the memory cost will be the same.
-/// However, repeatedly inserting this resource into the app will **leak more memory**.
+/// A sound playing from an [`AudioOutput`]
+struct PlayingSound<Source: Asset> {
+    sink_handle: HandleId,
+    /// The source of the sound, kept to build the decoders seeking and looping in it. Generated
+    /// sounds have none.
+    source_handle: Option<Handle<Source>>,
+    controls: Arc<PlaybackControls>,
+}
+
+/// Used internally to play the audio queued in the [`Audio`] resource on the [`AudioBuses`]
 #[derive(Resource)]
 pub struct AudioOutput<Source = AudioSource>
 where
-    Source: Decodable,
+    Source: Asset + Decodable,
 {
-    stream_handle: Option<OutputStreamHandle>,
-    phantom: PhantomData<Source>,
+    playing: Vec<PlayingSound<Source>>,
 }
 
 impl<Source> Default for AudioOutput<Source>
 where
-    Source: Decodable,
+    Source: Asset + Decodable,
 {
     fn default() -> Self {
-        if let Ok((stream, stream_handle)) = OutputStream::try_default() {
-            // We leak `OutputStream` to prevent the audio from stopping.
-            std::mem::forget(stream);
-            Self {
-                stream_handle: Some(stream_handle),
-                phantom: PhantomData,
-            }
-        } else {
-            warn!("No audio device found.");
-            Self {
-                stream_handle: None,
-                phantom: PhantomData,
-            }
+        Self {
+            playing: Vec::new(),
         }
     }
 }
@@ -53,23 +46,48 @@ impl<Source> AudioOutput<Source>
 where
     Source: Asset + Decodable,
 {
-    fn play_source(&self, audio_source: &Source, repeat: bool) -> Option<Sink> {
-        self.stream_handle.as_ref().map(|stream_handle| {
-            let sink = Sink::try_new(stream_handle).unwrap();
-            if repeat {
-                sink.append(audio_source.decoder().repeat_infinite());
-            } else {
-                sink.append(audio_source.decoder());
-            }
-            sink
-        })
+    fn play_source(
+        &mut self,
+        decoder: PlaybackDecoder,
+        sound: PlayingSound<Source>,
+        settings: &PlaybackSettings,
+        sinks: &mut Assets<AudioSink>,
+        audio_buses: &AudioBuses,
+        spatial_sounds: &mut SpatialSounds,
+    ) {
+        let Some((sink, effects, analysis)) =
+            audio_buses.new_sink(settings.bus, settings.start_time)
+        else {
+            return;
+        };
+        let source = Playback::new(decoder, sound.controls.clone());
+        match settings.emitter {
+            Some(emitter) => sink.append(spatial_sounds.spatialize(source, emitter)),
+            None => sink.append(source),
+        }
+        sink.set_speed(settings.speed);
+        sink.set_volume(settings.volume);
+
+        // don't keep the strong handle. there is no way to return it to the user here as it is async
+        let _ = sinks.set(
+            sound.sink_handle,
+            AudioSink {
+                sink: Some(sink),
+                effects,
+                analysis,
+                controls: sound.controls.clone(),
+            },
+        );
+        self.playing.push(sound);
     }
 
     fn try_play_queued(
-        &self,
+        &mut self,
         audio_sources: &Assets<Source>,
         audio: &mut Audio<Source>,
         sinks: &mut Assets<AudioSink>,
+        audio_buses: &AudioBuses,
+        spatial_sounds: &mut SpatialSounds,
     ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
@@ -77,34 +95,93 @@ where
         while i < len {
             let config = queue.pop_front().unwrap();
             if let Some(audio_source) = audio_sources.get(&config.source_handle) {
-                if let Some(sink) = self.play_source(audio_source, config.settings.repeat) {
-                    sink.set_speed(config.settings.speed);
-                    sink.set_volume(config.settings.volume);
-
-                    // don't keep the strong handle. there is no way to return it to the user here as it is async
-                    let _ = sinks.set(config.sink_handle, AudioSink { sink: Some(sink) });
-                }
+                let decoder = decoder_at(audio_source, 0.0);
+                let sound = PlayingSound {
+                    sink_handle: config.sink_handle,
+                    source_handle: Some(config.source_handle),
+                    controls: Arc::new(PlaybackControls::new(config.settings.repeat)),
+                };
+                self.play_source(
+                    decoder,
+                    sound,
+                    &config.settings,
+                    sinks,
+                    audio_buses,
+                    spatial_sounds,
+                );
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
                 queue.push_back(config);
             }
             i += 1;
         }
+
+        for config in audio.generators.get_mut().drain(..) {
+            let sound = PlayingSound {
+                sink_handle: config.sink_handle,
+                source_handle: None,
+                controls: Arc::new(PlaybackControls::generated()),
+            };
+            self.play_source(
+                SyncCell::to_inner(config.source),
+                sound,
+                &config.settings,
+                sinks,
+                audio_buses,
+                spatial_sounds,
+            );
+        }
     }
 }
 
 /// Plays audio currently queued in the [`Audio`] resource through the [`AudioOutput`] resource
 pub fn play_queued_audio_system<Source: Asset + Decodable>(
-    audio_output: Res<AudioOutput<Source>>,
+    mut audio_output: ResMut<AudioOutput<Source>>,
     audio_sources: Option<Res<Assets<Source>>>,
     mut audio: ResMut<Audio<Source>>,
     mut sinks: ResMut<Assets<AudioSink>>,
+    audio_buses: Res<AudioBuses>,
+    mut spatial_sounds: ResMut<SpatialSounds>,
 ) {
     if let Some(audio_sources) = audio_sources {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut sinks);
+        audio_output.try_play_queued(
+            &*audio_sources,
+            &mut *audio,
+            &mut sinks,
+            &audio_buses,
+            &mut spatial_sounds,
+        );
     };
 }
 
+/// Seeks and loops the sounds playing from the [`AudioOutput`] resource, and sends a
+/// [`PlaybackFinished`] event when they reach their end.
+pub fn update_playback_system<Source: Asset + Decodable>(
+    mut audio_output: ResMut<AudioOutput<Source>>,
+    audio_sources: Option<Res<Assets<Source>>>,
+    mut finished_events: EventWriter<PlaybackFinished>,
+) {
+    audio_output.playing.retain(|sound| {
+        if sound.controls.is_ended() {
+            if sound.controls.is_finished() {
+                finished_events.send(PlaybackFinished {
+                    sink: Handle::weak(sound.sink_handle),
+                });
+            }
+            return false;
+        }
+        if let Some(audio_source) = sound
+            .source_handle
+            .as_ref()
+            .zip(audio_sources.as_ref())
+            .and_then(|(source_handle, audio_sources)| audio_sources.get(source_handle))
+        {
+            sound.controls.prepare(audio_source);
+        }
+        true
+    });
+}
+
 /// Asset controlling the playback of a sound
 ///
 /// ```
@@ -133,6 +210,9 @@ pub struct AudioSink {
     // This field is an Option in order to allow us to have a safe drop that will detach the sink.
     // It will never be None during its life
     sink: Option<Sink>,
+    effects: EffectChain,
+    pub(crate) analysis: AnalysisTap,
+    controls: Arc<PlaybackControls>,
 }
 
 impl Drop for AudioSink {
@@ -169,11 +249,54 @@ impl AudioSink {
     /// Changes the speed of the sound.
     ///
     /// The value `1.0` is the "normal" speed (unfiltered input). Any value other than `1.0`
-    /// will change the play speed of the sound.
+    /// will change the play speed of the sound, and its pitch with it.
+    ///
+    /// The speed is applied every few milliseconds, so it can be animated by changing it every
+    /// frame.
     pub fn set_speed(&self, speed: f32) {
         self.sink.as_ref().unwrap().set_speed(speed);
     }
 
+    /// Gets the position of the playback in the sound.
+    ///
+    /// It goes back to the start of the loop when the sound loops.
+    pub fn position(&self) -> Duration {
+        Duration::from_secs_f64(self.controls.position())
+    }
+
+    /// Seeks to a position in the sound.
+    ///
+    /// The sound jumps to the position once its decoder is ready, usually at the next frame. Sounds
+    /// are decoded from their start up to the position, so seeking far in a long sound takes time.
+    /// Sounds from an [`AudioGenerator`](crate::AudioGenerator) can't seek.
+    pub fn seek(&self, position: Duration) {
+        self.controls.seek(position.as_secs_f64());
+    }
+
+    /// Loops the sound from `start` once it reaches `end`, or its own end when `end` is `None`.
+    ///
+    /// The decoder starting the loop is built in advance, so that the sound loops seamlessly.
+    /// Sounds from an [`AudioGenerator`](crate::AudioGenerator) can't loop.
+    pub fn set_loop(&self, start: Duration, end: Option<Duration>) {
+        let end = end.map_or(f64::INFINITY, |end| end.as_secs_f64());
+        self.controls.set_loop(start.as_secs_f64(), end);
+    }
+
+    /// Stops looping the sound, that plays until its end.
+    pub fn clear_loop(&self) {
+        self.controls.clear_loop();
+    }
+
+    /// Gets the start and the end of the loop of the sound, if it loops.
+    ///
+    /// The end is `None` when the sound loops at its own end.
+    pub fn loop_points(&self) -> Option<(Duration, Option<Duration>)> {
+        self.controls.loop_points().map(|(start, end)| {
+            let end = end.is_finite().then(|| Duration::from_secs_f64(end));
+            (Duration::from_secs_f64(start), end)
+        })
+    }
+
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
@@ -213,4 +336,26 @@ impl AudioSink {
     pub fn stop(&self) {
         self.sink.as_ref().unwrap().stop();
     }
+
+    /// Adds an effect at the end of the effect chain of the sink, before the sound is mixed in its
+    /// [`AudioBus`](crate::AudioBus).
+    pub fn add_effect(&self, effect: impl AudioEffect) {
+        self.effects.push(effect);
+    }
+
+    /// Removes all the effects of the sink.
+    pub fn clear_effects(&self) {
+        self.effects.clear();
+    }
+
+    /// Is the sound of this sink analyzed in the [`AudioAnalysis`](crate::AudioAnalysis) resource?
+    pub fn is_analyzed(&self) -> bool {
+        self.analysis.is_enabled()
+    }
+
+    /// Analyzes the sound of this sink in the [`AudioAnalysis`](crate::AudioAnalysis) resource,
+    /// from the next frame, or stops analyzing it.
+    pub fn set_analyzed(&self, analyzed: bool) {
+        self.analysis.set_enabled(analyzed);
+    }
 }
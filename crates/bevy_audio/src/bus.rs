@@ -0,0 +1,461 @@
+use crate::{
+    effect::{EffectChain, EffectOutput},
+    AnalysisTap, AudioEffect,
+};
+use bevy_ecs::system::Resource;
+use bevy_utils::tracing::warn;
+use parking_lot::Mutex;
+use rodio::{
+    cpal::traits::{DeviceTrait, HostTrait},
+    dynamic_mixer::{self, DynamicMixer, DynamicMixerController},
+    source::UniformSourceIterator,
+    OutputStream, Sink, Source,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// The number of frames of a bus processed at once, by its effects and with its volume.
+const BUS_BLOCK_FRAMES: usize = 128;
+
+/// A bus mixing the sounds routed to it with
+/// [`PlaybackSettings::with_bus`](crate::PlaybackSettings::with_bus), before they are played with
+/// the volume and the effects of the bus. The sounds of the other buses are mixed in the
+/// [`AudioBus::Master`] bus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AudioBus {
+    /// The bus every sound ends up in, where the sounds play by default
+    #[default]
+    Master,
+    /// The bus of the music
+    Music,
+    /// The bus of the sound effects
+    Sfx,
+    /// The bus of the dialogues and voice chats
+    Voice,
+}
+
+impl AudioBus {
+    /// All the buses, the master bus first.
+    pub const ALL: [AudioBus; 4] = [
+        AudioBus::Master,
+        AudioBus::Music,
+        AudioBus::Sfx,
+        AudioBus::Voice,
+    ];
+}
+
+/// The number of channels the buses mix their sounds in.
+const BUS_CHANNELS: u16 = 2;
+
+/// The controls of a bus, shared with the audio thread.
+struct BusControls {
+    volume: Mutex<f32>,
+    muted: AtomicBool,
+    effects: EffectChain,
+    analysis: AnalysisTap,
+    /// The frame of the audio clock being mixed, which all the buses mix in step
+    frame: Arc<AtomicU64>,
+}
+
+impl Default for BusControls {
+    fn default() -> Self {
+        Self {
+            volume: Mutex::new(1.0),
+            muted: AtomicBool::new(false),
+            effects: EffectChain::default(),
+            analysis: AnalysisTap::new(true),
+            frame: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+struct Bus {
+    mixer: Arc<DynamicMixerController<f32>>,
+    controls: Arc<BusControls>,
+}
+
+/// Use this [`Resource`] to control the volume and the effects of the [`AudioBus`]es, like from
+/// the sliders of an options menu.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_audio::{AudioBus, AudioBuses};
+/// fn mute_music_system(audio_buses: Res<AudioBuses>) {
+///     audio_buses.set_volume(AudioBus::Sfx, 0.5);
+///     audio_buses.set_muted(AudioBus::Music, true);
+/// }
+/// ```
+///
+/// ## Note
+///
+/// Initializing this resource will leak [`rodio::OutputStream`](rodio::OutputStream)
+/// using [`std::mem::forget`].
+/// This is done to avoid storing this in the struct (and making this `!Send`)
+/// while preventing it from dropping (to avoid halting of audio).
+///
+/// This is fine when initializing this once (as is default when adding the audio plugin),
+/// since the memory cost will be the same.
+/// However, repeatedly inserting this resource into the app will **leak more memory**.
+#[derive(Resource)]
+pub struct AudioBuses {
+    /// The buses, in the order of [`AudioBus::ALL`]
+    buses: Vec<Bus>,
+    /// Whether the master bus is played on an audio device
+    has_output: bool,
+    /// The sample rate the buses mix their sounds at
+    sample_rate: u32,
+}
+
+impl Default for AudioBuses {
+    fn default() -> Self {
+        // The sounds are mixed at the sample rate of the device, to only be resampled once
+        let sample_rate = rodio::cpal::default_host()
+            .default_output_device()
+            .and_then(|device| device.default_output_config().ok())
+            .map_or(44_100, |config| config.sample_rate().0);
+        let (buses, mut outputs): (Vec<_>, Vec<_>) = AudioBus::ALL
+            .iter()
+            .map(|_| {
+                let (mixer, output) = dynamic_mixer::mixer(BUS_CHANNELS, sample_rate);
+                let controls = Arc::new(BusControls::default());
+                let output = BusOutput::new(output, controls.clone());
+                (Bus { mixer, controls }, output)
+            })
+            .unzip();
+        let master = outputs.remove(AudioBus::Master as usize);
+        for output in outputs {
+            buses[AudioBus::Master as usize].mixer.add(output);
+        }
+
+        let has_output = if let Ok((stream, stream_handle)) = OutputStream::try_default() {
+            // We leak `OutputStream` to prevent the audio from stopping.
+            std::mem::forget(stream);
+            stream_handle.play_raw(master).is_ok()
+        } else {
+            warn!("No audio device found.");
+            false
+        };
+        Self {
+            buses,
+            has_output,
+            sample_rate,
+        }
+    }
+}
+
+impl AudioBuses {
+    fn bus(&self, bus: AudioBus) -> &Bus {
+        &self.buses[bus as usize]
+    }
+
+    /// Creates a sink playing in the bus from a time of the audio clock, through a chain of
+    /// effects and an analysis tap, disabled until the sink is analyzed, if there is an audio
+    /// device.
+    pub(crate) fn new_sink(
+        &self,
+        bus: AudioBus,
+        start_time: Option<Duration>,
+    ) -> Option<(Sink, EffectChain, AnalysisTap)> {
+        self.has_output.then(|| {
+            let (sink, output) = Sink::new_idle();
+            let effects = EffectChain::default();
+            let analysis = AnalysisTap::new(false);
+            let output = EffectOutput::new(output, effects.clone(), analysis.clone());
+            // The sink is converted to the format of the bus to count its frames on the clock
+            let output = UniformSourceIterator::new(output, BUS_CHANNELS, self.sample_rate);
+            let start_frame = start_time.map_or(0, |start_time| {
+                (start_time.as_secs_f64() * self.sample_rate as f64).round() as u64
+            });
+            let bus = self.bus(bus);
+            bus.mixer.add(Scheduled::new(
+                output,
+                bus.controls.frame.clone(),
+                start_frame,
+            ));
+            (sink, effects, analysis)
+        })
+    }
+
+    /// Gets the time of the audio clock, which is the duration of sound mixed by the buses since
+    /// they started playing.
+    ///
+    /// Sounds played with [`PlaybackSettings::with_start_time`](crate::PlaybackSettings::with_start_time)
+    /// start exactly at a time of this clock. The clock advances by blocks of a few milliseconds,
+    /// ahead of the sound heard by the latency of the audio device. It doesn't advance without an
+    /// audio device.
+    pub fn clock(&self) -> Duration {
+        let frame = self
+            .bus(AudioBus::Master)
+            .controls
+            .frame
+            .load(Ordering::Relaxed);
+        Duration::from_secs_f64(frame as f64 / self.sample_rate as f64)
+    }
+
+    /// Gets the sample rate the buses mix their sounds at, which is the one of the audio device.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub(crate) fn analysis_tap(&self, bus: AudioBus) -> &AnalysisTap {
+        &self.bus(bus).controls.analysis
+    }
+
+    /// Gets the volume of the bus.
+    ///
+    /// The value `1.0` is the "normal" volume (unfiltered input). Any value other than `1.0`
+    /// will multiply each sample by this value.
+    pub fn volume(&self, bus: AudioBus) -> f32 {
+        *self.bus(bus).controls.volume.lock()
+    }
+
+    /// Changes the volume of the bus.
+    ///
+    /// The value `1.0` is the "normal" volume (unfiltered input). Any value other than `1.0`
+    /// will multiply each sample by this value.
+    pub fn set_volume(&self, bus: AudioBus, volume: f32) {
+        *self.bus(bus).controls.volume.lock() = volume;
+    }
+
+    /// Is this bus muted?
+    pub fn is_muted(&self, bus: AudioBus) -> bool {
+        self.bus(bus).controls.muted.load(Ordering::Relaxed)
+    }
+
+    /// Mutes or unmutes the bus, keeping its volume.
+    pub fn set_muted(&self, bus: AudioBus, muted: bool) {
+        self.bus(bus).controls.muted.store(muted, Ordering::Relaxed);
+    }
+
+    /// Toggles the mute of the bus.
+    pub fn toggle_mute(&self, bus: AudioBus) {
+        self.set_muted(bus, !self.is_muted(bus));
+    }
+
+    /// Adds an effect at the end of the effect chain of the bus.
+    pub fn add_effect(&self, bus: AudioBus, effect: impl AudioEffect) {
+        self.bus(bus).controls.effects.push(effect);
+    }
+
+    /// Removes all the effects of the bus.
+    pub fn clear_effects(&self, bus: AudioBus) {
+        self.bus(bus).controls.effects.clear();
+    }
+}
+
+/// The sound of a bus, processed by its effects and with its volume block by block.
+struct BusOutput {
+    mixer: DynamicMixer<f32>,
+    controls: Arc<BusControls>,
+    block: Vec<f32>,
+    position: usize,
+    /// The gain at the end of the last block, ramped to the current one over the next block to
+    /// avoid clicks
+    gain: f32,
+    /// The number of frames mixed
+    frames: u64,
+}
+
+impl BusOutput {
+    fn new(mixer: DynamicMixer<f32>, controls: Arc<BusControls>) -> Self {
+        Self {
+            mixer,
+            controls,
+            block: Vec::new(),
+            position: 0,
+            gain: 1.0,
+            frames: 0,
+        }
+    }
+
+    fn process_block(&mut self) {
+        let channels = self.mixer.channels();
+        self.block.clear();
+        for frame in self.frames..self.frames + BUS_BLOCK_FRAMES as u64 {
+            // The scheduled sounds start from the frame being mixed
+            self.controls.frame.store(frame, Ordering::Relaxed);
+            for _ in 0..channels {
+                // The bus keeps playing silence while no sound is routed to it
+                self.block.push(self.mixer.next().unwrap_or(0.0));
+            }
+        }
+        self.frames += BUS_BLOCK_FRAMES as u64;
+        self.controls
+            .effects
+            .process(&mut self.block, channels, self.mixer.sample_rate());
+
+        let gain = if self.controls.muted.load(Ordering::Relaxed) {
+            0.0
+        } else {
+            *self.controls.volume.lock()
+        };
+        for (index, frame) in self.block.chunks_mut(channels as usize).enumerate() {
+            let t = index as f32 / BUS_BLOCK_FRAMES as f32;
+            let frame_gain = self.gain + (gain - self.gain) * t;
+            for sample in frame {
+                *sample *= frame_gain;
+            }
+        }
+        self.gain = gain;
+        self.position = 0;
+        self.controls
+            .analysis
+            .write(&self.block, channels, self.mixer.sample_rate());
+    }
+}
+
+impl Iterator for BusOutput {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position == self.block.len() {
+            self.process_block();
+        }
+        let sample = self.block[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for BusOutput {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.mixer.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.mixer.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A sound mixed in a bus, silent until the frame of the audio clock it starts at.
+struct Scheduled<I> {
+    input: I,
+    /// The frame being mixed by the bus
+    bus_frame: Arc<AtomicU64>,
+    start_frame: u64,
+    /// The frame of the next sample, once mixed
+    frame: Option<u64>,
+    channel: u16,
+}
+
+impl<I> Scheduled<I>
+where
+    I: Source<Item = f32>,
+{
+    fn new(input: I, bus_frame: Arc<AtomicU64>, start_frame: u64) -> Self {
+        Self {
+            input,
+            bus_frame,
+            start_frame,
+            frame: None,
+            channel: 0,
+        }
+    }
+}
+
+impl<I> Iterator for Scheduled<I>
+where
+    I: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let bus_frame = &self.bus_frame;
+        let frame = self
+            .frame
+            .get_or_insert_with(|| bus_frame.load(Ordering::Relaxed));
+        if *frame >= self.start_frame {
+            return self.input.next();
+        }
+        self.channel += 1;
+        if self.channel == self.input.channels() {
+            self.channel = 0;
+            *frame += 1;
+        }
+        Some(0.0)
+    }
+}
+
+impl<I> Source for Scheduled<I>
+where
+    I: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn bus_effects_and_volume() {
+        let (mixer, output) = dynamic_mixer::mixer(1, 100);
+        let controls = Arc::new(BusControls::default());
+        let mut bus = BusOutput::new(output, controls.clone());
+        controls.effects.push(|samples: &mut [f32], _, _| {
+            for sample in samples {
+                *sample *= 2.0;
+            }
+        });
+        *controls.volume.lock() = 0.5;
+        mixer.add(SamplesBuffer::new(1, 100, vec![1.0; 1000]));
+
+        // The volume is ramped from 1.0 over the first block, and the effect doubles the sound
+        assert_eq!(bus.next(), Some(2.0));
+        let block: Vec<f32> = bus.by_ref().take(2 * BUS_BLOCK_FRAMES - 1).collect();
+        assert!(block[BUS_BLOCK_FRAMES - 2] > 1.0);
+        assert_eq!(block[BUS_BLOCK_FRAMES - 1], 1.0);
+
+        controls.muted.store(true, Ordering::Relaxed);
+        let block: Vec<f32> = bus.by_ref().take(2 * BUS_BLOCK_FRAMES).collect();
+        assert_eq!(block[BUS_BLOCK_FRAMES], 0.0);
+
+        // The bus keeps playing once its sounds ended
+        assert_eq!(bus.nth(1000), Some(0.0));
+    }
+
+    #[test]
+    fn scheduled_start() {
+        let (mixer, output) = dynamic_mixer::mixer(1, 100);
+        let controls = Arc::new(BusControls::default());
+        let mut bus = BusOutput::new(output, controls.clone());
+        assert_eq!(bus.nth(99), Some(0.0));
+
+        // The sound starts at its frame, in the middle of a block
+        let sound = SamplesBuffer::new(1, 100, vec![1.0; 10]);
+        mixer.add(Scheduled::new(sound, controls.frame.clone(), 300));
+        let samples: Vec<f32> = bus.by_ref().take(300).collect();
+        assert_eq!(samples[199], 0.0);
+        assert_eq!(samples[200], 1.0);
+        assert_eq!(samples[209], 1.0);
+        assert_eq!(samples[210], 0.0);
+    }
+}
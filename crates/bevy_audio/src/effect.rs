@@ -0,0 +1,708 @@
+use crate::AnalysisTap;
+use parking_lot::Mutex;
+use rodio::Source;
+use std::{
+    f32::consts::{FRAC_1_SQRT_2, PI},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// The number of frames of a sink processed at once by its effects.
+const EFFECT_BLOCK_FRAMES: usize = 128;
+
+/// An effect processing the sound of an [`AudioBus`](crate::AudioBus) or an
+/// [`AudioSink`](crate::AudioSink) on the audio thread.
+///
+/// The built-in effects share their parameters with their clones, to change them from systems
+/// once the effect is added, while each clone keeps its own state.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_audio::{AudioBus, AudioBuses};
+/// // Clips the sound effects
+/// fn distort_sound_effects(audio_buses: Res<AudioBuses>) {
+///     audio_buses.add_effect(AudioBus::Sfx, |samples: &mut [f32], _channels, _sample_rate| {
+///         for sample in samples {
+///             *sample = sample.clamp(-0.5, 0.5);
+///         }
+///     });
+/// }
+/// ```
+pub trait AudioEffect: Send + 'static {
+    /// Processes a block of interleaved samples in place, with the number of channels and the
+    /// sample rate of the sound.
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32);
+}
+
+impl<F> AudioEffect for F
+where
+    F: FnMut(&mut [f32], u16, u32) + Send + 'static,
+{
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        self(samples, channels, sample_rate);
+    }
+}
+
+/// A chain of [`AudioEffect`]s, shared with the audio thread which processes the sound through
+/// them in order.
+#[derive(Clone, Default)]
+pub(crate) struct EffectChain {
+    effects: Arc<Mutex<Vec<Box<dyn AudioEffect>>>>,
+}
+
+impl EffectChain {
+    pub(crate) fn push(&self, effect: impl AudioEffect) {
+        self.effects.lock().push(Box::new(effect));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.effects.lock().clear();
+    }
+
+    pub(crate) fn process(&self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        for effect in self.effects.lock().iter_mut() {
+            effect.process(samples, channels, sample_rate);
+        }
+    }
+}
+
+/// The sound of a sink, processed by its effects block by block.
+pub(crate) struct EffectOutput<I> {
+    input: I,
+    effects: EffectChain,
+    analysis: AnalysisTap,
+    block: Vec<f32>,
+    position: usize,
+    /// The number of channels and the sample rate of the samples of the block
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl<I> EffectOutput<I>
+where
+    I: Source<Item = f32>,
+{
+    pub(crate) fn new(input: I, effects: EffectChain, analysis: AnalysisTap) -> Self {
+        Self {
+            channels: input.channels(),
+            sample_rate: input.sample_rate(),
+            input,
+            effects,
+            analysis,
+            block: Vec::new(),
+            position: 0,
+        }
+    }
+
+    fn process_block(&mut self) {
+        self.channels = self.input.channels();
+        self.sample_rate = self.input.sample_rate();
+        // The block doesn't span two frames of the input, which may have different channels
+        let max_len = EFFECT_BLOCK_FRAMES * self.channels as usize;
+        let len = match self.input.current_frame_len() {
+            Some(0) | None => max_len,
+            Some(frame_len) => frame_len.min(max_len),
+        };
+        self.block.clear();
+        self.block.extend(self.input.by_ref().take(len));
+        self.effects
+            .process(&mut self.block, self.channels, self.sample_rate);
+        self.analysis
+            .write(&self.block, self.channels, self.sample_rate);
+        self.position = 0;
+    }
+}
+
+impl<I> Iterator for EffectOutput<I>
+where
+    I: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position == self.block.len() {
+            self.process_block();
+        }
+        let sample = self.block.get(self.position).copied()?;
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl<I> Source for EffectOutput<I>
+where
+    I: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        if self.position < self.block.len() {
+            Some(self.block.len() - self.position)
+        } else {
+            self.input.current_frame_len()
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        if self.position < self.block.len() {
+            self.channels
+        } else {
+            self.input.channels()
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        if self.position < self.block.len() {
+            self.sample_rate
+        } else {
+            self.input.sample_rate()
+        }
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
+
+/// A parameter of an effect, shared with the audio thread.
+#[derive(Debug)]
+struct Parameter(AtomicU32);
+
+impl Parameter {
+    fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum FilterKind {
+    LowPass,
+    HighPass,
+}
+
+/// A second order Butterworth filter, from the cookbook formulae of Robert Bristow-Johnson.
+#[derive(Clone, Debug)]
+struct Biquad {
+    kind: FilterKind,
+    cutoff: Arc<Parameter>,
+    /// The state of the filter of each channel, in transposed direct form II
+    states: Vec<[f32; 2]>,
+}
+
+impl Biquad {
+    fn new(kind: FilterKind, cutoff: f32) -> Self {
+        Self {
+            kind,
+            cutoff: Arc::new(Parameter::new(cutoff)),
+            states: Vec::new(),
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        let sample_rate = sample_rate as f32;
+        let cutoff = self.cutoff.get().clamp(10.0, 0.45 * sample_rate);
+        let (sin, cos) = (2.0 * PI * cutoff / sample_rate).sin_cos();
+        let alpha = sin / (2.0 * FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+        let (b0, b1) = match self.kind {
+            FilterKind::LowPass => ((1.0 - cos) / 2.0, 1.0 - cos),
+            FilterKind::HighPass => ((1.0 + cos) / 2.0, -(1.0 + cos)),
+        };
+        let (b0, b1, b2) = (b0 / a0, b1 / a0, b0 / a0);
+        let (a1, a2) = (-2.0 * cos / a0, (1.0 - alpha) / a0);
+
+        self.states.resize(channels as usize, [0.0; 2]);
+        for frame in samples.chunks_mut(channels as usize) {
+            for (sample, state) in frame.iter_mut().zip(&mut self.states) {
+                let input = *sample;
+                let output = b0 * input + state[0];
+                state[0] = b1 * input - a1 * output + state[1];
+                state[1] = b2 * input - a2 * output;
+                *sample = output;
+            }
+        }
+    }
+}
+
+/// An effect keeping the frequencies of the sound below its cutoff, muffling it like when heard
+/// through a wall or under water.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioBus, AudioBuses, LowPassFilter};
+/// #[derive(Resource)]
+/// struct Underwater {
+///     is_underwater: bool,
+///     filter: LowPassFilter,
+/// }
+///
+/// fn setup(mut commands: Commands, audio_buses: Res<AudioBuses>) {
+///     let filter = LowPassFilter::new(20_000.0);
+///     // The filter of the bus shares its cutoff with the one of the resource
+///     audio_buses.add_effect(AudioBus::Sfx, filter.clone());
+///     commands.insert_resource(Underwater {
+///         is_underwater: false,
+///         filter,
+///     });
+/// }
+///
+/// fn muffle_underwater(underwater: Res<Underwater>) {
+///     let cutoff = if underwater.is_underwater { 500.0 } else { 20_000.0 };
+///     underwater.filter.set_cutoff(cutoff);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LowPassFilter {
+    biquad: Biquad,
+}
+
+impl LowPassFilter {
+    /// Creates a filter with a cutoff frequency, in hertz.
+    pub fn new(cutoff: f32) -> Self {
+        Self {
+            biquad: Biquad::new(FilterKind::LowPass, cutoff),
+        }
+    }
+
+    /// Gets the cutoff frequency of the filter, in hertz.
+    pub fn cutoff(&self) -> f32 {
+        self.biquad.cutoff.get()
+    }
+
+    /// Changes the cutoff frequency of the filter, in hertz.
+    pub fn set_cutoff(&self, cutoff: f32) {
+        self.biquad.cutoff.set(cutoff);
+    }
+}
+
+impl AudioEffect for LowPassFilter {
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        self.biquad.process(samples, channels, sample_rate);
+    }
+}
+
+/// An effect keeping the frequencies of the sound above its cutoff, thinning it like when heard
+/// through a phone or a radio.
+#[derive(Clone, Debug)]
+pub struct HighPassFilter {
+    biquad: Biquad,
+}
+
+impl HighPassFilter {
+    /// Creates a filter with a cutoff frequency, in hertz.
+    pub fn new(cutoff: f32) -> Self {
+        Self {
+            biquad: Biquad::new(FilterKind::HighPass, cutoff),
+        }
+    }
+
+    /// Gets the cutoff frequency of the filter, in hertz.
+    pub fn cutoff(&self) -> f32 {
+        self.biquad.cutoff.get()
+    }
+
+    /// Changes the cutoff frequency of the filter, in hertz.
+    pub fn set_cutoff(&self, cutoff: f32) {
+        self.biquad.cutoff.set(cutoff);
+    }
+}
+
+impl AudioEffect for HighPassFilter {
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        self.biquad.process(samples, channels, sample_rate);
+    }
+}
+
+#[derive(Debug)]
+struct DelayParameters {
+    time: Parameter,
+    feedback: Parameter,
+    mix: Parameter,
+}
+
+/// An effect repeating the sound after a delay, in echoes fading with its feedback.
+#[derive(Clone, Debug)]
+pub struct Delay {
+    parameters: Arc<DelayParameters>,
+    /// The interleaved samples of the last `time` seconds, in a ring buffer
+    buffer: Vec<f32>,
+    position: usize,
+}
+
+impl Delay {
+    /// Creates a delay repeating the sound after `time` seconds, with the part of each echo
+    /// repeated in the next one, and the part of the echoes mixed with the sound.
+    pub fn new(time: f32, feedback: f32, mix: f32) -> Self {
+        Self {
+            parameters: Arc::new(DelayParameters {
+                time: Parameter::new(time),
+                feedback: Parameter::new(feedback),
+                mix: Parameter::new(mix),
+            }),
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Gets the delay of the echoes, in seconds.
+    pub fn time(&self) -> f32 {
+        self.parameters.time.get()
+    }
+
+    /// Changes the delay of the echoes, in seconds.
+    pub fn set_time(&self, time: f32) {
+        self.parameters.time.set(time);
+    }
+
+    /// Gets the part of each echo repeated in the next one, from `0.0` to `1.0`.
+    pub fn feedback(&self) -> f32 {
+        self.parameters.feedback.get()
+    }
+
+    /// Changes the part of each echo repeated in the next one, from `0.0` to `1.0`.
+    pub fn set_feedback(&self, feedback: f32) {
+        self.parameters.feedback.set(feedback);
+    }
+
+    /// Gets the part of the echoes mixed with the sound, from `0.0` to `1.0`.
+    pub fn mix(&self) -> f32 {
+        self.parameters.mix.get()
+    }
+
+    /// Changes the part of the echoes mixed with the sound, from `0.0` to `1.0`.
+    pub fn set_mix(&self, mix: f32) {
+        self.parameters.mix.set(mix);
+    }
+}
+
+impl AudioEffect for Delay {
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        let frames = ((self.time() * sample_rate as f32) as usize).max(1);
+        let len = frames * channels as usize;
+        if self.buffer.len() != len {
+            self.buffer.resize(len, 0.0);
+            self.position %= len;
+        }
+        let (feedback, mix) = (self.feedback(), self.mix());
+        for sample in samples {
+            let echo = self.buffer[self.position];
+            self.buffer[self.position] = *sample + echo * feedback;
+            self.position = (self.position + 1) % len;
+            *sample = *sample * (1.0 - mix) + echo * mix;
+        }
+    }
+}
+
+/// The delays of the comb and all-pass filters of [`Reverb`], in samples at 44100 Hz.
+const COMB_DELAYS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALL_PASS_DELAYS: [usize; 4] = [556, 441, 341, 225];
+/// The difference between the delays of two channels, in samples at 44100 Hz
+const STEREO_SPREAD: usize = 23;
+
+#[derive(Clone, Debug)]
+struct DelayLine {
+    buffer: Vec<f32>,
+    position: usize,
+    /// The low-pass filtered output of a comb filter
+    filtered: f32,
+}
+
+impl DelayLine {
+    fn new(delay: usize, sample_rate: u32) -> Self {
+        Self {
+            buffer: vec![0.0; (delay * sample_rate as usize / 44_100).max(1)],
+            position: 0,
+            filtered: 0.0,
+        }
+    }
+
+    fn comb(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.buffer[self.position];
+        self.filtered = output * (1.0 - damping) + self.filtered * damping;
+        self.buffer[self.position] = input + self.filtered * feedback;
+        self.position = (self.position + 1) % self.buffer.len();
+        output
+    }
+
+    fn all_pass(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.position];
+        self.buffer[self.position] = input + delayed * 0.5;
+        self.position = (self.position + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+#[derive(Debug)]
+struct ReverbParameters {
+    room_size: Parameter,
+    damping: Parameter,
+    mix: Parameter,
+}
+
+/// An effect simulating the reflections of the sound in a room, following the Freeverb algorithm.
+#[derive(Clone, Debug)]
+pub struct Reverb {
+    parameters: Arc<ReverbParameters>,
+    /// The comb and all-pass filters of each channel, for a sample rate
+    channels: Vec<(Vec<DelayLine>, Vec<DelayLine>)>,
+    sample_rate: u32,
+}
+
+impl Reverb {
+    /// Creates a reverb in a room of a size and damping of the walls from `0.0` to `1.0`, with
+    /// the part of the reverberated sound mixed with the sound.
+    pub fn new(room_size: f32, damping: f32, mix: f32) -> Self {
+        Self {
+            parameters: Arc::new(ReverbParameters {
+                room_size: Parameter::new(room_size),
+                damping: Parameter::new(damping),
+                mix: Parameter::new(mix),
+            }),
+            channels: Vec::new(),
+            sample_rate: 0,
+        }
+    }
+
+    /// Gets the size of the room, from `0.0` to `1.0`.
+    pub fn room_size(&self) -> f32 {
+        self.parameters.room_size.get()
+    }
+
+    /// Changes the size of the room, from `0.0` to `1.0`.
+    pub fn set_room_size(&self, room_size: f32) {
+        self.parameters.room_size.set(room_size);
+    }
+
+    /// Gets how much the walls absorb the high frequencies, from `0.0` to `1.0`.
+    pub fn damping(&self) -> f32 {
+        self.parameters.damping.get()
+    }
+
+    /// Changes how much the walls absorb the high frequencies, from `0.0` to `1.0`.
+    pub fn set_damping(&self, damping: f32) {
+        self.parameters.damping.set(damping);
+    }
+
+    /// Gets the part of the reverberated sound mixed with the sound, from `0.0` to `1.0`.
+    pub fn mix(&self) -> f32 {
+        self.parameters.mix.get()
+    }
+
+    /// Changes the part of the reverberated sound mixed with the sound, from `0.0` to `1.0`.
+    pub fn set_mix(&self, mix: f32) {
+        self.parameters.mix.set(mix);
+    }
+}
+
+impl AudioEffect for Reverb {
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        if self.channels.len() != channels as usize || self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.channels = (0..channels as usize)
+                .map(|channel| {
+                    let spread = channel * STEREO_SPREAD;
+                    let delay_lines = |delays: &[usize]| {
+                        delays
+                            .iter()
+                            .map(|delay| DelayLine::new(delay + spread, sample_rate))
+                            .collect()
+                    };
+                    (delay_lines(&COMB_DELAYS), delay_lines(&ALL_PASS_DELAYS))
+                })
+                .collect();
+        }
+
+        let feedback = self.room_size().clamp(0.0, 1.0) * 0.28 + 0.7;
+        let damping = self.damping().clamp(0.0, 1.0) * 0.4;
+        let mix = self.mix();
+        for frame in samples.chunks_mut(channels as usize) {
+            let input = frame.iter().sum::<f32>() * 0.015;
+            for (sample, (combs, all_passes)) in frame.iter_mut().zip(&mut self.channels) {
+                let reverberated = combs
+                    .iter_mut()
+                    .map(|comb| comb.comb(input, feedback, damping))
+                    .sum();
+                let reverberated = all_passes
+                    .iter_mut()
+                    .fold(reverberated, |sound, all_pass| all_pass.all_pass(sound));
+                *sample = *sample * (1.0 - mix) + reverberated * 3.0 * mix;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompressorParameters {
+    threshold: Parameter,
+    ratio: Parameter,
+    attack: Parameter,
+    release: Parameter,
+    makeup_gain: Parameter,
+}
+
+/// An effect reducing the dynamic range of the sound, lowering its volume when it is louder than
+/// a threshold.
+#[derive(Clone, Debug)]
+pub struct Compressor {
+    parameters: Arc<CompressorParameters>,
+    /// The level of the sound, following its peaks
+    envelope: f32,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(-20.0, 4.0, 0.01, 0.1)
+    }
+}
+
+impl Compressor {
+    /// Creates a compressor dividing by the `ratio` the level of the sound above the `threshold`
+    /// in decibels, within `attack` seconds once it gets louder and `release` seconds once it gets
+    /// quieter.
+    pub fn new(threshold: f32, ratio: f32, attack: f32, release: f32) -> Self {
+        Self {
+            parameters: Arc::new(CompressorParameters {
+                threshold: Parameter::new(threshold),
+                ratio: Parameter::new(ratio),
+                attack: Parameter::new(attack),
+                release: Parameter::new(release),
+                makeup_gain: Parameter::new(0.0),
+            }),
+            envelope: 0.0,
+        }
+    }
+
+    /// Helper to set the gain applied to the compressed sound, in decibels.
+    pub fn with_makeup_gain(self, makeup_gain: f32) -> Self {
+        self.set_makeup_gain(makeup_gain);
+        self
+    }
+
+    /// Gets the level above which the sound is compressed, in decibels.
+    pub fn threshold(&self) -> f32 {
+        self.parameters.threshold.get()
+    }
+
+    /// Changes the level above which the sound is compressed, in decibels.
+    pub fn set_threshold(&self, threshold: f32) {
+        self.parameters.threshold.set(threshold);
+    }
+
+    /// Gets the ratio dividing the level of the sound above the threshold.
+    pub fn ratio(&self) -> f32 {
+        self.parameters.ratio.get()
+    }
+
+    /// Changes the ratio dividing the level of the sound above the threshold.
+    pub fn set_ratio(&self, ratio: f32) {
+        self.parameters.ratio.set(ratio);
+    }
+
+    /// Gets the time the compressor takes to react to a louder sound, in seconds.
+    pub fn attack(&self) -> f32 {
+        self.parameters.attack.get()
+    }
+
+    /// Changes the time the compressor takes to react to a louder sound, in seconds.
+    pub fn set_attack(&self, attack: f32) {
+        self.parameters.attack.set(attack);
+    }
+
+    /// Gets the time the compressor takes to react to a quieter sound, in seconds.
+    pub fn release(&self) -> f32 {
+        self.parameters.release.get()
+    }
+
+    /// Changes the time the compressor takes to react to a quieter sound, in seconds.
+    pub fn set_release(&self, release: f32) {
+        self.parameters.release.set(release);
+    }
+
+    /// Gets the gain applied to the compressed sound, in decibels.
+    pub fn makeup_gain(&self) -> f32 {
+        self.parameters.makeup_gain.get()
+    }
+
+    /// Changes the gain applied to the compressed sound, in decibels.
+    pub fn set_makeup_gain(&self, makeup_gain: f32) {
+        self.parameters.makeup_gain.set(makeup_gain);
+    }
+}
+
+impl AudioEffect for Compressor {
+    fn process(&mut self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        let sample_rate = sample_rate as f32;
+        let attack = (-1.0 / (self.attack() * sample_rate).max(1.0)).exp();
+        let release = (-1.0 / (self.release() * sample_rate).max(1.0)).exp();
+        let (threshold, ratio) = (self.threshold(), self.ratio().max(1.0));
+        let makeup_gain = self.makeup_gain();
+        for frame in samples.chunks_mut(channels as usize) {
+            // The channels are compressed together, to keep their balance
+            let level = frame
+                .iter()
+                .fold(0.0, |level: f32, sample| level.max(sample.abs()));
+            let coefficient = if level > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = level + coefficient * (self.envelope - level);
+
+            let over = 20.0 * self.envelope.max(1e-9).log10() - threshold;
+            let reduction = over.max(0.0) * (1.0 - 1.0 / ratio);
+            let gain = 10.0_f32.powf((makeup_gain - reduction) / 20.0);
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The peak amplitude of a sine wave of a frequency once filtered, at 44100 Hz.
+    fn filtered_amplitude(mut effect: impl AudioEffect, frequency: f32) -> f32 {
+        let mut samples: Vec<f32> = (0..4410)
+            .map(|index| (2.0 * PI * frequency * index as f32 / 44_100.0).sin())
+            .collect();
+        effect.process(&mut samples, 1, 44_100);
+        // Once the filter settled
+        samples[2205..]
+            .iter()
+            .fold(0.0, |peak, sample| sample.abs().max(peak))
+    }
+
+    #[test]
+    fn filters() {
+        let low_pass = LowPassFilter::new(1_000.0);
+        assert!(filtered_amplitude(low_pass.clone(), 100.0) > 0.95);
+        assert!(filtered_amplitude(low_pass.clone(), 10_000.0) < 0.02);
+        // The clones of the filter share its cutoff
+        low_pass.set_cutoff(20_000.0);
+        assert!(filtered_amplitude(low_pass, 10_000.0) > 0.95);
+
+        let high_pass = HighPassFilter::new(1_000.0);
+        assert!(filtered_amplitude(high_pass.clone(), 100.0) < 0.02);
+        assert!(filtered_amplitude(high_pass, 10_000.0) > 0.95);
+    }
+
+    #[test]
+    fn delay_echoes() {
+        let mut delay = Delay::new(2.0, 0.5, 0.5);
+        let mut samples = vec![0.0; 8];
+        samples[0] = 1.0;
+        delay.process(&mut samples, 1, 1);
+        assert_eq!(samples, [0.5, 0.0, 0.5, 0.0, 0.25, 0.0, 0.125, 0.0]);
+    }
+}
@@ -0,0 +1,208 @@
+use crossbeam_channel::{Receiver, Sender};
+use rodio::Source;
+use std::time::Duration;
+
+/// The number of frames generated at once by an [`AudioGenerator`].
+const GENERATOR_BLOCK_FRAMES: usize = 128;
+
+/// A sound synthesized on the audio thread, played with
+/// [`Audio::play_generator`](crate::Audio::play_generator).
+///
+/// Systems control the sound by sending it [`Message`](Self::Message)s through the
+/// [`AudioGeneratorSender`], which are received before it generates its next samples.
+///
+/// ```
+/// # use bevy_audio::AudioGenerator;
+/// // A sine wave, whose frequency can be changed from the systems
+/// struct Tone {
+///     frequency: f32,
+///     phase: f32,
+/// }
+///
+/// impl AudioGenerator for Tone {
+///     type Message = f32;
+///
+///     fn channels(&self) -> u16 {
+///         1
+///     }
+///
+///     fn sample_rate(&self) -> u32 {
+///         44_100
+///     }
+///
+///     fn receive(&mut self, frequency: f32) {
+///         self.frequency = frequency;
+///     }
+///
+///     fn generate(&mut self, samples: &mut [f32]) -> bool {
+///         for sample in samples {
+///             *sample = (self.phase * std::f32::consts::TAU).sin() * 0.2;
+///             self.phase = (self.phase + self.frequency / 44_100.0).fract();
+///         }
+///         true
+///     }
+/// }
+/// ```
+pub trait AudioGenerator: Send + 'static {
+    /// The type of the messages sent to the sound by the systems.
+    type Message: Send + 'static;
+
+    /// The number of channels of the generated samples.
+    fn channels(&self) -> u16;
+
+    /// The sample rate of the generated samples.
+    fn sample_rate(&self) -> u32;
+
+    /// Receives a message sent by a system, before the next samples are generated.
+    fn receive(&mut self, message: Self::Message) {
+        let _ = message;
+    }
+
+    /// Fills a block of interleaved samples, initially silent.
+    ///
+    /// Returns `false` once the sound reached its end, after this block.
+    fn generate(&mut self, samples: &mut [f32]) -> bool;
+}
+
+/// Sends messages to the [`AudioGenerator`] of a sound, from the systems.
+pub struct AudioGeneratorSender<M> {
+    sender: Sender<M>,
+}
+
+impl<M> Clone for AudioGeneratorSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<M> AudioGeneratorSender<M> {
+    /// Sends a message to the generator, received before it generates its next samples.
+    ///
+    /// Returns `false` if the sound stopped playing.
+    pub fn send(&self, message: M) -> bool {
+        self.sender.send(message).is_ok()
+    }
+}
+
+/// The sound of an [`AudioGenerator`], generated block by block.
+pub(crate) struct Generated<G: AudioGenerator> {
+    generator: G,
+    messages: Receiver<G::Message>,
+    channels: u16,
+    sample_rate: u32,
+    block: Vec<f32>,
+    position: usize,
+    playing: bool,
+}
+
+impl<G: AudioGenerator> Generated<G> {
+    pub(crate) fn new(generator: G) -> (Self, AudioGeneratorSender<G::Message>) {
+        let (sender, messages) = crossbeam_channel::unbounded();
+        let generated = Self {
+            channels: generator.channels(),
+            sample_rate: generator.sample_rate(),
+            generator,
+            messages,
+            block: Vec::new(),
+            position: 0,
+            playing: true,
+        };
+        (generated, AudioGeneratorSender { sender })
+    }
+}
+
+impl<G: AudioGenerator> Iterator for Generated<G> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position == self.block.len() {
+            if !self.playing {
+                return None;
+            }
+            for message in self.messages.try_iter() {
+                self.generator.receive(message);
+            }
+            self.block.clear();
+            self.block
+                .resize(GENERATOR_BLOCK_FRAMES * self.channels as usize, 0.0);
+            self.playing = self.generator.generate(&mut self.block);
+            self.position = 0;
+        }
+        let sample = self.block[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl<G: AudioGenerator> Source for Generated<G> {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts its samples up to a limit, sent as a message
+    struct Counter {
+        count: f32,
+        limit: f32,
+    }
+
+    impl AudioGenerator for Counter {
+        type Message = f32;
+
+        fn channels(&self) -> u16 {
+            1
+        }
+
+        fn sample_rate(&self) -> u32 {
+            100
+        }
+
+        fn receive(&mut self, limit: f32) {
+            self.limit = limit;
+        }
+
+        fn generate(&mut self, samples: &mut [f32]) -> bool {
+            for sample in samples {
+                *sample = self.count;
+                self.count += 1.0;
+            }
+            self.count < self.limit
+        }
+    }
+
+    #[test]
+    fn generator_messages() {
+        let counter = Counter {
+            count: 0.0,
+            limit: f32::INFINITY,
+        };
+        let (mut generated, sender) = Generated::new(counter);
+        assert_eq!(generated.nth(10), Some(10.0));
+
+        // The message is received with the next block, which is the last one
+        assert!(sender.send(200.0));
+        let block = GENERATOR_BLOCK_FRAMES as f32;
+        assert_eq!(generated.by_ref().last(), Some(2.0 * block - 1.0));
+
+        drop(generated);
+        assert!(!sender.send(0.0));
+    }
+}
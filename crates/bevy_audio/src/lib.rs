@@ -22,19 +22,36 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod analysis;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod bus;
+mod effect;
+mod generator;
+mod playback;
+mod spatial;
+mod streaming;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable, PlaybackSettings};
+    pub use crate::{
+        Audio, AudioAnalysis, AudioBus, AudioBuses, AudioEmitter, AudioGenerator, AudioListener,
+        AudioOutput, AudioSource, AudioVelocity, Decodable, DistanceModel, PlaybackSettings,
+    };
 }
 
+pub use analysis::*;
 pub use audio::*;
 pub use audio_output::*;
 pub use audio_source::*;
+pub use bus::*;
+pub use effect::*;
+pub use generator::*;
+pub use playback::*;
+pub use spatial::*;
+pub use streaming::*;
 
 pub use rodio::cpal::Sample as CpalSample;
 pub use rodio::source::Source;
@@ -42,23 +59,45 @@ pub use rodio::Sample;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Asset};
+use bevy_ecs::prelude::*;
+use bevy_transform::TransformSystem;
 
 /// Adds support for audio playback to a Bevy Application
 ///
-/// Use the [`Audio`] resource to play audio.
+/// Use the [`Audio`] resource to play audio, and the [`AudioBuses`] resource to control the buses
+/// it is mixed in.
 #[derive(Default)]
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AudioOutput<AudioSource>>()
+        app.init_resource::<AudioBuses>()
+            .init_resource::<AudioOutput<AudioSource>>()
             .add_asset::<AudioSource>()
             .add_asset::<AudioSink>()
             .init_resource::<Audio<AudioSource>>()
+            .init_resource::<SpatialSounds>()
+            .init_resource::<AudioAnalysis>()
+            .add_event::<PlaybackFinished>()
+            .register_type::<AudioEmitter>()
+            .register_type::<AudioListener>()
+            .register_type::<AudioVelocity>()
+            .register_type::<DistanceModel>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                play_queued_audio_system::<AudioSource>,
-            );
+                play_queued_audio_system::<AudioSource>.before(update_spatial_audio_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_playback_system::<AudioSource>
+                    .after(play_queued_audio_system::<AudioSource>),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, update_audio_analysis_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_spatial_audio_system.after(TransformSystem::TransformPropagate),
+            )
+            .add_audio_source::<StreamingAudioSource>();
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         app.init_asset_loader::<AudioLoader>();
@@ -73,6 +112,13 @@ impl AddAudioSource for App {
         self.add_asset::<T>()
             .init_resource::<Audio<T>>()
             .init_resource::<AudioOutput<T>>()
-            .add_system_to_stage(CoreStage::PostUpdate, play_queued_audio_system::<T>)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                play_queued_audio_system::<T>.before(update_spatial_audio_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_playback_system::<T>.after(play_queued_audio_system::<T>),
+            )
     }
 }
@@ -0,0 +1,355 @@
+use crate::{AudioSink, Decodable};
+use bevy_asset::Handle;
+use parking_lot::Mutex;
+use rodio::Source;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// An event sent when a sound played from the [`Audio`](crate::Audio) resource reaches its end.
+///
+/// It isn't sent for the sounds that loop or are stopped.
+///
+/// ```
+/// # use bevy_ecs::event::EventReader;
+/// # use bevy_audio::PlaybackFinished;
+/// fn on_sound_finished(mut finished_events: EventReader<PlaybackFinished>) {
+///     for event in finished_events.iter() {
+///         println!("{:?} finished playing", event.sink);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PlaybackFinished {
+    /// The sink the sound played in, as returned by [`Audio::play`](crate::Audio::play)
+    pub sink: Handle<AudioSink>,
+}
+
+/// A decoder of a sound converted to `f32` samples
+pub(crate) type PlaybackDecoder = Box<dyn Source<Item = f32> + Send>;
+
+/// Builds a decoder of the sound starting at the position, in seconds.
+///
+/// Decoders can't seek, so the sound is decoded up to the position.
+pub(crate) fn decoder_at<S: Decodable>(audio_source: &S, position: f64) -> PlaybackDecoder {
+    let mut decoder = audio_source.decoder().convert_samples::<f32>();
+    let frames = (position * decoder.sample_rate() as f64) as usize;
+    let samples = frames * decoder.channels() as usize;
+    decoder.by_ref().take(samples).for_each(drop);
+    Box::new(decoder)
+}
+
+/// A time in seconds, shared with the audio thread.
+struct Seconds(AtomicU64);
+
+impl Seconds {
+    fn new(value: f64) -> Self {
+        Self(AtomicU64::new(value.to_bits()))
+    }
+
+    fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// The controls of the playback of a sound, shared between its [`AudioSink`] and the audio
+/// thread.
+///
+/// The decoders needed to seek and loop are built by
+/// [`update_playback_system`](crate::update_playback_system), and handed to the audio thread to
+/// jump to them at the start of a frame.
+pub(crate) struct PlaybackControls {
+    position: Seconds,
+    /// The start of the loop, or NaN when the sound doesn't loop
+    loop_start: Seconds,
+    /// The end of the loop, infinite to loop at the end of the sound
+    loop_end: Seconds,
+    /// The position to seek to, waiting for its decoder
+    seek: Mutex<Option<f64>>,
+    /// The decoder to jump to, with its position
+    jump: Mutex<Option<(PlaybackDecoder, f64)>>,
+    /// The decoder starting at the start of the loop, built in advance to loop seamlessly
+    loop_decoder: Mutex<Option<(PlaybackDecoder, f64)>>,
+    /// Whether the sound reached its end
+    finished: AtomicBool,
+    /// Whether the sound stopped playing, because it finished or was stopped
+    ended: AtomicBool,
+    /// Whether the sound can seek and loop, which generated sounds can't
+    seekable: bool,
+}
+
+impl PlaybackControls {
+    pub(crate) fn new(repeat: bool) -> Self {
+        let controls = Self {
+            position: Seconds::new(0.0),
+            loop_start: Seconds::new(f64::NAN),
+            loop_end: Seconds::new(f64::INFINITY),
+            seek: Mutex::new(None),
+            jump: Mutex::new(None),
+            loop_decoder: Mutex::new(None),
+            finished: AtomicBool::new(false),
+            ended: AtomicBool::new(false),
+            seekable: true,
+        };
+        if repeat {
+            controls.set_loop(0.0, f64::INFINITY);
+        }
+        controls
+    }
+
+    /// The controls of a sound generated by an [`AudioGenerator`](crate::AudioGenerator).
+    pub(crate) fn generated() -> Self {
+        Self {
+            seekable: false,
+            ..Self::new(false)
+        }
+    }
+
+    pub(crate) fn position(&self) -> f64 {
+        self.position.get()
+    }
+
+    pub(crate) fn seek(&self, position: f64) {
+        if self.seekable {
+            *self.seek.lock() = Some(position);
+        }
+    }
+
+    pub(crate) fn set_loop(&self, start: f64, end: f64) {
+        if !self.seekable {
+            return;
+        }
+        // The end is set first, for the audio thread to never see a loop ending before its start
+        self.loop_end.set(end);
+        self.loop_start.set(start);
+    }
+
+    pub(crate) fn clear_loop(&self) {
+        self.loop_start.set(f64::NAN);
+        self.loop_end.set(f64::INFINITY);
+    }
+
+    pub(crate) fn loop_points(&self) -> Option<(f64, f64)> {
+        let start = self.loop_start.get();
+        (!start.is_nan()).then(|| (start, self.loop_end.get()))
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_ended(&self) -> bool {
+        self.ended.load(Ordering::Relaxed)
+    }
+
+    /// Builds the decoders to seek and to loop, away from the audio thread.
+    pub(crate) fn prepare<S: Decodable>(&self, audio_source: &S) {
+        if let Some(position) = self.seek.lock().take() {
+            *self.jump.lock() = Some((decoder_at(audio_source, position), position));
+        }
+
+        let loop_start = self.loop_points().map(|(start, _)| start);
+        let prepared_start = self.loop_decoder.lock().as_ref().map(|(_, start)| *start);
+        if loop_start != prepared_start {
+            let loop_decoder = loop_start.map(|start| (decoder_at(audio_source, start), start));
+            // The previous decoder is dropped here rather than on the audio thread
+            let _previous = std::mem::replace(&mut *self.loop_decoder.lock(), loop_decoder);
+        }
+    }
+}
+
+/// A sound controlled by its [`PlaybackControls`], that can seek, loop and track its position.
+pub(crate) struct Playback {
+    input: PlaybackDecoder,
+    controls: Arc<PlaybackControls>,
+    /// The position of the start of the input, in seconds
+    start: f64,
+    /// The number of samples played from the input
+    samples: u64,
+    /// The channel of the next sample
+    channel: u16,
+}
+
+impl Playback {
+    pub(crate) fn new(input: PlaybackDecoder, controls: Arc<PlaybackControls>) -> Self {
+        Self {
+            input,
+            controls,
+            start: 0.0,
+            samples: 0,
+            channel: 0,
+        }
+    }
+
+    fn input_position(&self) -> f64 {
+        let frames = self.samples / self.input.channels().max(1) as u64;
+        self.start + frames as f64 / self.input.sample_rate() as f64
+    }
+
+    fn jump(&mut self, input: PlaybackDecoder, position: f64) {
+        self.input = input;
+        self.start = position;
+        self.samples = 0;
+    }
+
+    /// Jumps to the start of the loop, if its decoder is ready.
+    fn try_loop(&mut self) -> bool {
+        let loop_decoder = self
+            .controls
+            .loop_decoder
+            .try_lock()
+            .and_then(|mut loop_decoder| loop_decoder.take());
+        match loop_decoder {
+            Some((input, start)) => {
+                self.jump(input, start);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Seeks and loops at the start of a frame, and shares the position.
+    fn start_frame(&mut self) {
+        let jump = self
+            .controls
+            .jump
+            .try_lock()
+            .and_then(|mut jump| jump.take());
+        if let Some((input, position)) = jump {
+            self.jump(input, position);
+        }
+        if let Some((_, end)) = self.controls.loop_points() {
+            if self.input_position() >= end {
+                self.try_loop();
+            }
+        }
+        self.controls.position.set(self.input_position());
+    }
+}
+
+impl Iterator for Playback {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            self.start_frame();
+        }
+        let sample = match self.input.next() {
+            Some(sample) => {
+                self.samples += 1;
+                sample
+            }
+            None if self.controls.loop_points().is_some() => {
+                if self.channel == 0 && self.try_loop() {
+                    return self.next();
+                }
+                // The decoder of the loop isn't ready yet
+                0.0
+            }
+            None => {
+                self.controls.finished.store(true, Ordering::Relaxed);
+                return None;
+            }
+        };
+        self.channel = (self.channel + 1) % self.input.channels().max(1);
+        Some(sample)
+    }
+}
+
+impl Source for Playback {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Drop for Playback {
+    fn drop(&mut self) {
+        self.controls.ended.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    struct Ramp;
+
+    impl Decodable for Ramp {
+        type DecoderItem = f32;
+        type Decoder = SamplesBuffer<f32>;
+
+        fn decoder(&self) -> Self::Decoder {
+            SamplesBuffer::new(
+                1,
+                10,
+                (0..20).map(|sample| sample as f32).collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    #[test]
+    fn seek_and_loop() {
+        let controls = Arc::new(PlaybackControls::new(false));
+        let mut playback = Playback::new(decoder_at(&Ramp, 0.0), controls.clone());
+        assert_eq!(playback.nth(2), Some(2.0));
+
+        controls.seek(1.5);
+        controls.prepare(&Ramp);
+        assert_eq!(playback.next(), Some(15.0));
+        assert_eq!(playback.next(), Some(16.0));
+        assert!((controls.position() - 1.6).abs() < 1e-9);
+
+        // The loop decoder is ready, so the sound loops seamlessly at the end of the loop
+        controls.set_loop(0.5, 1.8);
+        controls.prepare(&Ramp);
+        let samples: Vec<f32> = playback.by_ref().take(5).collect();
+        assert_eq!(samples, vec![17.0, 5.0, 6.0, 7.0, 8.0]);
+
+        // It plays until its end once the loop is cleared
+        controls.clear_loop();
+        controls.prepare(&Ramp);
+        assert_eq!(playback.by_ref().count(), 11);
+        assert!(controls.is_finished());
+        drop(playback);
+        assert!(controls.is_ended());
+    }
+
+    #[test]
+    fn repeat() {
+        let controls = Arc::new(PlaybackControls::new(true));
+        let mut playback = Playback::new(decoder_at(&Ramp, 0.0), controls.clone());
+        controls.prepare(&Ramp);
+        assert_eq!(playback.nth(20), Some(0.0));
+        assert_eq!(playback.next(), Some(1.0));
+
+        // Silence plays until the next loop is prepared
+        assert_eq!(playback.nth(17), Some(19.0));
+        assert_eq!(playback.next(), Some(0.0));
+        assert_eq!(controls.position(), 2.0);
+        controls.prepare(&Ramp);
+        assert_eq!(playback.next(), Some(0.0));
+        assert_eq!(playback.next(), Some(1.0));
+        assert!(!controls.is_finished());
+    }
+}
@@ -0,0 +1,540 @@
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use rodio::Source;
+use std::{
+    collections::VecDeque,
+    f32::consts::{FRAC_PI_4, PI},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The speed of sound in the air, in meters per second.
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// The cutoff frequency of the ear away from a sound coming from the side of the listener, the
+/// head muffling the higher frequencies of the sound.
+const HEAD_SHADOW_CUTOFF: f32 = 2_000.0;
+
+/// The number of frames between two updates of the mix of a spatial sound on the audio thread,
+/// its gains and delays being interpolated from one update to the next.
+const MIX_UPDATE_FRAMES: u32 = 256;
+
+/// An entity playing spatial sounds from the position of its [`GlobalTransform`].
+///
+/// Sounds are played from an emitter with
+/// [`PlaybackSettings::with_emitter`](crate::PlaybackSettings::with_emitter).
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioEmitter {
+    /// The distance from the listener up to which the sounds of the emitter play at their full
+    /// volume. Further away, their volume decreases following the distance model.
+    pub reference_distance: f32,
+    /// How the volume of the sounds of the emitter decreases beyond the reference distance
+    pub distance_model: DistanceModel,
+}
+
+impl Default for AudioEmitter {
+    fn default() -> Self {
+        Self {
+            reference_distance: 1.0,
+            distance_model: DistanceModel::Inverse,
+        }
+    }
+}
+
+/// How the volume of the sounds of an [`AudioEmitter`] decreases with their distance from the
+/// listener, beyond the reference distance of the emitter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, FromReflect)]
+pub enum DistanceModel {
+    /// The volume is inversely proportional to the distance, like sounds in the open air.
+    #[default]
+    Inverse,
+    /// The volume decreases linearly with the distance, down to silence at a maximum distance.
+    Linear {
+        /// The distance from the listener from which the sounds are silent
+        max_distance: f32,
+    },
+    /// The volume is inversely proportional to the distance raised to the power of the rolloff,
+    /// higher rolloffs fading the sounds faster.
+    Exponential {
+        /// The exponent of the distance
+        rolloff: f32,
+    },
+    /// The volume doesn't decrease with the distance, for sounds heard from anywhere.
+    Constant,
+}
+
+impl DistanceModel {
+    /// The gain of a sound at a distance from the listener, for an emitter with a reference
+    /// distance.
+    pub fn attenuation(&self, distance: f32, reference_distance: f32) -> f32 {
+        let distance = distance.max(reference_distance);
+        match *self {
+            DistanceModel::Inverse => reference_distance / distance,
+            DistanceModel::Linear { max_distance } => {
+                if distance >= max_distance {
+                    0.0
+                } else {
+                    (max_distance - distance) / (max_distance - reference_distance)
+                }
+            }
+            DistanceModel::Exponential { rolloff } => {
+                (distance / reference_distance).powf(-rolloff)
+            }
+            DistanceModel::Constant => 1.0,
+        }
+    }
+}
+
+/// The velocity of an [`AudioEmitter`] or of the [`AudioListener`], shifting the pitch of the
+/// spatial sounds by the Doppler effect.
+///
+/// Without this component, the velocity is tracked from the movement of the [`GlobalTransform`]
+/// since the last frame. Use it for the entities whose velocity is known, like from a physics
+/// engine, or that teleport.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioVelocity(pub Vec3);
+
+/// The entity hearing the spatial sounds, usually the camera, positioned and oriented by its
+/// [`GlobalTransform`]: its right ear is along its local X axis, and it faces its local -Z axis.
+///
+/// Only the first listener is used. Without one, the sounds are heard from the origin.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct AudioListener {
+    /// The distance between the ears of the listener
+    pub ear_gap: f32,
+    /// Approximates a head-related transfer function: on top of being quieter in the ear away from
+    /// a sound, the sound reaches it later and muffled by the head.
+    pub hrtf: bool,
+    /// Scales the velocities of the listener and the emitters for the Doppler effect: `0.0`
+    /// disables it, and values above `1.0` exaggerate it, like in worlds smaller than their units.
+    pub doppler_factor: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        Self {
+            ear_gap: 0.2,
+            hrtf: false,
+            doppler_factor: 1.0,
+        }
+    }
+}
+
+/// How a spatial sound is heard by the left and right ears of the listener.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SpatialMix {
+    pub(crate) gains: [f32; 2],
+    /// The delays of the sound reaching the ears, in seconds
+    pub(crate) delays: [f32; 2],
+    /// The cutoff frequencies of the low-pass filters of the ears, in hertz
+    pub(crate) cutoffs: [f32; 2],
+    /// The ratio of the pitch heard to the one of the sound, from the Doppler effect
+    pub(crate) pitch: f32,
+}
+
+impl SpatialMix {
+    /// The mix of a sound of the emitter, at the position relative to the listener in the space of
+    /// the listener, with the velocities of the emitter and the listener in the same space.
+    pub(crate) fn new(
+        listener: &AudioListener,
+        emitter: &AudioEmitter,
+        position: Vec3,
+        emitter_velocity: Vec3,
+        listener_velocity: Vec3,
+    ) -> Self {
+        let distance = position.length();
+        let attenuation = emitter
+            .distance_model
+            .attenuation(distance, emitter.reference_distance);
+        // The sine of the azimuth of the sound, from -1 on the left to 1 on the right
+        let pan = if distance > 0.0 {
+            (position.x / distance).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        // Equal-power panning, a centered sound is as loud in both ears
+        let angle = (pan + 1.0) * FRAC_PI_4;
+        let gains = [angle.cos() * attenuation, angle.sin() * attenuation];
+
+        // The speeds along the line from the listener to the emitter, clamped below the speed of
+        // sound to keep the pitch within a few octaves
+        let pitch = if distance > 0.0 {
+            let direction = position / distance;
+            let max_speed = SPEED_OF_SOUND / 2.0;
+            let speed = |velocity: Vec3| {
+                (velocity.dot(direction) * listener.doppler_factor).clamp(-max_speed, max_speed)
+            };
+            (SPEED_OF_SOUND + speed(listener_velocity)) / (SPEED_OF_SOUND + speed(emitter_velocity))
+        } else {
+            1.0
+        };
+        if !listener.hrtf {
+            return Self {
+                gains,
+                delays: [0.0; 2],
+                cutoffs: [f32::INFINITY; 2],
+                pitch,
+            };
+        }
+
+        // The sound goes around the head to reach the far ear, following Woodworth's formula
+        let side = pan.abs();
+        let delay = listener.ear_gap / 2.0 / SPEED_OF_SOUND * (side.asin() + side);
+        let cutoff = HEAD_SHADOW_CUTOFF / side;
+        let (delays, cutoffs) = if pan < 0.0 {
+            ([0.0, delay], [f32::INFINITY, cutoff])
+        } else {
+            ([delay, 0.0], [cutoff, f32::INFINITY])
+        };
+        Self {
+            gains,
+            delays,
+            cutoffs,
+            pitch,
+        }
+    }
+}
+
+/// A sound played from an [`AudioEmitter`], whose mix is shared with the audio thread.
+struct SpatialSound {
+    emitter: Entity,
+    mix: Arc<Mutex<Option<SpatialMix>>>,
+}
+
+/// The sounds playing from [`AudioEmitter`]s, mixed for the [`AudioListener`] every frame.
+#[derive(Resource, Default)]
+pub struct SpatialSounds {
+    sounds: Vec<SpatialSound>,
+    /// The positions of the listener and the emitters at the last frame, to track their velocity
+    last_positions: HashMap<Entity, Vec3>,
+}
+
+impl SpatialSounds {
+    /// Plays the source from the emitter, once mixed by [`update_spatial_audio_system`].
+    pub(crate) fn spatialize<I>(&mut self, input: I, emitter: Entity) -> Spatial<I>
+    where
+        I: Source<Item = f32>,
+    {
+        let mix = Arc::new(Mutex::new(None));
+        self.sounds.push(SpatialSound {
+            emitter,
+            mix: mix.clone(),
+        });
+        Spatial {
+            input,
+            mix,
+            from: None,
+            to: None,
+            frame: 0,
+            history: VecDeque::from(vec![0.0; 2]),
+            phase: 0.0,
+            coefficients: [0.0; 2],
+            filtered: [0.0; 2],
+            right: None,
+        }
+    }
+}
+
+/// Updates the mix of the sounds playing from [`AudioEmitter`]s, from their position and velocity
+/// relative to the [`AudioListener`].
+///
+/// The sounds of an entity without an [`AudioEmitter`] keep their last mix, and play where the
+/// listener is if they were never mixed.
+pub fn update_spatial_audio_system(
+    time: Res<Time>,
+    listeners: Query<(
+        Entity,
+        &AudioListener,
+        &GlobalTransform,
+        Option<&AudioVelocity>,
+    )>,
+    emitters: Query<(&AudioEmitter, &GlobalTransform, Option<&AudioVelocity>)>,
+    mut spatial_sounds: ResMut<SpatialSounds>,
+) {
+    let spatial_sounds = &mut *spatial_sounds;
+    let last_positions = std::mem::take(&mut spatial_sounds.last_positions);
+    let delta_seconds = time.delta_seconds();
+    // Tracks the velocity of an entity from its last position, unless it has an `AudioVelocity`
+    let mut track = |entity: Entity, position: Vec3, velocity: Option<&AudioVelocity>| {
+        spatial_sounds.last_positions.insert(entity, position);
+        match (velocity, last_positions.get(&entity)) {
+            (Some(velocity), _) => velocity.0,
+            (None, Some(last_position)) if delta_seconds > 0.0 => {
+                (position - *last_position) / delta_seconds
+            }
+            _ => Vec3::ZERO,
+        }
+    };
+
+    let (listener, listener_transform, listener_velocity) = listeners
+        .iter()
+        .next()
+        .map(|(entity, listener, transform, velocity)| {
+            let transform = transform.compute_transform();
+            let velocity = track(entity, transform.translation, velocity);
+            (*listener, transform, velocity)
+        })
+        .unwrap_or((AudioListener::default(), Transform::IDENTITY, Vec3::ZERO));
+    let inverse_rotation = listener_transform.rotation.inverse();
+    let listener_velocity = inverse_rotation * listener_velocity;
+
+    // The audio thread drops the sounds that finished playing
+    spatial_sounds
+        .sounds
+        .retain(|sound| Arc::strong_count(&sound.mix) > 1);
+    for sound in &spatial_sounds.sounds {
+        let (emitter, position, velocity) = match emitters.get(sound.emitter) {
+            Ok((emitter, transform, velocity)) => {
+                let position = transform.translation();
+                (*emitter, position, track(sound.emitter, position, velocity))
+            }
+            Err(_) if sound.mix.lock().is_some() => continue,
+            Err(_) => (
+                AudioEmitter::default(),
+                listener_transform.translation,
+                Vec3::ZERO,
+            ),
+        };
+        let position = inverse_rotation * (position - listener_transform.translation);
+        let velocity = inverse_rotation * velocity;
+        *sound.mix.lock() = Some(SpatialMix::new(
+            &listener,
+            &emitter,
+            position,
+            velocity,
+            listener_velocity,
+        ));
+    }
+}
+
+/// A source downmixed to mono and played in stereo with the [`SpatialMix`] of its emitter, silent
+/// until it is first mixed.
+///
+/// The input is resampled by the pitch of the mix, read between its last two frames.
+pub(crate) struct Spatial<I> {
+    input: I,
+    mix: Arc<Mutex<Option<SpatialMix>>>,
+    /// The mixes at the start and at the end of the current block of frames
+    from: Option<SpatialMix>,
+    to: Option<SpatialMix>,
+    /// The index of the current frame in its block
+    frame: u32,
+    /// The last mono samples of the input, the latest first, to delay them
+    history: VecDeque<f32>,
+    /// The position of the read between the last two frames of the input
+    phase: f32,
+    /// The coefficients of the one-pole low-pass filters of the ears
+    coefficients: [f32; 2],
+    filtered: [f32; 2],
+    /// The sample of the right ear, once the one of the left ear is returned
+    right: Option<f32>,
+}
+
+impl<I> Spatial<I>
+where
+    I: Source<Item = f32>,
+{
+    fn update_mix(&mut self) {
+        self.from = self.to;
+        if let Some(mix) = *self.mix.lock() {
+            self.to = Some(mix);
+        }
+        let Some(to) = self.to else {
+            return;
+        };
+        let from = *self.from.get_or_insert(to);
+
+        let sample_rate = self.input.sample_rate() as f32;
+        for (coefficient, cutoff) in self.coefficients.iter_mut().zip(to.cutoffs) {
+            *coefficient = (-2.0 * PI * cutoff / sample_rate).exp();
+        }
+        let max_delay = from.delays.into_iter().chain(to.delays).fold(0.0, f32::max);
+        let history_len = (max_delay * sample_rate).ceil() as usize + 2;
+        if self.history.len() < history_len {
+            self.history.resize(history_len, 0.0);
+        }
+    }
+
+    /// The mono input delayed by a number of samples, interpolated between them.
+    fn delayed(&self, delay: f32) -> f32 {
+        let index = delay as usize;
+        let fraction = delay - index as f32;
+        let sample = |index| self.history.get(index).copied().unwrap_or(0.0);
+        sample(index) * (1.0 - fraction) + sample(index + 1) * fraction
+    }
+}
+
+impl<I> Iterator for Spatial<I>
+where
+    I: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(right) = self.right.take() {
+            return Some(right);
+        }
+
+        let t = self.frame as f32 / MIX_UPDATE_FRAMES as f32;
+        if self.frame == 0 {
+            self.update_mix();
+        }
+        self.frame = (self.frame + 1) % MIX_UPDATE_FRAMES;
+        let (Some(from), Some(to)) = (self.from, self.to) else {
+            self.right = Some(0.0);
+            return Some(0.0);
+        };
+
+        self.phase += from.pitch + (to.pitch - from.pitch) * t;
+        let channels = self.input.channels().max(1);
+        while self.phase >= 1.0 {
+            self.phase -= 1.0;
+            let mut sum = 0.0;
+            for _ in 0..channels {
+                sum += self.input.next()?;
+            }
+            self.history.push_front(sum / channels as f32);
+            self.history.pop_back();
+        }
+
+        let sample_rate = self.input.sample_rate() as f32;
+        let mut output = [0.0; 2];
+        for (ear, output) in output.iter_mut().enumerate() {
+            let gain = from.gains[ear] + (to.gains[ear] - from.gains[ear]) * t;
+            let delay = from.delays[ear] + (to.delays[ear] - from.delays[ear]) * t;
+            let sample = self.delayed(delay * sample_rate + 1.0 - self.phase);
+            let filtered = &mut self.filtered[ear];
+            *filtered = sample + self.coefficients[ear] * (*filtered - sample);
+            *output = *filtered * gain;
+        }
+        self.right = Some(output[1]);
+        Some(output[0])
+    }
+}
+
+impl<I> Source for Spatial<I>
+where
+    I: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        let channels = self.input.channels().max(1) as usize;
+        self.input.current_frame_len().map(|len| len / channels * 2)
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn spatial_mix() {
+        let emitter = AudioEmitter::default();
+        let listener = AudioListener::default();
+        // Centered and within the reference distance, the sound is as loud in both ears
+        let front = SpatialMix::new(
+            &listener,
+            &emitter,
+            Vec3::new(0.0, 0.0, -0.5),
+            Vec3::ZERO,
+            Vec3::ZERO,
+        );
+        assert!((front.gains[0] - front.gains[1]).abs() < 1e-6);
+        assert!((front.gains[0] - FRAC_PI_4.cos()).abs() < 1e-6);
+
+        // On the right, four times further than the reference distance
+        let right = SpatialMix::new(
+            &listener,
+            &emitter,
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::ZERO,
+            Vec3::ZERO,
+        );
+        assert!(right.gains[0].abs() < 1e-6);
+        assert!((right.gains[1] - 0.25).abs() < 1e-6);
+        assert_eq!(right.delays, [0.0; 2]);
+
+        let hrtf_listener = AudioListener {
+            hrtf: true,
+            ..Default::default()
+        };
+        let left = SpatialMix::new(
+            &hrtf_listener,
+            &emitter,
+            Vec3::new(-2.0, 0.0, 0.0),
+            Vec3::ZERO,
+            Vec3::ZERO,
+        );
+        assert_eq!(left.delays[0], 0.0);
+        assert!(left.delays[1] > 0.0);
+        assert_eq!(left.cutoffs, [f32::INFINITY, HEAD_SHADOW_CUTOFF]);
+    }
+
+    #[test]
+    fn distance_models() {
+        let linear = DistanceModel::Linear { max_distance: 5.0 };
+        assert_eq!(linear.attenuation(0.5, 1.0), 1.0);
+        assert_eq!(linear.attenuation(3.0, 1.0), 0.5);
+        assert_eq!(linear.attenuation(6.0, 1.0), 0.0);
+        let exponential = DistanceModel::Exponential { rolloff: 2.0 };
+        assert_eq!(exponential.attenuation(4.0, 2.0), 0.25);
+        assert_eq!(DistanceModel::Constant.attenuation(100.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn doppler() {
+        let emitter = AudioEmitter::default();
+        let listener = AudioListener::default();
+        let position = Vec3::new(0.0, 0.0, -10.0);
+        // Approaching the listener at a tenth of the speed of sound
+        let approaching = Vec3::new(0.0, 0.0, SPEED_OF_SOUND / 10.0);
+        let mix = SpatialMix::new(&listener, &emitter, position, approaching, Vec3::ZERO);
+        assert!((mix.pitch - 10.0 / 9.0).abs() < 1e-6);
+        let mix = SpatialMix::new(&listener, &emitter, position, Vec3::ZERO, -approaching);
+        assert!((mix.pitch - 1.1).abs() < 1e-6);
+        // Passing by the listener doesn't shift the pitch
+        let passing = Vec3::new(SPEED_OF_SOUND / 10.0, 0.0, 0.0);
+        let mix = SpatialMix::new(&listener, &emitter, position, passing, Vec3::ZERO);
+        assert_eq!(mix.pitch, 1.0);
+
+        // The input is read faster when the pitch is higher
+        let mut spatial = SpatialSounds::default().spatialize(
+            SamplesBuffer::new(
+                1,
+                100,
+                (0..200).map(|sample| sample as f32).collect::<Vec<_>>(),
+            ),
+            Entity::from_raw(0),
+        );
+        *spatial.mix.lock() = Some(SpatialMix {
+            pitch: 2.0,
+            ..SpatialMix::new(&listener, &emitter, Vec3::ZERO, Vec3::ZERO, Vec3::ZERO)
+        });
+        assert_eq!(spatial.by_ref().count(), 200);
+    }
+}
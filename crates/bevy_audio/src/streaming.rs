@@ -0,0 +1,179 @@
+use crate::Decodable;
+use anyhow::Result;
+use bevy_asset::AssetServer;
+use bevy_reflect::TypeUuid;
+use bevy_utils::tracing::error;
+use crossbeam_channel::Receiver;
+use rodio::Source;
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+/// The number of frames decoded at once by the thread streaming a sound.
+const STREAM_CHUNK_FRAMES: usize = 1024;
+
+/// A sound streamed from its file while it plays, instead of being loaded at once like an
+/// [`AudioSource`](crate::AudioSource).
+///
+/// The file is read progressively with [`AssetIo::open_stream`](bevy_asset::AssetIo::open_stream),
+/// and decoded ahead of the playback on a background thread, so that only the buffers are kept in
+/// memory. This suits long music tracks, played with the `Audio<StreamingAudioSource>` resource.
+///
+/// ```
+/// # use bevy_ecs::system::{Res, ResMut};
+/// # use bevy_asset::{AssetServer, Assets};
+/// # use bevy_audio::{Audio, StreamingAudioSource};
+/// # use std::time::Duration;
+/// fn play_soundtrack_system(
+///     asset_server: Res<AssetServer>,
+///     mut streams: ResMut<Assets<StreamingAudioSource>>,
+///     audio: Res<Audio<StreamingAudioSource>>,
+/// ) {
+///     let soundtrack = StreamingAudioSource::new(&asset_server, "music/soundtrack.ogg")
+///         .with_decode_ahead(Duration::from_secs(2));
+///     audio.play(streams.add(soundtrack));
+/// }
+/// ```
+#[derive(Clone, TypeUuid)]
+#[uuid = "c433ff1a-bff7-4699-b7b3-cefa4c2d60f1"]
+pub struct StreamingAudioSource {
+    asset_server: AssetServer,
+    path: PathBuf,
+    /// The number of bytes read from the file at once.
+    pub read_buffer_size: usize,
+    /// The duration of the sound decoded ahead of the playback.
+    pub decode_ahead: Duration,
+}
+
+impl StreamingAudioSource {
+    /// Streams the file at the path, relative to the assets folder of the [`AssetServer`].
+    ///
+    /// It is read by blocks of 64 KiB, and decoded one second ahead of the playback.
+    pub fn new(asset_server: &AssetServer, path: impl Into<PathBuf>) -> Self {
+        Self {
+            asset_server: asset_server.clone(),
+            path: path.into(),
+            read_buffer_size: 64 * 1024,
+            decode_ahead: Duration::from_secs(1),
+        }
+    }
+
+    /// Helper to set the number of bytes read from the file at once.
+    pub fn with_read_buffer_size(mut self, read_buffer_size: usize) -> Self {
+        self.read_buffer_size = read_buffer_size;
+        self
+    }
+
+    /// Helper to set the duration of the sound decoded ahead of the playback.
+    pub fn with_decode_ahead(mut self, decode_ahead: Duration) -> Self {
+        self.decode_ahead = decode_ahead;
+        self
+    }
+
+    /// Returns the path of the streamed file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn stream(&self) -> Result<StreamingDecoder> {
+        let stream = self.asset_server.asset_io().open_stream(&self.path)?;
+        let decoder = rodio::Decoder::new(BufReader::with_capacity(self.read_buffer_size, stream))?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        let total_duration = decoder.total_duration();
+
+        let chunk_len = STREAM_CHUNK_FRAMES * channels as usize;
+        let chunks_ahead =
+            self.decode_ahead.as_secs_f64() * sample_rate as f64 / STREAM_CHUNK_FRAMES as f64;
+        let (sender, chunks) = crossbeam_channel::bounded(chunks_ahead.ceil().max(1.0) as usize);
+        thread::Builder::new()
+            .name("audio stream".to_string())
+            .spawn(move || {
+                let mut samples = decoder.convert_samples::<f32>();
+                loop {
+                    let chunk: Vec<f32> = samples.by_ref().take(chunk_len).collect();
+                    // Stops at the end of the sound, or once its decoder was dropped
+                    if chunk.is_empty() || sender.send(chunk).is_err() {
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(StreamingDecoder {
+            chunks: Some(chunks),
+            chunk: Vec::new(),
+            position: 0,
+            channels,
+            sample_rate,
+            total_duration,
+        })
+    }
+}
+
+impl Decodable for StreamingAudioSource {
+    type DecoderItem = f32;
+    type Decoder = StreamingDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        self.stream().unwrap_or_else(|err| {
+            error!("Failed to stream {:?}: {}", self.path, err);
+            StreamingDecoder {
+                chunks: None,
+                chunk: Vec::new(),
+                position: 0,
+                channels: 1,
+                sample_rate: 44_100,
+                total_duration: Some(Duration::ZERO),
+            }
+        })
+    }
+}
+
+/// The decoder of a [`StreamingAudioSource`], playing the chunks of samples decoded by its
+/// background thread.
+///
+/// A decoder that failed to stream its file plays no sound.
+pub struct StreamingDecoder {
+    chunks: Option<Receiver<Vec<f32>>>,
+    chunk: Vec<f32>,
+    position: usize,
+    channels: u16,
+    sample_rate: u32,
+    total_duration: Option<Duration>,
+}
+
+impl Iterator for StreamingDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position == self.chunk.len() {
+            // Waits for the background thread if it falls behind
+            self.chunk = self.chunks.as_ref()?.recv().ok()?;
+            self.position = 0;
+        }
+        let sample = self.chunk[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for StreamingDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+}
@@ -8,6 +8,7 @@ pub mod graph {
     }
     pub mod node {
         pub const MAIN_PASS: &str = "main_pass";
+        pub const LIGHTING: &str = "lighting";
         pub const BLOOM: &str = "bloom";
         pub const TONEMAPPING: &str = "tonemapping";
         pub const FXAA: &str = "fxaa";
@@ -103,8 +104,33 @@ impl Plugin for Core2dPlugin {
     }
 }
 
+/// The key sorting the [`Transparent2d`] items, drawn from the lowest key to the highest: by
+/// sorting layer, then by order in the layer, then by position on the Y axis for the layers sorted
+/// by it, then by position on the Z axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Transparent2dSortKey {
+    pub layer: i32,
+    pub order: i32,
+    /// The opposite of the position on the Y axis for the layers sorted by it, for the lower items
+    /// to be drawn over the higher ones, and zero otherwise.
+    pub y: FloatOrd,
+    pub z: FloatOrd,
+}
+
+impl Transparent2dSortKey {
+    /// The key of an item in the default layer, sorted by its position on the Z axis only.
+    pub fn from_z(z: f32) -> Self {
+        Self {
+            layer: 0,
+            order: 0,
+            y: FloatOrd(0.0),
+            z: FloatOrd(z),
+        }
+    }
+}
+
 pub struct Transparent2d {
-    pub sort_key: FloatOrd,
+    pub sort_key: Transparent2dSortKey,
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
@@ -113,7 +139,7 @@ pub struct Transparent2d {
 }
 
 impl PhaseItem for Transparent2d {
-    type SortKey = FloatOrd;
+    type SortKey = Transparent2dSortKey;
 
     #[inline]
     fn entity(&self) -> Entity {
@@ -0,0 +1,303 @@
+mod node;
+
+pub mod graph {
+    pub const NAME: &str = "pixel_perfect";
+    pub mod input {
+        pub const VIEW_ENTITY: &str = "view_entity";
+    }
+    pub mod node {
+        pub const PIXEL_PERFECT_UPSCALING: &str = "pixel_perfect_upscaling";
+        pub const UPSCALING: &str = "upscaling";
+    }
+}
+
+pub use node::PixelPerfectUpscalingNode;
+
+use crate::upscaling::{UpscalingMode, UpscalingNode, UpscalingPipeline, UpscalingPipelineKey};
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::UVec2;
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::{
+        Camera, CameraRenderGraph, CameraUpdateSystem, OrthographicProjection, RenderTarget,
+        Viewport,
+    },
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{RenderGraph, SlotInfo, SlotType},
+    render_resource::*,
+    texture::{BevyDefault, Image, ImageSampler},
+    view::{ViewTarget, VisibleEntities},
+    RenderApp, RenderStage,
+};
+use bevy_transform::prelude::GlobalTransform;
+
+/// Renders a 2D camera at a fixed resolution, upscaled by an integer factor to its target with
+/// nearest sampling, for pixel-art games without shimmering or half-pixel artifacts.
+///
+/// The camera renders to an image of the given resolution, and the upscaled image is drawn to the
+/// original target of the camera by another camera, spawned with the same order. The camera is
+/// then given the lowest order to render its image first. The image is drawn centered with the
+/// largest integer scale fitting in the target, the rest of the target being cleared with the
+/// [`ClearColor`](crate::clear_color::ClearColor).
+///
+/// With the default [`OrthographicProjection`], one unit of the world is one pixel of the image.
+/// The UI of the camera is rendered at the fixed resolution too.
+#[derive(Component, Reflect, Clone, Debug, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct PixelPerfectCamera {
+    /// The size of the image the camera renders to, in pixels.
+    pub resolution: UVec2,
+    /// Whether the sprites seen by the camera are moved by less than a pixel to start at the
+    /// corner of a pixel, so that the pixels of their images match the pixels of the camera.
+    pub snap_sprites: bool,
+}
+
+impl Default for PixelPerfectCamera {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(320, 180),
+            snap_sprites: true,
+        }
+    }
+}
+
+impl PixelPerfectCamera {
+    pub fn new(resolution: UVec2) -> Self {
+        Self {
+            resolution,
+            ..Default::default()
+        }
+    }
+
+    /// The integer factor by which the image of the camera is upscaled on a target of the given
+    /// physical size, at least 1.
+    pub fn scale(&self, target_size: UVec2) -> u32 {
+        let scale = target_size / self.resolution.max(UVec2::ONE);
+        scale.min_element().max(1)
+    }
+
+    /// The viewport of the upscaled image of the camera, centered on a target of the given physical
+    /// size. If the image does not fit in the target, it is shrunk to the whole target instead.
+    pub fn upscaled_viewport(&self, target_size: UVec2) -> Viewport {
+        let size = self.resolution * self.scale(target_size);
+        if size.x > target_size.x || size.y > target_size.y {
+            return Viewport {
+                physical_size: target_size,
+                ..Default::default()
+            };
+        }
+        Viewport {
+            physical_position: (target_size - size) / 2,
+            physical_size: size,
+            ..Default::default()
+        }
+    }
+}
+
+impl ExtractComponent for PixelPerfectCamera {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<'_, Self::Query>) -> Option<Self> {
+        Some(item.clone())
+    }
+}
+
+/// The camera drawing the upscaled image of a [`PixelPerfectCamera`] to its original target.
+#[derive(Component, Clone, Debug)]
+pub struct PixelPerfectOutput {
+    /// The entity of the [`PixelPerfectCamera`]
+    pub camera: Entity,
+    /// The image the [`PixelPerfectCamera`] renders to
+    pub image: Handle<Image>,
+    pub settings: PixelPerfectCamera,
+}
+
+impl ExtractComponent for PixelPerfectOutput {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<'_, Self::Query>) -> Option<Self> {
+        Some(item.clone())
+    }
+}
+
+/// Adds support for the [`PixelPerfectCamera`].
+pub struct PixelPerfectPlugin;
+
+impl Plugin for PixelPerfectPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PixelPerfectCamera>()
+            .add_plugin(ExtractComponentPlugin::<PixelPerfectCamera>::default())
+            .add_plugin(ExtractComponentPlugin::<PixelPerfectOutput>::default())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_pixel_perfect_cameras.before(CameraUpdateSystem),
+            );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app.add_system_to_stage(RenderStage::Queue, queue_pixel_perfect_pipelines);
+
+        let pixel_perfect_upscaling = PixelPerfectUpscalingNode::new(&mut render_app.world);
+        let upscaling = UpscalingNode::new(&mut render_app.world);
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+
+        let mut pixel_perfect_graph = RenderGraph::default();
+        pixel_perfect_graph.add_node(
+            graph::node::PIXEL_PERFECT_UPSCALING,
+            pixel_perfect_upscaling,
+        );
+        pixel_perfect_graph.add_node(graph::node::UPSCALING, upscaling);
+        let input_node_id = pixel_perfect_graph.set_input(vec![SlotInfo::new(
+            graph::input::VIEW_ENTITY,
+            SlotType::Entity,
+        )]);
+        pixel_perfect_graph.add_slot_edge(
+            input_node_id,
+            graph::input::VIEW_ENTITY,
+            graph::node::PIXEL_PERFECT_UPSCALING,
+            PixelPerfectUpscalingNode::IN_VIEW,
+        );
+        pixel_perfect_graph.add_slot_edge(
+            input_node_id,
+            graph::input::VIEW_ENTITY,
+            graph::node::UPSCALING,
+            UpscalingNode::IN_VIEW,
+        );
+        pixel_perfect_graph
+            .add_node_edge(graph::node::PIXEL_PERFECT_UPSCALING, graph::node::UPSCALING);
+        graph.add_sub_graph(graph::NAME, pixel_perfect_graph);
+    }
+}
+
+/// Makes the [`PixelPerfectCamera`]s render to their images, spawning the cameras drawing them
+/// to the original targets of the cameras, and restores the cameras when the component is removed.
+#[allow(clippy::type_complexity)]
+pub fn update_pixel_perfect_cameras(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<(Entity, &mut Camera, &PixelPerfectCamera), Without<PixelPerfectOutput>>,
+    mut outputs: Query<(Entity, &Camera, &mut PixelPerfectOutput), Without<PixelPerfectCamera>>,
+    mut restored_cameras: Query<
+        &mut Camera,
+        (Without<PixelPerfectCamera>, Without<PixelPerfectOutput>),
+    >,
+    mut projections: Query<&mut OrthographicProjection>,
+    removed: RemovedComponents<PixelPerfectCamera>,
+) {
+    for entity in removed.iter() {
+        let Some((output_entity, output_camera, _)) = outputs
+            .iter()
+            .find(|(_, _, output)| output.camera == entity)
+        else {
+            continue;
+        };
+        if let Ok(mut camera) = restored_cameras.get_mut(entity) {
+            camera.target = output_camera.target.clone();
+            camera.order = output_camera.order;
+            // Recomputes the projection for the restored target
+            if let Ok(mut projection) = projections.get_mut(entity) {
+                projection.set_changed();
+            }
+        }
+        commands.entity(output_entity).despawn();
+    }
+
+    for (entity, mut camera, pixel_perfect) in &mut cameras {
+        let size = Extent3d {
+            width: pixel_perfect.resolution.x.max(1),
+            height: pixel_perfect.resolution.y.max(1),
+            ..Default::default()
+        };
+
+        if let Some((_, _, mut output)) = outputs
+            .iter_mut()
+            .find(|(_, _, output)| output.camera == entity)
+        {
+            if output.settings != *pixel_perfect {
+                if output.settings.resolution != pixel_perfect.resolution {
+                    // Resizing the image updates the projection of the camera
+                    if let Some(image) = images.get_mut(&output.image) {
+                        image.resize(size);
+                    }
+                }
+                output.settings = pixel_perfect.clone();
+            }
+            continue;
+        }
+
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: None,
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::bevy_default(),
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+            },
+            sampler_descriptor: ImageSampler::nearest(),
+            ..Default::default()
+        };
+        image.resize(size);
+        let image = images.add(image);
+
+        let target = std::mem::replace(&mut camera.target, RenderTarget::Image(image.clone()));
+        // The image is rendered before it is drawn by the output camera
+        let order = std::mem::replace(&mut camera.order, isize::MIN);
+        if let Ok(mut projection) = projections.get_mut(entity) {
+            projection.set_changed();
+        }
+
+        commands.spawn((
+            Camera {
+                target,
+                order,
+                ..Default::default()
+            },
+            CameraRenderGraph::new(graph::NAME),
+            // Only used to compute the size of the target of the camera
+            OrthographicProjection::default(),
+            VisibleEntities::default(),
+            GlobalTransform::default(),
+            PixelPerfectOutput {
+                camera: entity,
+                image,
+                settings: pixel_perfect.clone(),
+            },
+        ));
+    }
+}
+
+#[derive(Component)]
+pub struct ViewPixelPerfectPipeline(CachedRenderPipelineId);
+
+fn queue_pixel_perfect_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UpscalingPipeline>>,
+    upscaling_pipeline: Res<UpscalingPipeline>,
+    view_targets: Query<(Entity, &ViewTarget), With<PixelPerfectOutput>>,
+) {
+    for (entity, view_target) in &view_targets {
+        let key = UpscalingPipelineKey {
+            upscaling_mode: UpscalingMode::Nearest,
+            texture_format: view_target.main_texture_format(),
+        };
+        let pipeline = pipelines.specialize(&pipeline_cache, &upscaling_pipeline, key);
+
+        commands
+            .entity(entity)
+            .insert(ViewPixelPerfectPipeline(pipeline));
+    }
+}
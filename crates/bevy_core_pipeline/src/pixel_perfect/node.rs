@@ -0,0 +1,142 @@
+use std::sync::Mutex;
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryState;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations,
+        PipelineCache, RenderPassDescriptor, TextureViewId,
+    },
+    renderer::RenderContext,
+    texture::Image,
+    view::ViewTarget,
+};
+
+use super::{PixelPerfectOutput, ViewPixelPerfectPipeline};
+use crate::{clear_color::ClearColor, upscaling::UpscalingPipeline};
+
+/// Draws the image of a [`PixelPerfectCamera`](super::PixelPerfectCamera), upscaled by an integer
+/// factor, to the main texture of its output camera.
+pub struct PixelPerfectUpscalingNode {
+    query: QueryState<(
+        &'static ViewTarget,
+        &'static ExtractedCamera,
+        &'static PixelPerfectOutput,
+        &'static ViewPixelPerfectPipeline,
+    )>,
+    cached_texture_bind_group: Mutex<Option<(TextureViewId, BindGroup)>>,
+}
+
+impl PixelPerfectUpscalingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: QueryState::new(world),
+            cached_texture_bind_group: Mutex::new(None),
+        }
+    }
+}
+
+impl Node for PixelPerfectUpscalingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(
+            PixelPerfectUpscalingNode::IN_VIEW,
+            SlotType::Entity,
+        )]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let upscaling_pipeline = world.resource::<UpscalingPipeline>();
+
+        let (target, camera, output, pixel_perfect_pipeline) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(query) => query,
+                Err(_) => return Ok(()),
+            };
+
+        let (Some(gpu_image), Some(target_size)) = (
+            world.resource::<RenderAssets<Image>>().get(&output.image),
+            camera.physical_target_size,
+        ) else {
+            return Ok(());
+        };
+
+        let pipeline = match pipeline_cache.get_render_pipeline(pixel_perfect_pipeline.0) {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        let mut cached_bind_group = self.cached_texture_bind_group.lock().unwrap();
+        let bind_group = match &mut *cached_bind_group {
+            Some((id, bind_group)) if gpu_image.texture_view.id() == *id => bind_group,
+            cached_bind_group => {
+                // The sampler of the image samples the nearest pixels
+                let bind_group =
+                    render_context
+                        .render_device
+                        .create_bind_group(&BindGroupDescriptor {
+                            label: None,
+                            layout: &upscaling_pipeline.texture_bind_group,
+                            entries: &[
+                                BindGroupEntry {
+                                    binding: 0,
+                                    resource: BindingResource::TextureView(&gpu_image.texture_view),
+                                },
+                                BindGroupEntry {
+                                    binding: 1,
+                                    resource: BindingResource::Sampler(&gpu_image.sampler),
+                                },
+                            ],
+                        });
+
+                let (_, bind_group) =
+                    cached_bind_group.insert((gpu_image.texture_view.id(), bind_group));
+                bind_group
+            }
+        };
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("pixel_perfect_upscaling_pass"),
+            color_attachments: &[Some(target.get_unsampled_color_attachment(Operations {
+                load: LoadOp::Clear(world.resource::<ClearColor>().0.into()),
+                store: true,
+            }))],
+            depth_stencil_attachment: None,
+        };
+
+        let mut render_pass = render_context
+            .command_encoder
+            .begin_render_pass(&pass_descriptor);
+
+        let viewport = output.settings.upscaled_viewport(target_size);
+        render_pass.set_viewport(
+            viewport.physical_position.x as f32,
+            viewport.physical_position.y as f32,
+            viewport.physical_size.x as f32,
+            viewport.physical_size.y as f32,
+            0.0,
+            1.0,
+        );
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
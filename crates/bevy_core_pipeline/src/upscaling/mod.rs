@@ -36,7 +36,7 @@ impl Plugin for UpscalingPlugin {
 
 #[derive(Resource)]
 pub struct UpscalingPipeline {
-    texture_bind_group: BindGroupLayout,
+    pub texture_bind_group: BindGroupLayout,
 }
 
 impl FromWorld for UpscalingPipeline {
@@ -78,8 +78,8 @@ pub enum UpscalingMode {
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct UpscalingPipelineKey {
-    upscaling_mode: UpscalingMode,
-    texture_format: TextureFormat,
+    pub upscaling_mode: UpscalingMode,
+    pub texture_format: TextureFormat,
 }
 
 impl SpecializedRenderPipeline for UpscalingPipeline {
@@ -1,4 +1,4 @@
-use bevy_macro_utils::{get_lit_str, Symbol};
+use bevy_macro_utils::{get_lit_bool, get_lit_str, Symbol};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
@@ -32,6 +32,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
+    let change_detection = attrs.change_detection;
 
     ast.generics
         .make_where_clause()
@@ -44,15 +45,18 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+            const CHANGE_DETECTION: bool = #change_detection;
         }
     })
 }
 
 pub const COMPONENT: Symbol = Symbol("component");
 pub const STORAGE: Symbol = Symbol("storage");
+pub const CHANGE_DETECTION: Symbol = Symbol("change_detection");
 
 struct Attrs {
     storage: StorageTy,
+    change_detection: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -70,6 +74,7 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
 
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        change_detection: true,
     };
 
     for meta in meta_items {
@@ -92,6 +97,9 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
             }
+            Meta(NameValue(m)) if m.path == CHANGE_DETECTION => {
+                attrs.change_detection = get_lit_bool(CHANGE_DETECTION, &m.lit)?;
+            }
             Meta(meta_item) => {
                 return Err(Error::new_spanned(
                     meta_item.path(),
@@ -320,6 +320,13 @@ pub fn derive_world_query_impl(ast: DeriveInput) -> TokenStream {
                     }
                 }
 
+                fn get_state(world: &#path::world::World) -> Option<#state_struct_name #user_ty_generics> {
+                    Some(#state_struct_name {
+                        #(#field_idents: <#field_types>::get_state(world)?,)*
+                        #(#ignored_field_idents: Default::default(),)*
+                    })
+                }
+
                 fn matches_component_set(state: &Self::State, _set_contains_id: &impl Fn(#path::component::ComponentId) -> bool) -> bool {
                     true #(&& <#field_types>::matches_component_set(&state.#field_idents, _set_contains_id))*
                 }
@@ -136,16 +136,12 @@ macro_rules! change_detection_impl {
         impl<$($generics),* : ?Sized $(+ $traits)?> DetectChanges for $name<$($generics),*> {
             #[inline]
             fn is_added(&self) -> bool {
-                self.ticks
-                    .added
-                    .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
+                self.ticks.is_added()
             }
 
             #[inline]
             fn is_changed(&self) -> bool {
-                self.ticks
-                    .changed
-                    .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
+                self.ticks.is_changed()
             }
 
             #[inline]
@@ -179,9 +175,7 @@ macro_rules! change_detection_mut_impl {
 
             #[inline]
             fn set_changed(&mut self) {
-                self.ticks
-                    .changed
-                    .set_changed(self.ticks.change_tick);
+                self.ticks.set_changed();
             }
 
             #[inline]
@@ -247,8 +241,8 @@ macro_rules! impl_methods {
                 Mut {
                     value: self.value,
                     ticks: TicksMut {
-                        added: self.ticks.added,
-                        changed: self.ticks.changed,
+                        added: self.ticks.added.as_deref_mut(),
+                        changed: self.ticks.changed.as_deref_mut(),
                         last_change_tick: self.ticks.last_change_tick,
                         change_tick: self.ticks.change_tick,
                     }
@@ -302,10 +296,12 @@ macro_rules! impl_debug {
     };
 }
 
+// NOTE: `added` and `changed` are `None` for components that opted out of change detection,
+// which are never reported as added or changed.
 #[derive(Clone)]
 pub(crate) struct Ticks<'a> {
-    pub(crate) added: &'a Tick,
-    pub(crate) changed: &'a Tick,
+    pub(crate) added: Option<&'a Tick>,
+    pub(crate) changed: Option<&'a Tick>,
     pub(crate) last_change_tick: u32,
     pub(crate) change_tick: u32,
 }
@@ -314,23 +310,39 @@ impl<'a> Ticks<'a> {
     /// # Safety
     /// This should never alias the underlying ticks with a mutable one such as `TicksMut`.
     #[inline]
-    pub(crate) unsafe fn from_tick_cells(
-        cells: TickCells<'a>,
+    pub(crate) unsafe fn from_optional_tick_cells(
+        cells: Option<TickCells<'a>>,
         last_change_tick: u32,
         change_tick: u32,
     ) -> Self {
         Self {
-            added: cells.added.deref(),
-            changed: cells.changed.deref(),
+            added: cells.as_ref().map(|cells| cells.added.deref()),
+            changed: cells.as_ref().map(|cells| cells.changed.deref()),
             last_change_tick,
             change_tick,
         }
     }
+
+    #[inline]
+    fn is_added(&self) -> bool {
+        match self.added {
+            Some(added) => added.is_older_than(self.last_change_tick, self.change_tick),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn is_changed(&self) -> bool {
+        match self.changed {
+            Some(changed) => changed.is_older_than(self.last_change_tick, self.change_tick),
+            None => false,
+        }
+    }
 }
 
 pub(crate) struct TicksMut<'a> {
-    pub(crate) added: &'a mut Tick,
-    pub(crate) changed: &'a mut Tick,
+    pub(crate) added: Option<&'a mut Tick>,
+    pub(crate) changed: Option<&'a mut Tick>,
     pub(crate) last_change_tick: u32,
     pub(crate) change_tick: u32,
 }
@@ -343,21 +355,55 @@ impl<'a> TicksMut<'a> {
         cells: TickCells<'a>,
         last_change_tick: u32,
         change_tick: u32,
+    ) -> Self {
+        Self::from_optional_tick_cells(Some(cells), last_change_tick, change_tick)
+    }
+
+    /// # Safety
+    /// This should never alias the underlying ticks. All access must be unique.
+    #[inline]
+    pub(crate) unsafe fn from_optional_tick_cells(
+        cells: Option<TickCells<'a>>,
+        last_change_tick: u32,
+        change_tick: u32,
     ) -> Self {
         Self {
-            added: cells.added.deref_mut(),
-            changed: cells.changed.deref_mut(),
+            added: cells.as_ref().map(|cells| cells.added.deref_mut()),
+            changed: cells.as_ref().map(|cells| cells.changed.deref_mut()),
             last_change_tick,
             change_tick,
         }
     }
+
+    #[inline]
+    fn is_added(&self) -> bool {
+        match &self.added {
+            Some(added) => added.is_older_than(self.last_change_tick, self.change_tick),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn is_changed(&self) -> bool {
+        match &self.changed {
+            Some(changed) => changed.is_older_than(self.last_change_tick, self.change_tick),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn set_changed(&mut self) {
+        if let Some(changed) = &mut self.changed {
+            changed.set_changed(self.change_tick);
+        }
+    }
 }
 
 impl<'a> From<TicksMut<'a>> for Ticks<'a> {
     fn from(ticks: TicksMut<'a>) -> Self {
         Ticks {
-            added: ticks.added,
-            changed: ticks.changed,
+            added: ticks.added.map(|added| &*added),
+            changed: ticks.changed.map(|changed| &*changed),
             last_change_tick: ticks.last_change_tick,
             change_tick: ticks.change_tick,
         }
@@ -613,8 +659,8 @@ impl<'a> MutUntyped<'a> {
         MutUntyped {
             value: self.value.reborrow(),
             ticks: TicksMut {
-                added: self.ticks.added,
-                changed: self.ticks.changed,
+                added: self.ticks.added.as_deref_mut(),
+                changed: self.ticks.changed.as_deref_mut(),
                 last_change_tick: self.ticks.last_change_tick,
                 change_tick: self.ticks.change_tick,
             },
@@ -651,16 +697,12 @@ impl<'a> MutUntyped<'a> {
 impl<'a> DetectChanges for MutUntyped<'a> {
     #[inline]
     fn is_added(&self) -> bool {
-        self.ticks
-            .added
-            .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
+        self.ticks.is_added()
     }
 
     #[inline]
     fn is_changed(&self) -> bool {
-        self.ticks
-            .changed
-            .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
+        self.ticks.is_changed()
     }
 
     #[inline]
@@ -674,7 +716,7 @@ impl<'a> DetectChangesMut for MutUntyped<'a> {
 
     #[inline]
     fn set_changed(&mut self) {
-        self.ticks.changed.set_changed(self.ticks.change_tick);
+        self.ticks.set_changed();
     }
 
     #[inline]
@@ -830,8 +872,8 @@ mod tests {
             changed: Tick::new(2),
         };
         let ticks = TicksMut {
-            added: &mut component_ticks.added,
-            changed: &mut component_ticks.changed,
+            added: Some(&mut component_ticks.added),
+            changed: Some(&mut component_ticks.changed),
             last_change_tick: 3,
             change_tick: 4,
         };
@@ -842,8 +884,8 @@ mod tests {
         };
 
         let into_mut: Mut<R> = res_mut.into();
-        assert_eq!(1, into_mut.ticks.added.tick);
-        assert_eq!(2, into_mut.ticks.changed.tick);
+        assert_eq!(1, into_mut.ticks.added.unwrap().tick);
+        assert_eq!(2, into_mut.ticks.changed.unwrap().tick);
         assert_eq!(3, into_mut.ticks.last_change_tick);
         assert_eq!(4, into_mut.ticks.change_tick);
     }
@@ -855,8 +897,8 @@ mod tests {
             changed: Tick::new(2),
         };
         let ticks = TicksMut {
-            added: &mut component_ticks.added,
-            changed: &mut component_ticks.changed,
+            added: Some(&mut component_ticks.added),
+            changed: Some(&mut component_ticks.changed),
             last_change_tick: 3,
             change_tick: 4,
         };
@@ -867,8 +909,8 @@ mod tests {
         };
 
         let into_mut: Mut<R> = non_send_mut.into();
-        assert_eq!(1, into_mut.ticks.added.tick);
-        assert_eq!(2, into_mut.ticks.changed.tick);
+        assert_eq!(1, into_mut.ticks.added.unwrap().tick);
+        assert_eq!(2, into_mut.ticks.changed.unwrap().tick);
         assert_eq!(3, into_mut.ticks.last_change_tick);
         assert_eq!(4, into_mut.ticks.change_tick);
     }
@@ -884,8 +926,8 @@ mod tests {
             changed: Tick::new(2),
         };
         let ticks = TicksMut {
-            added: &mut component_ticks.added,
-            changed: &mut component_ticks.changed,
+            added: Some(&mut component_ticks.added),
+            changed: Some(&mut component_ticks.changed),
             last_change_tick,
             change_tick,
         };
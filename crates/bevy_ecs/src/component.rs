@@ -85,6 +85,30 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Opting out of change detection
+///
+/// By default, the world records when each component was added and last changed, which is what
+/// [`Added`], [`Changed`] and [`DetectChanges`] are built on.
+/// For data that changes every frame and is never checked for changes, like particle state,
+/// this bookkeeping can be skipped with the `#[component(change_detection = false)]` attribute:
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// #
+/// #[derive(Component)]
+/// #[component(change_detection = false)]
+/// struct Velocity(f32, f32);
+/// ```
+///
+/// No change ticks are stored for such a component, so they use less memory and mutating them
+/// is cheaper. They are never reported as added or changed, and using them in an [`Added`],
+/// [`Changed`] or [`ChangeTrackers`] query panics.
+///
+/// [`Added`]: crate::query::Added
+/// [`Changed`]: crate::query::Changed
+/// [`ChangeTrackers`]: crate::query::ChangeTrackers
+/// [`DetectChanges`]: crate::change_detection::DetectChanges
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -144,6 +168,11 @@ use std::{
 /// [`Exclusive`]: https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
 pub trait Component: Send + Sync + 'static {
     type Storage: ComponentStorage;
+
+    /// Whether the world records when this component was added and last changed.
+    ///
+    /// See the [type-level documentation](Component#opting-out-of-change-detection) for details.
+    const CHANGE_DETECTION: bool = true;
 }
 
 pub struct TableStorage;
@@ -236,6 +265,12 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns `true` if change ticks are stored for this component.
+    #[inline]
+    pub fn has_change_detection(&self) -> bool {
+        self.descriptor.change_detection
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo { id, descriptor }
     }
@@ -292,6 +327,9 @@ pub struct ComponentDescriptor {
     // SAFETY: This must remain private. It must only be set to "true" if this component is
     // actually Send + Sync
     is_send_and_sync: bool,
+    // SAFETY: This must remain private. It must match `Component::CHANGE_DETECTION` of the
+    // associated rust component type if one exists.
+    change_detection: bool,
     type_id: Option<TypeId>,
     layout: Layout,
     // SAFETY: this function must be safe to call with pointers pointing to items of the type
@@ -307,6 +345,7 @@ impl std::fmt::Debug for ComponentDescriptor {
             .field("name", &self.name)
             .field("storage_type", &self.storage_type)
             .field("is_send_and_sync", &self.is_send_and_sync)
+            .field("change_detection", &self.change_detection)
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
             .finish()
@@ -325,6 +364,7 @@ impl ComponentDescriptor {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::Storage::STORAGE_TYPE,
             is_send_and_sync: true,
+            change_detection: T::CHANGE_DETECTION,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
@@ -346,6 +386,7 @@ impl ComponentDescriptor {
             name: name.into(),
             storage_type,
             is_send_and_sync: true,
+            change_detection: true,
             type_id: None,
             layout,
             drop,
@@ -362,6 +403,7 @@ impl ComponentDescriptor {
             // reasonable choice as `storage_type` for resources.
             storage_type: StorageType::Table,
             is_send_and_sync: true,
+            change_detection: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
@@ -373,6 +415,7 @@ impl ComponentDescriptor {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type,
             is_send_and_sync: false,
+            change_detection: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
@@ -389,6 +432,12 @@ impl ComponentDescriptor {
         self.type_id
     }
 
+    /// Returns `true` if change ticks are stored for this component.
+    #[inline]
+    pub fn has_change_detection(&self) -> bool {
+        self.change_detection
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         self.name.as_ref()
@@ -65,7 +65,7 @@ pub trait MapEntities {
 ///
 /// This is typically used to coordinate data transfer between sets of entities, such as between a scene and the world or over the network.
 /// This is required as [`Entity`] identifiers are opaque; you cannot and do not want to reuse identifiers directly.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct EntityMap {
     map: HashMap<Entity, Entity>,
 }
@@ -3,7 +3,7 @@
 use crate as bevy_ecs;
 use crate::system::{Local, Res, ResMut, Resource, SystemParam};
 use bevy_utils::tracing::trace;
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
 use std::{fmt, hash::Hash, iter::Chain, marker::PhantomData, slice::Iter};
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
@@ -120,6 +120,10 @@ struct EventInstance<E: Event> {
 /// but can be done by adding your event as a resource instead of using
 /// [`add_event`](https://docs.rs/bevy/*/bevy/app/struct.App.html#method.add_event).
 ///
+/// Both the clearing behavior and the maximum number of stored events can be configured with
+/// [`EventSettings`], which is useful for long-lived events such as network messages that must
+/// not be dropped just because no reader ran in the last two frames.
+///
 /// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/ecs/event.rs)
 /// [Example usage standalone.](https://github.com/bevyengine/bevy/blob/latest/crates/bevy_ecs/examples/events.rs)
 ///
@@ -131,6 +135,8 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    settings: EventSettings,
+    dropped_events: usize,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -140,11 +146,40 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            settings: Default::default(),
+            dropped_events: Default::default(),
         }
     }
 }
 
 impl<E: Event> Events<E> {
+    /// Creates an empty [`Events`] collection that uses the given [`EventSettings`].
+    pub fn with_settings(settings: EventSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the [`EventSettings`] of this collection.
+    pub fn settings(&self) -> &EventSettings {
+        &self.settings
+    }
+
+    /// Returns a mutable reference to the [`EventSettings`] of this collection.
+    ///
+    /// Lowering the capacity does not drop any events that are already stored.
+    pub fn settings_mut(&mut self) -> &mut EventSettings {
+        &mut self.settings
+    }
+
+    /// The total number of events dropped because this collection was at capacity.
+    ///
+    /// See [`EventSettings::capacity`].
+    pub fn dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
     pub fn oldest_event_count(&self) -> usize {
         self.events_a
             .start_event_count
@@ -152,9 +187,58 @@ impl<E: Event> Events<E> {
     }
 }
 
+/// Configures how an [`Events`] collection clears and limits the events it stores.
+///
+/// # Example
+/// ```
+/// use bevy_ecs::event::{EventOverflow, EventSettings, Events};
+///
+/// struct NetworkMessage(u32);
+///
+/// let mut events = Events::<NetworkMessage>::with_settings(EventSettings {
+///     manual_clearing: true,
+///     capacity: Some(2),
+///     overflow: EventOverflow::DropOldest,
+/// });
+/// let mut reader = events.get_reader();
+///
+/// for i in 0..3 {
+///     events.send(NetworkMessage(i));
+/// }
+///
+/// // the oldest message was dropped to make room for the newest one
+/// assert_eq!(reader.missed_events(&events), 1);
+/// let ids = reader.iter(&events).map(|message| message.0).collect::<Vec<_>>();
+/// assert_eq!(ids, vec![1, 2]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventSettings {
+    /// If `true`, [`Events::update_system`] leaves the events untouched, so they are kept until
+    /// they are explicitly removed with [`Events::update`], [`Events::clear`] or [`Events::drain`].
+    pub manual_clearing: bool,
+    /// The maximum number of events stored at once, or `None` for no limit.
+    pub capacity: Option<usize>,
+    /// What to do when an event is sent while the collection is at capacity.
+    pub overflow: EventOverflow,
+}
+
+/// Specifies which event is dropped when sending to an [`Events`] collection that is at capacity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventOverflow {
+    /// Drop the oldest stored event to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Drop the event that is being sent.
+    DropNewest,
+}
+
 #[derive(Debug)]
 struct EventSequence<E: Event> {
     events: Vec<EventInstance<E>>,
+    /// The number of events at the front of `events` that were dropped but not removed yet.
+    /// They are removed in bulk so dropping the oldest events one by one doesn't shift the
+    /// remaining events every time.
+    dropped: usize,
     start_event_count: usize,
 }
 
@@ -163,22 +247,47 @@ impl<E: Event> Default for EventSequence<E> {
     fn default() -> Self {
         Self {
             events: Default::default(),
+            dropped: 0,
             start_event_count: Default::default(),
         }
     }
 }
 
-impl<E: Event> Deref for EventSequence<E> {
-    type Target = Vec<EventInstance<E>>;
+impl<E: Event> EventSequence<E> {
+    fn push(&mut self, event: EventInstance<E>) {
+        self.events.push(event);
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.events
+    fn extend(&mut self, events: impl IntoIterator<Item = EventInstance<E>>) {
+        self.events.extend(events);
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+        self.dropped = 0;
+    }
+
+    fn drain(&mut self) -> impl Iterator<Item = EventInstance<E>> + '_ {
+        let dropped = std::mem::take(&mut self.dropped);
+        self.events.drain(..).skip(dropped)
+    }
+
+    /// Drops the oldest event. The sequence must not be empty.
+    fn drop_oldest(&mut self) {
+        self.dropped += 1;
+        self.start_event_count += 1;
+        if self.dropped * 2 >= self.events.len() {
+            self.events.drain(..self.dropped);
+            self.dropped = 0;
+        }
     }
 }
 
-impl<E: Event> DerefMut for EventSequence<E> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.events
+impl<E: Event> Deref for EventSequence<E> {
+    type Target = [EventInstance<E>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.events[self.dropped..]
     }
 }
 
@@ -243,6 +352,15 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     pub fn clear(&mut self) {
         self.iter().last();
     }
+
+    /// The number of events this [`EventReader`] missed because they were dropped before it
+    /// read them, either because it did not run for two updates or because the [`Events`]
+    /// collection was at capacity.
+    ///
+    /// Reading events resets this count.
+    pub fn missed_events(&self) -> usize {
+        self.reader.missed_events(&self.events)
+    }
 }
 
 /// Sends events of type `T`.
@@ -482,7 +600,20 @@ impl<'a, E: Event> ExactSizeIterator for ManualEventIteratorWithId<'a, E> {
 impl<E: Event> Events<E> {
     /// "Sends" an `event` by writing it to the current event buffer. [`EventReader`]s can then read
     /// the event.
+    ///
+    /// If the collection is at capacity, an event is dropped according to
+    /// [`EventSettings::overflow`].
     pub fn send(&mut self, event: E) {
+        if let Some(capacity) = self.settings.capacity {
+            if self.len() >= capacity
+                && (self.settings.overflow == EventOverflow::DropNewest || !self.drop_oldest())
+            {
+                self.dropped_events += 1;
+                trace!("Events::send() -> dropped, at capacity {}", capacity);
+                return;
+            }
+        }
+
         let event_id = EventId {
             id: self.event_count,
             _marker: PhantomData,
@@ -529,9 +660,30 @@ impl<E: Event> Events<E> {
         );
     }
 
-    /// A system that calls [`Events::update`] once per frame.
+    /// A system that calls [`Events::update`] once per frame, unless
+    /// [`EventSettings::manual_clearing`] is set.
     pub fn update_system(mut events: ResMut<Self>) {
-        events.update();
+        if !events.settings.manual_clearing {
+            events.update();
+        }
+    }
+
+    /// Removes the oldest stored event, returning `false` if there are no events.
+    fn drop_oldest(&mut self) -> bool {
+        let sequence = if self.events_a.is_empty() {
+            &mut self.events_b
+        } else {
+            &mut self.events_a
+        };
+        if sequence.is_empty() {
+            return false;
+        }
+        sequence.drop_oldest();
+        if self.events_a.is_empty() {
+            self.events_a.start_event_count = self.events_b.start_event_count;
+        }
+        self.dropped_events += 1;
+        true
     }
 
     #[inline]
@@ -565,8 +717,8 @@ impl<E: Event> Events<E> {
 
         // Drain the oldest events first, then the newest
         self.events_a
-            .drain(..)
-            .chain(self.events_b.drain(..))
+            .drain()
+            .chain(self.events_b.drain())
             .map(|i| i.event)
     }
 
@@ -616,6 +768,14 @@ impl<E: Event> std::iter::Extend<E> for Events<E> {
     where
         I: IntoIterator<Item = E>,
     {
+        if self.settings.capacity.is_some() {
+            // every event needs to go through the capacity check
+            for event in iter {
+                self.send(event);
+            }
+            return;
+        }
+
         let mut event_count = self.event_count;
         let events = iter.into_iter().map(|event| {
             let event_id = EventId {
@@ -770,6 +930,115 @@ mod tests {
         assert!(reader.iter(&events).eq([E(2), E(3)].iter()));
     }
 
+    #[test]
+    fn test_events_manual_clearing() {
+        let mut world = World::new();
+        world.insert_resource(Events::<TestEvent>::with_settings(EventSettings {
+            manual_clearing: true,
+            ..Default::default()
+        }));
+        let mut reader = world.resource::<Events<TestEvent>>().get_reader();
+
+        world.send_event(TestEvent { i: 0 });
+        let mut update = SystemState::<ResMut<Events<TestEvent>>>::new(&mut world);
+        for _ in 0..3 {
+            Events::update_system(update.get_mut(&mut world));
+        }
+
+        let events = world.resource::<Events<TestEvent>>();
+        assert_eq!(reader.missed_events(events), 0);
+        assert_eq!(get_events(events, &mut reader), vec![TestEvent { i: 0 }]);
+    }
+
+    #[test]
+    fn test_events_capacity_drop_oldest() {
+        let mut events = Events::<TestEvent>::with_settings(EventSettings {
+            capacity: Some(2),
+            overflow: EventOverflow::DropOldest,
+            ..Default::default()
+        });
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.extend([TestEvent { i: 1 }, TestEvent { i: 2 }, TestEvent { i: 3 }]);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events.dropped_events(), 2);
+        assert_eq!(reader.missed_events(&events), 2);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 2 }, TestEvent { i: 3 }]
+        );
+        assert!(events.get_event(1).is_none());
+        assert_eq!(events.get_event(2).unwrap().0, &TestEvent { i: 2 });
+    }
+
+    #[test]
+    fn test_events_capacity_drop_oldest_sustained() {
+        let mut events = Events::<TestEvent>::with_settings(EventSettings {
+            capacity: Some(3),
+            overflow: EventOverflow::DropOldest,
+            ..Default::default()
+        });
+        let mut reader = events.get_reader();
+
+        for i in 0..50 {
+            events.send(TestEvent { i });
+            if i % 7 == 0 {
+                events.update();
+            }
+        }
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events.dropped_events(), 47);
+        assert_eq!(events.oldest_id(), 47);
+        assert_eq!(events.get_event(48).unwrap().0, &TestEvent { i: 48 });
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![
+                TestEvent { i: 47 },
+                TestEvent { i: 48 },
+                TestEvent { i: 49 }
+            ]
+        );
+        assert_eq!(
+            events.drain().collect::<Vec<_>>(),
+            vec![
+                TestEvent { i: 47 },
+                TestEvent { i: 48 },
+                TestEvent { i: 49 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_capacity_drop_newest() {
+        let mut events = Events::<TestEvent>::with_settings(EventSettings {
+            capacity: Some(2),
+            overflow: EventOverflow::DropNewest,
+            ..Default::default()
+        });
+        let mut reader = events.get_reader();
+
+        for i in 0..4 {
+            events.send(TestEvent { i });
+        }
+
+        assert_eq!(events.dropped_events(), 2);
+        assert_eq!(reader.missed_events(&events), 0);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+
+        // dropped events don't consume ids
+        events.update();
+        events.update();
+        events.send(TestEvent { i: 4 });
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 4 }]);
+    }
+
     #[test]
     fn test_events_clear_and_read() {
         events_clear_and_read_impl(|events| events.clear());
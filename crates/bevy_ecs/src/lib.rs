@@ -53,13 +53,14 @@ pub use bevy_ecs_macros::all_tuples;
 #[cfg(test)]
 mod tests {
     use crate as bevy_ecs;
-    use crate::prelude::Or;
+    use crate::prelude::{DetectChanges, Or};
     use crate::{
         bundle::Bundle,
         component::{Component, ComponentId},
         entity::Entity,
         query::{
-            Added, ChangeTrackers, Changed, FilteredAccess, ReadOnlyWorldQuery, With, Without,
+            Added, BatchingStrategy, ChangeTrackers, Changed, FilteredAccess, ReadOnlyWorldQuery,
+            With, Without,
         },
         system::Resource,
         world::{Mut, World},
@@ -432,6 +433,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn par_iter() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::new();
+        // a skewed distribution: one big table and a small one
+        for i in 0..100 {
+            world.spawn(A(i));
+        }
+        world.spawn((A(100), B(1)));
+        world.spawn((A(101), SparseStored(1)));
+
+        let mut query = world.query::<(Entity, &A)>();
+        let expected = query
+            .iter(&world)
+            .map(|(e, &A(i))| (e, i))
+            .collect::<Vec<_>>();
+        let mapped = query.par_iter(&world).map(|(e, &A(i))| (e, i));
+        assert_eq!(mapped, expected);
+
+        let sums = query
+            .par_iter(&world)
+            .batching_strategy(BatchingStrategy::fixed(16))
+            .fold(|| 0, |sum, (_, &A(i))| sum + i);
+        // 101 entities in the table of `A` and 1 in the table of `(A, B)`
+        assert_eq!(sums.len(), 8);
+        assert_eq!(sums.into_iter().sum::<usize>(), (0..102).sum());
+
+        world
+            .query::<&mut A>()
+            .par_iter_mut(&mut world)
+            .for_each_mut(|mut a| a.0 *= 2);
+        let results = Arc::new(Mutex::new(Vec::new()));
+        world
+            .query_filtered::<&A, With<SparseStored>>()
+            .par_iter(&world)
+            .for_each(|&A(i)| results.lock().unwrap().push(i));
+        assert_eq!(&*results.lock().unwrap(), &[202]);
+    }
+
+    #[test]
+    fn batching_strategy() {
+        let strategy = BatchingStrategy::new();
+        assert_eq!(strategy.batch_size(100, 4), 25);
+        assert_eq!(strategy.batch_size(101, 4), 26);
+        assert_eq!(strategy.batch_size(0, 4), 1);
+
+        let strategy = strategy.batches_per_thread(5).min_batch_size(8);
+        assert_eq!(strategy.batch_size(1000, 4), 50);
+        assert_eq!(strategy.batch_size(100, 4), 8);
+        assert_eq!(strategy.max_batch_size(16).batch_size(1000, 4), 16);
+        assert_eq!(BatchingStrategy::fixed(64).batch_size(1000, 4), 64);
+    }
+
+    #[test]
+    fn query_transmute() {
+        let mut world = World::new();
+        let e1 = world.spawn((A(1), B(1))).id();
+        world.spawn(A(2));
+        let e3 = world.spawn((A(3), B(3), SparseStored(3))).id();
+
+        let query = world.query::<(Entity, &A, &B)>();
+        let mut narrow = query.transmute::<Entity>(&world);
+        let entities = narrow.iter(&world).collect::<Vec<_>>();
+        assert_eq!(entities, vec![e1, e3]);
+
+        // the table of `(A, B)` also holds `e3`, which a sparse query must still filter out
+        let query = world.query_filtered::<&A, With<SparseStored>>();
+        let mut narrow = query.transmute::<(Entity, &A)>(&world);
+        let results = narrow
+            .iter(&world)
+            .map(|(e, &A(i))| (e, i))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(e3, 3)]);
+
+        let query = world.query::<(Entity, &mut A)>();
+        let mut narrow = query.transmute_filtered::<Entity, Without<B>>(&world);
+        assert_eq!(narrow.iter(&world).count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn query_transmute_extra_access() {
+        let mut world = World::new();
+        world.spawn((A(1), B(1)));
+        world.query::<&A>().transmute::<&B>(&world);
+    }
+
+    #[test]
+    #[should_panic]
+    fn query_transmute_read_to_write() {
+        let mut world = World::new();
+        world.spawn(A(1));
+        world.query::<&A>().transmute::<&mut A>(&world);
+    }
+
     #[test]
     fn query_missing_component() {
         let mut world = World::new();
@@ -1083,6 +1179,83 @@ mod tests {
         assert_eq!(get_changed(&mut world), vec![e1]);
     }
 
+    #[test]
+    fn untracked_components() {
+        #[derive(Component, Debug, PartialEq)]
+        #[component(change_detection = false)]
+        struct Untracked(usize);
+
+        #[derive(Component, Debug, PartialEq)]
+        #[component(storage = "SparseSet", change_detection = false)]
+        struct UntrackedSparse(usize);
+
+        let mut world = World::default();
+        let e1 = world.spawn((A(1), Untracked(1), UntrackedSparse(1))).id();
+        let e2 = world.spawn((Untracked(2), UntrackedSparse(2))).id();
+        let e3 = world.spawn(Untracked(3)).id();
+
+        let id = world.components().component_id::<Untracked>().unwrap();
+        assert!(!world
+            .components()
+            .get_info(id)
+            .unwrap()
+            .has_change_detection());
+        let location = world.entities().get(e3).unwrap();
+        let column = world.storages().tables[location.table_id]
+            .get_column(id)
+            .unwrap();
+        // `e2` and `e3` share a table, as sparse set components aren't stored in tables
+        assert_eq!(column.len(), 2);
+        assert!(column.get_added_ticks_slice().is_empty());
+        assert!(world.entity(e1).get_change_ticks::<Untracked>().is_none());
+        assert!(world
+            .entity(e1)
+            .get_change_ticks::<UntrackedSparse>()
+            .is_none());
+        assert!(world.entity(e1).get_change_ticks::<A>().is_some());
+
+        for (mut untracked, mut sparse) in world
+            .query::<(&mut Untracked, &mut UntrackedSparse)>()
+            .iter_mut(&mut world)
+        {
+            untracked.0 *= 10;
+            sparse.0 *= 10;
+            assert!(!untracked.is_added());
+            assert!(!untracked.is_changed());
+            assert!(!sparse.is_changed());
+        }
+
+        // moving between tables and removing keeps the data intact
+        world.entity_mut(e1).remove::<A>();
+        world.entity_mut(e2).insert(B(2));
+        world.entity_mut(e3).insert(UntrackedSparse(3));
+        world.despawn(e1);
+        let mut values = world
+            .query::<(Entity, &Untracked, &UntrackedSparse)>()
+            .iter(&world)
+            .map(|(e, untracked, sparse)| (e, untracked.0, sparse.0))
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![(e2, 20, 20), (e3, 3, 3)]);
+
+        let mut entity = world.entity_mut(e3);
+        let mut untracked = entity.get_mut::<Untracked>().unwrap();
+        untracked.0 += 1;
+        assert!(!untracked.is_changed());
+        assert_eq!(world.get::<Untracked>(e3), Some(&Untracked(4)));
+    }
+
+    #[test]
+    #[should_panic = "opted out of change detection"]
+    fn untracked_component_changed_filter() {
+        #[derive(Component)]
+        #[component(change_detection = false)]
+        struct Untracked;
+
+        let mut world = World::default();
+        world.query_filtered::<Entity, Changed<Untracked>>();
+    }
+
     #[test]
     fn resource() {
         use crate::system::Resource;
@@ -160,6 +160,17 @@ impl<T: SparseSetIndex> Access<T> {
             && other.writes.is_disjoint(&self.reads_and_writes)
     }
 
+    /// Returns `true` if every element this can access can also be accessed by `other`,
+    /// and every element this can exclusively access can also be exclusively accessed by `other`.
+    pub fn is_subset(&self, other: &Access<T>) -> bool {
+        if self.reads_all && !other.reads_all {
+            return false;
+        }
+
+        (other.reads_all || self.reads_and_writes.is_subset(&other.reads_and_writes))
+            && self.writes.is_subset(&other.writes)
+    }
+
     /// Returns a vector of elements that the access and `other` cannot access at the same time.
     pub fn get_conflicts(&self, other: &Access<T>) -> Vec<T> {
         let mut conflicts = FixedBitSet::default();
@@ -424,6 +424,13 @@ pub unsafe trait WorldQuery {
     );
 
     fn init_state(world: &mut World) -> Self::State;
+
+    /// Attempts to initialize a [`State`](WorldQuery::State) for this [`WorldQuery`] type
+    /// using only shared access to the `world`.
+    ///
+    /// Returns `None` if a component or resource this query needs has not been registered yet.
+    fn get_state(world: &World) -> Option<Self::State>;
+
     fn matches_component_set(
         state: &Self::State,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -504,6 +511,10 @@ unsafe impl WorldQuery for Entity {
 
     fn init_state(_world: &mut World) {}
 
+    fn get_state(_world: &World) -> Option<()> {
+        Some(())
+    }
+
     fn matches_component_set(
         _state: &Self::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -642,6 +653,10 @@ unsafe impl<T: Component> WorldQuery for &T {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -756,8 +771,10 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                 Ref {
                     value: table_components.get(table_row.index()).deref(),
                     ticks: Ticks {
-                        added: added_ticks.get(table_row.index()).deref(),
-                        changed: changed_ticks.get(table_row.index()).deref(),
+                        added: T::CHANGE_DETECTION
+                            .then(|| added_ticks.get(table_row.index()).deref()),
+                        changed: T::CHANGE_DETECTION
+                            .then(|| changed_ticks.get(table_row.index()).deref()),
                         change_tick: fetch.change_tick,
                         last_change_tick: fetch.last_change_tick,
                     },
@@ -771,7 +788,11 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                     .debug_checked_unwrap();
                 Ref {
                     value: component.deref(),
-                    ticks: Ticks::from_tick_cells(ticks, fetch.last_change_tick, fetch.change_tick),
+                    ticks: Ticks::from_optional_tick_cells(
+                        ticks,
+                        fetch.last_change_tick,
+                        fetch.change_tick,
+                    ),
                 }
             }
         }
@@ -803,6 +824,10 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -917,8 +942,10 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                 Mut {
                     value: table_components.get(table_row.index()).deref_mut(),
                     ticks: TicksMut {
-                        added: added_ticks.get(table_row.index()).deref_mut(),
-                        changed: changed_ticks.get(table_row.index()).deref_mut(),
+                        added: T::CHANGE_DETECTION
+                            .then(|| added_ticks.get(table_row.index()).deref_mut()),
+                        changed: T::CHANGE_DETECTION
+                            .then(|| changed_ticks.get(table_row.index()).deref_mut()),
                         change_tick: fetch.change_tick,
                         last_change_tick: fetch.last_change_tick,
                     },
@@ -932,7 +959,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                     .debug_checked_unwrap();
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: TicksMut::from_tick_cells(
+                    ticks: TicksMut::from_optional_tick_cells(
                         ticks,
                         fetch.last_change_tick,
                         fetch.change_tick,
@@ -968,6 +995,10 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &state: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1072,6 +1103,10 @@ unsafe impl<T: WorldQuery> WorldQuery for Option<T> {
         T::init_state(world)
     }
 
+    fn get_state(world: &World) -> Option<T::State> {
+        T::get_state(world)
+    }
+
     fn matches_component_set(
         _state: &T::State,
         _set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1287,6 +1322,11 @@ unsafe impl<T: Component> WorldQuery for ChangeTrackers<T> {
     }
 
     fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            T::CHANGE_DETECTION,
+            "ChangeTrackers<{}> can't be used because the component opted out of change detection.",
+            std::any::type_name::<T>(),
+        );
         assert!(
             !access.access().has_write(id),
             "ChangeTrackers<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
@@ -1309,6 +1349,10 @@ unsafe impl<T: Component> WorldQuery for ChangeTrackers<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -1410,6 +1454,10 @@ macro_rules! impl_tuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_world: &World) -> Option<Self::State> {
+                Some(($($name::get_state(_world)?,)*))
+            }
+
             fn matches_component_set(state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = state;
                 true $(&& $name::matches_component_set($name, _set_contains_id))*
@@ -1552,6 +1600,10 @@ macro_rules! impl_anytuple_fetch {
                 ($($name::init_state(_world),)*)
             }
 
+            fn get_state(_world: &World) -> Option<Self::State> {
+                Some(($($name::get_state(_world)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($name,)*) = _state;
                 false $(|| $name::matches_component_set($name, _set_contains_id))*
@@ -1629,6 +1681,10 @@ unsafe impl<Q: WorldQuery> WorldQuery for NopWorldQuery<Q> {
         Q::init_state(world)
     }
 
+    fn get_state(world: &World) -> Option<Self::State> {
+        Q::get_state(world)
+    }
+
     fn matches_component_set(
         state: &Self::State,
         set_contains_id: &impl Fn(ComponentId) -> bool,
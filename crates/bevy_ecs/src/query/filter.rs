@@ -106,6 +106,10 @@ unsafe impl<T: Component> WorldQuery for With<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -208,6 +212,10 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
         world.init_component::<T>()
     }
 
+    fn get_state(world: &World) -> Option<ComponentId> {
+        world.components().component_id::<T>()
+    }
+
     fn matches_component_set(
         &id: &ComponentId,
         set_contains_id: &impl Fn(ComponentId) -> bool,
@@ -386,6 +394,10 @@ macro_rules! impl_query_filter_tuple {
                 ($($filter::init_state(world),)*)
             }
 
+            fn get_state(world: &World) -> Option<Self::State> {
+                Some(($($filter::get_state(world)?,)*))
+            }
+
             fn matches_component_set(_state: &Self::State, _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 let ($($filter,)*) = _state;
                 false $(|| $filter::matches_component_set($filter, _set_contains_id))*
@@ -534,6 +546,12 @@ macro_rules! impl_tick_filter {
 
             #[inline]
             fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+                assert!(
+                    T::CHANGE_DETECTION,
+                    "{}<{}> can't be used because the component opted out of change detection.",
+                    stringify!($name),
+                    std::any::type_name::<T>(),
+                );
                 if access.access().has_write(id) {
                     panic!("$state_name<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
                         std::any::type_name::<T>());
@@ -556,6 +574,10 @@ macro_rules! impl_tick_filter {
                 world.init_component::<T>()
             }
 
+            fn get_state(world: &World) -> Option<ComponentId> {
+                world.components().component_id::<T>()
+            }
+
             fn matches_component_set(&id: &ComponentId, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
                 set_contains_id(id)
             }
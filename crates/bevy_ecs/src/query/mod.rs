@@ -2,12 +2,14 @@ mod access;
 mod fetch;
 mod filter;
 mod iter;
+mod par_iter;
 mod state;
 
 pub use access::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
+pub use par_iter::*;
 pub use state::*;
 
 /// A debug checked version of [`Option::unwrap_unchecked`]. Will panic in
@@ -710,6 +712,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn many_entities_order() {
+        let mut world = World::new();
+        let e1 = world.spawn((A(1), B(1))).id();
+        let e2 = world.spawn(A(2)).id();
+        let e3 = world.spawn((A(3), B(3))).id();
+        let e4 = world.spawn((A(4), Sparse(4))).id();
+        let e5 = world.spawn(B(5)).id();
+        let despawned = world.spawn(A(6)).id();
+        world.despawn(despawned);
+
+        // entities are visited in list order, moving back and forth between archetypes
+        let list = [e3, e5, e2, despawned, e1, e4, e1, e2];
+        let mut query = world.query::<&A>();
+        let values = query
+            .iter_many(&world, &list)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![3, 2, 1, 4, 1, 2]);
+
+        let mut query = world.query_filtered::<&mut A, Without<B>>();
+        let mut iter = query.iter_many_mut(&mut world, &list);
+        while let Some(mut a) = iter.fetch_next() {
+            a.0 *= 10;
+        }
+        let mut query = world.query::<&A>();
+        let values = query
+            .iter_many(&world, [e1, e2, e3, e4])
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 200, 3, 40]);
+    }
+
     #[test]
     fn mut_to_immut_query_methods_have_immut_item() {
         #[derive(Component)]
@@ -0,0 +1,271 @@
+use crate::world::World;
+use bevy_tasks::ComputeTaskPool;
+use std::ops::Range;
+
+use super::{QueryItem, QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery};
+
+/// Dictates how a parallel query chunks up large tables/archetypes
+/// during iteration.
+///
+/// A parallel query will chunk up large tables and archetypes into
+/// chunks of at most a certain batch size.
+///
+/// By default, this batch size is automatically determined by dividing
+/// the size of the largest matched archetype by the number
+/// of threads (rounded up). This attempts to minimize the overhead of scheduling
+/// tasks onto multiple threads, but assumes each entity has roughly the
+/// same amount of work to be done, which may not hold true in every
+/// workload.
+///
+/// See [`Query::par_iter`] for more information.
+///
+/// [`Query::par_iter`]: crate::system::Query::par_iter
+#[derive(Clone, Debug)]
+pub struct BatchingStrategy {
+    /// The upper and lower limits for how large a batch of entities.
+    ///
+    /// Setting the bounds to the same value will result in a fixed
+    /// batch size.
+    ///
+    /// Defaults to `[1, usize::MAX]`.
+    pub batch_size_limits: Range<usize>,
+    /// The number of batches per thread in the [`ComputeTaskPool`].
+    /// Increasing this value will decrease the batch size, which may
+    /// increase the scheduling overhead for the iteration, but lets idle
+    /// threads steal work from busy ones when the work per entity varies.
+    ///
+    /// Defaults to 1.
+    pub batches_per_thread: usize,
+}
+
+impl Default for BatchingStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchingStrategy {
+    /// Creates a new unconstrained default batching strategy.
+    pub const fn new() -> Self {
+        Self {
+            batch_size_limits: 1..usize::MAX,
+            batches_per_thread: 1,
+        }
+    }
+
+    /// Declares a batching strategy with a fixed batch size.
+    pub const fn fixed(batch_size: usize) -> Self {
+        Self {
+            batch_size_limits: batch_size..batch_size,
+            batches_per_thread: 1,
+        }
+    }
+
+    /// Configures the minimum allowed batch size of this instance.
+    pub const fn min_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size_limits.start = batch_size;
+        self
+    }
+
+    /// Configures the maximum allowed batch size of this instance.
+    pub const fn max_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size_limits.end = batch_size;
+        self
+    }
+
+    /// Configures the number of batches to assign to each thread for this instance.
+    pub fn batches_per_thread(mut self, batches_per_thread: usize) -> Self {
+        assert!(
+            batches_per_thread > 0,
+            "The number of batches per thread must be non-zero."
+        );
+        self.batches_per_thread = batches_per_thread;
+        self
+    }
+
+    /// Returns the batch size to use for a query whose largest matched table or archetype
+    /// contains `max_size` entities, when running on `thread_count` threads.
+    pub fn batch_size(&self, max_size: usize, thread_count: usize) -> usize {
+        if self.batch_size_limits.is_empty() {
+            return self.batch_size_limits.start.max(1);
+        }
+
+        let batches = thread_count.max(1) * self.batches_per_thread;
+        let mut batch_size = max_size / batches;
+        // round up so the largest archetype is split into at most `batches` batches
+        if batch_size * batches < max_size {
+            batch_size += 1;
+        }
+        batch_size
+            .clamp(self.batch_size_limits.start, self.batch_size_limits.end)
+            .max(1)
+    }
+}
+
+/// A parallel iterator over query results of a [`Query`](crate::system::Query).
+///
+/// This struct is created by the [`Query::par_iter`](crate::system::Query::par_iter) and
+/// [`Query::par_iter_mut`](crate::system::Query::par_iter_mut) methods.
+pub struct QueryParIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> {
+    pub(crate) world: &'w World,
+    pub(crate) state: &'s QueryState<Q, F>,
+    pub(crate) batching_strategy: BatchingStrategy,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
+}
+
+impl<'w, 's, Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery> QueryParIter<'w, 's, Q, F> {
+    /// Runs `func` on each query result in parallel.
+    ///
+    /// This can only be called for read-only queries, see [`Self::for_each_mut`] for
+    /// write-queries.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn for_each<FN: Fn(ROQueryItem<'w, Q>) + Send + Sync + Clone>(&self, func: FN) {
+        // SAFETY: query is read only
+        unsafe {
+            self.for_each_unchecked(func);
+        }
+    }
+
+    /// Folds the query results into one value per batch in parallel, and returns the values
+    /// of all batches in iteration order.
+    ///
+    /// Each batch starts from `init()`, so the returned values can be combined with a
+    /// sequential [`Iterator::fold`] or [`Iterator::sum`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// fn total_health(query: Query<&Health>) {
+    ///     let total: u32 = query
+    ///         .par_iter()
+    ///         .fold(|| 0, |sum, health| sum + health.0)
+    ///         .into_iter()
+    ///         .sum();
+    ///     println!("{total}");
+    /// }
+    /// # bevy_ecs::system::assert_is_system(total_health);
+    /// ```
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn fold<T, INIT, FOLD>(&self, init: INIT, fold: FOLD) -> Vec<T>
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FOLD: Fn(T, ROQueryItem<'w, Q>) -> T + Send + Sync + Clone,
+    {
+        // SAFETY: query is read only
+        unsafe { self.fold_unchecked(init, fold) }
+    }
+
+    /// Maps each query result to a value in parallel, and returns the values in iteration order.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn map<T, FN>(&self, func: FN) -> Vec<T>
+    where
+        T: Send + 'static,
+        FN: Fn(ROQueryItem<'w, Q>) -> T + Send + Sync + Clone,
+    {
+        let batches = self.fold(Vec::new, move |mut batch, item| {
+            batch.push(func(item));
+            batch
+        });
+        batches.into_iter().flatten().collect()
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryParIter<'w, 's, Q, F> {
+    /// Changes the batching strategy used when iterating.
+    ///
+    /// For more information on how this affects the resultant iteration, see
+    /// [`BatchingStrategy`].
+    pub fn batching_strategy(mut self, strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = strategy;
+        self
+    }
+
+    /// Runs `func` on each query result in parallel.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn for_each_mut<FN: Fn(QueryItem<'w, Q>) + Send + Sync + Clone>(&mut self, func: FN) {
+        // SAFETY: query has unique world access
+        unsafe {
+            self.for_each_unchecked(func);
+        }
+    }
+
+    /// Folds the query results into one value per batch in parallel, and returns the values
+    /// of all batches in iteration order.
+    ///
+    /// See [`Self::fold`] for more details.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn fold_mut<T, INIT, FOLD>(&mut self, init: INIT, fold: FOLD) -> Vec<T>
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FOLD: Fn(T, QueryItem<'w, Q>) -> T + Send + Sync + Clone,
+    {
+        // SAFETY: query has unique world access
+        unsafe { self.fold_unchecked(init, fold) }
+    }
+
+    /// Runs `func` on each query result in parallel.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    #[inline]
+    pub unsafe fn for_each_unchecked<FN: Fn(QueryItem<'w, Q>) + Send + Sync + Clone>(
+        &self,
+        func: FN,
+    ) {
+        self.fold_unchecked(|| (), move |(), item| func(item));
+    }
+
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    unsafe fn fold_unchecked<T, INIT, FOLD>(&self, init: INIT, fold: FOLD) -> Vec<T>
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FOLD: Fn(T, QueryItem<'w, Q>) -> T + Send + Sync + Clone,
+    {
+        let thread_count = ComputeTaskPool::get().thread_num();
+        let batch_size = self
+            .batching_strategy
+            .batch_size(self.state.max_matched_storage_len(self.world), thread_count);
+        self.state.par_fold_unchecked_manual(
+            self.world,
+            batch_size,
+            init,
+            fold,
+            self.last_change_tick,
+            self.change_tick,
+        )
+    }
+}
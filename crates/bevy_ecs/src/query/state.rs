@@ -15,7 +15,9 @@ use bevy_utils::tracing::Instrument;
 use fixedbitset::FixedBitSet;
 use std::{borrow::Borrow, fmt, mem::MaybeUninit};
 
-use super::{NopWorldQuery, QueryManyIter, ROQueryItem, ReadOnlyWorldQuery};
+use super::{
+    BatchingStrategy, NopWorldQuery, QueryManyIter, QueryParIter, ROQueryItem, ReadOnlyWorldQuery,
+};
 
 /// Provides scoped access to a [`World`] state according to a given [`WorldQuery`] and query filter.
 #[repr(C)]
@@ -35,6 +37,9 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    // NOTE: this is `Q::IS_DENSE && F::IS_DENSE`, unless the state was transmuted from a sparse
+    // query, whose matched tables may contain entities the query doesn't match
+    pub(crate) is_dense: bool,
 }
 
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> std::fmt::Debug for QueryState<Q, F> {
@@ -122,11 +127,84 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            is_dense: Q::IS_DENSE && F::IS_DENSE,
         };
         state.update_archetypes(world);
         state
     }
 
+    /// Creates a [`QueryState`] for `NewQ` that matches the same entities as this one.
+    ///
+    /// This allows a query to be viewed as a narrower one, for example `(&A, &mut B)` as `&A`,
+    /// so it can be passed to functions that only accept the narrower query.
+    ///
+    /// The returned state only matches the archetypes this state currently matches. Calling
+    /// [`update_archetypes`](Self::update_archetypes) on it adds new archetypes based on
+    /// `NewQ` alone, so it is best used through a [`QueryLens`](crate::system::QueryLens).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `NewQ` accesses data this query doesn't, or accesses mutably what this
+    /// query only reads, or if a component `NewQ` uses is not registered in the `world`.
+    pub fn transmute<NewQ: WorldQuery>(&self, world: &World) -> QueryState<NewQ> {
+        self.transmute_filtered::<NewQ, ()>(world)
+    }
+
+    /// Creates a [`QueryState`] for `NewQ` and the additional filter `NewF`, that matches the
+    /// entities this one matches which also match `NewF`.
+    ///
+    /// See [`transmute`](Self::transmute) for more details.
+    pub fn transmute_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &self,
+        world: &World,
+    ) -> QueryState<NewQ, NewF> {
+        self.validate_world(world);
+        let new_state_name = std::any::type_name::<QueryState<NewQ, NewF>>();
+        let (Some(fetch_state), Some(filter_state)) =
+            (NewQ::get_state(world), NewF::get_state(world))
+        else {
+            panic!(
+                "Could not transmute {} to {}: all components the new query uses must be registered in the World.",
+                std::any::type_name::<Self>(),
+                new_state_name,
+            );
+        };
+
+        let mut component_access = FilteredAccess::default();
+        NewQ::update_component_access(&fetch_state, &mut component_access);
+        let mut filter_component_access = FilteredAccess::default();
+        NewF::update_component_access(&filter_state, &mut filter_component_access);
+        component_access.extend(&filter_component_access);
+        assert!(
+            component_access
+                .access()
+                .is_subset(self.component_access.access()),
+            "Could not transmute {} to {}: the new query accesses data the original one can't.",
+            std::any::type_name::<Self>(),
+            new_state_name,
+        );
+
+        let mut state = QueryState {
+            world_id: self.world_id,
+            archetype_generation: self.archetype_generation,
+            matched_table_ids: Vec::new(),
+            matched_archetype_ids: Vec::new(),
+            fetch_state,
+            filter_state,
+            component_access,
+            matched_tables: Default::default(),
+            matched_archetypes: Default::default(),
+            archetype_component_access: Default::default(),
+            // tables are only matched as a whole if both queries are dense
+            is_dense: self.is_dense && NewQ::IS_DENSE && NewF::IS_DENSE,
+        };
+        let archetypes = world.archetypes();
+        for archetype_id in &self.matched_archetype_ids {
+            state.new_archetype(&archetypes[*archetype_id]);
+        }
+        state
+    }
+
     /// Checks if the query is empty for the given [`World`], where the last change and current tick are given.
     #[inline]
     pub fn is_empty(&self, world: &World, last_change_tick: u32, change_tick: u32) -> bool {
@@ -813,6 +891,45 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         );
     }
 
+    /// Returns a parallel iterator over the query results for the given [`World`].
+    ///
+    /// This can only be called for read-only queries, see [`Self::par_iter_mut`] for
+    /// write-queries.
+    ///
+    /// Unlike [`Self::par_for_each`], the batch size is chosen automatically based on the
+    /// sizes of the matched archetypes, see [`BatchingStrategy`](super::BatchingStrategy).
+    #[inline]
+    pub fn par_iter<'w, 's>(
+        &'s mut self,
+        world: &'w World,
+    ) -> QueryParIter<'w, 's, Q::ReadOnly, F::ReadOnly> {
+        self.update_archetypes(world);
+        QueryParIter {
+            world,
+            state: self.as_readonly(),
+            batching_strategy: BatchingStrategy::new(),
+            last_change_tick: world.last_change_tick(),
+            change_tick: world.read_change_tick(),
+        }
+    }
+
+    /// Returns a parallel iterator over the query results for the given [`World`].
+    ///
+    /// Unlike [`Self::par_for_each_mut`], the batch size is chosen automatically based on the
+    /// sizes of the matched archetypes, see [`BatchingStrategy`](super::BatchingStrategy).
+    #[inline]
+    pub fn par_iter_mut<'w, 's>(&'s mut self, world: &'w mut World) -> QueryParIter<'w, 's, Q, F> {
+        let change_tick = world.change_tick();
+        self.update_archetypes(world);
+        QueryParIter {
+            world,
+            state: self,
+            batching_strategy: BatchingStrategy::new(),
+            last_change_tick: world.last_change_tick(),
+            change_tick,
+        }
+    }
+
     /// Runs `func` on each query result in parallel.
     ///
     /// This can only be called for read-only queries, see [`Self::par_for_each_mut`] for
@@ -919,7 +1036,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         let mut filter = F::init_fetch(world, &self.filter_state, last_change_tick, change_tick);
 
         let tables = &world.storages().tables;
-        if Q::IS_DENSE && F::IS_DENSE {
+        if self.is_dense {
             for table_id in &self.matched_table_ids {
                 let table = tables.get(*table_id).debug_checked_unwrap();
                 Q::set_table(&mut fetch, &self.fetch_state, table);
@@ -988,10 +1105,50 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_change_tick: u32,
         change_tick: u32,
     ) {
+        self.par_fold_unchecked_manual(
+            world,
+            batch_size,
+            || (),
+            move |(), item| func(item),
+            last_change_tick,
+            change_tick,
+        );
+    }
+
+    /// Splits the query results for the given [`World`] into batches of at most `batch_size`
+    /// items, and folds each batch in parallel into a value starting from `init()`.
+    ///
+    /// Returns the value of each batch in iteration order.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn par_fold_unchecked_manual<'w, T, INIT, FOLD>(
+        &self,
+        world: &'w World,
+        batch_size: usize,
+        init: INIT,
+        fold: FOLD,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Vec<T>
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FOLD: Fn(T, Q::Item<'w>) -> T + Send + Sync + Clone,
+    {
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
+        assert!(batch_size > 0, "batch size must be greater than zero");
         ComputeTaskPool::get().scope(|scope| {
-            if Q::IS_DENSE && F::IS_DENSE {
+            if self.is_dense {
                 let tables = &world.storages().tables;
                 for table_id in &self.matched_table_ids {
                     let table = &tables[*table_id];
@@ -1001,7 +1158,8 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
 
                     let mut offset = 0;
                     while offset < table.entity_count() {
-                        let func = func.clone();
+                        let init = init.clone();
+                        let fold = fold.clone();
                         let len = batch_size.min(table.entity_count() - offset);
                         let task = async move {
                             let mut fetch = Q::init_fetch(
@@ -1021,14 +1179,16 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
                             let entities = table.entities();
                             Q::set_table(&mut fetch, &self.fetch_state, table);
                             F::set_table(&mut filter, &self.filter_state, table);
+                            let mut accum = init();
                             for row in offset..offset + len {
                                 let entity = entities.get_unchecked(row);
                                 let row = TableRow::new(row);
                                 if !F::filter_fetch(&mut filter, *entity, row) {
                                     continue;
                                 }
-                                func(Q::fetch(&mut fetch, *entity, row));
+                                accum = fold(accum, Q::fetch(&mut fetch, *entity, row));
                             }
+                            accum
                         };
                         #[cfg(feature = "trace")]
                         let span = bevy_utils::tracing::info_span!(
@@ -1053,7 +1213,8 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
                     }
 
                     while offset < archetype.len() {
-                        let func = func.clone();
+                        let init = init.clone();
+                        let fold = fold.clone();
                         let len = batch_size.min(archetype.len() - offset);
                         let task = async move {
                             let mut fetch = Q::init_fetch(
@@ -1076,6 +1237,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
                             F::set_archetype(&mut filter, &self.filter_state, archetype, table);
 
                             let entities = archetype.entities();
+                            let mut accum = init();
                             for archetype_row in offset..offset + len {
                                 let archetype_entity = entities.get_unchecked(archetype_row);
                                 if !F::filter_fetch(
@@ -1085,12 +1247,16 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
                                 ) {
                                     continue;
                                 }
-                                func(Q::fetch(
-                                    &mut fetch,
-                                    archetype_entity.entity(),
-                                    archetype_entity.table_row(),
-                                ));
+                                accum = fold(
+                                    accum,
+                                    Q::fetch(
+                                        &mut fetch,
+                                        archetype_entity.entity(),
+                                        archetype_entity.table_row(),
+                                    ),
+                                );
                             }
+                            accum
                         };
 
                         #[cfg(feature = "trace")]
@@ -1108,7 +1274,26 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
                     }
                 }
             }
-        });
+        })
+    }
+
+    /// Returns the number of entities in the largest table (for dense queries) or archetype
+    /// matched by this query, used to size the batches of parallel iteration.
+    pub(crate) fn max_matched_storage_len(&self, world: &World) -> usize {
+        if self.is_dense {
+            let tables = &world.storages().tables;
+            self.matched_table_ids
+                .iter()
+                .map(|id| tables[*id].entity_count())
+                .max()
+                .unwrap_or(0)
+        } else {
+            self.matched_archetype_ids
+                .iter()
+                .map(|id| world.archetypes[*id].len())
+                .max()
+                .unwrap_or(0)
+        }
     }
 
     /// Returns a single immutable query result when there is exactly one entity matching
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 pub use common_conditions::*;
 
-use crate::system::BoxedSystem;
+use crate::system::{BoxedSystem, Combinator, CombinatorSystem, IntoSystem, System};
 
 pub type BoxedCondition = BoxedSystem<(), bool>;
 
@@ -8,27 +10,147 @@ pub type BoxedCondition = BoxedSystem<(), bool>;
 ///
 /// Implemented for functions and closures that convert into [`System<In=(), Out=bool>`](crate::system::System)
 /// with [read-only](crate::system::ReadOnlySystemParam) parameters.
-pub trait Condition<Params>: sealed::Condition<Params> {}
+pub trait Condition<Params>: sealed::Condition<Params> {
+    /// Returns a new run condition that only returns `true`
+    /// if both this one and the passed `and_then` return `true`.
+    ///
+    /// The returned run condition is short-circuiting, meaning
+    /// `and_then` will only be invoked if `self` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_ecs::schedule_v3::{resource_exists, resource_equals, Condition, IntoSystemConfig, Schedule};
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct R(u32);
+    ///
+    /// # let mut app = Schedule::new();
+    /// # let mut world = World::new();
+    /// # fn my_system() {}
+    /// app.add_system(
+    ///     // The `resource_equals` run condition will panic since we don't initialize `R`,
+    ///     // just like if we used `Res<R>` in a system.
+    ///     my_system.run_if(resource_equals(R(0))),
+    /// );
+    /// # app.run(&mut world);
+    /// ```
+    ///
+    /// Use `.and_then()` to avoid checking the condition.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::schedule_v3::{resource_exists, resource_equals, Condition, IntoSystemConfig, Schedule};
+    /// # #[derive(Resource, PartialEq)]
+    /// # struct R(u32);
+    /// # let mut app = Schedule::new();
+    /// # let mut world = World::new();
+    /// # fn my_system() {}
+    /// app.add_system(
+    ///     // `resource_equals` will only get run if the resource `R` exists.
+    ///     my_system.run_if(resource_exists::<R>().and_then(resource_equals(R(0)))),
+    /// );
+    /// # app.run(&mut world);
+    /// ```
+    ///
+    /// Note that in this case, it's better to just use the run condition [`resource_exists_and_equals`].
+    fn and_then<P, C: Condition<P>>(self, and_then: C) -> AndThen<Self::System, C::System> {
+        let a = IntoSystem::into_system(self);
+        let b = IntoSystem::into_system(and_then);
+        let name = format!("{} && {}", a.name(), b.name());
+        CombinatorSystem::new(a, b, Cow::Owned(name))
+    }
+
+    /// Returns a new run condition that returns `true`
+    /// if either this one or the passed `or_else` return `true`.
+    ///
+    /// The returned run condition is short-circuiting, meaning
+    /// `or_else` will only be invoked if `self` returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_ecs::schedule_v3::{resource_equals, Condition, IntoSystemConfig, Schedule};
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct A(u32);
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct B(u32);
+    ///
+    /// # let mut app = Schedule::new();
+    /// # let mut world = World::new();
+    /// # #[derive(Resource)] struct C(bool);
+    /// # fn my_system(mut c: ResMut<C>) { c.0 = true; }
+    /// app.add_system(
+    ///     // Only run the system if either `A` or `B` exist.
+    ///     my_system.run_if(resource_equals(A(0)).or_else(resource_equals(B(0)))),
+    /// );
+    /// #
+    /// # world.insert_resource(C(false));
+    /// # world.insert_resource(A(1));
+    /// # world.insert_resource(B(1));
+    /// # app.run(&mut world);
+    /// # assert!(!world.resource::<C>().0);
+    /// #
+    /// # world.insert_resource(B(0));
+    /// # app.run(&mut world);
+    /// # assert!(world.resource::<C>().0);
+    /// ```
+    fn or_else<P, C: Condition<P>>(self, or_else: C) -> OrElse<Self::System, C::System> {
+        let a = IntoSystem::into_system(self);
+        let b = IntoSystem::into_system(or_else);
+        let name = format!("{} || {}", a.name(), b.name());
+        CombinatorSystem::new(a, b, Cow::Owned(name))
+    }
+}
 
 impl<Params, F> Condition<Params> for F where F: sealed::Condition<Params> {}
 
 mod sealed {
-    use crate::system::{IntoSystem, IsFunctionSystem, ReadOnlySystemParam, SystemParamFunction};
+    use crate::system::{IntoSystem, ReadOnlySystem};
 
-    pub trait Condition<Params>: IntoSystem<(), bool, Params> {}
+    pub trait Condition<Params>:
+        IntoSystem<(), bool, Params, System = Self::ReadOnlySystem>
+    {
+        // This associated type is necessary to let the compiler
+        // know that `Self::System` is `ReadOnlySystem`.
+        type ReadOnlySystem: ReadOnlySystem<In = (), Out = bool>;
+    }
 
-    impl<Params, Marker, F> Condition<(IsFunctionSystem, Params, Marker)> for F
+    impl<Params, F> Condition<Params> for F
     where
-        F: SystemParamFunction<(), bool, Params, Marker> + Send + Sync + 'static,
-        Params: ReadOnlySystemParam + 'static,
-        Marker: 'static,
+        F: IntoSystem<(), bool, Params>,
+        F::System: ReadOnlySystem,
     {
+        type ReadOnlySystem = F::System;
     }
 }
 
 mod common_conditions {
-    use crate::schedule_v3::{State, States};
-    use crate::system::{Res, Resource};
+    use super::Condition;
+    use crate::{
+        change_detection::DetectChanges,
+        event::{Event, EventReader},
+        schedule_v3::{State, States},
+        system::{In, IntoPipeSystem, Res, Resource},
+    };
+
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the first time the condition is run and false every time after
+    pub fn run_once() -> impl FnMut() -> bool {
+        let mut has_run = false;
+        move || {
+            if !has_run {
+                has_run = true;
+                true
+            } else {
+                false
+            }
+        }
+    }
 
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if the resource exists.
@@ -66,6 +188,50 @@ mod common_conditions {
         }
     }
 
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the resource of the given type has been added since the condition was last checked.
+    pub fn resource_added<T>() -> impl FnMut(Option<Res<T>>) -> bool
+    where
+        T: Resource,
+    {
+        move |res: Option<Res<T>>| match res {
+            Some(res) => res.is_added(),
+            None => false,
+        }
+    }
+
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the resource of the given type has had its value changed since the condition
+    /// was last checked.
+    ///
+    /// The value is considered changed when it is added. The first time this condition
+    /// is checked after the resource was added, it will return `true`.
+    ///
+    /// # Panics
+    ///
+    /// The condition will panic if the resource does not exist.
+    pub fn resource_changed<T>() -> impl FnMut(Res<T>) -> bool
+    where
+        T: Resource,
+    {
+        move |res: Res<T>| res.is_changed()
+    }
+
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the resource of the given type exists and has had its value changed since the
+    /// condition was last checked.
+    ///
+    /// The condition will return `false` if the resource does not exist.
+    pub fn resource_exists_and_changed<T>() -> impl FnMut(Option<Res<T>>) -> bool
+    where
+        T: Resource,
+    {
+        move |res: Option<Res<T>>| match res {
+            Some(res) => res.is_changed(),
+            None => false,
+        }
+    }
+
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if the state machine exists.
     pub fn state_exists<S: States>() -> impl FnMut(Option<Res<State<S>>>) -> bool {
@@ -94,4 +260,116 @@ mod common_conditions {
             None => false,
         }
     }
+
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the state machine changed state since the condition was last checked.
+    ///
+    /// This is also `true` the first time the condition is checked after the state
+    /// machine was added.
+    ///
+    /// # Panics
+    ///
+    /// The condition will panic if the resource does not exist.
+    pub fn state_changed<S: States>() -> impl FnMut(Res<State<S>>) -> bool {
+        move |current_state: Res<State<S>>| current_state.is_changed()
+    }
+
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if there are any new events of the given type since it was last called.
+    ///
+    /// The events are consumed by the condition, so they will not be seen again by it.
+    /// Other [`EventReader`]s, including the ones of the systems this condition guards,
+    /// are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// The condition will panic if the `Events<T>` resource does not exist.
+    pub fn on_event<T: Event>() -> impl FnMut(EventReader<T>) -> bool {
+        // The events need to be consumed, so that there are no false positives on subsequent
+        // calls of the run condition. Simply checking `is_empty` would not be enough.
+        move |mut reader: EventReader<T>| {
+            let has_events = !reader.is_empty();
+            reader.clear();
+            has_events
+        }
+    }
+
+    /// Generates a [`Condition`](super::Condition) that inverses the result of passed one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_ecs::schedule_v3::{not, resource_exists, IntoSystemConfig, Schedule};
+    ///
+    /// #[derive(Resource)]
+    /// struct Paused;
+    ///
+    /// # #[derive(Resource)] struct Counter(u32);
+    /// # fn tick(mut counter: ResMut<Counter>) { counter.0 += 1; }
+    /// let mut schedule = Schedule::new();
+    /// schedule.add_system(tick.run_if(not(resource_exists::<Paused>())));
+    ///
+    /// let mut world = World::new();
+    /// # world.insert_resource(Counter(0));
+    /// schedule.run(&mut world);
+    /// # assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// world.insert_resource(Paused);
+    /// schedule.run(&mut world);
+    /// # assert_eq!(world.resource::<Counter>().0, 1);
+    /// ```
+    pub fn not<Params>(
+        condition: impl Condition<Params>,
+    ) -> impl Condition<crate::system::AlreadyWasSystem> {
+        condition.pipe(|In(val): In<bool>| !val)
+    }
+}
+
+/// Combines the outputs of two systems using the `&&` operator.
+pub type AndThen<A, B> = CombinatorSystem<AndThenMarker, A, B>;
+
+/// Combines the outputs of two systems using the `||` operator.
+pub type OrElse<A, B> = CombinatorSystem<OrElseMarker, A, B>;
+
+#[doc(hidden)]
+pub struct AndThenMarker;
+
+impl<In, A, B> Combinator<A, B> for AndThenMarker
+where
+    In: Copy,
+    A: System<In = In, Out = bool>,
+    B: System<In = In, Out = bool>,
+{
+    type In = In;
+    type Out = bool;
+
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(<A as System>::In) -> <A as System>::Out,
+        b: impl FnOnce(<B as System>::In) -> <B as System>::Out,
+    ) -> Self::Out {
+        a(input) && b(input)
+    }
+}
+
+#[doc(hidden)]
+pub struct OrElseMarker;
+
+impl<In, A, B> Combinator<A, B> for OrElseMarker
+where
+    In: Copy,
+    A: System<In = In, Out = bool>,
+    B: System<In = In, Out = bool>,
+{
+    type In = In;
+    type Out = bool;
+
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(<A as System>::In) -> <A as System>::Out,
+        b: impl FnOnce(<B as System>::In) -> <B as System>::Out,
+    ) -> Self::Out {
+        a(input) || b(input)
+    }
 }
@@ -4,6 +4,7 @@ use bevy_utils::default;
 use crate::{
     schedule_v3::{
         condition::{BoxedCondition, Condition},
+        error::{handle_errors, BevyError, ScheduleErrorPolicy},
         graph_utils::{Ambiguity, Dependency, DependencyKind, GraphInfo},
         set::{BoxedSystemSet, IntoSystemSet, SystemSet},
     },
@@ -43,6 +44,9 @@ pub struct SystemConfig {
     pub(super) system: BoxedSystem,
     pub(super) graph_info: GraphInfo,
     pub(super) conditions: Vec<BoxedCondition>,
+    pub(super) defer_buffers: bool,
+    /// The schedule's error policy, shared with the error handler of a fallible system.
+    pub(super) error_policy: Option<ScheduleErrorPolicy>,
 }
 
 impl SystemConfig {
@@ -57,6 +61,16 @@ impl SystemConfig {
                 ambiguous_with: default(),
             },
             conditions: Vec::new(),
+            defer_buffers: false,
+            error_policy: None,
+        }
+    }
+
+    fn new_fallible<Params>(system: impl IntoSystem<(), Result<(), BevyError>, Params>) -> Self {
+        let (system, error_policy) = handle_errors(system);
+        Self {
+            error_policy: Some(error_policy),
+            ..Self::new(system)
         }
     }
 }
@@ -222,7 +236,9 @@ impl IntoSystemSetConfig for SystemSetConfig {
 /// Types that can be converted into a [`SystemConfig`].
 ///
 /// This has been implemented for boxed [`System<In=(), Out=()>`](crate::system::System)
-/// trait objects and all functions that turn into such.
+/// trait objects and all functions that turn into such. Functions that turn into systems
+/// returning `Result<(), BevyError>` are also accepted, their errors are handled according to the
+/// [`SystemErrorPolicy`](super::SystemErrorPolicy).
 pub trait IntoSystemConfig<Params>: sealed::IntoSystemConfig<Params> {
     /// Convert into a [`SystemConfig`].
     #[doc(hidden)]
@@ -244,11 +260,17 @@ pub trait IntoSystemConfig<Params>: sealed::IntoSystemConfig<Params> {
     /// Suppress warnings and errors that would result from this system having ambiguities
     /// (conflicting access but indeterminate order) with any other system.
     fn ambiguous_with_all(self) -> SystemConfig;
+    /// Only apply this system's buffers (e.g. [`Commands`](crate::system::Commands)) at
+    /// [`apply_deferred`](super::apply_deferred) sync points.
+    ///
+    /// They are not applied by [`apply_system_buffers`](super::apply_system_buffers) or at the
+    /// end of the schedule, so heavy command batches can be flushed at a chosen point.
+    fn defer_buffers(self) -> SystemConfig;
 }
 
-impl<Params, F> IntoSystemConfig<Params> for F
+impl<Params, F> IntoSystemConfig<(Infallible, Params)> for F
 where
-    F: IntoSystem<(), (), Params> + sealed::IntoSystemConfig<Params>,
+    F: IntoSystem<(), (), Params> + sealed::IntoSystemConfig<(Infallible, Params)>,
 {
     fn into_config(self) -> SystemConfig {
         SystemConfig::new(Box::new(IntoSystem::into_system(self)))
@@ -277,6 +299,47 @@ where
     fn ambiguous_with_all(self) -> SystemConfig {
         SystemConfig::new(Box::new(IntoSystem::into_system(self))).ambiguous_with_all()
     }
+
+    fn defer_buffers(self) -> SystemConfig {
+        SystemConfig::new(Box::new(IntoSystem::into_system(self))).defer_buffers()
+    }
+}
+
+impl<Params, F> IntoSystemConfig<(Fallible, Params)> for F
+where
+    F: IntoSystem<(), Result<(), BevyError>, Params> + sealed::IntoSystemConfig<(Fallible, Params)>,
+{
+    fn into_config(self) -> SystemConfig {
+        SystemConfig::new_fallible(self)
+    }
+
+    fn in_set(self, set: impl SystemSet) -> SystemConfig {
+        SystemConfig::new_fallible(self).in_set(set)
+    }
+
+    fn before<M>(self, set: impl IntoSystemSet<M>) -> SystemConfig {
+        SystemConfig::new_fallible(self).before(set)
+    }
+
+    fn after<M>(self, set: impl IntoSystemSet<M>) -> SystemConfig {
+        SystemConfig::new_fallible(self).after(set)
+    }
+
+    fn run_if<P>(self, condition: impl Condition<P>) -> SystemConfig {
+        SystemConfig::new_fallible(self).run_if(condition)
+    }
+
+    fn ambiguous_with<M>(self, set: impl IntoSystemSet<M>) -> SystemConfig {
+        SystemConfig::new_fallible(self).ambiguous_with(set)
+    }
+
+    fn ambiguous_with_all(self) -> SystemConfig {
+        SystemConfig::new_fallible(self).ambiguous_with_all()
+    }
+
+    fn defer_buffers(self) -> SystemConfig {
+        SystemConfig::new_fallible(self).defer_buffers()
+    }
 }
 
 impl IntoSystemConfig<()> for BoxedSystem<(), ()> {
@@ -307,6 +370,10 @@ impl IntoSystemConfig<()> for BoxedSystem<(), ()> {
     fn ambiguous_with_all(self) -> SystemConfig {
         SystemConfig::new(self).ambiguous_with_all()
     }
+
+    fn defer_buffers(self) -> SystemConfig {
+        SystemConfig::new(self).defer_buffers()
+    }
 }
 
 impl IntoSystemConfig<()> for SystemConfig {
@@ -353,20 +420,39 @@ impl IntoSystemConfig<()> for SystemConfig {
         self.graph_info.ambiguous_with = Ambiguity::IgnoreAll;
         self
     }
+
+    fn defer_buffers(mut self) -> Self {
+        self.defer_buffers = true;
+        self
+    }
 }
 
-// only `System<In=(), Out=()>` system objects can be scheduled
+/// Marker for [`IntoSystemConfig`] implementations of systems that return `()`.
+#[doc(hidden)]
+pub struct Infallible;
+
+/// Marker for [`IntoSystemConfig`] implementations of systems that return
+/// `Result<(), BevyError>`.
+#[doc(hidden)]
+pub struct Fallible;
+
+// only `System<In=(), Out=()>` system objects and fallible systems can be scheduled
 mod sealed {
     use crate::{
-        schedule_v3::{BoxedSystemSet, SystemSet},
+        schedule_v3::{BevyError, BoxedSystemSet, SystemSet},
         system::{BoxedSystem, IntoSystem},
     };
 
-    use super::{SystemConfig, SystemSetConfig};
+    use super::{Fallible, Infallible, SystemConfig, SystemSetConfig};
 
     pub trait IntoSystemConfig<Params> {}
 
-    impl<Params, F: IntoSystem<(), (), Params>> IntoSystemConfig<Params> for F {}
+    impl<Params, F: IntoSystem<(), (), Params>> IntoSystemConfig<(Infallible, Params)> for F {}
+
+    impl<Params, F: IntoSystem<(), Result<(), BevyError>, Params>>
+        IntoSystemConfig<(Fallible, Params)> for F
+    {
+    }
 
     impl IntoSystemConfig<()> for BoxedSystem<(), ()> {}
 
@@ -424,6 +510,11 @@ where
         self.into_configs().ambiguous_with_all()
     }
 
+    /// Only apply the buffers of these systems at [`apply_deferred`](super::apply_deferred) sync points.
+    fn defer_buffers(self) -> SystemConfigs {
+        self.into_configs().defer_buffers()
+    }
+
     /// Treat this collection as a sequence of systems.
     ///
     /// Ordering constraints will be applied between the successive elements.
@@ -490,6 +581,14 @@ impl IntoSystemConfigs<()> for SystemConfigs {
         self
     }
 
+    fn defer_buffers(mut self) -> Self {
+        for config in &mut self.systems {
+            config.defer_buffers = true;
+        }
+
+        self
+    }
+
     fn chain(mut self) -> Self {
         self.chained = true;
         self
@@ -0,0 +1,187 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use bevy_utils::tracing::{debug, error};
+
+use crate as bevy_ecs;
+use crate::event::Events;
+use crate::system::{BoxedSystem, Commands, In, IntoSystem, PipeSystem, Res, Resource, System};
+use crate::world::World;
+
+/// An error returned by a fallible system.
+///
+/// Any type implementing [`Error`] converts into a `BevyError`, so systems returning
+/// `Result<(), BevyError>` can use `?` instead of unwrapping. Errors are handled according to
+/// the [`SystemErrorPolicy`] of the schedule running the system.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule_v3::{BevyError, Schedule, SystemErrorPolicy};
+/// #[derive(Resource)]
+/// struct Input(String);
+///
+/// #[derive(Resource)]
+/// struct Score(u32);
+///
+/// fn parse_score(input: Res<Input>, mut score: ResMut<Score>) -> Result<(), BevyError> {
+///     score.0 = input.0.parse()?;
+///     Ok(())
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Input("not a number".to_string()));
+/// world.insert_resource(Score(0));
+///
+/// let mut schedule = Schedule::new();
+/// schedule
+///     .add_system(parse_score)
+///     .set_error_policy(SystemErrorPolicy::Log);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Score>().0, 0);
+/// ```
+pub struct BevyError(Box<dyn Error + Send + Sync + 'static>);
+
+impl BevyError {
+    /// Returns the underlying error.
+    pub fn inner(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.0
+    }
+
+    /// Returns the underlying error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync + 'static> {
+        self.0
+    }
+}
+
+// BevyError can't implement Error itself, as that would conflict with this impl
+impl<E: Error + Send + Sync + 'static> From<E> for BevyError {
+    fn from(error: E) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+impl fmt::Debug for BevyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for BevyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// An error returned by a system, along with the name of that system.
+#[derive(Debug)]
+pub struct SystemError {
+    /// The name of the system that returned the error.
+    pub system_name: Cow<'static, str>,
+    /// The error returned by the system.
+    pub error: BevyError,
+}
+
+impl fmt::Display for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Encountered an error in system `{}`: {}",
+            self.system_name, self.error
+        )
+    }
+}
+
+/// Specifies how errors returned by fallible systems are handled.
+///
+/// Insert this as a resource to change the behavior for the whole [`World`], or use
+/// [`Schedule::set_error_policy`](super::Schedule::set_error_policy) to change it for the systems
+/// of a single schedule. If neither is set, [`SystemErrorPolicy::Panic`] is used.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SystemErrorPolicy {
+    /// Panic with the error message.
+    #[default]
+    Panic,
+    /// Log the error at the error level.
+    Log,
+    /// Silently discard the error. It is still logged at the debug level.
+    Ignore,
+    /// Send the error as an event when the system's buffers are applied, so it can be read with
+    /// an [`EventReader<SystemError>`](crate::event::EventReader).
+    ///
+    /// The `Events<SystemError>` resource must be registered for this to work, otherwise the
+    /// error is logged at the error level instead.
+    SendEvent,
+}
+
+impl SystemErrorPolicy {
+    /// Handles `error` according to this policy.
+    ///
+    /// Events are sent through `commands`.
+    pub fn handle(self, error: SystemError, commands: &mut Commands) {
+        match self {
+            SystemErrorPolicy::Panic => panic!("{}", error),
+            SystemErrorPolicy::Log => error!("{}", error),
+            SystemErrorPolicy::Ignore => debug!("{}", error),
+            SystemErrorPolicy::SendEvent => commands.add(move |world: &mut World| {
+                if let Some(mut events) = world.get_resource_mut::<Events<SystemError>>() {
+                    events.send(error);
+                } else {
+                    error!("{}", error);
+                }
+            }),
+        }
+    }
+}
+
+/// The [`SystemErrorPolicy`] set with
+/// [`Schedule::set_error_policy`](super::Schedule::set_error_policy), shared between the schedule
+/// and the error handler of one of its fallible systems.
+#[derive(Clone, Default)]
+pub(super) struct ScheduleErrorPolicy(Arc<Mutex<Option<SystemErrorPolicy>>>);
+
+impl ScheduleErrorPolicy {
+    pub(super) fn get(&self) -> Option<SystemErrorPolicy> {
+        *self.0.lock().unwrap()
+    }
+
+    pub(super) fn set(&self, policy: Option<SystemErrorPolicy>) {
+        *self.0.lock().unwrap() = policy;
+    }
+}
+
+/// Converts a fallible system into one that handles its errors according to the policy of its
+/// schedule, or the [`SystemErrorPolicy`] resource if the schedule doesn't set one.
+///
+/// The returned system keeps the name and system type set of the original.
+pub(super) fn handle_errors<Params>(
+    system: impl IntoSystem<(), Result<(), BevyError>, Params>,
+) -> (BoxedSystem, ScheduleErrorPolicy) {
+    let system = IntoSystem::into_system(system);
+    let name = system.name();
+    let system_name = name.clone();
+    let schedule_policy = ScheduleErrorPolicy::default();
+    let handler_schedule_policy = schedule_policy.clone();
+    let handler = move |In(result): In<Result<(), BevyError>>,
+                        policy: Option<Res<SystemErrorPolicy>>,
+                        mut commands: Commands| {
+        if let Err(error) = result {
+            let error = SystemError {
+                system_name: system_name.clone(),
+                error,
+            };
+            let policy = handler_schedule_policy
+                .get()
+                .or_else(|| policy.map(|policy| *policy))
+                .unwrap_or_default();
+            policy.handle(error, &mut commands);
+        }
+    };
+    let system = Box::new(PipeSystem::new(
+        system,
+        IntoSystem::into_system(handler),
+        name,
+    ));
+    (system, schedule_policy)
+}
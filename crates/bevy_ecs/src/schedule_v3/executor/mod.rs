@@ -55,6 +55,10 @@ pub(super) struct SystemSchedule {
     pub(super) system_dependents: Vec<Vec<usize>>,
     pub(super) sets_of_systems: Vec<FixedBitSet>,
     pub(super) systems_in_sets: Vec<FixedBitSet>,
+    /// Systems whose buffers are only applied by [`apply_deferred`].
+    pub(super) deferred_systems: FixedBitSet,
+    /// Whether systems must be run in batches that don't depend on thread timing.
+    pub(super) deterministic: bool,
 }
 
 impl SystemSchedule {
@@ -69,6 +73,8 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_of_systems: Vec::new(),
             systems_in_sets: Vec::new(),
+            deferred_systems: FixedBitSet::new(),
+            deterministic: false,
         }
     }
 }
@@ -76,6 +82,9 @@ impl SystemSchedule {
 /// Instructs the executor to call [`apply_buffers`](crate::system::System::apply_buffers)
 /// on the systems that have run but not applied their buffers.
 ///
+/// Systems configured with [`defer_buffers`](super::IntoSystemConfig::defer_buffers) are skipped,
+/// use [`apply_deferred`] to apply their buffers as well.
+///
 /// **Notes**
 /// - This function (currently) does nothing if it's called manually or wrapped inside a [`PipeSystem`](crate::system::PipeSystem).
 /// - Modifying a [`Schedule`](super::Schedule) may change the order buffers are applied.
@@ -88,3 +97,23 @@ pub(super) fn is_apply_system_buffers(system: &BoxedSystem) -> bool {
     // deref to use `System::type_id` instead of `Any::type_id`
     system.as_ref().type_id() == apply_system_buffers.type_id()
 }
+
+/// Like [`apply_system_buffers`], but also instructs the executor to call
+/// [`apply_buffers`](crate::system::System::apply_buffers) on every system configured with
+/// [`defer_buffers`](super::IntoSystemConfig::defer_buffers).
+///
+/// This is an explicit control point for flushing heavy command batches (e.g. spawning a level),
+/// whose systems would otherwise be flushed at the next [`apply_system_buffers`] or at the end
+/// of the schedule.
+///
+/// **Notes**
+/// - This function (currently) does nothing if it's called manually or wrapped inside a [`PipeSystem`](crate::system::PipeSystem).
+#[allow(unused_variables)]
+pub fn apply_deferred(world: &mut World) {}
+
+/// Returns `true` if the [`System`](crate::system::System) is an instance of [`apply_deferred`].
+pub(super) fn is_apply_deferred(system: &BoxedSystem) -> bool {
+    use std::any::Any;
+    // deref to use `System::type_id` instead of `Any::type_id`
+    system.as_ref().type_id() == apply_deferred.type_id()
+}
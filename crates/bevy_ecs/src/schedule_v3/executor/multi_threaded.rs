@@ -11,7 +11,8 @@ use crate::{
     archetype::ArchetypeComponentId,
     query::Access,
     schedule_v3::{
-        is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        is_apply_deferred, is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor,
+        SystemSchedule,
     },
     system::BoxedSystem,
     world::World,
@@ -91,6 +92,10 @@ pub struct MultiThreadedExecutor {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// Systems whose buffers are only applied by `apply_deferred`.
+    deferred_systems: FixedBitSet,
+    /// Returns `true` if all running systems must complete before more are spawned.
+    deterministic: bool,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -116,6 +121,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
         self.skipped_systems = FixedBitSet::with_capacity(sys_count);
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
+        self.deferred_systems = schedule.deferred_systems.clone();
 
         self.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
@@ -128,6 +134,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         }
 
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
+        self.deterministic = schedule.deterministic;
     }
 
     fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
@@ -175,6 +182,16 @@ impl SystemExecutor for MultiThreadedExecutor {
                             self.finish_system_and_signal_dependents(index);
                         }
 
+                        // the next batch must not depend on which systems finished first
+                        while self.deterministic && self.num_running_systems > 0 {
+                            let index = self
+                                .receiver
+                                .recv()
+                                .await
+                                .unwrap_or_else(|error| unreachable!("{}", error));
+                            self.finish_system_and_signal_dependents(index);
+                        }
+
                         self.rebuild_active_access();
                     }
                 }
@@ -221,6 +238,8 @@ impl MultiThreadedExecutor {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            deferred_systems: FixedBitSet::new(),
+            deterministic: false,
         }
     }
 
@@ -457,9 +476,13 @@ impl MultiThreadedExecutor {
         let system_span = info_span!("system", name = &*system.name());
 
         let sender = self.sender.clone();
-        if is_apply_system_buffers(system) {
+        let apply_deferred = is_apply_deferred(system);
+        if apply_deferred || is_apply_system_buffers(system) {
             // TODO: avoid allocation
             let mut unapplied_systems = self.unapplied_systems.clone();
+            if apply_deferred {
+                unapplied_systems.union_with(&self.deferred_systems);
+            }
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
@@ -511,7 +534,9 @@ impl MultiThreadedExecutor {
         self.num_completed_systems += 1;
         self.running_systems.set(system_index, false);
         self.completed_systems.insert(system_index);
-        self.unapplied_systems.insert(system_index);
+        if !self.deferred_systems.contains(system_index) {
+            self.unapplied_systems.insert(system_index);
+        }
         self.signal_dependents(system_index);
     }
 
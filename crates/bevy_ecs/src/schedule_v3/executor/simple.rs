@@ -3,12 +3,15 @@ use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
 
 use crate::{
-    schedule_v3::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule_v3::{
+        is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+    },
     world::World,
 };
 
 /// A variant of [`SingleThreadedExecutor`](crate::schedule_v3::SingleThreadedExecutor) that calls
-/// [`apply_buffers`](crate::system::System::apply_buffers) immediately after running each system.
+/// [`apply_buffers`](crate::system::System::apply_buffers) immediately after running each system,
+/// except for systems configured with [`defer_buffers`](crate::schedule_v3::IntoSystemConfig::defer_buffers).
 #[derive(Default)]
 pub struct SimpleExecutor {
     /// Systems sets whose conditions have been evaluated.
@@ -72,12 +75,28 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let system = &mut schedule.systems[system_index];
+            if is_apply_deferred(system) {
+                for deferred_index in schedule.deferred_systems.ones() {
+                    let system = &mut schedule.systems[deferred_index];
+                    #[cfg(feature = "trace")]
+                    let _apply_buffers_span =
+                        info_span!("apply_buffers", name = &*system.name()).entered();
+                    system.apply_buffers(world);
+                }
+                continue;
+            }
+
             #[cfg(feature = "trace")]
             let system_span = info_span!("system", name = &*name).entered();
             system.run((), world);
             #[cfg(feature = "trace")]
             system_span.exit();
 
+            // deferred systems wait for the next `apply_deferred`
+            if schedule.deferred_systems.contains(system_index) {
+                continue;
+            }
+
             #[cfg(feature = "trace")]
             let _apply_buffers_span = info_span!("apply_buffers", name = &*name).entered();
             system.apply_buffers(world);
@@ -4,7 +4,8 @@ use fixedbitset::FixedBitSet;
 
 use crate::{
     schedule_v3::{
-        is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        is_apply_deferred, is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor,
+        SystemSchedule,
     },
     world::World,
 };
@@ -80,9 +81,14 @@ impl SystemExecutor for SingleThreadedExecutor {
             }
 
             let system = &mut schedule.systems[system_index];
-            if is_apply_system_buffers(system) {
+            let apply_deferred = is_apply_deferred(system);
+            if apply_deferred || is_apply_system_buffers(system) {
                 #[cfg(feature = "trace")]
                 let system_span = info_span!("system", name = &*name).entered();
+                if apply_deferred {
+                    self.unapplied_systems
+                        .union_with(&schedule.deferred_systems);
+                }
                 self.apply_system_buffers(schedule, world);
                 #[cfg(feature = "trace")]
                 system_span.exit();
@@ -92,7 +98,9 @@ impl SystemExecutor for SingleThreadedExecutor {
                 system.run((), world);
                 #[cfg(feature = "trace")]
                 system_span.exit();
-                self.unapplied_systems.insert(system_index);
+                if !schedule.deferred_systems.contains(system_index) {
+                    self.unapplied_systems.insert(system_index);
+                }
             }
         }
 
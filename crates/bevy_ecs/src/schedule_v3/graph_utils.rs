@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Debug};
 
 use bevy_utils::{
     petgraph::{graphmap::NodeTrait, prelude::*},
@@ -231,3 +231,49 @@ where
         transitive_closure,
     }
 }
+
+/// Returns a topological ordering of a DAG in which nodes without an ordering between them are
+/// sorted by `key`.
+///
+/// Unlike the ordering produced by a strongly connected components search, this one depends
+/// only on the graph's edges and the keys, not on the order nodes and edges were added in.
+pub(crate) fn stable_topsort<V, K>(graph: &DiGraphMap<V, ()>, mut key: impl FnMut(V) -> K) -> Vec<V>
+where
+    V: NodeTrait,
+    K: Ord,
+{
+    let mut in_degrees = HashMap::with_capacity(graph.node_count());
+    let mut ready = BinaryHeap::new();
+    for node in graph.nodes() {
+        let in_degree = graph.neighbors_directed(node, Direction::Incoming).count();
+        if in_degree == 0 {
+            ready.push(Reverse((key(node), node)));
+        } else {
+            in_degrees.insert(node, in_degree);
+        }
+    }
+
+    let mut topsort = Vec::with_capacity(graph.node_count());
+    while let Some(Reverse((_, node))) = ready.pop() {
+        topsort.push(node);
+        for succ in graph.neighbors_directed(node, Direction::Outgoing) {
+            let in_degree = in_degrees.get_mut(&succ).unwrap();
+            *in_degree -= 1;
+            if *in_degree == 0 {
+                ready.push(Reverse((key(succ), succ)));
+            }
+        }
+    }
+
+    debug_assert_eq!(topsort.len(), graph.node_count(), "graph contains a cycle");
+    topsort
+}
+
+/// Mixes `seed` and `value` into a well-distributed hash that is stable across runs and
+/// platforms (the finalizer of the SplitMix64 generator).
+pub(crate) fn seeded_hash(seed: u64, value: u64) -> u64 {
+    let mut z = seed.wrapping_add(value.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -26,13 +26,23 @@ pub trait AppExt {
 pub trait WorldExt {
     /// Runs the [`Schedule`] associated with `label`.
     fn run_schedule(&mut self, label: impl ScheduleLabel);
+    /// Runs the [`Schedule`] associated with `label`.
+    ///
+    /// Unlike [`run_schedule`](Self::run_schedule), this works with labels that are only
+    /// available as trait objects, such as a [`BoxedScheduleLabel`].
+    fn run_schedule_ref(&mut self, label: &dyn ScheduleLabel);
 }
 
 impl WorldExt for World {
     fn run_schedule(&mut self, label: impl ScheduleLabel) {
-        if let Some(mut schedule) = self.resource_mut::<Schedules>().remove(&label) {
+        self.run_schedule_ref(&label);
+    }
+
+    fn run_schedule_ref(&mut self, label: &dyn ScheduleLabel) {
+        if let Some(mut schedule) = self.resource_mut::<Schedules>().remove(label) {
             schedule.run(self);
-            self.resource_mut::<Schedules>().insert(label, schedule);
+            self.resource_mut::<Schedules>()
+                .insert_boxed(label.dyn_clone(), schedule);
         }
     }
 }
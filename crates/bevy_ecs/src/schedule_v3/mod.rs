@@ -1,5 +1,6 @@
 mod condition;
 mod config;
+mod error;
 mod executor;
 mod graph_utils;
 mod migration;
@@ -9,6 +10,7 @@ mod state;
 
 pub use self::condition::*;
 pub use self::config::*;
+pub use self::error::*;
 pub use self::executor::*;
 use self::graph_utils::*;
 pub use self::migration::*;
@@ -38,7 +40,7 @@ mod tests {
     #[derive(Resource, Default)]
     struct SystemOrder(Vec<u32>);
 
-    #[derive(Resource, Default)]
+    #[derive(Resource, Default, PartialEq)]
     struct RunConditionBool(pub bool);
 
     #[derive(Resource, Default)]
@@ -116,6 +118,171 @@ mod tests {
         }
     }
 
+    mod system_buffers {
+        use super::*;
+        use crate::system::Commands;
+
+        fn spawn_entity(mut commands: Commands) {
+            commands.spawn_empty();
+        }
+
+        fn count_entities(world: &mut World) {
+            let count = world.entities().len();
+            world.resource_mut::<SystemOrder>().0.push(count);
+        }
+
+        fn run_deferred_buffers(executor: ExecutorKind) {
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+
+            world.init_resource::<SystemOrder>();
+
+            schedule.add_systems(
+                (
+                    spawn_entity.defer_buffers(),
+                    apply_system_buffers,
+                    count_entities,
+                    apply_deferred,
+                    count_entities,
+                    spawn_entity.defer_buffers(),
+                )
+                    .chain(),
+            );
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1]);
+            // the last system's buffers are not applied at the end of the schedule
+            assert_eq!(world.entities().len(), 1);
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1, 1, 3]);
+            assert_eq!(world.entities().len(), 3);
+        }
+
+        #[test]
+        fn deferred_buffers_single_threaded() {
+            run_deferred_buffers(ExecutorKind::SingleThreaded);
+        }
+
+        #[test]
+        fn deferred_buffers_multi_threaded() {
+            run_deferred_buffers(ExecutorKind::MultiThreaded);
+        }
+
+        #[test]
+        fn deferred_buffers_simple() {
+            run_deferred_buffers(ExecutorKind::Simple);
+        }
+    }
+
+    mod fallible_systems {
+        use super::*;
+        use crate::event::Events;
+
+        fn parse(input: &str) -> Result<(), BevyError> {
+            input.parse::<u32>()?;
+            Ok(())
+        }
+
+        fn succeed(mut order: ResMut<SystemOrder>) -> Result<(), BevyError> {
+            order.0.push(0);
+            parse("0")
+        }
+
+        fn fail(mut order: ResMut<SystemOrder>) -> Result<(), BevyError> {
+            order.0.push(1);
+            parse("one")?;
+            order.0.push(2);
+            Ok(())
+        }
+
+        fn take_errors(world: &mut World) -> Vec<SystemError> {
+            world
+                .resource_mut::<Events<SystemError>>()
+                .drain()
+                .collect()
+        }
+
+        #[test]
+        fn send_error_events() {
+            let mut world = World::new();
+            world.init_resource::<SystemOrder>();
+            world.init_resource::<Events<SystemError>>();
+            world.insert_resource(SystemErrorPolicy::SendEvent);
+
+            let mut schedule = Schedule::new();
+            schedule.add_systems((succeed, fail.after(succeed)));
+            schedule.run(&mut world);
+
+            assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1]);
+            let errors = take_errors(&mut world);
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].system_name.ends_with("fail"));
+            assert_eq!(errors[0].error.to_string(), "invalid digit found in string");
+        }
+
+        #[test]
+        fn schedule_error_policy() {
+            let mut world = World::new();
+            world.init_resource::<SystemOrder>();
+            world.insert_resource(SystemErrorPolicy::Panic);
+
+            let mut schedule = Schedule::new();
+            schedule
+                .add_system(fail)
+                .set_error_policy(SystemErrorPolicy::Ignore);
+            world.clear_trackers();
+            schedule.run(&mut world);
+
+            assert_eq!(world.resource::<SystemOrder>().0, vec![1]);
+            // the world's policy is left untouched
+            assert_eq!(
+                *world.resource::<SystemErrorPolicy>(),
+                SystemErrorPolicy::Panic
+            );
+            assert!(!world.is_resource_changed::<SystemErrorPolicy>());
+
+            // systems added after the policy was set use it as well
+            schedule.add_system(fail);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<SystemOrder>().0, vec![1, 1, 1]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panic_by_default() {
+            let mut world = World::new();
+            world.init_resource::<SystemOrder>();
+
+            let mut schedule = Schedule::new();
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            schedule.add_system(fail);
+            schedule.run(&mut world);
+        }
+
+        #[test]
+        fn fallible_exclusive_system() {
+            fn spawn_and_fail(world: &mut World) -> Result<(), BevyError> {
+                world.spawn_empty();
+                parse("one")
+            }
+
+            let mut world = World::new();
+            world.init_resource::<Events<SystemError>>();
+
+            let mut schedule = Schedule::new();
+            schedule
+                .add_system(spawn_and_fail)
+                .set_error_policy(SystemErrorPolicy::SendEvent);
+            schedule.run(&mut world);
+
+            assert_eq!(world.entities().len(), 1);
+            assert_eq!(take_errors(&mut world).len(), 1);
+            assert!(!world.contains_resource::<SystemErrorPolicy>());
+        }
+    }
+
     mod system_ordering {
         use super::*;
 
@@ -194,6 +361,43 @@ mod tests {
             schedule.run(&mut world);
             assert_eq!(world.resource::<SystemOrder>().0, vec![0, 1, 2, 3]);
         }
+
+        #[test]
+        fn deterministic_execution() {
+            fn run_with_seed(seed: u64) -> Vec<u32> {
+                let mut world = World::new();
+                world.init_resource::<SystemOrder>();
+
+                let mut schedule = Schedule::new();
+                schedule.set_build_settings(
+                    ScheduleBuildSettings::new().with_deterministic_execution(seed),
+                );
+                // all of these conflict, but only the last two are ordered
+                for tag in 0..6 {
+                    schedule.add_system(make_function_system(tag));
+                }
+                schedule.add_system(make_exclusive_system(6));
+                schedule.add_system(make_function_system(7).after(named_system));
+                schedule.add_system(named_system);
+
+                schedule.run(&mut world);
+                schedule.run(&mut world);
+                std::mem::take(&mut world.resource_mut::<SystemOrder>().0)
+            }
+
+            let order = run_with_seed(7);
+            assert_eq!(order.len(), 18);
+            // both runs of the schedule use the same order
+            assert_eq!(order[..9], order[9..]);
+            let named = order.iter().position(|&tag| tag == u32::MAX).unwrap();
+            let seventh = order.iter().position(|&tag| tag == 7).unwrap();
+            assert!(named < seventh);
+
+            for _ in 0..4 {
+                assert_eq!(run_with_seed(7), order);
+            }
+            assert_ne!(run_with_seed(8), order);
+        }
     }
 
     mod conditions {
@@ -277,6 +481,90 @@ mod tests {
             assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 1);
         }
 
+        #[test]
+        fn combined_conditions() {
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<Counter>();
+
+            schedule.add_system(counting_system.run_if((|| false).and_then(|| true)));
+            schedule.add_system(counting_system.run_if((|| true).and_then(|| true)));
+            schedule.add_system(counting_system.run_if((|| false).or_else(|| false)));
+            schedule.add_system(counting_system.run_if((|| false).or_else(|| true)));
+            schedule.add_system(counting_system.run_if(not(|| true)));
+            schedule.add_system(counting_system.run_if(not((|| false).or_else(|| false))));
+            schedule.configure_set(TestSet::A.run_if(not(|| false).and_then(|| true)));
+            schedule.add_system(counting_system.in_set(TestSet::A));
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 4);
+        }
+
+        #[test]
+        fn combined_conditions_short_circuit() {
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<Counter>();
+
+            // `resource_equals` would panic if it was evaluated
+            schedule.add_system(
+                counting_system.run_if(
+                    resource_exists::<RunConditionBool>()
+                        .and_then(resource_equals(RunConditionBool(true))),
+                ),
+            );
+            schedule.add_system(
+                counting_system.run_if((|| true).or_else(resource_equals(RunConditionBool(true)))),
+            );
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 1);
+        }
+
+        #[test]
+        fn event_and_state_conditions() {
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            enum GameState {
+                Menu,
+                Playing,
+            }
+
+            impl States for GameState {
+                type Iter = std::array::IntoIter<GameState, 2>;
+
+                fn states() -> Self::Iter {
+                    [GameState::Menu, GameState::Playing].into_iter()
+                }
+            }
+
+            struct Ping;
+
+            let mut world = World::default();
+            world.init_resource::<Counter>();
+            world.init_resource::<crate::event::Events<Ping>>();
+            world.insert_resource(State(GameState::Menu));
+            let mut schedule = Schedule::default();
+
+            schedule.add_system(counting_system.run_if(on_event::<Ping>()));
+            schedule.add_system(counting_system.run_if(state_changed::<GameState>()));
+            schedule.add_system(counting_system.run_if(state_equals(GameState::Playing)));
+
+            // the state was just added
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 1);
+
+            world.send_event(Ping);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 2);
+
+            // the event was consumed by the condition
+            world.resource_mut::<State<GameState>>().0 = GameState::Playing;
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 4);
+        }
+
         #[test]
         fn systems_nested_in_system_sets() {
             let mut world = World::default();
@@ -619,4 +907,259 @@ mod tests {
             assert!(matches!(result, Err(ScheduleBuildError::Ambiguity)));
         }
     }
+
+    mod graph_export {
+        use super::*;
+
+        #[derive(Resource)]
+        struct X;
+
+        fn res_ref(_x: Res<X>) {}
+        fn res_mut(_x: ResMut<X>) {}
+        fn unrelated() {}
+
+        fn build_schedule(world: &mut World) -> Schedule {
+            let mut schedule = Schedule::new();
+            schedule.add_systems((
+                res_ref,
+                res_mut,
+                unrelated.before(res_mut).in_set(TestSet::A),
+            ));
+            schedule.initialize(world).unwrap();
+            schedule
+        }
+
+        #[test]
+        fn ambiguity_report() {
+            let mut world = World::new();
+            let schedule = build_schedule(&mut world);
+
+            let ambiguities = schedule.ambiguities();
+            assert_eq!(ambiguities.len(), 1);
+            let ambiguity = &ambiguities[0];
+            assert!(ambiguity.system_a.ends_with("res_ref"));
+            assert!(ambiguity.system_b.ends_with("res_mut"));
+            assert_eq!(ambiguity.conflicts, vec![std::any::type_name::<X>()]);
+
+            // resolving the ambiguity removes it from the report
+            let mut schedule = Schedule::new();
+            schedule.add_systems((res_ref, res_mut.ambiguous_with(res_ref)));
+            schedule.initialize(&mut world).unwrap();
+            assert!(schedule.ambiguities().is_empty());
+        }
+
+        #[test]
+        fn export() {
+            let mut world = World::new();
+            let schedule = build_schedule(&mut world);
+
+            let dot = schedule.graph_to_dot();
+            assert!(dot.starts_with("digraph schedule {"));
+            assert!(dot.contains("system_2 -> system_1;"));
+            assert!(dot.contains("[label=\"A\", shape=ellipse]"));
+            assert!(dot.contains("-> system_2 [style=dashed];"));
+            assert!(dot.contains("system_0 -> system_1 [dir=none, style=dashed, color=red"));
+
+            let json = schedule.graph_to_json();
+            assert!(json.contains("{ \"before\": \"system_2\", \"after\": \"system_1\" }"));
+            assert!(json.contains(&format!(
+                "{{ \"system_a\": \"system_0\", \"system_b\": \"system_1\", \"conflicts\": [\"{}\"] }}",
+                std::any::type_name::<X>()
+            )));
+        }
+    }
+
+    mod states {
+        use super::*;
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        enum AppState {
+            Menu,
+            InGame,
+            Settings,
+        }
+
+        impl States for AppState {
+            type Iter = std::array::IntoIter<AppState, 3>;
+
+            fn states() -> Self::Iter {
+                [AppState::Menu, AppState::InGame, AppState::Settings].into_iter()
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        enum GamePhase {
+            #[default]
+            Running,
+            Paused,
+        }
+
+        impl States for GamePhase {
+            type Iter = std::array::IntoIter<GamePhase, 2>;
+
+            fn states() -> Self::Iter {
+                [GamePhase::Running, GamePhase::Paused].into_iter()
+            }
+        }
+
+        impl SubStates for GamePhase {
+            type Parent = AppState;
+
+            fn exists_in(parent: &AppState) -> bool {
+                *parent == AppState::InGame
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct TransitionLog(Vec<String>);
+
+        fn add_logging_schedules<S: States>(world: &mut World) {
+            let mut schedules = world.get_resource_or_insert_with(Schedules::new);
+            for state in S::states() {
+                let log = |event: &'static str, state: S| {
+                    let mut schedule = Schedule::new();
+                    schedule.add_system(move |mut log: ResMut<TransitionLog>| {
+                        log.0.push(format!("{event} {state:?}"));
+                    });
+                    schedule
+                };
+                schedules.insert(OnEnter(state.clone()), log("enter", state.clone()));
+                schedules.insert(OnExit(state.clone()), log("exit", state.clone()));
+                schedules.insert(OnPause(state.clone()), log("pause", state.clone()));
+                schedules.insert(OnResume(state.clone()), log("resume", state.clone()));
+            }
+        }
+
+        fn take_log(world: &mut World) -> Vec<String> {
+            std::mem::take(&mut world.resource_mut::<TransitionLog>().0)
+        }
+
+        #[test]
+        fn state_stack() {
+            let mut world = World::new();
+            world.init_resource::<TransitionLog>();
+            world.insert_resource(State(AppState::InGame));
+            world.insert_resource(NextState::<AppState>(None));
+            world.init_resource::<StateStack<AppState>>();
+            add_logging_schedules::<AppState>(&mut world);
+
+            world
+                .resource_mut::<StateStack<AppState>>()
+                .push(AppState::Settings);
+            apply_state_transition::<AppState>(&mut world);
+            assert_eq!(take_log(&mut world), vec!["pause InGame", "enter Settings"]);
+            assert_eq!(world.resource::<State<AppState>>().0, AppState::Settings);
+            assert_eq!(world.resource::<StateStack<AppState>>().len(), 1);
+
+            world.resource_mut::<StateStack<AppState>>().pop();
+            apply_state_transition::<AppState>(&mut world);
+            assert_eq!(take_log(&mut world), vec!["exit Settings", "resume InGame"]);
+            assert_eq!(world.resource::<State<AppState>>().0, AppState::InGame);
+            assert!(world.resource::<StateStack<AppState>>().is_empty());
+
+            // popping an empty stack does nothing
+            world.resource_mut::<StateStack<AppState>>().pop();
+            apply_state_transition::<AppState>(&mut world);
+            assert!(take_log(&mut world).is_empty());
+            assert_eq!(world.resource::<State<AppState>>().0, AppState::InGame);
+        }
+
+        #[test]
+        fn sub_states() {
+            let mut world = World::new();
+            world.init_resource::<TransitionLog>();
+            world.insert_resource(State(AppState::Menu));
+            world.insert_resource(NextState::<AppState>(None));
+            add_logging_schedules::<AppState>(&mut world);
+            add_logging_schedules::<GamePhase>(&mut world);
+            register_sub_state::<GamePhase>(&mut world);
+
+            let transition = |world: &mut World| {
+                apply_state_transition::<AppState>(world);
+                apply_sub_state_transition::<GamePhase>(world);
+            };
+
+            transition(&mut world);
+            assert!(!world.contains_resource::<State<GamePhase>>());
+
+            world.resource_mut::<NextState<AppState>>().0 = Some(AppState::InGame);
+            transition(&mut world);
+            assert_eq!(
+                take_log(&mut world),
+                vec!["exit Menu", "enter InGame", "enter Running"]
+            );
+            assert_eq!(world.resource::<State<GamePhase>>().0, GamePhase::Running);
+
+            world.resource_mut::<NextState<GamePhase>>().0 = Some(GamePhase::Paused);
+            transition(&mut world);
+            assert_eq!(take_log(&mut world), vec!["exit Running", "enter Paused"]);
+
+            world.resource_mut::<NextState<AppState>>().0 = Some(AppState::Menu);
+            transition(&mut world);
+            assert_eq!(
+                take_log(&mut world),
+                vec!["exit Paused", "exit InGame", "enter Menu"]
+            );
+            assert!(!world.contains_resource::<State<GamePhase>>());
+            assert!(!world.contains_resource::<NextState<GamePhase>>());
+        }
+
+        #[test]
+        fn state_scoped() {
+            use crate::component::Component;
+
+            #[derive(Component)]
+            struct Marker;
+
+            #[derive(Resource, Default)]
+            struct MenuData(u32);
+
+            let mut world = World::new();
+            world.insert_resource(State(AppState::Menu));
+            world.insert_resource(NextState::<AppState>(None));
+            world.init_resource::<StateStack<AppState>>();
+            let mut schedules = Schedules::new();
+            for state in AppState::states() {
+                let mut on_enter = Schedule::new();
+                let mut on_exit = Schedule::new();
+                on_exit.add_system(clear_state_scoped_entities(state));
+                if state == AppState::Menu {
+                    on_enter.add_system(init_state_scoped_resource::<MenuData>);
+                    on_exit.add_system(remove_state_scoped_resource::<MenuData>);
+                }
+                schedules.insert(OnEnter(state), on_enter);
+                schedules.insert(OnExit(state), on_exit);
+            }
+            world.insert_resource(schedules);
+
+            let menu = world.spawn((Marker, StateScoped(AppState::Menu))).id();
+            let game = world.spawn((Marker, StateScoped(AppState::InGame))).id();
+            let unscoped = world.spawn(Marker).id();
+
+            world.resource_mut::<NextState<AppState>>().0 = Some(AppState::InGame);
+            apply_state_transition::<AppState>(&mut world);
+            assert!(world.get_entity(menu).is_none());
+            assert!(world.get_entity(game).is_some());
+            assert!(!world.contains_resource::<MenuData>());
+
+            // pausing a state doesn't exit it
+            world
+                .resource_mut::<StateStack<AppState>>()
+                .push(AppState::Menu);
+            apply_state_transition::<AppState>(&mut world);
+            assert!(world.get_entity(game).is_some());
+            assert_eq!(world.resource::<MenuData>().0, 0);
+            world.resource_mut::<MenuData>().0 = 1;
+
+            world.resource_mut::<StateStack<AppState>>().pop();
+            apply_state_transition::<AppState>(&mut world);
+            assert!(!world.contains_resource::<MenuData>());
+
+            world.resource_mut::<NextState<AppState>>().0 = Some(AppState::Menu);
+            apply_state_transition::<AppState>(&mut world);
+            assert!(world.get_entity(game).is_none());
+            assert!(world.get_entity(unscoped).is_some());
+            assert_eq!(world.resource::<MenuData>().0, 0);
+        }
+    }
 }
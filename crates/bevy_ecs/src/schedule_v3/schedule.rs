@@ -17,8 +17,8 @@ use fixedbitset::FixedBitSet;
 
 use crate::{
     self as bevy_ecs,
-    component::ComponentId,
-    schedule_v3::*,
+    component::{ComponentId, Components},
+    schedule_v3::{error::ScheduleErrorPolicy, *},
     system::{BoxedSystem, Resource},
     world::World,
 };
@@ -42,7 +42,18 @@ impl Schedules {
     /// If the map already had an entry for `label`, `schedule` is inserted,
     /// and the old schedule is returned. Otherwise, `None` is returned.
     pub fn insert(&mut self, label: impl ScheduleLabel, schedule: Schedule) -> Option<Schedule> {
-        let label: Box<dyn ScheduleLabel> = Box::new(label);
+        self.insert_boxed(Box::new(label), schedule)
+    }
+
+    /// Inserts a schedule into the map under an already boxed label.
+    ///
+    /// If the map already had an entry for `label`, `schedule` is inserted,
+    /// and the old schedule is returned. Otherwise, `None` is returned.
+    pub fn insert_boxed(
+        &mut self,
+        label: BoxedScheduleLabel,
+        schedule: Schedule,
+    ) -> Option<Schedule> {
         if self.inner.contains_key(&label) {
             warn!("schedule with label {:?} already exists", label);
         }
@@ -145,6 +156,7 @@ impl Schedule {
     /// Changes miscellaneous build settings.
     pub fn set_build_settings(&mut self, settings: ScheduleBuildSettings) -> &mut Self {
         self.graph.settings = settings;
+        self.graph.changed = true;
         self
     }
 
@@ -166,6 +178,13 @@ impl Schedule {
         self
     }
 
+    /// Sets how errors returned by the systems of this schedule are handled, taking precedence
+    /// over the [`SystemErrorPolicy`] resource.
+    pub fn set_error_policy(&mut self, policy: SystemErrorPolicy) -> &mut Self {
+        self.graph.set_error_policy(policy);
+        self
+    }
+
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         world.check_change_ticks();
@@ -181,7 +200,8 @@ impl Schedule {
     pub fn initialize(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
         if self.graph.changed {
             self.graph.initialize(world);
-            self.graph.update_schedule(&mut self.executable)?;
+            self.graph
+                .update_schedule(&mut self.executable, world.components())?;
             self.graph.changed = false;
             self.executor_initialized = false;
         }
@@ -194,6 +214,195 @@ impl Schedule {
         Ok(())
     }
 
+    /// Returns the pairs of systems that have conflicting access but no order between them,
+    /// found the last time the schedule was built by [`initialize`](Self::initialize) or
+    /// [`run`](Self::run).
+    ///
+    /// Pairs of systems that were allowed to be ambiguous with `ambiguous_with` are not included.
+    pub fn ambiguities(&self) -> Vec<SystemAmbiguity> {
+        let names = self.node_names();
+        self.graph
+            .conflicting_systems
+            .iter()
+            .map(|(a, b, conflicts)| SystemAmbiguity {
+                system_a: names[a].clone(),
+                system_b: names[b].clone(),
+                conflicts: conflicts.clone(),
+            })
+            .collect()
+    }
+
+    /// Exports the graph of the systems in the schedule in the [DOT] format, so it can be
+    /// visualized with tools like Graphviz.
+    ///
+    /// Systems are drawn as boxes and system sets as ellipses, with dashed arrows to their
+    /// members. Orderings between systems are drawn as solid arrows and
+    /// [ambiguities](Self::ambiguities) as red dashed lines labeled with their conflicts.
+    /// Like the ambiguities, the orderings are the ones found the last time the schedule was
+    /// built.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn graph_to_dot(&self) -> String {
+        let names = self.node_names();
+        let graph = &self.graph;
+        let mut dot = String::from("digraph schedule {\n    node [shape=box];\n");
+
+        for index in 0..graph.systems.len() {
+            let name = escape_dot(&names[&NodeId::System(index)]);
+            writeln!(dot, "    system_{index} [label=\"{name}\"];").unwrap();
+        }
+
+        for index in 0..graph.system_sets.len() {
+            let id = NodeId::Set(index);
+            if !graph.is_system_type_set(id) {
+                let name = escape_dot(&names[&id]);
+                writeln!(dot, "    set_{index} [label=\"{name}\", shape=ellipse];").unwrap();
+            }
+        }
+
+        for (parent, child, _) in graph.hierarchy.graph.all_edges() {
+            if !graph.is_system_type_set(parent) {
+                let (parent, child) = (dot_id(parent), dot_id(child));
+                writeln!(dot, "    {parent} -> {child} [style=dashed];").unwrap();
+            }
+        }
+
+        for (before, after, _) in graph.dependency_flattened.graph.all_edges() {
+            let (before, after) = (dot_id(before), dot_id(after));
+            writeln!(dot, "    {before} -> {after};").unwrap();
+        }
+
+        for (a, b, conflicts) in &graph.conflicting_systems {
+            let label = if conflicts.is_empty() {
+                // one or both systems must be exclusive
+                std::any::type_name::<World>().to_string()
+            } else {
+                conflicts.join(", ")
+            };
+            writeln!(
+                dot,
+                "    {} -> {} [dir=none, style=dashed, color=red, label=\"{}\"];",
+                dot_id(*a),
+                dot_id(*b),
+                escape_dot(&label),
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the graph of the systems in the schedule as JSON, for use in external tools.
+    ///
+    /// The output is an object with the following fields:
+    /// - `systems` and `sets`: arrays of `{ "id", "name" }` objects.
+    /// - `hierarchy`: an array of `{ "parent", "child" }` objects, one per set membership.
+    /// - `dependencies`: an array of `{ "before", "after" }` objects, one per ordering between
+    ///   two systems.
+    /// - `ambiguities`: an array of `{ "system_a", "system_b", "conflicts" }` objects, see
+    ///   [`ambiguities`](Self::ambiguities).
+    ///
+    /// Like the ambiguities, the orderings are the ones found the last time the schedule was
+    /// built.
+    pub fn graph_to_json(&self) -> String {
+        let names = self.node_names();
+        let graph = &self.graph;
+        let mut json = String::from("{\n  \"systems\": [");
+        let systems = (0..graph.systems.len()).map(NodeId::System);
+        let sets = (0..graph.system_sets.len())
+            .map(NodeId::Set)
+            .filter(|&id| !graph.is_system_type_set(id));
+        write_json_array(&mut json, systems, |json, id| {
+            write!(json, "{{ \"id\": \"{}\", \"name\": ", dot_id(id)).unwrap();
+            write_json_string(json, &names[&id]);
+            json.push_str(" }");
+        });
+
+        json.push_str(",\n  \"sets\": [");
+        write_json_array(&mut json, sets, |json, id| {
+            write!(json, "{{ \"id\": \"{}\", \"name\": ", dot_id(id)).unwrap();
+            write_json_string(json, &names[&id]);
+            json.push_str(" }");
+        });
+
+        json.push_str(",\n  \"hierarchy\": [");
+        let memberships = graph
+            .hierarchy
+            .graph
+            .all_edges()
+            .filter(|&(parent, _, _)| !graph.is_system_type_set(parent));
+        write_json_array(&mut json, memberships, |json, (parent, child, _)| {
+            write!(
+                json,
+                "{{ \"parent\": \"{}\", \"child\": \"{}\" }}",
+                dot_id(parent),
+                dot_id(child)
+            )
+            .unwrap();
+        });
+
+        json.push_str(",\n  \"dependencies\": [");
+        let dependencies = graph.dependency_flattened.graph.all_edges();
+        write_json_array(&mut json, dependencies, |json, (before, after, _)| {
+            write!(
+                json,
+                "{{ \"before\": \"{}\", \"after\": \"{}\" }}",
+                dot_id(before),
+                dot_id(after)
+            )
+            .unwrap();
+        });
+
+        json.push_str(",\n  \"ambiguities\": [");
+        write_json_array(
+            &mut json,
+            graph.conflicting_systems.iter(),
+            |json, (a, b, conflicts)| {
+                write!(
+                    json,
+                    "{{ \"system_a\": \"{}\", \"system_b\": \"{}\", \"conflicts\": [",
+                    dot_id(*a),
+                    dot_id(*b)
+                )
+                .unwrap();
+                for (i, conflict) in conflicts.iter().enumerate() {
+                    if i > 0 {
+                        json.push_str(", ");
+                    }
+                    write_json_string(json, conflict);
+                }
+                json.push_str("] }");
+            },
+        );
+
+        json.push_str("\n}\n");
+        json
+    }
+
+    /// Returns the names of all systems and system sets in the schedule, including the ones
+    /// that were moved into the executable schedule.
+    fn node_names(&self) -> HashMap<NodeId, String> {
+        let mut names = HashMap::new();
+        for (index, system) in self.graph.systems.iter().enumerate() {
+            if let Some(system) = system {
+                names.insert(NodeId::System(index), system.name().to_string());
+            }
+        }
+        for (&id, system) in self
+            .executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+        {
+            names.insert(id, system.name().to_string());
+        }
+        for (index, set) in self.graph.system_sets.iter().enumerate() {
+            names.insert(NodeId::Set(index), set.name());
+        }
+        names
+    }
+
     /// Iterates the change ticks of all systems in the schedule and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -273,7 +482,12 @@ struct ScheduleGraph {
     ambiguous_with: UnGraphMap<NodeId, ()>,
     ambiguous_with_flattened: UnGraphMap<NodeId, ()>,
     ambiguous_with_all: HashSet<NodeId>,
+    deferred_systems: HashSet<NodeId>,
+    conflicting_systems: Vec<(NodeId, NodeId, Vec<String>)>,
     default_set: Option<BoxedSystemSet>,
+    error_policy: Option<SystemErrorPolicy>,
+    /// The error policies shared with the fallible systems of this schedule.
+    system_error_policies: Vec<ScheduleErrorPolicy>,
     changed: bool,
     settings: ScheduleBuildSettings,
 }
@@ -293,12 +507,23 @@ impl ScheduleGraph {
             ambiguous_with: UnGraphMap::new(),
             ambiguous_with_flattened: UnGraphMap::new(),
             ambiguous_with_all: HashSet::new(),
+            deferred_systems: HashSet::new(),
+            conflicting_systems: Vec::new(),
             default_set: None,
+            error_policy: None,
+            system_error_policies: Vec::new(),
             changed: false,
             settings: default(),
         }
     }
 
+    fn set_error_policy(&mut self, policy: SystemErrorPolicy) {
+        self.error_policy = Some(policy);
+        for system_error_policy in &self.system_error_policies {
+            system_error_policy.set(self.error_policy);
+        }
+    }
+
     fn set_default_set(&mut self, set: impl SystemSet) {
         assert!(
             !set.is_system_type(),
@@ -337,6 +562,8 @@ impl ScheduleGraph {
             system,
             mut graph_info,
             conditions,
+            defer_buffers,
+            error_policy,
         } = system.into_config();
 
         let id = NodeId::System(self.systems.len());
@@ -354,6 +581,13 @@ impl ScheduleGraph {
         self.uninit.push((id, 0));
         self.systems.push(Some(system));
         self.system_conditions.push(Some(conditions));
+        if defer_buffers {
+            self.deferred_systems.insert(id);
+        }
+        if let Some(error_policy) = error_policy {
+            error_policy.set(self.error_policy);
+            self.system_error_policies.push(error_policy);
+        }
 
         Ok(id)
     }
@@ -555,7 +789,10 @@ impl ScheduleGraph {
         }
     }
 
-    fn build_schedule(&mut self) -> Result<SystemSchedule, ScheduleBuildError> {
+    fn build_schedule(
+        &mut self,
+        components: &Components,
+    ) -> Result<SystemSchedule, ScheduleBuildError> {
         // check hierarchy for cycles
         let hier_scc = tarjan_scc(&self.hierarchy.graph);
         if self.contains_cycles(&hier_scc) {
@@ -689,9 +926,13 @@ impl ScheduleGraph {
             return Err(ScheduleBuildError::DependencyCycle);
         }
 
+        self.dependency_flattened.topsort = match self.settings.deterministic_seed {
+            Some(seed) => stable_topsort(&dependency_flattened, |id| {
+                (seeded_hash(seed, id.index() as u64), id)
+            }),
+            None => flat_scc.into_iter().flatten().rev().collect::<Vec<_>>(),
+        };
         self.dependency_flattened.graph = dependency_flattened;
-        self.dependency_flattened.topsort =
-            flat_scc.into_iter().flatten().rev().collect::<Vec<_>>();
 
         let flat_results = check_graph(
             &self.dependency_flattened.graph,
@@ -754,8 +995,21 @@ impl ScheduleGraph {
             }
         }
 
-        if self.contains_conflicts(&conflicting_systems) {
-            self.report_conflicts(&conflicting_systems);
+        self.conflicting_systems = conflicting_systems
+            .into_iter()
+            .map(|(a, b, conflicts)| {
+                let names = conflicts
+                    .iter()
+                    .map(|&id| component_name(components, id).to_string())
+                    .collect();
+                (a.min(b), a.max(b), names)
+            })
+            .collect();
+        // sort for a stable report
+        self.conflicting_systems.sort_by_key(|&(a, b, _)| (a, b));
+
+        if self.contains_conflicts(&self.conflicting_systems) {
+            self.report_conflicts(&self.conflicting_systems);
             if matches!(self.settings.ambiguity_detection, LogLevel::Error) {
                 return Err(ScheduleBuildError::Ambiguity);
             }
@@ -848,6 +1102,11 @@ impl ScheduleGraph {
             }
         }
 
+        let mut deferred_systems = FixedBitSet::with_capacity(sys_count);
+        for (i, sys_id) in dg_system_ids.iter().enumerate() {
+            deferred_systems.set(i, self.deferred_systems.contains(sys_id));
+        }
+
         Ok(SystemSchedule {
             systems: Vec::with_capacity(sys_count),
             system_conditions: Vec::with_capacity(sys_count),
@@ -858,10 +1117,16 @@ impl ScheduleGraph {
             system_dependents,
             sets_of_systems,
             systems_in_sets,
+            deferred_systems,
+            deterministic: self.settings.deterministic_seed.is_some(),
         })
     }
 
-    fn update_schedule(&mut self, schedule: &mut SystemSchedule) -> Result<(), ScheduleBuildError> {
+    fn update_schedule(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        components: &Components,
+    ) -> Result<(), ScheduleBuildError> {
         if !self.uninit.is_empty() {
             return Err(ScheduleBuildError::Uninitialized);
         }
@@ -885,7 +1150,7 @@ impl ScheduleGraph {
             self.system_set_conditions[id.index()] = Some(conditions);
         }
 
-        *schedule = self.build_schedule()?;
+        *schedule = self.build_schedule(components)?;
 
         // move systems into new schedule
         for &id in &schedule.system_ids {
@@ -906,6 +1171,10 @@ impl ScheduleGraph {
 
 // methods for reporting errors
 impl ScheduleGraph {
+    fn is_system_type_set(&self, id: NodeId) -> bool {
+        id.is_set() && self.system_sets[id.index()].is_system_type()
+    }
+
     fn get_node_name(&self, id: &NodeId) -> String {
         match id {
             NodeId::System(_) => self.systems[id.index()]
@@ -983,7 +1252,7 @@ impl ScheduleGraph {
         error!("{}", message);
     }
 
-    fn contains_conflicts(&self, conflicts: &[(NodeId, NodeId, Vec<ComponentId>)]) -> bool {
+    fn contains_conflicts(&self, conflicts: &[(NodeId, NodeId, Vec<String>)]) -> bool {
         if conflicts.is_empty() {
             return false;
         }
@@ -991,7 +1260,7 @@ impl ScheduleGraph {
         true
     }
 
-    fn report_conflicts(&self, ambiguities: &[(NodeId, NodeId, Vec<ComponentId>)]) {
+    fn report_conflicts(&self, ambiguities: &[(NodeId, NodeId, Vec<String>)]) {
         let mut string = String::from(
             "Some systems with conflicting access have indeterminate execution order. \
             Consider adding `before`, `after`, or `ambiguous_with` relationships between these:\n",
@@ -1017,6 +1286,81 @@ impl ScheduleGraph {
     }
 }
 
+fn component_name(components: &Components, id: ComponentId) -> &str {
+    components
+        .get_info(id)
+        .map_or("<unknown component>", |info| info.name())
+}
+
+/// A pair of systems with conflicting access and no order between them, see
+/// [`Schedule::ambiguities`].
+///
+/// Since either system may run first, the result of running the schedule can differ between
+/// runs. Adding `before`, `after` or `ambiguous_with` relationships between the systems
+/// resolves the ambiguity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemAmbiguity {
+    /// The name of the first system.
+    pub system_a: String,
+    /// The name of the second system.
+    pub system_b: String,
+    /// The names of the components and resources that one system writes and the other one
+    /// reads or writes.
+    ///
+    /// This is empty if either system is exclusive, because it conflicts with the whole [`World`].
+    pub conflicts: Vec<String>,
+}
+
+impl std::fmt::Display for SystemAmbiguity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} and {} conflict on: ", self.system_a, self.system_b)?;
+        if self.conflicts.is_empty() {
+            write!(f, "{}", std::any::type_name::<World>())
+        } else {
+            write!(f, "{}", self.conflicts.join(", "))
+        }
+    }
+}
+
+fn dot_id(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn escape_dot(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_json_array<T>(
+    json: &mut String,
+    items: impl Iterator<Item = T>,
+    mut write_item: impl FnMut(&mut String, T),
+) {
+    let mut empty = true;
+    for item in items {
+        json.push_str(if empty { "\n    " } else { ",\n    " });
+        write_item(json, item);
+        empty = false;
+    }
+    json.push_str(if empty { "]" } else { "\n  ]" });
+}
+
+fn write_json_string(json: &mut String, string: &str) {
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
 /// Category of errors encountered during schedule construction.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -1066,6 +1410,7 @@ pub enum LogLevel {
 pub struct ScheduleBuildSettings {
     ambiguity_detection: LogLevel,
     hierarchy_detection: LogLevel,
+    deterministic_seed: Option<u64>,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1079,6 +1424,7 @@ impl ScheduleBuildSettings {
         Self {
             ambiguity_detection: LogLevel::Warn,
             hierarchy_detection: LogLevel::Warn,
+            deterministic_seed: None,
         }
     }
 
@@ -1096,4 +1442,19 @@ impl ScheduleBuildSettings {
         self.hierarchy_detection = level;
         self
     }
+
+    /// Makes the execution of the schedule reproducible across runs.
+    ///
+    /// Systems are sorted into a stable topological order, where systems without an ordering
+    /// between them are arranged by `seed`. The [`MultiThreadedExecutor`] still runs
+    /// non-conflicting systems in parallel, but starts them in batches: it waits for a whole
+    /// batch to complete before choosing the next one in that order, so which systems run
+    /// together never depends on thread timing.
+    ///
+    /// Changing the seed shuffles the order of ambiguous systems, which can help reveal
+    /// accidental dependencies on it.
+    pub fn with_deterministic_execution(mut self, seed: u64) -> Self {
+        self.deterministic_seed = Some(seed);
+        self
+    }
 }
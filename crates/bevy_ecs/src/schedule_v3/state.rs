@@ -1,11 +1,17 @@
+use std::any::TypeId;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem;
 
+use bevy_utils::tracing::warn;
+
 use crate as bevy_ecs;
+use crate::component::Component;
+use crate::entity::Entity;
 use crate::schedule_v3::{ScheduleLabel, SystemSet, WorldExt};
 use crate::system::Resource;
-use crate::world::World;
+use crate::world::{FromWorld, World};
 
 /// Types that can define states in a finite-state machine.
 pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {
@@ -25,6 +31,16 @@ pub struct OnEnter<S: States>(pub S);
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct OnExit<S: States>(pub S);
 
+/// The label of a [`Schedule`](super::Schedule) that runs whenever this state is pushed down
+/// the [`StateStack<S>`] because another state was pushed on top of it.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`](super::Schedule) that runs whenever this state becomes the
+/// current state again because the state on top of it was popped off the [`StateStack<S>`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnResume<S: States>(pub S);
+
 /// A [`SystemSet`] that will run within \<insert-`bevy_core`-set-name\> when this state is active.
 ///
 /// This is provided for convenience. A more general [`state_equals`](super::state_equals)
@@ -47,18 +63,305 @@ pub struct State<S: States>(pub S);
 #[derive(Resource)]
 pub struct NextState<S: States>(pub Option<S>);
 
-/// If a new state is queued in [`NextState<S>`], this system:
+/// Marks an entity that only lives as long as [`State<S>`] stays in the contained state.
+///
+/// When the state is exited, the entity is despawned by [`clear_state_scoped_entities`], which
+/// `App::enable_state_scoped_entities` adds to the [`OnExit`] schedule of every variant. Pausing
+/// the state with a [`StateStack<S>`] does not count as exiting it.
+///
+/// Only the marked entity is despawned, so every entity of a hierarchy that should go away (e.g.
+/// the nodes of a menu) needs its own marker.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StateScoped<S: States>(pub S);
+
+/// States that only exist while their parent [`States`] is in certain variants,
+/// e.g. a `Paused`/`Running` state that only makes sense while the game is `InGame`.
+///
+/// While the parent state matches, [`State<Self>`] and [`NextState<Self>`] exist and the sub-state
+/// can be transitioned like any other state. When the parent state stops matching, the sub-state
+/// is exited and both resources are removed again. See [`register_sub_state`] and
+/// [`apply_sub_state_transition`].
+pub trait SubStates: States + Default {
+    /// The state this sub-state is nested in.
+    type Parent: States;
+
+    /// Returns `true` if this sub-state should exist while the parent is in `parent`.
+    fn exists_in(parent: &Self::Parent) -> bool;
+}
+
+/// A stack of previous [`States`] below the current [`State<S>`], for flows that need to
+/// return where they came from (e.g. opening a pause menu on top of any other screen).
+///
+/// Transitions are queued with [`push`](Self::push) and [`pop`](Self::pop) and applied by the next
+/// [`apply_state_transition::<S>`] system:
+/// - Pushing runs [`OnPause(paused_state)`] and then [`OnEnter(pushed_state)`].
+/// - Popping runs [`OnExit(popped_state)`] and then [`OnResume(resumed_state)`].
+///
+/// A transition queued in [`NextState<S>`] replaces the current state without touching the
+/// stack.
+#[derive(Resource)]
+pub struct StateStack<S: States> {
+    stack: Vec<S>,
+    queued: Option<StackTransition<S>>,
+}
+
+enum StackTransition<S: States> {
+    Push(S),
+    Pop,
+}
+
+// Derived Default impl would incorrectly require S: Default
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self {
+            stack: Vec::new(),
+            queued: None,
+        }
+    }
+}
+
+impl<S: States> StateStack<S> {
+    /// Queues a transition that pushes the current state onto the stack and enters `state`.
+    ///
+    /// This replaces any transition queued in this stack that hasn't been applied yet.
+    pub fn push(&mut self, state: S) {
+        self.queued = Some(StackTransition::Push(state));
+    }
+
+    /// Queues a transition that exits the current state and resumes the state on top of the
+    /// stack. Does nothing when applied if the stack is empty.
+    ///
+    /// This replaces any transition queued in this stack that hasn't been applied yet.
+    pub fn pop(&mut self) {
+        self.queued = Some(StackTransition::Pop);
+    }
+
+    /// Returns the number of states below the current one.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Returns `true` if there are no states below the current one.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Iterates over the states below the current one, from the bottom of the stack to the top.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &S> + ExactSizeIterator {
+        self.stack.iter()
+    }
+}
+
+/// The [`SubStates`] nested in `S`, see [`register_sub_state`].
+#[derive(Resource)]
+struct SubStateHooks<S: States> {
+    hooks: Vec<SubStateHook>,
+    marker: PhantomData<S>,
+}
+
+// Derived Default impl would incorrectly require S: Default
+impl<S: States> Default for SubStateHooks<S> {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SubStateHook {
+    sub_state: TypeId,
+    exit: fn(&mut World),
+    enter: fn(&mut World),
+}
+
+/// Registers the [`SubStates`] `S` with its parent state, so that the [`apply_state_transition`]
+/// system of the parent exits `S` before running the [`OnExit`] schedule of the parent and enters
+/// it after running the [`OnEnter`] schedule of the parent. Nested states are thus exited
+/// innermost first and entered outermost first.
+///
+/// Registering the same sub-state more than once has no effect.
+pub fn register_sub_state<S: SubStates>(world: &mut World) {
+    let mut sub_states = world.get_resource_or_insert_with(SubStateHooks::<S::Parent>::default);
+    if sub_states
+        .hooks
+        .iter()
+        .all(|hook| hook.sub_state != TypeId::of::<S>())
+    {
+        sub_states.hooks.push(SubStateHook {
+            sub_state: TypeId::of::<S>(),
+            exit: exit_sub_state_outside_parent::<S>,
+            enter: enter_sub_state_inside_parent::<S>,
+        });
+    }
+}
+
+/// Exits the registered sub-states of `S` that don't exist in the current [`State<S>`].
+fn exit_sub_states<S: States>(world: &mut World) {
+    if let Some(sub_states) = world.get_resource::<SubStateHooks<S>>() {
+        for hook in sub_states.hooks.clone() {
+            (hook.exit)(world);
+        }
+    }
+}
+
+/// Enters the registered sub-states of `S` that exist in the current [`State<S>`].
+fn enter_sub_states<S: States>(world: &mut World) {
+    if let Some(sub_states) = world.get_resource::<SubStateHooks<S>>() {
+        for hook in sub_states.hooks.clone() {
+            (hook.enter)(world);
+        }
+    }
+}
+
+fn sub_state_should_exist<S: SubStates>(world: &World) -> bool {
+    match world.get_resource::<State<S::Parent>>() {
+        Some(parent) => S::exists_in(&parent.0),
+        None => false,
+    }
+}
+
+fn exit_sub_state_outside_parent<S: SubStates>(world: &mut World) {
+    if !sub_state_should_exist::<S>(world) && world.contains_resource::<State<S>>() {
+        exit_sub_state::<S>(world);
+    }
+}
+
+fn enter_sub_state_inside_parent<S: SubStates>(world: &mut World) {
+    if sub_state_should_exist::<S>(world) && !world.contains_resource::<State<S>>() {
+        enter_sub_state::<S>(world);
+    }
+}
+
+fn enter_sub_state<S: SubStates>(world: &mut World) {
+    let entered_state = S::default();
+    world.insert_resource(State(entered_state.clone()));
+    world.insert_resource(NextState::<S>(None));
+    world.run_schedule(OnEnter(entered_state));
+    enter_sub_states::<S>(world);
+}
+
+fn exit_sub_state<S: SubStates>(world: &mut World) {
+    let exited_state = world.remove_resource::<State<S>>().unwrap().0;
+    world.remove_resource::<NextState<S>>();
+    // The state is removed first, so its own sub-states see that they don't exist anymore
+    exit_sub_states::<S>(world);
+    world.run_schedule(OnExit(exited_state));
+}
+
+/// If a transition is queued in [`StateStack<S>`] (if it exists), it is applied first.
+/// Then, if a new state is queued in [`NextState<S>`], this system:
 /// - Takes the new state value from [`NextState<S>`] and updates [`State<S>`].
 /// - Runs the [`OnExit(exited_state)`] schedule.
 /// - Runs the [`OnEnter(entered_state)`] schedule.
+///
+/// Sub-states registered with [`register_sub_state`] are exited before the exited or paused state
+/// and entered after the entered or resumed state.
 pub fn apply_state_transition<S: States>(world: &mut World) {
+    let stack_transition = world
+        .get_resource_mut::<StateStack<S>>()
+        .and_then(|mut stack| stack.queued.take());
+    match stack_transition {
+        Some(StackTransition::Push(entered_state)) => {
+            let paused_state = mem::replace(
+                &mut world.resource_mut::<State<S>>().0,
+                entered_state.clone(),
+            );
+            world
+                .resource_mut::<StateStack<S>>()
+                .stack
+                .push(paused_state.clone());
+            exit_sub_states::<S>(world);
+            world.run_schedule(OnPause(paused_state));
+            world.run_schedule(OnEnter(entered_state));
+            enter_sub_states::<S>(world);
+        }
+        Some(StackTransition::Pop) => {
+            if let Some(resumed_state) = world.resource_mut::<StateStack<S>>().stack.pop() {
+                let exited_state = mem::replace(
+                    &mut world.resource_mut::<State<S>>().0,
+                    resumed_state.clone(),
+                );
+                exit_sub_states::<S>(world);
+                world.run_schedule(OnExit(exited_state));
+                world.run_schedule(OnResume(resumed_state));
+                enter_sub_states::<S>(world);
+            } else {
+                warn!(
+                    "Tried to pop the state stack of {} but it was empty",
+                    std::any::type_name::<S>()
+                );
+            }
+        }
+        None => {}
+    }
+
     if world.resource::<NextState<S>>().0.is_some() {
         let entered_state = world.resource_mut::<NextState<S>>().0.take().unwrap();
         let exited_state = mem::replace(
             &mut world.resource_mut::<State<S>>().0,
             entered_state.clone(),
         );
+        exit_sub_states::<S>(world);
         world.run_schedule(OnExit(exited_state));
         world.run_schedule(OnEnter(entered_state));
+        enter_sub_states::<S>(world);
+    }
+}
+
+/// Keeps the [`SubStates`] `S` in sync with its parent state and applies its transitions:
+/// - If the parent state now matches and `S` doesn't exist, inserts [`State<S>`] with the
+///   default value and [`NextState<S>`], then runs [`OnEnter(default_state)`].
+/// - If the parent state no longer matches and `S` exists, removes both resources and runs
+///   [`OnExit(current_state)`].
+/// - Otherwise, applies a transition queued in [`NextState<S>`] like [`apply_state_transition`].
+///
+/// This should run after the [`apply_state_transition`] system of the parent state. If `S` is
+/// registered with [`register_sub_state`], the transitions of the parent state already enter and
+/// exit `S` in the right order, and this system only needs to apply the transitions of `S` itself
+/// (or enter `S` if the parent state was inserted in a matching state).
+pub fn apply_sub_state_transition<S: SubStates>(world: &mut World) {
+    let should_exist = sub_state_should_exist::<S>(world);
+    let exists = world.contains_resource::<State<S>>();
+
+    match (should_exist, exists) {
+        (true, false) => enter_sub_state::<S>(world),
+        (false, true) => exit_sub_state::<S>(world),
+        (true, true) => apply_state_transition::<S>(world),
+        (false, false) => {}
+    }
+}
+
+/// Returns an exclusive system that despawns every entity marked with [`StateScoped(state)`](StateScoped).
+///
+/// This is meant to run in the [`OnExit(state)`](OnExit) schedule, see
+/// `App::enable_state_scoped_entities`.
+pub fn clear_state_scoped_entities<S: States>(
+    state: S,
+) -> impl FnMut(&mut World) + Send + Sync + 'static {
+    move |world: &mut World| {
+        let entities = world
+            .query::<(Entity, &StateScoped<S>)>()
+            .iter(world)
+            .filter(|(_, scoped)| scoped.0 == state)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in entities {
+            world.despawn(entity);
+        }
     }
 }
+
+/// Initializes the resource `R` if it doesn't exist yet.
+///
+/// Together with [`remove_state_scoped_resource::<R>`], this backs
+/// `App::init_resource_on_enter`.
+pub fn init_state_scoped_resource<R: Resource + FromWorld>(world: &mut World) {
+    world.init_resource::<R>();
+}
+
+/// Removes the resource `R` if it exists.
+pub fn remove_state_scoped_resource<R: Resource>(world: &mut World) {
+    world.remove_resource::<R>();
+}
@@ -86,7 +86,7 @@ impl<const SEND: bool> ResourceData<SEND> {
     /// Gets a read-only reference to the change ticks of the underlying resource, if available.
     #[inline]
     pub fn get_ticks(&self) -> Option<ComponentTicks> {
-        self.column.get_ticks(Self::ROW)
+        self.column.get_ticks(Self::ROW).flatten()
     }
 
     /// # Panics
@@ -94,9 +94,10 @@ impl<const SEND: bool> ResourceData<SEND> {
     /// original thread it was inserted in.
     #[inline]
     pub(crate) fn get_with_ticks(&self) -> Option<(Ptr<'_>, TickCells<'_>)> {
-        self.column.get(Self::ROW).map(|res| {
+        self.column.get(Self::ROW).map(|(ptr, ticks)| {
             self.validate_access();
-            res
+            // Resources always have change detection
+            (ptr, ticks.unwrap())
         })
     }
 
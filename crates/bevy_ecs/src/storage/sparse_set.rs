@@ -189,8 +189,10 @@ impl ComponentSparseSet {
         })
     }
 
+    /// Returns the component of `entity` and its ticks, which are `None` if the component has
+    /// no change detection.
     #[inline]
-    pub fn get_with_ticks(&self, entity: Entity) -> Option<(Ptr<'_>, TickCells<'_>)> {
+    pub fn get_with_ticks(&self, entity: Entity) -> Option<(Ptr<'_>, Option<TickCells<'_>>)> {
         let dense_index = TableRow::new(*self.sparse.get(entity.index())? as usize);
         #[cfg(debug_assertions)]
         assert_eq!(entity, self.entities[dense_index.index()]);
@@ -198,49 +200,39 @@ impl ComponentSparseSet {
         unsafe {
             Some((
                 self.dense.get_data_unchecked(dense_index),
-                TickCells {
-                    added: self.dense.get_added_ticks_unchecked(dense_index),
-                    changed: self.dense.get_changed_ticks_unchecked(dense_index),
-                },
+                self.dense.get_tick_cells_unchecked(dense_index),
             ))
         }
     }
 
+    /// Returns `None` if `entity` has no component in this set or the component has no change
+    /// detection.
     #[inline]
     pub fn get_added_ticks(&self, entity: Entity) -> Option<&UnsafeCell<Tick>> {
         let dense_index = *self.sparse.get(entity.index())? as usize;
         #[cfg(debug_assertions)]
         assert_eq!(entity, self.entities[dense_index]);
-        // SAFETY: if the sparse index points to something in the dense vec, it exists
-        unsafe {
-            Some(
-                self.dense
-                    .get_added_ticks_unchecked(TableRow::new(dense_index)),
-            )
-        }
+        self.dense.get_added_ticks(TableRow::new(dense_index))
     }
 
+    /// Returns `None` if `entity` has no component in this set or the component has no change
+    /// detection.
     #[inline]
     pub fn get_changed_ticks(&self, entity: Entity) -> Option<&UnsafeCell<Tick>> {
         let dense_index = *self.sparse.get(entity.index())? as usize;
         #[cfg(debug_assertions)]
         assert_eq!(entity, self.entities[dense_index]);
-        // SAFETY: if the sparse index points to something in the dense vec, it exists
-        unsafe {
-            Some(
-                self.dense
-                    .get_changed_ticks_unchecked(TableRow::new(dense_index)),
-            )
-        }
+        self.dense.get_changed_ticks(TableRow::new(dense_index))
     }
 
+    /// Returns `None` if `entity` has no component in this set or the component has no change
+    /// detection.
     #[inline]
     pub fn get_ticks(&self, entity: Entity) -> Option<ComponentTicks> {
         let dense_index = *self.sparse.get(entity.index())? as usize;
         #[cfg(debug_assertions)]
         assert_eq!(entity, self.entities[dense_index]);
-        // SAFETY: if the sparse index points to something in the dense vec, it exists
-        unsafe { Some(self.dense.get_ticks_unchecked(TableRow::new(dense_index))) }
+        self.dense.get_ticks(TableRow::new(dense_index)).flatten()
     }
 
     /// Removes the `entity` from this sparse set and returns a pointer to the associated value (if
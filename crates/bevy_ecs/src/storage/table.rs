@@ -89,21 +89,32 @@ impl TableRow {
 #[derive(Debug)]
 pub struct Column {
     data: BlobVec,
+    // NOTE: these are always empty if the component opted out of change detection
     added_ticks: Vec<UnsafeCell<Tick>>,
     changed_ticks: Vec<UnsafeCell<Tick>>,
+    change_detection: bool,
 }
 
 impl Column {
     #[inline]
     pub(crate) fn with_capacity(component_info: &ComponentInfo, capacity: usize) -> Self {
+        let change_detection = component_info.has_change_detection();
+        let ticks_capacity = if change_detection { capacity } else { 0 };
         Column {
             // SAFETY: component_info.drop() is valid for the types that will be inserted.
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
-            added_ticks: Vec::with_capacity(capacity),
-            changed_ticks: Vec::with_capacity(capacity),
+            added_ticks: Vec::with_capacity(ticks_capacity),
+            changed_ticks: Vec::with_capacity(ticks_capacity),
+            change_detection,
         }
     }
 
+    /// Returns `true` if change ticks are stored for the components in this column.
+    #[inline]
+    pub fn has_change_detection(&self) -> bool {
+        self.change_detection
+    }
+
     #[inline]
     pub fn item_layout(&self) -> Layout {
         self.data.layout()
@@ -119,8 +130,10 @@ impl Column {
     pub(crate) unsafe fn initialize(&mut self, row: TableRow, data: OwningPtr<'_>, tick: Tick) {
         debug_assert!(row.index() < self.len());
         self.data.initialize_unchecked(row.index(), data);
-        *self.added_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
-        *self.changed_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
+        if self.change_detection {
+            *self.added_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
+            *self.changed_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
+        }
     }
 
     /// Writes component data to the column at given row.
@@ -132,10 +145,12 @@ impl Column {
     pub(crate) unsafe fn replace(&mut self, row: TableRow, data: OwningPtr<'_>, change_tick: u32) {
         debug_assert!(row.index() < self.len());
         self.data.replace_unchecked(row.index(), data);
-        self.changed_ticks
-            .get_unchecked_mut(row.index())
-            .get_mut()
-            .set_changed(change_tick);
+        if self.change_detection {
+            self.changed_ticks
+                .get_unchecked_mut(row.index())
+                .get_mut()
+                .set_changed(change_tick);
+        }
     }
 
     /// Writes component data to the column at given row.
@@ -165,8 +180,19 @@ impl Column {
     #[inline]
     pub(crate) unsafe fn swap_remove_unchecked(&mut self, row: TableRow) {
         self.data.swap_remove_and_drop_unchecked(row.index());
-        self.added_ticks.swap_remove(row.index());
-        self.changed_ticks.swap_remove(row.index());
+        self.swap_remove_ticks(row);
+    }
+
+    /// Removes the ticks at `row`, returning zeroed ticks if this column has no change detection.
+    #[inline]
+    fn swap_remove_ticks(&mut self, row: TableRow) -> ComponentTicks {
+        if self.change_detection {
+            let added = self.added_ticks.swap_remove(row.index()).into_inner();
+            let changed = self.changed_ticks.swap_remove(row.index()).into_inner();
+            ComponentTicks { added, changed }
+        } else {
+            ComponentTicks::new(0)
+        }
     }
 
     #[inline]
@@ -176,11 +202,10 @@ impl Column {
         row: TableRow,
     ) -> Option<(OwningPtr<'_>, ComponentTicks)> {
         (row.index() < self.data.len()).then(|| {
+            let ticks = self.swap_remove_ticks(row);
             // SAFETY: The row was length checked before this.
             let data = unsafe { self.data.swap_remove_and_forget_unchecked(row.index()) };
-            let added = self.added_ticks.swap_remove(row.index()).into_inner();
-            let changed = self.changed_ticks.swap_remove(row.index()).into_inner();
-            (data, ComponentTicks { added, changed })
+            (data, ticks)
         })
     }
 
@@ -192,10 +217,9 @@ impl Column {
         &mut self,
         row: TableRow,
     ) -> (OwningPtr<'_>, ComponentTicks) {
+        let ticks = self.swap_remove_ticks(row);
         let data = self.data.swap_remove_and_forget_unchecked(row.index());
-        let added = self.added_ticks.swap_remove(row.index()).into_inner();
-        let changed = self.changed_ticks.swap_remove(row.index()).into_inner();
-        (data, ComponentTicks { added, changed })
+        (data, ticks)
     }
 
     /// Removes the element from `other` at `src_row` and inserts it
@@ -219,25 +243,31 @@ impl Column {
         debug_assert!(self.data.layout() == other.data.layout());
         let ptr = self.data.get_unchecked_mut(dst_row.index());
         other.data.swap_remove_unchecked(src_row.index(), ptr);
-        *self.added_ticks.get_unchecked_mut(dst_row.index()) =
-            other.added_ticks.swap_remove(src_row.index());
-        *self.changed_ticks.get_unchecked_mut(dst_row.index()) =
-            other.changed_ticks.swap_remove(src_row.index());
+        if self.change_detection {
+            *self.added_ticks.get_unchecked_mut(dst_row.index()) =
+                other.added_ticks.swap_remove(src_row.index());
+            *self.changed_ticks.get_unchecked_mut(dst_row.index()) =
+                other.changed_ticks.swap_remove(src_row.index());
+        }
     }
 
     // # Safety
     // - ptr must point to valid data of this column's component type
     pub(crate) unsafe fn push(&mut self, ptr: OwningPtr<'_>, ticks: ComponentTicks) {
         self.data.push(ptr);
-        self.added_ticks.push(UnsafeCell::new(ticks.added));
-        self.changed_ticks.push(UnsafeCell::new(ticks.changed));
+        if self.change_detection {
+            self.added_ticks.push(UnsafeCell::new(ticks.added));
+            self.changed_ticks.push(UnsafeCell::new(ticks.changed));
+        }
     }
 
     #[inline]
     pub(crate) fn reserve_exact(&mut self, additional: usize) {
         self.data.reserve_exact(additional);
-        self.added_ticks.reserve_exact(additional);
-        self.changed_ticks.reserve_exact(additional);
+        if self.change_detection {
+            self.added_ticks.reserve_exact(additional);
+            self.changed_ticks.reserve_exact(additional);
+        }
     }
 
     #[inline]
@@ -251,28 +281,30 @@ impl Column {
         self.data.get_slice()
     }
 
+    /// Returns the added ticks of all rows, which is empty if this column has no change detection.
     #[inline]
     pub fn get_added_ticks_slice(&self) -> &[UnsafeCell<Tick>] {
         &self.added_ticks
     }
 
+    /// Returns the changed ticks of all rows, which is empty if this column has no change detection.
     #[inline]
     pub fn get_changed_ticks_slice(&self) -> &[UnsafeCell<Tick>] {
         &self.changed_ticks
     }
 
+    /// Returns the data and ticks at `row`, or `None` if `row` is out of bounds.
+    ///
+    /// The ticks are `None` if this column has no change detection.
     #[inline]
-    pub fn get(&self, row: TableRow) -> Option<(Ptr<'_>, TickCells<'_>)> {
+    pub fn get(&self, row: TableRow) -> Option<(Ptr<'_>, Option<TickCells<'_>>)> {
         (row.index() < self.data.len())
             // SAFETY: The row is length checked before fetching the pointer. This is being
             // accessed through a read-only reference to the column.
             .then(|| unsafe {
                 (
                     self.data.get_unchecked(row.index()),
-                    TickCells {
-                        added: self.added_ticks.get_unchecked(row.index()),
-                        changed: self.changed_ticks.get_unchecked(row.index()),
-                    },
+                    self.get_tick_cells_unchecked(row),
                 )
             })
     }
@@ -319,26 +351,42 @@ impl Column {
         self.changed_ticks.get(row.index())
     }
 
+    /// Returns the ticks at `row`, or `None` if `row` is out of bounds.
+    ///
+    /// The ticks are `None` if this column has no change detection.
     #[inline]
-    pub fn get_ticks(&self, row: TableRow) -> Option<ComponentTicks> {
-        if row.index() < self.data.len() {
-            // SAFETY: The size of the column has already been checked.
-            Some(unsafe { self.get_ticks_unchecked(row) })
-        } else {
-            None
-        }
+    pub fn get_ticks(&self, row: TableRow) -> Option<Option<ComponentTicks>> {
+        (row.index() < self.data.len()).then(|| {
+            self.change_detection
+                // SAFETY: The size of the column has already been checked.
+                .then(|| unsafe { self.get_ticks_unchecked(row) })
+        })
     }
 
+    /// Returns the ticks at `row`, or `None` if this column has no change detection.
+    ///
     /// # Safety
     /// index must be in-bounds
     #[inline]
+    pub unsafe fn get_tick_cells_unchecked(&self, row: TableRow) -> Option<TickCells<'_>> {
+        self.change_detection.then(|| TickCells {
+            added: self.added_ticks.get_unchecked(row.index()),
+            changed: self.changed_ticks.get_unchecked(row.index()),
+        })
+    }
+
+    /// # Safety
+    /// - index must be in-bounds
+    /// - this column must have change detection
+    #[inline]
     pub unsafe fn get_added_ticks_unchecked(&self, row: TableRow) -> &UnsafeCell<Tick> {
         debug_assert!(row.index() < self.added_ticks.len());
         self.added_ticks.get_unchecked(row.index())
     }
 
     /// # Safety
-    /// index must be in-bounds
+    /// - index must be in-bounds
+    /// - this column must have change detection
     #[inline]
     pub unsafe fn get_changed_ticks_unchecked(&self, row: TableRow) -> &UnsafeCell<Tick> {
         debug_assert!(row.index() < self.changed_ticks.len());
@@ -346,7 +394,8 @@ impl Column {
     }
 
     /// # Safety
-    /// index must be in-bounds
+    /// - index must be in-bounds
+    /// - this column must have change detection
     #[inline]
     pub unsafe fn get_ticks_unchecked(&self, row: TableRow) -> ComponentTicks {
         debug_assert!(row.index() < self.added_ticks.len());
@@ -586,8 +635,10 @@ impl Table {
         self.entities.push(entity);
         for column in self.columns.values_mut() {
             column.data.set_len(self.entities.len());
-            column.added_ticks.push(UnsafeCell::new(Tick::new(0)));
-            column.changed_ticks.push(UnsafeCell::new(Tick::new(0)));
+            if column.change_detection {
+                column.added_ticks.push(UnsafeCell::new(Tick::new(0)));
+                column.changed_ticks.push(UnsafeCell::new(Tick::new(0)));
+            }
         }
         TableRow::new(index)
     }
@@ -751,6 +802,10 @@ mod tests {
     #[derive(Component)]
     struct W<T>(T);
 
+    #[derive(Component)]
+    #[component(change_detection = false)]
+    struct NoTicks(u32);
+
     #[test]
     fn table() {
         let mut components = Components::default();
@@ -779,4 +834,35 @@ mod tests {
         assert_eq!(table.entity_capacity(), 256);
         assert_eq!(table.entity_count(), 200);
     }
+
+    #[test]
+    fn column_without_change_detection() {
+        let mut components = Components::default();
+        let mut storages = Storages::default();
+        let component_id = components.init_component::<NoTicks>(&mut storages);
+        let mut builder = TableBuilder::with_capacity(0, 1);
+        builder.add_column(components.get_info(component_id).unwrap());
+        let mut table = builder.build();
+        // SAFETY: we allocate and immediately set data afterwards
+        let row = unsafe {
+            let row = table.allocate(Entity::from_raw(0));
+            OwningPtr::make(NoTicks(7), |value_ptr| {
+                table.get_column_mut(component_id).unwrap().initialize(
+                    row,
+                    value_ptr,
+                    Tick::new(0),
+                );
+            });
+            row
+        };
+
+        let column = table.get_column(component_id).unwrap();
+        let (value, ticks) = column.get(row).unwrap();
+        // SAFETY: the column stores `NoTicks`
+        assert_eq!(unsafe { value.deref::<NoTicks>() }.0, 7);
+        assert!(ticks.is_none());
+        assert!(matches!(column.get_ticks(row), Some(None)));
+        assert!(column.get(TableRow::new(1)).is_none());
+        assert!(column.get_ticks(TableRow::new(1)).is_none());
+    }
 }
@@ -0,0 +1,223 @@
+use std::{any::TypeId, borrow::Cow, marker::PhantomData};
+
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::ComponentId,
+    query::Access,
+    system::{ReadOnlySystem, System},
+    world::World,
+};
+
+/// Customizes the behavior of a [`CombinatorSystem`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{CombinatorSystem, Combinator};
+///
+/// // A system combinator that performs an exclusive-or (XOR)
+/// // operation on the output of two systems.
+/// pub type Xor<A, B> = CombinatorSystem<XorMarker, A, B>;
+///
+/// // This struct is used to customize the behavior of our combinator.
+/// pub struct XorMarker;
+///
+/// impl<A, B> Combinator<A, B> for XorMarker
+/// where
+///     A: System<In = (), Out = bool>,
+///     B: System<In = (), Out = bool>,
+/// {
+///     type In = ();
+///     type Out = bool;
+///
+///     fn combine(
+///         _input: Self::In,
+///         a: impl FnOnce(A::In) -> A::Out,
+///         b: impl FnOnce(B::In) -> B::Out,
+///     ) -> Self::Out {
+///         a(()) ^ b(())
+///     }
+/// }
+///
+/// # #[derive(Resource, PartialEq, Eq)] struct A(u32);
+/// # #[derive(Resource, PartialEq, Eq)] struct B(u32);
+/// # let mut world = World::new();
+/// # world.insert_resource(A(0));
+/// # world.insert_resource(B(0));
+/// let mut system = Xor::new(
+///     IntoSystem::into_system(|a: Res<A>| a.0 == 1),
+///     IntoSystem::into_system(|b: Res<B>| b.0 == 1),
+///     "a ^ b".into(),
+/// );
+/// system.initialize(&mut world);
+/// assert!(!system.run((), &mut world));
+///
+/// world.resource_mut::<A>().0 = 1;
+/// assert!(system.run((), &mut world));
+/// ```
+pub trait Combinator<A: System, B: System>: 'static {
+    /// The [input type](System::In) for a [`CombinatorSystem`].
+    type In;
+
+    /// The [output type](System::Out) for a [`CombinatorSystem`].
+    type Out;
+
+    /// When used in a [`CombinatorSystem`], this function customizes how
+    /// the two composite systems are invoked and their outputs are combined.
+    ///
+    /// See the trait-level docs for [`Combinator`] for an example implementation.
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(A::In) -> A::Out,
+        b: impl FnOnce(B::In) -> B::Out,
+    ) -> Self::Out;
+}
+
+/// A [`System`] defined by combining two other systems.
+/// The behavior of this combinator is specified by implementing the [`Combinator`] trait.
+/// For a full usage example, see the docs for [`Combinator`].
+pub struct CombinatorSystem<Func, A, B> {
+    _marker: PhantomData<fn() -> Func>,
+    a: A,
+    b: B,
+    name: Cow<'static, str>,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<Func, A, B> CombinatorSystem<Func, A, B> {
+    /// Manual constructor for creating a [`CombinatorSystem`].
+    /// This should only be used when the combinator's convenience methods cannot be used,
+    /// such as in `const` contexts.
+    pub const fn new(a: A, b: B, name: Cow<'static, str>) -> Self {
+        Self {
+            _marker: PhantomData,
+            a,
+            b,
+            name,
+            component_access: Access::new(),
+            archetype_component_access: Access::new(),
+        }
+    }
+}
+
+impl<A, B, Func> System for CombinatorSystem<Func, A, B>
+where
+    Func: Combinator<A, B> + 'static,
+    A: System,
+    B: System,
+{
+    type In = Func::In;
+    type Out = Func::Out;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.a.is_send() && self.b.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.a.is_exclusive() || self.b.is_exclusive()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: &World) -> Self::Out {
+        Func::combine(
+            input,
+            // SAFETY: The world accesses for both underlying systems have been registered,
+            // so the caller will guarantee that no other systems will conflict with `a` or `b`.
+            // Since these closures are `!Send + !Sync + !'static`, they can never be called
+            // in parallel, so their world accesses will not conflict with each other.
+            |input| self.a.run_unsafe(input, world),
+            |input| self.b.run_unsafe(input, world),
+        )
+    }
+
+    // needed to make exclusive systems work
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        // SAFETY: Converting `&mut T` -> `&UnsafeCell<T>`
+        // is explicitly allowed in the docs for `UnsafeCell`.
+        let world: &std::cell::UnsafeCell<World> = unsafe { std::mem::transmute(world) };
+        // SAFETY: Since these closures are `!Send + !Sync + !'static`, they can never
+        // be called in parallel. Since mutable access to `world` only exists within
+        // the scope of either closure, we can be sure they will never alias one another.
+        Func::combine(
+            input,
+            // SAFETY: See above.
+            |input| self.a.run(input, unsafe { &mut *world.get() }),
+            // SAFETY: See above.
+            |input| self.b.run(input, unsafe { &mut *world.get() }),
+        )
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.a.apply_buffers(world);
+        self.b.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.a.initialize(world);
+        self.b.initialize(world);
+        self.component_access.extend(self.a.component_access());
+        self.component_access.extend(self.b.component_access());
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        self.a.update_archetype_component_access(world);
+        self.b.update_archetype_component_access(world);
+
+        self.archetype_component_access
+            .extend(self.a.archetype_component_access());
+        self.archetype_component_access
+            .extend(self.b.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.a.check_change_tick(change_tick);
+        self.b.check_change_tick(change_tick);
+    }
+
+    fn get_last_change_tick(&self) -> u32 {
+        self.a.get_last_change_tick()
+    }
+
+    fn set_last_change_tick(&mut self, last_change_tick: u32) {
+        self.a.set_last_change_tick(last_change_tick);
+        self.b.set_last_change_tick(last_change_tick);
+    }
+
+    fn default_labels(&self) -> Vec<crate::schedule::SystemLabelId> {
+        let mut labels = self.a.default_labels();
+        labels.extend(&self.b.default_labels());
+        labels
+    }
+
+    fn default_system_sets(&self) -> Vec<Box<dyn crate::schedule_v3::SystemSet>> {
+        let mut system_sets = self.a.default_system_sets();
+        system_sets.extend_from_slice(&self.b.default_system_sets());
+        system_sets
+    }
+}
+
+// SAFETY: Both systems are read-only, so any system created by combining them will only read from the world.
+unsafe impl<A, B, Func> ReadOnlySystem for CombinatorSystem<Func, A, B>
+where
+    Func: Combinator<A, B> + 'static,
+    A: ReadOnlySystem,
+    B: ReadOnlySystem,
+{
+}
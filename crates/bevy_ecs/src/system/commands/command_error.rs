@@ -0,0 +1,81 @@
+use crate::{self as bevy_ecs, entity::Entity, event::Events, system::Resource, world::World};
+use bevy_utils::tracing::{debug, warn};
+use std::fmt;
+
+/// An error encountered while applying a fallible command, such as
+/// [`EntityCommands::try_insert`](super::EntityCommands::try_insert).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandError {
+    /// The entity targeted by the command does not exist (anymore).
+    NoSuchEntity {
+        /// The entity the command targeted.
+        entity: Entity,
+        /// A short description of what the command tried to do.
+        command: &'static str,
+    },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::NoSuchEntity { entity, command } => write!(
+                f,
+                "error[B0003]: Could not {command} entity {entity:?} because it doesn't exist in this World."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Specifies how failures of fallible commands are handled.
+///
+/// Insert this as a resource to change the behavior for the whole [`World`]. If the resource is
+/// missing, [`CommandErrorPolicy::Warn`] is used.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::CommandErrorPolicy;
+/// let mut world = World::new();
+/// // entities despawned by other systems are expected, don't make noise about them
+/// world.insert_resource(CommandErrorPolicy::Ignore);
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CommandErrorPolicy {
+    /// Panic with the error message.
+    Panic,
+    /// Log the error at the warn level.
+    #[default]
+    Warn,
+    /// Silently discard the error. It is still logged at the debug level.
+    Ignore,
+    /// Send the error as an event, so it can be read with an
+    /// [`EventReader<CommandError>`](crate::event::EventReader).
+    ///
+    /// The `Events<CommandError>` resource must be registered for this to work, otherwise the
+    /// error is logged at the warn level instead.
+    SendEvent,
+}
+
+impl CommandErrorPolicy {
+    /// Handles `error` according to the [`CommandErrorPolicy`] resource of the `world`.
+    pub fn handle(world: &mut World, error: CommandError) {
+        let policy = world
+            .get_resource::<CommandErrorPolicy>()
+            .copied()
+            .unwrap_or_default();
+        match policy {
+            CommandErrorPolicy::Panic => panic!("{}", error),
+            CommandErrorPolicy::Warn => warn!("{}", error),
+            CommandErrorPolicy::Ignore => debug!("{}", error),
+            CommandErrorPolicy::SendEvent => {
+                if let Some(mut events) = world.get_resource_mut::<Events<CommandError>>() {
+                    events.send(error);
+                } else {
+                    warn!("{}", error);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod command_error;
 mod command_queue;
 mod parallel_scope;
 
@@ -7,6 +8,7 @@ use crate::{
     world::{FromWorld, World},
 };
 use bevy_utils::tracing::{error, info};
+pub use command_error::*;
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
@@ -688,6 +690,37 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Tries to add a [`Bundle`] of components to the entity.
+    ///
+    /// This will overwrite any previous value(s) of the same component type.
+    ///
+    /// Unlike [`insert`](Self::insert), this doesn't panic if the entity doesn't exist when the
+    /// command is applied, for example because another system despawned it in the meantime.
+    /// The failure is instead handled according to the world's [`CommandErrorPolicy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct TargetEntity { entity: Entity }
+    /// #[derive(Component)]
+    /// struct Burning;
+    ///
+    /// fn ignite_system(mut commands: Commands, target: Res<TargetEntity>) {
+    ///     // the target might have been despawned by the time this is applied
+    ///     commands.entity(target.entity).try_insert(Burning);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(ignite_system);
+    /// ```
+    pub fn try_insert(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.commands.add(TryInsert {
+            entity: self.entity,
+            bundle,
+        });
+        self
+    }
+
     /// Removes a [`Bundle`] of components from the entity.
     ///
     /// See [`EntityMut::remove`](crate::world::EntityMut::remove) for more
@@ -768,6 +801,16 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Tries to despawn the entity.
+    ///
+    /// Unlike [`despawn`](Self::despawn), a missing entity is handled according to the world's
+    /// [`CommandErrorPolicy`] instead of always being logged as a warning.
+    pub fn try_despawn(&mut self) {
+        self.commands.add(TryDespawn {
+            entity: self.entity,
+        });
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
@@ -903,6 +946,27 @@ impl Command for Despawn {
     }
 }
 
+#[derive(Debug)]
+pub struct TryDespawn {
+    pub entity: Entity,
+}
+
+impl Command for TryDespawn {
+    fn write(self, world: &mut World) {
+        if let Some(entity) = world.get_entity_mut(self.entity) {
+            entity.despawn();
+        } else {
+            CommandErrorPolicy::handle(
+                world,
+                CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    command: "despawn",
+                },
+            );
+        }
+    }
+}
+
 pub struct Insert<T> {
     pub entity: Entity,
     pub bundle: T,
@@ -921,6 +985,30 @@ where
     }
 }
 
+pub struct TryInsert<T> {
+    pub entity: Entity,
+    pub bundle: T,
+}
+
+impl<T> Command for TryInsert<T>
+where
+    T: Bundle + 'static,
+{
+    fn write(self, world: &mut World) {
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            entity.insert(self.bundle);
+        } else {
+            CommandErrorPolicy::handle(
+                world,
+                CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    command: "insert a bundle for",
+                },
+            );
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Remove<T> {
     pub entity: Entity,
@@ -992,7 +1080,8 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands, Resource},
+        event::Events,
+        system::{CommandError, CommandErrorPolicy, CommandQueue, Commands, Resource},
         world::World,
     };
     use std::sync::{
@@ -1077,6 +1166,59 @@ mod tests {
         assert_eq!(results3, vec![(42u32, 0u64), (0u32, 42u64)]);
     }
 
+    #[test]
+    fn fallible_commands() {
+        let mut world = World::default();
+        world.insert_resource(CommandErrorPolicy::SendEvent);
+        world.init_resource::<Events<CommandError>>();
+        let mut command_queue = CommandQueue::default();
+
+        let entity = world.spawn_empty().id();
+        {
+            let mut commands = Commands::new(&mut command_queue, &world);
+            commands.entity(entity).try_insert(W(1u32));
+            commands.entity(entity).try_despawn();
+            // the entity no longer exists when these are applied
+            commands.entity(entity).try_insert(W(2u32));
+            commands.entity(entity).try_despawn();
+        }
+        command_queue.apply(&mut world);
+
+        assert!(world.get_entity(entity).is_none());
+        let errors = world
+            .resource_mut::<Events<CommandError>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                CommandError::NoSuchEntity {
+                    entity,
+                    command: "insert a bundle for"
+                },
+                CommandError::NoSuchEntity {
+                    entity,
+                    command: "despawn"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fallible_commands_panic_policy() {
+        let mut world = World::default();
+        world.insert_resource(CommandErrorPolicy::Panic);
+        let mut command_queue = CommandQueue::default();
+
+        let entity = world.spawn_empty().id();
+        world.despawn(entity);
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .try_insert(W(1u32));
+        command_queue.apply(&mut world);
+    }
+
     #[test]
     fn remove_components() {
         let mut world = World::default();
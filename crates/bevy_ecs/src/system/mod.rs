@@ -94,6 +94,7 @@
 //! - All tuples between 1 to 16 elements where each element implements [`SystemParam`]
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
+mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
@@ -104,6 +105,7 @@ mod system;
 mod system_param;
 mod system_piping;
 
+pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
@@ -142,7 +144,7 @@ mod tests {
         schedule::{Schedule, Stage, SystemStage},
         system::{
             Commands, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, QueryComponentError,
-            RemovedComponents, Res, ResMut, Resource, System, SystemState,
+            QueryLens, RemovedComponents, Res, ResMut, Resource, System, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -711,6 +713,52 @@ mod tests {
         assert_eq!(conflicts, vec![b_id, d_id]);
     }
 
+    #[test]
+    fn query_lens() {
+        #[derive(Component, Debug, PartialEq, Eq)]
+        struct Health(u32);
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Shield;
+
+        fn count_alive(mut lens: QueryLens<&Health>) -> usize {
+            lens.query().iter().filter(|health| health.0 > 0).count()
+        }
+
+        let mut world = World::new();
+        world.spawn((Health(0), A));
+        world.spawn((Health(1), A, Shield));
+        world.spawn((Health(2), A));
+        world.spawn(Health(3));
+
+        let mut system_state: SystemState<Query<(&mut Health, &A)>> = SystemState::new(&mut world);
+        let mut query = system_state.get_mut(&mut world);
+        assert_eq!(count_alive(query.transmute_lens::<&Health>()), 2);
+
+        let mut lens = query.transmute_lens_filtered::<&mut Health, Without<Shield>>();
+        for mut health in lens.query().iter_mut() {
+            health.0 += 10;
+        }
+        let mut values = world
+            .query::<&Health>()
+            .iter(&world)
+            .map(|health| health.0)
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 3, 10, 12]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn query_lens_read_only() {
+        let mut world = World::new();
+        world.spawn(A);
+        let mut system_state: SystemState<Query<&mut A>> = SystemState::new(&mut world);
+        let query = system_state.get_mut(&mut world);
+        let mut read_only = query.to_readonly();
+        read_only.transmute_lens::<&mut A>();
+    }
+
     #[test]
     fn query_is_empty() {
         fn without_filter(not_empty: Query<&A>, empty: Query<&B>) {
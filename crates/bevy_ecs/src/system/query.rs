@@ -2,8 +2,8 @@ use crate::{
     component::Component,
     entity::Entity,
     query::{
-        QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter, QuerySingleError,
-        QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
+        BatchingStrategy, QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter,
+        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
     },
     world::{Mut, World},
 };
@@ -188,6 +188,7 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// |Query methods|Effect|
 /// |:---:|---|
 /// |[`iter`]\([`_mut`][`iter_mut`])|Returns an iterator over all query items.|
+/// |[`par_iter`]\([`_mut`][`par_iter_mut`])|Returns a parallel iterator over all query items.|
 /// |[`for_each`]\([`_mut`][`for_each_mut`]),<br>[`par_for_each`]\([`_mut`][`par_for_each_mut`])|Runs a specified function for each query item.|
 /// |[`iter_many`]\([`_mut`][`iter_many_mut`])|Iterates or runs a specified function over query items generated by a list of entities.|
 /// |[`iter_combinations`]\([`_mut`][`iter_combinations_mut`])|Returns an iterator over all combinations of a specified number of query items.|
@@ -224,6 +225,7 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// |Query operation|Computational complexity|
 /// |:---:|:---:|
 /// |[`iter`]\([`_mut`][`iter_mut`])|O(n)|
+/// |[`par_iter`]\([`_mut`][`par_iter_mut`])|O(n)|
 /// |[`for_each`]\([`_mut`][`for_each_mut`]),<br>[`par_for_each`]\([`_mut`][`par_for_each_mut`])|O(n)|
 /// |[`iter_many`]\([`_mut`][`iter_many_mut`])|O(k)|
 /// |[`iter_combinations`]\([`_mut`][`iter_combinations_mut`])|O(<sub>n</sub>C<sub>r</sub>)|
@@ -265,6 +267,8 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// [`Or`]: crate::query::Or
 /// [`par_for_each`]: Self::par_for_each
 /// [`par_for_each_mut`]: Self::par_for_each_mut
+/// [`par_iter`]: Self::par_iter
+/// [`par_iter_mut`]: Self::par_iter_mut
 /// [performance]: #performance
 /// [`single`]: Self::single
 /// [`single_mut`]: Self::single_mut
@@ -322,6 +326,68 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns a [`QueryLens`] that views this query as a query for `NewQ`, matching the same
+    /// entities.
+    ///
+    /// `NewQ` can only access a subset of the data this query accesses, with the same or more
+    /// restrictive mutability. This allows helper functions to accept a narrower query than the
+    /// one a system has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::QueryLens;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// # #[derive(Component)]
+    /// # struct Armor(u32);
+    /// fn count_alive(mut lens: QueryLens<&Health>) -> usize {
+    ///     lens.query().iter().filter(|health| health.0 > 0).count()
+    /// }
+    ///
+    /// fn combat_system(mut query: Query<(&mut Health, &Armor)>) {
+    ///     let alive = count_alive(query.transmute_lens::<&Health>());
+    ///     println!("{alive} fighters left");
+    /// }
+    /// # bevy_ecs::system::assert_is_system(combat_system);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `NewQ` accesses data this query doesn't, or accesses mutably what this
+    /// query only reads.
+    pub fn transmute_lens<NewQ: WorldQuery>(&mut self) -> QueryLens<'_, NewQ> {
+        self.transmute_lens_filtered::<NewQ, ()>()
+    }
+
+    /// Returns a [`QueryLens`] that views this query as a query for `NewQ`, matching the
+    /// entities this query matches which also match the filter `NewF`.
+    ///
+    /// See [`transmute_lens`](Self::transmute_lens) for more details.
+    pub fn transmute_lens_filtered<NewQ: WorldQuery, NewF: ReadOnlyWorldQuery>(
+        &mut self,
+    ) -> QueryLens<'_, NewQ, NewF> {
+        let state = self.state.transmute_filtered::<NewQ, NewF>(self.world);
+        // the state of a query created by `to_readonly` still has the write access of the
+        // original query, see the comments on `force_read_only_component_access`
+        assert!(
+            !self.force_read_only_component_access
+                || state.component_access.access().writes().next().is_none(),
+            "Could not transmute {} to {}: the original query is read-only.",
+            std::any::type_name::<Self>(),
+            std::any::type_name::<Query<NewQ, NewF>>(),
+        );
+        QueryLens {
+            world: self.world,
+            state,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            force_read_only_component_access: self.force_read_only_component_access,
+        }
+    }
+
     /// Returns another `Query` from this that fetches the read-only version of the query items.
     ///
     /// For example, `Query<(&mut A, &B, &mut C), With<D>>` will become `Query<(&A, &B, &C), With<D>>`.
@@ -487,6 +553,10 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
     /// Items are returned in the order of the list of entities.
     /// Entities that don't match the query are skipped.
     ///
+    /// This is faster than calling [`get`](Self::get) for each entity of the list, as the
+    /// storage of an archetype is only looked up again when the next entity is in a different
+    /// archetype.
+    ///
     /// # Example
     ///
     /// ```
@@ -734,6 +804,91 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         };
     }
 
+    /// Returns a parallel iterator over the read-only query items.
+    ///
+    /// Parallelization is achieved by using the [`World`]'s [`ComputeTaskPool`]. Unlike
+    /// [`par_for_each`](Self::par_for_each), the items are split into batches whose size is
+    /// derived from the size of the largest matched archetype and the number of threads, so
+    /// a few large archetypes don't end up in a handful of huge batches, and many small ones
+    /// don't get split into tiny batches. Use [`QueryParIter::batching_strategy`] to tune this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query::BatchingStrategy;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Velocity { x: f32, y: f32, z: f32 }
+    /// fn max_speed_system(query: Query<&Velocity>) {
+    ///     let max_speed = query
+    ///         .par_iter()
+    ///         // give idle threads a chance to steal work from busy ones
+    ///         .batching_strategy(BatchingStrategy::new().batches_per_thread(4))
+    ///         .fold(
+    ///             || 0.0f32,
+    ///             |max, v| max.max((v.x * v.x + v.y * v.y + v.z * v.z).sqrt()),
+    ///         )
+    ///         .into_iter()
+    ///         .fold(0.0, f32::max);
+    ///     println!("Max speed: {max_speed}");
+    /// }
+    /// # bevy_ecs::system::assert_is_system(max_speed_system);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the [`ComputeTaskPool`] is not initialized.
+    /// If using this from a query that is being initialized and run from the [`Schedule`](crate::schedule::Schedule), this never panics.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::prelude::ComputeTaskPool
+    ///
+    /// # See also
+    ///
+    /// - [`par_iter_mut`](Self::par_iter_mut) for operating on mutable query items.
+    #[inline]
+    pub fn par_iter(&self) -> QueryParIter<'_, 's, Q::ReadOnly, F::ReadOnly> {
+        QueryParIter {
+            world: self.world,
+            state: self.state.as_readonly(),
+            batching_strategy: BatchingStrategy::new(),
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+
+    /// Returns a parallel iterator over the query items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Velocity { x: f32, y: f32, z: f32 }
+    /// fn gravity_system(mut query: Query<&mut Velocity>) {
+    ///     const DELTA: f32 = 1.0 / 60.0;
+    ///     query.par_iter_mut().for_each_mut(|mut velocity| {
+    ///         velocity.y -= 9.8 * DELTA;
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(gravity_system);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`par_iter`](Self::par_iter) for more usage details.
+    #[inline]
+    pub fn par_iter_mut(&mut self) -> QueryParIter<'_, 's, Q, F> {
+        QueryParIter {
+            world: self.world,
+            state: self.state,
+            batching_strategy: BatchingStrategy::new(),
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+
     /// Runs `f` on each read-only query item in parallel.
     ///
     /// Parallelization is achieved by using the [`World`]'s [`ComputeTaskPool`].
@@ -758,6 +913,7 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
     /// # See also
     ///
     /// - [`par_for_each_mut`](Self::par_for_each_mut) for operating on mutable query items.
+    /// - [`par_iter`](Self::par_iter) for choosing the batch size automatically.
     #[inline]
     pub fn par_for_each<'this>(
         &'this self,
@@ -1429,6 +1585,43 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w mut Quer
     }
 }
 
+/// A [`Query`] for `Q` created from another query with [`Query::transmute_lens`], that owns its
+/// [`QueryState`].
+///
+/// Call [`query`](Self::query) to use it like any other [`Query`].
+pub struct QueryLens<'w, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+    world: &'w World,
+    state: QueryState<Q, F>,
+    last_change_tick: u32,
+    change_tick: u32,
+    force_read_only_component_access: bool,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryLens<'w, Q, F> {
+    /// Returns a [`Query`] for the lens.
+    pub fn query(&mut self) -> Query<'w, '_, Q, F> {
+        // SAFETY: the lens borrows the query it was created from mutably, and the state only
+        // accesses a subset of the data of that query
+        unsafe {
+            Query::new(
+                self.world,
+                &self.state,
+                self.last_change_tick,
+                self.change_tick,
+                self.force_read_only_component_access,
+            )
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> From<&'s mut QueryLens<'w, Q, F>>
+    for Query<'w, 's, Q, F>
+{
+    fn from(lens: &'s mut QueryLens<'w, Q, F>) -> Self {
+        lens.query()
+    }
+}
+
 /// An error that occurs when retrieving a specific [`Entity`]'s component from a [`Query`]
 #[derive(Debug, PartialEq, Eq)]
 pub enum QueryComponentError {
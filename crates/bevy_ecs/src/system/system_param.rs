@@ -3,7 +3,7 @@ use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundles,
     change_detection::{Ticks, TicksMut},
-    component::{Component, ComponentId, ComponentTicks, Components},
+    component::{Component, ComponentId, ComponentTicks, Components, Tick},
     entity::{Entities, Entity},
     query::{
         Access, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyWorldQuery, WorldQuery,
@@ -454,8 +454,8 @@ unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
         Res {
             value: ptr.deref(),
             ticks: Ticks {
-                added: ticks.added.deref(),
-                changed: ticks.changed.deref(),
+                added: Some(ticks.added.deref()),
+                changed: Some(ticks.changed.deref()),
                 last_change_tick: system_meta.last_change_tick,
                 change_tick,
             },
@@ -487,8 +487,8 @@ unsafe impl<'a, T: Resource> SystemParam for Option<Res<'a, T>> {
             .map(|(ptr, ticks)| Res {
                 value: ptr.deref(),
                 ticks: Ticks {
-                    added: ticks.added.deref(),
-                    changed: ticks.changed.deref(),
+                    added: Some(ticks.added.deref()),
+                    changed: Some(ticks.changed.deref()),
                     last_change_tick: system_meta.last_change_tick,
                     change_tick,
                 },
@@ -918,9 +918,10 @@ impl<'a, T> From<NonSendMut<'a, T>> for NonSend<'a, T> {
     fn from(nsm: NonSendMut<'a, T>) -> Self {
         Self {
             value: nsm.value,
+            // non-send resources always have change detection
             ticks: ComponentTicks {
-                added: nsm.ticks.added.to_owned(),
-                changed: nsm.ticks.changed.to_owned(),
+                added: nsm.ticks.added.map_or(Tick::new(0), |added| *added),
+                changed: nsm.ticks.changed.map_or(Tick::new(0), |changed| *changed),
             },
             change_tick: nsm.ticks.change_tick,
             last_change_tick: nsm.ticks.last_change_tick,
@@ -2,7 +2,7 @@ use crate::{
     archetype::ArchetypeComponentId,
     component::ComponentId,
     query::Access,
-    system::{IntoSystem, System},
+    system::{IntoSystem, ReadOnlySystem, System},
     world::World,
 };
 use std::{any::TypeId, borrow::Cow};
@@ -159,6 +159,12 @@ impl<SystemA: System, SystemB: System<In = SystemA::Out>> System for PipeSystem<
     }
 }
 
+// SAFETY: Both systems are read-only, so piping them together will only read from the world.
+unsafe impl<SystemA: ReadOnlySystem, SystemB: ReadOnlySystem<In = SystemA::Out>> ReadOnlySystem
+    for PipeSystem<SystemA, SystemB>
+{
+}
+
 /// An extension trait providing the [`IntoPipeSystem::pipe`] method to pass input from one system into the next.
 ///
 /// The first system must have return type `T`
@@ -10,6 +10,8 @@ use crate::{
     world::{Mut, World},
 };
 use bevy_ptr::{OwningPtr, Ptr};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, ReflectFromPtr, TypeRegistry};
 use bevy_utils::tracing::debug;
 use std::any::TypeId;
 
@@ -97,6 +99,9 @@ impl<'w> EntityRef<'w> {
 
     /// Retrieves the change ticks for the given component. This can be useful for implementing change
     /// detection in custom runtimes.
+    ///
+    /// Returns `None` if the entity doesn't have the component or the component opted out of
+    /// change detection.
     #[inline]
     pub fn get_change_ticks<T: Component>(&self) -> Option<ComponentTicks> {
         // SAFETY:
@@ -168,7 +173,7 @@ impl<'w> EntityRef<'w> {
                 // - returned component is of type T
                 // - Caller guarantees that this reference will not alias.
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(ticks, last_change_tick, change_tick),
+                ticks: TicksMut::from_optional_tick_cells(ticks, last_change_tick, change_tick),
             })
     }
 }
@@ -200,6 +205,35 @@ impl<'w> EntityRef<'w> {
     }
 }
 
+#[cfg(feature = "bevy_reflect")]
+impl<'w> EntityRef<'w> {
+    /// Gets the component of the given [`ComponentId`] from the entity as a `&dyn Reflect`.
+    ///
+    /// Returns `None` if the entity does not have the component, or if its type
+    /// was not registered in `registry` with [`ReflectFromPtr`] type data.
+    pub fn get_reflect(
+        &self,
+        component_id: ComponentId,
+        registry: &TypeRegistry,
+    ) -> Option<&'w dyn Reflect> {
+        let reflect_from_ptr = reflect_from_ptr(self.world, component_id, registry)?;
+        let ptr = self.get_by_id(component_id)?;
+        // SAFETY: `reflect_from_ptr` was registered for the type of the component behind `ptr`
+        Some(unsafe { reflect_from_ptr.as_reflect_ptr(ptr) })
+    }
+
+    /// Returns an iterator over every component of the entity whose type is registered
+    /// in `registry` with [`ReflectFromPtr`] type data, along with its [`ComponentId`].
+    pub fn reflect_components<'a>(
+        &'a self,
+        registry: &'a TypeRegistry,
+    ) -> impl Iterator<Item = (ComponentId, &'w dyn Reflect)> + 'a {
+        self.archetype().components().filter_map(|component_id| {
+            Some((component_id, self.get_reflect(component_id, registry)?))
+        })
+    }
+}
+
 impl<'w> From<EntityMut<'w>> for EntityRef<'w> {
     fn from(entity_mut: EntityMut<'w>) -> EntityRef<'w> {
         // SAFETY: the safety invariants on EntityMut and EntityRef are identical
@@ -296,6 +330,9 @@ impl<'w> EntityMut<'w> {
 
     /// Retrieves the change ticks for the given component. This can be useful for implementing change
     /// detection in custom runtimes.
+    ///
+    /// Returns `None` if the entity doesn't have the component or the component opted out of
+    /// change detection.
     #[inline]
     pub fn get_change_ticks<T: Component>(&self) -> Option<ComponentTicks> {
         // SAFETY:
@@ -360,7 +397,7 @@ impl<'w> EntityMut<'w> {
             )
             .map(|(value, ticks)| Mut {
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(
+                ticks: TicksMut::from_optional_tick_cells(
                     ticks,
                     self.world.last_change_tick(),
                     self.world.read_change_tick(),
@@ -712,6 +749,53 @@ impl<'w> EntityMut<'w> {
     }
 }
 
+#[cfg(feature = "bevy_reflect")]
+impl<'w> EntityMut<'w> {
+    /// Gets the component of the given [`ComponentId`] from the entity as a `&dyn Reflect`.
+    ///
+    /// Returns `None` if the entity does not have the component, or if its type
+    /// was not registered in `registry` with [`ReflectFromPtr`] type data.
+    pub fn get_reflect(
+        &self,
+        component_id: ComponentId,
+        registry: &TypeRegistry,
+    ) -> Option<&'_ dyn Reflect> {
+        let reflect_from_ptr = reflect_from_ptr(self.world, component_id, registry)?;
+        let ptr = self.get_by_id(component_id)?;
+        // SAFETY: `reflect_from_ptr` was registered for the type of the component behind `ptr`
+        Some(unsafe { reflect_from_ptr.as_reflect_ptr(ptr) })
+    }
+
+    /// Gets a [`Mut`] to the component of the given [`ComponentId`] from the entity as a `dyn Reflect`.
+    ///
+    /// Returns `None` if the entity does not have the component, or if its type
+    /// was not registered in `registry` with [`ReflectFromPtr`] type data.
+    pub fn get_reflect_mut(
+        &mut self,
+        component_id: ComponentId,
+        registry: &TypeRegistry,
+    ) -> Option<Mut<'_, dyn Reflect>> {
+        let reflect_from_ptr = reflect_from_ptr(self.world, component_id, registry)?;
+        let MutUntyped { value, ticks } = self.get_mut_by_id(component_id)?;
+        Some(Mut {
+            // SAFETY: `reflect_from_ptr` was registered for the type of the component behind `value`
+            value: unsafe { reflect_from_ptr.as_reflect_ptr_mut(value) },
+            ticks,
+        })
+    }
+}
+
+/// Looks up the [`ReflectFromPtr`] registered for the type of the given component.
+#[cfg(feature = "bevy_reflect")]
+fn reflect_from_ptr<'r>(
+    world: &World,
+    component_id: ComponentId,
+    registry: &'r TypeRegistry,
+) -> Option<&'r ReflectFromPtr> {
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    registry.get_type_data::<ReflectFromPtr>(type_id)
+}
+
 fn contains_component_with_type(world: &World, type_id: TypeId, location: EntityLocation) -> bool {
     if let Some(component_id) = world.components.get_id(type_id) {
         contains_component_with_id(world, component_id, location)
@@ -870,7 +954,7 @@ pub(crate) unsafe fn get_mut<T: Component>(
             // - world access is unique and ties world lifetime to `Mut` lifetime
             // - `value` is of type `T`
             value: value.assert_unique().deref_mut::<T>(),
-            ticks: TicksMut::from_tick_cells(ticks, last_change_tick, change_tick),
+            ticks: TicksMut::from_optional_tick_cells(ticks, last_change_tick, change_tick),
         })
 }
 
@@ -894,7 +978,7 @@ pub(crate) unsafe fn get_mut_by_id(
         .map(|(value, ticks)| MutUntyped {
             // SAFETY: world access is unique and ties world lifetime to `MutUntyped` lifetime
             value: value.assert_unique(),
-            ticks: TicksMut::from_tick_cells(ticks, world.last_change_tick(), change_tick),
+            ticks: TicksMut::from_optional_tick_cells(ticks, world.last_change_tick(), change_tick),
         })
 }
 
@@ -1017,6 +1101,43 @@ mod tests {
         assert_eq!(test_component.0, 43);
     }
 
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn entity_get_reflect() {
+        use bevy_reflect::{Reflect, TypeRegistry};
+
+        #[derive(Component, Reflect, Default)]
+        struct ReflectedComponent(u32);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<ReflectedComponent>();
+
+        let mut world = World::new();
+        let entity = world.spawn((ReflectedComponent(42), TestComponent(1))).id();
+        let component_id = world.init_component::<ReflectedComponent>();
+        let unregistered_id = world.init_component::<TestComponent>();
+
+        let entity_ref = world.entity(entity);
+        let reflected = entity_ref.get_reflect(component_id, &registry).unwrap();
+        assert_eq!(
+            reflected.downcast_ref::<ReflectedComponent>().unwrap().0,
+            42
+        );
+        assert!(entity_ref.get_reflect(unregistered_id, &registry).is_none());
+        let components: Vec<_> = entity_ref
+            .reflect_components(&registry)
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(components, vec![component_id]);
+
+        world.clear_trackers();
+        let mut entity_mut = world.entity_mut(entity);
+        let mut reflected = entity_mut.get_reflect_mut(component_id, &registry).unwrap();
+        reflected.apply(&ReflectedComponent(43));
+        assert!(reflected.is_changed());
+        assert_eq!(world.get::<ReflectedComponent>(entity).unwrap().0, 43);
+    }
+
     #[test]
     fn entity_ref_get_by_id_invalid_component_id() {
         let invalid_component_id = ComponentId::new(usize::MAX);
@@ -608,6 +608,119 @@ impl World {
         }
     }
 
+    /// Despawns every entity in `entities` that exists, returning how many were despawned.
+    /// Entities that do not exist (or appear more than once) are skipped silently.
+    ///
+    /// This is faster than calling [`World::despawn`] in a loop when despawning many entities:
+    /// the entities are removed with a single pass over each affected archetype and table, from
+    /// the back to the front, so no entity that is about to be despawned is ever swapped into the
+    /// slot left behind by an earlier removal, and only the entities that are swapped in have to
+    /// be relocated.
+    /// ```
+    /// use bevy_ecs::{component::Component, world::World};
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// let mut world = World::new();
+    /// let enemies = world.spawn_batch((0..10).map(|_| Enemy)).collect::<Vec<_>>();
+    /// assert_eq!(world.despawn_batch(enemies.iter().copied()), 10);
+    /// assert_eq!(world.entities().len(), 0);
+    /// ```
+    pub fn despawn_batch(&mut self, entities: impl IntoIterator<Item = Entity>) -> usize {
+        self.flush();
+        let mut locations = entities
+            .into_iter()
+            .filter_map(|entity| Some((entity, self.entities.get(entity)?)))
+            .collect::<Vec<_>>();
+        locations.sort_unstable_by(|(_, a), (_, b)| {
+            a.archetype_id
+                .index()
+                .cmp(&b.archetype_id.index())
+                .then_with(|| b.archetype_row.index().cmp(&a.archetype_row.index()))
+        });
+        // duplicates share a location, so they are adjacent after sorting
+        locations.dedup_by_key(|(entity, _)| *entity);
+
+        for &(entity, _) in &locations {
+            self.entities.free(entity);
+        }
+
+        // remove the entities from their archetypes, one archetype at a time
+        let mut table_rows = Vec::with_capacity(locations.len());
+        let mut remaining = &locations[..];
+        while let Some(&(_, first)) = remaining.first() {
+            let group_len = remaining
+                .iter()
+                .take_while(|(_, location)| location.archetype_id == first.archetype_id)
+                .count();
+            let (group, rest) = remaining.split_at(group_len);
+            remaining = rest;
+
+            let archetype = &mut self.archetypes[first.archetype_id];
+            for component_id in archetype.components() {
+                self.removed_components
+                    .get_or_insert_with(component_id, Vec::new)
+                    .extend(group.iter().map(|(entity, _)| *entity));
+            }
+            for component_id in archetype.sparse_set_components() {
+                let sparse_set = self.storages.sparse_sets.get_mut(component_id).unwrap();
+                for &(entity, _) in group {
+                    sparse_set.remove(entity);
+                }
+            }
+            // rows are removed back-to-front, so the swapped in entities are never despawned
+            for &(_, location) in group {
+                let remove_result = archetype.swap_remove(location.archetype_row);
+                if let Some(swapped_entity) = remove_result.swapped_entity {
+                    let swapped_location = self.entities.get(swapped_entity).unwrap();
+                    // SAFETY: swapped_entity is valid and was just moved to this archetype row
+                    unsafe {
+                        self.entities.set(
+                            swapped_entity.index(),
+                            EntityLocation {
+                                archetype_row: location.archetype_row,
+                                ..swapped_location
+                            },
+                        );
+                    }
+                }
+                table_rows.push((location.table_id, remove_result.table_row));
+            }
+        }
+
+        // then remove their components from the tables, one table at a time
+        table_rows.sort_unstable_by(|(table_a, row_a), (table_b, row_b)| {
+            table_a
+                .index()
+                .cmp(&table_b.index())
+                .then_with(|| row_b.index().cmp(&row_a.index()))
+        });
+        for (table_id, table_row) in table_rows {
+            // SAFETY: table rows stored in archetypes always exist, and rows are removed
+            // back-to-front so the rows that are still to be removed stay in bounds
+            let moved_entity =
+                unsafe { self.storages.tables[table_id].swap_remove_unchecked(table_row) };
+            if let Some(moved_entity) = moved_entity {
+                let moved_location = self.entities.get(moved_entity).unwrap();
+                // SAFETY: moved_entity is valid and was just moved to this table row
+                unsafe {
+                    self.entities.set(
+                        moved_entity.index(),
+                        EntityLocation {
+                            table_row,
+                            ..moved_location
+                        },
+                    );
+                }
+                self.archetypes[moved_location.archetype_id]
+                    .set_entity_table_row(moved_location.archetype_row, table_row);
+            }
+        }
+
+        locations.len()
+    }
+
     /// Clears the internal component tracker state.
     ///
     /// The world maintains some internal state about changed and removed components. This state
@@ -1327,8 +1440,8 @@ impl World {
         let value_mut = Mut {
             value: &mut value,
             ticks: TicksMut {
-                added: &mut ticks.added,
-                changed: &mut ticks.changed,
+                added: Some(&mut ticks.added),
+                changed: Some(&mut ticks.changed),
                 last_change_tick,
                 change_tick,
             },
@@ -1447,8 +1560,8 @@ impl World {
         Some(Mut {
             value: ptr.assert_unique().deref_mut(),
             ticks: TicksMut {
-                added: ticks.added.deref_mut(),
-                changed: ticks.changed.deref_mut(),
+                added: Some(ticks.added.deref_mut()),
+                changed: Some(ticks.changed.deref_mut()),
                 last_change_tick: self.last_change_tick(),
                 change_tick: self.read_change_tick(),
             },
@@ -1822,7 +1935,7 @@ impl World {
         storage_type: StorageType,
         entity: Entity,
         location: EntityLocation,
-    ) -> Option<(Ptr<'_>, TickCells<'_>)> {
+    ) -> Option<(Ptr<'_>, Option<TickCells<'_>>)> {
         let component_id = self.components.get_id(type_id)?;
         // SAFETY: component_id is valid, the rest is deferred to caller
         self.get_component_and_ticks(component_id, storage_type, entity, location)
@@ -1830,6 +1943,8 @@ impl World {
 
     /// Get a raw pointer to a particular [`Component`](crate::component::Component) and its [`ComponentTicks`]
     ///
+    /// The ticks are `None` if the component has no change detection.
+    ///
     /// # Safety
     /// - `location` must refer to an archetype that contains `entity`
     /// - `component_id` must be valid
@@ -1842,7 +1957,7 @@ impl World {
         storage_type: StorageType,
         entity: Entity,
         location: EntityLocation,
-    ) -> Option<(Ptr<'_>, TickCells<'_>)> {
+    ) -> Option<(Ptr<'_>, Option<TickCells<'_>>)> {
         match storage_type {
             StorageType::Table => {
                 let (components, table_row) = self.fetch_table(location, component_id)?;
@@ -1850,10 +1965,7 @@ impl World {
                 // SAFETY: archetypes only store valid table_rows and caller ensure aliasing rules
                 Some((
                     components.get_data_unchecked(table_row),
-                    TickCells {
-                        added: components.get_added_ticks_unchecked(table_row),
-                        changed: components.get_changed_ticks_unchecked(table_row),
-                    },
+                    components.get_tick_cells_unchecked(table_row),
                 ))
             }
             StorageType::SparseSet => self.fetch_sparse_set(component_id)?.get_with_ticks(entity),
@@ -1946,8 +2058,7 @@ impl World {
         match storage_type {
             StorageType::Table => {
                 let (components, table_row) = self.fetch_table(location, component_id)?;
-                // SAFETY: archetypes only store valid table_rows and caller ensure aliasing rules
-                Some(components.get_ticks_unchecked(table_row))
+                components.get_ticks(table_row).flatten()
             }
             StorageType::SparseSet => self.fetch_sparse_set(component_id)?.get_ticks(entity),
         }
@@ -2399,4 +2510,73 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[test]
+    fn despawn_batch() {
+        #[derive(Component, Debug, PartialEq)]
+        struct A(usize);
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct B;
+
+        let mut world = World::new();
+        // the archetypes of (A,) and (A, B) share a table
+        let entities = (0..30)
+            .map(|i| {
+                if i % 3 == 0 {
+                    world.spawn((A(i), B)).id()
+                } else {
+                    world.spawn(A(i)).id()
+                }
+            })
+            .collect::<Vec<_>>();
+        let despawned = entities
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(i, _)| i % 2 == 0 || *i < 5)
+            .map(|(_, entity)| entity)
+            .collect::<Vec<_>>();
+
+        // duplicates are only despawned once
+        let count = world.despawn_batch(despawned.iter().chain(&despawned[..3]).copied());
+        assert_eq!(count, despawned.len());
+        assert_eq!(world.despawn_batch(despawned.iter().copied()), 0);
+
+        for (i, &entity) in entities.iter().enumerate() {
+            if despawned.contains(&entity) {
+                assert!(world.get_entity(entity).is_none());
+            } else {
+                assert_eq!(world.get::<A>(entity), Some(&A(i)));
+                assert_eq!(world.get::<B>(entity).is_some(), i % 3 == 0);
+            }
+        }
+        let mut remaining = world
+            .query::<&A>()
+            .iter(&world)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        remaining.sort_unstable();
+        assert_eq!(
+            remaining,
+            (5..30).filter(|i| i % 2 == 1).collect::<Vec<_>>()
+        );
+        assert_eq!(world.removed::<A>().count(), despawned.len());
+        assert_eq!(
+            world.removed::<B>().count(),
+            despawned
+                .iter()
+                .filter(|entity| entities.iter().position(|e| e == *entity).unwrap() % 3 == 0)
+                .count()
+        );
+
+        let survivors = entities
+            .iter()
+            .copied()
+            .filter(|entity| !despawned.contains(entity))
+            .collect::<Vec<_>>();
+        assert_eq!(world.despawn_batch(survivors), 13);
+        assert_eq!(world.entities().len(), 0);
+    }
 }
@@ -0,0 +1,77 @@
+use std::{path::Path, sync::Arc};
+
+use bevy_asset::{Asset, Handle, LoadContext};
+use bevy_ecs::world::World;
+use bevy_pbr::StandardMaterial;
+use bevy_render::mesh::Mesh;
+
+/// The asset of a glTF file being processed by a hook added to the
+/// [`GltfPlugin`](crate::GltfPlugin).
+pub struct GltfHookContext<'a, 'b> {
+    /// The label of the asset, such as `Mesh0/Primitive0`, `Material0` or `Scene0`.
+    pub label: &'a str,
+    /// The name of the glTF mesh, material or scene the asset is loaded from, if it has one.
+    pub name: Option<&'a str>,
+    load_context: &'a LoadContext<'b>,
+}
+
+impl<'a, 'b> GltfHookContext<'a, 'b> {
+    pub(crate) fn new(
+        label: &'a str,
+        name: Option<&'a str>,
+        load_context: &'a LoadContext<'b>,
+    ) -> Self {
+        Self {
+            label,
+            name,
+            load_context,
+        }
+    }
+
+    /// Gets the path of the glTF file.
+    pub fn path(&self) -> &Path {
+        self.load_context.path()
+    }
+
+    /// Gets an asset of the glTF file that was loaded before this one.
+    ///
+    /// Textures are loaded before materials, and meshes and materials before scenes, so a scene
+    /// hook can read the [`Mesh`] of the `Handle<Mesh>` components of its entities.
+    pub fn get_asset<T: Asset>(&self, handle: &Handle<T>) -> Option<&T> {
+        self.load_context.get_asset(handle)
+    }
+}
+
+type Hook<T> = Arc<dyn Fn(&GltfHookContext, &mut T) + Send + Sync>;
+
+/// The callbacks that process the assets of glTF files before they are added.
+#[derive(Default, Clone)]
+pub(crate) struct GltfHooks {
+    pub(crate) meshes: Vec<Hook<Mesh>>,
+    pub(crate) materials: Vec<Hook<StandardMaterial>>,
+    pub(crate) scenes: Vec<Hook<World>>,
+}
+
+impl GltfHooks {
+    pub(crate) fn process_mesh(&self, context: &GltfHookContext, mesh: &mut Mesh) {
+        for hook in &self.meshes {
+            hook(context, mesh);
+        }
+    }
+
+    pub(crate) fn process_material(
+        &self,
+        context: &GltfHookContext,
+        material: &mut StandardMaterial,
+    ) {
+        for hook in &self.materials {
+            hook(context, material);
+        }
+    }
+
+    pub(crate) fn process_scene(&self, context: &GltfHookContext, world: &mut World) {
+        for hook in &self.scenes {
+            hook(context, world);
+        }
+    }
+}
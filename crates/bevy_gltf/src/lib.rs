@@ -2,29 +2,160 @@
 use bevy_animation::AnimationClip;
 use bevy_utils::HashMap;
 
+mod hooks;
 mod loader;
+mod meshopt;
+mod variants;
+pub use hooks::GltfHookContext;
 pub use loader::*;
+pub use variants::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Handle};
-use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    prelude::{Component, FromWorld},
+    reflect::ReflectComponent,
+};
 use bevy_pbr::StandardMaterial;
 use bevy_reflect::{Reflect, TypeUuid};
-use bevy_render::mesh::Mesh;
+use bevy_render::mesh::{Mesh, MeshVertexAttribute};
 use bevy_scene::Scene;
+use hooks::GltfHooks;
+use std::sync::Arc;
 
 /// Adds support for glTF file loading to the app.
 #[derive(Default)]
-pub struct GltfPlugin;
+pub struct GltfPlugin {
+    custom_vertex_attributes: HashMap<String, MeshVertexAttribute>,
+    hooks: GltfHooks,
+    lod_ratios: Vec<f32>,
+    #[cfg(feature = "bevy_animation")]
+    animation_compression: Option<bevy_animation::AnimationCompression>,
+}
+
+impl GltfPlugin {
+    /// Loads the custom glTF vertex attribute `name` as `attribute` in the meshes of glTF files.
+    ///
+    /// Custom attribute names start with an underscore, such as `_TEMPERATURE`. The attribute is
+    /// converted to the [`VertexFormat`](bevy_render::render_resource::VertexFormat) of
+    /// `attribute`, so it can be read by the shader of a custom material.
+    ///
+    /// ```no_run
+    /// # use bevy_gltf::GltfPlugin;
+    /// # use bevy_render::{mesh::MeshVertexAttribute, render_resource::VertexFormat};
+    /// const ATTRIBUTE_TEMPERATURE: MeshVertexAttribute =
+    ///     MeshVertexAttribute::new("Temperature", 988540917, VertexFormat::Float32);
+    ///
+    /// let plugin = GltfPlugin::default()
+    ///     .add_custom_vertex_attribute("_TEMPERATURE", ATTRIBUTE_TEMPERATURE);
+    /// ```
+    pub fn add_custom_vertex_attribute(
+        mut self,
+        name: &str,
+        attribute: MeshVertexAttribute,
+    ) -> Self {
+        self.custom_vertex_attributes
+            .insert(name.to_string(), attribute);
+        self
+    }
+
+    /// Calls `hook` on each mesh of a glTF file before it is added.
+    ///
+    /// Hooks are called in the order they were added.
+    ///
+    /// This can post-process the meshes of the file, such as welding their duplicate vertices and
+    /// reordering them for the vertex cache of the GPU:
+    ///
+    /// ```no_run
+    /// # use bevy_gltf::GltfPlugin;
+    /// let plugin = GltfPlugin::default().add_mesh_hook(|_, mesh| {
+    ///     mesh.weld_vertices();
+    ///     mesh.optimize_vertex_cache();
+    /// });
+    /// ```
+    pub fn add_mesh_hook(
+        mut self,
+        hook: impl Fn(&GltfHookContext, &mut Mesh) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.meshes.push(Arc::new(hook));
+        self
+    }
+
+    /// Calls `hook` on each material of a glTF file before it is added.
+    ///
+    /// This can replace properties of the file, such as making all materials unlit:
+    ///
+    /// ```no_run
+    /// # use bevy_gltf::GltfPlugin;
+    /// let plugin = GltfPlugin::default().add_material_hook(|_, material| material.unlit = true);
+    /// ```
+    pub fn add_material_hook(
+        mut self,
+        hook: impl Fn(&GltfHookContext, &mut StandardMaterial) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.materials.push(Arc::new(hook));
+        self
+    }
+
+    /// Calls `hook` on the [`World`](bevy_ecs::world::World) of each scene of a glTF file before
+    /// it is added.
+    ///
+    /// Components added to its entities, such as colliders built from their meshes with
+    /// [`GltfHookContext::get_asset`], are spawned with the scene.
+    pub fn add_scene_hook(
+        mut self,
+        hook: impl Fn(&GltfHookContext, &mut bevy_ecs::world::World) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.scenes.push(Arc::new(hook));
+        self
+    }
+
+    /// Generates simplified versions of the meshes of glTF files when they are loaded, with about
+    /// the given ratios of their triangles, see [`Mesh::simplify`].
+    ///
+    /// The levels of detail are added to [`GltfPrimitive::lods`], labeled after their mesh such
+    /// as `Mesh0/Primitive0/Lod1` for the first ratio.
+    ///
+    /// ```no_run
+    /// # use bevy_gltf::GltfPlugin;
+    /// let plugin = GltfPlugin::default().with_generated_lods(&[0.5, 0.25, 0.1]);
+    /// ```
+    pub fn with_generated_lods(mut self, ratios: &[f32]) -> Self {
+        self.lod_ratios = ratios.to_vec();
+        self
+    }
+
+    /// Compresses the animations of glTF files with the given settings when they are loaded,
+    /// see [`AnimationClip::compress`](bevy_animation::AnimationClip::compress).
+    #[cfg(feature = "bevy_animation")]
+    pub fn with_animation_compression(
+        mut self,
+        compression: bevy_animation::AnimationCompression,
+    ) -> Self {
+        self.animation_compression = Some(compression);
+        self
+    }
+}
 
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut App) {
-        app.init_asset_loader::<GltfLoader>()
+        let mut loader = GltfLoader::from_world(&mut app.world);
+        loader.custom_vertex_attributes = self.custom_vertex_attributes.clone();
+        loader.hooks = self.hooks.clone();
+        loader.lod_ratios = self.lod_ratios.clone();
+        #[cfg(feature = "bevy_animation")]
+        {
+            loader.animation_compression = self.animation_compression;
+        }
+        app.add_asset_loader(loader)
             .register_type::<GltfExtras>()
+            .register_type::<GltfMaterialVariants>()
+            .register_type::<GltfActiveVariant>()
             .add_asset::<Gltf>()
             .add_asset::<GltfNode>()
             .add_asset::<GltfPrimitive>()
-            .add_asset::<GltfMesh>();
+            .add_asset::<GltfMesh>()
+            .add_system_to_stage(CoreStage::PostUpdate, apply_material_variants);
     }
 }
 
@@ -40,6 +171,9 @@ pub struct Gltf {
     pub named_materials: HashMap<String, Handle<StandardMaterial>>,
     pub nodes: Vec<Handle<GltfNode>>,
     pub named_nodes: HashMap<String, Handle<GltfNode>>,
+    /// The names of the material variants of the `KHR_materials_variants` extension, see
+    /// [`GltfActiveVariant`].
+    pub variants: Vec<String>,
     pub default_scene: Option<Handle<Scene>>,
     #[cfg(feature = "bevy_animation")]
     pub animations: Vec<Handle<AnimationClip>>,
@@ -69,6 +203,9 @@ pub struct GltfMesh {
 #[uuid = "cbfca302-82fd-41cb-af77-cab6b3d50af1"]
 pub struct GltfPrimitive {
     pub mesh: Handle<Mesh>,
+    /// Simplified versions of [`GltfPrimitive::mesh`], from the most to the least detailed,
+    /// generated when [`GltfPlugin::with_generated_lods`] is used.
+    pub lods: Vec<Handle<Mesh>>,
     pub material: Option<Handle<StandardMaterial>>,
 }
 
@@ -20,11 +20,13 @@ use bevy_render::{
     color::Color,
     mesh::{
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-        Indices, Mesh, VertexAttributeValues,
+        Indices, Mesh, MeshVertexAttribute, VertexAttributeValues,
     },
     prelude::SpatialBundle,
     primitives::{Aabb, Frustum},
-    render_resource::{AddressMode, Face, FilterMode, PrimitiveTopology, SamplerDescriptor},
+    render_resource::{
+        AddressMode, Face, FilterMode, PrimitiveTopology, SamplerDescriptor, VertexFormat,
+    },
     renderer::RenderDevice,
     texture::{CompressedImageFormats, Image, ImageSampler, ImageType, TextureError},
     view::VisibleEntities,
@@ -36,14 +38,22 @@ use bevy_transform::components::Transform;
 
 use bevy_utils::{HashMap, HashSet};
 use gltf::{
-    mesh::Mode,
+    accessor::{sparse::IndexType, Accessor, DataType},
+    mesh::{BoundingBox, Mode, Semantic},
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Node, Primitive,
 };
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
-use crate::{Gltf, GltfNode};
+use crate::{
+    hooks::{GltfHookContext, GltfHooks},
+    meshopt::{self, CompressedBufferViews},
+    Gltf, GltfNode,
+};
+
+/// Extensions supported by the loader itself rather than by the `gltf` crate.
+const LOADER_EXTENSIONS: &[&str] = &[meshopt::EXTENSION_NAME, "KHR_mesh_quantization"];
 
 /// An error that occurs when loading a glTF file.
 #[derive(Error, Debug)]
@@ -52,6 +62,12 @@ pub enum GltfError {
     UnsupportedPrimitive { mode: Mode },
     #[error("invalid glTF file: {0}")]
     Gltf(#[from] gltf::Error),
+    #[error("the glTF file requires the unsupported extension {0}")]
+    UnsupportedExtension(String),
+    #[error("failed to decode meshopt compressed buffer data")]
+    MeshoptDecode,
+    #[error("accessor {0} doesn't match its attribute or is out of the bounds of its buffer")]
+    InvalidAccessor(usize),
     #[error("binary blob is missing")]
     MissingBlob,
     #[error("failed to decode base64 mesh data")]
@@ -73,6 +89,16 @@ pub enum GltfError {
 /// Loads glTF files with all of their data as their corresponding bevy representations.
 pub struct GltfLoader {
     supported_compressed_formats: CompressedImageFormats,
+    /// The mesh vertex attributes that custom glTF attributes are loaded as, by attribute name,
+    /// see [`GltfPlugin::add_custom_vertex_attribute`](crate::GltfPlugin::add_custom_vertex_attribute).
+    pub custom_vertex_attributes: HashMap<String, MeshVertexAttribute>,
+    pub(crate) hooks: GltfHooks,
+    /// The ratios of triangles kept by the levels of detail generated for each mesh, see
+    /// [`GltfPlugin::with_generated_lods`](crate::GltfPlugin::with_generated_lods).
+    pub lod_ratios: Vec<f32>,
+    /// The compression applied to the animations of glTF files, if any.
+    #[cfg(feature = "bevy_animation")]
+    pub animation_compression: Option<bevy_animation::AnimationCompression>,
 }
 
 impl AssetLoader for GltfLoader {
@@ -81,9 +107,7 @@ impl AssetLoader for GltfLoader {
         bytes: &'a [u8],
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
-        Box::pin(async move {
-            Ok(load_gltf(bytes, load_context, self.supported_compressed_formats).await?)
-        })
+        Box::pin(async move { Ok(load_gltf(bytes, load_context, self).await?) })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -100,6 +124,11 @@ impl FromWorld for GltfLoader {
         };
         Self {
             supported_compressed_formats,
+            custom_vertex_attributes: HashMap::default(),
+            hooks: GltfHooks::default(),
+            lod_ratios: Vec::new(),
+            #[cfg(feature = "bevy_animation")]
+            animation_compression: None,
         }
     }
 }
@@ -108,16 +137,56 @@ impl FromWorld for GltfLoader {
 async fn load_gltf<'a, 'b>(
     bytes: &'a [u8],
     load_context: &'a mut LoadContext<'b>,
-    supported_compressed_formats: CompressedImageFormats,
+    loader: &GltfLoader,
 ) -> Result<(), GltfError> {
-    let gltf = gltf::Gltf::from_slice(bytes)?;
-    let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
+    let supported_compressed_formats = loader.supported_compressed_formats;
+    let gltf = gltf::Gltf::from_slice_without_validation(bytes)?;
+    // report unsupported extensions by name, as validation only reports their index. Files that
+    // require `KHR_draco_mesh_compression` can't be loaded yet, as there is no Draco decoder, but
+    // files that only use it also contain uncompressed meshes, which are loaded instead
+    if let Some(extension) = gltf.extensions_required().find(|extension| {
+        !gltf::json::extensions::ENABLED_EXTENSIONS.contains(extension)
+            && !LOADER_EXTENSIONS.contains(extension)
+    }) {
+        return Err(GltfError::UnsupportedExtension(extension.to_string()));
+    }
+    let compressed_buffer_views = if gltf
+        .extensions_used()
+        .any(|extension| extension == meshopt::EXTENSION_NAME)
+    {
+        let json = if bytes.starts_with(b"glTF") {
+            gltf::Glb::from_slice(bytes)?.json
+        } else {
+            bytes.into()
+        };
+        Some(CompressedBufferViews::from_json(&json)?)
+    } else {
+        None
+    };
+    let mut json = gltf.document.into_json();
+    json.extensions_required
+        .retain(|extension| !LOADER_EXTENSIONS.contains(&extension.as_str()));
+    let gltf = gltf::Gltf {
+        document: gltf::Document::from_json(json)?,
+        blob: gltf.blob,
+    };
+
+    let mut buffer_data = load_buffers(
+        &gltf,
+        compressed_buffer_views.as_ref(),
+        load_context,
+        load_context.path(),
+    )
+    .await?;
+    if let Some(compressed_buffer_views) = &compressed_buffer_views {
+        compressed_buffer_views.decode(&mut buffer_data)?;
+    }
 
     let mut materials = vec![];
     let mut named_materials = HashMap::default();
     let mut linear_textures = HashSet::default();
     for material in gltf.materials() {
-        let handle = load_material(&material, load_context);
+        let handle = load_material(&material, load_context, &loader.hooks);
         if let Some(name) = material.name() {
             named_materials.insert(name.to_string(), handle.clone());
         }
@@ -178,22 +247,29 @@ async fn load_gltf<'a, 'b>(
                     return Err(GltfError::MissingAnimationSampler(animation.index()));
                 };
 
+                // Morph target weights are animated with a property curve for each target instead
+                // of transform keyframes
+                let mut morph_weight_curves = Vec::new();
                 let keyframes = if let Some(outputs) = reader.read_outputs() {
                     match outputs {
-                        gltf::animation::util::ReadOutputs::Translations(tr) => {
-                            bevy_animation::Keyframes::Translation(tr.map(Vec3::from).collect())
-                        }
+                        gltf::animation::util::ReadOutputs::Translations(tr) => Some(
+                            bevy_animation::Keyframes::Translation(tr.map(Vec3::from).collect()),
+                        ),
                         gltf::animation::util::ReadOutputs::Rotations(rots) => {
-                            bevy_animation::Keyframes::Rotation(
+                            Some(bevy_animation::Keyframes::Rotation(
                                 rots.into_f32().map(bevy_math::Quat::from_array).collect(),
-                            )
-                        }
-                        gltf::animation::util::ReadOutputs::Scales(scale) => {
-                            bevy_animation::Keyframes::Scale(scale.map(Vec3::from).collect())
+                            ))
                         }
-                        gltf::animation::util::ReadOutputs::MorphTargetWeights(_) => {
-                            warn!("Morph animation property not yet supported");
-                            continue;
+                        gltf::animation::util::ReadOutputs::Scales(scale) => Some(
+                            bevy_animation::Keyframes::Scale(scale.map(Vec3::from).collect()),
+                        ),
+                        gltf::animation::util::ReadOutputs::MorphTargetWeights(weights) => {
+                            let weights: Vec<f32> = weights.into_f32().collect();
+                            morph_weight_curves = bevy_animation::MorphWeights::curves(
+                                &keyframe_timestamps,
+                                &weights,
+                            );
+                            None
                         }
                     }
                 } else {
@@ -203,15 +279,22 @@ async fn load_gltf<'a, 'b>(
 
                 if let Some((root_index, path)) = paths.get(&node.index()) {
                     animation_roots.insert(root_index);
-                    animation_clip.add_curve_to_path(
-                        bevy_animation::EntityPath {
-                            parts: path.clone(),
-                        },
-                        bevy_animation::VariableCurve {
-                            keyframe_timestamps,
-                            keyframes,
-                        },
-                    );
+                    let path = bevy_animation::EntityPath {
+                        parts: path.clone(),
+                    };
+                    if let Some(keyframes) = keyframes {
+                        animation_clip.add_curve_to_path(
+                            path,
+                            bevy_animation::VariableCurve {
+                                keyframe_timestamps,
+                                keyframes,
+                            },
+                        );
+                    } else {
+                        for curve in morph_weight_curves {
+                            animation_clip.add_property_curve_to_path(path.clone(), curve);
+                        }
+                    }
                 } else {
                     warn!(
                         "Animation ignored for node {}: part of its hierarchy is missing a name",
@@ -219,6 +302,9 @@ async fn load_gltf<'a, 'b>(
                     );
                 }
             }
+            if let Some(compression) = &loader.animation_compression {
+                animation_clip.compress(compression);
+            }
             let handle = load_context.set_labeled_asset(
                 &format!("Animation{}", animation.index()),
                 LoadedAsset::new(animation_clip),
@@ -237,28 +323,32 @@ async fn load_gltf<'a, 'b>(
         let mut primitives = vec![];
         for primitive in mesh.primitives() {
             let primitive_label = primitive_label(&mesh, &primitive);
+            let mesh_name = mesh.name();
             let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
             let primitive_topology = get_primitive_topology(primitive.mode())?;
 
             let mut mesh = Mesh::new(primitive_topology);
 
-            if let Some(vertex_attribute) = reader
-                .read_positions()
-                .map(|v| VertexAttributeValues::Float32x3(v.collect()))
+            if let Some(vertex_attribute) =
+                read_quantized_attribute(&primitive, &Semantic::Positions, &buffer_data)?
+                    .or_else(|| reader.read_positions().map(Iterator::collect))
+                    .map(VertexAttributeValues::Float32x3)
             {
                 mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertex_attribute);
             }
 
-            if let Some(vertex_attribute) = reader
-                .read_normals()
-                .map(|v| VertexAttributeValues::Float32x3(v.collect()))
+            if let Some(vertex_attribute) =
+                read_quantized_attribute(&primitive, &Semantic::Normals, &buffer_data)?
+                    .or_else(|| reader.read_normals().map(Iterator::collect))
+                    .map(VertexAttributeValues::Float32x3)
             {
                 mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vertex_attribute);
             }
 
-            if let Some(vertex_attribute) = reader
-                .read_tex_coords(0)
-                .map(|v| VertexAttributeValues::Float32x2(v.into_f32().collect()))
+            if let Some(vertex_attribute) =
+                read_quantized_attribute(&primitive, &Semantic::TexCoords(0), &buffer_data)?
+                    .or_else(|| reader.read_tex_coords(0).map(|v| v.into_f32().collect()))
+                    .map(VertexAttributeValues::Float32x2)
             {
                 mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vertex_attribute);
             }
@@ -282,6 +372,30 @@ async fn load_gltf<'a, 'b>(
                 mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vertex_attribute);
             }
 
+            for (semantic, accessor) in primitive.attributes() {
+                // gltf strips the leading underscore of custom attribute names
+                let name = match semantic {
+                    Semantic::Extras(name) => format!("_{name}"),
+                    _ => continue,
+                };
+                let attribute = match loader.custom_vertex_attributes.get(&name) {
+                    Some(attribute) => attribute,
+                    None => continue,
+                };
+                match read_custom_attribute(&accessor, attribute.format, &buffer_data) {
+                    Some(values) => mesh.insert_attribute(attribute.clone(), values),
+                    None => warn!(
+                        "Custom attribute {} of {} can't be loaded as {:?}: its accessor is {:?} {:?}{}",
+                        name,
+                        primitive_label,
+                        attribute.format,
+                        accessor.dimensions(),
+                        accessor.data_type(),
+                        if accessor.normalized() { " (normalized)" } else { "" },
+                    ),
+                }
+            }
+
             if let Some(indices) = reader.read_indices() {
                 mesh.set_indices(Some(Indices::U32(indices.into_u32().collect())));
             };
@@ -303,9 +417,10 @@ async fn load_gltf<'a, 'b>(
                 }
             }
 
-            if let Some(vertex_attribute) = reader
-                .read_tangents()
-                .map(|v| VertexAttributeValues::Float32x4(v.collect()))
+            if let Some(vertex_attribute) =
+                read_quantized_attribute(&primitive, &Semantic::Tangents, &buffer_data)?
+                    .or_else(|| reader.read_tangents().map(Iterator::collect))
+                    .map(VertexAttributeValues::Float32x4)
             {
                 mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vertex_attribute);
             } else if mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some()
@@ -322,9 +437,30 @@ async fn load_gltf<'a, 'b>(
                 }
             }
 
+            loader.hooks.process_mesh(
+                &GltfHookContext::new(&primitive_label, mesh_name, load_context),
+                &mut mesh,
+            );
+            let mut lods = Vec::new();
+            if matches!(mesh.primitive_topology(), PrimitiveTopology::TriangleList)
+                && mesh
+                    .attribute(Mesh::ATTRIBUTE_POSITION)
+                    .and_then(VertexAttributeValues::as_float3)
+                    .is_some()
+            {
+                for (lod, ratio) in loader.lod_ratios.iter().enumerate() {
+                    let mut lod_mesh = mesh.clone();
+                    lod_mesh.simplify(*ratio);
+                    lods.push(load_context.set_labeled_asset(
+                        &format!("{}/Lod{}", primitive_label, lod + 1),
+                        LoadedAsset::new(lod_mesh),
+                    ));
+                }
+            }
             let mesh = load_context.set_labeled_asset(&primitive_label, LoadedAsset::new(mesh));
             primitives.push(super::GltfPrimitive {
                 mesh,
+                lods,
                 material: primitive
                     .material()
                     .index()
@@ -455,6 +591,15 @@ async fn load_gltf<'a, 'b>(
         })
         .collect();
 
+    let variants = gltf
+        .variants()
+        .map(|variants| {
+            variants
+                .map(|variant| variant.name().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
     let mut scenes = vec![];
     let mut named_scenes = HashMap::default();
     let mut active_camera_found = false;
@@ -475,6 +620,8 @@ async fn load_gltf<'a, 'b>(
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
+                        &variants,
+                        &loader.hooks,
                     );
                     if result.is_err() {
                         err = Some(result);
@@ -513,8 +660,13 @@ async fn load_gltf<'a, 'b>(
             });
         }
 
-        let scene_handle = load_context
-            .set_labeled_asset(&scene_label(&scene), LoadedAsset::new(Scene::new(world)));
+        let scene_label = scene_label(&scene);
+        loader.hooks.process_scene(
+            &GltfHookContext::new(&scene_label, scene.name(), load_context),
+            &mut world,
+        );
+        let scene_handle =
+            load_context.set_labeled_asset(&scene_label, LoadedAsset::new(Scene::new(world)));
 
         if let Some(name) = scene.name() {
             named_scenes.insert(name.to_string(), scene_handle.clone());
@@ -535,6 +687,7 @@ async fn load_gltf<'a, 'b>(
         named_materials,
         nodes,
         named_nodes,
+        variants,
         #[cfg(feature = "bevy_animation")]
         animations,
         #[cfg(feature = "bevy_animation")]
@@ -620,7 +773,11 @@ async fn load_texture<'a>(
 }
 
 /// Loads a glTF material as a bevy [`StandardMaterial`] and returns it.
-fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<StandardMaterial> {
+fn load_material(
+    material: &Material,
+    load_context: &mut LoadContext,
+    hooks: &GltfHooks,
+) -> Handle<StandardMaterial> {
     let material_label = material_label(material);
 
     let pbr = material.pbr_metallic_roughness();
@@ -657,7 +814,11 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         load_context.get_handle(path)
     });
 
-    let emissive = material.emissive_factor();
+    // the emissive factor is limited to 1, `KHR_materials_emissive_strength` scales it for HDR
+    let emissive_strength = material.emissive_strength().unwrap_or(1.0);
+    let emissive = material
+        .emissive_factor()
+        .map(|factor| factor * emissive_strength);
     let emissive_texture = material.emissive_texture().map(|info| {
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
@@ -666,29 +827,55 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         load_context.get_handle(path)
     });
 
-    load_context.set_labeled_asset(
-        &material_label,
-        LoadedAsset::new(StandardMaterial {
-            base_color: Color::rgba_linear(color[0], color[1], color[2], color[3]),
-            base_color_texture,
-            perceptual_roughness: pbr.roughness_factor(),
-            metallic: pbr.metallic_factor(),
-            metallic_roughness_texture,
-            normal_map_texture,
-            double_sided: material.double_sided(),
-            cull_mode: if material.double_sided() {
-                None
-            } else {
-                Some(Face::Back)
-            },
-            occlusion_texture,
-            emissive: Color::rgb_linear(emissive[0], emissive[1], emissive[2]),
-            emissive_texture,
-            unlit: material.unlit(),
-            alpha_mode: alpha_mode(material),
-            ..Default::default()
-        }),
-    )
+    if material.transmission().is_some() {
+        warn!(
+            "Transmission of glTF material {} is not supported and will be ignored",
+            material_label
+        );
+    }
+    if material.volume().is_some() {
+        warn!(
+            "Volume of glTF material {} is not supported and will be ignored",
+            material_label
+        );
+    }
+    if let Some(specular) = material.specular() {
+        // the specular factors are part of the reflectance, but not the textures
+        if specular.specular_texture().is_some() || specular.specular_color_texture().is_some() {
+            warn!(
+                "Specular textures of glTF material {} are not supported and will be ignored",
+                material_label
+            );
+        }
+    }
+
+    let mut standard_material = StandardMaterial {
+        base_color: Color::rgba_linear(color[0], color[1], color[2], color[3]),
+        base_color_texture,
+        perceptual_roughness: pbr.roughness_factor(),
+        metallic: pbr.metallic_factor(),
+        metallic_roughness_texture,
+        normal_map_texture,
+        double_sided: material.double_sided(),
+        cull_mode: if material.double_sided() {
+            None
+        } else {
+            Some(Face::Back)
+        },
+        occlusion_texture,
+        emissive: Color::rgb_linear(emissive[0], emissive[1], emissive[2]),
+        emissive_texture,
+        reflectance: reflectance(material),
+        unlit: material.unlit(),
+        alpha_mode: alpha_mode(material),
+        ..Default::default()
+    };
+    hooks.process_material(
+        &GltfHookContext::new(&material_label, material.name(), load_context),
+        &mut standard_material,
+    );
+
+    load_context.set_labeled_asset(&material_label, LoadedAsset::new(standard_material))
 }
 
 /// Loads a glTF node.
@@ -699,6 +886,8 @@ fn load_node(
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
     active_camera_found: &mut bool,
+    variants: &[String],
+    hooks: &GltfHooks,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -760,6 +949,25 @@ fn load_node(
         *active_camera_found = true;
     }
 
+    // Add the weights of the morph targets, animated by the weight channels of the animations
+    #[cfg(feature = "bevy_animation")]
+    if let Some(mesh) = gltf_node.mesh() {
+        let target_count = mesh
+            .primitives()
+            .map(|primitive| primitive.morph_targets().count())
+            .max()
+            .unwrap_or(0);
+        if target_count > 0 {
+            let mut weights = gltf_node
+                .weights()
+                .or_else(|| mesh.weights())
+                .map(<[f32]>::to_vec)
+                .unwrap_or_default();
+            weights.resize(target_count, 0.0);
+            node.insert(bevy_animation::MorphWeights::new(weights));
+        }
+    }
+
     // Map node index to entity
     node_index_to_entity_map.insert(gltf_node.index(), node.id());
 
@@ -774,11 +982,11 @@ fn load_node(
                 // added when iterating over all the gltf materials (since the default material is
                 // not explicitly listed in the gltf).
                 if !load_context.has_labeled_asset(&material_label) {
-                    load_material(&material, load_context);
+                    load_material(&material, load_context, hooks);
                 }
 
                 let primitive_label = primitive_label(&mesh, &primitive);
-                let bounds = primitive.bounding_box();
+                let bounds = primitive_bounds(&primitive);
                 let mesh_asset_path =
                     AssetPath::new_ref(load_context.path(), Some(&primitive_label));
                 let material_asset_path =
@@ -786,7 +994,7 @@ fn load_node(
 
                 let mut mesh_entity = parent.spawn(PbrBundle {
                     mesh: load_context.get_handle(mesh_asset_path),
-                    material: load_context.get_handle(material_asset_path),
+                    material: load_context.get_handle(material_asset_path.clone()),
                     ..Default::default()
                 });
                 mesh_entity.insert(Aabb::from_min_max(
@@ -794,6 +1002,26 @@ fn load_node(
                     Vec3::from_slice(&bounds.max),
                 ));
 
+                let mut material_variants = HashMap::default();
+                for mapping in primitive.mappings() {
+                    let variant_material_label = material_label(&mapping.material());
+                    let variant_material = load_context.get_handle(AssetPath::new_ref(
+                        load_context.path(),
+                        Some(&variant_material_label),
+                    ));
+                    for &variant in mapping.variants() {
+                        if let Some(name) = variants.get(variant as usize) {
+                            material_variants.insert(name.clone(), variant_material.clone());
+                        }
+                    }
+                }
+                if !material_variants.is_empty() {
+                    mesh_entity.insert(super::GltfMaterialVariants {
+                        default: load_context.get_handle(material_asset_path),
+                        variants: material_variants,
+                    });
+                }
+
                 if let Some(extras) = primitive.extras() {
                     mesh_entity.insert(super::GltfExtras {
                         value: extras.get().to_string(),
@@ -894,6 +1122,8 @@ fn load_node(
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
+                variants,
+                hooks,
             ) {
                 gltf_error = Some(err);
                 return;
@@ -1019,9 +1249,300 @@ fn alpha_mode(material: &Material) -> AlphaMode {
     }
 }
 
+/// Reads a vertex attribute stored with one of the integer component types allowed by
+/// `KHR_mesh_quantization`, converting it to floats.
+///
+/// Returns `None` for attributes stored as floats, which are read by the `gltf` crate.
+fn read_quantized_attribute<const N: usize>(
+    primitive: &Primitive,
+    semantic: &Semantic,
+    buffer_data: &[Vec<u8>],
+) -> Result<Option<Vec<[f32; N]>>, GltfError> {
+    let accessor = match primitive.get(semantic) {
+        Some(accessor) => accessor,
+        None => return Ok(None),
+    };
+    let data_type = accessor.data_type();
+    if data_type == DataType::F32 {
+        return Ok(None);
+    }
+    let normalized = accessor.normalized();
+    read_components(&accessor, buffer_data, |bytes| {
+        component_to_f32(data_type, bytes, normalized)
+    })
+    .map(Some)
+    .ok_or_else(|| GltfError::InvalidAccessor(accessor.index()))
+}
+
+/// Reads the values of `accessor` with `N` components each, converting each component with `read`.
+///
+/// Values replaced by a sparse accessor are read from its sparse storage, and accessors without a
+/// buffer view start out as zeros. Returns `None` if the accessor doesn't have `N` components or
+/// its data or sparse indices are out of bounds.
+fn read_components<T: Copy + Default, const N: usize>(
+    accessor: &Accessor,
+    buffer_data: &[Vec<u8>],
+    read: impl Fn(&[u8]) -> T,
+) -> Option<Vec<[T; N]>> {
+    if accessor.dimensions().multiplicity() != N {
+        return None;
+    }
+    let component_size = accessor.data_type().size();
+    let read_value = |data: &[u8], start: usize| {
+        let mut value = [T::default(); N];
+        for (component, value) in value.iter_mut().enumerate() {
+            let start = start + component * component_size;
+            *value = read(data.get(start..start + component_size)?);
+        }
+        Some(value)
+    };
+
+    let mut values = match accessor.view() {
+        Some(view) => {
+            let stride = view.stride().unwrap_or(component_size * N);
+            let offset = view.offset() + accessor.offset();
+            let data = &buffer_data[view.buffer().index()];
+            (0..accessor.count())
+                .map(|index| read_value(data, offset + index * stride))
+                .collect::<Option<Vec<_>>>()?
+        }
+        None => vec![[T::default(); N]; accessor.count()],
+    };
+
+    if let Some(sparse) = accessor.sparse() {
+        let indices = sparse.indices();
+        let index_type = match indices.index_type() {
+            IndexType::U8 => DataType::U8,
+            IndexType::U16 => DataType::U16,
+            IndexType::U32 => DataType::U32,
+        };
+        let index_size = index_type.size();
+        let indices_view = indices.view();
+        let indices_offset = indices_view.offset() + indices.offset() as usize;
+        let indices_data = &buffer_data[indices_view.buffer().index()];
+        // Sparse values are tightly packed
+        let sparse_values = sparse.values();
+        let values_view = sparse_values.view();
+        let values_offset = values_view.offset() + sparse_values.offset() as usize;
+        let values_data = &buffer_data[values_view.buffer().index()];
+        for i in 0..sparse.count() as usize {
+            let start = indices_offset + i * index_size;
+            let index = component_to_i64(index_type, indices_data.get(start..start + index_size)?);
+            *values.get_mut(index as usize)? =
+                read_value(values_data, values_offset + i * component_size * N)?;
+        }
+    }
+
+    Some(values)
+}
+
+/// Reads a custom vertex attribute as `format`.
+///
+/// Float formats accept any component type, which is normalized if the accessor is. Integer
+/// formats need integer components, and normalized formats need components of the same type that
+/// are normalized. Returns `None` if the accessor can't be read as `format`.
+fn read_custom_attribute(
+    accessor: &Accessor,
+    format: VertexFormat,
+    buffer_data: &[Vec<u8>],
+) -> Option<VertexAttributeValues> {
+    use VertexAttributeValues as Values;
+
+    fn scalars<T>(values: Vec<[T; 1]>) -> Vec<T> {
+        values.into_iter().map(|[value]| value).collect()
+    }
+
+    let data_type = accessor.data_type();
+    let normalized = accessor.normalized();
+    let float = |bytes: &[u8]| component_to_f32(data_type, bytes, normalized);
+    let int = |bytes: &[u8]| component_to_i64(data_type, bytes);
+    let values = match format {
+        VertexFormat::Float32 => {
+            Values::Float32(scalars(read_components(accessor, buffer_data, float)?))
+        }
+        VertexFormat::Float32x2 => {
+            Values::Float32x2(read_components(accessor, buffer_data, float)?)
+        }
+        VertexFormat::Float32x3 => {
+            Values::Float32x3(read_components(accessor, buffer_data, float)?)
+        }
+        VertexFormat::Float32x4 => {
+            Values::Float32x4(read_components(accessor, buffer_data, float)?)
+        }
+        _ if data_type == DataType::F32 => return None,
+        VertexFormat::Unorm16x2 | VertexFormat::Unorm16x4
+            if !normalized || data_type != DataType::U16 =>
+        {
+            return None
+        }
+        VertexFormat::Snorm16x2 | VertexFormat::Snorm16x4
+            if !normalized || data_type != DataType::I16 =>
+        {
+            return None
+        }
+        VertexFormat::Unorm8x2 | VertexFormat::Unorm8x4
+            if !normalized || data_type != DataType::U8 =>
+        {
+            return None
+        }
+        VertexFormat::Snorm8x2 | VertexFormat::Snorm8x4
+            if !normalized || data_type != DataType::I8 =>
+        {
+            return None
+        }
+        VertexFormat::Unorm16x2 => {
+            Values::Unorm16x2(read_components(accessor, buffer_data, |b| int(b) as u16)?)
+        }
+        VertexFormat::Unorm16x4 => {
+            Values::Unorm16x4(read_components(accessor, buffer_data, |b| int(b) as u16)?)
+        }
+        VertexFormat::Snorm16x2 => {
+            Values::Snorm16x2(read_components(accessor, buffer_data, |b| int(b) as i16)?)
+        }
+        VertexFormat::Snorm16x4 => {
+            Values::Snorm16x4(read_components(accessor, buffer_data, |b| int(b) as i16)?)
+        }
+        VertexFormat::Unorm8x2 => {
+            Values::Unorm8x2(read_components(accessor, buffer_data, |b| int(b) as u8)?)
+        }
+        VertexFormat::Unorm8x4 => {
+            Values::Unorm8x4(read_components(accessor, buffer_data, |b| int(b) as u8)?)
+        }
+        VertexFormat::Snorm8x2 => {
+            Values::Snorm8x2(read_components(accessor, buffer_data, |b| int(b) as i8)?)
+        }
+        VertexFormat::Snorm8x4 => {
+            Values::Snorm8x4(read_components(accessor, buffer_data, |b| int(b) as i8)?)
+        }
+        _ if normalized => return None,
+        VertexFormat::Uint32 => {
+            Values::Uint32(scalars(read_components(accessor, buffer_data, |b| {
+                int(b) as u32
+            })?))
+        }
+        VertexFormat::Uint32x2 => {
+            Values::Uint32x2(read_components(accessor, buffer_data, |b| int(b) as u32)?)
+        }
+        VertexFormat::Uint32x3 => {
+            Values::Uint32x3(read_components(accessor, buffer_data, |b| int(b) as u32)?)
+        }
+        VertexFormat::Uint32x4 => {
+            Values::Uint32x4(read_components(accessor, buffer_data, |b| int(b) as u32)?)
+        }
+        VertexFormat::Sint32 => {
+            Values::Sint32(scalars(read_components(accessor, buffer_data, |b| {
+                int(b) as i32
+            })?))
+        }
+        VertexFormat::Sint32x2 => {
+            Values::Sint32x2(read_components(accessor, buffer_data, |b| int(b) as i32)?)
+        }
+        VertexFormat::Sint32x3 => {
+            Values::Sint32x3(read_components(accessor, buffer_data, |b| int(b) as i32)?)
+        }
+        VertexFormat::Sint32x4 => {
+            Values::Sint32x4(read_components(accessor, buffer_data, |b| int(b) as i32)?)
+        }
+        VertexFormat::Uint16x2 => {
+            Values::Uint16x2(read_components(accessor, buffer_data, |b| int(b) as u16)?)
+        }
+        VertexFormat::Uint16x4 => {
+            Values::Uint16x4(read_components(accessor, buffer_data, |b| int(b) as u16)?)
+        }
+        VertexFormat::Sint16x2 => {
+            Values::Sint16x2(read_components(accessor, buffer_data, |b| int(b) as i16)?)
+        }
+        VertexFormat::Sint16x4 => {
+            Values::Sint16x4(read_components(accessor, buffer_data, |b| int(b) as i16)?)
+        }
+        VertexFormat::Uint8x2 => {
+            Values::Uint8x2(read_components(accessor, buffer_data, |b| int(b) as u8)?)
+        }
+        VertexFormat::Uint8x4 => {
+            Values::Uint8x4(read_components(accessor, buffer_data, |b| int(b) as u8)?)
+        }
+        VertexFormat::Sint8x2 => {
+            Values::Sint8x2(read_components(accessor, buffer_data, |b| int(b) as i8)?)
+        }
+        VertexFormat::Sint8x4 => {
+            Values::Sint8x4(read_components(accessor, buffer_data, |b| int(b) as i8)?)
+        }
+        _ => return None,
+    };
+    Some(values)
+}
+
+fn component_to_i64(data_type: DataType, bytes: &[u8]) -> i64 {
+    match data_type {
+        DataType::I8 => bytes[0] as i8 as i64,
+        DataType::U8 => bytes[0] as i64,
+        DataType::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        DataType::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        DataType::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64,
+        DataType::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64,
+    }
+}
+
+fn component_to_f32(data_type: DataType, bytes: &[u8], normalized: bool) -> f32 {
+    let value = match data_type {
+        DataType::I8 => bytes[0] as i8 as f32,
+        DataType::U8 => bytes[0] as f32,
+        DataType::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+        DataType::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+        DataType::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+        DataType::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+    };
+    if normalized {
+        normalize_component(data_type, value)
+    } else {
+        value
+    }
+}
+
+fn normalize_component(data_type: DataType, value: f32) -> f32 {
+    match data_type {
+        DataType::I8 => (value / 127.0).max(-1.0),
+        DataType::U8 => value / 255.0,
+        DataType::I16 => (value / 32767.0).max(-1.0),
+        DataType::U16 => value / 65535.0,
+        DataType::U32 | DataType::F32 => value,
+    }
+}
+
+/// Returns the bounding box of the positions of `primitive`.
+fn primitive_bounds(primitive: &Primitive) -> BoundingBox {
+    let mut bounds = primitive.bounding_box();
+    // the bounds of normalized positions are stored before being normalized
+    if let Some(positions) = primitive
+        .get(&Semantic::Positions)
+        .filter(|positions| positions.normalized())
+    {
+        for value in bounds.min.iter_mut().chain(&mut bounds.max) {
+            *value = normalize_component(positions.data_type(), *value);
+        }
+    }
+    bounds
+}
+
+/// Returns the [`StandardMaterial::reflectance`] matching the index of refraction and specular
+/// extensions of a glTF material.
+fn reflectance(material: &Material) -> f32 {
+    // reflectance at normal incidence, from the index of refraction (1.5 by default)
+    let ior = material.ior().unwrap_or(1.5);
+    let mut f0 = ((ior - 1.0) / (ior + 1.0)).powi(2);
+    if let Some(specular) = material.specular() {
+        let [r, g, b] = specular.specular_color_factor();
+        f0 *= specular.specular_factor() * r.max(g).max(b);
+    }
+    // `StandardMaterial` maps its reflectance to a reflectance at normal incidence of
+    // `0.16 * reflectance^2`
+    (f0 / 0.16).sqrt().min(1.0)
+}
+
 /// Loads the raw glTF buffer data for a specific glTF file.
 async fn load_buffers(
     gltf: &gltf::Gltf,
+    compressed_buffer_views: Option<&CompressedBufferViews>,
     load_context: &LoadContext<'_>,
     asset_path: &Path,
 ) -> Result<Vec<Vec<u8>>, GltfError> {
@@ -1029,6 +1550,11 @@ async fn load_buffers(
 
     let mut buffer_data = Vec::new();
     for buffer in gltf.buffers() {
+        // buffers receiving decompressed data are filled once all buffers are loaded
+        if compressed_buffer_views.map_or(false, |views| views.is_fallback_buffer(buffer.index())) {
+            buffer_data.push(vec![0; buffer.length()]);
+            continue;
+        }
         match buffer.source() {
             gltf::buffer::Source::Uri(uri) => {
                 let uri = percent_encoding::percent_decode_str(uri)
@@ -1157,8 +1683,9 @@ impl<'a> DataUri<'a> {
 mod test {
     use std::path::PathBuf;
 
-    use super::resolve_node_hierarchy;
+    use super::{component_to_i64, read_components, resolve_node_hierarchy};
     use crate::GltfNode;
+    use gltf::accessor::DataType;
 
     impl GltfNode {
         fn empty() -> Self {
@@ -1274,4 +1801,67 @@ mod test {
         assert_eq!(result[0].0, "l2");
         assert_eq!(result[0].1.children.len(), 0);
     }
+
+    #[test]
+    fn read_sparse_components() {
+        // Three u16 pairs, the index 2 as a u8 padded to 4 bytes, then the u16 pair replacing it
+        let buffer: Vec<u8> = [1u16, 2, 3, 4, 5, 6]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .chain([2, 0, 0, 0])
+            .chain([7u16, 8].iter().flat_map(|value| value.to_le_bytes()))
+            .collect();
+        let gltf = gltf::Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 20 }],
+                "bufferViews": [
+                    { "buffer": 0, "byteOffset": 0, "byteLength": 12 },
+                    { "buffer": 0, "byteOffset": 12, "byteLength": 1 },
+                    { "buffer": 0, "byteOffset": 16, "byteLength": 4 }
+                ],
+                "accessors": [
+                    {
+                        "bufferView": 0, "componentType": 5123, "count": 3, "type": "VEC2",
+                        "sparse": {
+                            "count": 1,
+                            "indices": { "bufferView": 1, "componentType": 5121 },
+                            "values": { "bufferView": 2 }
+                        }
+                    },
+                    {
+                        "componentType": 5123, "count": 3, "type": "VEC2",
+                        "sparse": {
+                            "count": 1,
+                            "indices": { "bufferView": 1, "componentType": 5121 },
+                            "values": { "bufferView": 2 }
+                        }
+                    },
+                    {
+                        "componentType": 5123, "count": 2, "type": "VEC2",
+                        "sparse": {
+                            "count": 1,
+                            "indices": { "bufferView": 1, "componentType": 5121 },
+                            "values": { "bufferView": 2 }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let buffer_data = vec![buffer];
+        let read = |bytes: &[u8]| component_to_i64(DataType::U16, bytes);
+        let accessors: Vec<_> = gltf.accessors().collect();
+
+        let values: Option<Vec<[i64; 2]>> = read_components(&accessors[0], &buffer_data, read);
+        assert_eq!(values, Some(vec![[1, 2], [3, 4], [7, 8]]));
+
+        // Accessors without a buffer view are zeros apart from their sparse values
+        let values: Option<Vec<[i64; 2]>> = read_components(&accessors[1], &buffer_data, read);
+        assert_eq!(values, Some(vec![[0, 0], [0, 0], [7, 8]]));
+
+        // The sparse index 2 is out of bounds of an accessor of 2 values
+        let values: Option<Vec<[i64; 2]>> = read_components(&accessors[2], &buffer_data, read);
+        assert_eq!(values, None);
+    }
 }
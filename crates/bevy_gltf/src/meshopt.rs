@@ -0,0 +1,623 @@
+//! Decoding of buffer views compressed with the `EXT_meshopt_compression` extension.
+//!
+//! See <https://github.com/KhronosGroup/glTF/tree/main/extensions/2.0/Vendor/EXT_meshopt_compression>
+//! for the specification of the bitstreams.
+
+use serde::Deserialize;
+
+use crate::GltfError;
+
+/// The name of the extension.
+pub(crate) const EXTENSION_NAME: &str = "EXT_meshopt_compression";
+
+/// The parts of the glTF JSON used by the extension, which the `gltf` crate doesn't expose.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Root {
+    #[serde(default)]
+    buffers: Vec<Buffer>,
+    #[serde(default)]
+    buffer_views: Vec<BufferView>,
+}
+
+#[derive(Deserialize)]
+struct Buffer {
+    #[serde(default)]
+    extensions: BufferExtensions,
+}
+
+#[derive(Deserialize, Default)]
+struct BufferExtensions {
+    #[serde(rename = "EXT_meshopt_compression")]
+    meshopt: Option<FallbackBuffer>,
+}
+
+#[derive(Deserialize)]
+struct FallbackBuffer {
+    #[serde(default)]
+    fallback: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BufferView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    #[serde(default)]
+    extensions: BufferViewExtensions,
+}
+
+#[derive(Deserialize, Default)]
+struct BufferViewExtensions {
+    #[serde(rename = "EXT_meshopt_compression")]
+    meshopt: Option<CompressedBufferView>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompressedBufferView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: usize,
+    count: usize,
+    mode: Mode,
+    #[serde(default)]
+    filter: Filter,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Mode {
+    Attributes,
+    Triangles,
+    Indices,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Filter {
+    #[default]
+    None,
+    Octahedral,
+    Quaternion,
+    Exponential,
+}
+
+/// The compressed buffer views of a glTF file.
+pub(crate) struct CompressedBufferViews {
+    root: Root,
+}
+
+impl CompressedBufferViews {
+    /// Reads the compressed buffer views from the JSON of a glTF file.
+    pub(crate) fn from_json(json: &[u8]) -> Result<Self, GltfError> {
+        let root = serde_json::from_slice(json).map_err(|_| GltfError::MeshoptDecode)?;
+        Ok(Self { root })
+    }
+
+    /// Returns `true` if the buffer at `index` only exists to receive decompressed data, in which
+    /// case it may not have any data of its own.
+    pub(crate) fn is_fallback_buffer(&self, index: usize) -> bool {
+        self.root
+            .buffers
+            .get(index)
+            .and_then(|buffer| buffer.extensions.meshopt.as_ref())
+            .map_or(false, |meshopt| meshopt.fallback)
+    }
+
+    /// Decompresses every compressed buffer view into the buffer it points to.
+    pub(crate) fn decode(&self, buffer_data: &mut [Vec<u8>]) -> Result<(), GltfError> {
+        for view in &self.root.buffer_views {
+            let compressed = match &view.extensions.meshopt {
+                Some(compressed) => compressed,
+                None => continue,
+            };
+            let source = buffer_data
+                .get(compressed.buffer)
+                .and_then(|buffer| {
+                    buffer.get(
+                        compressed.byte_offset..compressed.byte_offset + compressed.byte_length,
+                    )
+                })
+                .ok_or(GltfError::MeshoptDecode)?;
+            let decoded = decode_buffer_view(compressed, source).ok_or(GltfError::MeshoptDecode)?;
+            buffer_data
+                .get_mut(view.buffer)
+                .and_then(|buffer| {
+                    buffer.get_mut(view.byte_offset..view.byte_offset + decoded.len())
+                })
+                .ok_or(GltfError::MeshoptDecode)?
+                .copy_from_slice(&decoded);
+        }
+        Ok(())
+    }
+}
+
+fn decode_buffer_view(view: &CompressedBufferView, data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = match view.mode {
+        Mode::Attributes => decode_vertex_buffer(data, view.count, view.byte_stride)?,
+        Mode::Triangles => decode_index_buffer(data, view.count, view.byte_stride)?,
+        Mode::Indices => decode_index_sequence(data, view.count, view.byte_stride)?,
+    };
+    match view.filter {
+        Filter::None => {}
+        Filter::Octahedral if view.mode == Mode::Attributes => {
+            decode_octahedral_filter(&mut decoded, view.byte_stride)?;
+        }
+        Filter::Quaternion if view.mode == Mode::Attributes && view.byte_stride == 8 => {
+            decode_quaternion_filter(&mut decoded);
+        }
+        Filter::Exponential if view.mode == Mode::Attributes && view.byte_stride % 4 == 0 => {
+            decode_exponential_filter(&mut decoded);
+        }
+        _ => return None,
+    }
+    Some(decoded)
+}
+
+const VERTEX_HEADER: u8 = 0xa0;
+const VERTEX_BLOCK_SIZE_BYTES: usize = 8192;
+const VERTEX_BLOCK_MAX_SIZE: usize = 256;
+const BYTE_GROUP_SIZE: usize = 16;
+const BYTE_GROUP_DECODE_LIMIT: usize = 24;
+const TAIL_MAX_SIZE: usize = 32;
+
+fn unzigzag8(value: u8) -> u8 {
+    (value >> 1) ^ 0u8.wrapping_sub(value & 1)
+}
+
+/// Decodes `count` vertices of `stride` bytes encoded with the `ATTRIBUTES` mode.
+fn decode_vertex_buffer(data: &[u8], count: usize, stride: usize) -> Option<Vec<u8>> {
+    if stride == 0 || stride > 256 || stride % 4 != 0 || data.len() < 1 + stride {
+        return None;
+    }
+    let (&header, mut data) = data.split_first()?;
+    if header != VERTEX_HEADER {
+        return None;
+    }
+
+    let tail_size = stride.max(TAIL_MAX_SIZE);
+    let mut last_vertex = data[data.len() - stride..].to_vec();
+    let block_size =
+        ((VERTEX_BLOCK_SIZE_BYTES / stride) & !(BYTE_GROUP_SIZE - 1)).min(VERTEX_BLOCK_MAX_SIZE);
+
+    let mut decoded = vec![0; count * stride];
+    let mut bytes = [0; VERTEX_BLOCK_MAX_SIZE];
+    for block in decoded.chunks_mut(block_size * stride) {
+        let block_count = block.len() / stride;
+        let aligned_count = (block_count + BYTE_GROUP_SIZE - 1) & !(BYTE_GROUP_SIZE - 1);
+        for byte in 0..stride {
+            data = decode_bytes(data, &mut bytes[..aligned_count])?;
+            let mut previous = last_vertex[byte];
+            for (vertex, &delta) in bytes[..block_count].iter().enumerate() {
+                previous = unzigzag8(delta).wrapping_add(previous);
+                block[vertex * stride + byte] = previous;
+            }
+        }
+        last_vertex.copy_from_slice(&block[(block_count - 1) * stride..]);
+    }
+
+    (data.len() == tail_size).then_some(decoded)
+}
+
+fn decode_bytes<'a>(data: &'a [u8], buffer: &mut [u8]) -> Option<&'a [u8]> {
+    // 2 bits per group of bytes select how the group is encoded
+    let header_size = (buffer.len() / BYTE_GROUP_SIZE + 3) / 4;
+    let (header, mut data) = (data.get(..header_size)?, &data[header_size..]);
+    for (group_index, group) in buffer.chunks_mut(BYTE_GROUP_SIZE).enumerate() {
+        if data.len() < BYTE_GROUP_DECODE_LIMIT {
+            return None;
+        }
+        let bits_log2 = (header[group_index / 4] >> ((group_index % 4) * 2)) & 3;
+        data = decode_bytes_group(data, group, bits_log2);
+    }
+    Some(data)
+}
+
+fn decode_bytes_group<'a>(data: &'a [u8], group: &mut [u8], bits_log2: u8) -> &'a [u8] {
+    match bits_log2 {
+        0 => {
+            group.fill(0);
+            data
+        }
+        3 => {
+            group.copy_from_slice(&data[..BYTE_GROUP_SIZE]);
+            &data[BYTE_GROUP_SIZE..]
+        }
+        _ => {
+            // values are packed in 2 or 4 bits, and values with all bits set are read from the
+            // bytes following the packed values instead
+            let bits = 1 << bits_log2;
+            let packed_size = BYTE_GROUP_SIZE * bits / 8;
+            let (packed, mut extra) = data.split_at(packed_size);
+            let max = (1 << bits) - 1;
+            for (index, value) in group.iter_mut().enumerate() {
+                let bit_offset = index * bits;
+                let encoded = (packed[bit_offset / 8] >> (8 - bits - bit_offset % 8)) & max;
+                if encoded == max {
+                    *value = extra[0];
+                    extra = &extra[1..];
+                } else {
+                    *value = encoded;
+                }
+            }
+            extra
+        }
+    }
+}
+
+const INDEX_HEADER: u8 = 0xe0;
+const SEQUENCE_HEADER: u8 = 0xd0;
+
+fn write_index(decoded: &mut [u8], position: usize, index_size: usize, index: u32) {
+    let bytes = index.to_le_bytes();
+    decoded[position * index_size..(position + 1) * index_size]
+        .copy_from_slice(&bytes[..index_size]);
+}
+
+fn decode_vbyte(data: &mut &[u8]) -> Option<u32> {
+    let (&lead, rest) = data.split_first()?;
+    *data = rest;
+    if lead < 128 {
+        return Some(lead as u32);
+    }
+    let mut result = (lead & 127) as u32;
+    let mut shift = 7;
+    for _ in 0..4 {
+        let (&group, rest) = data.split_first()?;
+        *data = rest;
+        result |= ((group & 127) as u32) << shift;
+        shift += 7;
+        if group < 128 {
+            break;
+        }
+    }
+    Some(result)
+}
+
+fn decode_index(data: &mut &[u8], last: u32) -> Option<u32> {
+    let value = decode_vbyte(data)?;
+    let delta = (value >> 1) ^ 0u32.wrapping_sub(value & 1);
+    Some(last.wrapping_add(delta))
+}
+
+/// Decodes `count` indices of `index_size` bytes encoded with the `TRIANGLES` mode.
+fn decode_index_buffer(buffer: &[u8], count: usize, index_size: usize) -> Option<Vec<u8>> {
+    if count % 3 != 0 || !(index_size == 2 || index_size == 4) {
+        return None;
+    }
+    // the smallest encoding has a header, a byte per triangle and a 16 bytes table
+    if buffer.len() < 1 + count / 3 + 16 || buffer[0] & 0xf0 != INDEX_HEADER {
+        return None;
+    }
+    let version = buffer[0] & 0x0f;
+    if version > 1 {
+        return None;
+    }
+
+    let mut edge_fifo = [[u32::MAX; 2]; 16];
+    let mut vertex_fifo = [u32::MAX; 16];
+    let mut edge_fifo_offset = 0usize;
+    let mut vertex_fifo_offset = 0usize;
+    let push_edge = |fifo: &mut [[u32; 2]; 16], offset: &mut usize, a: u32, b: u32| {
+        fifo[*offset] = [a, b];
+        *offset = (*offset + 1) & 15;
+    };
+    let vertex =
+        |fifo: &[u32; 16], offset: usize, index: usize| fifo[offset.wrapping_sub(index) & 15];
+    let push_vertex = |fifo: &mut [u32; 16], offset: &mut usize, index: u32, advance: bool| {
+        fifo[*offset] = index;
+        *offset = (*offset + advance as usize) & 15;
+    };
+
+    let mut next = 0u32;
+    let mut last = 0u32;
+    let fec_max = if version >= 1 { 13 } else { 15 };
+
+    let codes = &buffer[1..1 + count / 3];
+    let table_start = buffer.len() - 16;
+    let code_aux_table = &buffer[table_start..];
+    let mut data = &buffer[1 + count / 3..table_start];
+    let mut decoded = vec![0; count * index_size];
+
+    for (triangle, &code) in codes.iter().enumerate() {
+        let [a, b, c] = if code < 0xf0 {
+            // an edge from the edge fifo, and a new vertex or one from the vertex fifo
+            let fe = (code >> 4) as usize;
+            let [a, b] = edge_fifo[edge_fifo_offset.wrapping_sub(1 + fe) & 15];
+            let fec = (code & 15) as usize;
+            let c = if fec == 0 {
+                next += 1;
+                next - 1
+            } else if fec < fec_max {
+                vertex(&vertex_fifo, vertex_fifo_offset, 1 + fec)
+            } else {
+                // 13 and 14 encode the previous free index minus or plus one
+                last = match fec {
+                    13 => last.wrapping_sub(1),
+                    14 => last.wrapping_add(1),
+                    _ => decode_index(&mut data, last)?,
+                };
+                last
+            };
+            push_vertex(
+                &mut vertex_fifo,
+                &mut vertex_fifo_offset,
+                c,
+                fec == 0 || fec >= fec_max,
+            );
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, c, b);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, a, c);
+            [a, b, c]
+        } else {
+            // no edge from the edge fifo
+            let (code_aux, fea) = if code < 0xfe {
+                (code_aux_table[(code & 15) as usize], 0)
+            } else {
+                let (&code_aux, rest) = data.split_first()?;
+                data = rest;
+                if code_aux == 0 {
+                    next = 0;
+                }
+                (code_aux, if code == 0xfe { 0 } else { 15 })
+            };
+            let feb = (code_aux >> 4) as usize;
+            let fec = (code_aux & 15) as usize;
+            let mut read_vertex = |fe: usize| {
+                if fe == 0 {
+                    next += 1;
+                    Some(next - 1)
+                } else if fe < 15 {
+                    Some(vertex(&vertex_fifo, vertex_fifo_offset, fe))
+                } else {
+                    None
+                }
+            };
+            let a = read_vertex(fea);
+            let b = read_vertex(feb);
+            let c = read_vertex(fec);
+            // free indices are read after the others
+            let mut read_free = |index: Option<u32>| match index {
+                Some(index) => Some(index),
+                None => {
+                    last = decode_index(&mut data, last)?;
+                    Some(last)
+                }
+            };
+            let a = read_free(a)?;
+            let b = read_free(b)?;
+            let c = read_free(c)?;
+            push_vertex(&mut vertex_fifo, &mut vertex_fifo_offset, a, true);
+            push_vertex(
+                &mut vertex_fifo,
+                &mut vertex_fifo_offset,
+                b,
+                feb == 0 || feb == 15,
+            );
+            push_vertex(
+                &mut vertex_fifo,
+                &mut vertex_fifo_offset,
+                c,
+                fec == 0 || fec == 15,
+            );
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, b, a);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, c, b);
+            push_edge(&mut edge_fifo, &mut edge_fifo_offset, a, c);
+            [a, b, c]
+        };
+        write_index(&mut decoded, triangle * 3, index_size, a);
+        write_index(&mut decoded, triangle * 3 + 1, index_size, b);
+        write_index(&mut decoded, triangle * 3 + 2, index_size, c);
+    }
+
+    data.is_empty().then_some(decoded)
+}
+
+/// Decodes `count` indices of `index_size` bytes encoded with the `INDICES` mode.
+fn decode_index_sequence(buffer: &[u8], count: usize, index_size: usize) -> Option<Vec<u8>> {
+    if !(index_size == 2 || index_size == 4) {
+        return None;
+    }
+    // the smallest encoding has a header, a byte per index and a 4 bytes tail
+    if buffer.len() < 1 + count + 4 || buffer[0] & 0xf0 != SEQUENCE_HEADER {
+        return None;
+    }
+    if buffer[0] & 0x0f > 1 {
+        return None;
+    }
+
+    let mut data = &buffer[1..buffer.len() - 4];
+    let mut last = [0u32; 2];
+    let mut decoded = vec![0; count * index_size];
+    for position in 0..count {
+        let value = decode_vbyte(&mut data)?;
+        // the lowest bit selects which of the two previous indices the delta is relative to
+        let baseline = (value & 1) as usize;
+        let value = value >> 1;
+        let delta = (value >> 1) ^ 0u32.wrapping_sub(value & 1);
+        last[baseline] = last[baseline].wrapping_add(delta);
+        write_index(&mut decoded, position, index_size, last[baseline]);
+    }
+
+    data.is_empty().then_some(decoded)
+}
+
+fn round_to_int(value: f32) -> i32 {
+    (value + if value >= 0.0 { 0.5 } else { -0.5 }) as i32
+}
+
+/// Reconstructs unit vectors stored as octahedral coordinates in 4 signed 8 or 16 bits
+/// components.
+fn decode_octahedral_filter(data: &mut [u8], stride: usize) -> Option<()> {
+    match stride {
+        4 => {
+            for vector in data.chunks_exact_mut(4) {
+                let [x, y, z] = decode_octahedral(
+                    [
+                        vector[0] as i8 as f32,
+                        vector[1] as i8 as f32,
+                        vector[2] as i8 as f32,
+                    ],
+                    127.0,
+                );
+                vector[0] = x as i8 as u8;
+                vector[1] = y as i8 as u8;
+                vector[2] = z as i8 as u8;
+            }
+        }
+        8 => {
+            for vector in data.chunks_exact_mut(8) {
+                let component = |i: usize| i16::from_le_bytes([vector[i * 2], vector[i * 2 + 1]]);
+                let decoded = decode_octahedral(
+                    [
+                        component(0) as f32,
+                        component(1) as f32,
+                        component(2) as f32,
+                    ],
+                    32767.0,
+                );
+                for (i, value) in decoded.into_iter().enumerate() {
+                    vector[i * 2..i * 2 + 2].copy_from_slice(&(value as i16).to_le_bytes());
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn decode_octahedral([x, y, z]: [f32; 3], max: f32) -> [i32; 3] {
+    // the third component stores the value of 1.0
+    let z = z - x.abs() - y.abs();
+    // unfold the octahedron for the lower hemisphere
+    let t = z.min(0.0);
+    let x = x + if x >= 0.0 { t } else { -t };
+    let y = y + if y >= 0.0 { t } else { -t };
+    let scale = max / (x * x + y * y + z * z).sqrt();
+    [
+        round_to_int(x * scale),
+        round_to_int(y * scale),
+        round_to_int(z * scale),
+    ]
+}
+
+/// Reconstructs unit quaternions stored as 3 components and the index of the largest one in 4
+/// signed 16 bits components.
+fn decode_quaternion_filter(data: &mut [u8]) {
+    let scale = 1.0 / 2f32.sqrt();
+    for quaternion in data.chunks_exact_mut(8) {
+        let component = |i: usize| i16::from_le_bytes([quaternion[i * 2], quaternion[i * 2 + 1]]);
+        // the scale is stored in the high bits of the last component, and the index of the
+        // largest component in its 2 lowest bits
+        let last = component(3);
+        let component_scale = scale / (last | 3) as f32;
+        let x = component(0) as f32 * component_scale;
+        let y = component(1) as f32 * component_scale;
+        let z = component(2) as f32 * component_scale;
+        let w = (1.0 - x * x - y * y - z * z).max(0.0).sqrt();
+
+        let largest = (last & 3) as usize;
+        let mut decoded = [0; 4];
+        decoded[(largest + 1) & 3] = round_to_int(x * 32767.0);
+        decoded[(largest + 2) & 3] = round_to_int(y * 32767.0);
+        decoded[(largest + 3) & 3] = round_to_int(z * 32767.0);
+        decoded[largest] = round_to_int(w * 32767.0);
+        for (i, value) in decoded.into_iter().enumerate() {
+            quaternion[i * 2..i * 2 + 2].copy_from_slice(&(value as i16).to_le_bytes());
+        }
+    }
+}
+
+/// Reconstructs floats stored as a 24 bits mantissa and an 8 bits exponent.
+fn decode_exponential_filter(data: &mut [u8]) {
+    for value in data.chunks_exact_mut(4) {
+        let encoded = i32::from_le_bytes([value[0], value[1], value[2], value[3]]);
+        let mantissa = (encoded << 8) >> 8;
+        let exponent = encoded >> 24;
+        let decoded = mantissa as f32 * 2f32.powi(exponent);
+        value.copy_from_slice(&decoded.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(decoded: &[u8]) -> Vec<u16> {
+        decoded
+            .chunks_exact(2)
+            .map(|index| u16::from_le_bytes([index[0], index[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn decode_vertices() {
+        let mut data = vec![VERTEX_HEADER];
+        // first byte: raw deltas
+        data.extend([0x03, 2, 4]);
+        data.extend([0; 14]);
+        // second byte: 2 bits deltas, the second one read after the packed values
+        data.extend([0x01, 0b0111_0000, 0, 0, 0, 6]);
+        // third and fourth bytes: no delta
+        data.extend([0x00, 0x00]);
+        // tail with the baseline vertex
+        data.extend([0; 28]);
+        data.extend([10, 20, 30, 40]);
+
+        let decoded = decode_vertex_buffer(&data, 2, 4).unwrap();
+        assert_eq!(decoded, vec![11, 19, 30, 40, 13, 22, 30, 40]);
+
+        assert!(decode_vertex_buffer(&data[..data.len() - 1], 2, 4).is_none());
+        assert!(decode_vertex_buffer(&data, 2, 3).is_none());
+    }
+
+    #[test]
+    fn decode_triangles() {
+        let data = [
+            0xe0, 0xf0, 0x10, 0xfe, 0xff, 0xf0, 0x0c, 0xff, 0x02, 0x02, 0x02, 0x00, 0x76, 0x87,
+            0x56, 0x67, 0x78, 0xa9, 0x86, 0x65, 0x89, 0x68, 0x98, 0x01, 0x69, 0x00, 0x00,
+        ];
+        let decoded = decode_index_buffer(&data, 12, 2).unwrap();
+        assert_eq!(indices(&decoded), vec![0, 1, 2, 2, 1, 3, 4, 6, 5, 7, 8, 9]);
+
+        assert!(decode_index_buffer(&data, 9, 2).is_none());
+    }
+
+    #[test]
+    fn decode_indices() {
+        // deltas relative to two baselines: 5 (+5), 7 (+2), 3 (second baseline, +3), 4 (-3)
+        let encode =
+            |delta: i32, baseline: u32| (((delta << 1) ^ (delta >> 31)) as u32) << 1 | baseline;
+        let mut data = vec![SEQUENCE_HEADER];
+        data.extend([encode(5, 0), encode(2, 0), encode(3, 1), encode(-3, 0)].map(|v| v as u8));
+        data.extend([0; 4]);
+
+        let decoded = decode_index_sequence(&data, 4, 2).unwrap();
+        assert_eq!(indices(&decoded), vec![5, 7, 3, 4]);
+    }
+
+    #[test]
+    fn decode_filters() {
+        // +X axis stored as octahedral coordinates
+        let mut normal = [127, 0, 127, 0];
+        decode_octahedral_filter(&mut normal, 4).unwrap();
+        assert_eq!(normal, [127, 0, 0, 0]);
+        // -Z axis, on the lower hemisphere
+        let mut normal = [127, 127, 127, 0];
+        decode_octahedral_filter(&mut normal, 4).unwrap();
+        assert_eq!(normal, [0, 0, 0u8.wrapping_sub(127), 0]);
+
+        // identity quaternion, with w as its largest component
+        let mut quaternion = [0, 0, 0, 0, 0, 0, 0xff, 0x7f];
+        decode_quaternion_filter(&mut quaternion);
+        assert_eq!(quaternion, [0, 0, 0, 0, 0, 0, 0xff, 0x7f]);
+
+        // 3 * 2^-1
+        let mut value = 3i32.wrapping_add(-1 << 24).to_le_bytes();
+        decode_exponential_filter(&mut value);
+        assert_eq!(f32::from_le_bytes(value), 1.5);
+    }
+}
@@ -0,0 +1,105 @@
+use bevy_asset::Handle;
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    query::{Added, Changed},
+    reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_pbr::StandardMaterial;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+/// The materials of a glTF mesh primitive for each material variant of the
+/// `KHR_materials_variants` extension.
+///
+/// This is added to the primitives of a glTF scene that have a material for at least one
+/// variant. The variants of a glTF file are listed in [`Gltf::variants`](crate::Gltf::variants).
+#[derive(Clone, Debug, Reflect, Default, Component)]
+#[reflect(Component)]
+pub struct GltfMaterialVariants {
+    /// The material used when no variant is active, or when the primitive has no material for the
+    /// active variant.
+    pub default: Handle<StandardMaterial>,
+    /// The material of each variant, by name.
+    pub variants: HashMap<String, Handle<StandardMaterial>>,
+}
+
+impl GltfMaterialVariants {
+    /// Returns the material to use for the variant named `variant`.
+    pub fn material(&self, variant: Option<&str>) -> &Handle<StandardMaterial> {
+        variant
+            .and_then(|variant| self.variants.get(variant))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Selects the material variant of the glTF mesh primitives in the hierarchy of this entity.
+///
+/// Add this to the entity a glTF scene is spawned under to switch all of its primitives to a
+/// variant, such as the paint color of a car, without duplicating the scene. Setting it to `None`
+/// restores the default materials. A primitive uses the variant of its closest ancestor with this
+/// component.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gltf::GltfActiveVariant;
+/// fn paint_red(mut cars: Query<&mut GltfActiveVariant>) {
+///     for mut variant in &mut cars {
+///         variant.0 = Some("red".to_string());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(paint_red);
+/// ```
+#[derive(Clone, Debug, Reflect, Default, Component)]
+#[reflect(Component)]
+pub struct GltfActiveVariant(pub Option<String>);
+
+/// Applies the [`GltfActiveVariant`] of entities to the glTF mesh primitives in their hierarchy.
+pub fn apply_material_variants(
+    changed_variants: Query<(Entity, &GltfActiveVariant), Changed<GltfActiveVariant>>,
+    added_primitives: Query<Entity, Added<GltfMaterialVariants>>,
+    active_variants: Query<&GltfActiveVariant>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    mut primitives: Query<(&GltfMaterialVariants, &mut Handle<StandardMaterial>)>,
+) {
+    let mut set_material = |entity: Entity, variant: Option<&str>| {
+        if let Ok((variants, mut material)) = primitives.get_mut(entity) {
+            let variant_material = variants.material(variant);
+            if *material != *variant_material {
+                *material = variant_material.clone();
+            }
+        }
+    };
+
+    for (root, active_variant) in &changed_variants {
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            // an entity below with its own variant takes precedence
+            if entity != root && active_variants.contains(entity) {
+                continue;
+            }
+            set_material(entity, active_variant.0.as_deref());
+            if let Ok(entity_children) = children.get(entity) {
+                stack.extend(entity_children.iter().copied());
+            }
+        }
+    }
+
+    // primitives spawned after the variant was selected, such as when the scene is still loading
+    for primitive in &added_primitives {
+        let mut entity = primitive;
+        loop {
+            if let Ok(active_variant) = active_variants.get(entity) {
+                set_material(primitive, active_variant.0.as_deref());
+                break;
+            }
+            match parents.get(entity) {
+                Ok(parent) => entity = parent.get(),
+                Err(_) => break,
+            }
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::components::{Children, Parent};
 use bevy_ecs::{
     entity::Entity,
-    system::{Command, EntityCommands},
+    system::{Command, Commands, EntityCommands},
     world::{EntityMut, World},
 };
-use bevy_utils::tracing::debug;
+use bevy_utils::{tracing::debug, HashSet};
 
 /// Despawns the given entity and all its children recursively
 #[derive(Debug)]
@@ -20,6 +20,13 @@ pub struct DespawnChildrenRecursive {
     pub entity: Entity,
 }
 
+/// Despawns the given entities and all their children recursively, in a single batch
+#[derive(Debug)]
+pub struct DespawnRecursiveBatch {
+    /// Target entities
+    pub entities: Vec<Entity>,
+}
+
 /// Function for despawning an entity and all its children
 pub fn despawn_with_children_recursive(world: &mut World, entity: Entity) {
     // first, make the entity's own parent forget about it
@@ -46,6 +53,45 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
     }
 }
 
+/// Function for despawning many entities and all their children at once
+///
+/// The full set of descendants is gathered once and then removed with [`World::despawn_batch`],
+/// which is much cheaper than calling [`despawn_with_children_recursive`] for each entity when
+/// tearing down large hierarchies (e.g. unloading a level).
+pub fn despawn_with_children_recursive_batch(world: &mut World, entities: &[Entity]) {
+    let mut to_despawn = HashSet::default();
+    let mut stack = entities.to_vec();
+    while let Some(entity) = stack.pop() {
+        if !to_despawn.insert(entity) {
+            continue;
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().copied());
+        }
+    }
+
+    // make the parents that survive forget about their despawned children
+    let surviving_parents = entities
+        .iter()
+        .filter_map(|&entity| world.get::<Parent>(entity).map(|parent| parent.0))
+        .filter(|parent| !to_despawn.contains(parent))
+        .collect::<HashSet<_>>();
+    for parent in surviving_parents {
+        if let Some(mut children) = world.get_mut::<Children>(parent) {
+            children.0.retain(|c| !to_despawn.contains(c));
+        }
+    }
+
+    let despawned = world.despawn_batch(to_despawn.iter().copied());
+    if despawned != to_despawn.len() {
+        debug!(
+            "Failed to despawn {} of {} entities",
+            to_despawn.len() - despawned,
+            to_despawn.len()
+        );
+    }
+}
+
 fn despawn_children(world: &mut World, entity: Entity) {
     if let Some(mut children) = world.get_mut::<Children>(entity) {
         for e in std::mem::take(&mut children.0) {
@@ -80,6 +126,19 @@ impl Command for DespawnChildrenRecursive {
     }
 }
 
+impl Command for DespawnRecursiveBatch {
+    fn write(self, world: &mut World) {
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "command",
+            name = "DespawnRecursiveBatch",
+            count = self.entities.len()
+        )
+        .entered();
+        despawn_with_children_recursive_batch(world, &self.entities);
+    }
+}
+
 /// Trait that holds functions for despawning recursively down the transform hierarchy
 pub trait DespawnRecursiveExt {
     /// Despawns the provided entity alongside all descendants.
@@ -135,6 +194,31 @@ impl<'w> DespawnRecursiveExt for EntityMut<'w> {
     }
 }
 
+/// Trait that holds functions for despawning many hierarchies at once
+pub trait DespawnRecursiveBatchExt {
+    /// Despawns all of the provided entities alongside all of their descendants.
+    fn despawn_recursive_batch(&mut self, entities: &[Entity]);
+}
+
+impl<'w, 's> DespawnRecursiveBatchExt for Commands<'w, 's> {
+    fn despawn_recursive_batch(&mut self, entities: &[Entity]) {
+        self.add(DespawnRecursiveBatch {
+            entities: entities.to_vec(),
+        });
+    }
+}
+
+impl DespawnRecursiveBatchExt for World {
+    fn despawn_recursive_batch(&mut self, entities: &[Entity]) {
+        #[cfg(feature = "trace")]
+        let _span =
+            bevy_utils::tracing::info_span!("despawn_recursive_batch", count = entities.len())
+                .entered();
+
+        despawn_with_children_recursive_batch(self, entities);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -143,7 +227,7 @@ mod tests {
         world::World,
     };
 
-    use super::DespawnRecursiveExt;
+    use super::{DespawnRecursiveBatchExt, DespawnRecursiveExt};
     use crate::{child_builder::BuildChildren, components::Children};
 
     #[derive(Component, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug)]
@@ -226,4 +310,55 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn despawn_recursive_batch() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let (root, parents, bystander);
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            root = commands.spawn(Idx(0)).id();
+            let mut spawned = Vec::new();
+            commands.entity(root).with_children(|root| {
+                for i in 0..3 {
+                    spawned.push(
+                        root.spawn(Idx(1 + i * 10))
+                            .with_children(|parent| {
+                                parent.spawn(Idx(2 + i * 10)).with_children(|child| {
+                                    child.spawn(Idx(3 + i * 10));
+                                });
+                            })
+                            .id(),
+                    );
+                }
+            });
+            parents = spawned;
+            bystander = commands.spawn(Idx(100)).id();
+        }
+        queue.apply(&mut world);
+
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            // duplicates should not cause issues
+            commands.despawn_recursive_batch(&[parents[0], parents[2], parents[2]]);
+        }
+        queue.apply(&mut world);
+
+        let mut results = world
+            .query::<&Idx>()
+            .iter(&world)
+            .copied()
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, vec![Idx(0), Idx(11), Idx(12), Idx(13), Idx(100)]);
+        assert_eq!(
+            &**world.get::<Children>(root).unwrap(),
+            &[parents[1]],
+            "root should only know about its surviving child"
+        );
+
+        world.despawn_recursive_batch(&[root, bystander]);
+        assert_eq!(world.entities().len(), 0);
+    }
 }
@@ -21,6 +21,7 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`AudioPlugin`](crate::audio::AudioPlugin) - with feature `bevy_audio`
 /// * [`GilrsPlugin`](crate::gilrs::GilrsPlugin) - with feature `bevy_gilrs`
 /// * [`GltfPlugin`](crate::gltf::GltfPlugin) - with feature `bevy_gltf`
+/// * [`NavMeshPlugin`](crate::navmesh::NavMeshPlugin) - with feature `bevy_navmesh`
 /// * [`WinitPlugin`](crate::winit::WinitPlugin) - with feature `bevy_winit`
 ///
 /// See also [`MinimalPlugins`] for a slimmed down option
@@ -102,6 +103,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_gltf::GltfPlugin::default());
         }
 
+        #[cfg(feature = "bevy_navmesh")]
+        {
+            group = group.add(bevy_navmesh::NavMeshPlugin::default());
+        }
+
         #[cfg(feature = "bevy_audio")]
         {
             group = group.add(bevy_audio::AudioPlugin::default());
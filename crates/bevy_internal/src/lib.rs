@@ -127,6 +127,12 @@ pub mod gltf {
     pub use bevy_gltf::*;
 }
 
+#[cfg(feature = "bevy_navmesh")]
+pub mod navmesh {
+    //! Navigation meshes and pathfinding.
+    pub use bevy_navmesh::*;
+}
+
 #[cfg(feature = "bevy_pbr")]
 pub mod pbr {
     //! Physically based rendering.
@@ -23,6 +23,10 @@ pub use crate::animation::prelude::*;
 #[cfg(feature = "bevy_core_pipeline")]
 pub use crate::core_pipeline::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_navmesh")]
+pub use crate::navmesh::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_pbr")]
 pub use crate::pbr::prelude::*;
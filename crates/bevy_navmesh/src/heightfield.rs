@@ -0,0 +1,282 @@
+use crate::NavMeshSettings;
+use bevy_math::Vec3;
+use std::{collections::VecDeque, ops::Range};
+
+/// The offsets of the four neighbors of a column along the X and Z axes.
+pub(crate) const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub(crate) const NEGATIVE_X: usize = 0;
+pub(crate) const POSITIVE_Z: usize = 1;
+pub(crate) const POSITIVE_X: usize = 2;
+pub(crate) const NEGATIVE_Z: usize = 3;
+
+/// A solid interval of a column of the [`Heightfield`], in units of the cell height.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    min: u32,
+    max: u32,
+    /// Whether the top of the span is a walkable surface.
+    walkable: bool,
+}
+
+/// The triangles of the level rasterized into columns of solid spans, along the Y axis.
+pub(crate) struct Heightfield {
+    pub origin: Vec3,
+    pub width: usize,
+    pub depth: usize,
+    pub cell_size: f32,
+    pub cell_height: f32,
+    columns: Vec<Vec<Span>>,
+}
+
+impl Heightfield {
+    /// Creates an empty heightfield covering the bounds from `min` to `max`.
+    pub fn new(min: Vec3, max: Vec3, settings: &NavMeshSettings) -> Self {
+        let width = ((max.x - min.x) / settings.cell_size).ceil().max(1.0) as usize;
+        let depth = ((max.z - min.z) / settings.cell_size).ceil().max(1.0) as usize;
+        Self {
+            origin: min,
+            width,
+            depth,
+            cell_size: settings.cell_size,
+            cell_height: settings.cell_height,
+            columns: vec![Vec::new(); width * depth],
+        }
+    }
+
+    /// Adds the solid spans covered by the triangle to the columns it overlaps.
+    pub fn rasterize_triangle(&mut self, triangle: [Vec3; 3], walkable: bool, merge_climb: u32) {
+        let min = triangle[0].min(triangle[1]).min(triangle[2]) - self.origin;
+        let max = triangle[0].max(triangle[1]).max(triangle[2]) - self.origin;
+        let z_range = self.cell_range(min.z, max.z, self.depth);
+        let x_range = self.cell_range(min.x, max.x, self.width);
+
+        let polygon: Vec<Vec3> = triangle
+            .iter()
+            .map(|&vertex| vertex - self.origin)
+            .collect();
+        for z in z_range {
+            let row = clip(&polygon, 2, z as f32 * self.cell_size, true);
+            let row = clip(&row, 2, (z + 1) as f32 * self.cell_size, false);
+            if row.is_empty() {
+                continue;
+            }
+            for x in x_range.clone() {
+                let cell = clip(&row, 0, x as f32 * self.cell_size, true);
+                let cell = clip(&cell, 0, (x + 1) as f32 * self.cell_size, false);
+                if cell.is_empty() {
+                    continue;
+                }
+                let (y_min, y_max) = cell.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+                    (min.min(v.y), max.max(v.y))
+                });
+                let span_min = (y_min / self.cell_height).floor().max(0.0) as u32;
+                let span_max =
+                    ((y_max / self.cell_height).ceil().max(0.0) as u32).max(span_min + 1);
+                self.add_span(
+                    x + z * self.width,
+                    Span {
+                        min: span_min,
+                        max: span_max,
+                        walkable,
+                    },
+                    merge_climb,
+                );
+            }
+        }
+    }
+
+    /// The range of cells overlapped by the interval from `min` to `max` along an axis.
+    fn cell_range(&self, min: f32, max: f32, count: usize) -> Range<usize> {
+        let first = (min / self.cell_size).floor().max(0.0) as usize;
+        let last = ((max / self.cell_size).floor().max(0.0) as usize).min(count - 1);
+        first..last + 1
+    }
+
+    /// Inserts a span in a column, merging it with the spans it overlaps. The top of the merged
+    /// span is walkable if the top of one of them is walkable and within `merge_climb` of it.
+    fn add_span(&mut self, column: usize, mut span: Span, merge_climb: u32) {
+        let spans = &mut self.columns[column];
+        let mut index = 0;
+        while index < spans.len() {
+            let other = spans[index];
+            if other.min > span.max {
+                break;
+            }
+            if other.max < span.min {
+                index += 1;
+                continue;
+            }
+            if other.max.abs_diff(span.max) <= merge_climb {
+                span.walkable |= other.walkable;
+            } else if other.max > span.max {
+                span.walkable = other.walkable;
+            }
+            span.min = span.min.min(other.min);
+            span.max = span.max.max(other.max);
+            spans.remove(index);
+        }
+        spans.insert(index, span);
+    }
+}
+
+/// Keeps the part of a convex polygon on one side of the plane `axis = offset`.
+fn clip(polygon: &[Vec3], axis: usize, offset: f32, keep_above: bool) -> Vec<Vec3> {
+    let side = |vertex: &Vec3| {
+        if keep_above {
+            vertex[axis] - offset
+        } else {
+            offset - vertex[axis]
+        }
+    };
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (index, vertex) in polygon.iter().enumerate() {
+        let next = &polygon[(index + 1) % polygon.len()];
+        let (distance, next_distance) = (side(vertex), side(next));
+        if distance >= 0.0 {
+            clipped.push(*vertex);
+        }
+        if (distance >= 0.0) != (next_distance >= 0.0) {
+            let t = distance / (distance - next_distance);
+            clipped.push(vertex.lerp(*next, t));
+        }
+    }
+    clipped
+}
+
+/// A walkable surface on top of a span of the [`Heightfield`], with enough room above it for an
+/// agent to stand.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Floor {
+    pub x: usize,
+    pub z: usize,
+    /// The height of the floor, in units of the cell height.
+    pub y: u32,
+    /// The height of the next span above the floor, in units of the cell height.
+    pub ceiling: u32,
+    /// The index of the floor reachable from this one in each of the [`DIRECTIONS`].
+    pub neighbors: [Option<usize>; 4],
+    /// Whether the floor is too close to a border for an agent to stand on it.
+    pub eroded: bool,
+}
+
+/// The floors of a [`Heightfield`] and how agents can walk between them.
+pub(crate) struct Floors {
+    pub width: usize,
+    pub depth: usize,
+    pub floors: Vec<Floor>,
+    /// The range of the floors of each column in `floors`.
+    pub columns: Vec<Range<usize>>,
+}
+
+impl Floors {
+    /// Finds the walkable floors of the heightfield, links the neighbors an agent can climb to
+    /// and removes the floors closer to a border than the radius of the agent.
+    pub fn new(heightfield: &Heightfield, settings: &NavMeshSettings) -> Self {
+        let agent_height = (settings.agent_height / settings.cell_height).ceil() as u32;
+        let max_climb = (settings.max_climb / settings.cell_height).floor() as u32;
+
+        let mut floors = Vec::new();
+        let mut columns = Vec::with_capacity(heightfield.columns.len());
+        for (column, spans) in heightfield.columns.iter().enumerate() {
+            let start = floors.len();
+            for (index, span) in spans.iter().enumerate() {
+                let ceiling = spans.get(index + 1).map_or(u32::MAX, |next| next.min);
+                if span.walkable && ceiling - span.max >= agent_height {
+                    floors.push(Floor {
+                        x: column % heightfield.width,
+                        z: column / heightfield.width,
+                        y: span.max,
+                        ceiling,
+                        neighbors: [None; 4],
+                        eroded: false,
+                    });
+                }
+            }
+            columns.push(start..floors.len());
+        }
+
+        let mut result = Self {
+            width: heightfield.width,
+            depth: heightfield.depth,
+            floors,
+            columns,
+        };
+        for index in 0..result.floors.len() {
+            let floor = result.floors[index];
+            for (direction, (dx, dz)) in DIRECTIONS.iter().enumerate() {
+                let Some(column) = result.column(floor.x as i32 + dx, floor.z as i32 + dz) else {
+                    continue;
+                };
+                result.floors[index].neighbors[direction] =
+                    result.columns[column].clone().find(|&other| {
+                        let other = &result.floors[other];
+                        other.y.abs_diff(floor.y) <= max_climb
+                            && other
+                                .ceiling
+                                .min(floor.ceiling)
+                                .saturating_sub(other.y.max(floor.y))
+                                >= agent_height
+                    });
+            }
+        }
+
+        result.erode(settings.agent_radius / settings.cell_size);
+        result
+    }
+
+    /// The index of the column at `x` and `z`, if it is in the heightfield.
+    pub fn column(&self, x: i32, z: i32) -> Option<usize> {
+        (x >= 0 && z >= 0 && (x as usize) < self.width && (z as usize) < self.depth)
+            .then(|| x as usize + z as usize * self.width)
+    }
+
+    /// The floors reachable from a floor in each of the [`DIRECTIONS`], then diagonally by
+    /// going in one direction and turning left.
+    fn surrounding(&self, index: usize) -> [Option<usize>; 8] {
+        let neighbors = self.floors[index].neighbors;
+        let mut surrounding = [None; 8];
+        for direction in 0..4 {
+            surrounding[direction] = neighbors[direction];
+            surrounding[direction + 4] = neighbors[direction]
+                .and_then(|neighbor| self.floors[neighbor].neighbors[(direction + 1) % 4]);
+        }
+        surrounding
+    }
+
+    /// Removes the floors closer than `radius` cells to a border, where an agent can't stand.
+    fn erode(&mut self, radius: f32) {
+        // The distance in cells from each floor to the nearest border, including diagonally, with
+        // the floors along the border at a distance of one
+        let mut distances = vec![u32::MAX; self.floors.len()];
+        let mut queue = VecDeque::new();
+        for (index, distance) in distances.iter_mut().enumerate() {
+            if self.surrounding(index).iter().any(Option::is_none) {
+                *distance = 1;
+                queue.push_back(index);
+            }
+        }
+        while let Some(index) = queue.pop_front() {
+            for neighbor in self.surrounding(index).into_iter().flatten() {
+                if distances[neighbor] == u32::MAX {
+                    distances[neighbor] = distances[index] + 1;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // Keep the floors whose center is further than the radius from the border
+        for (floor, distance) in self.floors.iter_mut().zip(&distances) {
+            floor.eroded = (*distance as f32 - 0.5) < radius;
+        }
+        for index in 0..self.floors.len() {
+            let eroded = self.floors[index].eroded;
+            for direction in 0..4 {
+                if let Some(neighbor) = self.floors[index].neighbors[direction] {
+                    if eroded || self.floors[neighbor].eroded {
+                        self.floors[index].neighbors[direction] = None;
+                    }
+                }
+            }
+        }
+    }
+}
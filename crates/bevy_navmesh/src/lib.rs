@@ -0,0 +1,169 @@
+//! Navigation meshes baked from the meshes of a level, to find paths for agents walking on it.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::Assets;
+//! # use bevy_math::Vec3;
+//! # use bevy_navmesh::{NavMesh, NavMeshSettings, NavMeshSources};
+//! fn bake_level(sources: NavMeshSources, mut navmeshes: ResMut<Assets<NavMesh>>) {
+//!     let navmesh = sources.bake(&NavMeshSettings::default());
+//!     if let Some(path) = navmesh.find_path(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)) {
+//!         println!("walk through {path:?}");
+//!     }
+//!     navmeshes.add(navmesh);
+//! }
+//! # bevy_ecs::system::assert_is_system(bake_level);
+//! ```
+
+#![warn(missing_docs)]
+
+mod heightfield;
+mod navmesh;
+mod path;
+
+pub use navmesh::*;
+
+#[allow(missing_docs)]
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{NavMesh, NavMeshPlugin, NavMeshSettings, NavMeshSource, NavMeshSources};
+}
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AddAsset, Assets, Handle};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent, system::SystemParam};
+use bevy_math::{Mat4, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+use bevy_transform::components::GlobalTransform;
+
+/// Adds the [`NavMesh`] asset.
+#[derive(Default)]
+pub struct NavMeshPlugin;
+
+impl Plugin for NavMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<NavMesh>().register_type::<NavMeshSource>();
+    }
+}
+
+/// The dimensions of the agents walking on a [`NavMesh`], and the resolution it is baked at.
+#[derive(Debug, Clone, Copy)]
+pub struct NavMeshSettings {
+    /// The size of the cells of the heightfield the level is rasterized into, on the XZ plane.
+    ///
+    /// Smaller cells follow the shape of the level more closely, but take longer to bake.
+    pub cell_size: f32,
+    /// The height of the cells of the heightfield the level is rasterized into.
+    pub cell_height: f32,
+    /// The height of the agents, which can't walk under lower ceilings.
+    pub agent_height: f32,
+    /// The radius of the agents, which keep this far from the walls and edges.
+    pub agent_radius: f32,
+    /// The highest step the agents can climb up or down.
+    pub max_climb: f32,
+    /// The steepest slope the agents can walk on, in radians.
+    pub max_slope: f32,
+}
+
+impl Default for NavMeshSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_height: 2.0,
+            agent_radius: 0.5,
+            max_climb: 0.5,
+            max_slope: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+/// Marks an entity with a [`Handle<Mesh>`] as part of the level baked by [`NavMeshSources`].
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct NavMeshSource;
+
+/// A [`SystemParam`] to bake a [`NavMesh`] from the meshes of the [`NavMeshSource`] entities.
+#[derive(SystemParam)]
+pub struct NavMeshSources<'w, 's> {
+    meshes: Res<'w, Assets<Mesh>>,
+    sources: Query<'w, 's, (&'static Handle<Mesh>, &'static GlobalTransform), With<NavMeshSource>>,
+}
+
+impl<'w, 's> NavMeshSources<'w, 's> {
+    /// Bakes the navigation mesh of the triangles of the meshes of the [`NavMeshSource`]
+    /// entities, see [`NavMesh::bake`].
+    ///
+    /// The meshes that aren't loaded yet are skipped, as well as those that aren't a
+    /// [`PrimitiveTopology::TriangleList`] with [`Mesh::ATTRIBUTE_POSITION`] in
+    /// [`VertexAttributeValues::Float32x3`].
+    pub fn bake(&self, settings: &NavMeshSettings) -> NavMesh {
+        let mut triangles = Vec::new();
+        for (handle, transform) in &self.sources {
+            if let Some(mesh) = self.meshes.get(handle) {
+                triangles.extend(mesh_triangles(mesh, transform.compute_matrix()));
+            }
+        }
+        NavMesh::bake(settings, triangles)
+    }
+}
+
+/// The triangles of `mesh`, transformed by `transform`.
+fn mesh_triangles(mesh: &Mesh, transform: Mat4) -> Vec<[Vec3; 3]> {
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions))
+            if mesh.primitive_topology() == PrimitiveTopology::TriangleList =>
+        {
+            positions
+        }
+        _ => return Vec::new(),
+    };
+    let position = |index: usize| {
+        positions
+            .get(index)
+            .map(|&position| transform.transform_point3(position.into()))
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    indices
+        .chunks_exact(3)
+        .filter_map(|triangle| {
+            Some([
+                position(triangle[0])?,
+                position(triangle[1])?,
+                position(triangle[2])?,
+            ])
+        })
+        .collect()
+}
+
+impl NavMesh {
+    /// Creates a [`Mesh`] of the outlines of the polygons of the navigation mesh, to see it when
+    /// debugging.
+    ///
+    /// It is a [`PrimitiveTopology::LineList`] with normals pointing up, which can be spawned with
+    /// an unlit material, slightly above the level to avoid being hidden by it.
+    pub fn debug_mesh(&self) -> Mesh {
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+        for polygon in self.polygons() {
+            let offset = positions.len() as u32;
+            positions.extend(polygon.iter().map(|vertex| vertex.to_array()));
+            for index in 0..polygon.len() as u32 {
+                indices.extend([offset + index, offset + (index + 1) % polygon.len() as u32]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0.0, 1.0, 0.0]; positions.len()],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh
+    }
+}
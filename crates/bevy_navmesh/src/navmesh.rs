@@ -0,0 +1,300 @@
+use crate::{
+    heightfield::{Floors, Heightfield, NEGATIVE_X, NEGATIVE_Z, POSITIVE_X, POSITIVE_Z},
+    NavMeshSettings,
+};
+use bevy_math::Vec3;
+use bevy_reflect::TypeUuid;
+
+/// The surfaces of a level an agent can walk on, as convex polygons connected by portals.
+///
+/// It is baked from the triangles of the level with [`NavMesh::bake`], or from the meshes of the
+/// [`NavMeshSource`](crate::NavMeshSource) entities with
+/// [`NavMeshSources::bake`](crate::NavMeshSources::bake), and finds paths between points with
+/// [`NavMesh::find_path`].
+#[derive(Debug, Clone, Default, TypeUuid)]
+#[uuid = "5a3c4a1e-5e8b-4c1f-9e57-2a07fbc3d2a6"]
+pub struct NavMesh {
+    origin: Vec3,
+    cell_size: f32,
+    width: usize,
+    depth: usize,
+    agent_height: f32,
+    /// The height and the polygon of the floors of each column of cells.
+    columns: Vec<Vec<(f32, usize)>>,
+    pub(crate) polygons: Vec<NavPolygon>,
+    pub(crate) portals: Vec<NavPortal>,
+}
+
+/// A convex polygon of a [`NavMesh`].
+#[derive(Debug, Clone)]
+pub(crate) struct NavPolygon {
+    /// The vertices, counter-clockwise when seen from above.
+    pub vertices: Vec<Vec3>,
+    /// The portals to the neighboring polygons.
+    pub portals: Vec<usize>,
+}
+
+/// The edge shared by two neighboring polygons of a [`NavMesh`].
+#[derive(Debug, Clone)]
+pub(crate) struct NavPortal {
+    pub polygons: [usize; 2],
+    /// The ends of the edge, in the counter-clockwise order of the vertices of the first polygon.
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl NavPortal {
+    /// The other polygon of the portal.
+    pub fn neighbor(&self, polygon: usize) -> usize {
+        if self.polygons[0] == polygon {
+            self.polygons[1]
+        } else {
+            self.polygons[0]
+        }
+    }
+
+    /// The left and right ends of the portal, when crossing it from `polygon` to its neighbor.
+    pub fn left_right(&self, polygon: usize) -> (Vec3, Vec3) {
+        if self.polygons[0] == polygon {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        }
+    }
+}
+
+impl NavMesh {
+    /// Bakes the navigation mesh of the level made of `triangles`, in world space.
+    ///
+    /// The triangles are rasterized into a heightfield of cells of
+    /// [`cell_size`](NavMeshSettings::cell_size) on the XZ plane, keeping the walkable surfaces
+    /// where an agent fits, which are then merged into convex polygons. A triangle is walkable if
+    /// it faces up, with its vertices counter-clockwise when seen from above, and isn't steeper
+    /// than [`max_slope`](NavMeshSettings::max_slope).
+    ///
+    /// Baking a large level can take a while, so consider doing it in a task of the
+    /// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool).
+    pub fn bake(
+        settings: &NavMeshSettings,
+        triangles: impl IntoIterator<Item = [Vec3; 3]>,
+    ) -> Self {
+        let triangles: Vec<[Vec3; 3]> = triangles.into_iter().collect();
+        let Some((min, max)) = triangles.iter().flatten().fold(None, |bounds, &vertex| {
+            Some(match bounds {
+                Some((min, max)) => (vertex.min(min), vertex.max(max)),
+                None => (vertex, vertex),
+            })
+        }) else {
+            return Self::default();
+        };
+
+        let mut heightfield = Heightfield::new(min, max, settings);
+        let min_normal_y = settings.max_slope.cos();
+        let merge_climb = (settings.max_climb / settings.cell_height).floor() as u32;
+        for [a, b, c] in triangles {
+            let normal = (b - a).cross(c - a).normalize_or_zero();
+            heightfield.rasterize_triangle([a, b, c], normal.y >= min_normal_y, merge_climb);
+        }
+
+        let floors = Floors::new(&heightfield, settings);
+        let mut navmesh = Self {
+            origin: heightfield.origin,
+            cell_size: heightfield.cell_size,
+            width: heightfield.width,
+            depth: heightfield.depth,
+            agent_height: settings.agent_height,
+            columns: vec![Vec::new(); heightfield.width * heightfield.depth],
+            polygons: Vec::new(),
+            portals: Vec::new(),
+        };
+        navmesh.polygonize(&floors, heightfield.cell_height, merge_climb);
+        navmesh
+    }
+
+    /// Returns `true` if the navigation mesh has no walkable surface.
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// The vertices of each polygon of the navigation mesh, counter-clockwise when seen from
+    /// above.
+    pub fn polygons(&self) -> impl Iterator<Item = &[Vec3]> {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.vertices.as_slice())
+    }
+
+    /// Returns the point of the navigation mesh below or above `point`, not further than the
+    /// height of the agent, or `None` if there is no walkable surface there.
+    pub fn project_point(&self, point: Vec3) -> Option<Vec3> {
+        self.locate(point)
+            .map(|(_, height)| Vec3::new(point.x, height, point.z))
+    }
+
+    /// Returns the polygon of the floor below or above `point` and the height of the floor.
+    pub(crate) fn locate(&self, point: Vec3) -> Option<(usize, f32)> {
+        let cell = ((point - self.origin) / self.cell_size).floor();
+        if cell.x < 0.0 || cell.z < 0.0 {
+            return None;
+        }
+        let (x, z) = (cell.x as usize, cell.z as usize);
+        if x >= self.width || z >= self.depth {
+            return None;
+        }
+        self.columns[x + z * self.width]
+            .iter()
+            .filter(|(height, _)| (height - point.y).abs() <= self.agent_height)
+            .min_by(|a, b| (a.0 - point.y).abs().total_cmp(&(b.0 - point.y).abs()))
+            .map(|&(height, polygon)| (polygon, height))
+    }
+
+    /// Merges the floors into rectangles, greedily growing them along the X axis then the Z axis
+    /// while the floors are connected and within `max_climb` of the height of the first one, and
+    /// links the rectangles by the portals along their sides.
+    fn polygonize(&mut self, floors: &Floors, cell_height: f32, max_climb: u32) {
+        let mut polygon_of: Vec<Option<usize>> = vec![None; floors.floors.len()];
+        // The floors of each polygon, by rows along the Z axis
+        let mut rectangles: Vec<Vec<Vec<usize>>> = Vec::new();
+        let is_free = |polygon_of: &[Option<usize>], floor: usize, first: usize| {
+            polygon_of[floor].is_none()
+                && !floors.floors[floor].eroded
+                && floors.floors[floor].y.abs_diff(floors.floors[first].y) <= max_climb
+        };
+
+        for first in 0..floors.floors.len() {
+            if !is_free(&polygon_of, first, first) {
+                continue;
+            }
+
+            let mut row = vec![first];
+            while let Some(next) = floors.floors[*row.last().unwrap()].neighbors[POSITIVE_X] {
+                if !is_free(&polygon_of, next, first) {
+                    break;
+                }
+                row.push(next);
+            }
+
+            let mut rows = vec![row];
+            loop {
+                let last = rows.last().unwrap();
+                let mut next_row: Vec<usize> = Vec::with_capacity(last.len());
+                for &floor in last {
+                    let Some(next) = floors.floors[floor].neighbors[POSITIVE_Z] else {
+                        break;
+                    };
+                    let connected = next_row.last().map_or(true, |&previous| {
+                        floors.floors[previous].neighbors[POSITIVE_X] == Some(next)
+                    });
+                    if !connected || !is_free(&polygon_of, next, first) {
+                        break;
+                    }
+                    next_row.push(next);
+                }
+                if next_row.len() < last.len() {
+                    break;
+                }
+                rows.push(next_row);
+            }
+
+            for &floor in rows.iter().flatten() {
+                polygon_of[floor] = Some(rectangles.len());
+            }
+            rectangles.push(rows);
+        }
+
+        let (origin, cell_size) = (self.origin, self.cell_size);
+        let height = |floor: usize| origin.y + floors.floors[floor].y as f32 * cell_height;
+        let corner = |x: usize, z: usize, floor: usize| {
+            Vec3::new(
+                origin.x + x as f32 * cell_size,
+                height(floor),
+                origin.z + z as f32 * cell_size,
+            )
+        };
+
+        for rows in &rectangles {
+            let (first, last) = (rows[0][0], *rows.last().unwrap().last().unwrap());
+            let (x0, z0) = (floors.floors[first].x, floors.floors[first].z);
+            let (x1, z1) = (floors.floors[last].x + 1, floors.floors[last].z + 1);
+            let top_left = *rows.last().unwrap().first().unwrap();
+            let bottom_right = *rows[0].last().unwrap();
+            self.polygons.push(NavPolygon {
+                vertices: vec![
+                    corner(x0, z0, first),
+                    corner(x0, z1, top_left),
+                    corner(x1, z1, last),
+                    corner(x1, z0, bottom_right),
+                ],
+                portals: Vec::new(),
+            });
+        }
+
+        for (polygon, rows) in rectangles.iter().enumerate() {
+            // The floors along each side of the rectangle, in the counter-clockwise order of its
+            // vertices, with the direction of their neighbors outside of it
+            let sides: [(usize, Vec<usize>); 4] = [
+                (NEGATIVE_X, rows.iter().map(|row| row[0]).collect()),
+                (POSITIVE_Z, rows.last().unwrap().clone()),
+                (
+                    POSITIVE_X,
+                    rows.iter().rev().map(|row| *row.last().unwrap()).collect(),
+                ),
+                (NEGATIVE_Z, rows[0].iter().rev().copied().collect()),
+            ];
+
+            for (direction, side) in &sides {
+                let mut start = 0;
+                while start < side.len() {
+                    let neighbor_polygon = |index: usize| {
+                        floors.floors[side[index]].neighbors[*direction]
+                            .and_then(|neighbor| polygon_of[neighbor])
+                    };
+                    let neighbor = neighbor_polygon(start);
+                    let mut end = start + 1;
+                    while end < side.len() && neighbor_polygon(end) == neighbor {
+                        end += 1;
+                    }
+
+                    if let Some(neighbor) = neighbor.filter(|&neighbor| neighbor > polygon) {
+                        let point = |floor: usize, at_end: bool| {
+                            let floor_data = &floors.floors[floor];
+                            let other = floor_data.neighbors[*direction].unwrap();
+                            let y = (height(floor) + height(other)) / 2.0;
+                            // The corner of the cell along the side, in counter-clockwise order
+                            let (x, z) = match (*direction, at_end) {
+                                (NEGATIVE_X, false) => (floor_data.x, floor_data.z),
+                                (NEGATIVE_X, true) => (floor_data.x, floor_data.z + 1),
+                                (POSITIVE_Z, false) => (floor_data.x, floor_data.z + 1),
+                                (POSITIVE_Z, true) => (floor_data.x + 1, floor_data.z + 1),
+                                (POSITIVE_X, false) => (floor_data.x + 1, floor_data.z + 1),
+                                (POSITIVE_X, true) => (floor_data.x + 1, floor_data.z),
+                                (_, false) => (floor_data.x + 1, floor_data.z),
+                                (_, true) => (floor_data.x, floor_data.z),
+                            };
+                            Vec3::new(
+                                origin.x + x as f32 * cell_size,
+                                y,
+                                origin.z + z as f32 * cell_size,
+                            )
+                        };
+                        let portal = self.portals.len();
+                        self.portals.push(NavPortal {
+                            polygons: [polygon, neighbor],
+                            start: point(side[start], false),
+                            end: point(side[end - 1], true),
+                        });
+                        self.polygons[polygon].portals.push(portal);
+                        self.polygons[neighbor].portals.push(portal);
+                    }
+                    start = end;
+                }
+            }
+        }
+
+        for (index, floor) in floors.floors.iter().enumerate() {
+            if let Some(polygon) = polygon_of[index] {
+                self.columns[floor.x + floor.z * self.width].push((height(index), polygon));
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::NavMesh;
+use bevy_math::Vec3;
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// A polygon to visit in the A* search, ordered by its estimated total cost.
+struct Visit {
+    estimated_cost: f32,
+    cost: f32,
+    polygon: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimated_cost == other.estimated_cost
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, for the binary heap to pop the lowest cost first
+        other.estimated_cost.total_cmp(&self.estimated_cost)
+    }
+}
+
+impl NavMesh {
+    /// Finds the shortest path from `start` to `end` on the navigation mesh.
+    ///
+    /// The path starts and ends at the points of the navigation mesh below or above `start` and
+    /// `end`, see [`NavMesh::project_point`], and goes straight between them except to turn
+    /// around the corners of the walkable surfaces. Returns `None` if `start` or `end` isn't on
+    /// the navigation mesh, or if `end` can't be reached from `start`.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let (start_polygon, start_height) = self.locate(start)?;
+        let (end_polygon, end_height) = self.locate(end)?;
+        let start = Vec3::new(start.x, start_height, start.z);
+        let end = Vec3::new(end.x, end_height, end.z);
+
+        let portals = self.find_portals(start, start_polygon, end, end_polygon)?;
+        Some(string_pull(start, end, &portals))
+    }
+
+    /// Finds the portals crossed from `start` to `end` with an A* search over the polygons, going
+    /// through the middle of the portals. Returns their left and right ends, when crossing them.
+    fn find_portals(
+        &self,
+        start: Vec3,
+        start_polygon: usize,
+        end: Vec3,
+        end_polygon: usize,
+    ) -> Option<Vec<(Vec3, Vec3)>> {
+        let mut costs = vec![f32::INFINITY; self.polygons.len()];
+        // The point where each polygon is entered, and the portal it is entered from
+        let mut entries: Vec<(Vec3, Option<usize>)> = vec![(start, None); self.polygons.len()];
+        let mut queue = BinaryHeap::new();
+        costs[start_polygon] = 0.0;
+        queue.push(Visit {
+            estimated_cost: start.distance(end),
+            cost: 0.0,
+            polygon: start_polygon,
+        });
+
+        while let Some(Visit { cost, polygon, .. }) = queue.pop() {
+            if polygon == end_polygon {
+                break;
+            }
+            if cost > costs[polygon] {
+                continue;
+            }
+            let position = entries[polygon].0;
+            for &portal_index in &self.polygons[polygon].portals {
+                let portal = &self.portals[portal_index];
+                let neighbor = portal.neighbor(polygon);
+                let middle = (portal.start + portal.end) / 2.0;
+                let neighbor_cost = cost + position.distance(middle);
+                if neighbor_cost < costs[neighbor] {
+                    costs[neighbor] = neighbor_cost;
+                    entries[neighbor] = (middle, Some(portal_index));
+                    queue.push(Visit {
+                        estimated_cost: neighbor_cost + middle.distance(end),
+                        cost: neighbor_cost,
+                        polygon: neighbor,
+                    });
+                }
+            }
+        }
+
+        if costs[end_polygon].is_infinite() {
+            return None;
+        }
+        let mut portals = Vec::new();
+        let mut polygon = end_polygon;
+        while let Some(portal_index) = entries[polygon].1 {
+            let portal = &self.portals[portal_index];
+            let previous = portal.neighbor(polygon);
+            portals.push(portal.left_right(previous));
+            polygon = previous;
+        }
+        portals.reverse();
+        Some(portals)
+    }
+}
+
+/// Twice the signed area of the triangle on the XZ plane, positive if `c` is on the left of the
+/// line from `a` to `b`, when seen from above.
+fn signed_area(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    (b.z - a.z) * (c.x - a.x) - (b.x - a.x) * (c.z - a.z)
+}
+
+/// Finds the shortest path from `start` to `end` through the portals, with the "simple stupid
+/// funnel algorithm" by Mikko Mononen: a funnel from the last corner of the path is narrowed down
+/// by the ends of the successive portals, until one side crosses the other, which adds a corner.
+fn string_pull(start: Vec3, end: Vec3, portals: &[(Vec3, Vec3)]) -> Vec<Vec3> {
+    let portals: Vec<(Vec3, Vec3)> = std::iter::once((start, start))
+        .chain(portals.iter().copied())
+        .chain(std::iter::once((end, end)))
+        .collect();
+
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut index = 1;
+    while index < portals.len() {
+        let (portal_left, portal_right) = portals[index];
+
+        // Narrow the right side of the funnel
+        if signed_area(apex, right, portal_right) >= 0.0 {
+            if apex == right || signed_area(apex, left, portal_right) < 0.0 {
+                right = portal_right;
+                right_index = index;
+            } else {
+                // The right side crosses the left one, which becomes a corner of the path
+                path.push(left);
+                apex = left;
+                (left, right) = (apex, apex);
+                right_index = left_index;
+                index = left_index + 1;
+                continue;
+            }
+        }
+
+        // Narrow the left side of the funnel
+        if signed_area(apex, left, portal_left) <= 0.0 {
+            if apex == left || signed_area(apex, right, portal_left) > 0.0 {
+                left = portal_left;
+                left_index = index;
+            } else {
+                // The left side crosses the right one, which becomes a corner of the path
+                path.push(right);
+                apex = right;
+                (left, right) = (apex, apex);
+                left_index = right_index;
+                index = right_index + 1;
+                continue;
+            }
+        }
+
+        index += 1;
+    }
+
+    path.push(end);
+    path.dedup();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NavMesh, NavMeshSettings};
+    use bevy_math::Vec3;
+
+    /// The triangles of a box from `min` to `max`, with its top facing up.
+    fn cuboid(min: Vec3, max: Vec3) -> Vec<[Vec3; 3]> {
+        let corner = |x: bool, y: bool, z: bool| {
+            Vec3::new(
+                if x { max.x } else { min.x },
+                if y { max.y } else { min.y },
+                if z { max.z } else { min.z },
+            )
+        };
+        let quad = |a: Vec3, b: Vec3, c: Vec3, d: Vec3| [[a, b, c], [a, c, d]];
+        [
+            quad(
+                corner(false, true, false),
+                corner(false, true, true),
+                corner(true, true, true),
+                corner(true, true, false),
+            ),
+            quad(
+                corner(false, false, false),
+                corner(false, true, false),
+                corner(true, true, false),
+                corner(true, false, false),
+            ),
+            quad(
+                corner(false, false, true),
+                corner(true, false, true),
+                corner(true, true, true),
+                corner(false, true, true),
+            ),
+            quad(
+                corner(false, false, false),
+                corner(false, false, true),
+                corner(false, true, true),
+                corner(false, true, false),
+            ),
+            quad(
+                corner(true, false, false),
+                corner(true, true, false),
+                corner(true, true, true),
+                corner(true, false, true),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    #[test]
+    fn path_around_wall() {
+        // A floor split by a wall too high to climb, with a gap at one end
+        let mut triangles = cuboid(Vec3::new(-10.0, -1.0, -10.0), Vec3::new(10.0, 0.0, 10.0));
+        triangles.extend(cuboid(
+            Vec3::new(-1.0, 0.0, -10.0),
+            Vec3::new(1.0, 1.5, 6.0),
+        ));
+        let settings = NavMeshSettings::default();
+        let navmesh = NavMesh::bake(&settings, triangles);
+        let on_floor = |point: Vec3| point.y.abs() <= settings.cell_height + f32::EPSILON;
+
+        // The agent can't stand under the wall or next to it
+        assert!(navmesh
+            .project_point(Vec3::new(0.0, 0.0, 0.0))
+            .map_or(false, |point| point.y > settings.max_climb));
+        assert_eq!(navmesh.project_point(Vec3::new(1.2, 0.0, 0.0)), None);
+        assert!(navmesh
+            .project_point(Vec3::new(2.0, 0.5, 0.0))
+            .map_or(false, on_floor));
+
+        let start = Vec3::new(-5.0, 0.0, 0.0);
+        let end = Vec3::new(5.0, 0.0, 0.0);
+        let path = navmesh.find_path(start, end).unwrap();
+        // Turning around the two corners at the end of the wall
+        assert_eq!(path.len(), 4);
+        assert_eq!(path[0].x, start.x);
+        assert_eq!(path[3].x, end.x);
+        for corner in &path[1..3] {
+            assert!(corner.z > 6.0 && corner.z < 7.0);
+        }
+        for segment in path.windows(2) {
+            for step in 0..=10 {
+                let point = segment[0].lerp(segment[1], step as f32 / 10.0);
+                assert!(on_floor(point));
+                assert!(navmesh.project_point(point).is_some());
+            }
+        }
+
+        // Unreachable from the top of the wall
+        assert!(navmesh.project_point(Vec3::new(0.0, 1.5, 0.0)).is_some());
+        assert_eq!(navmesh.find_path(Vec3::new(0.0, 1.5, 0.0), end), None);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
-    directional_light_order, point_light_order, AmbientLight, Clusters, CubemapVisibleEntities,
-    DirectionalLight, DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline,
-    NotShadowCaster, PointLight, PointLightShadowMap, SetMeshBindGroup, SpotLight,
-    VisiblePointLights, SHADOW_SHADER_HANDLE,
+    directional_light_order, is_compute_skinned, point_light_order, skinned_vertex_buffer_layout,
+    AmbientLight, Clusters, CubemapVisibleEntities, DirectionalLight, DirectionalLightShadowMap,
+    DrawMesh, GlobalVisiblePointLights, MeshPipeline, NotShadowCaster, PointLight,
+    PointLightShadowMap, SetMeshBindGroup, SpotLight, VisiblePointLights,
+    ATTRIBUTE_VERTEX_ANIMATION_INDEX, SHADOW_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_core_pipeline::core_3d::Transparent3d;
@@ -218,6 +219,7 @@ pub struct ShadowPipeline {
     pub view_layout: BindGroupLayout,
     pub mesh_layout: BindGroupLayout,
     pub skinned_mesh_layout: BindGroupLayout,
+    pub vertex_animated_mesh_layout: BindGroupLayout,
     pub point_light_sampler: Sampler,
     pub directional_light_sampler: Sampler,
 }
@@ -251,6 +253,7 @@ impl FromWorld for ShadowPipeline {
             view_layout,
             mesh_layout: mesh_pipeline.mesh_layout.clone(),
             skinned_mesh_layout,
+            vertex_animated_mesh_layout: mesh_pipeline.vertex_animated_mesh_layout.clone(),
             point_light_sampler: render_device.create_sampler(&SamplerDescriptor {
                 address_mode_u: AddressMode::ClampToEdge,
                 address_mode_v: AddressMode::ClampToEdge,
@@ -316,7 +319,11 @@ impl SpecializedMeshPipeline for ShadowPipeline {
         key: Self::Key,
         layout: &MeshVertexBufferLayout,
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
-        let mut vertex_attributes = vec![Mesh::ATTRIBUTE_POSITION.at_shader_location(0)];
+        let compute_skinned = is_compute_skinned(layout);
+        let mut vertex_attributes = Vec::new();
+        if !compute_skinned {
+            vertex_attributes.push(Mesh::ATTRIBUTE_POSITION.at_shader_location(0));
+        }
 
         let mut bind_group_layout = vec![self.view_layout.clone()];
         let mut shader_defs = Vec::new();
@@ -325,7 +332,14 @@ impl SpecializedMeshPipeline for ShadowPipeline {
             MAX_DIRECTIONAL_LIGHTS as u32,
         ));
 
-        if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
+        if layout.contains(ATTRIBUTE_VERTEX_ANIMATION_INDEX) {
+            shader_defs.push("VERTEX_ANIMATED".into());
+            vertex_attributes.push(ATTRIBUTE_VERTEX_ANIMATION_INDEX.at_shader_location(6));
+            bind_group_layout.push(self.vertex_animated_mesh_layout.clone());
+        } else if compute_skinned {
+            shader_defs.push("COMPUTE_SKINNED".into());
+            bind_group_layout.push(self.mesh_layout.clone());
+        } else if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
             && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
         {
             shader_defs.push("SKINNED".into());
@@ -336,14 +350,19 @@ impl SpecializedMeshPipeline for ShadowPipeline {
             bind_group_layout.push(self.mesh_layout.clone());
         }
 
-        let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
+        let mut vertex_buffer_layouts = vec![layout.get_layout(&vertex_attributes)?];
+        if compute_skinned {
+            vertex_buffer_layouts.push(skinned_vertex_buffer_layout(&[
+                Mesh::ATTRIBUTE_POSITION.at_shader_location(0)
+            ]));
+        }
 
         Ok(RenderPipelineDescriptor {
             vertex: VertexState {
                 shader: SHADOW_SHADER_HANDLE.typed::<Shader>(),
                 entry_point: "vertex".into(),
                 shader_defs,
-                buffers: vec![vertex_buffer_layout],
+                buffers: vertex_buffer_layouts,
             },
             fragment: None,
             layout: Some(bind_group_layout),
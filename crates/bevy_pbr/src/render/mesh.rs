@@ -1,7 +1,12 @@
 use crate::{
-    GlobalLightMeta, GpuLights, GpuPointLights, LightMeta, NotShadowCaster, NotShadowReceiver,
-    ShadowPipeline, ViewClusterBindings, ViewLightsUniformOffset, ViewShadowBindings,
-    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_DIRECTIONAL_LIGHTS,
+    extract_vertex_animations, is_compute_skinned, prepare_skinned_vertex_buffers,
+    skinned_vertex_buffer_layout, ComputeSkinningNode, ExtractedVertexAnimation, GlobalLightMeta,
+    GpuLights, GpuPointLights, LightMeta, NotShadowCaster, NotShadowReceiver, ShadowPipeline,
+    SkinnedVertexBuffer, SkinnedVertexBuffers, SkinningDispatches, SkinningPipeline,
+    VertexAnimation, VertexAnimationUniform, ViewClusterBindings, ViewLightsUniformOffset,
+    ViewShadowBindings, ATTRIBUTE_VERTEX_ANIMATION_INDEX, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    COMPUTE_SKINNING, COMPUTE_SKINNING_NODE, MAX_DIRECTIONAL_LIGHTS,
+    SKINNING_COMPUTE_SHADER_HANDLE, VERTEX_ANIMATION_SHADER_HANDLE,
 };
 use bevy_app::Plugin;
 use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
@@ -20,6 +25,7 @@ use bevy_render::{
         GpuBufferInfo, Mesh, MeshVertexBufferLayout,
     },
     render_asset::RenderAssets,
+    render_graph::RenderGraph,
     render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
@@ -30,6 +36,7 @@ use bevy_render::{
     Extract, RenderApp, RenderStage,
 };
 use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
 use std::num::NonZeroU64;
 
 #[derive(Default)]
@@ -91,8 +98,22 @@ impl Plugin for MeshRenderPlugin {
         );
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            SKINNING_COMPUTE_SHADER_HANDLE,
+            "skinning_compute.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VERTEX_ANIMATION_SHADER_HANDLE,
+            "vertex_animation.wgsl",
+            Shader::from_wgsl
+        );
 
-        app.add_plugin(UniformComponentPlugin::<MeshUniform>::default());
+        app.register_type::<VertexAnimation>()
+            .add_plugin(UniformComponentPlugin::<MeshUniform>::default())
+            .add_plugin(UniformComponentPlugin::<VertexAnimationUniform>::default());
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -100,9 +121,27 @@ impl Plugin for MeshRenderPlugin {
                 .init_resource::<SkinnedMeshUniform>()
                 .add_system_to_stage(RenderStage::Extract, extract_meshes)
                 .add_system_to_stage(RenderStage::Extract, extract_skinned_meshes)
+                .add_system_to_stage(RenderStage::Extract, extract_vertex_animations)
                 .add_system_to_stage(RenderStage::Prepare, prepare_skinned_meshes)
                 .add_system_to_stage(RenderStage::Queue, queue_mesh_bind_group)
                 .add_system_to_stage(RenderStage::Queue, queue_mesh_view_bind_groups);
+
+            if COMPUTE_SKINNING {
+                render_app
+                    .init_resource::<SkinningPipeline>()
+                    .init_resource::<SkinnedVertexBuffers>()
+                    .init_resource::<SkinningDispatches>()
+                    .add_system_to_stage(
+                        RenderStage::Prepare,
+                        prepare_skinned_vertex_buffers.after(prepare_skinned_meshes),
+                    );
+                let mut graph = render_app.world.resource_mut::<RenderGraph>();
+                graph.add_node(COMPUTE_SKINNING_NODE, ComputeSkinningNode);
+                graph.add_node_edge(
+                    COMPUTE_SKINNING_NODE,
+                    bevy_render::main_graph::node::CAMERA_DRIVER,
+                );
+            }
         }
     }
 }
@@ -256,6 +295,7 @@ pub struct MeshPipeline {
     pub view_layout: BindGroupLayout,
     pub mesh_layout: BindGroupLayout,
     pub skinned_mesh_layout: BindGroupLayout,
+    pub vertex_animated_mesh_layout: BindGroupLayout,
     // This dummy white texture is to be used in place of optional StandardMaterial textures
     pub dummy_white_gpu_image: GpuImage,
     pub clustered_forward_buffer_binding_type: BufferBindingType,
@@ -429,6 +469,38 @@ impl FromWorld for MeshPipeline {
                 label: Some("skinned_mesh_layout"),
             });
 
+        let vertex_animation_texture = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::VERTEX,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: false },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let vertex_animated_mesh_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                entries: &[
+                    mesh_binding,
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::VERTEX,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: Some(VertexAnimationUniform::min_size()),
+                        },
+                        count: None,
+                    },
+                    // Positions
+                    vertex_animation_texture(2),
+                    // Normals
+                    vertex_animation_texture(3),
+                ],
+                label: Some("vertex_animated_mesh_layout"),
+            });
+
         // A 1x1x1 'all 1.0' texture to use as a dummy texture to use in place of optional StandardMaterial textures
         let dummy_white_gpu_image = {
             let image = Image::new_fill(
@@ -482,6 +554,7 @@ impl FromWorld for MeshPipeline {
             view_layout,
             mesh_layout,
             skinned_mesh_layout,
+            vertex_animated_mesh_layout,
             clustered_forward_buffer_binding_type,
             dummy_white_gpu_image,
         }
@@ -576,15 +649,28 @@ impl SpecializedMeshPipeline for MeshPipeline {
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
         let mut shader_defs = Vec::new();
         let mut vertex_attributes = Vec::new();
+        // The attributes skinned by the compute skinning pass, read from the skinned vertex buffer
+        let mut skinned_attributes = Vec::new();
+        let compute_skinned = is_compute_skinned(layout);
+        let skinnable_attributes = if compute_skinned {
+            &mut skinned_attributes
+        } else {
+            &mut vertex_attributes
+        };
 
         if layout.contains(Mesh::ATTRIBUTE_POSITION) {
             shader_defs.push("VERTEX_POSITIONS".into());
-            vertex_attributes.push(Mesh::ATTRIBUTE_POSITION.at_shader_location(0));
+            skinnable_attributes.push(Mesh::ATTRIBUTE_POSITION.at_shader_location(0));
         }
 
         if layout.contains(Mesh::ATTRIBUTE_NORMAL) {
             shader_defs.push("VERTEX_NORMALS".into());
-            vertex_attributes.push(Mesh::ATTRIBUTE_NORMAL.at_shader_location(1));
+            skinnable_attributes.push(Mesh::ATTRIBUTE_NORMAL.at_shader_location(1));
+        }
+
+        if layout.contains(Mesh::ATTRIBUTE_TANGENT) {
+            shader_defs.push("VERTEX_TANGENTS".into());
+            skinnable_attributes.push(Mesh::ATTRIBUTE_TANGENT.at_shader_location(3));
         }
 
         shader_defs.push(ShaderDefVal::UInt(
@@ -597,18 +683,20 @@ impl SpecializedMeshPipeline for MeshPipeline {
             vertex_attributes.push(Mesh::ATTRIBUTE_UV_0.at_shader_location(2));
         }
 
-        if layout.contains(Mesh::ATTRIBUTE_TANGENT) {
-            shader_defs.push("VERTEX_TANGENTS".into());
-            vertex_attributes.push(Mesh::ATTRIBUTE_TANGENT.at_shader_location(3));
-        }
-
         if layout.contains(Mesh::ATTRIBUTE_COLOR) {
             shader_defs.push("VERTEX_COLORS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(4));
         }
 
         let mut bind_group_layout = vec![self.view_layout.clone()];
-        if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
+        if layout.contains(ATTRIBUTE_VERTEX_ANIMATION_INDEX) {
+            shader_defs.push("VERTEX_ANIMATED".into());
+            vertex_attributes.push(ATTRIBUTE_VERTEX_ANIMATION_INDEX.at_shader_location(7));
+            bind_group_layout.push(self.vertex_animated_mesh_layout.clone());
+        } else if compute_skinned {
+            shader_defs.push("COMPUTE_SKINNED".into());
+            bind_group_layout.push(self.mesh_layout.clone());
+        } else if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
             && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
         {
             shader_defs.push("SKINNED".into());
@@ -619,7 +707,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             bind_group_layout.push(self.mesh_layout.clone());
         };
 
-        let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
+        let mut vertex_buffer_layouts = vec![layout.get_layout(&vertex_attributes)?];
+        if compute_skinned {
+            vertex_buffer_layouts.push(skinned_vertex_buffer_layout(&skinned_attributes));
+        }
 
         let (label, blend, depth_write_enabled);
         if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
@@ -656,7 +747,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 shader: MESH_SHADER_HANDLE.typed::<Shader>(),
                 entry_point: "vertex".into(),
                 shader_defs: shader_defs.clone(),
-                buffers: vec![vertex_buffer_layout],
+                buffers: vertex_buffer_layouts,
             },
             fragment: Some(FragmentState {
                 shader: MESH_SHADER_HANDLE.typed::<Shader>(),
@@ -708,14 +799,20 @@ impl SpecializedMeshPipeline for MeshPipeline {
 pub struct MeshBindGroup {
     pub normal: BindGroup,
     pub skinned: Option<BindGroup>,
+    /// The bind groups of vertex animated meshes, for each pair of position and normal textures.
+    pub vertex_animated: HashMap<(Handle<Image>, Handle<Image>), BindGroup>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn queue_mesh_bind_group(
     mut commands: Commands,
     mesh_pipeline: Res<MeshPipeline>,
     render_device: Res<RenderDevice>,
     mesh_uniforms: Res<ComponentUniforms<MeshUniform>>,
     skinned_mesh_uniform: Res<SkinnedMeshUniform>,
+    vertex_animation_uniforms: Res<ComponentUniforms<VertexAnimationUniform>>,
+    vertex_animations: Query<&ExtractedVertexAnimation>,
+    images: Res<RenderAssets<Image>>,
 ) {
     if let Some(mesh_binding) = mesh_uniforms.uniforms().binding() {
         let mut mesh_bind_group = MeshBindGroup {
@@ -728,8 +825,46 @@ pub fn queue_mesh_bind_group(
                 layout: &mesh_pipeline.mesh_layout,
             }),
             skinned: None,
+            vertex_animated: HashMap::default(),
         };
 
+        if let Some(vertex_animation_binding) = vertex_animation_uniforms.uniforms().binding() {
+            for animation in &vertex_animations {
+                let textures = (animation.positions.clone(), animation.normals.clone());
+                if mesh_bind_group.vertex_animated.contains_key(&textures) {
+                    continue;
+                }
+                let (Some(positions), Some(normals)) =
+                    (images.get(&textures.0), images.get(&textures.1))
+                else {
+                    continue;
+                };
+                let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: mesh_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: vertex_animation_binding.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: BindingResource::TextureView(&positions.texture_view),
+                        },
+                        BindGroupEntry {
+                            binding: 3,
+                            resource: BindingResource::TextureView(&normals.texture_view),
+                        },
+                    ],
+                    label: Some("vertex_animated_mesh_bind_group"),
+                    layout: &mesh_pipeline.vertex_animated_mesh_layout,
+                });
+                mesh_bind_group.vertex_animated.insert(textures, bind_group);
+            }
+        }
+
         if let Some(skinned_joints_buffer) = skinned_mesh_uniform.buffer.buffer() {
             mesh_bind_group.skinned = Some(render_device.create_bind_group(&BindGroupDescriptor {
                 entries: &[
@@ -908,15 +1043,40 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMeshBindGroup<I> {
     type ItemWorldQuery = (
         Read<DynamicUniformIndex<MeshUniform>>,
         Option<Read<SkinnedMeshJoints>>,
+        Option<Read<SkinnedVertexBuffer>>,
+        Option<(
+            Read<DynamicUniformIndex<VertexAnimationUniform>>,
+            Read<ExtractedVertexAnimation>,
+        )>,
     );
     #[inline]
     fn render<'w>(
         _item: &P,
         _view: (),
-        (mesh_index, skinned_mesh_joints): ROQueryItem<'_, Self::ItemWorldQuery>,
+        (mesh_index, skinned_mesh_joints, skinned_vertex_buffer, vertex_animation): ROQueryItem<
+            '_,
+            Self::ItemWorldQuery,
+        >,
         mesh_bind_group: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
+        if let Some((animation_index, animation)) = vertex_animation {
+            // The textures of the animation may not be loaded yet
+            let textures = (animation.positions.clone(), animation.normals.clone());
+            let Some(bind_group) = mesh_bind_group.into_inner().vertex_animated.get(&textures)
+            else {
+                return RenderCommandResult::Failure;
+            };
+            pass.set_bind_group(
+                I,
+                bind_group,
+                &[mesh_index.index(), animation_index.index()],
+            );
+            return RenderCommandResult::Success;
+        }
+
+        // Meshes skinned by the compute skinning pass don't need their joints anymore
+        let skinned_mesh_joints = skinned_mesh_joints.filter(|_| skinned_vertex_buffer.is_none());
         if let Some(joints) = skinned_mesh_joints {
             pass.set_bind_group(
                 I,
@@ -938,17 +1098,25 @@ pub struct DrawMesh;
 impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
     type Param = SRes<RenderAssets<Mesh>>;
     type ViewWorldQuery = ();
-    type ItemWorldQuery = Read<Handle<Mesh>>;
+    type ItemWorldQuery = (Read<Handle<Mesh>>, Option<Read<SkinnedVertexBuffer>>);
     #[inline]
     fn render<'w>(
         _item: &P,
         _view: (),
-        mesh_handle: ROQueryItem<'_, Self::ItemWorldQuery>,
+        (mesh_handle, skinned_vertex_buffer): ROQueryItem<'w, Self::ItemWorldQuery>,
         meshes: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         if let Some(gpu_mesh) = meshes.into_inner().get(mesh_handle) {
             pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+            if is_compute_skinned(&gpu_mesh.layout) {
+                // The pipelines of compute skinned meshes read the skinned vertices from a second
+                // vertex buffer
+                let Some(skinned_vertex_buffer) = skinned_vertex_buffer else {
+                    return RenderCommandResult::Failure;
+                };
+                pass.set_vertex_buffer(1, skinned_vertex_buffer.buffer.slice(..));
+            }
             match &gpu_mesh.buffer_info {
                 GpuBufferInfo::Indexed {
                     buffer,
@@ -24,6 +24,9 @@ struct Vertex {
     @location(5) joint_indices: vec4<u32>,
     @location(6) joint_weights: vec4<f32>,
 #endif
+#ifdef VERTEX_ANIMATED
+    @location(7) animation_index: u32,
+#endif
 };
 
 struct VertexOutput {
@@ -32,22 +35,46 @@ struct VertexOutput {
 };
 
 @vertex
-fn vertex(vertex: Vertex) -> VertexOutput {
+fn vertex(mesh_vertex: Vertex) -> VertexOutput {
+    var vertex = mesh_vertex;
+#ifdef VERTEX_ANIMATED
+#ifdef VERTEX_POSITIONS
+    vertex.position = vertex_animation_position(vertex.animation_index);
+#endif
+#ifdef VERTEX_NORMALS
+    vertex.normal = vertex_animation_normal(vertex.animation_index);
+#endif
+#endif
+
     var out: VertexOutput;
 
 #ifdef SKINNED
     var model = skin_model(vertex.joint_indices, vertex.joint_weights);
+#else
+#ifdef COMPUTE_SKINNED
+    // The vertices were skinned to world space by the compute skinning pass
+    var model = mat4x4<f32>(
+        vec4<f32>(1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, 1.0, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 1.0, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0)
+    );
 #else
     var model = mesh.model;
 #endif
+#endif
 
 #ifdef VERTEX_NORMALS
 #ifdef SKINNED
     out.world_normal = skin_normals(model, vertex.normal);
+#else
+#ifdef COMPUTE_SKINNED
+    out.world_normal = vertex.normal;
 #else
     out.world_normal = mesh_normal_local_to_world(vertex.normal);
 #endif
 #endif
+#endif
 
 #ifdef VERTEX_POSITIONS
     out.world_position = mesh_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
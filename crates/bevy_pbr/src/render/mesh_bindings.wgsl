@@ -9,3 +9,12 @@ var<uniform> mesh: Mesh;
 var<uniform> joint_matrices: SkinnedMesh;
 #import bevy_pbr::skinning
 #endif
+#ifdef VERTEX_ANIMATED
+@group(2) @binding(1)
+var<uniform> vertex_animation: VertexAnimation;
+@group(2) @binding(2)
+var vertex_animation_positions: texture_2d<f32>;
+@group(2) @binding(3)
+var vertex_animation_normals: texture_2d<f32>;
+#import bevy_pbr::vertex_animation
+#endif
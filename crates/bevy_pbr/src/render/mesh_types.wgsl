@@ -13,6 +13,15 @@ struct SkinnedMesh {
 };
 #endif
 
+#ifdef VERTEX_ANIMATED
+struct VertexAnimation {
+    vertex_count: u32,
+    frame: u32,
+    next_frame: u32,
+    interpolation: f32,
+};
+#endif
+
 let MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 let MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
@@ -1,5 +1,9 @@
 mod light;
 mod mesh;
+mod skinning;
+mod vertex_animation;
 
 pub use light::*;
 pub use mesh::*;
+pub use skinning::*;
+pub use vertex_animation::*;
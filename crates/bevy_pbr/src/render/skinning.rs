@@ -0,0 +1,375 @@
+use crate::{SkinnedMeshJoints, SkinnedMeshUniform, JOINT_BUFFER_SIZE};
+use bevy_asset::{Handle, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::{GpuMesh, Mesh, MeshVertexAttribute, MeshVertexBufferLayout, VertexAttributeDescriptor},
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraphContext},
+    render_resource::*,
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+};
+use bevy_utils::{HashMap, HashSet};
+use std::num::NonZeroU64;
+
+/// Whether skinned meshes are skinned once per frame by the compute skinning pass, instead of
+/// in the vertex shader of each pass. Compute shaders aren't available on WebGL.
+pub(crate) const COMPUTE_SKINNING: bool = cfg!(not(feature = "webgl"));
+
+/// Label for the compute skinning node of the main render graph, which runs before the cameras
+/// are rendered.
+pub const COMPUTE_SKINNING_NODE: &str = "compute_skinning";
+
+pub const SKINNING_COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7307429612468130214);
+
+/// Size in bytes of a vertex written by the compute skinning pass: a position, a normal and a
+/// tangent, in world space.
+const SKINNED_VERTEX_SIZE: u64 = 10 * 4;
+const WORKGROUP_SIZE: u32 = 64;
+/// Offset of the attributes that a mesh doesn't have.
+const NO_ATTRIBUTE: u32 = u32::MAX;
+
+/// The layout of the vertex buffer written by the compute skinning pass, with the given
+/// attributes of the skinned vertices at their shader locations.
+///
+/// Only [`Mesh::ATTRIBUTE_POSITION`], [`Mesh::ATTRIBUTE_NORMAL`] and [`Mesh::ATTRIBUTE_TANGENT`]
+/// are skinned, the other attributes are read from the vertex buffer of the mesh.
+pub fn skinned_vertex_buffer_layout(
+    attributes: &[VertexAttributeDescriptor],
+) -> VertexBufferLayout {
+    VertexBufferLayout {
+        array_stride: SKINNED_VERTEX_SIZE,
+        step_mode: VertexStepMode::Vertex,
+        attributes: attributes
+            .iter()
+            .filter_map(|attribute| {
+                let (format, offset) = if attribute.id == Mesh::ATTRIBUTE_POSITION.id {
+                    (VertexFormat::Float32x3, 0)
+                } else if attribute.id == Mesh::ATTRIBUTE_NORMAL.id {
+                    (VertexFormat::Float32x3, 3 * 4)
+                } else if attribute.id == Mesh::ATTRIBUTE_TANGENT.id {
+                    (VertexFormat::Float32x4, 6 * 4)
+                } else {
+                    return None;
+                };
+                Some(VertexAttribute {
+                    format,
+                    offset,
+                    shader_location: attribute.shader_location,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Whether a mesh is skinned by the compute skinning pass, in which case its pipelines read the
+/// skinned attributes from the vertex buffer in [`SkinnedVertexBuffer`].
+pub fn is_compute_skinned(layout: &MeshVertexBufferLayout) -> bool {
+    COMPUTE_SKINNING
+        && layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
+        && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
+}
+
+/// The vertex buffer with the vertices of a skinned mesh entity, skinned this frame by the
+/// compute skinning pass. It is used instead of vertex skinning by every pass drawing the mesh.
+#[derive(Component, Clone)]
+pub struct SkinnedVertexBuffer {
+    pub buffer: Buffer,
+}
+
+/// Parameters of the compute skinning of a mesh. Offsets and strides are in 4 bytes words.
+#[derive(ShaderType, Clone)]
+struct SkinningParams {
+    vertex_count: u32,
+    vertex_stride: u32,
+    position_offset: u32,
+    normal_offset: u32,
+    tangent_offset: u32,
+    joint_index_offset: u32,
+    joint_weight_offset: u32,
+}
+
+impl SkinningParams {
+    fn new(gpu_mesh: &GpuMesh) -> Option<Self> {
+        let layout = &gpu_mesh.layout;
+        let stride = layout.layout().array_stride;
+        if stride == 0 {
+            return None;
+        }
+        let offset = |attribute: MeshVertexAttribute| {
+            layout
+                .attribute_offset(attribute)
+                .map_or(NO_ATTRIBUTE, |offset| (offset / 4) as u32)
+        };
+        Some(Self {
+            vertex_count: gpu_mesh.vertex_count,
+            vertex_stride: (stride / 4) as u32,
+            position_offset: layout
+                .attribute_offset(Mesh::ATTRIBUTE_POSITION)
+                .map(|offset| (offset / 4) as u32)?,
+            normal_offset: offset(Mesh::ATTRIBUTE_NORMAL),
+            tangent_offset: offset(Mesh::ATTRIBUTE_TANGENT),
+            joint_index_offset: offset(Mesh::ATTRIBUTE_JOINT_INDEX),
+            joint_weight_offset: offset(Mesh::ATTRIBUTE_JOINT_WEIGHT),
+        })
+    }
+}
+
+#[derive(Resource)]
+pub struct SkinningPipeline {
+    pub layout: BindGroupLayout,
+    pub pipeline_id: CachedComputePipelineId,
+}
+
+impl FromWorld for SkinningPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                // Skinning parameters
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(SkinningParams::min_size()),
+                    },
+                    count: None,
+                },
+                // Vertices of the mesh
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Joints
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: BufferSize::new(JOINT_BUFFER_SIZE as u64),
+                    },
+                    count: None,
+                },
+                // Skinned vertices
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+            label: Some("skinning_layout"),
+        });
+        let pipeline_id =
+            world
+                .resource::<PipelineCache>()
+                .queue_compute_pipeline(ComputePipelineDescriptor {
+                    label: Some("skinning_pipeline".into()),
+                    layout: Some(vec![layout.clone()]),
+                    shader: SKINNING_COMPUTE_SHADER_HANDLE.typed::<Shader>(),
+                    shader_defs: vec!["SKINNED".into()],
+                    entry_point: "skin".into(),
+                });
+        Self {
+            layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// The skinned vertex buffers of the skinned mesh entities and their size, kept from one frame
+/// to the next.
+#[derive(Resource, Default)]
+pub struct SkinnedVertexBuffers {
+    buffers: HashMap<Entity, (Buffer, u64)>,
+    params: DynamicUniformBuffer<SkinningParams>,
+}
+
+struct SkinningDispatch {
+    bind_group: BindGroup,
+    params_offset: u32,
+    joints_offset: u32,
+    workgroups: u32,
+}
+
+/// The meshes to skin this frame.
+#[derive(Resource, Default)]
+pub struct SkinningDispatches {
+    dispatches: Vec<SkinningDispatch>,
+}
+
+/// Prepares the skinned vertex buffer and the compute skinning of each skinned mesh entity.
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_skinned_vertex_buffers(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    skinning_pipeline: Res<SkinningPipeline>,
+    skinned_mesh_uniform: Res<SkinnedMeshUniform>,
+    meshes: Res<RenderAssets<Mesh>>,
+    mut skinned_vertex_buffers: ResMut<SkinnedVertexBuffers>,
+    mut skinning_dispatches: ResMut<SkinningDispatches>,
+    skinned_meshes: Query<(Entity, &Handle<Mesh>, &SkinnedMeshJoints)>,
+) {
+    let skinned_vertex_buffers = skinned_vertex_buffers.as_mut();
+    skinning_dispatches.dispatches.clear();
+    skinned_vertex_buffers.params.clear();
+
+    let mut prepared = Vec::new();
+    for (entity, mesh_handle, joints) in &skinned_meshes {
+        let Some(gpu_mesh) = meshes.get(mesh_handle) else {
+            continue;
+        };
+        if !is_compute_skinned(&gpu_mesh.layout) {
+            continue;
+        }
+        let Some(params) = SkinningParams::new(gpu_mesh) else {
+            continue;
+        };
+        let size = params.vertex_count as u64 * SKINNED_VERTEX_SIZE;
+        if size == 0 {
+            continue;
+        }
+        let (buffer, _) = skinned_vertex_buffers
+            .buffers
+            .entry(entity)
+            .and_modify(|(buffer, buffer_size)| {
+                if *buffer_size != size {
+                    *buffer = create_skinned_vertex_buffer(&render_device, size);
+                    *buffer_size = size;
+                }
+            })
+            .or_insert_with(|| (create_skinned_vertex_buffer(&render_device, size), size))
+            .clone();
+        let workgroups = (params.vertex_count + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
+        let params_offset = skinned_vertex_buffers.params.push(params);
+        prepared.push((
+            entity,
+            gpu_mesh.vertex_buffer.clone(),
+            buffer,
+            params_offset,
+            joints.index,
+            workgroups,
+        ));
+    }
+
+    // Drop the buffers of the entities that aren't skinned anymore
+    let skinned_entities = prepared
+        .iter()
+        .map(|(entity, ..)| *entity)
+        .collect::<HashSet<_>>();
+    skinned_vertex_buffers
+        .buffers
+        .retain(|entity, _| skinned_entities.contains(entity));
+
+    skinned_vertex_buffers
+        .params
+        .write_buffer(&render_device, &render_queue);
+    let (Some(params_binding), Some(joints_buffer)) = (
+        skinned_vertex_buffers.params.binding(),
+        skinned_mesh_uniform.buffer.buffer(),
+    ) else {
+        return;
+    };
+
+    let mut values = Vec::with_capacity(prepared.len());
+    for (entity, vertex_buffer, buffer, params_offset, joints_offset, workgroups) in prepared {
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_binding.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: vertex_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Buffer(BufferBinding {
+                        buffer: joints_buffer,
+                        offset: 0,
+                        size: Some(NonZeroU64::new(JOINT_BUFFER_SIZE as u64).unwrap()),
+                    }),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: buffer.as_entire_binding(),
+                },
+            ],
+            label: Some("skinning_bind_group"),
+            layout: &skinning_pipeline.layout,
+        });
+        skinning_dispatches.dispatches.push(SkinningDispatch {
+            bind_group,
+            params_offset,
+            joints_offset,
+            workgroups,
+        });
+        values.push((entity, SkinnedVertexBuffer { buffer }));
+    }
+    commands.insert_or_spawn_batch(values);
+}
+
+fn create_skinned_vertex_buffer(render_device: &RenderDevice, size: u64) -> Buffer {
+    render_device.create_buffer(&BufferDescriptor {
+        label: Some("skinned_vertex_buffer"),
+        size,
+        usage: BufferUsages::VERTEX | BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    })
+}
+
+/// Skins the vertices of the skinned meshes, before any camera is rendered.
+#[derive(Default)]
+pub struct ComputeSkinningNode;
+
+impl Node for ComputeSkinningNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let skinning_pipeline = world.resource::<SkinningPipeline>();
+        let skinning_dispatches = world.resource::<SkinningDispatches>();
+        if skinning_dispatches.dispatches.is_empty() {
+            return Ok(());
+        }
+        let Some(pipeline) = pipeline_cache.get_compute_pipeline(skinning_pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+
+        let mut pass = render_context
+            .command_encoder
+            .begin_compute_pass(&ComputePassDescriptor {
+                label: Some("compute_skinning"),
+            });
+        pass.set_pipeline(pipeline);
+        for dispatch in &skinning_dispatches.dispatches {
+            pass.set_bind_group(
+                0,
+                &dispatch.bind_group,
+                &[dispatch.params_offset, dispatch.joints_offset],
+            );
+            pass.dispatch_workgroups(dispatch.workgroups, 1, 1);
+        }
+        Ok(())
+    }
+}
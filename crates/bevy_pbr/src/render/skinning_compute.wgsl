@@ -0,0 +1,84 @@
+#import bevy_pbr::mesh_types
+
+// Skins the vertices of a mesh to world space, in a vertex buffer read by every pass drawing the
+// mesh. Offsets and strides are in 4 bytes words.
+struct SkinningParams {
+    vertex_count: u32,
+    vertex_stride: u32,
+    position_offset: u32,
+    normal_offset: u32,
+    tangent_offset: u32,
+    joint_index_offset: u32,
+    joint_weight_offset: u32,
+};
+
+// Offset of the attributes that a mesh doesn't have.
+let NO_ATTRIBUTE: u32 = 0xFFFFFFFFu;
+
+@group(0) @binding(0)
+var<uniform> params: SkinningParams;
+@group(0) @binding(1)
+var<storage, read> vertices: array<u32>;
+@group(0) @binding(2)
+var<uniform> joint_matrices: SkinnedMesh;
+@group(0) @binding(3)
+var<storage, read_write> skinned_vertices: array<f32>;
+
+#import bevy_pbr::skinning
+
+fn read_vec3(offset: u32) -> vec3<f32> {
+    return vec3<f32>(
+        bitcast<f32>(vertices[offset]),
+        bitcast<f32>(vertices[offset + 1u]),
+        bitcast<f32>(vertices[offset + 2u])
+    );
+}
+
+fn read_vec4(offset: u32) -> vec4<f32> {
+    return vec4<f32>(read_vec3(offset), bitcast<f32>(vertices[offset + 3u]));
+}
+
+fn write_vec3(offset: u32, value: vec3<f32>) {
+    skinned_vertices[offset] = value.x;
+    skinned_vertices[offset + 1u] = value.y;
+    skinned_vertices[offset + 2u] = value.z;
+}
+
+@compute @workgroup_size(64)
+fn skin(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    let index = invocation_id.x;
+    if (index >= params.vertex_count) {
+        return;
+    }
+    let vertex = index * params.vertex_stride;
+    // The joint indices are 4 u16, packed two by two in little endian
+    let packed_indices = vec2<u32>(
+        vertices[vertex + params.joint_index_offset],
+        vertices[vertex + params.joint_index_offset + 1u]
+    );
+    let joint_indices = vec4<u32>(
+        packed_indices.x & 0xFFFFu,
+        packed_indices.x >> 16u,
+        packed_indices.y & 0xFFFFu,
+        packed_indices.y >> 16u
+    );
+    let joint_weights = read_vec4(vertex + params.joint_weight_offset);
+    let model = skin_model(joint_indices, joint_weights);
+
+    // Skinned vertices are a position, a normal and a tangent
+    let skinned_vertex = index * 10u;
+    let position = model * vec4<f32>(read_vec3(vertex + params.position_offset), 1.0);
+    write_vec3(skinned_vertex, position.xyz);
+    if (params.normal_offset != NO_ATTRIBUTE) {
+        let normal = skin_normals(model, read_vec3(vertex + params.normal_offset));
+        write_vec3(skinned_vertex + 3u, normal);
+    }
+    if (params.tangent_offset != NO_ATTRIBUTE) {
+        let tangent = read_vec4(vertex + params.tangent_offset);
+        let world_tangent = normalize(
+            mat3x3<f32>(model[0].xyz, model[1].xyz, model[2].xyz) * tangent.xyz
+        );
+        write_vec3(skinned_vertex + 6u, world_tangent);
+        skinned_vertices[skinned_vertex + 9u] = tangent.w;
+    }
+}
@@ -0,0 +1,73 @@
+use crate::VertexAnimation;
+use bevy_asset::{Handle, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    render_resource::{Shader, ShaderType},
+    texture::Image,
+    view::ComputedVisibility,
+    Extract,
+};
+use bevy_time::Time;
+
+pub const VERTEX_ANIMATION_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1495834129805123712);
+
+/// The frames of a [`VertexAnimation`] blended this frame.
+#[derive(Component, ShaderType, Clone)]
+pub struct VertexAnimationUniform {
+    pub vertex_count: u32,
+    pub frame: u32,
+    pub next_frame: u32,
+    /// The weight of the next frame.
+    pub interpolation: f32,
+}
+
+impl VertexAnimationUniform {
+    pub fn new(animation: &VertexAnimation, elapsed: f64) -> Self {
+        let frame_count = animation.frame_count.max(1);
+        let frame = animation.frame(elapsed);
+        // Guard against the rounding of the remainder up to the frame count
+        let start = (frame as u32).min(frame_count - 1);
+        Self {
+            vertex_count: animation.vertex_count,
+            frame: start,
+            next_frame: (start + 1) % frame_count,
+            interpolation: frame.fract() as f32,
+        }
+    }
+}
+
+/// The textures of the [`VertexAnimation`] of an entity in the render world.
+#[derive(Component, Clone)]
+pub struct ExtractedVertexAnimation {
+    pub positions: Handle<Image>,
+    pub normals: Handle<Image>,
+}
+
+pub fn extract_vertex_animations(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    time: Extract<Res<Time>>,
+    query: Extract<Query<(Entity, &ComputedVisibility, &VertexAnimation)>>,
+) {
+    let elapsed = time.elapsed_seconds_f64();
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, computed_visibility, animation) in &query {
+        if !computed_visibility.is_visible() {
+            continue;
+        }
+        values.push((
+            entity,
+            (
+                ExtractedVertexAnimation {
+                    positions: animation.positions.clone_weak(),
+                    normals: animation.normals.clone_weak(),
+                },
+                VertexAnimationUniform::new(animation, elapsed),
+            ),
+        ));
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
@@ -0,0 +1,35 @@
+#define_import_path bevy_pbr::vertex_animation
+
+fn vertex_animation_texel(vertex_index: u32, frame: u32) -> vec2<i32> {
+    let width = u32(textureDimensions(vertex_animation_positions).x);
+    let texel = frame * vertex_animation.vertex_count + vertex_index;
+    return vec2<i32>(i32(texel % width), i32(texel / width));
+}
+
+fn vertex_animation_position(vertex_index: u32) -> vec3<f32> {
+    let start = textureLoad(
+        vertex_animation_positions,
+        vertex_animation_texel(vertex_index, vertex_animation.frame),
+        0
+    ).xyz;
+    let end = textureLoad(
+        vertex_animation_positions,
+        vertex_animation_texel(vertex_index, vertex_animation.next_frame),
+        0
+    ).xyz;
+    return mix(start, end, vertex_animation.interpolation);
+}
+
+fn vertex_animation_normal(vertex_index: u32) -> vec3<f32> {
+    let start = textureLoad(
+        vertex_animation_normals,
+        vertex_animation_texel(vertex_index, vertex_animation.frame),
+        0
+    ).xyz;
+    let end = textureLoad(
+        vertex_animation_normals,
+        vertex_animation_texel(vertex_index, vertex_animation.next_frame),
+        0
+    ).xyz;
+    return normalize(mix(start, end, vertex_animation.interpolation));
+}
@@ -10,6 +10,16 @@ var<uniform> joint_matrices: SkinnedMesh;
 #import bevy_pbr::skinning
 #endif
 
+#ifdef VERTEX_ANIMATED
+@group(1) @binding(1)
+var<uniform> vertex_animation: VertexAnimation;
+@group(1) @binding(2)
+var vertex_animation_positions: texture_2d<f32>;
+@group(1) @binding(3)
+var vertex_animation_normals: texture_2d<f32>;
+#import bevy_pbr::vertex_animation
+#endif
+
 // NOTE: Bindings must come before functions that use them!
 #import bevy_pbr::mesh_functions
 
@@ -19,6 +29,9 @@ struct Vertex {
     @location(4) joint_indexes: vec4<u32>,
     @location(5) joint_weights: vec4<f32>,
 #endif
+#ifdef VERTEX_ANIMATED
+    @location(7) animation_index: u32,
+#endif
 };
 
 struct VertexOutput {
@@ -29,12 +42,28 @@ struct VertexOutput {
 fn vertex(vertex: Vertex) -> VertexOutput {
 #ifdef SKINNED
     let model = skin_model(vertex.joint_indexes, vertex.joint_weights);
+#else
+#ifdef COMPUTE_SKINNED
+    // The vertices were skinned to world space by the compute skinning pass
+    let model = mat4x4<f32>(
+        vec4<f32>(1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, 1.0, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 1.0, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0)
+    );
 #else
     let model = mesh.model;
 #endif
+#endif
+
+#ifdef VERTEX_ANIMATED
+    let position = vertex_animation_position(vertex.animation_index);
+#else
+    let position = vertex.position;
+#endif
 
     var out: VertexOutput;
-    out.clip_position = mesh_position_local_to_clip(model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_local_to_clip(model, vec4<f32>(position, 1.0));
     return out;
 }
 
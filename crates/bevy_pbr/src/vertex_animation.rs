@@ -0,0 +1,364 @@
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::{Mat3, Mat4, Vec3};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    mesh::{Mesh, MeshVertexAttribute, VertexAttributeValues},
+    primitives::Aabb,
+    render_resource::{Extent3d, TextureDimension, TextureFormat, VertexFormat},
+    texture::Image,
+};
+use thiserror::Error;
+
+/// The index of each vertex of a mesh baked by a [`VertexAnimationBaker`] in the textures of its
+/// [`VertexAnimation`].
+///
+/// Meshes with this attribute are drawn from the positions and normals of the current frame of
+/// the [`VertexAnimation`] of their entity, with any material.
+pub const ATTRIBUTE_VERTEX_ANIMATION_INDEX: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_AnimationIndex", 2_763_411_005, VertexFormat::Uint32);
+
+/// The maximum width of the textures of a [`VertexAnimation`]. The frames are laid out one after
+/// another, wrapping to the next row at this width.
+pub const VERTEX_ANIMATION_TEXTURE_WIDTH: u32 = 2048;
+
+/// The maximum height of the textures of a [`VertexAnimation`], the smallest maximum texture size
+/// guaranteed by the supported backends.
+const VERTEX_ANIMATION_MAX_TEXTURE_HEIGHT: u32 = 2048;
+
+/// Plays back the vertex animation baked in textures by a [`VertexAnimationBaker`] on a baked
+/// mesh.
+///
+/// The animation loops from the start of the app, the frames are selected on the GPU so each
+/// entity only costs a few bytes of uniform data per frame. Use `speed` and `time_offset` to vary
+/// the playback of the entities sharing the same animation, such as the agents of a crowd.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct VertexAnimation {
+    /// The positions of the vertices for each frame, in the local space of the mesh.
+    pub positions: Handle<Image>,
+    /// The normals of the vertices for each frame, in the local space of the mesh.
+    pub normals: Handle<Image>,
+    /// The number of vertices of the baked mesh.
+    pub vertex_count: u32,
+    /// The number of frames in the textures.
+    pub frame_count: u32,
+    /// The number of frames per second the animation was baked at.
+    pub frame_rate: f32,
+    /// The speed of the playback, `1.0` playing the animation at the speed it was baked at.
+    pub speed: f32,
+    /// The time in seconds the playback is offset by.
+    pub time_offset: f32,
+}
+
+impl Default for VertexAnimation {
+    fn default() -> Self {
+        Self {
+            positions: Default::default(),
+            normals: Default::default(),
+            vertex_count: 0,
+            frame_count: 0,
+            frame_rate: 30.0,
+            speed: 1.0,
+            time_offset: 0.0,
+        }
+    }
+}
+
+impl VertexAnimation {
+    /// Set the speed of the playback.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the time in seconds the playback is offset by.
+    pub fn with_time_offset(mut self, time_offset: f32) -> Self {
+        self.time_offset = time_offset;
+        self
+    }
+
+    /// The frame played `elapsed` seconds after the start of the app, with a fractional part
+    /// interpolating towards the next frame.
+    pub fn frame(&self, elapsed: f64) -> f64 {
+        let frame_count = self.frame_count.max(1) as f64;
+        ((elapsed * self.speed as f64 + self.time_offset as f64) * self.frame_rate as f64)
+            .rem_euclid(frame_count)
+    }
+}
+
+/// An error that occurs when baking a vertex animation.
+#[derive(Error, Debug)]
+pub enum VertexAnimationBakeError {
+    #[error("the mesh is missing the {0} attribute, or it isn't in the expected format")]
+    MissingAttribute(&'static str),
+    #[error("the frame has {frame} vertices but the mesh has {mesh}")]
+    VertexCountMismatch { frame: usize, mesh: usize },
+    #[error("the animation needs {rows} rows of texture, more than the maximum of {max}")]
+    TooManyFrames { rows: u32, max: u32 },
+}
+
+/// Bakes the frames of a skeletal or soft-body animation of a mesh into position and normal
+/// textures, to be played back with a [`VertexAnimation`].
+///
+/// Frames are added at a fixed rate, either as the deformed vertices of the mesh, or as the
+/// joint matrices of a skinned mesh.
+pub struct VertexAnimationBaker<'a> {
+    mesh: &'a Mesh,
+    frame_rate: f32,
+    frame_count: u32,
+    positions: Vec<[f32; 4]>,
+    normals: Vec<[f32; 4]>,
+    min: Vec3,
+    max: Vec3,
+}
+
+impl<'a> VertexAnimationBaker<'a> {
+    /// Create a baker for the animation of `mesh`, with `frame_rate` frames added per second of
+    /// animation.
+    pub fn new(mesh: &'a Mesh, frame_rate: f32) -> Self {
+        Self {
+            mesh,
+            frame_rate,
+            frame_count: 0,
+            positions: Vec::new(),
+            normals: Vec::new(),
+            min: Vec3::splat(f32::MAX),
+            max: Vec3::splat(f32::MIN),
+        }
+    }
+
+    /// Add a frame with the positions and normals of each vertex of the mesh, in its local space.
+    pub fn add_frame(
+        &mut self,
+        positions: &[Vec3],
+        normals: &[Vec3],
+    ) -> Result<&mut Self, VertexAnimationBakeError> {
+        let vertex_count = self.mesh.count_vertices();
+        for frame in [positions.len(), normals.len()] {
+            if frame != vertex_count {
+                return Err(VertexAnimationBakeError::VertexCountMismatch {
+                    frame,
+                    mesh: vertex_count,
+                });
+            }
+        }
+        for (position, normal) in positions.iter().zip(normals) {
+            self.min = self.min.min(*position);
+            self.max = self.max.max(*position);
+            self.positions.push(position.extend(1.0).to_array());
+            self.normals.push(normal.extend(0.0).to_array());
+        }
+        self.frame_count += 1;
+        Ok(self)
+    }
+
+    /// Add a frame of a skinned mesh, with the matrix of each joint transforming the bind pose
+    /// to the local space of the mesh.
+    ///
+    /// This is the [`GlobalTransform`](bevy_transform::components::GlobalTransform) of the joint
+    /// multiplied by its inverse bindpose, relative to the `GlobalTransform` of the mesh.
+    pub fn add_skinned_frame(
+        &mut self,
+        joint_matrices: &[Mat4],
+    ) -> Result<&mut Self, VertexAnimationBakeError> {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            self.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return Err(VertexAnimationBakeError::MissingAttribute(
+                Mesh::ATTRIBUTE_POSITION.name,
+            ));
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            self.mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            return Err(VertexAnimationBakeError::MissingAttribute(
+                Mesh::ATTRIBUTE_NORMAL.name,
+            ));
+        };
+        let Some(VertexAttributeValues::Uint16x4(joint_indices)) =
+            self.mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX)
+        else {
+            return Err(VertexAnimationBakeError::MissingAttribute(
+                Mesh::ATTRIBUTE_JOINT_INDEX.name,
+            ));
+        };
+        let Some(VertexAttributeValues::Float32x4(joint_weights)) =
+            self.mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT)
+        else {
+            return Err(VertexAnimationBakeError::MissingAttribute(
+                Mesh::ATTRIBUTE_JOINT_WEIGHT.name,
+            ));
+        };
+
+        let mut skinned_positions = Vec::with_capacity(positions.len());
+        let mut skinned_normals = Vec::with_capacity(normals.len());
+        for (((position, normal), indices), weights) in positions
+            .iter()
+            .zip(normals)
+            .zip(joint_indices)
+            .zip(joint_weights)
+        {
+            // Same blending of the joint matrices as the skinning shaders
+            let model = indices
+                .iter()
+                .zip(weights)
+                .map(|(&index, &weight)| {
+                    joint_matrices
+                        .get(index as usize)
+                        .map_or(Mat4::ZERO, |joint| *joint * weight)
+                })
+                .fold(Mat4::ZERO, |model, joint| model + joint);
+            skinned_positions.push(model.transform_point3(Vec3::from(*position)));
+            let inverse_transpose = Mat3::from_mat4(model).inverse().transpose();
+            skinned_normals.push((inverse_transpose * Vec3::from(*normal)).normalize_or_zero());
+        }
+        self.add_frame(&skinned_positions, &skinned_normals)
+    }
+
+    /// Bake the frames into textures.
+    ///
+    /// The baked mesh has the [`ATTRIBUTE_VERTEX_ANIMATION_INDEX`] attribute, and no joint
+    /// attributes anymore.
+    pub fn bake(self) -> Result<VertexAnimationBake, VertexAnimationBakeError> {
+        let vertex_count = self.mesh.count_vertices() as u32;
+        let texels = self.positions.len() as u32;
+        let width = texels.clamp(1, VERTEX_ANIMATION_TEXTURE_WIDTH);
+        let rows = (texels + width - 1) / width;
+        if rows > VERTEX_ANIMATION_MAX_TEXTURE_HEIGHT {
+            return Err(VertexAnimationBakeError::TooManyFrames {
+                rows,
+                max: VERTEX_ANIMATION_MAX_TEXTURE_HEIGHT,
+            });
+        }
+        let texture = |mut texels: Vec<[f32; 4]>| {
+            texels.resize((width * rows.max(1)) as usize, [0.0; 4]);
+            Image::new(
+                Extent3d {
+                    width,
+                    height: rows.max(1),
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                bytemuck::cast_slice(&texels).to_vec(),
+                TextureFormat::Rgba32Float,
+            )
+        };
+
+        let mut mesh = self.mesh.clone();
+        mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_INDEX);
+        mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT);
+        mesh.insert_attribute(
+            ATTRIBUTE_VERTEX_ANIMATION_INDEX,
+            VertexAttributeValues::Uint32((0..vertex_count).collect()),
+        );
+
+        Ok(VertexAnimationBake {
+            mesh,
+            positions: texture(self.positions),
+            normals: texture(self.normals),
+            aabb: if self.frame_count > 0 {
+                Aabb::from_min_max(self.min, self.max)
+            } else {
+                Aabb::from_min_max(Vec3::ZERO, Vec3::ZERO)
+            },
+            vertex_count,
+            frame_count: self.frame_count,
+            frame_rate: self.frame_rate,
+        })
+    }
+}
+
+/// A vertex animation baked by a [`VertexAnimationBaker`].
+#[derive(Clone, Debug)]
+pub struct VertexAnimationBake {
+    /// The mesh to draw with the animation.
+    pub mesh: Mesh,
+    /// The positions of the vertices for each frame.
+    pub positions: Image,
+    /// The normals of the vertices for each frame.
+    pub normals: Image,
+    /// The bounds of the vertices over all the frames.
+    ///
+    /// Insert it on the entities playing the animation, the bounds computed from the mesh only
+    /// cover its rest pose.
+    pub aabb: Aabb,
+    /// The number of vertices of the mesh.
+    pub vertex_count: u32,
+    /// The number of baked frames.
+    pub frame_count: u32,
+    /// The number of frames per second of animation.
+    pub frame_rate: f32,
+}
+
+impl VertexAnimationBake {
+    /// Add the baked mesh and textures to the assets, returning the handle of the mesh and a
+    /// [`VertexAnimation`] playing the animation at its baked speed.
+    pub fn add_to_assets(
+        self,
+        meshes: &mut Assets<Mesh>,
+        images: &mut Assets<Image>,
+    ) -> (Handle<Mesh>, VertexAnimation) {
+        let animation = VertexAnimation {
+            positions: images.add(self.positions),
+            normals: images.add(self.normals),
+            vertex_count: self.vertex_count,
+            frame_count: self.frame_count,
+            frame_rate: self.frame_rate,
+            ..Default::default()
+        };
+        (meshes.add(self.mesh), animation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::render_resource::PrimitiveTopology;
+
+    #[test]
+    fn bake_skinned_frames() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; 2]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0, 0, 0, 0], [1, 0, 0, 0]]),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vec![[1.0, 0.0, 0.0, 0.0]; 2]);
+
+        let mut baker = VertexAnimationBaker::new(&mesh, 30.0);
+        baker
+            .add_skinned_frame(&[Mat4::IDENTITY, Mat4::IDENTITY])
+            .unwrap()
+            .add_skinned_frame(&[Mat4::IDENTITY, Mat4::from_translation(Vec3::Y)])
+            .unwrap();
+        assert!(baker.add_frame(&[Vec3::ZERO], &[Vec3::Y]).is_err());
+        let bake = baker.bake().unwrap();
+
+        assert_eq!(2, bake.frame_count);
+        assert!(bake
+            .mesh
+            .contains_attribute(ATTRIBUTE_VERTEX_ANIMATION_INDEX));
+        assert!(!bake.mesh.contains_attribute(Mesh::ATTRIBUTE_JOINT_INDEX));
+        assert_eq!(4, bake.positions.texture_descriptor.size.width);
+        let positions: &[[f32; 4]] = bytemuck::cast_slice(&bake.positions.data);
+        assert_eq!([1.0, 1.0, 0.0, 1.0], positions[3]);
+        assert_eq!(Vec3::new(1.0, 1.0, 0.0), Vec3::from(bake.aabb.max()));
+    }
+
+    #[test]
+    fn loop_frames() {
+        let animation = VertexAnimation {
+            frame_count: 10,
+            frame_rate: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(5.0, animation.frame(0.5));
+        assert_eq!(5.0, animation.frame(1.5));
+        assert_eq!(5.0, animation.clone().with_time_offset(-0.5).frame(0.0));
+    }
+}
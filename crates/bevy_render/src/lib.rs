@@ -11,6 +11,7 @@ pub mod extract_resource;
 pub mod globals;
 pub mod mesh;
 pub mod primitives;
+pub mod raycast;
 pub mod render_asset;
 pub mod render_graph;
 pub mod render_phase;
@@ -18,6 +19,7 @@ pub mod render_resource;
 pub mod renderer;
 pub mod settings;
 mod spatial_bundle;
+pub mod spatial_index;
 pub mod texture;
 pub mod view;
 
@@ -30,6 +32,7 @@ pub mod prelude {
         camera::{Camera, OrthographicProjection, PerspectiveProjection, Projection},
         color::Color,
         mesh::{shape, Mesh},
+        raycast::{Raycast, RaycastMesh},
         render_resource::Shader,
         spatial_bundle::SpatialBundle,
         texture::{Image, ImagePlugin},
@@ -44,9 +47,11 @@ pub use once_cell;
 use crate::{
     camera::CameraPlugin,
     mesh::MeshPlugin,
+    raycast::RaycastPlugin,
     render_resource::{PipelineCache, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance},
     settings::WgpuSettings,
+    spatial_index::SpatialIndexPlugin,
     view::{ViewPlugin, WindowRenderPlugin},
 };
 use bevy_app::{App, AppLabel, Plugin};
@@ -265,6 +270,8 @@ impl Plugin for RenderPlugin {
             .add_plugin(CameraPlugin)
             .add_plugin(ViewPlugin)
             .add_plugin(MeshPlugin)
+            .add_plugin(RaycastPlugin)
+            .add_plugin(SpatialIndexPlugin)
             .add_plugin(GlobalsPlugin);
 
         app.register_type::<color::Color>()
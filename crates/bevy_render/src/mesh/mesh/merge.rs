@@ -0,0 +1,341 @@
+use super::{processing::compact_indices, Mesh, VertexAttributeValues};
+use bevy_math::{Mat3, Vec3};
+use bevy_transform::components::Transform;
+use bevy_utils::HashMap;
+use std::hash::Hash;
+use thiserror::Error;
+use wgpu::PrimitiveTopology;
+
+/// An error that occurs when merging two meshes with [`Mesh::merge`].
+#[derive(Error, Debug)]
+pub enum MergeMeshError {
+    #[error("cannot merge a {0:?} mesh with a {1:?} mesh")]
+    IncompatibleTopology(PrimitiveTopology, PrimitiveTopology),
+    #[error("cannot merge meshes with the {0:?} topology, the strips would be joined")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error(
+        "the '{0}' vertex attribute is missing from one of the meshes or has a different format"
+    )]
+    MismatchedAttribute(&'static str),
+}
+
+impl Mesh {
+    /// Transforms the positions, normals and tangents of the mesh by `transform`.
+    ///
+    /// The winding of the triangles is not changed, so transforms with a negative scale on an
+    /// odd number of axes turn the mesh inside out.
+    pub fn transform_by(&mut self, transform: &Transform) {
+        let matrix = transform.compute_matrix();
+        let normal_matrix = Mat3::from_mat4(matrix).inverse().transpose();
+        let tangent_matrix = Mat3::from_mat4(matrix);
+
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            self.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for position in positions {
+                *position = matrix.transform_point3(Vec3::from(*position)).to_array();
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            self.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for normal in normals {
+                *normal = (normal_matrix * Vec3::from(*normal))
+                    .normalize_or_zero()
+                    .to_array();
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x4(tangents)) =
+            self.attribute_mut(Mesh::ATTRIBUTE_TANGENT)
+        {
+            for tangent in tangents {
+                let [x, y, z, w] = *tangent;
+                let [x, y, z] = (tangent_matrix * Vec3::new(x, y, z))
+                    .normalize_or_zero()
+                    .to_array();
+                *tangent = [x, y, z, w];
+            }
+        }
+    }
+
+    /// Appends the vertices and indices of `other`, transformed by `transform`, to the mesh.
+    ///
+    /// Both meshes must have the same list topology and the same vertex attributes, in the same
+    /// formats. The indices of `other` are rebased after the vertices of the mesh, and widened to
+    /// [`Indices::U32`](super::Indices::U32) when needed. If only one of the meshes is indexed, indices are generated
+    /// for the other one.
+    pub fn merge(&mut self, other: &Mesh, transform: &Transform) -> Result<(), MergeMeshError> {
+        if self.primitive_topology != other.primitive_topology {
+            return Err(MergeMeshError::IncompatibleTopology(
+                self.primitive_topology,
+                other.primitive_topology,
+            ));
+        }
+        if matches!(
+            self.primitive_topology,
+            PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip
+        ) {
+            return Err(MergeMeshError::UnsupportedTopology(self.primitive_topology));
+        }
+        // Validate all the attributes before modifying the mesh
+        for (id, data) in &self.attributes {
+            let compatible = other.attributes.get(id).map_or(false, |other_data| {
+                data.attribute.format == other_data.attribute.format
+            });
+            if !compatible {
+                return Err(MergeMeshError::MismatchedAttribute(data.attribute.name));
+            }
+        }
+        if let Some((_, data)) = other
+            .attributes
+            .iter()
+            .find(|(id, _)| !self.attributes.contains_key(id))
+        {
+            return Err(MergeMeshError::MismatchedAttribute(data.attribute.name));
+        }
+
+        let vertex_count = self.count_vertices();
+        let other_vertex_count = other.count_vertices();
+        let indices = match (self.indices.take(), other.indices()) {
+            (None, None) => None,
+            (indices, other_indices) => {
+                let mut merged = indices
+                    .map(|indices| indices.iter().map(|index| index as u32).collect::<Vec<_>>())
+                    .unwrap_or_else(|| (0..vertex_count as u32).collect());
+                match other_indices {
+                    Some(other_indices) => merged.extend(
+                        other_indices
+                            .iter()
+                            .map(|index| (index + vertex_count) as u32),
+                    ),
+                    None => merged
+                        .extend(vertex_count as u32..(vertex_count + other_vertex_count) as u32),
+                }
+                Some(compact_indices(merged, vertex_count + other_vertex_count))
+            }
+        };
+        self.indices = indices;
+
+        let mut other = other.clone();
+        other.transform_by(transform);
+        for (id, data) in &mut self.attributes {
+            data.values.extend(&other.attributes[id].values);
+        }
+        Ok(())
+    }
+
+    /// Combines meshes, each transformed by its [`Transform`], into a single mesh.
+    ///
+    /// Returns `Ok(None)` if there are no meshes. See [`Mesh::merge`] for the requirements on the
+    /// meshes.
+    pub fn combine<'a>(
+        meshes: impl IntoIterator<Item = (&'a Mesh, Transform)>,
+    ) -> Result<Option<Mesh>, MergeMeshError> {
+        let mut combined: Option<Mesh> = None;
+        for (mesh, transform) in meshes {
+            match &mut combined {
+                Some(combined) => combined.merge(mesh, &transform)?,
+                None => {
+                    let mut mesh = mesh.clone();
+                    mesh.transform_by(&transform);
+                    combined = Some(mesh);
+                }
+            }
+        }
+        Ok(combined)
+    }
+}
+
+impl VertexAttributeValues {
+    /// Appends the values of `other`, returning `false` if they have a different format.
+    #[allow(clippy::match_same_arms)]
+    pub fn extend(&mut self, other: &VertexAttributeValues) -> bool {
+        match (self, other) {
+            (VertexAttributeValues::Float32(values), VertexAttributeValues::Float32(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint32(values), VertexAttributeValues::Sint32(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint32(values), VertexAttributeValues::Uint32(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Float32x2(values), VertexAttributeValues::Float32x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint32x2(values), VertexAttributeValues::Sint32x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint32x2(values), VertexAttributeValues::Uint32x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Float32x3(values), VertexAttributeValues::Float32x3(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint32x3(values), VertexAttributeValues::Sint32x3(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint32x3(values), VertexAttributeValues::Uint32x3(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Float32x4(values), VertexAttributeValues::Float32x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint32x4(values), VertexAttributeValues::Sint32x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint32x4(values), VertexAttributeValues::Uint32x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint16x2(values), VertexAttributeValues::Sint16x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Snorm16x2(values), VertexAttributeValues::Snorm16x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint16x2(values), VertexAttributeValues::Uint16x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Unorm16x2(values), VertexAttributeValues::Unorm16x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint16x4(values), VertexAttributeValues::Sint16x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Snorm16x4(values), VertexAttributeValues::Snorm16x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint16x4(values), VertexAttributeValues::Uint16x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Unorm16x4(values), VertexAttributeValues::Unorm16x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint8x2(values), VertexAttributeValues::Sint8x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Snorm8x2(values), VertexAttributeValues::Snorm8x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint8x2(values), VertexAttributeValues::Uint8x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Unorm8x2(values), VertexAttributeValues::Unorm8x2(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Sint8x4(values), VertexAttributeValues::Sint8x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Snorm8x4(values), VertexAttributeValues::Snorm8x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Uint8x4(values), VertexAttributeValues::Uint8x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            (VertexAttributeValues::Unorm8x4(values), VertexAttributeValues::Unorm8x4(other)) => {
+                values.extend_from_slice(other);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Combines many static meshes into one mesh per key, typically the handle of the material
+/// they share, to draw them with fewer draw calls.
+///
+/// ```
+/// # use bevy_render::mesh::{shape, Mesh, MeshBatcher};
+/// # use bevy_transform::components::Transform;
+/// let cube = Mesh::from(shape::Cube::default());
+/// let mut batcher = MeshBatcher::default();
+/// for x in 0..10 {
+///     batcher
+///         .add("stone", &cube, &Transform::from_xyz(x as f32, 0.0, 0.0))
+///         .unwrap();
+/// }
+/// let batches = batcher.build();
+/// assert_eq!(240, batches["stone"].count_vertices());
+/// ```
+pub struct MeshBatcher<K> {
+    batches: HashMap<K, Mesh>,
+}
+
+impl<K> Default for MeshBatcher<K> {
+    fn default() -> Self {
+        Self {
+            batches: HashMap::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> MeshBatcher<K> {
+    /// Adds a mesh, transformed by `transform`, to the batch of `key`.
+    ///
+    /// See [`Mesh::merge`] for the requirements on the meshes of a batch.
+    pub fn add(
+        &mut self,
+        key: K,
+        mesh: &Mesh,
+        transform: &Transform,
+    ) -> Result<&mut Self, MergeMeshError> {
+        match self.batches.get_mut(&key) {
+            Some(batch) => batch.merge(mesh, transform)?,
+            None => {
+                let mut mesh = mesh.clone();
+                mesh.transform_by(transform);
+                self.batches.insert(key, mesh);
+            }
+        }
+        Ok(self)
+    }
+
+    /// The combined mesh of each key.
+    pub fn build(self) -> HashMap<K, Mesh> {
+        self.batches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Indices;
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh
+    }
+
+    #[test]
+    fn merge_meshes() {
+        let mut mesh = triangle();
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        mesh.merge(&triangle(), &Transform::from_xyz(0.0, 0.0, 2.0))
+            .unwrap();
+
+        assert_eq!(6, mesh.count_vertices());
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 5],
+            mesh.indices().unwrap().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&[1.0, 0.0, 2.0][..]),
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(|positions| positions.as_float3())
+                .map(|positions| &positions[4][..])
+        );
+
+        let mut untextured = triangle();
+        untextured.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 3]);
+        assert!(matches!(
+            mesh.merge(&untextured, &Transform::IDENTITY),
+            Err(MergeMeshError::MismatchedAttribute(_))
+        ));
+        assert_eq!(6, mesh.count_vertices());
+    }
+}
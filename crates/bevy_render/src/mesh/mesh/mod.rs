@@ -1,12 +1,17 @@
 mod conversions;
+mod merge;
+mod processing;
+mod simplify;
 pub mod skinning;
+pub use merge::*;
+pub use processing::*;
 pub use wgpu::PrimitiveTopology;
 
 use crate::{
     primitives::Aabb,
     render_asset::{PrepareAssetError, RenderAsset},
     render_resource::{Buffer, VertexBufferLayout},
-    renderer::RenderDevice,
+    renderer::{RenderDevice, RenderQueue},
 };
 use bevy_core::cast_slice;
 use bevy_derive::EnumVariantMeta;
@@ -14,11 +19,11 @@ use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
 use bevy_math::*;
 use bevy_reflect::TypeUuid;
 use bevy_utils::{tracing::error, Hashed};
-use std::{collections::BTreeMap, hash::Hash, iter::FusedIterator};
+use std::{collections::BTreeMap, hash::Hash, iter::FusedIterator, ops::Range};
 use thiserror::Error;
 use wgpu::{
     util::BufferInitDescriptor, BufferUsages, IndexFormat, VertexAttribute, VertexFormat,
-    VertexStepMode,
+    VertexStepMode, COPY_BUFFER_ALIGNMENT,
 };
 
 pub const INDEX_BUFFER_ASSET_INDEX: u64 = 0;
@@ -35,6 +40,10 @@ pub struct Mesh {
     /// which allows easy stable VertexBuffers (i.e. same buffer order)
     attributes: BTreeMap<MeshVertexAttributeId, MeshAttributeData>,
     indices: Option<Indices>,
+    /// The vertices modified during this frame, see [`Mesh::mark_vertices_dirty`].
+    dirty_vertices: Option<Range<usize>>,
+    /// The indices modified during this frame, see [`Mesh::mark_indices_dirty`].
+    dirty_indices: Option<Range<usize>>,
 }
 
 /// Contains geometry in the form of a mesh.
@@ -91,6 +100,8 @@ impl Mesh {
             primitive_topology,
             attributes: Default::default(),
             indices: None,
+            dirty_vertices: None,
+            dirty_indices: None,
         }
     }
 
@@ -255,19 +266,23 @@ impl Mesh {
     /// # Panics
     /// Panics if the attributes have different vertex counts.
     pub fn get_vertex_buffer_data(&self) -> Vec<u8> {
-        let mut vertex_size = 0;
-        for attribute_data in self.attributes.values() {
-            let vertex_format = attribute_data.attribute.format;
-            vertex_size += vertex_format.get_size() as usize;
-        }
+        self.get_vertex_buffer_data_range(0..self.count_vertices())
+    }
 
-        let vertex_count = self.count_vertices();
-        let mut attributes_interleaved_buffer = vec![0; vertex_count * vertex_size];
+    /// Computes and returns the vertex data of the vertices in `range` as bytes, laid out as in
+    /// [`Mesh::get_vertex_buffer_data`].
+    ///
+    /// # Panics
+    /// Panics if the attributes have different vertex counts, or if `range` is out of bounds.
+    pub fn get_vertex_buffer_data_range(&self, range: Range<usize>) -> Vec<u8> {
+        let vertex_size = self.get_vertex_size() as usize;
+        let mut attributes_interleaved_buffer = vec![0; range.len() * vertex_size];
         // bundle into interleaved buffers
         let mut attribute_offset = 0;
         for attribute_data in self.attributes.values() {
             let attribute_size = attribute_data.attribute.format.get_size() as usize;
-            let attributes_bytes = attribute_data.values.get_bytes();
+            let attributes_bytes = &attribute_data.values.get_bytes()
+                [range.start * attribute_size..range.end * attribute_size];
             for (vertex_index, attribute_bytes) in
                 attributes_bytes.chunks_exact(attribute_size).enumerate()
             {
@@ -282,53 +297,63 @@ impl Mesh {
         attributes_interleaved_buffer
     }
 
+    /// Returns the size of the data of a vertex in the vertex buffer, in bytes.
+    pub fn get_vertex_size(&self) -> u64 {
+        self.attributes
+            .values()
+            .map(|data| data.attribute.format.get_size())
+            .sum()
+    }
+
+    /// Marks the vertices in `range` as modified, so only their data is written to the
+    /// existing vertex buffer of the mesh on the GPU, instead of creating a new buffer with all
+    /// its vertices.
+    ///
+    /// This is meant for meshes modified often, such as deforming meshes or chunks of voxel
+    /// terrain. The ranges marked during a frame are merged, and only used if the attributes,
+    /// the number of vertices and the number of indices of the mesh are unchanged. Modifying
+    /// a mesh without marking any range uploads it again entirely.
+    pub fn mark_vertices_dirty(&mut self, range: Range<usize>) {
+        self.dirty_vertices = Some(merge_ranges(self.dirty_vertices.take(), range));
+    }
+
+    /// Marks the indices in `range` as modified, so only their data is written to the existing
+    /// index buffer of the mesh on the GPU, see [`Mesh::mark_vertices_dirty`].
+    pub fn mark_indices_dirty(&mut self, range: Range<usize>) {
+        self.dirty_indices = Some(merge_ranges(self.dirty_indices.take(), range));
+    }
+
+    /// Returns the vertices marked as modified during this frame, see
+    /// [`Mesh::mark_vertices_dirty`].
+    pub fn dirty_vertices(&self) -> Option<Range<usize>> {
+        self.dirty_vertices.clone()
+    }
+
+    /// Returns the indices marked as modified during this frame, see
+    /// [`Mesh::mark_indices_dirty`].
+    pub fn dirty_indices(&self) -> Option<Range<usize>> {
+        self.dirty_indices.clone()
+    }
+
+    /// Clears the ranges marked as modified, which is done by the [`MeshPlugin`](super::MeshPlugin)
+    /// at the start of each frame, once they were extracted.
+    pub fn clear_dirty_ranges(&mut self) {
+        self.dirty_vertices = None;
+        self.dirty_indices = None;
+    }
+
     /// Duplicates the vertex attributes so that no vertices are shared.
     ///
     /// This can dramatically increase the vertex count, so make sure this is what you want.
     /// Does nothing if no [Indices] are set.
-    #[allow(clippy::match_same_arms)]
     pub fn duplicate_vertices(&mut self) {
-        fn duplicate<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
-            indices.map(|i| values[i]).collect()
-        }
-
         let indices = match self.indices.take() {
             Some(indices) => indices,
             None => return,
         };
 
         for attributes in self.attributes.values_mut() {
-            let indices = indices.iter();
-            match &mut attributes.values {
-                VertexAttributeValues::Float32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x4(vec) => *vec = duplicate(vec, indices),
-            }
+            attributes.values.gather(indices.iter());
         }
     }
 
@@ -458,6 +483,16 @@ impl InnerMeshVertexBufferLayout {
         &self.layout
     }
 
+    /// The offset in bytes of the attribute in each vertex, or `None` if the layout doesn't
+    /// contain the attribute.
+    pub fn attribute_offset(&self, attribute_id: impl Into<MeshVertexAttributeId>) -> Option<u64> {
+        let attribute_id = attribute_id.into();
+        self.attribute_ids
+            .iter()
+            .position(|id| *id == attribute_id)
+            .map(|index| self.layout.attributes[index].offset)
+    }
+
     pub fn get_layout(
         &self,
         attribute_descriptors: &[VertexAttributeDescriptor],
@@ -652,6 +687,45 @@ impl VertexAttributeValues {
         self.len() == 0
     }
 
+    /// Replaces the values by the values at `indices`, in order.
+    #[allow(clippy::match_same_arms)]
+    fn gather(&mut self, indices: impl Iterator<Item = usize>) {
+        fn gather<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
+            indices.map(|i| values[i]).collect()
+        }
+
+        match self {
+            VertexAttributeValues::Float32(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint32(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint32(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Float32x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint32x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint32x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Float32x3(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint32x3(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint32x3(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint32x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint32x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Float32x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint16x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Snorm16x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint16x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Unorm16x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint16x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Snorm16x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint16x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Unorm16x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint8x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Snorm8x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint8x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Unorm8x2(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Sint8x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Snorm8x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Uint8x4(vec) => *vec = gather(vec, indices),
+            VertexAttributeValues::Unorm8x4(vec) => *vec = gather(vec, indices),
+        }
+    }
+
     /// Returns the values as float triples if possible.
     pub fn as_float3(&self) -> Option<&[[f32; 3]]> {
         match self {
@@ -809,6 +883,8 @@ impl From<&Indices> for IndexFormat {
 pub struct GpuMesh {
     /// Contains all attribute data for each vertex.
     pub vertex_buffer: Buffer,
+    /// The number of vertices in `vertex_buffer`.
+    pub vertex_count: u32,
     pub buffer_info: GpuBufferInfo,
     pub primitive_topology: PrimitiveTopology,
     pub layout: MeshVertexBufferLayout,
@@ -831,7 +907,7 @@ pub enum GpuBufferInfo {
 impl RenderAsset for Mesh {
     type ExtractedAsset = Mesh;
     type PreparedAsset = GpuMesh;
-    type Param = SRes<RenderDevice>;
+    type Param = (SRes<RenderDevice>, SRes<RenderQueue>);
 
     /// Clones the mesh.
     fn extract_asset(&self) -> Self::ExtractedAsset {
@@ -841,11 +917,20 @@ impl RenderAsset for Mesh {
     /// Converts the extracted mesh a into [`GpuMesh`].
     fn prepare_asset(
         mesh: Self::ExtractedAsset,
-        render_device: &mut SystemParamItem<Self::Param>,
+        (render_device, _): &mut SystemParamItem<Self::Param>,
     ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
         let vertex_buffer_data = mesh.get_vertex_buffer_data();
+        // The vertices of skinned meshes are read by the compute skinning pass, which isn't
+        // available on WebGL
+        let usage = if cfg!(not(feature = "webgl"))
+            && mesh.contains_attribute(Mesh::ATTRIBUTE_JOINT_INDEX)
+        {
+            BufferUsages::VERTEX | BufferUsages::STORAGE
+        } else {
+            BufferUsages::VERTEX
+        };
         let vertex_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
+            usage: usage | BufferUsages::COPY_DST,
             label: Some("Mesh Vertex Buffer"),
             contents: &vertex_buffer_data,
         });
@@ -856,7 +941,7 @@ impl RenderAsset for Mesh {
             },
             |data| GpuBufferInfo::Indexed {
                 buffer: render_device.create_buffer_with_data(&BufferInitDescriptor {
-                    usage: BufferUsages::INDEX,
+                    usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
                     contents: data,
                     label: Some("Mesh Index Buffer"),
                 }),
@@ -869,11 +954,108 @@ impl RenderAsset for Mesh {
 
         Ok(GpuMesh {
             vertex_buffer,
+            vertex_count: mesh.count_vertices() as u32,
             buffer_info,
             primitive_topology: mesh.primitive_topology(),
             layout: mesh_vertex_buffer_layout,
         })
     }
+
+    /// Writes the vertices and indices marked with [`Mesh::mark_vertices_dirty`] and
+    /// [`Mesh::mark_indices_dirty`] to the buffers of the [`GpuMesh`], if their layout and sizes
+    /// are unchanged.
+    fn update_prepared_asset(
+        mesh: Self::ExtractedAsset,
+        gpu_mesh: &mut Self::PreparedAsset,
+        (_, render_queue): &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), Self::ExtractedAsset> {
+        let vertex_count = mesh.count_vertices();
+        let indices_unchanged = match (&gpu_mesh.buffer_info, mesh.indices()) {
+            (
+                GpuBufferInfo::Indexed {
+                    count,
+                    index_format,
+                    ..
+                },
+                Some(indices),
+            ) => *count as usize == indices.len() && *index_format == IndexFormat::from(indices),
+            (GpuBufferInfo::NonIndexed { .. }, None) => true,
+            _ => false,
+        };
+        if (mesh.dirty_vertices.is_none() && mesh.dirty_indices.is_none())
+            || !indices_unchanged
+            || gpu_mesh.vertex_count as usize != vertex_count
+            || gpu_mesh.primitive_topology != mesh.primitive_topology
+            || gpu_mesh.layout != mesh.get_mesh_vertex_buffer_layout()
+        {
+            return Err(mesh);
+        }
+
+        if let Some(range) = mesh.dirty_vertices() {
+            let vertex_size = mesh.get_vertex_size() as usize;
+            let range = aligned_range(range, vertex_size, vertex_count);
+            if !range.is_empty() {
+                write_buffer_aligned(
+                    render_queue,
+                    &gpu_mesh.vertex_buffer,
+                    range.start * vertex_size,
+                    mesh.get_vertex_buffer_data_range(range),
+                );
+            }
+        }
+
+        if let (GpuBufferInfo::Indexed { buffer, count, .. }, Some(range), Some(data)) = (
+            &gpu_mesh.buffer_info,
+            mesh.dirty_indices(),
+            mesh.get_index_buffer_bytes(),
+        ) {
+            let index_size = data.len() / (*count as usize).max(1);
+            let range = aligned_range(range, index_size, *count as usize);
+            if !range.is_empty() {
+                write_buffer_aligned(
+                    render_queue,
+                    buffer,
+                    range.start * index_size,
+                    data[range.start * index_size..range.end * index_size].to_vec(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The smallest range containing both `range` and `other`, if any.
+fn merge_ranges(range: Option<Range<usize>>, other: Range<usize>) -> Range<usize> {
+    match range {
+        Some(range) => range.start.min(other.start)..range.end.max(other.end),
+        None => other,
+    }
+}
+
+/// Clamps the `range` of elements of `size` bytes to `len` elements, and widens it so its bytes
+/// start at an offset aligned for buffer writes.
+fn aligned_range(range: Range<usize>, size: usize, len: usize) -> Range<usize> {
+    let range = range.start.min(len)..range.end.min(len);
+    if size % COPY_BUFFER_ALIGNMENT as usize == 0 {
+        return range;
+    }
+    // Vertex formats and indices have an even size, so pairs of elements are aligned
+    (range.start & !1)..((range.end + 1) & !1).min(len)
+}
+
+/// Writes `data` to the `buffer` at `offset`, padding it to the alignment of buffer writes.
+///
+/// Buffers created with data are padded to this alignment, so the padding is in bounds.
+fn write_buffer_aligned(
+    render_queue: &RenderQueue,
+    buffer: &Buffer,
+    offset: usize,
+    mut data: Vec<u8>,
+) {
+    let alignment = COPY_BUFFER_ALIGNMENT as usize;
+    data.resize((data.len() + alignment - 1) / alignment * alignment, 0);
+    render_queue.write_buffer(buffer, offset as u64, &data);
 }
 
 struct MikktspaceGeometryHelper<'a> {
@@ -1004,7 +1186,7 @@ fn generate_tangents_for_mesh(mesh: &Mesh) -> Result<Vec<[f32; 4]>, GenerateTang
 
 #[cfg(test)]
 mod tests {
-    use super::Mesh;
+    use super::{aligned_range, Mesh};
     use wgpu::PrimitiveTopology;
 
     #[test]
@@ -1013,4 +1195,26 @@ mod tests {
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0, 0.0]]);
     }
+
+    #[test]
+    fn dirty_vertex_ranges() {
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]; 4]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[1.0, 1.0]; 4]);
+        mesh.mark_vertices_dirty(2..3);
+        mesh.mark_vertices_dirty(0..1);
+        assert_eq!(Some(0..3), mesh.dirty_vertices());
+        assert_eq!(
+            &mesh.get_vertex_buffer_data()[20..60],
+            &mesh.get_vertex_buffer_data_range(1..3)[..]
+        );
+
+        mesh.clear_dirty_ranges();
+        assert_eq!(None, mesh.dirty_vertices());
+
+        // 2 byte indices are written in pairs
+        assert_eq!(2..6, aligned_range(3..5, 2, 7));
+        assert_eq!(6..7, aligned_range(6..7, 2, 7));
+        assert_eq!(3..5, aligned_range(3..5, 4, 7));
+    }
 }
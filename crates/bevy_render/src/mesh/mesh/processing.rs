@@ -0,0 +1,320 @@
+use super::{GenerateTangentsError, Indices, Mesh};
+use bevy_math::Vec3;
+use bevy_utils::HashMap;
+use wgpu::PrimitiveTopology;
+
+/// The number of vertices in the simulated post-transform cache of
+/// [`Mesh::optimize_vertex_cache`].
+const VERTEX_CACHE_SIZE: usize = 32;
+
+impl Mesh {
+    /// Calculates the [`Mesh::ATTRIBUTE_NORMAL`] of a mesh, shared by the triangles around each
+    /// vertex and weighted by their area.
+    ///
+    /// Unindexed meshes don't share vertices, consider calling [`Mesh::weld_vertices`] first.
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any other
+    /// topology than [`PrimitiveTopology::TriangleList`].
+    pub fn compute_smooth_normals(&mut self) {
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`compute_smooth_normals` can only work on `TriangleList`s"
+        );
+
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .expect("`Mesh::ATTRIBUTE_POSITION` vertex attributes should be of type `float3`");
+
+        let mut normals = vec![Vec3::ZERO; positions.len()];
+        let indices = self.triangle_indices();
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]]
+                .map(|index| Vec3::from(positions[index as usize]));
+            // The length of the cross product is proportional to the area of the triangle
+            let normal = (b - a).cross(c - a);
+            for &index in triangle {
+                normals[index as usize] += normal;
+            }
+        }
+        let normals: Vec<[f32; 3]> = normals
+            .into_iter()
+            .map(|normal| normal.normalize_or_zero().into())
+            .collect();
+
+        self.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    }
+
+    /// Calculates the [`Mesh::ATTRIBUTE_NORMAL`] of a mesh, smooth if it is indexed, see
+    /// [`Mesh::compute_smooth_normals`], and flat otherwise, see [`Mesh::compute_flat_normals`].
+    pub fn compute_normals(&mut self) {
+        if self.indices().is_some() {
+            self.compute_smooth_normals();
+        } else {
+            self.compute_flat_normals();
+        }
+    }
+
+    /// Consumes the mesh and returns it with its normals calculated by
+    /// [`Mesh::compute_normals`].
+    pub fn with_computed_normals(mut self) -> Self {
+        self.compute_normals();
+        self
+    }
+
+    /// Consumes the mesh and returns it with tangents generated by
+    /// [`Mesh::generate_tangents`].
+    pub fn with_generated_tangents(mut self) -> Result<Self, GenerateTangentsError> {
+        self.generate_tangents()?;
+        Ok(self)
+    }
+
+    /// Merges the vertices with exactly the same values for all their attributes, indexing the
+    /// mesh if it wasn't.
+    pub fn weld_vertices(&mut self) {
+        let vertex_count = self.count_vertices();
+        let mut unique = HashMap::<Vec<u8>, u32>::default();
+        let mut kept = Vec::new();
+        let remap: Vec<u32> = {
+            let attributes: Vec<&[u8]> = self
+                .attributes
+                .values()
+                .map(|data| data.values.get_bytes())
+                .collect();
+            (0..vertex_count)
+                .map(|vertex| {
+                    let key: Vec<u8> = attributes
+                        .iter()
+                        .flat_map(|bytes| {
+                            let size = bytes.len() / vertex_count;
+                            &bytes[vertex * size..(vertex + 1) * size]
+                        })
+                        .copied()
+                        .collect();
+                    *unique.entry(key).or_insert_with(|| {
+                        kept.push(vertex);
+                        kept.len() as u32 - 1
+                    })
+                })
+                .collect()
+        };
+        if kept.len() == vertex_count && self.indices.is_some() {
+            return;
+        }
+
+        let indices = match &self.indices {
+            Some(indices) => indices.iter().map(|index| remap[index]).collect(),
+            None => remap,
+        };
+        for data in self.attributes.values_mut() {
+            data.values.gather(kept.iter().copied());
+        }
+        self.indices = Some(compact_indices(indices, kept.len()));
+    }
+
+    /// Reorders the triangles of the mesh to reuse the vertices recently processed by the GPU,
+    /// then reorders the vertices in the order they are first used by the triangles.
+    ///
+    /// This uses Tom Forsyth's linear-speed vertex cache optimisation. Vertices not used by any
+    /// triangle are removed. Does nothing if the mesh isn't an indexed
+    /// [`PrimitiveTopology::TriangleList`].
+    pub fn optimize_vertex_cache(&mut self) {
+        if !matches!(self.primitive_topology, PrimitiveTopology::TriangleList)
+            || self.indices.is_none()
+        {
+            return;
+        }
+        let indices = optimize_triangle_order(&self.triangle_indices(), self.count_vertices());
+        self.set_compacted_indices(indices);
+    }
+
+    /// Sets the indices of the mesh, reordering its vertices in the order they are first used by
+    /// `indices` and removing the vertices they don't use.
+    pub(super) fn set_compacted_indices(&mut self, mut indices: Vec<u32>) {
+        let mut remap = vec![u32::MAX; self.count_vertices()];
+        let mut order = Vec::with_capacity(remap.len());
+        for index in &mut indices {
+            let remapped = &mut remap[*index as usize];
+            if *remapped == u32::MAX {
+                *remapped = order.len() as u32;
+                order.push(*index as usize);
+            }
+            *index = *remapped;
+        }
+        for data in self.attributes.values_mut() {
+            data.values.gather(order.iter().copied());
+        }
+        self.indices = Some(compact_indices(indices, order.len()));
+    }
+
+    /// The indices of the vertices of the mesh, or of all its vertices in order if it isn't
+    /// indexed.
+    pub(super) fn triangle_indices(&self) -> Vec<u32> {
+        match &self.indices {
+            Some(indices) => indices.iter().map(|index| index as u32).collect(),
+            None => (0..self.count_vertices() as u32).collect(),
+        }
+    }
+}
+
+/// The smallest [`Indices`] for the indices of `vertex_count` vertices.
+pub(super) fn compact_indices(indices: Vec<u32>, vertex_count: usize) -> Indices {
+    if vertex_count <= u16::MAX as usize + 1 {
+        Indices::U16(indices.into_iter().map(|index| index as u16).collect())
+    } else {
+        Indices::U32(indices)
+    }
+}
+
+/// The score of a vertex in Forsyth's algorithm, higher when it is in the cache, and when few
+/// triangles remain to be added with it.
+fn vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+    if remaining_triangles == 0 {
+        return -1.0;
+    }
+    let cache_score = match cache_position {
+        // The vertices of the last triangle have a fixed score, so the next triangle doesn't
+        // favor them depending on the order they were added in
+        Some(position) if position < 3 => 0.75,
+        Some(position) => (1.0 - (position - 3) as f32 / (VERTEX_CACHE_SIZE - 3) as f32).powf(1.5),
+        None => 0.0,
+    };
+    // Boost the vertices with few remaining triangles to finish them off
+    cache_score + 2.0 * (remaining_triangles as f32).powf(-0.5)
+}
+
+fn optimize_triangle_order(indices: &[u32], vertex_count: usize) -> Vec<u32> {
+    let triangle_count = indices.len() / 3;
+    let mut vertex_triangles = vec![Vec::new(); vertex_count];
+    for (triangle, vertices) in indices.chunks_exact(3).enumerate() {
+        for &vertex in vertices {
+            vertex_triangles[vertex as usize].push(triangle);
+        }
+    }
+    let mut vertex_scores: Vec<f32> = vertex_triangles
+        .iter()
+        .map(|triangles| vertex_score(None, triangles.len()))
+        .collect();
+    let triangle_score = |triangle: usize, vertex_scores: &[f32]| -> f32 {
+        indices[triangle * 3..triangle * 3 + 3]
+            .iter()
+            .map(|&vertex| vertex_scores[vertex as usize])
+            .sum()
+    };
+    let mut triangle_added = vec![false; triangle_count];
+    let mut cache: Vec<u32> = Vec::with_capacity(VERTEX_CACHE_SIZE + 3);
+    let mut output = Vec::with_capacity(triangle_count * 3);
+    let mut best_triangle = None;
+
+    for _ in 0..triangle_count {
+        // Fall back to a full search when no triangle of the cached vertices is left
+        let triangle = best_triangle.unwrap_or_else(|| {
+            (0..triangle_count)
+                .filter(|&triangle| !triangle_added[triangle])
+                .max_by(|&a, &b| {
+                    triangle_score(a, &vertex_scores).total_cmp(&triangle_score(b, &vertex_scores))
+                })
+                .unwrap()
+        });
+        triangle_added[triangle] = true;
+        let vertices = &indices[triangle * 3..triangle * 3 + 3];
+        output.extend_from_slice(vertices);
+
+        for &vertex in vertices.iter().rev() {
+            vertex_triangles[vertex as usize].retain(|&other| other != triangle);
+            if let Some(position) = cache.iter().position(|&cached| cached == vertex) {
+                cache.remove(position);
+            }
+            cache.insert(0, vertex);
+        }
+        let evicted = cache.split_off(cache.len().min(VERTEX_CACHE_SIZE));
+        for &vertex in &evicted {
+            vertex_scores[vertex as usize] =
+                vertex_score(None, vertex_triangles[vertex as usize].len());
+        }
+        for (position, &vertex) in cache.iter().enumerate() {
+            vertex_scores[vertex as usize] =
+                vertex_score(Some(position), vertex_triangles[vertex as usize].len());
+        }
+
+        best_triangle = None;
+        let mut best_score = f32::MIN;
+        for &vertex in &cache {
+            for &candidate in &vertex_triangles[vertex as usize] {
+                let score = triangle_score(candidate, &vertex_scores);
+                if score > best_score {
+                    best_score = score;
+                    best_triangle = Some(candidate);
+                }
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+        );
+        mesh
+    }
+
+    #[test]
+    fn weld_and_optimize() {
+        let mut mesh = quad();
+        mesh.weld_vertices();
+        assert_eq!(4, mesh.count_vertices());
+        assert_eq!(
+            vec![0, 1, 2, 0, 2, 3],
+            mesh.indices().unwrap().iter().collect::<Vec<_>>()
+        );
+
+        mesh.compute_normals();
+        assert_eq!(
+            Some(&[[0.0, 0.0, 1.0]; 4][..]),
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+                .and_then(|normals| normals.as_float3())
+        );
+
+        mesh.optimize_vertex_cache();
+        assert_eq!(4, mesh.count_vertices());
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        let mut triangles: Vec<Vec<[f32; 3]>> = mesh
+            .triangle_indices()
+            .chunks_exact(3)
+            .map(|triangle| {
+                triangle
+                    .iter()
+                    .map(|&index| positions[index as usize])
+                    .collect()
+            })
+            .collect();
+        triangles.sort_by(|a, b| a[1][1].total_cmp(&b[1][1]));
+        assert_eq!(
+            vec![
+                vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+                vec![[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+            ],
+            triangles
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use super::Mesh;
+use bevy_math::{DVec3, Vec3};
+use bevy_utils::HashMap;
+use std::ops::Add;
+use wgpu::PrimitiveTopology;
+
+/// How much more moving a vertex away from the border of an open mesh costs than moving it away
+/// from the surface of the mesh.
+const BORDER_WEIGHT: f64 = 10.0;
+
+impl Mesh {
+    /// Reduces the number of triangles of the mesh to about `target_ratio` of their current
+    /// count, by collapsing the edges that change its shape the least, as measured by quadric
+    /// error metrics.
+    ///
+    /// Vertices are collapsed onto one of their neighbors, keeping all their attributes, so the
+    /// vertices on the attribute seams of the mesh, such as UV seams and hard edges, are never
+    /// moved. The borders of open meshes are preserved. The mesh keeps more triangles than
+    /// requested when no edge can be collapsed without moving these vertices or flipping
+    /// triangles.
+    ///
+    /// The duplicate vertices of the mesh are welded first, see [`Mesh::weld_vertices`].
+    ///
+    /// # Panics
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is not of type `float3` or if the mesh has any other
+    /// topology than [`PrimitiveTopology::TriangleList`].
+    pub fn simplify(&mut self, target_ratio: f32) {
+        assert!(
+            matches!(self.primitive_topology, PrimitiveTopology::TriangleList),
+            "`simplify` can only work on `TriangleList`s"
+        );
+        self.weld_vertices();
+
+        let positions: Vec<DVec3> = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .expect("`Mesh::ATTRIBUTE_POSITION` vertex attributes should be of type `float3`")
+            .iter()
+            .map(|position| Vec3::from(*position).as_dvec3())
+            .collect();
+        let mut indices = self.triangle_indices();
+        let target_count = (indices.len() / 3) as f32 * target_ratio.clamp(0.0, 1.0);
+        let target_count = target_count as usize;
+
+        // Welded vertices sharing their position differ by other attributes
+        let mut position_counts = HashMap::<[u64; 3], usize>::default();
+        for position in &positions {
+            *position_counts
+                .entry(position.to_array().map(f64::to_bits))
+                .or_default() += 1;
+        }
+        let seams: Vec<bool> = positions
+            .iter()
+            .map(|position| position_counts[&position.to_array().map(f64::to_bits)] > 1)
+            .collect();
+
+        let mut quadrics = vertex_quadrics(&positions, &indices);
+        let mut remap: Vec<u32> = (0..positions.len() as u32).collect();
+        while indices.len() / 3 > target_count {
+            let mut vertex_triangles = vec![Vec::new(); positions.len()];
+            for (triangle, vertices) in indices.chunks_exact(3).enumerate() {
+                for &vertex in vertices {
+                    vertex_triangles[vertex as usize].push(triangle);
+                }
+            }
+
+            let mut collapses = Vec::new();
+            for triangle in indices.chunks_exact(3) {
+                for i in 0..3 {
+                    let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                    for (from, to) in [(a, b), (b, a)] {
+                        if !seams[from as usize] {
+                            let quadric = quadrics[from as usize] + quadrics[to as usize];
+                            collapses.push((quadric.error(positions[to as usize]), from, to));
+                        }
+                    }
+                }
+            }
+            collapses.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Each vertex is collapsed at most once per pass, so the triangles around it are only
+            // changed by that collapse
+            let mut collapsed = vec![false; positions.len()];
+            let mut triangle_count = indices.len() / 3;
+            for (_, from, to) in collapses {
+                if triangle_count <= target_count {
+                    break;
+                }
+                let triangles = &vertex_triangles[from as usize];
+                if collapsed[from as usize]
+                    || collapsed[to as usize]
+                    || flips(&positions, &indices, &remap, triangles, from, to)
+                {
+                    continue;
+                }
+                remap[from as usize] = to;
+                quadrics[to as usize] = quadrics[to as usize] + quadrics[from as usize];
+                collapsed[from as usize] = true;
+                collapsed[to as usize] = true;
+                triangle_count -= triangles
+                    .iter()
+                    .filter(|&&triangle| indices[triangle * 3..triangle * 3 + 3].contains(&to))
+                    .count();
+            }
+            if triangle_count == indices.len() / 3 {
+                break;
+            }
+
+            indices = indices
+                .chunks_exact(3)
+                .map(|triangle| [0, 1, 2].map(|i| remap[triangle[i] as usize]))
+                .filter(|[a, b, c]| a != b && b != c && c != a)
+                .flatten()
+                .collect();
+        }
+
+        self.set_compacted_indices(indices);
+    }
+}
+
+/// A symmetric 4x4 matrix measuring the squared distance of a point to a set of planes.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_plane(normal: DVec3, point: DVec3, weight: f64) -> Self {
+        let [a, b, c] = normal.to_array();
+        let d = -normal.dot(point);
+        Self([
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ])
+        .scale(weight)
+    }
+
+    fn scale(self, weight: f64) -> Self {
+        Self(self.0.map(|value| value * weight))
+    }
+
+    fn error(&self, point: DVec3) -> f64 {
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+        let [x, y, z] = point.to_array();
+        let error = aa * x * x
+            + bb * y * y
+            + cc * z * z
+            + 2.0 * (ab * x * y + ac * x * z + bc * y * z)
+            + 2.0 * (ad * x + bd * y + cd * z)
+            + dd;
+        // Rounding errors can make the error slightly negative
+        error.abs()
+    }
+}
+
+impl Add for Quadric {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (value, other) in self.0.iter_mut().zip(other.0) {
+            *value += other;
+        }
+        self
+    }
+}
+
+/// The sum of the quadrics of the planes of the triangles around each vertex, weighted by their
+/// area, and of planes perpendicular to the borders of the mesh.
+fn vertex_quadrics(positions: &[DVec3], indices: &[u32]) -> Vec<Quadric> {
+    let mut quadrics = vec![Quadric::default(); positions.len()];
+    let mut edge_counts = HashMap::<(u32, u32), usize>::default();
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+        let normal = (b - a).cross(c - a);
+        let quadric = Quadric::from_plane(normal.normalize_or_zero(), a, normal.length() / 2.0);
+        for i in 0..3 {
+            quadrics[triangle[i] as usize] = quadrics[triangle[i] as usize] + quadric;
+            let (start, end) = (triangle[i], triangle[(i + 1) % 3]);
+            *edge_counts
+                .entry((start.min(end), start.max(end)))
+                .or_default() += 1;
+        }
+    }
+
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        for i in 0..3 {
+            let (start, end) = (triangle[i], triangle[(i + 1) % 3]);
+            if edge_counts[&(start.min(end), start.max(end))] != 1 {
+                continue;
+            }
+            let (start_position, end_position) =
+                (positions[start as usize], positions[end as usize]);
+            let edge = end_position - start_position;
+            let quadric = Quadric::from_plane(
+                edge.cross(normal).normalize_or_zero(),
+                start_position,
+                edge.length_squared() * BORDER_WEIGHT,
+            );
+            quadrics[start as usize] = quadrics[start as usize] + quadric;
+            quadrics[end as usize] = quadrics[end as usize] + quadric;
+        }
+    }
+    quadrics
+}
+
+/// Whether collapsing `from` onto `to` flips any of the `triangles` around `from`.
+fn flips(
+    positions: &[DVec3],
+    indices: &[u32],
+    remap: &[u32],
+    triangles: &[usize],
+    from: u32,
+    to: u32,
+) -> bool {
+    triangles.iter().any(|&triangle| {
+        let vertices = [0, 1, 2].map(|i| remap[indices[triangle * 3 + i] as usize]);
+        // Triangles along the collapsed edge are removed
+        if vertices.contains(&to) {
+            return false;
+        }
+        let [a, b, c] = vertices.map(|vertex| positions[vertex as usize]);
+        let [new_a, new_b, new_c] =
+            vertices.map(|vertex| positions[if vertex == from { to } else { vertex } as usize]);
+        let normal = (b - a).cross(c - a);
+        let new_normal = (new_b - new_a).cross(new_c - new_a);
+        normal.dot(new_normal) < 0.0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Indices;
+
+    #[test]
+    fn simplify_grid() {
+        // A flat grid of 8x8 quads, which can be simplified without changing its shape
+        let size = 9;
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                positions.push([x as f32, y as f32, 0.0]);
+                if x + 1 < size && y + 1 < size {
+                    let i = y * size + x;
+                    indices.extend([i, i + 1, i + size + 1, i, i + size + 1, i + size]);
+                }
+            }
+        }
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.set_indices(Some(Indices::U32(indices)));
+
+        mesh.simplify(0.25);
+        let triangle_count = mesh.indices().unwrap().len() / 3;
+        assert!(triangle_count > 0 && triangle_count <= 32);
+
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        for corner in [
+            [0.0, 0.0, 0.0],
+            [8.0, 0.0, 0.0],
+            [0.0, 8.0, 0.0],
+            [8.0, 8.0, 0.0],
+        ] {
+            assert!(positions.contains(&corner));
+        }
+        // The area of the grid is preserved
+        let area: f32 = mesh
+            .triangle_indices()
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(positions[triangle[i] as usize]));
+                (b - a).cross(c - a).z / 2.0
+            })
+            .sum();
+        assert!((area - 64.0).abs() < 1e-4);
+    }
+}
@@ -5,10 +5,15 @@ use bevy_ecs::{
     prelude::ReflectComponent,
     reflect::ReflectMapEntities,
 };
-use bevy_math::Mat4;
+use bevy_math::{Mat4, Vec3};
 use bevy_reflect::{Reflect, TypeUuid};
 use std::ops::Deref;
 
+use crate::{
+    mesh::{Mesh, VertexAttributeValues},
+    primitives::Aabb,
+};
+
 #[derive(Component, Debug, Default, Clone, Reflect)]
 #[reflect(Component, MapEntities)]
 pub struct SkinnedMesh {
@@ -42,3 +47,61 @@ impl Deref for SkinnedMeshInverseBindposes {
         &self.0
     }
 }
+
+/// The bounds of the vertices influenced by each joint of a [`SkinnedMesh`], in the bind pose of
+/// the mesh.
+///
+/// It is computed from the [`Mesh`] when missing and used to update the [`Aabb`] of the skinned
+/// mesh each frame from the transforms of its joints, so that it isn't culled when its animation
+/// moves it outside of the bounds of its bind pose.
+#[derive(Component, Debug, Default, Clone)]
+pub struct SkinnedMeshBounds {
+    /// The bounds of the vertices influenced by each joint, or `None` for joints that don't
+    /// influence any vertex. Indexed by the joint index of the vertices.
+    pub joint_aabbs: Vec<Option<Aabb>>,
+}
+
+impl SkinnedMeshBounds {
+    /// Compute the bounds of the vertices influenced by each joint of a skinned mesh.
+    ///
+    /// Returns `None` if the mesh doesn't have positions, joint indices and joint weights.
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        let (
+            Some(VertexAttributeValues::Float32x3(positions)),
+            Some(VertexAttributeValues::Uint16x4(joint_indices)),
+            Some(VertexAttributeValues::Float32x4(joint_weights)),
+        ) = (
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+            mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX),
+            mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT),
+        )
+        else {
+            return None;
+        };
+
+        let mut bounds: Vec<Option<(Vec3, Vec3)>> = Vec::new();
+        for ((position, indices), weights) in positions.iter().zip(joint_indices).zip(joint_weights)
+        {
+            let position = Vec3::from_slice(position);
+            for (index, weight) in indices.iter().zip(weights) {
+                if *weight <= 0.0 {
+                    continue;
+                }
+                let index = *index as usize;
+                if bounds.len() <= index {
+                    bounds.resize(index + 1, None);
+                }
+                bounds[index] = Some(match bounds[index] {
+                    Some((minimum, maximum)) => (minimum.min(position), maximum.max(position)),
+                    None => (position, position),
+                });
+            }
+        }
+        Some(Self {
+            joint_aabbs: bounds
+                .into_iter()
+                .map(|bounds| bounds.map(|(minimum, maximum)| Aabb::from_min_max(minimum, maximum)))
+                .collect(),
+        })
+    }
+}
@@ -6,9 +6,9 @@ pub mod shape;
 pub use mesh::*;
 
 use crate::render_asset::RenderAssetPlugin;
-use bevy_app::{App, Plugin};
-use bevy_asset::AddAsset;
-use bevy_ecs::entity::Entity;
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{AddAsset, AssetEvent, Assets};
+use bevy_ecs::{entity::Entity, event::EventReader, system::ResMut};
 
 /// Adds the [`Mesh`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct MeshPlugin;
@@ -19,6 +19,22 @@ impl Plugin for MeshPlugin {
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
             .register_type::<Vec<Entity>>()
-            .add_plugin(RenderAssetPlugin::<Mesh>::default());
+            .add_plugin(RenderAssetPlugin::<Mesh>::default())
+            .add_system_to_stage(CoreStage::First, clear_mesh_dirty_ranges);
+    }
+}
+
+/// Clears the ranges marked as modified in the meshes modified during the previous frame, once
+/// they were extracted, see [`Mesh::mark_vertices_dirty`].
+fn clear_mesh_dirty_ranges(
+    mut events: EventReader<AssetEvent<Mesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for event in events.iter() {
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            if let Some(mesh) = meshes.get_mut_without_event(handle) {
+                mesh.clear_dirty_ranges();
+            }
+        }
     }
 }
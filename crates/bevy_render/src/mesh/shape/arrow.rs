@@ -0,0 +1,96 @@
+use super::{Cone, Cylinder};
+use crate::mesh::Mesh;
+use bevy_math::Quat;
+use bevy_transform::components::Transform;
+
+/// An arrow pointing up the Y axis, from the origin to its tip.
+#[derive(Debug, Copy, Clone)]
+pub struct Arrow {
+    /// Length of the arrow, including its head.
+    pub length: f32,
+    /// Radius of the shaft in the XZ plane.
+    pub shaft_radius: f32,
+    /// Radius of the base of the head in the XZ plane.
+    pub head_radius: f32,
+    /// Length of the head in the Y axis.
+    pub head_length: f32,
+    /// The number of vertices around each horizontal slice of the arrow.
+    pub resolution: u32,
+}
+
+impl Default for Arrow {
+    fn default() -> Self {
+        Arrow {
+            length: 1.0,
+            shaft_radius: 0.02,
+            head_radius: 0.06,
+            head_length: 0.2,
+            resolution: 16,
+        }
+    }
+}
+
+impl From<Arrow> for Mesh {
+    fn from(arrow: Arrow) -> Self {
+        debug_assert!(arrow.head_length < arrow.length);
+        debug_assert!(arrow.shaft_radius < arrow.head_radius);
+
+        let shaft_length = arrow.length - arrow.head_length;
+        let mut mesh = Mesh::from(Cylinder {
+            radius: arrow.shaft_radius,
+            height: shaft_length,
+            resolution: arrow.resolution,
+            segments: 1,
+            // Hidden by the base of the head
+            top_cap: false,
+            bottom_cap: true,
+        });
+        mesh.transform_by(&Transform::from_xyz(0.0, shaft_length / 2.0, 0.0));
+
+        let head = Mesh::from(Cone {
+            radius: arrow.head_radius,
+            height: arrow.head_length,
+            resolution: arrow.resolution,
+            segments: 1,
+            cap: true,
+        });
+        mesh.merge(
+            &head,
+            &Transform::from_xyz(0.0, shaft_length + arrow.head_length / 2.0, 0.0),
+        )
+        .expect("the shaft and the head of an arrow have the same attributes");
+        mesh
+    }
+}
+
+/// Red, green and blue [`Arrow`]s pointing along the X, Y and Z axes, showing the orientation of
+/// an entity. The colors are stored in the [`Mesh::ATTRIBUTE_COLOR`] of the vertices.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Axes {
+    /// The arrow used for each axis, pointing up the Y axis.
+    pub arrow: Arrow,
+}
+
+impl From<Axes> for Mesh {
+    fn from(axes: Axes) -> Self {
+        let arrow = Mesh::from(axes.arrow);
+        let colored = |color: [f32; 4]| {
+            let mut mesh = arrow.clone();
+            mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![color; arrow.count_vertices()]);
+            mesh
+        };
+
+        let mut mesh = colored([1.0, 0.0, 0.0, 1.0]);
+        mesh.transform_by(&Transform::from_rotation(Quat::from_rotation_z(
+            -std::f32::consts::FRAC_PI_2,
+        )));
+        mesh.merge(&colored([0.0, 1.0, 0.0, 1.0]), &Transform::IDENTITY)
+            .expect("the arrows of the axes have the same attributes");
+        mesh.merge(
+            &colored([0.0, 0.0, 1.0, 1.0]),
+            &Transform::from_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        )
+        .expect("the arrows of the axes have the same attributes");
+        mesh
+    }
+}
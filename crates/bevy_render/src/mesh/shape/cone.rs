@@ -0,0 +1,116 @@
+use crate::mesh::{Indices, Mesh};
+use bevy_math::Vec3;
+use wgpu::PrimitiveTopology;
+
+/// A cone which stands on the XZ plane, with its tip pointing up the Y axis.
+#[derive(Debug, Copy, Clone)]
+pub struct Cone {
+    /// Radius of the base in the XZ plane.
+    pub radius: f32,
+    /// Height of the cone in the Y axis.
+    pub height: f32,
+    /// The number of vertices around each horizontal slice of the cone.
+    /// A higher number will make it appear more circular.
+    pub resolution: u32,
+    /// The number of segments between the base and the tip.
+    pub segments: u32,
+    /// Whether to close the base of the cone with a disc.
+    pub cap: bool,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 16,
+            segments: 1,
+            cap: true,
+        }
+    }
+}
+
+impl From<Cone> for Mesh {
+    fn from(c: Cone) -> Self {
+        debug_assert!(c.radius > 0.0);
+        debug_assert!(c.height > 0.0);
+        debug_assert!(c.resolution > 2);
+        debug_assert!(c.segments > 0);
+
+        let num_rings = c.segments + 1;
+        let num_vertices = c.resolution + num_rings * (c.resolution + 1);
+        let num_indices = (c.resolution * (2 * c.segments - 1) + c.resolution - 2) * 3;
+
+        let mut positions = Vec::with_capacity(num_vertices as usize);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(num_vertices as usize);
+        let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut indices = Vec::with_capacity(num_indices as usize);
+
+        let step_theta = std::f32::consts::TAU / c.resolution as f32;
+
+        // rings, the last one collapsed to the tip
+
+        for ring in 0..num_rings {
+            let t = ring as f32 / c.segments as f32;
+            let y = -c.height / 2.0 + t * c.height;
+            let radius = c.radius * (1.0 - t);
+
+            for segment in 0..=c.resolution {
+                // The normals at the tip point halfway between the faces they belong to
+                let theta = if ring == c.segments {
+                    (segment as f32 + 0.5) * step_theta
+                } else {
+                    segment as f32 * step_theta
+                };
+                let (sin, cos) = theta.sin_cos();
+
+                positions.push([radius * cos, y, radius * sin]);
+                normals.push(
+                    Vec3::new(c.height * cos, c.radius, c.height * sin)
+                        .normalize()
+                        .into(),
+                );
+                uvs.push([segment as f32 / c.resolution as f32, t]);
+            }
+        }
+
+        // sloped skin, with a single triangle per segment next to the tip
+
+        for i in 0..c.segments {
+            let ring = i * (c.resolution + 1);
+            let next_ring = (i + 1) * (c.resolution + 1);
+
+            for j in 0..c.resolution {
+                indices.extend_from_slice(&[ring + j, next_ring + j, ring + j + 1]);
+                if i + 1 < c.segments {
+                    indices.extend_from_slice(&[next_ring + j, next_ring + j + 1, ring + j + 1]);
+                }
+            }
+        }
+
+        // base
+
+        if c.cap {
+            let offset = positions.len() as u32;
+            for i in 0..c.resolution {
+                let theta = i as f32 * step_theta;
+                let (sin, cos) = theta.sin_cos();
+
+                positions.push([cos * c.radius, -c.height / 2.0, sin * c.radius]);
+                normals.push([0.0, -1.0, 0.0]);
+                uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+            }
+
+            for i in 1..(c.resolution - 1) {
+                indices.extend_from_slice(&[offset, offset + i, offset + i + 1]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        super::with_tangents(mesh)
+    }
+}
@@ -15,6 +15,10 @@ pub struct Cylinder {
     /// height of the cylinder. Setting it to 2 will have two sets of triangles with a horizontal slice in the middle of
     /// cylinder. Greater numbers increase triangles/slices in the same way.
     pub segments: u32,
+    /// Whether to close the top of the cylinder with a disc.
+    pub top_cap: bool,
+    /// Whether to close the bottom of the cylinder with a disc.
+    pub bottom_cap: bool,
 }
 
 impl Default for Cylinder {
@@ -24,6 +28,8 @@ impl Default for Cylinder {
             height: 1.0,
             resolution: 16,
             segments: 1,
+            top_cap: true,
+            bottom_cap: true,
         }
     }
 }
@@ -112,16 +118,18 @@ impl From<Cylinder> for Mesh {
             }
         };
 
-        // top
-
-        build_cap(true);
-        build_cap(false);
+        if c.top_cap {
+            build_cap(true);
+        }
+        if c.bottom_cap {
+            build_cap(false);
+        }
 
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.set_indices(Some(Indices::U32(indices)));
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-        mesh
+        super::with_tangents(mesh)
     }
 }
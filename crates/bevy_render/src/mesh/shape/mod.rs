@@ -187,51 +187,98 @@ impl From<Quad> for Mesh {
 pub struct Plane {
     /// The total side length of the square.
     pub size: f32,
+    /// The number of lines splitting the square along each axis, so a plane with 1 subdivision
+    /// has 4 quads.
+    pub subdivisions: u32,
 }
 
 impl Default for Plane {
     fn default() -> Self {
-        Plane { size: 1.0 }
+        Plane {
+            size: 1.0,
+            subdivisions: 0,
+        }
+    }
+}
+
+impl Plane {
+    /// Creates a plane of the given side length, without subdivisions.
+    pub fn from_size(size: f32) -> Self {
+        Plane {
+            size,
+            subdivisions: 0,
+        }
     }
 }
 
 impl From<Plane> for Mesh {
     fn from(plane: Plane) -> Self {
-        let extent = plane.size / 2.0;
-
-        let vertices = [
-            ([extent, 0.0, -extent], [0.0, 1.0, 0.0], [1.0, 1.0]),
-            ([extent, 0.0, extent], [0.0, 1.0, 0.0], [1.0, 0.0]),
-            ([-extent, 0.0, extent], [0.0, 1.0, 0.0], [0.0, 0.0]),
-            ([-extent, 0.0, -extent], [0.0, 1.0, 0.0], [0.0, 1.0]),
-        ];
-
-        let indices = Indices::U32(vec![0, 2, 1, 0, 3, 2]);
+        let vertex_count = plane.subdivisions + 2;
+
+        let num_vertices = (vertex_count * vertex_count) as usize;
+        let mut positions = Vec::with_capacity(num_vertices);
+        let mut normals = Vec::with_capacity(num_vertices);
+        let mut uvs = Vec::with_capacity(num_vertices);
+        for z in 0..vertex_count {
+            for x in 0..vertex_count {
+                let u = x as f32 / (vertex_count - 1) as f32;
+                let v = z as f32 / (vertex_count - 1) as f32;
+                positions.push([(u - 0.5) * plane.size, 0.0, (v - 0.5) * plane.size]);
+                normals.push([0.0, 1.0, 0.0]);
+                uvs.push([u, 1.0 - v]);
+            }
+        }
 
-        let positions: Vec<_> = vertices.iter().map(|(p, _, _)| *p).collect();
-        let normals: Vec<_> = vertices.iter().map(|(_, n, _)| *n).collect();
-        let uvs: Vec<_> = vertices.iter().map(|(_, _, uv)| *uv).collect();
+        let mut indices = Vec::with_capacity(((vertex_count - 1).pow(2) * 6) as usize);
+        for z in 0..vertex_count - 1 {
+            for x in 0..vertex_count - 1 {
+                let quad = z * vertex_count + x;
+                indices.extend_from_slice(&[
+                    quad,
+                    quad + vertex_count,
+                    quad + 1,
+                    quad + 1,
+                    quad + vertex_count,
+                    quad + vertex_count + 1,
+                ]);
+            }
+        }
 
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
-        mesh.set_indices(Some(indices));
+        mesh.set_indices(Some(Indices::U32(indices)));
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-        mesh
+        with_tangents(mesh)
     }
 }
 
+/// Generates the tangents of a shape with the `mikktspace` algorithm, see
+/// [`Mesh::generate_tangents`].
+fn with_tangents(mesh: Mesh) -> Mesh {
+    mesh.with_generated_tangents()
+        .expect("shapes should have the normals and UVs needed to generate tangents")
+}
+
+mod arrow;
 mod capsule;
+mod cone;
 mod cylinder;
 mod icosphere;
 mod regular_polygon;
+mod rounded_box;
 mod torus;
+mod torus_knot;
 mod uvsphere;
 
+pub use arrow::{Arrow, Axes};
 pub use capsule::{Capsule, CapsuleUvProfile};
+pub use cone::Cone;
 pub use cylinder::Cylinder;
 pub use icosphere::Icosphere;
 pub use regular_polygon::{Circle, RegularPolygon};
+pub use rounded_box::RoundedBox;
 pub use torus::Torus;
+pub use torus_knot::TorusKnot;
 pub use uvsphere::UVSphere;
 use wgpu::PrimitiveTopology;
@@ -0,0 +1,121 @@
+use crate::mesh::{Indices, Mesh};
+use bevy_math::Vec3;
+use wgpu::PrimitiveTopology;
+
+/// A box with rounded edges and corners, centered at the origin.
+#[derive(Debug, Copy, Clone)]
+pub struct RoundedBox {
+    /// Full size of the box along each axis.
+    pub size: Vec3,
+    /// Radius of the rounded edges and corners, clamped to half of the smallest side of the box.
+    pub radius: f32,
+    /// Number of segments along each rounded edge, on each of the two faces it joins.
+    pub subdivisions: u32,
+}
+
+impl RoundedBox {
+    pub fn new(size: Vec3, radius: f32) -> Self {
+        Self {
+            size,
+            radius,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for RoundedBox {
+    fn default() -> Self {
+        RoundedBox {
+            size: Vec3::ONE,
+            radius: 0.1,
+            subdivisions: 4,
+        }
+    }
+}
+
+impl From<RoundedBox> for Mesh {
+    fn from(rounded_box: RoundedBox) -> Self {
+        let half_size = rounded_box.size / 2.0;
+        let radius = rounded_box.radius.clamp(0.0, half_size.min_element());
+        let inner = half_size - Vec3::splat(radius);
+
+        // The coordinates of the vertices along an axis of a face, before rounding. Each face
+        // covers half of the rounded edges it joins, split in steps of equal angles.
+        let steps = if radius > 0.0 {
+            rounded_box.subdivisions.max(1)
+        } else {
+            0
+        };
+        let coordinates = |inner: f32| {
+            let rounded = (1..=steps).map(|step| {
+                let angle = step as f32 / steps as f32 * std::f32::consts::FRAC_PI_4;
+                inner + radius * angle.tan()
+            });
+            let mut coordinates: Vec<f32> = rounded.clone().rev().map(|x| -x).collect();
+            coordinates.push(-inner);
+            if inner > 0.0 {
+                coordinates.push(inner);
+            }
+            coordinates.extend(rounded);
+            coordinates
+        };
+
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut normals: Vec<[f32; 3]> = Vec::new();
+        let mut uvs: Vec<[f32; 2]> = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+
+        // The normal of each face, and the axes of its UVs, facing the normal
+        let faces = [
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+        ];
+        for (normal, u_axis, v_axis) in faces {
+            let us = coordinates(u_axis.abs().dot(inner));
+            let vs = coordinates(v_axis.abs().dot(inner));
+            let (u_size, v_size) = (
+                u_axis.abs().dot(rounded_box.size),
+                v_axis.abs().dot(rounded_box.size),
+            );
+            let offset = positions.len() as u32;
+
+            for &v in &vs {
+                for &u in &us {
+                    let point = normal * normal.abs().dot(half_size) + u_axis * u + v_axis * v;
+                    let center = point.clamp(-inner, inner);
+                    let direction = (point - center).try_normalize().unwrap_or(normal);
+
+                    positions.push((center + direction * radius).into());
+                    normals.push(direction.into());
+                    uvs.push([0.5 + u / u_size, 0.5 - v / v_size]);
+                }
+            }
+
+            let row = us.len() as u32;
+            for j in 0..vs.len() as u32 - 1 {
+                for i in 0..row - 1 {
+                    let corner = offset + j * row + i;
+                    indices.extend_from_slice(&[
+                        corner,
+                        corner + 1,
+                        corner + row + 1,
+                        corner,
+                        corner + row + 1,
+                        corner + row,
+                    ]);
+                }
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        super::with_tangents(mesh)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::mesh::{Indices, Mesh};
+use bevy_math::Vec3;
+use wgpu::PrimitiveTopology;
+
+/// A tube winding around the surface of a torus lying on the XZ plane.
+#[derive(Debug, Clone, Copy)]
+pub struct TorusKnot {
+    /// Radius of the torus the knot winds around.
+    pub radius: f32,
+    /// Radius of the tube.
+    pub tube_radius: f32,
+    /// How many times the knot winds around the axis of rotational symmetry of the torus.
+    pub p: u32,
+    /// How many times the knot winds around the inside of the torus.
+    pub q: u32,
+    /// Number of segments along the length of the tube.
+    pub tubular_segments: usize,
+    /// Number of segments around the tube.
+    pub radial_segments: usize,
+}
+
+impl Default for TorusKnot {
+    fn default() -> Self {
+        TorusKnot {
+            radius: 1.0,
+            tube_radius: 0.2,
+            p: 2,
+            q: 3,
+            tubular_segments: 128,
+            radial_segments: 16,
+        }
+    }
+}
+
+impl TorusKnot {
+    /// The point at `angle` along the center of the tube.
+    fn position_on_curve(&self, angle: f32) -> Vec3 {
+        let q_over_p = self.q as f32 / self.p as f32 * angle;
+        let distance = self.radius * (2.0 + q_over_p.cos()) * 0.5;
+        Vec3::new(
+            distance * angle.cos(),
+            self.radius * q_over_p.sin() * 0.5,
+            -distance * angle.sin(),
+        )
+    }
+}
+
+impl From<TorusKnot> for Mesh {
+    fn from(knot: TorusKnot) -> Self {
+        // code adapted from the `TorusKnotGeometry` of three.js (https://github.com/mrdoob/three.js)
+
+        let n_vertices = (knot.tubular_segments + 1) * (knot.radial_segments + 1);
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(n_vertices);
+
+        for tubular in 0..=knot.tubular_segments {
+            let angle = tubular as f32 / knot.tubular_segments as f32
+                * knot.p as f32
+                * std::f32::consts::TAU;
+
+            // A frame moving along the curve, with the tube around its tangent
+            let center = knot.position_on_curve(angle);
+            let next = knot.position_on_curve(angle + 0.01);
+            let tangent = next - center;
+            let binormal = tangent.cross(next + center).normalize();
+            let normal = binormal.cross(tangent).normalize();
+
+            for radial in 0..=knot.radial_segments {
+                let phi = radial as f32 / knot.radial_segments as f32 * std::f32::consts::TAU;
+                let offset = -phi.cos() * normal + phi.sin() * binormal;
+
+                positions.push((center + knot.tube_radius * offset).into());
+                normals.push(offset.into());
+                uvs.push([
+                    tubular as f32 / knot.tubular_segments as f32,
+                    radial as f32 / knot.radial_segments as f32,
+                ]);
+            }
+        }
+
+        let n_indices = knot.tubular_segments * knot.radial_segments * 6;
+        let mut indices: Vec<u32> = Vec::with_capacity(n_indices);
+
+        let n_vertices_per_row = knot.radial_segments + 1;
+        for tubular in 0..knot.tubular_segments {
+            for radial in 0..knot.radial_segments {
+                let lt = radial + tubular * n_vertices_per_row;
+                let rt = (radial + 1) + tubular * n_vertices_per_row;
+
+                let lb = radial + (tubular + 1) * n_vertices_per_row;
+                let rb = (radial + 1) + (tubular + 1) * n_vertices_per_row;
+
+                indices.push(lt as u32);
+                indices.push(lb as u32);
+                indices.push(rt as u32);
+
+                indices.push(lb as u32);
+                indices.push(rb as u32);
+                indices.push(rt as u32);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        super::with_tangents(mesh)
+    }
+}
@@ -1,5 +1,5 @@
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
-use bevy_math::{Mat4, Vec3, Vec3A, Vec4, Vec4Swizzles};
+use bevy_math::{Mat4, Ray, Vec3, Vec3A, Vec4, Vec4Swizzles};
 use bevy_reflect::Reflect;
 
 /// An Axis-Aligned Bounding Box
@@ -46,6 +46,19 @@ impl Aabb {
     pub fn max(&self) -> Vec3A {
         self.center + self.half_extents
     }
+
+    /// Returns the distance along the ray at which it enters the AABB, or zero if its origin is
+    /// inside the AABB. The distance is in units of the length of the direction of the ray.
+    #[inline]
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<f32> {
+        let origin = Vec3A::from(ray.origin);
+        let inverse_direction = Vec3A::from(ray.direction).recip();
+        let t_1 = (self.min() - origin) * inverse_direction;
+        let t_2 = (self.max() - origin) * inverse_direction;
+        let near = t_1.min(t_2).max_element().max(0.0);
+        let far = t_1.max(t_2).min_element();
+        (near <= far).then_some(near)
+    }
 }
 
 impl From<Sphere> for Aabb {
@@ -364,4 +377,32 @@ mod tests {
         };
         assert!(frustum.intersects_sphere(&sphere, true));
     }
+
+    #[test]
+    fn aabb_intersect_ray() {
+        let aabb = Aabb::from_min_max(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        let ray = |origin: Vec3, direction: Vec3| Ray { origin, direction };
+
+        // Entering the box through a face
+        assert_eq!(
+            aabb.intersect_ray(&ray(Vec3::new(0.5, 0.0, -5.0), Vec3::Z)),
+            Some(4.0)
+        );
+        // The distance is in units of the direction of the ray
+        assert_eq!(
+            aabb.intersect_ray(&ray(Vec3::new(0.5, 0.0, -5.0), Vec3::Z * 2.0)),
+            Some(2.0)
+        );
+        // From inside the box
+        assert_eq!(aabb.intersect_ray(&ray(Vec3::ZERO, Vec3::X)), Some(0.0));
+        // Missing the box, or pointing away from it
+        assert_eq!(
+            aabb.intersect_ray(&ray(Vec3::new(2.0, 0.0, -5.0), Vec3::Z)),
+            None
+        );
+        assert_eq!(
+            aabb.intersect_ray(&ray(Vec3::new(0.0, 0.0, -5.0), Vec3::NEG_Z)),
+            None
+        );
+    }
 }
@@ -0,0 +1,323 @@
+use crate::{
+    mesh::{Mesh, PrimitiveTopology, VertexAttributeValues},
+    primitives::Aabb,
+};
+use bevy_math::{Ray, Vec3};
+
+/// The maximum number of triangles in a leaf of a [`MeshBvh`].
+const MAX_LEAF_TRIANGLES: usize = 4;
+
+/// A bounding volume hierarchy over the triangles of a [`Mesh`], to intersect rays with them
+/// without testing every triangle.
+///
+/// It is a copy of the positions of the mesh at the time it was built, and must be rebuilt when
+/// the mesh is modified.
+#[derive(Clone, Debug)]
+pub struct MeshBvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<BvhTriangle>,
+    positions: Vec<Vec3>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    bounds: Aabb,
+    /// The index of the first triangle of a leaf, or of the first of the two children of an
+    /// inner node.
+    first: usize,
+    /// The number of triangles of a leaf, zero for an inner node.
+    count: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BvhTriangle {
+    /// The index of the triangle in the mesh.
+    index: usize,
+    vertices: [u32; 3],
+}
+
+/// The intersection of a ray with a triangle of a [`MeshBvh`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleHit {
+    /// The distance along the ray, in units of the length of its direction.
+    pub distance: f32,
+    /// The index of the triangle in the mesh.
+    pub triangle: usize,
+    /// The indices of the vertices of the triangle.
+    pub vertices: [u32; 3],
+    /// The weights of the vertices of the triangle at the hit point.
+    pub barycentric: Vec3,
+}
+
+impl TriangleHit {
+    /// Interpolates the values of an attribute of the vertices of the triangle at the hit point,
+    /// or returns `None` if `values` doesn't have a value for each of them.
+    pub fn interpolate<const N: usize>(&self, values: &[[f32; N]]) -> Option<[f32; N]> {
+        let mut interpolated = [0.0; N];
+        for (vertex, weight) in self.vertices.iter().zip(self.barycentric.to_array()) {
+            let value = values.get(*vertex as usize)?;
+            for (interpolated, value) in interpolated.iter_mut().zip(value) {
+                *interpolated += value * weight;
+            }
+        }
+        Some(interpolated)
+    }
+}
+
+impl MeshBvh {
+    /// Builds the hierarchy over the triangles of `mesh`.
+    ///
+    /// Returns `None` if the mesh isn't a [`PrimitiveTopology::TriangleList`], or doesn't have
+    /// [`Mesh::ATTRIBUTE_POSITION`] in [`VertexAttributeValues::Float32x3`].
+    pub fn new(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions: Vec<Vec3> = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => {
+                positions.iter().copied().map(Vec3::from).collect()
+            }
+            _ => return None,
+        };
+
+        let indices: Vec<u32> = match mesh.indices() {
+            Some(indices) => indices.iter().map(|index| index as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        let mut triangles: Vec<BvhTriangle> = indices
+            .chunks_exact(3)
+            .enumerate()
+            .filter(|(_, vertices)| {
+                vertices
+                    .iter()
+                    .all(|&vertex| (vertex as usize) < positions.len())
+            })
+            .map(|(index, vertices)| BvhTriangle {
+                index,
+                vertices: [vertices[0], vertices[1], vertices[2]],
+            })
+            .collect();
+
+        let mut nodes = Vec::new();
+        if !triangles.is_empty() {
+            nodes.push(BvhNode {
+                bounds: Aabb::default(),
+                first: 0,
+                count: 0,
+            });
+            build_node(&mut nodes, 0, &mut triangles, 0, &positions);
+        }
+
+        Some(Self {
+            nodes,
+            triangles,
+            positions,
+        })
+    }
+
+    /// The bounds of all the triangles, or `None` if there are none.
+    pub fn bounds(&self) -> Option<&Aabb> {
+        self.nodes.first().map(|node| &node.bounds)
+    }
+
+    /// Returns the nearest intersection of `ray` with a triangle, not further than
+    /// `max_distance`. Both faces of the triangles are hit.
+    ///
+    /// The direction of the ray doesn't need to be normalized, the distance of the hit is then in
+    /// units of its length. This allows casting a ray transformed to the space of the mesh, while
+    /// keeping the distance of the original ray.
+    pub fn cast_ray(&self, ray: Ray, max_distance: f32) -> Option<TriangleHit> {
+        let mut nearest: Option<TriangleHit> = None;
+        let mut max_distance = max_distance;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            match node.bounds.intersect_ray(&ray) {
+                Some(distance) if distance <= max_distance => {}
+                _ => continue,
+            }
+
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+
+            for triangle in &self.triangles[node.first..node.first + node.count] {
+                let corners = triangle
+                    .vertices
+                    .map(|vertex| self.positions[vertex as usize]);
+                if let Some((distance, barycentric)) = intersect_triangle(&ray, corners) {
+                    if distance <= max_distance {
+                        max_distance = distance;
+                        nearest = Some(TriangleHit {
+                            distance,
+                            triangle: triangle.index,
+                            vertices: triangle.vertices,
+                            barycentric,
+                        });
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// The normal of the plane of the hit triangle, facing the side where its vertices are
+    /// counter-clockwise.
+    pub fn face_normal(&self, hit: &TriangleHit) -> Vec3 {
+        let [a, b, c] = hit.vertices.map(|vertex| self.positions[vertex as usize]);
+        (b - a).cross(c - a).normalize_or_zero()
+    }
+}
+
+/// Computes the bounds of `triangles`, and splits them in two children along the longest axis of
+/// the bounds of their centroids until there are few enough of them for a leaf.
+fn build_node(
+    nodes: &mut Vec<BvhNode>,
+    node: usize,
+    triangles: &mut [BvhTriangle],
+    first: usize,
+    positions: &[Vec3],
+) {
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    let mut centroid_min = Vec3::splat(f32::MAX);
+    let mut centroid_max = Vec3::splat(f32::MIN);
+    for triangle in triangles.iter() {
+        let corners = triangle.vertices.map(|vertex| positions[vertex as usize]);
+        for corner in corners {
+            min = min.min(corner);
+            max = max.max(corner);
+        }
+        let centroid = (corners[0] + corners[1] + corners[2]) / 3.0;
+        centroid_min = centroid_min.min(centroid);
+        centroid_max = centroid_max.max(centroid);
+    }
+    nodes[node].bounds = Aabb::from_min_max(min, max);
+
+    let extents = centroid_max - centroid_min;
+    let axis = if extents.x >= extents.y && extents.x >= extents.z {
+        0
+    } else if extents.y >= extents.z {
+        1
+    } else {
+        2
+    };
+    if triangles.len() <= MAX_LEAF_TRIANGLES || extents[axis] <= 0.0 {
+        nodes[node].first = first;
+        nodes[node].count = triangles.len();
+        return;
+    }
+
+    let middle = triangles.len() / 2;
+    let centroid = |triangle: &BvhTriangle| {
+        triangle
+            .vertices
+            .iter()
+            .map(|&vertex| positions[vertex as usize][axis])
+            .sum::<f32>()
+    };
+    triangles.select_nth_unstable_by(middle, |a, b| centroid(a).total_cmp(&centroid(b)));
+
+    let children = nodes.len();
+    nodes[node].first = children;
+    nodes[node].count = 0;
+    for _ in 0..2 {
+        nodes.push(BvhNode {
+            bounds: Aabb::default(),
+            first: 0,
+            count: 0,
+        });
+    }
+    let (left, right) = triangles.split_at_mut(middle);
+    build_node(nodes, children, left, first, positions);
+    build_node(nodes, children + 1, right, first + middle, positions);
+}
+
+/// Intersects `ray` with a triangle using the Möller–Trumbore algorithm, returning the distance
+/// along the ray and the weights of the corners at the hit point.
+pub(super) fn intersect_triangle(ray: &Ray, [a, b, c]: [Vec3; 3]) -> Option<(f32, Vec3)> {
+    let edge_1 = b - a;
+    let edge_2 = c - a;
+    let p = ray.direction.cross(edge_2);
+    let determinant = edge_1.dot(p);
+    if determinant == 0.0 {
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+
+    let s = ray.origin - a;
+    let u = s.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge_1);
+    let v = ray.direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let distance = edge_2.dot(q) * inverse_determinant;
+    (distance >= 0.0).then_some((distance, Vec3::new(1.0 - u - v, u, v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::shape;
+
+    #[test]
+    fn bvh_matches_brute_force() {
+        let mut mesh = Mesh::from(shape::Plane {
+            size: 2.0,
+            subdivisions: 15,
+        });
+        mesh.duplicate_vertices();
+        let bvh = MeshBvh::new(&mesh).unwrap();
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => panic!("the plane has positions"),
+        };
+
+        for i in 0..20 {
+            let x = -1.2 + i as f32 * 0.12;
+            let ray = Ray {
+                origin: Vec3::new(x, 1.0, 0.3 * x),
+                direction: Vec3::new(0.1, -1.0, 0.05).normalize(),
+            };
+            let expected = positions
+                .chunks_exact(3)
+                .enumerate()
+                .filter_map(|(triangle, corners)| {
+                    let corners = [corners[0], corners[1], corners[2]].map(Vec3::from);
+                    intersect_triangle(&ray, corners).map(|(distance, _)| (triangle, distance))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            let hit = bvh.cast_ray(ray, f32::INFINITY);
+            assert_eq!(
+                hit.map(|hit| hit.triangle),
+                expected.map(|(triangle, _)| triangle)
+            );
+            if let Some(hit) = hit {
+                let point = ray.get_point(hit.distance);
+                assert!(point.y.abs() < 1e-5);
+                assert_eq!(bvh.face_normal(&hit), Vec3::Y);
+                let interpolated = Vec3::from(hit.interpolate(positions).unwrap());
+                assert!(interpolated.abs_diff_eq(point, 1e-5));
+            }
+        }
+
+        let parallel = Ray {
+            origin: Vec3::new(0.0, 1.0, 0.0),
+            direction: Vec3::X,
+        };
+        assert_eq!(bvh.cast_ray(parallel, f32::INFINITY), None);
+    }
+}
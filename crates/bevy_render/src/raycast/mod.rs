@@ -0,0 +1,313 @@
+mod bvh;
+
+pub use bvh::*;
+
+use crate::{
+    mesh::{Mesh, VertexAttributeValues},
+    primitives::Aabb,
+    view::ComputedVisibility,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent, system::SystemParam};
+use bevy_math::{Mat4, Ray, Vec2, Vec3, Vec3A};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// Caches the [`MeshBvh`] of the meshes of [`RaycastMesh`] entities, to cast rays against their
+/// triangles with [`Raycast`].
+#[derive(Default)]
+pub struct RaycastPlugin;
+
+impl Plugin for RaycastPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RaycastMesh>()
+            .init_resource::<MeshBvhCache>()
+            .add_system_to_stage(CoreStage::PostUpdate, update_mesh_bvhs);
+    }
+}
+
+/// Marks an entity with a [`Handle<Mesh>`] to be hit by [`Raycast`] on the triangles of its mesh.
+///
+/// Other entities with an [`Aabb`] are hit on their bounding box. The triangles of a mesh are only
+/// hit once its [`MeshBvh`] was built, during the [`CoreStage::PostUpdate`] following the
+/// insertion of this component or the modification of the mesh.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct RaycastMesh;
+
+/// The [`MeshBvh`] of the meshes of the [`RaycastMesh`] entities.
+#[derive(Resource, Default)]
+pub struct MeshBvhCache {
+    bvhs: HashMap<Handle<Mesh>, MeshBvh>,
+}
+
+impl MeshBvhCache {
+    /// The hierarchy of the triangles of a mesh, if it was built.
+    pub fn get(&self, handle: &Handle<Mesh>) -> Option<&MeshBvh> {
+        self.bvhs.get(handle)
+    }
+}
+
+/// Builds the [`MeshBvh`] of the meshes of [`RaycastMesh`] entities, and discards those of the
+/// meshes that were modified or removed.
+pub fn update_mesh_bvhs(
+    mut cache: ResMut<MeshBvhCache>,
+    mut events: EventReader<AssetEvent<Mesh>>,
+    meshes: Res<Assets<Mesh>>,
+    raycast_meshes: Query<&Handle<Mesh>, With<RaycastMesh>>,
+) {
+    for event in events.iter() {
+        if let AssetEvent::Modified { handle } | AssetEvent::Removed { handle } = event {
+            cache.bvhs.remove(handle);
+        }
+    }
+
+    for handle in &raycast_meshes {
+        if cache.bvhs.contains_key(handle) {
+            continue;
+        }
+        if let Some(bvh) = meshes.get(handle).and_then(MeshBvh::new) {
+            cache.bvhs.insert(handle.clone_weak(), bvh);
+        }
+    }
+}
+
+/// Which entities can be hit by a [`Raycast`], depending on their [`ComputedVisibility`].
+/// Entities without it are always considered visible.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RaycastVisibility {
+    /// Hit all the entities.
+    Ignore,
+    /// Hit only the entities visible in the hierarchy.
+    #[default]
+    MustBeVisible,
+    /// Hit only the entities visible in the hierarchy and in a view, during the previous frame.
+    MustBeVisibleAndInView,
+}
+
+/// Settings of a [`Raycast`].
+#[derive(Clone, Copy, Debug)]
+pub struct RaycastSettings {
+    /// Which entities can be hit, depending on their visibility.
+    pub visibility: RaycastVisibility,
+    /// Hits further than this distance along the ray are ignored.
+    pub max_distance: f32,
+}
+
+impl Default for RaycastSettings {
+    fn default() -> Self {
+        Self {
+            visibility: RaycastVisibility::default(),
+            max_distance: f32::INFINITY,
+        }
+    }
+}
+
+/// The intersection of a ray with an entity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// The entity that was hit.
+    pub entity: Entity,
+    /// The distance along the ray, in units of the length of its direction.
+    pub distance: f32,
+    /// The hit point, in world space.
+    pub point: Vec3,
+    /// The normal of the surface at the hit point, in world space.
+    ///
+    /// For a [`RaycastMesh`], it is interpolated from the [`Mesh::ATTRIBUTE_NORMAL`] of the
+    /// vertices of the hit triangle, or is the normal of the triangle if the mesh has none.
+    pub normal: Vec3,
+    /// The [`Mesh::ATTRIBUTE_UV_0`] at the hit point, if the hit is on the triangle of a mesh
+    /// that has them.
+    pub uv: Option<Vec2>,
+    /// The index of the hit triangle, if the hit is on the triangle of a mesh.
+    pub triangle: Option<usize>,
+}
+
+/// A [`SystemParam`] to cast rays against the triangles of the meshes of [`RaycastMesh`]
+/// entities, and against the [`Aabb`] of other entities. This is the building block of picking
+/// entities with the cursor, using a ray from
+/// [`Camera::viewport_to_world`](crate::camera::Camera::viewport_to_world).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Ray;
+/// # use bevy_render::raycast::{Raycast, RaycastSettings};
+/// fn shoot(raycast: Raycast) {
+///     let ray = Ray {
+///         origin: bevy_math::Vec3::ZERO,
+///         direction: bevy_math::Vec3::NEG_Z,
+///     };
+///     if let Some(hit) = raycast.cast_ray_nearest(ray, &RaycastSettings::default()) {
+///         println!("hit {:?} at {}", hit.entity, hit.point);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(shoot);
+/// ```
+#[derive(SystemParam)]
+pub struct Raycast<'w, 's> {
+    meshes: Res<'w, Assets<Mesh>>,
+    bvhs: Res<'w, MeshBvhCache>,
+    raycast_meshes: Query<'w, 's, RaycastMeshQuery, With<RaycastMesh>>,
+    bounded: Query<'w, 's, BoundedQuery, Without<RaycastMesh>>,
+}
+
+type RaycastMeshQuery = (
+    Entity,
+    &'static Handle<Mesh>,
+    &'static GlobalTransform,
+    Option<&'static Aabb>,
+    Option<&'static ComputedVisibility>,
+);
+
+type BoundedQuery = (
+    Entity,
+    &'static Aabb,
+    &'static GlobalTransform,
+    Option<&'static ComputedVisibility>,
+);
+
+impl<'w, 's> Raycast<'w, 's> {
+    /// Returns the nearest hit of each entity intersected by `ray`, sorted by distance.
+    pub fn cast_ray(&self, ray: Ray, settings: &RaycastSettings) -> Vec<RayHit> {
+        let mut hits = Vec::new();
+        self.visit_hits(ray, settings, |hit| {
+            hits.push(hit);
+            settings.max_distance
+        });
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Returns the nearest hit of `ray`, skipping the entities further than the nearest hit found
+    /// so far.
+    pub fn cast_ray_nearest(&self, ray: Ray, settings: &RaycastSettings) -> Option<RayHit> {
+        let mut nearest = None;
+        self.visit_hits(ray, settings, |hit| {
+            nearest = Some(hit);
+            hit.distance
+        });
+        nearest
+    }
+
+    /// Calls `on_hit` with the nearest hit of each entity intersected by `ray`, which returns the
+    /// maximum distance of the following hits.
+    fn visit_hits(
+        &self,
+        ray: Ray,
+        settings: &RaycastSettings,
+        mut on_hit: impl FnMut(RayHit) -> f32,
+    ) {
+        let mut max_distance = settings.max_distance;
+
+        for (entity, handle, transform, aabb, visibility) in &self.raycast_meshes {
+            if !is_visible(visibility, settings.visibility) {
+                continue;
+            }
+            let world_to_local = transform.compute_matrix().inverse();
+            let local_ray = transform_ray(&world_to_local, ray);
+            // The bounds are a cheap early out before traversing the hierarchy
+            if let Some(aabb) = aabb {
+                match aabb.intersect_ray(&local_ray) {
+                    Some(distance) if distance <= max_distance => {}
+                    _ => continue,
+                }
+            }
+            let Some(bvh) = self.bvhs.get(handle) else {
+                continue;
+            };
+            let Some(hit) = bvh.cast_ray(local_ray, max_distance) else {
+                continue;
+            };
+
+            let mesh = self.meshes.get(handle);
+            let normal = mesh
+                .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_NORMAL))
+                .and_then(|normals| normals.as_float3())
+                .and_then(|normals| hit.interpolate(normals))
+                .map(Vec3::from)
+                .unwrap_or_else(|| bvh.face_normal(&hit));
+            let uv = mesh
+                .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_UV_0))
+                .and_then(|uvs| match uvs {
+                    VertexAttributeValues::Float32x2(uvs) => hit.interpolate(uvs),
+                    _ => None,
+                })
+                .map(Vec2::from);
+
+            max_distance = on_hit(RayHit {
+                entity,
+                distance: hit.distance,
+                point: ray.get_point(hit.distance),
+                normal: transform_normal(&world_to_local, normal),
+                uv,
+                triangle: Some(hit.triangle),
+            });
+        }
+
+        for (entity, aabb, transform, visibility) in &self.bounded {
+            if !is_visible(visibility, settings.visibility) {
+                continue;
+            }
+            let world_to_local = transform.compute_matrix().inverse();
+            let local_ray = transform_ray(&world_to_local, ray);
+            let Some(distance) = aabb.intersect_ray(&local_ray) else {
+                continue;
+            };
+            if distance > max_distance {
+                continue;
+            }
+
+            // The normal of the face of the box nearest to the hit point
+            let offset = Vec3A::from(local_ray.get_point(distance)) - aabb.center;
+            let face_distance = offset.abs() - aabb.half_extents;
+            let normal = if face_distance.x >= face_distance.y && face_distance.x >= face_distance.z
+            {
+                Vec3::X * offset.x.signum()
+            } else if face_distance.y >= face_distance.z {
+                Vec3::Y * offset.y.signum()
+            } else {
+                Vec3::Z * offset.z.signum()
+            };
+
+            max_distance = on_hit(RayHit {
+                entity,
+                distance,
+                point: ray.get_point(distance),
+                normal: transform_normal(&world_to_local, normal),
+                uv: None,
+                triangle: None,
+            });
+        }
+    }
+}
+
+fn is_visible(visibility: Option<&ComputedVisibility>, required: RaycastVisibility) -> bool {
+    match (visibility, required) {
+        (None, _) | (_, RaycastVisibility::Ignore) => true,
+        (Some(visibility), RaycastVisibility::MustBeVisible) => {
+            visibility.is_visible_in_hierarchy()
+        }
+        (Some(visibility), RaycastVisibility::MustBeVisibleAndInView) => visibility.is_visible(),
+    }
+}
+
+/// Transforms `ray` to local space, without normalizing its direction so that distances along it
+/// are the same in both spaces.
+fn transform_ray(world_to_local: &Mat4, ray: Ray) -> Ray {
+    Ray {
+        origin: world_to_local.transform_point3(ray.origin),
+        direction: world_to_local.transform_vector3(ray.direction),
+    }
+}
+
+/// Transforms a normal from local space to world space, with the inverse transpose of the
+/// local to world matrix.
+fn transform_normal(world_to_local: &Mat4, normal: Vec3) -> Vec3 {
+    world_to_local
+        .transpose()
+        .transform_vector3(normal)
+        .normalize_or_zero()
+}
@@ -38,6 +38,18 @@ pub trait RenderAsset: Asset {
         extracted_asset: Self::ExtractedAsset,
         param: &mut SystemParamItem<Self::Param>,
     ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>>;
+    /// Updates the [`RenderAsset::PreparedAsset`] of a modified asset in place, instead of
+    /// preparing it again, such as by writing the modified data to its existing buffers.
+    ///
+    /// Returns the `extracted_asset` when it can't be updated, so it is prepared again with
+    /// [`RenderAsset::prepare_asset`], which is the default.
+    fn update_prepared_asset(
+        extracted_asset: Self::ExtractedAsset,
+        _prepared_asset: &mut Self::PreparedAsset,
+        _param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), Self::ExtractedAsset> {
+        Err(extracted_asset)
+    }
 }
 
 #[derive(Clone, Hash, Debug, Default, PartialEq, Eq, SystemLabel)]
@@ -203,6 +215,15 @@ fn prepare_assets<R: RenderAsset>(
     }
 
     for (handle, extracted_asset) in std::mem::take(&mut extracted_assets.extracted) {
+        let extracted_asset = match render_assets.get_mut(&handle) {
+            Some(prepared_asset) => {
+                match R::update_prepared_asset(extracted_asset, prepared_asset, &mut param) {
+                    Ok(()) => continue,
+                    Err(extracted_asset) => extracted_asset,
+                }
+            }
+            None => extracted_asset,
+        };
         match R::prepare_asset(extracted_asset, &mut param) {
             Ok(prepared_asset) => {
                 render_assets.insert(handle, prepared_asset);
@@ -0,0 +1,498 @@
+use crate::{
+    primitives::{Aabb, Frustum},
+    view::VisibilitySystems,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_math::{IVec3, Mat4, Ray, Vec3, Vec3A};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::{HashMap, HashSet};
+
+/// Keeps the [`SpatialIndex`] up to date with the [`SpatialIndexed`] entities.
+///
+/// Insert a [`SpatialIndex`] with another cell size before adding this plugin to change it.
+#[derive(Default)]
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpatialIndexed>()
+            .init_resource::<SpatialIndex>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_spatial_index
+                    .label(SpatialIndexSystem::UpdateSpatialIndex)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(VisibilitySystems::CalculateBounds)
+                    .after(VisibilitySystems::CalculateSkinnedMeshBounds),
+            );
+    }
+}
+
+/// Label for the system updating the [`SpatialIndex`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum SpatialIndexSystem {
+    /// Updates the [`SpatialIndex`] with the [`SpatialIndexed`] entities that moved, changed
+    /// bounds, or were added or removed.
+    UpdateSpatialIndex,
+}
+
+/// Opts an entity into the [`SpatialIndex`].
+///
+/// The entity is indexed with the bounds of its [`Aabb`] transformed by its [`GlobalTransform`],
+/// or as a point at its translation if it doesn't have an [`Aabb`].
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpatialIndexed;
+
+/// A uniform grid over the world space bounds of the [`SpatialIndexed`] entities, to find the
+/// entities along a ray, in a sphere, in a frustum, or nearest to a point without testing all of
+/// them.
+///
+/// It is updated during [`CoreStage::PostUpdate`], after the transforms were propagated, so the
+/// queries return the entities where they were at the end of the previous frame when run before.
+#[derive(Resource, Debug)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<Entity>>,
+    entries: HashMap<Entity, SpatialEntry>,
+    /// The range of cells that ever contained an entity, which bounds the traversal of rays.
+    occupied: Option<(IVec3, IVec3)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SpatialEntry {
+    min: Vec3,
+    max: Vec3,
+    first_cell: IVec3,
+    last_cell: IVec3,
+}
+
+impl SpatialEntry {
+    fn aabb(&self) -> Aabb {
+        Aabb::from_min_max(self.min, self.max)
+    }
+
+    fn distance_squared(&self, point: Vec3) -> f32 {
+        point.clamp(self.min, self.max).distance_squared(point)
+    }
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    /// The default size of the cells of the grid.
+    pub const DEFAULT_CELL_SIZE: f32 = 10.0;
+
+    /// Creates an empty index with cubic cells of `cell_size`.
+    ///
+    /// The size should be about the size of the larger indexed entities: entities are stored in
+    /// all the cells their bounds overlap, and queries visit all the cells they cover.
+    pub fn new(cell_size: f32) -> Self {
+        debug_assert!(cell_size > 0.0);
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            entries: HashMap::default(),
+            occupied: None,
+        }
+    }
+
+    /// The size of the cells of the grid.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// The number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entity is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The world space bounds of an indexed entity.
+    pub fn get(&self, entity: Entity) -> Option<Aabb> {
+        self.entries.get(&entity).map(SpatialEntry::aabb)
+    }
+
+    /// Returns the entities whose bounds are intersected by `ray` not further than
+    /// `max_distance`, with the distance at which the ray enters their bounds, sorted by distance.
+    pub fn cast_ray(&self, ray: Ray, max_distance: f32) -> Vec<(Entity, f32)> {
+        let mut hits = Vec::new();
+        let Some((min_cell, max_cell)) = self.occupied else {
+            return hits;
+        };
+        let grid = Aabb::from_min_max(
+            min_cell.as_vec3() * self.cell_size,
+            (max_cell + IVec3::ONE).as_vec3() * self.cell_size,
+        );
+        let Some(mut distance) = grid.intersect_ray(&ray) else {
+            return hits;
+        };
+
+        // Walk through the cells along the ray, from the one where it enters the grid
+        let mut cell = self.cell(ray.get_point(distance)).clamp(min_cell, max_cell);
+        let mut step = IVec3::ZERO;
+        let mut next_distance = Vec3::splat(f32::INFINITY);
+        let mut delta_distance = Vec3::splat(f32::INFINITY);
+        for axis in 0..3 {
+            let direction = ray.direction[axis];
+            if direction == 0.0 {
+                continue;
+            }
+            let boundary = if direction > 0.0 {
+                step[axis] = 1;
+                (cell[axis] + 1) as f32 * self.cell_size
+            } else {
+                step[axis] = -1;
+                cell[axis] as f32 * self.cell_size
+            };
+            next_distance[axis] = (boundary - ray.origin[axis]) / direction;
+            delta_distance[axis] = self.cell_size / direction.abs();
+        }
+
+        let mut visited = HashSet::default();
+        while distance <= max_distance {
+            for &entity in self.cells.get(&cell).into_iter().flatten() {
+                if !visited.insert(entity) {
+                    continue;
+                }
+                if let Some(hit) = self.entries[&entity].aabb().intersect_ray(&ray) {
+                    if hit <= max_distance {
+                        hits.push((entity, hit));
+                    }
+                }
+            }
+
+            let axis = if next_distance.x <= next_distance.y && next_distance.x <= next_distance.z {
+                0
+            } else if next_distance.y <= next_distance.z {
+                1
+            } else {
+                2
+            };
+            if step[axis] == 0 {
+                break;
+            }
+            cell[axis] += step[axis];
+            if cell[axis] < min_cell[axis] || cell[axis] > max_cell[axis] {
+                break;
+            }
+            distance = next_distance[axis];
+            next_distance[axis] += delta_distance[axis];
+        }
+
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        hits
+    }
+
+    /// Returns the entities whose bounds intersect the sphere.
+    pub fn within_sphere(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        let mut entities = HashSet::default();
+        let radius_squared = radius * radius;
+        self.visit_cells(
+            self.cell(center - Vec3::splat(radius)),
+            self.cell(center + Vec3::splat(radius)),
+            |entity| {
+                if self.entries[&entity].distance_squared(center) <= radius_squared {
+                    entities.insert(entity);
+                }
+            },
+        );
+        entities.into_iter().collect()
+    }
+
+    /// Returns the entities whose bounds intersect the frustum, including its far plane.
+    pub fn in_frustum(&self, frustum: &Frustum) -> Vec<Entity> {
+        let mut entities = HashSet::default();
+        for (cell, cell_entities) in &self.cells {
+            let cell_bounds = Aabb {
+                center: Vec3A::from((cell.as_vec3() + 0.5) * self.cell_size),
+                half_extents: Vec3A::splat(self.cell_size / 2.0),
+            };
+            if !frustum.intersects_obb(&cell_bounds, &Mat4::IDENTITY, true) {
+                continue;
+            }
+            for &entity in cell_entities {
+                if !entities.contains(&entity)
+                    && frustum.intersects_obb(&self.entries[&entity].aabb(), &Mat4::IDENTITY, true)
+                {
+                    entities.insert(entity);
+                }
+            }
+        }
+        entities.into_iter().collect()
+    }
+
+    /// Returns the `k` entities whose bounds are nearest to `point`, with their distance to it,
+    /// sorted by distance. The distance is zero for the entities whose bounds contain the point.
+    pub fn k_nearest(&self, point: Vec3, k: usize) -> Vec<(Entity, f32)> {
+        let mut nearest = Vec::new();
+        let Some((min_cell, max_cell)) = self.occupied else {
+            return nearest;
+        };
+        if k == 0 {
+            return nearest;
+        }
+
+        // Visit shells of cells of growing size around the cell of the point, until the entities
+        // that weren't visited yet are further than the k nearest ones
+        let center = self.cell(point);
+        let first_ring = (min_cell - center).max(center - max_cell).max(IVec3::ZERO);
+        let last_ring = (max_cell - center).max(center - min_cell);
+        let mut visited = HashSet::default();
+        for ring in first_ring.max_element()..=last_ring.max_element() {
+            let ring_min = center - IVec3::splat(ring);
+            let ring_max = center + IVec3::splat(ring);
+            for x in ring_min.x.max(min_cell.x)..=ring_max.x.min(max_cell.x) {
+                for y in ring_min.y.max(min_cell.y)..=ring_max.y.min(max_cell.y) {
+                    let on_shell =
+                        x == ring_min.x || x == ring_max.x || y == ring_min.y || y == ring_max.y;
+                    // Only the two ends of the columns inside the shell are on it
+                    let z_step = if on_shell { 1 } else { 2 * ring as usize };
+                    for z in (ring_min.z..=ring_max.z)
+                        .step_by(z_step)
+                        .filter(|z| (min_cell.z..=max_cell.z).contains(z))
+                    {
+                        for &entity in self.cells.get(&IVec3::new(x, y, z)).into_iter().flatten() {
+                            if visited.insert(entity) {
+                                let distance = self.entries[&entity].distance_squared(point);
+                                nearest.push((entity, distance));
+                            }
+                        }
+                    }
+                }
+            }
+
+            nearest.sort_by(|a, b| a.1.total_cmp(&b.1));
+            nearest.truncate(k);
+            let unvisited_distance = (point - ring_min.as_vec3() * self.cell_size)
+                .min((ring_max + IVec3::ONE).as_vec3() * self.cell_size - point)
+                .min_element();
+            if nearest.len() == k && nearest[k - 1].1 <= unvisited_distance * unvisited_distance {
+                break;
+            }
+        }
+
+        nearest
+            .into_iter()
+            .map(|(entity, distance_squared)| (entity, distance_squared.sqrt()))
+            .collect()
+    }
+
+    /// The cell containing `point`.
+    fn cell(&self, point: Vec3) -> IVec3 {
+        (point / self.cell_size).floor().as_ivec3()
+    }
+
+    /// Calls `visit` with each entity in the cells from `min_cell` to `max_cell`, once per cell
+    /// it overlaps.
+    fn visit_cells(&self, min_cell: IVec3, max_cell: IVec3, mut visit: impl FnMut(Entity)) {
+        let Some((occupied_min, occupied_max)) = self.occupied else {
+            return;
+        };
+        let min_cell = min_cell.max(occupied_min);
+        let max_cell = max_cell.min(occupied_max);
+        if min_cell.cmpgt(max_cell).any() {
+            return;
+        }
+
+        // Iterate through the occupied cells instead of the range if there are fewer of them
+        let range_size = (max_cell - min_cell + IVec3::ONE).as_vec3().to_array();
+        if range_size.iter().product::<f32>() > self.cells.len() as f32 {
+            for (cell, entities) in &self.cells {
+                if cell.cmpge(min_cell).all() && cell.cmple(max_cell).all() {
+                    entities.iter().copied().for_each(&mut visit);
+                }
+            }
+        } else {
+            for x in min_cell.x..=max_cell.x {
+                for y in min_cell.y..=max_cell.y {
+                    for z in min_cell.z..=max_cell.z {
+                        if let Some(entities) = self.cells.get(&IVec3::new(x, y, z)) {
+                            entities.iter().copied().for_each(&mut visit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Inserts `entity` with the given world space bounds, or moves it if it is already indexed.
+    fn update(&mut self, entity: Entity, min: Vec3, max: Vec3) {
+        let first_cell = self.cell(min);
+        let last_cell = self.cell(max);
+        if let Some(entry) = self.entries.get_mut(&entity) {
+            if entry.first_cell == first_cell && entry.last_cell == last_cell {
+                entry.min = min;
+                entry.max = max;
+                return;
+            }
+            self.remove(entity);
+        }
+
+        for x in first_cell.x..=last_cell.x {
+            for y in first_cell.y..=last_cell.y {
+                for z in first_cell.z..=last_cell.z {
+                    self.cells
+                        .entry(IVec3::new(x, y, z))
+                        .or_default()
+                        .push(entity);
+                }
+            }
+        }
+        self.entries.insert(
+            entity,
+            SpatialEntry {
+                min,
+                max,
+                first_cell,
+                last_cell,
+            },
+        );
+        self.occupied = Some(match self.occupied {
+            Some((occupied_min, occupied_max)) => {
+                (occupied_min.min(first_cell), occupied_max.max(last_cell))
+            }
+            None => (first_cell, last_cell),
+        });
+    }
+
+    /// Removes `entity` from the index, if it is indexed.
+    fn remove(&mut self, entity: Entity) {
+        let Some(entry) = self.entries.remove(&entity) else {
+            return;
+        };
+        for x in entry.first_cell.x..=entry.last_cell.x {
+            for y in entry.first_cell.y..=entry.last_cell.y {
+                for z in entry.first_cell.z..=entry.last_cell.z {
+                    let cell = IVec3::new(x, y, z);
+                    if let Some(entities) = self.cells.get_mut(&cell) {
+                        entities.retain(|&other| other != entity);
+                        if entities.is_empty() {
+                            self.cells.remove(&cell);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Updates the [`SpatialIndex`] with the [`SpatialIndexed`] entities that moved, changed bounds,
+/// or were added or removed.
+#[allow(clippy::type_complexity)]
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    changed: Query<
+        (Entity, &GlobalTransform, Option<&Aabb>),
+        (
+            With<SpatialIndexed>,
+            Or<(
+                Changed<GlobalTransform>,
+                Changed<Aabb>,
+                Added<SpatialIndexed>,
+            )>,
+        ),
+    >,
+    removed: RemovedComponents<SpatialIndexed>,
+) {
+    for entity in removed.iter() {
+        index.remove(entity);
+    }
+
+    for (entity, transform, aabb) in &changed {
+        let (min, max) = match aabb {
+            Some(aabb) => {
+                let matrix = transform.compute_matrix();
+                let center = matrix.transform_point3a(aabb.center);
+                let half_extents = Vec3A::new(
+                    Vec3A::from(matrix.x_axis).abs().dot(aabb.half_extents),
+                    Vec3A::from(matrix.y_axis).abs().dot(aabb.half_extents),
+                    Vec3A::from(matrix.z_axis).abs().dot(aabb.half_extents),
+                );
+                (
+                    Vec3::from(center - half_extents),
+                    Vec3::from(center + half_extents),
+                )
+            }
+            None => (transform.translation(), transform.translation()),
+        };
+        index.update(entity, min, max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_points(points: &[Vec3]) -> (SpatialIndex, Vec<Entity>) {
+        let mut index = SpatialIndex::new(2.0);
+        let entities: Vec<Entity> = (0..points.len() as u32).map(Entity::from_raw).collect();
+        for (&entity, &point) in entities.iter().zip(points) {
+            index.update(entity, point - Vec3::splat(0.5), point + Vec3::splat(0.5));
+        }
+        (index, entities)
+    }
+
+    #[test]
+    fn spatial_index_queries() {
+        let points: Vec<Vec3> = (0..10)
+            .flat_map(|x| (0..10).map(move |z| Vec3::new(x as f32 * 3.0, 0.0, z as f32 * 3.0)))
+            .collect();
+        let (mut index, entities) = index_points(&points);
+        assert_eq!(index.len(), 100);
+
+        let ray = Ray {
+            origin: Vec3::new(-10.0, 0.0, 6.0),
+            direction: Vec3::X,
+        };
+        let hits = index.cast_ray(ray, 20.0);
+        assert_eq!(
+            hits,
+            vec![
+                (entities[2], 9.5),
+                (entities[12], 12.5),
+                (entities[22], 15.5),
+                (entities[32], 18.5),
+            ]
+        );
+
+        let mut within = index.within_sphere(Vec3::new(3.0, 0.0, 3.0), 2.6);
+        within.sort();
+        assert_eq!(
+            within,
+            vec![
+                entities[1],
+                entities[10],
+                entities[11],
+                entities[12],
+                entities[21]
+            ]
+        );
+
+        let nearest = index.k_nearest(Vec3::new(26.8, 0.0, 27.2), 2);
+        assert_eq!(nearest[0], (entities[99], 0.0));
+        assert_eq!(nearest[1].0, entities[89]);
+        assert!((nearest[1].1 - 2.3).abs() < 1e-5);
+
+        // Moving and removing entities
+        index.update(entities[99], Vec3::splat(-100.5), Vec3::splat(-99.5));
+        index.remove(entities[89]);
+        let nearest = index.k_nearest(Vec3::new(26.8, 0.0, 27.2), 1);
+        assert_eq!(nearest[0].0, entities[98]);
+        assert_eq!(
+            index.k_nearest(Vec3::splat(-100.0), 1)[0],
+            (entities[99], 0.0)
+        );
+        assert_eq!(index.len(), 99);
+    }
+}
@@ -346,6 +346,12 @@ impl Image {
         #[allow(unused_variables)] supported_compressed_formats: CompressedImageFormats,
         is_srgb: bool,
     ) -> Result<Image, TextureError> {
+        // Images processed by the `MipmapProcessor` are KTX2, whatever their source extension
+        #[cfg(feature = "ktx2")]
+        if buffer.starts_with(&KTX2_IDENTIFIER) {
+            return ktx2_buffer_to_image(buffer, supported_compressed_formats, is_srgb);
+        }
+
         let format = image_type.to_image_format()?;
 
         // Load the image in the expected format.
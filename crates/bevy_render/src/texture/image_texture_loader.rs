@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,51 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// Settings of the [`ImageTextureLoader`], read from the settings file of an image.
+///
+/// For example, `pixel_art.png.meta` could contain `(sampler: Nearest)`. See
+/// [`LoadContext::settings`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+    /// Whether the image stores colors in the sRGB color space. This should be `false` for images
+    /// storing other data, such as normal maps.
+    pub is_srgb: bool,
+    /// The sampler of the image.
+    pub sampler: ImageSamplerSettings,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageSamplerSettings::Default,
+        }
+    }
+}
+
+/// The sampler of an image loaded with [`ImageLoaderSettings`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSamplerSettings {
+    /// Use the default sampler of the [`ImagePlugin`](super::ImagePlugin).
+    #[default]
+    Default,
+    /// Use [`ImageSampler::linear`].
+    Linear,
+    /// Use [`ImageSampler::nearest`].
+    Nearest,
+}
+
+impl From<ImageSamplerSettings> for ImageSampler {
+    fn from(settings: ImageSamplerSettings) -> Self {
+        match settings {
+            ImageSamplerSettings::Default => ImageSampler::Default,
+            ImageSamplerSettings::Linear => ImageSampler::linear(),
+            ImageSamplerSettings::Nearest => ImageSampler::nearest(),
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -45,17 +91,19 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings: ImageLoaderSettings = load_context.settings()?;
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
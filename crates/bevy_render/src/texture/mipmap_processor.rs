@@ -0,0 +1,181 @@
+use anyhow::Error;
+use bevy_asset::{AssetProcessor, ProcessContext};
+use bevy_utils::BoxedFuture;
+use image::{imageops::FilterType, RgbaImage};
+
+use super::ktx2::KTX2_IDENTIFIER;
+
+/// `VK_FORMAT_R8G8B8A8_UNORM`
+const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+/// The size of the KTX2 identifier, header and index.
+const KTX2_LEVEL_INDEX_OFFSET: usize = 80;
+/// The size of a level in the KTX2 level index.
+const KTX2_LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+/// The size of the data format descriptor of an RGBA8 texture.
+const KTX2_DFD_SIZE: usize = 92;
+
+/// An [`AssetProcessor`] generating the mip chain of images ahead of time.
+///
+/// Images read by the `image` crate are processed into uncompressed RGBA8 KTX2 textures with all
+/// their mip levels, which the [`ImageTextureLoader`](super::ImageTextureLoader) loads whatever
+/// the extension of the source. Whether the texture is sRGB is still decided by the
+/// [`ImageLoaderSettings`](super::ImageLoaderSettings) of the image.
+#[derive(Clone, Default)]
+pub struct MipmapProcessor;
+
+impl AssetProcessor for MipmapProcessor {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        _process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async move {
+            let image = image::load_from_memory(bytes)?.into_rgba8();
+            Ok(write_ktx2(&mip_chain(image)))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png", "jpg", "jpeg", "bmp", "tga"]
+    }
+}
+
+/// Returns `image` followed by its mip levels, each half the size of the previous one.
+fn mip_chain(image: RgbaImage) -> Vec<RgbaImage> {
+    let mut levels = vec![image];
+    loop {
+        let previous = levels.last().unwrap();
+        let (width, height) = previous.dimensions();
+        if width == 1 && height == 1 {
+            return levels;
+        }
+        let level = image::imageops::resize(
+            previous,
+            (width / 2).max(1),
+            (height / 2).max(1),
+            FilterType::Triangle,
+        );
+        levels.push(level);
+    }
+}
+
+/// Writes `levels` as an uncompressed RGBA8 KTX2 texture.
+fn write_ktx2(levels: &[RgbaImage]) -> Vec<u8> {
+    let (width, height) = levels[0].dimensions();
+    let level_count = levels.len();
+    let dfd_offset = KTX2_LEVEL_INDEX_OFFSET + level_count * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+
+    let mut ktx2 = Vec::new();
+    ktx2.extend_from_slice(&KTX2_IDENTIFIER);
+    for value in [
+        VK_FORMAT_R8G8B8A8_UNORM,
+        // typeSize
+        1,
+        width,
+        height,
+        // pixelDepth, layerCount
+        0,
+        0,
+        // faceCount
+        1,
+        level_count as u32,
+        // supercompressionScheme
+        0,
+    ] {
+        ktx2.extend_from_slice(&value.to_le_bytes());
+    }
+    // The data format descriptor follows the level index, there is no key/value or
+    // supercompression global data
+    for value in [dfd_offset as u32, KTX2_DFD_SIZE as u32, 0, 0] {
+        ktx2.extend_from_slice(&value.to_le_bytes());
+    }
+    ktx2.extend_from_slice(&[0; 16]);
+
+    // The levels are stored from the smallest to the largest. Every RGBA8 level is a multiple of
+    // 4 bytes long, so they are all aligned as required.
+    let mut level_offset = dfd_offset + KTX2_DFD_SIZE;
+    let mut level_offsets = vec![0; level_count];
+    for (level, image) in levels.iter().enumerate().rev() {
+        level_offsets[level] = level_offset;
+        level_offset += image.as_raw().len();
+    }
+    for (image, offset) in levels.iter().zip(level_offsets) {
+        let length = image.as_raw().len() as u64;
+        for value in [offset as u64, length, length] {
+            ktx2.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    write_rgba8_dfd(&mut ktx2);
+    for image in levels.iter().rev() {
+        ktx2.extend_from_slice(image.as_raw());
+    }
+    ktx2
+}
+
+/// Writes the data format descriptor of a linear RGBA8 texture, made of a single basic
+/// descriptor block.
+fn write_rgba8_dfd(ktx2: &mut Vec<u8>) {
+    ktx2.extend_from_slice(&(KTX2_DFD_SIZE as u32).to_le_bytes());
+    // vendorId and descriptorType
+    ktx2.extend_from_slice(&0u32.to_le_bytes());
+    // versionNumber
+    ktx2.extend_from_slice(&2u16.to_le_bytes());
+    // descriptorBlockSize
+    ktx2.extend_from_slice(&(KTX2_DFD_SIZE as u16 - 4).to_le_bytes());
+    // colorModel (RGBSDA), colorPrimaries (BT709), transferFunction (linear), flags
+    ktx2.extend_from_slice(&[1, 1, 1, 0]);
+    // texelBlockDimension
+    ktx2.extend_from_slice(&[0; 4]);
+    // bytesPlane
+    ktx2.extend_from_slice(&[4, 0, 0, 0, 0, 0, 0, 0]);
+    // One sample per channel: red, green, blue and alpha
+    for (index, channel_type) in [0u8, 1, 2, 15].into_iter().enumerate() {
+        // bitOffset
+        ktx2.extend_from_slice(&(index as u16 * 8).to_le_bytes());
+        // bitLength (minus one) and channelType
+        ktx2.extend_from_slice(&[7, channel_type]);
+        // samplePosition
+        ktx2.extend_from_slice(&[0; 4]);
+        // sampleLower and sampleUpper
+        ktx2.extend_from_slice(&0u32.to_le_bytes());
+        ktx2.extend_from_slice(&255u32.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::texture::{CompressedImageFormats, Image, ImageType};
+    use wgpu::TextureFormat;
+
+    #[test]
+    fn mipmap_processor_output() {
+        let image = RgbaImage::from_fn(8, 2, |x, _| image::Rgba([x as u8 * 32, 0, 0, 255]));
+        let levels = mip_chain(image);
+        let dimensions: Vec<_> = levels.iter().map(RgbaImage::dimensions).collect();
+        assert_eq!(dimensions, vec![(8, 2), (4, 1), (2, 1), (1, 1)]);
+
+        // Processed images are loaded whatever the extension of their source
+        let ktx2 = write_ktx2(&levels);
+        let loaded = Image::from_buffer(
+            &ktx2,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+        )
+        .unwrap();
+        assert_eq!(loaded.texture_descriptor.mip_level_count, 4);
+        assert_eq!(loaded.texture_descriptor.size.width, 8);
+        assert_eq!(loaded.texture_descriptor.size.height, 2);
+        assert_eq!(
+            loaded.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        let expected: Vec<u8> = levels
+            .iter()
+            .flat_map(|level| level.as_raw().iter().copied())
+            .collect();
+        assert_eq!(loaded.data, expected);
+    }
+}
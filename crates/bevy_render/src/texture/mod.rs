@@ -10,6 +10,8 @@ mod image;
 mod image_texture_loader;
 #[cfg(feature = "ktx2")]
 mod ktx2;
+#[cfg(feature = "ktx2")]
+mod mipmap_processor;
 mod texture_cache;
 
 pub(crate) mod image_texture_conversion;
@@ -21,6 +23,8 @@ pub use self::ktx2::*;
 pub use dds::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
+#[cfg(feature = "ktx2")]
+pub use mipmap_processor::*;
 
 pub use fallback_image::*;
 pub use image_texture_loader::*;
@@ -83,6 +87,11 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<HdrTextureLoader>();
         }
 
+        #[cfg(feature = "ktx2")]
+        {
+            app.add_asset_processor(MipmapProcessor);
+        }
+
         app.add_plugin(RenderAssetPlugin::<Image>::with_prepare_asset_label(
             PrepareAssetLabel::PreAssetPrepare,
         ))
@@ -6,6 +6,7 @@ use bevy_app::{CoreStage, Plugin};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::{Children, Parent};
+use bevy_math::{Mat4, Vec3A};
 use bevy_reflect::Reflect;
 use bevy_reflect::{std_traits::ReflectDefault, FromReflect};
 use bevy_transform::components::GlobalTransform;
@@ -18,7 +19,10 @@ use crate::{
         camera_system, Camera, CameraProjection, OrthographicProjection, PerspectiveProjection,
         Projection,
     },
-    mesh::Mesh,
+    mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshBounds, SkinnedMeshInverseBindposes},
+        Mesh,
+    },
     primitives::{Aabb, Frustum, Sphere},
 };
 
@@ -195,6 +199,9 @@ impl VisibleEntities {
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum VisibilitySystems {
     CalculateBounds,
+    /// Label for the [`calculate_skinned_mesh_bounds()`] system updating each frame the [`Aabb`]
+    /// of skinned meshes from the transforms of their joints.
+    CalculateSkinnedMeshBounds,
     UpdateOrthographicFrusta,
     UpdatePerspectiveFrusta,
     UpdateProjectionFrusta,
@@ -214,6 +221,13 @@ impl Plugin for VisibilityPlugin {
             CoreStage::PostUpdate,
             calculate_bounds.label(CalculateBounds).before_commands(),
         )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            calculate_skinned_mesh_bounds
+                .label(CalculateSkinnedMeshBounds)
+                .after(TransformSystem::TransformPropagate)
+                .before_commands(),
+        )
         .add_system_to_stage(
             CoreStage::PostUpdate,
             update_frusta::<OrthographicProjection>
@@ -256,6 +270,7 @@ impl Plugin for VisibilityPlugin {
                 .after(UpdatePerspectiveFrusta)
                 .after(UpdateProjectionFrusta)
                 .after(VisibilityPropagate)
+                .after(CalculateSkinnedMeshBounds)
                 .after(TransformSystem::TransformPropagate),
         );
     }
@@ -275,6 +290,91 @@ pub fn calculate_bounds(
     }
 }
 
+/// Updates the [`Aabb`] of skinned meshes from the [`GlobalTransform`]s of their joints.
+///
+/// Skinned vertices are blended from the transforms of their joints, so they stay inside of the
+/// union of the [`SkinnedMeshBounds`] of their joints transformed by the joints. This union is
+/// converted to the space of the skinned mesh entity, as used by [`check_visibility()`].
+///
+/// The [`SkinnedMeshBounds`] are computed from the mesh when missing. They can be inserted
+/// manually instead, and have to be removed to be computed again after changing the mesh.
+#[allow(clippy::type_complexity)]
+pub fn calculate_skinned_mesh_bounds(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joints: Query<&GlobalTransform>,
+    without_bounds: Query<
+        (Entity, &Handle<Mesh>),
+        (
+            With<SkinnedMesh>,
+            Without<SkinnedMeshBounds>,
+            Without<NoFrustumCulling>,
+        ),
+    >,
+    mut skinned_meshes: Query<
+        (
+            &SkinnedMesh,
+            &SkinnedMeshBounds,
+            &GlobalTransform,
+            &mut Aabb,
+        ),
+        Without<NoFrustumCulling>,
+    >,
+) {
+    for (entity, mesh_handle) in &without_bounds {
+        if let Some(bounds) = meshes
+            .get(mesh_handle)
+            .and_then(SkinnedMeshBounds::from_mesh)
+        {
+            commands.entity(entity).insert(bounds);
+        }
+    }
+
+    for (skinned_mesh, bounds, transform, mut aabb) in &mut skinned_meshes {
+        let Some(bindposes) = inverse_bindposes.get(&skinned_mesh.inverse_bindposes) else {
+            continue;
+        };
+        let mut minimum = Vec3A::splat(f32::MAX);
+        let mut maximum = Vec3A::splat(f32::MIN);
+        for ((joint, inverse_bindpose), joint_aabb) in skinned_mesh
+            .joints
+            .iter()
+            .zip(bindposes.iter())
+            .zip(&bounds.joint_aabbs)
+        {
+            let (Some(joint_aabb), Ok(joint_transform)) = (joint_aabb, joints.get(*joint)) else {
+                continue;
+            };
+            let joint_aabb = transform_aabb(
+                joint_aabb,
+                &(joint_transform.compute_matrix() * *inverse_bindpose),
+            );
+            minimum = minimum.min(joint_aabb.min());
+            maximum = maximum.max(joint_aabb.max());
+        }
+        if minimum.cmpgt(maximum).any() {
+            continue;
+        }
+        *aabb = transform_aabb(
+            &Aabb::from_min_max(minimum.into(), maximum.into()),
+            &transform.compute_matrix().inverse(),
+        );
+    }
+}
+
+/// The axis-aligned bounding box of an [`Aabb`] transformed by `matrix`.
+fn transform_aabb(aabb: &Aabb, matrix: &Mat4) -> Aabb {
+    let center = matrix.transform_point3a(aabb.center);
+    let half_extents = matrix.x_axis.truncate().abs() * aabb.half_extents.x
+        + matrix.y_axis.truncate().abs() * aabb.half_extents.y
+        + matrix.z_axis.truncate().abs() * aabb.half_extents.z;
+    Aabb {
+        center,
+        half_extents: half_extents.into(),
+    }
+}
+
 pub fn update_frusta<T: Component + CameraProjection + Send + Sync + 'static>(
     mut views: Query<(&GlobalTransform, &T, &mut Frustum)>,
 ) {
@@ -460,6 +560,50 @@ mod test {
 
     use bevy_hierarchy::BuildWorldChildren;
 
+    #[test]
+    fn skinned_mesh_bounds() {
+        use crate::{
+            mesh::{skinning::SkinnedMeshBounds, VertexAttributeValues},
+            render_resource::PrimitiveTopology,
+        };
+        use bevy_math::Vec3;
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 2.0, 0.0], [-1.0, 4.0, 1.0]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0, 1, 0, 0], [1, 0, 0, 0], [1, 0, 0, 0]]),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![
+                [0.5, 0.5, 0.0, 0.0],
+                [1.0, 0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0, 0.0],
+            ],
+        );
+        let bounds = SkinnedMeshBounds::from_mesh(&mesh).unwrap();
+        assert_eq!(2, bounds.joint_aabbs.len());
+        let joint_0 = bounds.joint_aabbs[0].as_ref().unwrap();
+        assert_eq!(Vec3A::ZERO, joint_0.center);
+        assert_eq!(Vec3A::ZERO, joint_0.half_extents);
+        let joint_1 = bounds.joint_aabbs[1].as_ref().unwrap();
+        assert_eq!(Vec3A::new(-1.0, 0.0, 0.0), joint_1.min());
+        assert_eq!(Vec3A::new(1.0, 4.0, 1.0), joint_1.max());
+
+        // A joint rotated by a quarter turn around z and moved along x
+        let matrix = Mat4::from_rotation_translation(
+            bevy_math::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        );
+        let aabb = transform_aabb(joint_1, &matrix);
+        assert!(aabb.min().abs_diff_eq(Vec3A::new(6.0, -1.0, 0.0), 1e-5));
+        assert!(aabb.max().abs_diff_eq(Vec3A::new(10.0, 1.0, 1.0), 1e-5));
+    }
+
     #[test]
     fn visibility_propagation() {
         let mut app = App::new();
@@ -0,0 +1,127 @@
+use serde::{de::DeserializeSeed, Serialize};
+use thiserror::Error;
+
+/// The bytes binary scene files start with.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+
+/// The version of the binary scene format written by [`serialize_binary`].
+///
+/// It is stored after [`BINARY_SCENE_MAGIC`], so files written by a newer format version are
+/// rejected with [`BinarySceneError::UnsupportedVersion`] instead of being misread.
+pub const BINARY_SCENE_VERSION: u16 = 1;
+
+/// An error that occurs when reading or writing a binary scene.
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    #[error("not a binary scene: missing the binary scene header")]
+    MissingHeader,
+    #[error("binary scene version {0} is not supported, the latest supported version is {BINARY_SCENE_VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("invalid binary scene: {0}")]
+    Postcard(#[from] postcard::Error),
+}
+
+/// Returns `true` if `bytes` start with the header of a binary scene.
+pub fn is_binary_scene(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_SCENE_MAGIC)
+}
+
+/// Serialize a given Rust data structure into the compact binary scene format.
+///
+/// The data is serialized with [`postcard`] after a header made of [`BINARY_SCENE_MAGIC`] and
+/// [`BINARY_SCENE_VERSION`]. It is smaller and faster to load than ron, but can't be edited by
+/// hand.
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, BinarySceneError>
+where
+    S: Serialize,
+{
+    let mut bytes = BINARY_SCENE_MAGIC.to_vec();
+    bytes.extend_from_slice(&BINARY_SCENE_VERSION.to_le_bytes());
+    bytes.extend(postcard::to_allocvec(&serialize)?);
+    Ok(bytes)
+}
+
+/// Deserialize data written by [`serialize_binary`] with the given seed, such as a
+/// [`SceneDeserializer`](crate::serde::SceneDeserializer).
+pub fn deserialize_binary<'de, D>(seed: D, bytes: &'de [u8]) -> Result<D::Value, BinarySceneError>
+where
+    D: DeserializeSeed<'de>,
+{
+    let header_len = BINARY_SCENE_MAGIC.len() + 2;
+    if !is_binary_scene(bytes) || bytes.len() < header_len {
+        return Err(BinarySceneError::MissingHeader);
+    }
+    let version = u16::from_le_bytes([bytes[header_len - 2], bytes[header_len - 1]]);
+    if version > BINARY_SCENE_VERSION {
+        return Err(BinarySceneError::UnsupportedVersion(version));
+    }
+    let mut deserializer = postcard::Deserializer::from_bytes(&bytes[header_len..]);
+    Ok(seed.deserialize(&mut deserializer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serde::SceneDeserializer, DynamicScene};
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, ReflectComponent, World},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Foo(i32);
+
+    #[test]
+    fn should_roundtrip_binary_scene() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Foo>();
+        world.spawn(Foo(123));
+        world.spawn(Foo(456));
+
+        let scene = DynamicScene::from_world(&world, &registry);
+        let bytes = scene.serialize_binary(&registry.0).unwrap();
+        assert!(is_binary_scene(&bytes));
+        assert!(bytes.len() < scene.serialize_ron(&registry.0).unwrap().len());
+
+        let deserialized_scene = deserialize_binary(
+            SceneDeserializer {
+                type_registry: &registry.read(),
+            },
+            &bytes,
+        )
+        .unwrap();
+        assert_eq!(2, deserialized_scene.entities.len());
+
+        let mut dst_world = World::new();
+        dst_world.insert_resource(registry);
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        let mut values = dst_world
+            .query::<&Foo>()
+            .iter(&dst_world)
+            .map(|foo| foo.0)
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(vec![123, 456], values);
+    }
+
+    #[test]
+    fn should_reject_newer_version() {
+        let mut bytes = BINARY_SCENE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(BINARY_SCENE_VERSION + 1).to_le_bytes());
+        let registry = AppTypeRegistry::default();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        assert!(matches!(
+            deserialize_binary(scene_deserializer, &bytes),
+            Err(BinarySceneError::UnsupportedVersion(2))
+        ));
+        assert!(!is_binary_scene(b"(entities: {})"));
+    }
+}
@@ -1,12 +1,15 @@
-use crate::{DynamicSceneBuilder, Scene, SceneSpawnError};
+use crate::{
+    reflect_entities::map_component_entities, DynamicSceneBuilder, Scene, SceneSpawnError,
+};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
-    entity::EntityMap,
+    entity::{Entity, EntityMap},
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
+use bevy_utils::HashMap;
 
 #[cfg(feature = "serialize")]
 use crate::serde::SceneSerializer;
@@ -51,6 +54,24 @@ impl DynamicScene {
         builder.build()
     }
 
+    /// Clones the dynamic entities of this scene and their components.
+    pub(crate) fn clone_dynamic(&self) -> Self {
+        Self {
+            entities: self
+                .entities
+                .iter()
+                .map(|scene_entity| DynamicEntity {
+                    entity: scene_entity.entity,
+                    components: scene_entity
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
     /// Write the dynamic entities and their corresponding components to the given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
@@ -62,46 +83,7 @@ impl DynamicScene {
         entity_map: &mut EntityMap,
         type_registry: &AppTypeRegistry,
     ) -> Result<(), SceneSpawnError> {
-        let type_registry = type_registry.read();
-
-        for scene_entity in &self.entities {
-            // Fetch the entity with the given entity id from the `entity_map`
-            // or spawn a new entity with a transiently unique id if there is
-            // no corresponding entry.
-            let entity = *entity_map
-                .entry(bevy_ecs::entity::Entity::from_raw(scene_entity.entity))
-                .or_insert_with(|| world.spawn_empty().id());
-
-            // Apply/ add each component to the given entity.
-            for component in &scene_entity.components {
-                let registration = type_registry
-                    .get_with_name(component.type_name())
-                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                        type_name: component.type_name().to_string(),
-                    })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_name: component.type_name().to_string(),
-                        }
-                    })?;
-
-                // If the entity already has the given component attached,
-                // just apply the (possibly) new value, otherwise add the
-                // component to the entity.
-                reflect_component.apply_or_insert(world, entity, &**component);
-            }
-        }
-
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, entity_map)
-                    .unwrap();
-            }
-        }
-
-        Ok(())
+        write_entities_to_world(&self.entities, world, entity_map, &type_registry.read())
     }
 
     /// Write the dynamic entities and their corresponding components to the given world.
@@ -124,6 +106,179 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the compact binary scene format, see
+    /// [`serialize_binary`](crate::serialize_binary).
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, crate::BinarySceneError> {
+        crate::serialize_binary(SceneSerializer::new(self, registry))
+    }
+}
+
+/// Write the given dynamic entities and their corresponding components to the given world,
+/// remapping the entities referenced by those components through `entity_map`.
+pub(crate) fn write_entities_to_world(
+    entities: &[DynamicEntity],
+    world: &mut World,
+    entity_map: &mut EntityMap,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let mut written_components = Vec::new();
+    for scene_entity in entities {
+        // Fetch the entity with the given entity id from the `entity_map`
+        // or spawn a new entity with a transiently unique id if there is
+        // no corresponding entry.
+        let entity = *entity_map
+            .entry(Entity::from_raw(scene_entity.entity))
+            .or_insert_with(|| world.spawn_empty().id());
+
+        // Apply/ add each component to the given entity.
+        for component in &scene_entity.components {
+            // If the entity already has the given component attached,
+            // just apply the (possibly) new value, otherwise add the
+            // component to the entity.
+            reflect_component(type_registry, &**component)?.apply_or_insert(
+                world,
+                entity,
+                &**component,
+            );
+            written_components.push((entity, component.type_name()));
+        }
+    }
+
+    for registration in type_registry.iter() {
+        if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+            map_entities_reflect
+                .map_entities(world, entity_map)
+                .unwrap();
+        }
+    }
+    map_written_components(world, &written_components, entity_map, type_registry);
+
+    Ok(())
+}
+
+/// Write the differences between the `previous` and the current version of a scene's dynamic
+/// entities to an instance of `previous` in the given world.
+///
+/// Only the components whose value changed in the scene are written, and the components and
+/// entities removed from the scene are removed from the instance. Components that didn't change
+/// in the scene keep the value they have in the world, so run-time state of the instance survives.
+pub(crate) fn write_entity_changes_to_world(
+    previous: &[DynamicEntity],
+    entities: &[DynamicEntity],
+    world: &mut World,
+    entity_map: &mut EntityMap,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let previous = previous
+        .iter()
+        .map(|scene_entity| (scene_entity.entity, scene_entity))
+        .collect::<HashMap<_, _>>();
+    let mut written_components = Vec::new();
+
+    for scene_entity in entities {
+        let previous = previous.get(&scene_entity.entity);
+        let entity = match entity_map.get(Entity::from_raw(scene_entity.entity)) {
+            // entities despawned at run-time stay despawned
+            Ok(entity) if world.get_entity(entity).is_none() => continue,
+            Ok(entity) => entity,
+            Err(_) => {
+                let entity = world.spawn_empty().id();
+                entity_map.insert(Entity::from_raw(scene_entity.entity), entity);
+                entity
+            }
+        };
+
+        for component in &scene_entity.components {
+            let unchanged = previous
+                .and_then(|previous| find_component(&previous.components, &**component))
+                .and_then(|previous| previous.reflect_partial_eq(&**component))
+                .unwrap_or(false);
+            if !unchanged {
+                reflect_component(type_registry, &**component)?.apply_or_insert(
+                    world,
+                    entity,
+                    &**component,
+                );
+                written_components.push((entity, component.type_name()));
+            }
+        }
+        for component in previous.iter().flat_map(|previous| &previous.components) {
+            if find_component(&scene_entity.components, &**component).is_none() {
+                reflect_component(type_registry, &**component)?.remove(world, entity);
+            }
+        }
+    }
+
+    let removed_entities = previous.keys().filter(|&&id| {
+        !entities
+            .iter()
+            .any(|scene_entity| scene_entity.entity == id)
+    });
+    for &id in removed_entities {
+        if let Some(entity) = entity_map.remove(Entity::from_raw(id)) {
+            world.despawn(entity);
+        }
+    }
+
+    // The components that weren't written already reference entities of the world
+    for &(entity, type_name) in &written_components {
+        if let Some(map_entities_reflect) = type_registry
+            .get_with_name(type_name)
+            .and_then(|registration| registration.data::<ReflectMapEntities>())
+        {
+            map_entities_reflect
+                .map_specific_entities(world, entity_map, &[entity])
+                .unwrap();
+        }
+    }
+    map_written_components(world, &written_components, entity_map, type_registry);
+
+    Ok(())
+}
+
+/// Remaps the reflected entity fields of the components written to the world from a scene.
+fn map_written_components(
+    world: &mut World,
+    written_components: &[(Entity, &str)],
+    entity_map: &EntityMap,
+    type_registry: &TypeRegistry,
+) {
+    for &(entity, type_name) in written_components {
+        if let Some(registration) = type_registry.get_with_name(type_name) {
+            map_component_entities(world, entity, registration, entity_map);
+        }
+    }
+}
+
+fn find_component<'a>(
+    components: &'a [Box<dyn Reflect>],
+    component: &dyn Reflect,
+) -> Option<&'a dyn Reflect> {
+    components
+        .iter()
+        .find(|other| other.type_name() == component.type_name())
+        .map(|other| &**other)
+}
+
+pub(crate) fn reflect_component<'a>(
+    type_registry: &'a TypeRegistry,
+    component: &dyn Reflect,
+) -> Result<&'a ReflectComponent, SceneSpawnError> {
+    let registration = type_registry
+        .get_with_name(component.type_name())
+        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+            type_name: component.type_name().to_string(),
+        })?;
+    registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_name: component.type_name().to_string(),
+        })
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -137,3 +292,143 @@ where
         .new_line("\n".to_string());
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::write_entity_changes_to_world;
+    use crate::DynamicScene;
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::{Entity, EntityMap},
+        prelude::{Component, ReflectComponent, World},
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Foo(i32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Bar(i32);
+
+    #[test]
+    fn write_entity_changes_keeps_unchanged_components() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Foo>();
+        registry.write().register::<Bar>();
+
+        let mut source = World::new();
+        let a = source.spawn((Foo(1), Bar(1))).id();
+        let b = source.spawn(Foo(2)).id();
+        let c = source.spawn(Foo(3)).id();
+        let previous = DynamicScene::from_world(&source, &registry);
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        let mut entity_map = EntityMap::default();
+        previous
+            .write_to_world(&mut world, &mut entity_map)
+            .unwrap();
+        let instance = |entity: Entity| entity_map.get(entity).unwrap();
+        let (instance_a, instance_b, instance_c) = (instance(a), instance(b), instance(c));
+        world.get_mut::<Foo>(instance_a).unwrap().0 = 10;
+        world.get_mut::<Bar>(instance_a).unwrap().0 = 20;
+
+        source.get_mut::<Bar>(a).unwrap().0 = 5;
+        source.entity_mut(b).remove::<Foo>();
+        source.entity_mut(b).insert(Bar(7));
+        let d = source.spawn(Foo(4)).id();
+        source.despawn(c);
+        let scene = DynamicScene::from_world(&source, &registry);
+
+        write_entity_changes_to_world(
+            &previous.entities,
+            &scene.entities,
+            &mut world,
+            &mut entity_map,
+            &registry.read(),
+        )
+        .unwrap();
+
+        // the run-time value of the unchanged component is kept
+        assert_eq!(10, world.get::<Foo>(instance_a).unwrap().0);
+        assert_eq!(5, world.get::<Bar>(instance_a).unwrap().0);
+        assert!(world.get::<Foo>(instance_b).is_none());
+        assert_eq!(7, world.get::<Bar>(instance_b).unwrap().0);
+        assert!(world.get_entity(instance_c).is_none());
+        assert!(entity_map.get(c).is_err());
+        assert_eq!(4, world.get::<Foo>(entity_map.get(d).unwrap()).unwrap().0);
+    }
+
+    #[test]
+    fn write_entity_changes_keeps_hierarchy() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Foo>();
+        registry.write().register::<Parent>();
+        registry.write().register::<Children>();
+
+        let mut source = World::new();
+        let parent = source.spawn(Foo(1)).id();
+        let child = source.spawn(Foo(2)).id();
+        source.entity_mut(parent).push_children(&[child]);
+        let previous = DynamicScene::from_world(&source, &registry);
+
+        // the ids of the instance differ from the ids of the scene
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        for _ in 0..3 {
+            world.spawn_empty();
+        }
+        let mut entity_map = EntityMap::default();
+        previous
+            .write_to_world(&mut world, &mut entity_map)
+            .unwrap();
+        let instance_parent = entity_map.get(parent).unwrap();
+        let instance_child = entity_map.get(child).unwrap();
+
+        // only a component of the parent changes, its hierarchy is left as it is
+        source.get_mut::<Foo>(parent).unwrap().0 = 5;
+        let scene = DynamicScene::from_world(&source, &registry);
+        write_entity_changes_to_world(
+            &previous.entities,
+            &scene.entities,
+            &mut world,
+            &mut entity_map,
+            &registry.read(),
+        )
+        .unwrap();
+        assert_eq!(5, world.get::<Foo>(instance_parent).unwrap().0);
+        assert_eq!(
+            &[instance_child],
+            &**world.get::<Children>(instance_parent).unwrap()
+        );
+        assert_eq!(
+            instance_parent,
+            world.get::<Parent>(instance_child).unwrap().get()
+        );
+
+        // a new child changes the hierarchy, which is mapped to the instance
+        let new_child = source.spawn(Foo(3)).id();
+        source.entity_mut(parent).push_children(&[new_child]);
+        let next_scene = DynamicScene::from_world(&source, &registry);
+        write_entity_changes_to_world(
+            &scene.entities,
+            &next_scene.entities,
+            &mut world,
+            &mut entity_map,
+            &registry.read(),
+        )
+        .unwrap();
+        let instance_new_child = entity_map.get(new_child).unwrap();
+        assert_eq!(
+            &[instance_child, instance_new_child],
+            &**world.get::<Children>(instance_parent).unwrap()
+        );
+        assert_eq!(
+            instance_parent,
+            world.get::<Parent>(instance_new_child).unwrap().get()
+        );
+    }
+}
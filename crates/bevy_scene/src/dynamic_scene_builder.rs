@@ -1,6 +1,13 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, SceneFilter, WorldSnapshot};
 use bevy_app::AppTypeRegistry;
-use bevy_ecs::{prelude::Entity, reflect::ReflectComponent, world::World};
+use bevy_ecs::{
+    component::Component,
+    prelude::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::Reflect;
 use bevy_utils::default;
 use std::collections::BTreeMap;
 
@@ -12,6 +19,32 @@ use std::collections::BTreeMap;
 /// This means that inserting `Entity(1v0)` then `Entity(0v0)` will always result in the entities
 /// being ordered as `[Entity(0v0), Entity(1v0)]`.
 ///
+/// # Filters
+///
+/// The components and resources that are extracted can be limited with a [`SceneFilter`], such as
+/// to only save the gameplay state of a world and not the components used for rendering:
+/// ```
+/// # use bevy_scene::DynamicSceneBuilder;
+/// # use bevy_app::AppTypeRegistry;
+/// # use bevy_ecs::{component::Component, reflect::ReflectComponent, world::World};
+/// # use bevy_reflect::Reflect;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct Health;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct Player;
+/// # let mut world = World::default();
+/// # world.init_resource::<AppTypeRegistry>();
+/// let mut builder = DynamicSceneBuilder::from_world(&world);
+/// builder
+///     .allow::<Health>()
+///     .allow::<Player>()
+///     .extract_entities(world.iter_entities().map(|entity| entity.id()))
+///     .remove_empty_entities();
+/// let dynamic_scene = builder.build();
+/// ```
+///
 /// # Example
 /// ```
 /// # use bevy_scene::DynamicSceneBuilder;
@@ -32,6 +65,9 @@ use std::collections::BTreeMap;
 /// ```
 pub struct DynamicSceneBuilder<'w> {
     extracted_scene: BTreeMap<u32, DynamicEntity>,
+    extracted_resources: BTreeMap<String, Box<dyn Reflect>>,
+    component_filter: SceneFilter,
+    resource_filter: SceneFilter,
     type_registry: AppTypeRegistry,
     original_world: &'w World,
 }
@@ -40,11 +76,7 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// Prepare a builder that will extract entities and their component from the given [`World`].
     /// All components registered in that world's [`AppTypeRegistry`] resource will be extracted.
     pub fn from_world(world: &'w World) -> Self {
-        Self {
-            extracted_scene: default(),
-            type_registry: world.resource::<AppTypeRegistry>().clone(),
-            original_world: world,
-        }
+        Self::from_world_with_type_registry(world, world.resource::<AppTypeRegistry>().clone())
     }
 
     /// Prepare a builder that will extract entities and their component from the given [`World`].
@@ -52,21 +84,75 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world_with_type_registry(world: &'w World, type_registry: AppTypeRegistry) -> Self {
         Self {
             extracted_scene: default(),
+            extracted_resources: default(),
+            component_filter: default(),
+            resource_filter: default(),
             type_registry,
             original_world: world,
         }
     }
 
+    /// Sets the filter of the components extracted by the next calls to
+    /// [`Self::extract_entities`].
+    pub fn with_filter(&mut self, filter: SceneFilter) -> &mut Self {
+        self.component_filter = filter;
+        self
+    }
+
+    /// Sets the filter of the resources extracted by the next calls to
+    /// [`Self::extract_resources`].
+    pub fn with_resource_filter(&mut self, filter: SceneFilter) -> &mut Self {
+        self.resource_filter = filter;
+        self
+    }
+
+    /// Allows the component `T` to be extracted, see [`SceneFilter::allow`].
+    pub fn allow<T: Component>(&mut self) -> &mut Self {
+        self.component_filter.allow::<T>();
+        self
+    }
+
+    /// Prevents the component `T` from being extracted, see [`SceneFilter::deny`].
+    pub fn deny<T: Component>(&mut self) -> &mut Self {
+        self.component_filter.deny::<T>();
+        self
+    }
+
+    /// Allows the resource `T` to be extracted, see [`SceneFilter::allow`].
+    pub fn allow_resource<T: Resource>(&mut self) -> &mut Self {
+        self.resource_filter.allow::<T>();
+        self
+    }
+
+    /// Prevents the resource `T` from being extracted, see [`SceneFilter::deny`].
+    pub fn deny_resource<T: Resource>(&mut self) -> &mut Self {
+        self.resource_filter.deny::<T>();
+        self
+    }
+
     /// Consume the builder, producing a [`DynamicScene`].
     ///
     /// To make sure the dynamic scene doesn't contain entities without any components, call
     /// [`Self::remove_empty_entities`] before building the scene.
+    ///
+    /// Dynamic scenes don't contain resources, use [`Self::build_snapshot`] to keep the
+    /// extracted resources.
     pub fn build(self) -> DynamicScene {
         DynamicScene {
             entities: self.extracted_scene.into_values().collect(),
         }
     }
 
+    /// Consume the builder, producing a [`WorldSnapshot`] of the extracted entities and
+    /// resources.
+    pub fn build_snapshot(self) -> WorldSnapshot {
+        WorldSnapshot::from_parts(
+            self.original_world,
+            self.extracted_resources.into_values().collect(),
+            self.extracted_scene.into_values().collect(),
+        )
+    }
+
     /// Extract one entity from the builder's [`World`].
     ///
     /// Re-extracting an entity that was already extracted will have no effect.
@@ -129,7 +215,9 @@ impl<'w> DynamicSceneBuilder<'w> {
                     .original_world
                     .components()
                     .get_info(component_id)
-                    .and_then(|info| type_registry.get(info.type_id().unwrap()))
+                    .and_then(|info| info.type_id())
+                    .filter(|&type_id| self.component_filter.is_allowed_by_id(type_id))
+                    .and_then(|type_id| type_registry.get(type_id))
                     .and_then(|registration| registration.data::<ReflectComponent>());
 
                 if let Some(reflect_component) = reflect_component {
@@ -145,13 +233,44 @@ impl<'w> DynamicSceneBuilder<'w> {
         drop(type_registry);
         self
     }
+
+    /// Extract the resources of the builder's [`World`] registered with [`ReflectResource`].
+    ///
+    /// Re-extracting a resource that was already extracted updates its value.
+    pub fn extract_resources(&mut self) -> &mut Self {
+        let type_registry = self.type_registry.read();
+
+        for registration in type_registry.iter() {
+            if !self
+                .resource_filter
+                .is_allowed_by_id(registration.type_id())
+            {
+                continue;
+            }
+            let resource = registration
+                .data::<ReflectResource>()
+                .and_then(|reflect_resource| reflect_resource.reflect(self.original_world));
+            if let Some(resource) = resource {
+                self.extracted_resources
+                    .insert(resource.type_name().to_string(), resource.clone_value());
+            }
+        }
+
+        drop(type_registry);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::{
-        component::Component, prelude::Entity, query::With, reflect::ReflectComponent, world::World,
+        component::Component,
+        prelude::Entity,
+        query::With,
+        reflect::{ReflectComponent, ReflectResource},
+        system::Resource,
+        world::World,
     };
 
     use bevy_reflect::Reflect;
@@ -165,6 +284,13 @@ mod tests {
     #[reflect(Component)]
     struct ComponentB;
 
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceA;
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceB;
+
     #[test]
     fn extract_one_entity() {
         let mut world = World::default();
@@ -285,6 +411,39 @@ mod tests {
         assert_eq!(scene_entities, [entity_a_b.index(), entity_a.index()]);
     }
 
+    #[test]
+    fn extract_filtered_components_and_resources() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+            register.register::<ResourceA>();
+            register.register::<ResourceB>();
+        }
+        world.insert_resource(atr);
+        world.insert_resource(ResourceA);
+        world.insert_resource(ResourceB);
+
+        let entity = world.spawn((ComponentA, ComponentB)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder
+            .deny::<ComponentB>()
+            .extract_entity(entity)
+            .allow_resource::<ResourceB>()
+            .extract_resources();
+        let snapshot = builder.build_snapshot();
+
+        assert_eq!(snapshot.entities.len(), 1);
+        assert_eq!(snapshot.entities[0].components.len(), 1);
+        assert!(snapshot.entities[0].components[0].represents::<ComponentA>());
+        assert_eq!(snapshot.resources.len(), 1);
+        assert!(snapshot.resources[0].represents::<ResourceB>());
+    }
+
     #[test]
     fn remove_componentless_entity() {
         let mut world = World::default();
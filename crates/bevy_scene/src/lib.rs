@@ -1,24 +1,39 @@
+#[cfg(feature = "serialize")]
+mod binary;
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod reflect_entities;
 mod scene;
+mod scene_filter;
 mod scene_loader;
+mod scene_overrides;
 mod scene_spawner;
+mod world_snapshot;
+mod world_transfer;
 
 #[cfg(feature = "serialize")]
 pub mod serde;
 
+#[cfg(feature = "serialize")]
+pub use binary::*;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use reflect_entities::map_reflected_entities;
 pub use scene::*;
+pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_overrides::*;
 pub use scene_spawner::*;
+pub use world_snapshot::*;
+pub use world_transfer::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner,
+        WorldSnapshot,
     };
 }
 
@@ -36,9 +51,11 @@ impl Plugin for ScenePlugin {
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceSpawned>()
             .add_system_to_stage(CoreStage::PreUpdate, scene_spawner_system.at_end())
             // Systems `*_bundle_spawner` must run before `scene_spawner_system`
-            .add_system_to_stage(CoreStage::PreUpdate, scene_spawner);
+            .add_system_to_stage(CoreStage::PreUpdate, scene_spawner)
+            .add_system_to_stage(CoreStage::PreUpdate, scene_overrides);
     }
 }
 
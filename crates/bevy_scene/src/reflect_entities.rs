@@ -0,0 +1,171 @@
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_reflect::{Reflect, ReflectMut, TypeRegistration};
+
+/// Maps the [`Entity`] values found in the fields of a reflected value through `entity_map`.
+///
+/// Entities that are not in `entity_map`, such as entities outside of a scene, are left
+/// unchanged. Map keys are not mapped.
+pub fn map_reflected_entities(value: &mut dyn Reflect, entity_map: &EntityMap) {
+    if let Some(entity) = value.downcast_mut::<Entity>() {
+        if let Ok(mapped_entity) = entity_map.get(*entity) {
+            *entity = mapped_entity;
+        }
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, entity_map);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, entity_map);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, entity_map);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, entity_map);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, entity_map);
+                }
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys = (0..value.len())
+                .filter_map(|index| value.get_at(index))
+                .map(|(key, _)| key.clone_value())
+                .collect::<Vec<_>>();
+            for key in keys {
+                if let Some(item) = value.get_mut(&*key) {
+                    map_reflected_entities(item, entity_map);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, entity_map);
+                }
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
+
+/// Maps the entities referenced by the component of `entity` with the given type registration
+/// through `entity_map`.
+///
+/// Components registered with [`ReflectMapEntities`] are skipped, as they map their entities
+/// themselves. The entities in the reflected fields of other components are mapped with
+/// [`map_reflected_entities`].
+pub(crate) fn map_component_entities(
+    world: &mut World,
+    entity: Entity,
+    registration: &TypeRegistration,
+    entity_map: &EntityMap,
+) {
+    if registration.data::<ReflectMapEntities>().is_some() {
+        return;
+    }
+    if let Some(mut component) = registration
+        .data::<ReflectComponent>()
+        .and_then(|reflect_component| reflect_component.reflect_mut(world, entity))
+    {
+        map_reflected_entities(&mut *component, entity_map);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_reflected_entities;
+    use crate::DynamicScene;
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::{Entity, EntityMap},
+        prelude::{Component, ReflectComponent, World},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Reflect)]
+    struct Target {
+        entity: Entity,
+        optional: Option<Entity>,
+        list: Vec<(u32, Entity)>,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Follow(Option<Entity>);
+
+    #[test]
+    fn map_nested_entities() {
+        let mut entity_map = EntityMap::default();
+        entity_map.insert(Entity::from_raw(0), Entity::from_raw(10));
+        entity_map.insert(Entity::from_raw(1), Entity::from_raw(11));
+
+        let mut target = Target {
+            entity: Entity::from_raw(0),
+            optional: Some(Entity::from_raw(1)),
+            list: vec![(1, Entity::from_raw(0)), (2, Entity::from_raw(5))],
+        };
+        map_reflected_entities(&mut target, &entity_map);
+
+        assert_eq!(Entity::from_raw(10), target.entity);
+        assert_eq!(Some(Entity::from_raw(11)), target.optional);
+        // entities outside of the map are kept
+        assert_eq!(
+            vec![(1, Entity::from_raw(10)), (2, Entity::from_raw(5))],
+            target.list
+        );
+    }
+
+    #[test]
+    fn map_component_entities_on_write() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Follow>();
+
+        let mut source = World::new();
+        let leader = source.spawn(Follow(None)).id();
+        let follower = source.spawn(Follow(Some(leader))).id();
+        let scene = DynamicScene::from_world(&source, &registry);
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        // shift the entities of the instance away from the entities of the scene
+        world.spawn_empty();
+        world.spawn_empty();
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let instance_leader = entity_map.get(leader).unwrap();
+        let instance_follower = entity_map.get(follower).unwrap();
+        assert_ne!(leader, instance_leader);
+        assert_eq!(
+            Some(instance_leader),
+            world.get::<Follow>(instance_follower).unwrap().0
+        );
+    }
+}
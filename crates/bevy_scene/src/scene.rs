@@ -6,7 +6,9 @@ use bevy_ecs::{
 };
 use bevy_reflect::TypeUuid;
 
-use crate::{DynamicScene, InstanceInfo, SceneSpawnError};
+use crate::{
+    reflect_entities::map_component_entities, DynamicScene, InstanceInfo, SceneSpawnError,
+};
 
 /// To spawn a scene, you can use either:
 /// * [`SceneSpawner::spawn`](crate::SceneSpawner::spawn)
@@ -61,6 +63,7 @@ impl Scene {
         };
 
         let type_registry = type_registry.read();
+        let mut written_components = Vec::new();
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
                 let entity = *instance_info
@@ -74,19 +77,19 @@ impl Scene {
                         .get_info(component_id)
                         .expect("component_ids in archetypes should have ComponentInfo");
 
-                    let reflect_component = type_registry
+                    let registration = type_registry
                         .get(component_info.type_id().unwrap())
                         .ok_or_else(|| SceneSpawnError::UnregisteredType {
                             type_name: component_info.name().to_string(),
-                        })
-                        .and_then(|registration| {
-                            registration.data::<ReflectComponent>().ok_or_else(|| {
-                                SceneSpawnError::UnregisteredComponent {
-                                    type_name: component_info.name().to_string(),
-                                }
-                            })
+                        })?;
+                    let reflect_component =
+                        registration.data::<ReflectComponent>().ok_or_else(|| {
+                            SceneSpawnError::UnregisteredComponent {
+                                type_name: component_info.name().to_string(),
+                            }
                         })?;
                     reflect_component.copy(&self.world, world, scene_entity.entity(), entity);
+                    written_components.push((entity, registration));
                 }
             }
         }
@@ -97,6 +100,9 @@ impl Scene {
                     .unwrap();
             }
         }
+        for (entity, registration) in written_components {
+            map_component_entities(world, entity, registration, &instance_info.entity_map);
+        }
 
         Ok(instance_info)
     }
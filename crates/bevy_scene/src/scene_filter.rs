@@ -0,0 +1,96 @@
+use bevy_utils::HashSet;
+use std::any::{Any, TypeId};
+
+/// A filter of the component or resource types extracted by a
+/// [`DynamicSceneBuilder`](crate::DynamicSceneBuilder).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SceneFilter {
+    /// Every type is extracted.
+    #[default]
+    AllowAll,
+    /// Only the listed types are extracted.
+    Allowlist(HashSet<TypeId>),
+    /// Every type but the listed ones is extracted.
+    Denylist(HashSet<TypeId>),
+}
+
+impl SceneFilter {
+    /// Allows the type `T` to be extracted.
+    ///
+    /// If the filter allowed every type, it now only allows `T` and the types allowed after it.
+    pub fn allow<T: Any>(&mut self) -> &mut Self {
+        self.allow_by_id(TypeId::of::<T>())
+    }
+
+    /// Allows the type with the given [`TypeId`] to be extracted, see [`Self::allow`].
+    pub fn allow_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        match self {
+            Self::AllowAll => *self = Self::Allowlist(HashSet::from_iter([type_id])),
+            Self::Allowlist(list) => {
+                list.insert(type_id);
+            }
+            Self::Denylist(list) => {
+                list.remove(&type_id);
+            }
+        }
+        self
+    }
+
+    /// Prevents the type `T` from being extracted.
+    pub fn deny<T: Any>(&mut self) -> &mut Self {
+        self.deny_by_id(TypeId::of::<T>())
+    }
+
+    /// Prevents the type with the given [`TypeId`] from being extracted, see [`Self::deny`].
+    pub fn deny_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        match self {
+            Self::AllowAll => *self = Self::Denylist(HashSet::from_iter([type_id])),
+            Self::Allowlist(list) => {
+                list.remove(&type_id);
+            }
+            Self::Denylist(list) => {
+                list.insert(type_id);
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the type `T` is extracted.
+    pub fn is_allowed<T: Any>(&self) -> bool {
+        self.is_allowed_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the type with the given [`TypeId`] is extracted.
+    pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+        match self {
+            Self::AllowAll => true,
+            Self::Allowlist(list) => list.contains(&type_id),
+            Self::Denylist(list) => !list.contains(&type_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SceneFilter;
+
+    #[test]
+    fn allow_and_deny() {
+        let mut filter = SceneFilter::default();
+        assert!(filter.is_allowed::<u8>());
+
+        filter.deny::<u8>();
+        assert!(!filter.is_allowed::<u8>());
+        assert!(filter.is_allowed::<u16>());
+        filter.allow::<u8>();
+        assert!(filter.is_allowed::<u8>());
+
+        let mut filter = SceneFilter::default();
+        filter.allow::<u8>().allow::<u16>();
+        assert!(filter.is_allowed::<u8>());
+        assert!(filter.is_allowed::<u16>());
+        assert!(!filter.is_allowed::<u32>());
+        filter.deny::<u8>();
+        assert!(!filter.is_allowed::<u8>());
+    }
+}
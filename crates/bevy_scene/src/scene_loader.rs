@@ -1,4 +1,6 @@
 use crate::serde::SceneDeserializer;
+#[cfg(feature = "serialize")]
+use crate::{deserialize_binary, is_binary_scene};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
@@ -9,6 +11,11 @@ use bevy_utils::BoxedFuture;
 #[cfg(feature = "serialize")]
 use serde::de::DeserializeSeed;
 
+/// Loads [`DynamicScene`](crate::DynamicScene)s from `.scn` files in the binary scene format
+/// written by [`DynamicScene::serialize_binary`](crate::DynamicScene::serialize_binary), and from
+/// `.scn.ron` files written by [`DynamicScene::serialize_ron`](crate::DynamicScene::serialize_ron).
+///
+/// Ron scenes with the `.scn` extension are still supported.
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
@@ -31,11 +38,16 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
             let scene_deserializer = SceneDeserializer {
                 type_registry: &self.type_registry.read(),
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
+            // `.scn` files may be binary scenes, which start with a header
+            let scene = if is_binary_scene(bytes) {
+                deserialize_binary(scene_deserializer, bytes)?
+            } else {
+                let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+                scene_deserializer.deserialize(&mut deserializer)?
+            };
             load_context.set_default_asset(LoadedAsset::new(scene));
             Ok(())
         })
@@ -0,0 +1,178 @@
+use bevy_ecs::{
+    change_detection::ResMut,
+    entity::{Entity, EntityMap},
+    prelude::{Changed, Component},
+    system::Query,
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry};
+
+use crate::{
+    dynamic_scene::reflect_component, DynamicEntity, SceneInstance, SceneSpawnError, SceneSpawner,
+};
+
+#[cfg(feature = "serialize")]
+use crate::{serde::EntitiesSerializer, serialize_ron};
+#[cfg(feature = "serialize")]
+use bevy_reflect::TypeRegistryArc;
+
+/// Component overrides of the entities of the scene instance spawned under this entity.
+///
+/// Add this next to a [`SceneBundle`](crate::SceneBundle) or a
+/// [`DynamicSceneBundle`](crate::DynamicSceneBundle) to customize the entities of an instance
+/// without changing the shared source scene, like a prefab. The overrides are written to the
+/// entities of the instance when it is spawned, when the source scene is reloaded, and when this
+/// component changes.
+///
+/// Entities are identified by their entity in the source scene, see
+/// [`SceneSpawner::scene_entity`] to find it from an entity of an instance. Removing an override
+/// doesn't restore the value of the component in the source scene.
+#[derive(Component, Default)]
+pub struct SceneOverrides {
+    /// The overriding components of each overridden entity of the scene.
+    pub entities: Vec<DynamicEntity>,
+}
+
+impl SceneOverrides {
+    /// Overrides the component of the given type of `scene_entity` with `component`.
+    pub fn insert<C: Component + Reflect>(&mut self, scene_entity: Entity, component: C) {
+        let components = match self
+            .entities
+            .iter_mut()
+            .position(|overrides| overrides.entity == scene_entity.index())
+        {
+            Some(index) => &mut self.entities[index].components,
+            None => {
+                self.entities.push(DynamicEntity {
+                    entity: scene_entity.index(),
+                    components: Vec::new(),
+                });
+                &mut self.entities.last_mut().unwrap().components
+            }
+        };
+        components.retain(|other| other.type_name() != component.type_name());
+        components.push(Box::new(component));
+    }
+
+    /// Gets the overriding components of `scene_entity`.
+    pub fn get(&self, scene_entity: Entity) -> &[Box<dyn Reflect>] {
+        self.entities
+            .iter()
+            .find(|overrides| overrides.entity == scene_entity.index())
+            .map(|overrides| &overrides.components[..])
+            .unwrap_or_default()
+    }
+
+    /// Serialize the overrides into rust object notation (ron), in the format of the entities of
+    /// a dynamic scene.
+    ///
+    /// They can be deserialized with a
+    /// [`SceneEntitiesDeserializer`](crate::serde::SceneEntitiesDeserializer).
+    #[cfg(feature = "serialize")]
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        serialize_ron(EntitiesSerializer {
+            entities: &self.entities,
+            registry,
+        })
+    }
+
+    /// Clones the overrides.
+    pub(crate) fn clone_dynamic(&self) -> Self {
+        Self {
+            entities: self
+                .entities
+                .iter()
+                .map(|overrides| DynamicEntity {
+                    entity: overrides.entity,
+                    components: overrides
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the overrides to the entities of an instance.
+    pub(crate) fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        for overrides in &self.entities {
+            let entity = match entity_map.get(Entity::from_raw(overrides.entity)) {
+                Ok(entity) if world.get_entity(entity).is_some() => entity,
+                _ => continue,
+            };
+            for component in &overrides.components {
+                reflect_component(type_registry, &**component)?.apply_or_insert(
+                    world,
+                    entity,
+                    &**component,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// System that queues the overrides of scene instances to be written when they change.
+pub fn scene_overrides(
+    changed_overrides: Query<&SceneInstance, Changed<SceneOverrides>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
+) {
+    for instance in &changed_overrides {
+        scene_spawner.queue_overrides(**instance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, SceneOverrides};
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, ReflectComponent, World},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[test]
+    fn write_overrides_to_instance() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+
+        let mut source = World::new();
+        let a = source.spawn(Health(10)).id();
+        let b = source.spawn(Health(10)).id();
+        let scene = DynamicScene::from_world(&source, &registry);
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let mut overrides = SceneOverrides::default();
+        overrides.insert(b, Health(50));
+        overrides.insert(b, Health(100));
+        assert_eq!(1, overrides.get(b).len());
+        assert!(overrides.get(a).is_empty());
+        overrides
+            .write_to_world(&mut world, &entity_map, &registry.read())
+            .unwrap();
+
+        let health = |entity| {
+            world
+                .get::<Health>(entity_map.get(entity).unwrap())
+                .unwrap()
+                .0
+        };
+        assert_eq!(10, health(a));
+        assert_eq!(100, health(b));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{DynamicScene, Scene};
+use crate::{
+    dynamic_scene::{write_entities_to_world, write_entity_changes_to_world},
+    DynamicScene, Scene, SceneInstance, SceneOverrides,
+};
 use bevy_app::AppTypeRegistry;
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
@@ -22,6 +25,20 @@ pub struct InstanceInfo {
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
 
+/// Event sent when the entities of a scene instance were written to the world, either because the
+/// instance was spawned or because its source [`DynamicScene`] was modified.
+///
+/// The [`EntityMap`] can be used to remap the entity references that
+/// [`map_reflected_entities`](crate::map_reflected_entities) can't reach, such as entities stored
+/// in resources or in the fields of components that aren't reflected.
+#[derive(Debug, Clone)]
+pub struct SceneInstanceSpawned {
+    /// The id of the spawned instance.
+    pub instance_id: InstanceId,
+    /// Mapping of entities from the scene world to the instance world.
+    pub entity_map: EntityMap,
+}
+
 impl InstanceId {
     fn new() -> Self {
         InstanceId(Uuid::new_v4())
@@ -32,6 +49,9 @@ impl InstanceId {
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
+    /// The version of each spawned dynamic scene its instances were written from, used to only
+    /// write the changes of the scene when it is modified.
+    spawned_dynamic_scene_versions: HashMap<Handle<DynamicScene>, DynamicScene>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
@@ -39,6 +59,8 @@ pub struct SceneSpawner {
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    instances_to_override: Vec<InstanceId>,
+    instances_to_report: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
@@ -47,6 +69,10 @@ pub enum SceneSpawnError {
     UnregisteredComponent { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
+    #[error("world snapshot contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
+    #[error("moved entities reference the entity {entity:?}, which was not moved")]
+    UnmappedEntity { entity: Entity },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
@@ -99,6 +125,7 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
+        self.spawned_dynamic_scene_versions.remove(&scene_handle);
         if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
@@ -121,10 +148,11 @@ impl SceneSpawner {
         scene_handle: &Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
         let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        self.spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
         let instance_id = InstanceId::new();
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
+        self.instances_to_report.push(instance_id);
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
@@ -134,6 +162,7 @@ impl SceneSpawner {
     }
 
     fn spawn_dynamic_internal(
+        &mut self,
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
         entity_map: &mut EntityMap,
@@ -145,7 +174,11 @@ impl SceneSpawner {
                     .ok_or_else(|| SceneSpawnError::NonExistentScene {
                         handle: scene_handle.clone_weak(),
                     })?;
-            scene.write_to_world(world, entity_map)
+            scene.write_to_world(world, entity_map)?;
+            self.spawned_dynamic_scene_versions
+                .entry(scene_handle.clone_weak())
+                .or_insert_with(|| scene.clone_dynamic());
+            Ok(())
         })
     }
 
@@ -175,6 +208,8 @@ impl SceneSpawner {
                 scene.write_to_world_with(world, &world.resource::<AppTypeRegistry>().clone())?;
 
             self.spawned_instances.insert(instance_id, instance_info);
+            self.instances_to_override.push(instance_id);
+            self.instances_to_report.push(instance_id);
             let spawned = self
                 .spawned_scenes
                 .entry(scene_handle)
@@ -184,25 +219,54 @@ impl SceneSpawner {
         })
     }
 
+    /// Updates the instances of the given dynamic scenes after they were modified.
+    ///
+    /// Only the changes between the version of a scene its instances were spawned from and its
+    /// current version are written, so the components and entities that didn't change in the
+    /// scene keep their run-time state.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
-        for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            for scene_handle in scene_handles {
+                let (scene, spawned_instances) = match (
+                    scenes.get(scene_handle),
+                    self.spawned_dynamic_scenes.get(scene_handle),
+                ) {
+                    (Some(scene), Some(spawned_instances)) => (scene, spawned_instances),
+                    _ => continue,
+                };
+                let previous = self.spawned_dynamic_scene_versions.get(scene_handle);
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            scene_handle,
-                            &mut instance_info.entity_map,
-                        )?;
+                        match previous {
+                            Some(previous) => write_entity_changes_to_world(
+                                &previous.entities,
+                                &scene.entities,
+                                world,
+                                &mut instance_info.entity_map,
+                                &type_registry,
+                            )?,
+                            None => write_entities_to_world(
+                                &scene.entities,
+                                world,
+                                &mut instance_info.entity_map,
+                                &type_registry,
+                            )?,
+                        }
+                        self.instances_to_override.push(*instance_id);
+                        self.instances_to_report.push(*instance_id);
                     }
                 }
+                self.spawned_dynamic_scene_versions
+                    .insert(scene_handle.clone_weak(), scene.clone_dynamic());
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
@@ -228,10 +292,12 @@ impl SceneSpawner {
         for (scene_handle, instance_id) in scenes_to_spawn {
             let mut entity_map = EntityMap::default();
 
-            match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+            match self.spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
                 Ok(_) => {
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
+                    self.instances_to_override.push(instance_id);
+                    self.instances_to_report.push(instance_id);
                     let spawned = self
                         .spawned_dynamic_scenes
                         .entry(scene_handle.clone())
@@ -291,6 +357,70 @@ impl SceneSpawner {
         }
     }
 
+    /// Queues the [`SceneOverrides`] of an instance to be written to its entities.
+    pub(crate) fn queue_overrides(&mut self, instance_id: InstanceId) {
+        self.instances_to_override.push(instance_id);
+    }
+
+    /// Writes the [`SceneOverrides`] of the queued instances that are spawned to their entities.
+    pub fn write_queued_overrides(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let instances_to_override = std::mem::take(&mut self.instances_to_override);
+        if instances_to_override.is_empty() {
+            return Ok(());
+        }
+
+        let overrides = world
+            .query::<(&SceneInstance, &SceneOverrides)>()
+            .iter(world)
+            .filter(|(instance, _)| instances_to_override.contains(&***instance))
+            .map(|(instance, overrides)| (**instance, overrides.clone_dynamic()))
+            .collect::<Vec<_>>();
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        for (instance_id, overrides) in overrides {
+            if let Some(instance) = self.spawned_instances.get(&instance_id) {
+                overrides.write_to_world(world, &instance.entity_map, &type_registry)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a [`SceneInstanceSpawned`] event for each instance written to the world since the
+    /// last call.
+    ///
+    /// Nothing is sent if the events weren't added to the world.
+    pub fn send_instance_spawned_events(&mut self, world: &mut World) {
+        let instances_to_report = std::mem::take(&mut self.instances_to_report);
+        if let Some(mut events) = world.get_resource_mut::<Events<SceneInstanceSpawned>>() {
+            for instance_id in instances_to_report {
+                if let Some(instance) = self.spawned_instances.get(&instance_id) {
+                    events.send(SceneInstanceSpawned {
+                        instance_id,
+                        entity_map: instance.entity_map.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Gets the mapping of entities from the source scene to an instance, once it's spawned.
+    pub fn instance_entity_map(&self, instance_id: InstanceId) -> Option<&EntityMap> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| &instance.entity_map)
+    }
+
+    /// Gets the entity of the source scene that `entity` was spawned from in an instance.
+    pub fn scene_entity(&self, instance_id: InstanceId, entity: Entity) -> Option<Entity> {
+        self.spawned_instances
+            .get(&instance_id)?
+            .entity_map
+            .iter()
+            .find_map(|(scene_entity, instance_entity)| {
+                (instance_entity == entity).then_some(scene_entity)
+            })
+    }
+
     /// Check that an scene instance spawned previously is ready to use
     pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
         self.spawned_instances.contains_key(&instance_id)
@@ -338,5 +468,7 @@ pub fn scene_spawner_system(world: &mut World) {
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
         scene_spawner.set_scene_instance_parent_sync(world);
+        scene_spawner.write_queued_overrides(world).unwrap();
+        scene_spawner.send_instance_spawned_events(world);
     });
 }
@@ -1,5 +1,6 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, WorldSnapshot};
 use anyhow::Result;
+use bevy_asset::AssetPath;
 use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
 use bevy_reflect::{
     serde::{TypeRegistrationDeserializer, UntypedReflectDeserializer},
@@ -17,6 +18,11 @@ use std::fmt::Formatter;
 pub const SCENE_STRUCT: &str = "Scene";
 pub const SCENE_ENTITIES: &str = "entities";
 
+pub const WORLD_STRUCT: &str = "World";
+pub const WORLD_RESOURCES: &str = "resources";
+pub const WORLD_ENTITIES: &str = "entities";
+pub const WORLD_ASSETS: &str = "assets";
+
 pub const ENTITY_STRUCT: &str = "Entity";
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
@@ -48,6 +54,42 @@ impl<'a> Serialize for SceneSerializer<'a> {
     }
 }
 
+pub struct WorldSerializer<'a> {
+    pub snapshot: &'a WorldSnapshot,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> WorldSerializer<'a> {
+    pub fn new(snapshot: &'a WorldSnapshot, registry: &'a TypeRegistryArc) -> Self {
+        WorldSerializer { snapshot, registry }
+    }
+}
+
+impl<'a> Serialize for WorldSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(WORLD_STRUCT, 3)?;
+        state.serialize_field(
+            WORLD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.snapshot.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            WORLD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.snapshot.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(WORLD_ASSETS, &self.snapshot.asset_paths)?;
+        state.end()
+    }
+}
+
 pub struct EntitiesSerializer<'a> {
     pub entities: &'a [DynamicEntity],
     pub registry: &'a TypeRegistryArc,
@@ -121,6 +163,14 @@ enum SceneField {
     Entities,
 }
 
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum WorldField {
+    Resources,
+    Entities,
+    Assets,
+}
+
 #[derive(Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum EntityField {
@@ -196,6 +246,109 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     }
 }
 
+pub struct WorldDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for WorldDeserializer<'a> {
+    type Value = WorldSnapshot;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            WORLD_STRUCT,
+            &[WORLD_RESOURCES, WORLD_ENTITIES, WORLD_ASSETS],
+            WorldVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct WorldVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for WorldVisitor<'a> {
+    type Value = WorldSnapshot;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("world struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        let mut asset_paths = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                WorldField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(WORLD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                WorldField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(WORLD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                WorldField::Assets => {
+                    if asset_paths.is_some() {
+                        return Err(Error::duplicate_field(WORLD_ASSETS));
+                    }
+                    asset_paths = Some(map.next_value::<Vec<AssetPath<'static>>>()?);
+                }
+            }
+        }
+
+        let resources = resources.ok_or_else(|| Error::missing_field(WORLD_RESOURCES))?;
+        let entities = entities.ok_or_else(|| Error::missing_field(WORLD_ENTITIES))?;
+        let asset_paths = asset_paths.ok_or_else(|| Error::missing_field(WORLD_ASSETS))?;
+
+        Ok(WorldSnapshot {
+            resources,
+            entities,
+            asset_paths,
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let resources = seq
+            .next_element_seed(ComponentDeserializer {
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(WORLD_RESOURCES))?;
+        let entities = seq
+            .next_element_seed(SceneEntitiesDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(WORLD_ENTITIES))?;
+        let asset_paths = seq
+            .next_element::<Vec<AssetPath<'static>>>()?
+            .ok_or_else(|| Error::missing_field(WORLD_ASSETS))?;
+
+        Ok(WorldSnapshot {
+            resources,
+            entities,
+            asset_paths,
+        })
+    }
+}
+
 pub struct SceneEntitiesDeserializer<'a> {
     pub type_registry: &'a TypeRegistry,
 }
@@ -390,11 +543,11 @@ impl<'a, 'de> Visitor<'de> for ComponentVisitor<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::serde::{SceneDeserializer, SceneSerializer};
-    use crate::{DynamicScene, DynamicSceneBuilder};
+    use crate::serde::{SceneDeserializer, SceneSerializer, WorldDeserializer, WorldSerializer};
+    use crate::{DynamicScene, DynamicSceneBuilder, WorldSnapshot};
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::entity::EntityMap;
-    use bevy_ecs::prelude::{Component, ReflectComponent, World};
+    use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
     use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
@@ -411,6 +564,10 @@ mod tests {
     #[reflect(Component)]
     struct Baz(i32);
 
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(i32);
+
     #[derive(Component, Reflect, Default)]
     #[reflect(Component)]
     struct MyComponent {
@@ -437,6 +594,7 @@ mod tests {
             registry.register::<Foo>();
             registry.register::<Bar>();
             registry.register::<Baz>();
+            registry.register::<Score>();
             registry.register::<MyComponent>();
             registry.register::<MyEnum>();
             registry.register::<String>();
@@ -658,6 +816,73 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    #[test]
+    fn should_serialize_world() {
+        let mut world = create_world();
+        world.insert_resource(Score(42));
+        world.spawn(Foo(123));
+
+        let snapshot = WorldSnapshot::from_world(&world);
+
+        let expected = r#"(
+  resources: {
+    "bevy_scene::serde::tests::Score": (42),
+  },
+  entities: {
+    0: (
+      components: {
+        "bevy_scene::serde::tests::Foo": (123),
+      },
+    ),
+  },
+  assets: [],
+)"#;
+        let output = snapshot
+            .serialize_ron(&world.resource::<AppTypeRegistry>().0)
+            .unwrap();
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_roundtrip_world_bincode() {
+        let mut world = create_world();
+        world.insert_resource(Score(42));
+        world.spawn((Foo(1), Bar(2)));
+        world.spawn(MyComponent {
+            foo: [1, 2, 3],
+            bar: (1.3, 3.7),
+            baz: MyEnum::Struct { value: 7 },
+        });
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let snapshot = WorldSnapshot::from_world(&world);
+        let serialized_world =
+            bincode::serialize(&WorldSerializer::new(&snapshot, &registry.0)).unwrap();
+
+        let world_deserializer = WorldDeserializer {
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_snapshot = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(world_deserializer, &serialized_world)
+            .unwrap();
+
+        assert_eq!(1, deserialized_snapshot.resources.len());
+        assert_eq!(2, deserialized_snapshot.entities.len());
+
+        let mut dst_world = create_world();
+        deserialized_snapshot
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+
+        assert_eq!(42, dst_world.resource::<Score>().0);
+        assert_eq!(1, dst_world.query::<&Foo>().iter(&dst_world).count());
+        assert_eq!(1, dst_world.query::<&Bar>().iter(&dst_world).count());
+        let my_component = dst_world.query::<&MyComponent>().single(&dst_world);
+        assert_eq!([1, 2, 3], my_component.foo);
+        assert!(matches!(my_component.baz, MyEnum::Struct { value: 7 }));
+    }
+
     /// A crude equality checker for [`DynamicScene`], used solely for testing purposes.
     fn assert_scene_eq(expected: &DynamicScene, received: &DynamicScene) {
         assert_eq!(
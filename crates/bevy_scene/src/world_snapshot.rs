@@ -0,0 +1,340 @@
+use crate::{
+    dynamic_scene::write_entities_to_world, DynamicEntity, DynamicSceneBuilder, SceneSpawnError,
+};
+use bevy_app::AppTypeRegistry;
+use bevy_asset::{AssetPath, AssetServer, HandleId, HandleUntyped};
+use bevy_ecs::{entity::EntityMap, reflect::ReflectResource, system::Resource, world::World};
+use bevy_reflect::{Reflect, ReflectRef};
+use bevy_utils::HashSet;
+
+#[cfg(feature = "serialize")]
+use crate::{serde::WorldSerializer, serialize_ron};
+#[cfg(feature = "serialize")]
+use bevy_reflect::TypeRegistryArc;
+
+/// A reflection-powered snapshot of a whole [`World`], typically used for save games.
+///
+/// A snapshot contains every resource registered with [`ReflectResource`],
+/// every entity with its components
+/// registered with [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent), and the paths of
+/// the assets those values hold handles to.
+///
+/// Use [`WorldSerializer`](crate::serde::WorldSerializer) to write a snapshot with any serde
+/// format (such as ron or bincode), and [`WorldDeserializer`](crate::serde::WorldDeserializer)
+/// to read it back.
+///
+/// # Example
+/// ```
+/// # use bevy_scene::WorldSnapshot;
+/// # use bevy_app::AppTypeRegistry;
+/// # use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectComponent};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health(f32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.spawn(Health(10.0));
+///
+/// let snapshot = WorldSnapshot::from_world(&world);
+///
+/// let mut loaded_world = World::new();
+/// loaded_world.insert_resource(world.resource::<AppTypeRegistry>().clone());
+/// snapshot
+///     .write_to_world(&mut loaded_world, &mut EntityMap::default())
+///     .unwrap();
+/// assert_eq!(loaded_world.query::<&Health>().iter(&loaded_world).count(), 1);
+/// ```
+#[derive(Default)]
+pub struct WorldSnapshot {
+    /// The reflected resources of the world, sorted by type name.
+    pub resources: Vec<Box<dyn Reflect>>,
+    /// The entities of the world, along with their reflected components.
+    pub entities: Vec<DynamicEntity>,
+    /// The paths of the assets referenced by handles in `resources` and `entities`.
+    pub asset_paths: Vec<AssetPath<'static>>,
+}
+
+/// Strong handles to the assets loaded when a [`WorldSnapshot`] was written to a world.
+///
+/// Handles restored from a snapshot are weak, so these handles keep the assets they
+/// point to alive. Remove this resource once the restored handles are no longer needed.
+#[derive(Resource, Default)]
+pub struct WorldSnapshotAssets {
+    pub handles: Vec<HandleUntyped>,
+}
+
+impl WorldSnapshot {
+    /// Create a snapshot of the given world, using the world's [`AppTypeRegistry`] resource.
+    pub fn from_world(world: &World) -> Self {
+        Self::from_world_with(world, world.resource::<AppTypeRegistry>())
+    }
+
+    /// Create a snapshot of the given world.
+    ///
+    /// Only resources and components registered in the given [`AppTypeRegistry`] will be extracted.
+    /// If the world has an [`AssetServer`], the paths of the assets referenced by the extracted
+    /// values are recorded so they can be loaded again when the snapshot is restored.
+    pub fn from_world_with(world: &World, type_registry: &AppTypeRegistry) -> Self {
+        let mut builder =
+            DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone());
+        builder
+            .extract_entities(world.iter_entities().map(|entity| entity.id()))
+            .extract_resources();
+        builder.build_snapshot()
+    }
+
+    /// Create a snapshot of resources and entities extracted from the given world, recording
+    /// the paths of the assets they reference if the world has an [`AssetServer`].
+    pub(crate) fn from_parts(
+        world: &World,
+        resources: Vec<Box<dyn Reflect>>,
+        entities: Vec<DynamicEntity>,
+    ) -> Self {
+        let mut asset_paths = Vec::new();
+        if let Some(asset_server) = world.get_resource::<AssetServer>() {
+            let mut visited = HashSet::new();
+            let values = resources
+                .iter()
+                .chain(entities.iter().flat_map(|entity| entity.components.iter()));
+            for value in values {
+                visit_handle_ids(&**value, &mut |id| {
+                    if !visited.insert(id) {
+                        return;
+                    }
+                    if let Some(path) = asset_server.get_handle_path(id) {
+                        asset_paths.push(path.to_owned());
+                    }
+                });
+            }
+        }
+
+        Self {
+            resources,
+            entities,
+            asset_paths,
+        }
+    }
+
+    /// Write the snapshot to the given world, using the world's [`AppTypeRegistry`] resource.
+    ///
+    /// See [`WorldSnapshot::write_to_world_with`].
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.write_to_world_with(world, entity_map, &registry)
+    }
+
+    /// Write the snapshot to the given world, which can either be empty or already populated.
+    ///
+    /// Resources already present in the world are updated in place, and the snapshot's
+    /// entities are mapped to world entities through `entity_map`, spawning new ones as needed.
+    /// If the world has an [`AssetServer`], the snapshot's assets are loaded again and kept
+    /// alive by the [`WorldSnapshotAssets`] resource.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`] trait.
+    pub fn write_to_world_with(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+
+        for resource in &self.resources {
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                }
+            })?;
+            reflect_resource.apply_or_insert(world, &**resource);
+        }
+
+        write_entities_to_world(&self.entities, world, entity_map, &type_registry)?;
+
+        if let Some(asset_server) = world.get_resource::<AssetServer>().cloned() {
+            let handles = self
+                .asset_paths
+                .iter()
+                .map(|path| asset_server.load_untyped(path.clone()));
+            world
+                .get_resource_or_insert_with(WorldSnapshotAssets::default)
+                .handles
+                .extend(handles);
+        }
+
+        Ok(())
+    }
+
+    /// Serialize this snapshot into rust object notation (ron).
+    #[cfg(feature = "serialize")]
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        serialize_ron(WorldSerializer::new(self, registry))
+    }
+}
+
+/// Calls `f` with every [`HandleId`] found in the given reflected value.
+fn visit_handle_ids(value: &dyn Reflect, f: &mut impl FnMut(HandleId)) {
+    if let Some(id) = value.downcast_ref::<HandleId>() {
+        f(*id);
+        return;
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_handle_ids(field, f)),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_handle_ids(field, f)),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .for_each(|field| visit_handle_ids(field, f)),
+        ReflectRef::List(value) => value.iter().for_each(|item| visit_handle_ids(item, f)),
+        ReflectRef::Array(value) => value.iter().for_each(|item| visit_handle_ids(item, f)),
+        ReflectRef::Map(value) => value.iter().for_each(|(key, item)| {
+            visit_handle_ids(key, f);
+            visit_handle_ids(item, f);
+        }),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .for_each(|field| visit_handle_ids(field.value(), f)),
+        ReflectRef::Value(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppTypeRegistry;
+    use bevy_asset::{Handle, HandleId, HandleUntyped};
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, Resource, World},
+        reflect::{ReflectComponent, ReflectResource},
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::{Reflect, TypeUuid};
+
+    use super::{visit_handle_ids, WorldSnapshot};
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Resource, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(TypeUuid)]
+    #[uuid = "6bf1d5c2-2a6b-4f26-9b1a-4e5b0d1a5a31"]
+    struct TestAsset;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Equipment {
+        weapon: Handle<TestAsset>,
+        items: Vec<HandleId>,
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Score>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn snapshot_resources_and_entities() {
+        let mut world = create_world();
+        world.insert_resource(Score(7));
+        world.spawn(Health(3));
+        world.spawn_empty();
+
+        let snapshot = WorldSnapshot::from_world(&world);
+
+        assert_eq!(snapshot.resources.len(), 1);
+        assert!(snapshot.resources[0].represents::<Score>());
+        assert_eq!(snapshot.entities.len(), 2);
+        assert!(snapshot.asset_paths.is_empty());
+    }
+
+    #[test]
+    fn restore_into_existing_world() {
+        let mut world = create_world();
+        world.insert_resource(Score(7));
+        let parent = world
+            .spawn(Health(1))
+            .with_children(|parent| {
+                parent.spawn(Health(2));
+            })
+            .id();
+
+        let snapshot = WorldSnapshot::from_world(&world);
+
+        let mut loaded_world = create_world();
+        loaded_world.insert_resource(Score(0));
+        loaded_world.spawn(Health(100));
+        let mut entity_map = EntityMap::default();
+        snapshot
+            .write_to_world(&mut loaded_world, &mut entity_map)
+            .unwrap();
+
+        assert_eq!(loaded_world.resource::<Score>(), &Score(7));
+        assert_eq!(
+            loaded_world.query::<&Health>().iter(&loaded_world).count(),
+            3
+        );
+
+        let loaded_parent = entity_map.get(parent).unwrap();
+        let loaded_children = loaded_world.get::<Children>(loaded_parent).unwrap();
+        assert_eq!(loaded_children.len(), 1);
+        assert_eq!(
+            loaded_world
+                .get::<Parent>(loaded_children[0])
+                .unwrap()
+                .get(),
+            loaded_parent
+        );
+        assert_eq!(
+            loaded_world.get::<Health>(loaded_children[0]),
+            Some(&Health(2))
+        );
+    }
+
+    #[test]
+    fn visit_nested_handle_ids() {
+        let weapon: Handle<TestAsset> = Handle::weak(HandleId::random::<TestAsset>());
+        let item: HandleUntyped = HandleUntyped::weak(HandleId::random::<TestAsset>());
+        let equipment = Equipment {
+            weapon: weapon.clone_weak(),
+            items: vec![item.id()],
+        };
+
+        let mut ids = Vec::new();
+        visit_handle_ids(&equipment, &mut |id| ids.push(id));
+        assert_eq!(ids, vec![weapon.id(), item.id()]);
+
+        let mut ids = Vec::new();
+        visit_handle_ids(&*equipment.clone_value(), &mut |id| ids.push(id));
+        assert_eq!(ids, vec![weapon.id(), item.id()]);
+    }
+}
@@ -0,0 +1,285 @@
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::{Entity, EntityMap, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+use bevy_utils::HashSet;
+
+use crate::SceneSpawnError;
+
+/// Moves the given entities, along with all of their descendants, from `source` to `destination`.
+///
+/// Every component is moved through reflection, so all of them must be registered in the given
+/// [`AppTypeRegistry`] and reflect [`Component`](bevy_ecs::component::Component).
+/// Entity references held by components reflecting
+/// [`MapEntities`](bevy_ecs::entity::MapEntities), such as [`Parent`] and [`Children`],
+/// are remapped to the new entities. Moved entities whose parent is not moved are detached
+/// from it and become roots in `destination`.
+///
+/// This makes it possible to build a level in a secondary world, for example on a background
+/// thread by running a [`Schedule`](bevy_ecs::schedule::Schedule) against it, and to move it
+/// into the main world once it is ready.
+///
+/// Returns the map from the moved entities in `source` to their counterparts in `destination`.
+/// If an error is returned, no entity is moved.
+///
+/// # Example
+/// ```
+/// # use bevy_app::AppTypeRegistry;
+/// # use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+/// # use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::transfer_entities;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Wall;
+///
+/// let type_registry = AppTypeRegistry::default();
+/// {
+///     let mut type_registry = type_registry.write();
+///     type_registry.register::<Wall>();
+///     type_registry.register::<Parent>();
+///     type_registry.register::<Children>();
+/// }
+///
+/// let mut loading_world = World::new();
+/// let level = loading_world
+///     .spawn(Wall)
+///     .with_children(|level| {
+///         level.spawn(Wall);
+///     })
+///     .id();
+///
+/// let mut world = World::new();
+/// let entity_map =
+///     transfer_entities(&mut loading_world, &mut world, &[level], &type_registry).unwrap();
+///
+/// assert!(loading_world.get_entity(level).is_none());
+/// let level = entity_map.get(level).unwrap();
+/// assert_eq!(world.get::<Children>(level).unwrap().len(), 1);
+/// ```
+pub fn transfer_entities(
+    source: &mut World,
+    destination: &mut World,
+    entities: &[Entity],
+    type_registry: &AppTypeRegistry,
+) -> Result<EntityMap, SceneSpawnError> {
+    let type_registry = type_registry.read();
+
+    let mut to_transfer = Vec::new();
+    let mut visited = HashSet::default();
+    let mut stack = entities.to_vec();
+    while let Some(entity) = stack.pop() {
+        if !visited.insert(entity) {
+            continue;
+        }
+        to_transfer.push(entity);
+        if let Some(children) = source.get::<Children>(entity) {
+            stack.extend(children.iter().copied());
+        }
+    }
+
+    let mut reflect_components = Vec::new();
+    for &entity in &to_transfer {
+        for component_id in source.entity(entity).archetype().components() {
+            let component_info = source
+                .components()
+                .get_info(component_id)
+                .expect("component_ids in archetypes should have ComponentInfo");
+
+            let reflect_component = component_info
+                .type_id()
+                .and_then(|type_id| type_registry.get(type_id))
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: component_info.name().to_string(),
+                })
+                .and_then(|registration| {
+                    registration.data::<ReflectComponent>().ok_or_else(|| {
+                        SceneSpawnError::UnregisteredComponent {
+                            type_name: component_info.name().to_string(),
+                        }
+                    })
+                })?;
+            reflect_components.push((entity, reflect_component));
+        }
+    }
+
+    let mut entity_map = EntityMap::default();
+    for &entity in &to_transfer {
+        entity_map.insert(entity, destination.spawn_empty().id());
+    }
+    for (entity, reflect_component) in reflect_components {
+        let destination_entity = entity_map.get(entity).unwrap();
+        reflect_component.copy(source, destination, entity, destination_entity);
+    }
+
+    // Moved entities whose parent stays behind become roots.
+    let mut detached = Vec::new();
+    for &entity in &to_transfer {
+        if let Some(parent) = source.get::<Parent>(entity) {
+            if !visited.contains(&parent.get()) {
+                detached.push(entity);
+                let destination_entity = entity_map.get(entity).unwrap();
+                destination
+                    .entity_mut(destination_entity)
+                    .remove::<Parent>();
+            }
+        }
+    }
+
+    for registration in type_registry.iter() {
+        if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+            if let Err(MapEntitiesError::EntityNotFound(entity)) =
+                map_entities_reflect.map_entities(destination, &entity_map)
+            {
+                for destination_entity in entity_map.values() {
+                    destination.despawn(destination_entity);
+                }
+                return Err(SceneSpawnError::UnmappedEntity { entity });
+            }
+        }
+    }
+
+    for entity in detached {
+        source.entity_mut(entity).remove_parent();
+    }
+    for entity in to_transfer {
+        source.despawn(entity);
+    }
+
+    Ok(entity_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+        prelude::{Component, World},
+        reflect::{ReflectComponent, ReflectMapEntities},
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::Reflect;
+
+    use super::transfer_entities;
+    use crate::SceneSpawnError;
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Label(String);
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl Default for Target {
+        fn default() -> Self {
+            Self(Entity::from_raw(u32::MAX))
+        }
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    #[derive(Component)]
+    struct Unregistered;
+
+    fn create_registry() -> AppTypeRegistry {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Label>();
+            registry.register::<Target>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        registry
+    }
+
+    #[test]
+    fn transfer_hierarchy() {
+        let registry = create_registry();
+        let mut source = World::new();
+        let mut destination = World::new();
+        destination.spawn(Label("existing".to_string()));
+
+        let root = source.spawn(Label("root".to_string())).id();
+        let level = source.spawn(Label("level".to_string())).id();
+        let child = source.spawn(Label("child".to_string())).id();
+        source.entity_mut(root).push_children(&[level]);
+        source.entity_mut(level).push_children(&[child]);
+        source.entity_mut(child).insert(Target(level));
+
+        let entity_map =
+            transfer_entities(&mut source, &mut destination, &[level], &registry).unwrap();
+
+        // The moved entities are gone from the source, and the root forgot about them.
+        assert!(source.get_entity(level).is_none());
+        assert!(source.get_entity(child).is_none());
+        assert!(source.get::<Children>(root).is_none());
+
+        let moved_level = entity_map.get(level).unwrap();
+        let moved_child = entity_map.get(child).unwrap();
+        assert_eq!(
+            destination.get::<Label>(moved_level),
+            Some(&Label("level".to_string()))
+        );
+        assert!(destination.get::<Parent>(moved_level).is_none());
+        assert_eq!(
+            destination.get::<Parent>(moved_child).unwrap().get(),
+            moved_level
+        );
+        assert_eq!(
+            &**destination.get::<Children>(moved_level).unwrap(),
+            &[moved_child]
+        );
+        assert_eq!(
+            destination.get::<Target>(moved_child),
+            Some(&Target(moved_level))
+        );
+        assert_eq!(destination.entities().len(), 3);
+    }
+
+    #[test]
+    fn transfer_unregistered_component() {
+        let registry = create_registry();
+        let mut source = World::new();
+        let mut destination = World::new();
+        let entity = source
+            .spawn((Label("entity".to_string()), Unregistered))
+            .id();
+
+        let result = transfer_entities(&mut source, &mut destination, &[entity], &registry);
+
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::UnregisteredType { .. })
+        ));
+        assert!(source.get_entity(entity).is_some());
+        assert_eq!(destination.entities().len(), 0);
+    }
+
+    #[test]
+    fn transfer_unmapped_reference() {
+        let registry = create_registry();
+        let mut source = World::new();
+        let mut destination = World::new();
+        let other = source.spawn(Label("other".to_string())).id();
+        let entity = source.spawn(Target(other)).id();
+
+        let result = transfer_entities(&mut source, &mut destination, &[entity], &registry);
+
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::UnmappedEntity { entity }) if entity == other
+        ));
+        assert!(source.get_entity(entity).is_some());
+        assert_eq!(destination.entities().len(), 0);
+    }
+}
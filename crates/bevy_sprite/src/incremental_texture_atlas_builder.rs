@@ -0,0 +1,283 @@
+use crate::{TextureAtlas, TextureAtlasBuilderError};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::system::Resource;
+use bevy_log::{debug, warn};
+use bevy_math::{IVec2, Rect, UVec2, Vec2};
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::{Image, TextureFormatPixelInfo},
+};
+use bevy_utils::HashMap;
+use guillotiere::{size2, AtlasAllocator};
+
+/// An atlas of an [`IncrementalTextureAtlasBuilder`], with the space left in it.
+pub(crate) struct AtlasPage {
+    allocator: AtlasAllocator,
+    texture_atlas: Handle<TextureAtlas>,
+}
+
+impl AtlasPage {
+    fn new(
+        size: UVec2,
+        format: TextureFormat,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+    ) -> Self {
+        let atlas_texture = Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![0; format.pixel_size() * (size.x * size.y) as usize],
+            format,
+        );
+        let texture_atlas = TextureAtlas {
+            texture_handles: Some(HashMap::default()),
+            ..TextureAtlas::new_empty(textures.add(atlas_texture), size.as_vec2())
+        };
+        Self {
+            allocator: AtlasAllocator::new(size2(size.x as i32, size.y as i32)),
+            texture_atlas: texture_atlases.add(texture_atlas),
+        }
+    }
+
+    /// Doubles the size of the atlas, up to `max_size`, keeping the textures where they are.
+    /// Returns `false` if the atlas is already at its max size.
+    fn grow(
+        &mut self,
+        max_size: UVec2,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+    ) -> bool {
+        let size = self.allocator.size();
+        let new_size = UVec2::new(size.width as u32 * 2, size.height as u32 * 2).min(max_size);
+        if new_size.x as i32 <= size.width && new_size.y as i32 <= size.height {
+            return false;
+        }
+        self.allocator
+            .grow(size2(new_size.x as i32, new_size.y as i32));
+
+        let texture_atlas = texture_atlases.get_mut(&self.texture_atlas).unwrap();
+        texture_atlas.size = new_size.as_vec2();
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+        let old_width = atlas_texture.texture_descriptor.size.width as usize;
+        let old_data = std::mem::replace(
+            &mut atlas_texture.data,
+            vec![0; format_size * (new_size.x * new_size.y) as usize],
+        );
+        atlas_texture.texture_descriptor.size = Extent3d {
+            width: new_size.x,
+            height: new_size.y,
+            depth_or_array_layers: 1,
+        };
+        copy_to_atlas(atlas_texture, &old_data, UVec2::ZERO, old_width);
+        true
+    }
+}
+
+/// Packs textures into [`TextureAtlas`]es at runtime, one at a time. Created by
+/// [`TextureAtlasBuilder::finish_incremental`](crate::TextureAtlasBuilder::finish_incremental).
+///
+/// The atlases are updated in place when a texture is added, so that the sprites already using
+/// them keep working. Each atlas starts with the initial size of the builder, grows up to its max
+/// size, and another atlas is added when a texture fits in none of them. The sprites using the
+/// same atlas can be drawn together, unlike the sprites with their own images.
+#[derive(Resource)]
+pub struct IncrementalTextureAtlasBuilder {
+    pub(crate) pages: Vec<AtlasPage>,
+    pub(crate) initial_size: Vec2,
+    pub(crate) max_size: Vec2,
+    pub(crate) format: TextureFormat,
+    pub(crate) auto_format_conversion: bool,
+    pub(crate) padding: u32,
+    pub(crate) allow_rotation: bool,
+    /// The atlas and the index in it of each texture added to the builder
+    pub(crate) placements: HashMap<Handle<Image>, (Handle<TextureAtlas>, usize)>,
+}
+
+impl IncrementalTextureAtlasBuilder {
+    /// The atlases the textures are packed into, in the order they were added.
+    pub fn texture_atlases(&self) -> impl Iterator<Item = &Handle<TextureAtlas>> {
+        self.pages.iter().map(|page| &page.texture_atlas)
+    }
+
+    /// The atlas a texture was packed into and its index in it, if it was added to the builder.
+    pub fn get(&self, texture_handle: &Handle<Image>) -> Option<(&Handle<TextureAtlas>, usize)> {
+        self.placements
+            .get(texture_handle)
+            .map(|(texture_atlas, index)| (texture_atlas, *index))
+    }
+
+    /// Copies a texture into one of the atlases, and returns the atlas and the index of the
+    /// texture in it, to use with a [`TextureAtlasSprite`](crate::TextureAtlasSprite). The
+    /// texture isn't copied again if it was already added.
+    ///
+    /// # Errors
+    ///
+    /// If the texture isn't loaded, is larger than the max size of the atlases or has the wrong
+    /// format, an error will be returned.
+    pub fn add_texture(
+        &mut self,
+        texture_handle: &Handle<Image>,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+    ) -> Result<(Handle<TextureAtlas>, usize), TextureAtlasBuilderError> {
+        if let Some((texture_atlas, index)) = self.placements.get(texture_handle) {
+            return Ok((texture_atlas.clone(), *index));
+        }
+
+        let texture = textures
+            .get(texture_handle)
+            .ok_or(TextureAtlasBuilderError::NotLoaded)?;
+        let size = UVec2::new(
+            texture.texture_descriptor.size.width,
+            texture.texture_descriptor.size.height,
+        );
+        let data = if texture.texture_descriptor.format == self.format {
+            texture.data.clone()
+        } else if !self.auto_format_conversion {
+            warn!(
+                "Loading a texture of format '{:?}' in an atlas with format '{:?}'",
+                texture.texture_descriptor.format, self.format
+            );
+            return Err(TextureAtlasBuilderError::WrongFormat);
+        } else if let Some(converted_texture) = texture.convert(self.format) {
+            debug!(
+                "Converting texture from '{:?}' to '{:?}'",
+                texture.texture_descriptor.format, self.format
+            );
+            converted_texture.data
+        } else {
+            warn!(
+                "Error converting texture from '{:?}' to '{:?}'",
+                texture.texture_descriptor.format, self.format
+            );
+            return Err(TextureAtlasBuilderError::WrongFormat);
+        };
+
+        let (page_index, min, rotated) = self.allocate(size, textures, texture_atlases)?;
+        let texture_atlas_handle = self.pages[page_index].texture_atlas.clone();
+        let texture_atlas = texture_atlases.get_mut(&texture_atlas_handle).unwrap();
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        let format_size = self.format.pixel_size();
+        let size = if rotated {
+            copy_to_atlas(
+                atlas_texture,
+                &rotate_clockwise(&data, size, format_size),
+                min,
+                size.y as usize,
+            );
+            UVec2::new(size.y, size.x)
+        } else {
+            copy_to_atlas(atlas_texture, &data, min, size.x as usize);
+            size
+        };
+
+        let index = texture_atlas.add_texture(Rect {
+            min: min.as_vec2(),
+            max: (min + size).as_vec2(),
+        });
+        if rotated {
+            texture_atlas.rotated_textures.insert(index);
+        }
+        texture_atlas
+            .texture_handles
+            .get_or_insert_with(HashMap::default)
+            .insert(texture_handle.clone_weak(), index);
+        self.placements.insert(
+            texture_handle.clone_weak(),
+            (texture_atlas_handle.clone(), index),
+        );
+        Ok((texture_atlas_handle, index))
+    }
+
+    /// Finds space for a texture of `size` in one of the atlases, growing them or adding one if
+    /// needed. Returns the index of the atlas, the position of the texture in it, and whether the
+    /// texture is rotated.
+    fn allocate(
+        &mut self,
+        size: UVec2,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+    ) -> Result<(usize, UVec2, bool), TextureAtlasBuilderError> {
+        let max_size = self.max_size.as_uvec2();
+        let mut sizes = vec![(size, false)];
+        if self.allow_rotation && size.x != size.y {
+            sizes.push((UVec2::new(size.y, size.x), true));
+        }
+        // The space around the textures is allocated with them
+        for (size, _) in &mut sizes {
+            *size += self.padding;
+        }
+        if !sizes
+            .iter()
+            .any(|(size, _)| size.x <= max_size.x && size.y <= max_size.y)
+        {
+            return Err(TextureAtlasBuilderError::NotEnoughSpace);
+        }
+
+        for page_index in 0.. {
+            let new_page = page_index == self.pages.len();
+            if new_page {
+                let initial_size = self.initial_size.as_uvec2().min(max_size).max(UVec2::ONE);
+                self.pages.push(AtlasPage::new(
+                    initial_size,
+                    self.format,
+                    textures,
+                    texture_atlases,
+                ));
+            }
+            let page = &mut self.pages[page_index];
+            loop {
+                for &(size, rotated) in &sizes {
+                    let allocation = page.allocator.allocate(size2(size.x as i32, size.y as i32));
+                    if let Some(allocation) = allocation {
+                        let min =
+                            IVec2::new(allocation.rectangle.min.x, allocation.rectangle.min.y);
+                        return Ok((page_index, min.as_uvec2(), rotated));
+                    }
+                }
+                if !page.grow(max_size, textures, texture_atlases) {
+                    break;
+                }
+            }
+            if new_page {
+                break;
+            }
+        }
+        Err(TextureAtlasBuilderError::NotEnoughSpace)
+    }
+}
+
+/// Copies the pixels of a texture `width` pixels wide to `min` in the atlas.
+fn copy_to_atlas(atlas_texture: &mut Image, data: &[u8], min: UVec2, width: usize) {
+    let atlas_width = atlas_texture.texture_descriptor.size.width as usize;
+    let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+    let row_size = width * format_size;
+    if row_size == 0 {
+        return;
+    }
+    for (texture_y, row) in data.chunks_exact(row_size).enumerate() {
+        let begin = ((min.y as usize + texture_y) * atlas_width + min.x as usize) * format_size;
+        atlas_texture.data[begin..begin + row_size].copy_from_slice(row);
+    }
+}
+
+/// Rotates the pixels of a texture of `size` by 90 degrees clockwise.
+fn rotate_clockwise(data: &[u8], size: UVec2, format_size: usize) -> Vec<u8> {
+    let (width, height) = (size.x as usize, size.y as usize);
+    let mut rotated = vec![0; data.len()];
+    for y in 0..height {
+        for x in 0..width {
+            // The pixel at (x, y) moves to (height - 1 - y, x), in rows of `height` pixels
+            let source = (y * width + x) * format_size;
+            let destination = (x * height + height - 1 - y) * format_size;
+            rotated[destination..destination + format_size]
+                .copy_from_slice(&data[source..source + format_size]);
+        }
+    }
+    rotated
+}
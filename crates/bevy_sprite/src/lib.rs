@@ -1,8 +1,15 @@
 mod bundle;
 mod dynamic_texture_atlas_builder;
+mod incremental_texture_atlas_builder;
+mod lighting;
 mod mesh2d;
 mod render;
+mod sorting;
 mod sprite;
+mod sprite_animation;
+mod sprite_material;
+#[cfg(any(feature = "aseprite", feature = "texture_packer"))]
+mod sprite_sheet_loader;
 mod texture_atlas;
 mod texture_atlas_builder;
 
@@ -11,8 +18,16 @@ pub mod collide_aabb;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        bundle::{SpriteBundle, SpriteSheetBundle},
+        bundle::{Light2dBundle, SpriteBundle, SpriteSheetBundle},
+        lighting::{Light2d, Lighting2d, Occluder2d, SpriteNormalMap},
+        render::SpriteTextureArraySettings,
+        sorting::{SortingLayer, SortingLayers},
         sprite::Sprite,
+        sprite_animation::{
+            SpriteAnimation, SpriteAnimationClip, SpriteAnimationEvent, SpriteAnimationLoopMode,
+            SpriteAnimationPlayer,
+        },
+        sprite_material::SpriteMaterialPlugin,
         texture_atlas::{TextureAtlas, TextureAtlasSprite},
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
@@ -20,28 +35,45 @@ pub mod prelude {
 
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use incremental_texture_atlas_builder::*;
+pub use lighting::*;
 pub use mesh2d::*;
 pub use render::*;
+pub use sorting::*;
 pub use sprite::*;
+pub use sprite_animation::*;
+pub use sprite_material::*;
+#[cfg(any(feature = "aseprite", feature = "texture_packer"))]
+pub use sprite_sheet_loader::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::{AddAsset, Assets, HandleUntyped};
+use bevy_asset::{load_internal_asset, AddAsset, AssetEvent, Assets, Handle, HandleUntyped};
 use bevy_core_pipeline::core_2d::Transparent2d;
-use bevy_ecs::schedule::{IntoSystemDescriptor, SystemLabel};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
+    extract_resource::ExtractResourcePlugin,
+    primitives::Aabb,
     render_phase::AddRenderCommand,
     render_resource::{Shader, SpecializedRenderPipelines},
+    texture::Image,
+    view::{NoFrustumCulling, VisibilitySystems},
     RenderApp, RenderStage,
 };
+use bevy_utils::HashSet;
 
 #[derive(Default)]
 pub struct SpritePlugin;
 
 pub const SPRITE_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2763343953151597127);
+pub const SPRITE_BINDINGS_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9183628371094722613);
+pub const SPRITE_VERTEX_OUTPUT_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5390824169132766045);
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum SpriteSystem {
@@ -53,13 +85,51 @@ impl Plugin for SpritePlugin {
         let mut shaders = app.world.resource_mut::<Assets<Shader>>();
         let sprite_shader = Shader::from_wgsl(include_str!("render/sprite.wgsl"));
         shaders.set_untracked(SPRITE_SHADER_HANDLE, sprite_shader);
+        load_internal_asset!(
+            app,
+            SPRITE_BINDINGS_HANDLE,
+            "render/sprite_bindings.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_VERTEX_OUTPUT_HANDLE,
+            "render/sprite_vertex_output.wgsl",
+            Shader::from_wgsl
+        );
         app.add_asset::<TextureAtlas>()
             .register_asset_reflect::<TextureAtlas>()
             .register_type::<Sprite>()
             .register_type::<Anchor>()
             .register_type::<Mesh2dHandle>()
+            .add_asset::<SpriteAnimation>()
+            .register_asset_reflect::<SpriteAnimation>()
+            .register_type::<SpriteAnimationPlayer>()
+            .add_event::<SpriteAnimationEvent>()
+            .register_type::<SortingLayer>()
+            .register_type::<SortingLayers>()
+            .init_resource::<SortingLayers>()
+            .add_plugin(ExtractResourcePlugin::<SortingLayers>::default())
+            .register_type::<SpriteTextureArraySettings>()
+            .init_resource::<SpriteTextureArraySettings>()
+            .add_plugin(ExtractResourcePlugin::<SpriteTextureArraySettings>::default())
             .add_plugin(Mesh2dRenderPlugin)
-            .add_plugin(ColorMaterialPlugin);
+            .add_plugin(ColorMaterialPlugin)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animate_sprites.before(VisibilitySystems::CalculateBounds),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                calculate_bounds_2d
+                    .label(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::CheckVisibility),
+            );
+
+        #[cfg(feature = "aseprite")]
+        app.init_asset_loader::<AsepriteLoader>();
+        #[cfg(feature = "texture_packer")]
+        app.init_asset_loader::<TexturePackerLoader>();
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -68,14 +138,160 @@ impl Plugin for SpritePlugin {
                 .init_resource::<SpecializedRenderPipelines<SpritePipeline>>()
                 .init_resource::<SpriteMeta>()
                 .init_resource::<ExtractedSprites>()
+                .init_resource::<SpriteMaterialPipelines>()
                 .init_resource::<SpriteAssetEvents>()
+                .init_resource::<SpriteTextureArrays>()
                 .add_render_command::<Transparent2d, DrawSprite>()
                 .add_system_to_stage(
                     RenderStage::Extract,
                     render::extract_sprites.label(SpriteSystem::ExtractSprites),
                 )
                 .add_system_to_stage(RenderStage::Extract, render::extract_sprite_events)
+                .add_system_to_stage(RenderStage::Prepare, prepare_sprite_texture_arrays)
                 .add_system_to_stage(RenderStage::Queue, queue_sprites);
         };
+
+        // Uses the `SpritePipeline` of the render app
+        app.add_plugin(Lighting2dPlugin);
+    }
+}
+
+type SpriteBoundsQuery = (
+    Entity,
+    &'static Sprite,
+    &'static Handle<Image>,
+    Option<&'static mut Aabb>,
+);
+
+type AtlasSpriteBoundsQuery = (
+    Entity,
+    &'static TextureAtlasSprite,
+    &'static Handle<TextureAtlas>,
+    Option<&'static mut Aabb>,
+);
+
+/// Computes the [`Aabb`] of sprites and texture atlas sprites, so that
+/// [`check_visibility`](bevy_render::view::check_visibility) culls those outside of the view of
+/// the cameras instead of extracting them every frame.
+///
+/// The bounds are computed when missing, once the image or the texture atlas of the sprite is
+/// loaded, and updated when the sprite or its handle changes, or when its image or texture atlas
+/// is modified.
+#[allow(clippy::type_complexity)]
+pub fn calculate_bounds_2d(
+    mut commands: Commands,
+    images: Res<Assets<Image>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    mut sprites: ParamSet<(
+        Query<
+            SpriteBoundsQuery,
+            (
+                Without<NoFrustumCulling>,
+                Without<TextureAtlasSprite>,
+                Or<(Without<Aabb>, Changed<Sprite>, Changed<Handle<Image>>)>,
+            ),
+        >,
+        Query<SpriteBoundsQuery, (Without<NoFrustumCulling>, Without<TextureAtlasSprite>)>,
+    )>,
+    mut atlas_sprites: ParamSet<(
+        Query<
+            AtlasSpriteBoundsQuery,
+            (
+                Without<NoFrustumCulling>,
+                Or<(
+                    Without<Aabb>,
+                    Changed<TextureAtlasSprite>,
+                    Changed<Handle<TextureAtlas>>,
+                )>,
+            ),
+        >,
+        Query<AtlasSpriteBoundsQuery, Without<NoFrustumCulling>>,
+    )>,
+) {
+    let mut update_aabb = |entity: Entity, aabb: Option<Mut<Aabb>>, size: Vec2, anchor: &Anchor| {
+        // The quad of the sprite is offset by its anchor, scaled by its size
+        let new_aabb = Aabb {
+            center: (-anchor.as_vec() * size).extend(0.0).into(),
+            half_extents: (0.5 * size).extend(0.0).into(),
+        };
+        match aabb {
+            Some(mut aabb) => *aabb = new_aabb,
+            None => {
+                commands.entity(entity).insert(new_aabb);
+            }
+        }
+    };
+
+    let sprite_size = |sprite: &Sprite, image: &Handle<Image>| {
+        sprite.custom_size.or_else(|| {
+            sprite
+                .rect
+                .map(|rect| rect.size())
+                .or_else(|| images.get(image).map(Image::size))
+        })
+    };
+    let atlas_sprite_size = |atlas_sprite: &TextureAtlasSprite, atlas: &Handle<TextureAtlas>| {
+        atlas_sprite.custom_size.or_else(|| {
+            let atlas = atlases.get(atlas)?;
+            let size = atlas.textures.get(atlas_sprite.index)?.size();
+            // The rotated textures are as high in the atlas as they are wide
+            Some(if atlas.is_rotated(atlas_sprite.index) {
+                Vec2::new(size.y, size.x)
+            } else {
+                size
+            })
+        })
+    };
+
+    for (entity, sprite, image, aabb) in &mut sprites.p0() {
+        if let Some(size) = sprite_size(sprite, image) {
+            update_aabb(entity, aabb, size, &sprite.anchor);
+        }
+    }
+
+    // The size of the sprites using a modified image may have changed, as a hot reloaded image
+    // isn't necessarily as large as before
+    let modified_images: HashSet<_> = image_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    if !modified_images.is_empty() {
+        for (entity, sprite, image, aabb) in &mut sprites.p1() {
+            if !modified_images.contains(&image.id()) {
+                continue;
+            }
+            if let Some(size) = sprite_size(sprite, image) {
+                update_aabb(entity, aabb, size, &sprite.anchor);
+            }
+        }
+    }
+
+    for (entity, atlas_sprite, atlas, aabb) in &mut atlas_sprites.p0() {
+        if let Some(size) = atlas_sprite_size(atlas_sprite, atlas) {
+            update_aabb(entity, aabb, size, &atlas_sprite.anchor);
+        }
+    }
+
+    let modified_atlases: HashSet<_> = atlas_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+    if !modified_atlases.is_empty() {
+        for (entity, atlas_sprite, atlas, aabb) in &mut atlas_sprites.p1() {
+            if !modified_atlases.contains(&atlas.id()) {
+                continue;
+            }
+            if let Some(size) = atlas_sprite_size(atlas_sprite, atlas) {
+                update_aabb(entity, aabb, size, &atlas_sprite.anchor);
+            }
+        }
     }
 }
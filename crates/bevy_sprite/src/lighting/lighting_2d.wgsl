@@ -0,0 +1,93 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader
+
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+};
+
+struct Light2d {
+    // The color of the light multiplied by its intensity, in linear RGB
+    color: vec4<f32>,
+    position: vec2<f32>,
+    range: f32,
+    falloff: f32,
+    height: f32,
+    // The range of the segments in `Lighting2d::shadow_segments` casting shadows from the light
+    shadow_segments_start: u32,
+    shadow_segments_end: u32,
+};
+
+struct Lighting2d {
+    ambient: vec4<f32>,
+    lights: array<Light2d, #{MAX_LIGHTS_2D}u>,
+    light_count: u32,
+    // The start and end of the segments of the outlines of the occluders, in xy and zw
+    shadow_segments: array<vec4<f32>, #{MAX_SHADOW_SEGMENTS_2D}u>,
+};
+
+@group(0) @binding(0)
+var<uniform> view: View;
+@group(0) @binding(1)
+var<uniform> lighting: Lighting2d;
+@group(0) @binding(2)
+var color_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var normal_texture: texture_2d<f32>;
+
+fn cross_2d(a: vec2<f32>, b: vec2<f32>) -> f32 {
+    return a.x * b.y - a.y * b.x;
+}
+
+// Whether a segment of an occluder is between the position and the light. The light is on the
+// inside of the occluders of its segments, and the position must be outside of them, for the
+// occluders not to shadow themselves.
+fn is_shadowed(position: vec2<f32>, light: Light2d) -> bool {
+    let ray = light.position - position;
+    for (var i: u32 = light.shadow_segments_start; i < light.shadow_segments_end; i = i + 1u) {
+        let segment = lighting.shadow_segments[i];
+        let start = segment.xy;
+        let end = segment.zw;
+        if (cross_2d(end - start, position - start) < 0.0
+            && cross_2d(ray, start - position) * cross_2d(ray, end - position) <= 0.0) {
+            return true;
+        }
+    }
+    return false;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let color = textureLoad(color_texture, pixel, 0);
+    let normal = normalize(textureLoad(normal_texture, pixel, 0).rgb * 2.0 - 1.0);
+
+    // The position of the pixel in the world
+    let ndc = (in.position.xy - view.viewport.xy) / view.viewport.zw * vec2<f32>(2.0, -2.0)
+        + vec2<f32>(-1.0, 1.0);
+    let world_position = view.inverse_view_proj * vec4<f32>(ndc, 0.0, 1.0);
+    let position = world_position.xy / world_position.w;
+
+    var light = lighting.ambient.rgb;
+    for (var i: u32 = 0u; i < lighting.light_count; i = i + 1u) {
+        let light_2d = lighting.lights[i];
+        let offset = (light_2d.position - position) / light_2d.range;
+        let distance = length(offset);
+        if (distance < 1.0) {
+            let attenuation = pow(1.0 - distance, light_2d.falloff);
+            let direction = normalize(vec3<f32>(offset, light_2d.height));
+            let diffuse = max(dot(normal, direction), 0.0);
+            if (diffuse > 0.0 && !is_shadowed(position, light_2d)) {
+                light = light + light_2d.color.rgb * attenuation * diffuse;
+            }
+        }
+    }
+
+    return vec4<f32>(color.rgb * light, color.a);
+}
@@ -0,0 +1,675 @@
+mod node;
+mod shadows;
+
+pub use node::Lighting2dNode;
+pub use shadows::*;
+
+use crate::{SetSpriteTextureBindGroup, SetSpriteViewBindGroup, SpriteBatch, SpritePipeline};
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_core_pipeline::{
+    core_2d::{self, Camera2d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Vec2, Vec3, Vec3Swizzles, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    color::Color,
+    render_graph::RenderGraph,
+    render_phase::{
+        batch_phase_system, sort_phase_system, AddRenderCommand, BatchedPhaseItem,
+        CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem, RenderCommand,
+        RenderCommandResult, RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{BevyDefault, CachedTexture, Image, TextureCache},
+    view::{ComputedVisibility, ExtractedView, ViewTarget, ViewUniform},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::FloatOrd;
+use std::ops::Range;
+
+pub const SPRITE_NORMALS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6151372830584651307);
+pub const LIGHTING_2D_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1893470298113605544);
+
+/// The maximum number of [`Light2d`]s lighting a view, the closest to the center of the view
+/// being kept when there are more.
+pub const MAX_LIGHTS_2D: usize = 64;
+
+/// The maximum number of segments of the outlines of the [`Occluder2d`]s casting shadows in a
+/// view, from all the [`Light2d`]s. The lights the furthest from the center of the view don't
+/// cast shadows from the segments past this limit.
+pub const MAX_SHADOW_SEGMENTS_2D: usize = 512;
+
+/// Enables the 2D lighting of the sprites seen by a [`Camera2d`], by the [`Light2d`]s around.
+///
+/// The sprites are drawn as usual, then lit by a fullscreen pass using the normals of the sprites,
+/// read from their [`SpriteNormalMap`] if any. The other sprites and 2D meshes are lit as if they
+/// were flat. The lights with shadows enabled are blocked by the [`Occluder2d`]s.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Lighting2d {
+    /// The color of the light reaching every sprite, even away from any [`Light2d`].
+    pub ambient_color: Color,
+    /// A multiplier for the ambient color.
+    pub ambient_brightness: f32,
+}
+
+impl Default for Lighting2d {
+    fn default() -> Self {
+        Self {
+            ambient_color: Color::WHITE,
+            ambient_brightness: 0.1,
+        }
+    }
+}
+
+/// A light lighting the sprites around it, seen by a camera with [`Lighting2d`].
+///
+/// Its position is the translation of its [`GlobalTransform`] on the XY plane.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Light2d {
+    /// The color of the light.
+    pub color: Color,
+    /// A multiplier for the color of the light.
+    pub intensity: f32,
+    /// The distance the light reaches, past which it doesn't light anything.
+    pub range: f32,
+    /// How quickly the light fades with the distance, as the exponent of one minus the distance
+    /// over the range.
+    pub falloff: f32,
+    /// How high the light is above the sprites, relative to its range. Lower lights shine on
+    /// the normal mapped sprites at grazing angles, bringing out their relief, but also light
+    /// flat sprites less.
+    pub height: f32,
+    /// Whether the [`Occluder2d`]s cast shadows from this light.
+    pub shadows_enabled: bool,
+}
+
+impl Default for Light2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            range: 200.0,
+            falloff: 2.0,
+            height: 0.5,
+            shadows_enabled: false,
+        }
+    }
+}
+
+/// The normal map of a sprite lit by [`Light2d`]s, with the same layout as the image of the
+/// sprite, or of its texture atlas.
+///
+/// The normals are in the space of the image: its X axis points right, its Y axis points up and
+/// its Z axis points out of the image. The image should be loaded in a linear (non-sRGB) format.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpriteNormalMap(pub Handle<Image>);
+
+/// Adds the [`Lighting2d`] of cameras with a [`Camera2d`].
+#[derive(Default)]
+pub struct Lighting2dPlugin;
+
+impl Plugin for Lighting2dPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SPRITE_NORMALS_SHADER_HANDLE,
+            "sprite_normals.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            LIGHTING_2D_SHADER_HANDLE,
+            "lighting_2d.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Lighting2d>()
+            .register_type::<Light2d>()
+            .register_type::<SpriteNormalMap>()
+            .register_type::<Occluder2d>();
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<DrawFunctions<Normal2d>>()
+            .init_resource::<SpriteNormalsPipeline>()
+            .init_resource::<SpecializedRenderPipelines<SpriteNormalsPipeline>>()
+            .init_resource::<Lighting2dPipeline>()
+            .init_resource::<SpecializedRenderPipelines<Lighting2dPipeline>>()
+            .init_resource::<ExtractedLights2d>()
+            .init_resource::<ExtractedOccluders2d>()
+            .init_resource::<Lighting2dMeta>()
+            .add_render_command::<Normal2d, DrawSpriteNormals>()
+            .add_system_to_stage(RenderStage::Extract, extract_lighting_2d_cameras)
+            .add_system_to_stage(RenderStage::Extract, extract_lights_2d)
+            .add_system_to_stage(RenderStage::Extract, extract_occluders_2d)
+            .add_system_to_stage(RenderStage::Prepare, prepare_lighting_2d)
+            .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Normal2d>)
+            .add_system_to_stage(
+                RenderStage::PhaseSort,
+                batch_phase_system::<Normal2d>.after(sort_phase_system::<Normal2d>),
+            );
+
+        let lighting_node = Lighting2dNode::new(&mut render_app.world);
+        let mut binding = render_app.world.resource_mut::<RenderGraph>();
+        let graph = binding.get_sub_graph_mut(core_2d::graph::NAME).unwrap();
+
+        graph.add_node(core_2d::graph::node::LIGHTING, lighting_node);
+
+        graph.add_slot_edge(
+            graph.input_node().id,
+            core_2d::graph::input::VIEW_ENTITY,
+            core_2d::graph::node::LIGHTING,
+            Lighting2dNode::IN_VIEW,
+        );
+
+        graph.add_node_edge(
+            core_2d::graph::node::MAIN_PASS,
+            core_2d::graph::node::LIGHTING,
+        );
+        graph.add_node_edge(
+            core_2d::graph::node::LIGHTING,
+            core_2d::graph::node::TONEMAPPING,
+        );
+    }
+}
+
+/// The phase drawing the normals of the sprites seen by a camera with [`Lighting2d`], in the same
+/// order as the [`Transparent2d`](core_2d::Transparent2d) phase.
+pub struct Normal2d {
+    pub sort_key: core_2d::Transparent2dSortKey,
+    pub entity: Entity,
+    pub pipeline: CachedRenderPipelineId,
+    pub draw_function: DrawFunctionId,
+    /// Range in the vertex buffer of this item
+    pub batch_range: Option<Range<u32>>,
+}
+
+impl PhaseItem for Normal2d {
+    type SortKey = core_2d::Transparent2dSortKey;
+
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        self.sort_key
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        items.sort_by_key(|item| item.sort_key());
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Normal2d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+impl BatchedPhaseItem for Normal2d {
+    fn batch_range(&self) -> &Option<Range<u32>> {
+        &self.batch_range
+    }
+
+    fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+        &mut self.batch_range
+    }
+}
+
+pub fn extract_lighting_2d_cameras(
+    mut commands: Commands,
+    cameras_2d: Extract<Query<(Entity, &Camera, &Lighting2d), With<Camera2d>>>,
+) {
+    for (entity, camera, lighting) in &cameras_2d {
+        if camera.is_active {
+            commands
+                .get_or_spawn(entity)
+                .insert((lighting.clone(), RenderPhase::<Normal2d>::default()));
+        }
+    }
+}
+
+pub struct ExtractedLight2d {
+    pub position: Vec2,
+    /// The color of the light multiplied by its intensity, in linear RGB
+    pub color: Vec4,
+    pub range: f32,
+    pub falloff: f32,
+    pub height: f32,
+    pub shadows_enabled: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtractedLights2d {
+    pub lights: Vec<ExtractedLight2d>,
+}
+
+pub fn extract_lights_2d(
+    mut extracted_lights: ResMut<ExtractedLights2d>,
+    lights: Extract<Query<(&Light2d, &GlobalTransform, &ComputedVisibility)>>,
+) {
+    extracted_lights.lights.clear();
+    for (light, transform, visibility) in &lights {
+        if !visibility.is_visible() || light.range <= 0.0 {
+            continue;
+        }
+        extracted_lights.lights.push(ExtractedLight2d {
+            position: transform.translation().xy(),
+            color: Vec4::from_slice(&light.color.as_linear_rgba_f32()) * light.intensity,
+            range: light.range,
+            falloff: light.falloff,
+            height: light.height,
+            shadows_enabled: light.shadows_enabled,
+        });
+    }
+}
+
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct GpuLight2d {
+    color: Vec4,
+    position: Vec2,
+    range: f32,
+    falloff: f32,
+    height: f32,
+    /// The range of the segments in [`GpuLighting2d::shadow_segments`] casting shadows from the
+    /// light
+    shadow_segments_start: u32,
+    shadow_segments_end: u32,
+}
+
+#[derive(ShaderType)]
+pub struct GpuLighting2d {
+    ambient: Vec4,
+    lights: [GpuLight2d; MAX_LIGHTS_2D],
+    light_count: u32,
+    /// The start and end of the segments of the outlines of the occluders, in the XY and ZW
+    /// components
+    shadow_segments: [Vec4; MAX_SHADOW_SEGMENTS_2D],
+}
+
+#[derive(Resource, Default)]
+pub struct Lighting2dMeta {
+    pub view_gpu_lighting: DynamicUniformBuffer<GpuLighting2d>,
+}
+
+/// The resources of a view with [`Lighting2d`], prepared for the [`Lighting2dNode`].
+#[derive(Component)]
+pub struct ViewLighting2d {
+    /// The normals of the sprites drawn by the [`Normal2d`] phase, encoded in `[0, 1]`.
+    pub normal_texture: CachedTexture,
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_offset: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_lighting_2d(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline_cache: Res<PipelineCache>,
+    lighting_pipeline: Res<Lighting2dPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<Lighting2dPipeline>>,
+    mut lighting_meta: ResMut<Lighting2dMeta>,
+    extracted_lights: Res<ExtractedLights2d>,
+    extracted_occluders: Res<ExtractedOccluders2d>,
+    views: Query<(Entity, &ExtractedCamera, &ExtractedView, &Lighting2d)>,
+) {
+    lighting_meta.view_gpu_lighting.clear();
+    for (entity, camera, view, lighting) in &views {
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+
+        // The lights reaching the rectangle seen by the view, closest first
+        let inverse_view_proj = view.transform.compute_matrix() * view.projection.inverse();
+        let corners = [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)]
+            .map(|(x, y)| inverse_view_proj.project_point3(Vec3::new(x, y, 0.0)).xy());
+        let min = corners.iter().copied().reduce(Vec2::min).unwrap();
+        let max = corners.iter().copied().reduce(Vec2::max).unwrap();
+        let center = (min + max) / 2.0;
+        let mut lights: Vec<&ExtractedLight2d> = extracted_lights
+            .lights
+            .iter()
+            .filter(|light| light.position.clamp(min, max).distance(light.position) < light.range)
+            .collect();
+        lights.sort_by_key(|light| FloatOrd(light.position.distance_squared(center)));
+
+        let mut gpu_lighting = GpuLighting2d {
+            ambient: Vec4::from_slice(&lighting.ambient_color.as_linear_rgba_f32())
+                * lighting.ambient_brightness,
+            lights: [GpuLight2d::default(); MAX_LIGHTS_2D],
+            light_count: lights.len().min(MAX_LIGHTS_2D) as u32,
+            shadow_segments: [Vec4::ZERO; MAX_SHADOW_SEGMENTS_2D],
+        };
+        let mut shadow_segment_count = 0;
+        for (gpu_light, light) in gpu_lighting.lights.iter_mut().zip(lights) {
+            let shadow_segments_start = shadow_segment_count;
+            if light.shadows_enabled {
+                // Only the segments with the light on the inside of their occluder cast shadows,
+                // for the occluders not to shadow themselves
+                let segments = extracted_occluders.segments.iter().filter(|(start, end)| {
+                    let edge = *end - *start;
+                    let t = ((light.position - *start).dot(edge) / edge.length_squared())
+                        .clamp(0.0, 1.0);
+                    edge.perp_dot(light.position - *start) > 0.0
+                        && light.position.distance(*start + edge * t) < light.range
+                });
+                for (start, end) in segments {
+                    if shadow_segment_count == MAX_SHADOW_SEGMENTS_2D {
+                        break;
+                    }
+                    gpu_lighting.shadow_segments[shadow_segment_count] =
+                        Vec4::new(start.x, start.y, end.x, end.y);
+                    shadow_segment_count += 1;
+                }
+            }
+
+            *gpu_light = GpuLight2d {
+                color: light.color,
+                position: light.position,
+                range: light.range,
+                falloff: light.falloff,
+                height: light.height,
+                shadow_segments_start: shadow_segments_start as u32,
+                shadow_segments_end: shadow_segment_count as u32,
+            };
+        }
+
+        let normal_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("view_normal_2d_texture"),
+                size: Extent3d {
+                    depth_or_array_layers: 1,
+                    width: physical_target_size.x,
+                    height: physical_target_size.y,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: SpriteNormalsPipeline::TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            },
+        );
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &lighting_pipeline,
+            Lighting2dPipelineKey {
+                texture_format: if view.hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
+                } else {
+                    TextureFormat::bevy_default()
+                },
+            },
+        );
+
+        commands.entity(entity).insert(ViewLighting2d {
+            normal_texture,
+            pipeline_id,
+            uniform_offset: lighting_meta.view_gpu_lighting.push(gpu_lighting),
+        });
+    }
+
+    lighting_meta
+        .view_gpu_lighting
+        .write_buffer(&render_device, &render_queue);
+}
+
+#[derive(Resource)]
+pub struct SpriteNormalsPipeline {
+    view_layout: BindGroupLayout,
+    material_layout: BindGroupLayout,
+    texture_array_layout: BindGroupLayout,
+}
+
+impl SpriteNormalsPipeline {
+    pub const TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+}
+
+impl FromWorld for SpriteNormalsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let sprite_pipeline = world.resource::<SpritePipeline>();
+        SpriteNormalsPipeline {
+            view_layout: sprite_pipeline.view_layout.clone(),
+            material_layout: sprite_pipeline.material_layout.clone(),
+            texture_array_layout: sprite_pipeline.texture_array_layout.clone(),
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[repr(transparent)]
+    pub struct SpriteNormalsPipelineKey: u32 {
+        const NONE                        = 0;
+        const COLORED                     = (1 << 0);
+        const NORMAL_MAP                  = (1 << 1);
+        const TEXTURE_ARRAY               = (1 << 2);
+    }
+}
+
+impl SpecializedRenderPipeline for SpriteNormalsPipeline {
+    type Key = SpriteNormalsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut formats = vec![
+            // position
+            VertexFormat::Float32x3,
+            // uv
+            VertexFormat::Float32x2,
+            // layer in the texture array
+            VertexFormat::Uint32,
+        ];
+
+        let mut shader_defs = Vec::new();
+        if key.contains(SpriteNormalsPipelineKey::COLORED) {
+            // color
+            formats.push(VertexFormat::Float32x4);
+            // sharpness of the signed distance field, 0 if the texture isn't one
+            formats.push(VertexFormat::Float32);
+            shader_defs.push("COLORED".into());
+        }
+
+        let vertex_layout =
+            VertexBufferLayout::from_vertex_formats(VertexStepMode::Vertex, formats);
+
+        // The normal map uses the same layout as the image of the sprite
+        let texture_layout = if key.contains(SpriteNormalsPipelineKey::TEXTURE_ARRAY) {
+            shader_defs.push("TEXTURE_ARRAY".into());
+            self.texture_array_layout.clone()
+        } else {
+            self.material_layout.clone()
+        };
+        let mut layout = vec![self.view_layout.clone(), texture_layout];
+        if key.contains(SpriteNormalsPipelineKey::NORMAL_MAP) {
+            layout.push(self.material_layout.clone());
+            shader_defs.push("NORMAL_MAP".into());
+        }
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SPRITE_NORMALS_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![vertex_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: SPRITE_NORMALS_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: Self::TEXTURE_FORMAT,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(layout),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            label: Some("sprite_normals_pipeline".into()),
+        }
+    }
+}
+
+pub type DrawSpriteNormals = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteTextureBindGroup<1>,
+    SetSpriteNormalMapBindGroup<2>,
+    crate::DrawSpriteBatch,
+);
+
+pub struct SetSpriteNormalMapBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteNormalMapBindGroup<I> {
+    type Param = SRes<crate::ImageBindGroups>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<SpriteBatch>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        sprite_batch: &'_ SpriteBatch,
+        image_bind_groups: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        // Without a normal map, the pipeline doesn't use this bind group
+        if let Some(normal_map_handle_id) = sprite_batch.normal_map_handle_id {
+            let image_bind_groups = image_bind_groups.into_inner();
+            pass.set_bind_group(
+                I,
+                image_bind_groups
+                    .values
+                    .get(&Handle::weak(normal_map_handle_id))
+                    .unwrap(),
+                &[],
+            );
+        }
+        RenderCommandResult::Success
+    }
+}
+
+#[derive(Resource)]
+pub struct Lighting2dPipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for Lighting2dPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let texture_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: false },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("lighting_2d_layout"),
+                    entries: &[
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: true,
+                                min_binding_size: Some(ViewUniform::min_size()),
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: true,
+                                min_binding_size: Some(GpuLighting2d::min_size()),
+                            },
+                            count: None,
+                        },
+                        // The color of the sprites
+                        texture_entry(2),
+                        // The normals of the sprites
+                        texture_entry(3),
+                    ],
+                });
+
+        Lighting2dPipeline { layout }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Lighting2dPipelineKey {
+    texture_format: TextureFormat,
+}
+
+impl SpecializedRenderPipeline for Lighting2dPipeline {
+    type Key = Lighting2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("lighting_2d".into()),
+            layout: Some(vec![self.layout.clone()]),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: LIGHTING_2D_SHADER_HANDLE.typed(),
+                shader_defs: vec![
+                    ShaderDefVal::UInt("MAX_LIGHTS_2D".to_string(), MAX_LIGHTS_2D as u32),
+                    ShaderDefVal::UInt(
+                        "MAX_SHADOW_SEGMENTS_2D".to_string(),
+                        MAX_SHADOW_SEGMENTS_2D as u32,
+                    ),
+                ],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+        }
+    }
+}
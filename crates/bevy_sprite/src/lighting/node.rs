@@ -0,0 +1,152 @@
+use crate::{Lighting2dMeta, Lighting2dPipeline, Normal2d, ViewLighting2d};
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryState;
+use bevy_render::{
+    camera::ExtractedCamera,
+    color::Color,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_phase::RenderPhase,
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget, ViewUniformOffset, ViewUniforms},
+};
+
+/// Draws the normals of the sprites seen by a view with [`Lighting2d`](crate::Lighting2d), then
+/// lights its main texture with the [`Light2d`](crate::Light2d)s around.
+pub struct Lighting2dNode {
+    query: QueryState<
+        (
+            &'static ExtractedCamera,
+            &'static ViewTarget,
+            &'static ViewLighting2d,
+            &'static ViewUniformOffset,
+            &'static RenderPhase<Normal2d>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl Lighting2dNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for Lighting2dNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(Lighting2dNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (camera, target, lighting, view_uniform_offset, normal_phase) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(result) => result,
+                Err(_) => return Ok(()),
+            };
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(lighting.pipeline_id) else {
+            return Ok(());
+        };
+        let (Some(view_binding), Some(lighting_binding)) = (
+            world.resource::<ViewUniforms>().uniforms.binding(),
+            world
+                .resource::<Lighting2dMeta>()
+                .view_gpu_lighting
+                .binding(),
+        ) else {
+            return Ok(());
+        };
+
+        {
+            // Sprites without normals face the camera
+            let flat_normal = Color::rgba_linear(0.5, 0.5, 1.0, 1.0);
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("normal_pass_2d"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &lighting.normal_texture.default_view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(flat_normal.into()),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+
+            if let Some(viewport) = camera.viewport.as_ref() {
+                render_pass.set_camera_viewport(viewport);
+            }
+
+            normal_phase.render(&mut render_pass, world, view_entity);
+        }
+
+        let post_process = target.post_process_write();
+        let bind_group = render_context
+            .render_device
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("lighting_2d_bind_group"),
+                layout: &world.resource::<Lighting2dPipeline>().layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: view_binding,
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: lighting_binding,
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: BindingResource::TextureView(post_process.source),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: BindingResource::TextureView(
+                            &lighting.normal_texture.default_view,
+                        ),
+                    },
+                ],
+            });
+
+        let mut render_pass =
+            render_context
+                .command_encoder
+                .begin_render_pass(&RenderPassDescriptor {
+                    label: Some("lighting_pass_2d"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: post_process.destination,
+                        resolve_target: None,
+                        ops: Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(
+            0,
+            &bind_group,
+            &[view_uniform_offset.offset, lighting.uniform_offset],
+        );
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
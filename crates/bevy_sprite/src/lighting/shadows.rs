@@ -0,0 +1,353 @@
+use crate::{Sprite, TextureAtlas, TextureAtlasSprite};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::prelude::*;
+use bevy_math::{Rect, Vec2, Vec3Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_resource::TextureFormat, texture::Image, view::ComputedVisibility, Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// The alpha above which the pixels of a sprite block the light, for [`Occluder2d::SpriteAlpha`].
+pub const OCCLUDER_ALPHA_THRESHOLD: f32 = 0.5;
+
+/// Blocks the light of the [`Light2d`](crate::Light2d)s with shadows enabled, casting shadows
+/// behind it.
+///
+/// The occluder itself is lit as usual: only what is behind it, as seen from a light, is in its
+/// shadow. A light inside an occluder is blocked by it in every direction.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub enum Occluder2d {
+    /// The convex hull of the pixels of the [`Sprite`] or [`TextureAtlasSprite`] of the entity
+    /// whose alpha is above [`OCCLUDER_ALPHA_THRESHOLD`]. Use a [`Occluder2d::Polygon`] for the
+    /// shadows to follow the concave parts of a sprite.
+    ///
+    /// The image of the sprite must be an 8 bits per channel RGBA or BGRA image, or one that
+    /// [`Image::convert`] can convert to [`TextureFormat::Rgba8UnormSrgb`].
+    SpriteAlpha,
+    /// A simple polygon, with its vertices in the local space of the entity on the XY plane.
+    Polygon(Vec<Vec2>),
+}
+
+impl Default for Occluder2d {
+    fn default() -> Self {
+        Occluder2d::SpriteAlpha
+    }
+}
+
+/// The outlines of the [`Occluder2d`]s in the world, made of segments going counterclockwise
+/// around each occluder.
+#[derive(Resource, Default)]
+pub struct ExtractedOccluders2d {
+    pub segments: Vec<(Vec2, Vec2)>,
+}
+
+/// The hulls of the [`Occluder2d::SpriteAlpha`] occluders, by image and rect in pixels.
+#[derive(Default)]
+pub struct SpriteAlphaHulls {
+    hulls: HashMap<(HandleId, [u32; 4]), Vec<Vec2>>,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn extract_occluders_2d(
+    mut extracted_occluders: ResMut<ExtractedOccluders2d>,
+    mut sprite_alpha_hulls: Local<SpriteAlphaHulls>,
+    mut image_events: Extract<EventReader<AssetEvent<Image>>>,
+    images: Extract<Res<Assets<Image>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    occluders: Extract<
+        Query<(
+            &Occluder2d,
+            &GlobalTransform,
+            Option<&ComputedVisibility>,
+            Option<(&Sprite, &Handle<Image>)>,
+            Option<(&TextureAtlasSprite, &Handle<TextureAtlas>)>,
+        )>,
+    >,
+) {
+    for event in image_events.iter() {
+        if let AssetEvent::Modified { handle } | AssetEvent::Removed { handle } = event {
+            sprite_alpha_hulls
+                .hulls
+                .retain(|(handle_id, _), _| *handle_id != handle.id());
+        }
+    }
+
+    extracted_occluders.segments.clear();
+    for (occluder, transform, visibility, sprite, atlas_sprite) in &occluders {
+        // Occluders out of the views still cast shadows into them
+        if !visibility.map_or(true, ComputedVisibility::is_visible_in_hierarchy) {
+            continue;
+        }
+
+        let outline = match occluder {
+            Occluder2d::Polygon(vertices) => vertices.clone(),
+            Occluder2d::SpriteAlpha => {
+                // The image, the rect of the sprite in it, and how the sprite is drawn
+                let (image_handle, rect, rotated, custom_size, anchor, flip_x, flip_y) =
+                    match (sprite, atlas_sprite) {
+                        (Some((sprite, image_handle)), _) => (
+                            image_handle,
+                            sprite.rect,
+                            false,
+                            sprite.custom_size,
+                            sprite.anchor.as_vec(),
+                            sprite.flip_x,
+                            sprite.flip_y,
+                        ),
+                        (_, Some((atlas_sprite, atlas_handle))) => {
+                            let Some(texture_atlas) = texture_atlases.get(atlas_handle) else {
+                                continue;
+                            };
+                            let Some(&rect) = texture_atlas.textures.get(atlas_sprite.index) else {
+                                continue;
+                            };
+                            (
+                                &texture_atlas.texture,
+                                Some(rect),
+                                texture_atlas.is_rotated(atlas_sprite.index),
+                                atlas_sprite.custom_size,
+                                atlas_sprite.anchor.as_vec(),
+                                atlas_sprite.flip_x,
+                                atlas_sprite.flip_y,
+                            )
+                        }
+                        _ => continue,
+                    };
+                let Some(image) = images.get(image_handle) else {
+                    continue;
+                };
+                let rect = rect.unwrap_or(Rect {
+                    min: Vec2::ZERO,
+                    max: image.size(),
+                });
+                let size = custom_size.unwrap_or_else(|| {
+                    if rotated {
+                        Vec2::new(rect.size().y, rect.size().x)
+                    } else {
+                        rect.size()
+                    }
+                });
+
+                let key = (
+                    image_handle.id(),
+                    [rect.min.x, rect.min.y, rect.max.x, rect.max.y].map(|x| x as u32),
+                );
+                let hull = sprite_alpha_hulls
+                    .hulls
+                    .entry(key)
+                    .or_insert_with(|| sprite_alpha_hull(image, rect));
+                // From the top left corner of the rect of the image to the sprite
+                hull.iter()
+                    .map(|&point| {
+                        // Rotate the hull back by 90 degrees counterclockwise
+                        let point = if rotated {
+                            Vec2::new(point.y, 1.0 - point.x)
+                        } else {
+                            point
+                        };
+                        let x = if flip_x { 0.5 - point.x } else { point.x - 0.5 };
+                        let y = if flip_y { point.y - 0.5 } else { 0.5 - point.y };
+                        (Vec2::new(x, y) - anchor) * size
+                    })
+                    .collect()
+            }
+        };
+
+        let mut outline: Vec<Vec2> = outline
+            .into_iter()
+            .map(|vertex| transform.transform_point(vertex.extend(0.0)).xy())
+            .collect();
+        outline.dedup();
+        if outline.len() < 3 {
+            continue;
+        }
+        let area: f32 = (0..outline.len())
+            .map(|index| outline[index].perp_dot(outline[(index + 1) % outline.len()]))
+            .sum();
+        if area < 0.0 {
+            outline.reverse();
+        }
+        extracted_occluders.segments.extend(
+            (0..outline.len())
+                .map(|index| (outline[index], outline[(index + 1) % outline.len()]))
+                .filter(|(start, end)| start != end),
+        );
+    }
+}
+
+/// The convex hull of the pixels of `rect` in `image` whose alpha is above
+/// [`OCCLUDER_ALPHA_THRESHOLD`], from `(0, 0)` at the top left corner of the rect to `(1, 1)` at
+/// its bottom right corner. Empty if the format of the image isn't supported.
+fn sprite_alpha_hull(image: &Image, rect: Rect) -> Vec<Vec2> {
+    let converted;
+    let image = match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb => image,
+        _ => match image.convert(TextureFormat::Rgba8UnormSrgb) {
+            Some(image) => {
+                converted = image;
+                &converted
+            }
+            None => return Vec::new(),
+        },
+    };
+    let width = image.texture_descriptor.size.width as usize;
+    let height = image.texture_descriptor.size.height as usize;
+    if image.data.len() < width * height * 4 {
+        return Vec::new();
+    }
+    let min_x = (rect.min.x.max(0.0) as usize).min(width);
+    let max_x = (rect.max.x.max(0.0) as usize).clamp(min_x, width);
+    let min_y = (rect.min.y.max(0.0) as usize).min(height);
+    let max_y = (rect.max.y.max(0.0) as usize).clamp(min_y, height);
+    let threshold = (OCCLUDER_ALPHA_THRESHOLD * 255.0) as u8;
+
+    // The corners of the first and last opaque pixels of each row are enough for the hull
+    let mut corners = Vec::new();
+    for y in min_y..max_y {
+        let mut row = image.data[(y * width + min_x) * 4..(y * width + max_x) * 4].chunks_exact(4);
+        let is_opaque = |pixel: &[u8]| pixel[3] > threshold;
+        let Some(first) = row.position(is_opaque) else {
+            continue;
+        };
+        // `rposition` only searches the pixels after the first opaque one
+        let last = row
+            .rposition(is_opaque)
+            .map_or(first, |last| first + 1 + last);
+        for x in [first, last + 1] {
+            for y in [y - min_y, y - min_y + 1] {
+                corners.push(Vec2::new(x as f32, y as f32));
+            }
+        }
+    }
+
+    let size = Vec2::new((max_x - min_x) as f32, (max_y - min_y) as f32);
+    convex_hull(corners)
+        .into_iter()
+        .map(|corner| corner / size)
+        .collect()
+}
+
+/// The convex hull of the points, with Andrew's monotone chain algorithm.
+fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // The lower hull from left to right, then the upper hull from right to left
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+    for reversed in [false, true] {
+        let start = hull.len();
+        for index in 0..points.len() {
+            let point = if reversed {
+                points[points.len() - 1 - index]
+            } else {
+                points[index]
+            };
+            while hull.len() >= start + 2 {
+                let [a, b] = [hull[hull.len() - 2], hull[hull.len() - 1]];
+                if (b - a).perp_dot(point - a) > 0.0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each half is the first one of the other
+        hull.pop();
+    }
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::render_resource::{Extent3d, TextureDimension};
+
+    #[test]
+    fn convex_hull_of_square() {
+        let mut points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        points.extend([
+            Vec2::new(0.5, 0.5),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(0.2, 0.7),
+        ]);
+        let hull = convex_hull(points);
+        assert_eq!(
+            hull,
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(0.0, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn sprite_alpha_hull_of_opaque_pixels() {
+        // A 4x4 transparent image with an opaque L shape in its bottom right corner
+        let mut data = vec![0; 4 * 4 * 4];
+        for (x, y) in [(3, 1), (3, 2), (2, 2)] {
+            data[(y * 4 + x) * 4 + 3] = 255;
+        }
+        let image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        );
+
+        let hull = sprite_alpha_hull(
+            &image,
+            Rect {
+                min: Vec2::ZERO,
+                max: Vec2::splat(4.0),
+            },
+        );
+        assert_eq!(
+            hull,
+            vec![
+                Vec2::new(0.5, 0.5),
+                Vec2::new(0.75, 0.25),
+                Vec2::new(1.0, 0.25),
+                Vec2::new(1.0, 0.75),
+                Vec2::new(0.5, 0.75),
+            ]
+        );
+
+        // Only the part of the L shape in the rect
+        let hull = sprite_alpha_hull(
+            &image,
+            Rect {
+                min: Vec2::new(2.0, 2.0),
+                max: Vec2::splat(4.0),
+            },
+        );
+        assert_eq!(
+            hull,
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 0.5),
+                Vec2::new(0.0, 0.5),
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+};
+@group(0) @binding(0)
+var<uniform> view: View;
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+    @location(1) world_position: vec2<f32>,
+#ifdef COLORED
+    @location(2) alpha: f32,
+    @location(4) sdf_sharpness: f32,
+#endif
+    @location(3) @interpolate(flat) layer: u32,
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vertex(
+    @location(0) vertex_position: vec3<f32>,
+    @location(1) vertex_uv: vec2<f32>,
+    @location(2) vertex_layer: u32,
+#ifdef COLORED
+    @location(3) vertex_color: vec4<f32>,
+    @location(4) vertex_sdf_sharpness: f32,
+#endif
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.uv = vertex_uv;
+    out.layer = vertex_layer;
+    out.world_position = vertex_position.xy;
+    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
+#ifdef COLORED
+    out.alpha = vertex_color.a;
+    out.sdf_sharpness = vertex_sdf_sharpness;
+#endif
+    return out;
+}
+
+@group(1) @binding(0)
+#ifdef TEXTURE_ARRAY
+var sprite_texture: texture_2d_array<f32>;
+#else
+var sprite_texture: texture_2d<f32>;
+#endif
+@group(1) @binding(1)
+var sprite_sampler: sampler;
+
+#ifdef NORMAL_MAP
+@group(2) @binding(0)
+var normal_map_texture: texture_2d<f32>;
+@group(2) @binding(1)
+var normal_map_sampler: sampler;
+#endif
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef TEXTURE_ARRAY
+    var alpha = textureSample(sprite_texture, sprite_sampler, in.uv, i32(in.layer)).a;
+#else
+    var alpha = textureSample(sprite_texture, sprite_sampler, in.uv).a;
+#endif
+#ifdef COLORED
+    // The coverage of the signed distance fields, as in the sprite shader
+    let sdf_width = max(0.5 * fwidth(alpha), 1e-4) / max(in.sdf_sharpness, 1e-4);
+    let sdf_alpha = smoothstep(0.5 - sdf_width, 0.5 + sdf_width, alpha);
+    alpha = select(alpha, sdf_alpha, in.sdf_sharpness > 0.0);
+    alpha = alpha * in.alpha;
+#endif
+
+    var normal = vec3<f32>(0.0, 0.0, 1.0);
+#ifdef NORMAL_MAP
+    let image_normal = textureSample(normal_map_texture, normal_map_sampler, in.uv).rgb * 2.0 - 1.0;
+
+    // The directions of the X and Y axes of the image in the world, from the derivatives of the
+    // UVs, to follow the rotation, scale and flips of the sprite
+    let world_dx = dpdx(in.world_position);
+    let world_dy = dpdy(in.world_position);
+    let uv_dx = dpdx(in.uv);
+    let uv_dy = dpdy(in.uv);
+    let determinant = uv_dx.x * uv_dy.y - uv_dy.x * uv_dx.y;
+    let world_du = (world_dx * uv_dy.y - world_dy * uv_dx.y) / determinant;
+    let world_dv = (world_dy * uv_dx.x - world_dx * uv_dy.x) / determinant;
+    // The V axis of the UVs points down the image
+    let image_x = normalize(world_du);
+    let image_y = -normalize(world_dv);
+    normal = normalize(vec3<f32>(
+        image_normal.x * image_x + image_normal.y * image_y,
+        image_normal.z,
+    ));
+#endif
+
+    return vec4<f32>(normal * 0.5 + 0.5, alpha);
+}
@@ -35,13 +35,13 @@ use bevy_render::{
     Extract, RenderApp, RenderStage,
 };
 use bevy_transform::components::{GlobalTransform, Transform};
-use bevy_utils::{FloatOrd, HashMap, HashSet};
+use bevy_utils::{HashMap, HashSet};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
 use crate::{
     DrawMesh2d, Mesh2dHandle, Mesh2dPipeline, Mesh2dPipelineKey, Mesh2dUniform, SetMesh2dBindGroup,
-    SetMesh2dViewBindGroup,
+    SetMesh2dViewBindGroup, SortingLayer, SortingLayers,
 };
 
 /// Materials are used alongside [`Material2dPlugin`] and [`MaterialMesh2dBundle`]
@@ -323,7 +323,13 @@ pub fn queue_material2d_meshes<M: Material2d>(
     msaa: Res<Msaa>,
     render_meshes: Res<RenderAssets<Mesh>>,
     render_materials: Res<RenderMaterials2d<M>>,
-    material2d_meshes: Query<(&Handle<M>, &Mesh2dHandle, &Mesh2dUniform)>,
+    sorting_layers: Res<SortingLayers>,
+    material2d_meshes: Query<(
+        &Handle<M>,
+        &Mesh2dHandle,
+        &Mesh2dUniform,
+        Option<&SortingLayer>,
+    )>,
     mut views: Query<(
         &ExtractedView,
         &VisibleEntities,
@@ -354,7 +360,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
         }
 
         for visible_entity in &visible_entities.entities {
-            if let Ok((material2d_handle, mesh2d_handle, mesh2d_uniform)) =
+            if let Ok((material2d_handle, mesh2d_handle, mesh2d_uniform, sorting_layer)) =
                 material2d_meshes.get(*visible_entity)
             {
                 if let Some(material2d) = render_materials.get(material2d_handle) {
@@ -380,7 +386,7 @@ pub fn queue_material2d_meshes<M: Material2d>(
                             }
                         };
 
-                        let mesh_z = mesh2d_uniform.transform.w_axis.z;
+                        let mesh_translation = mesh2d_uniform.transform.w_axis.truncate();
                         transparent_phase.add(Transparent2d {
                             entity: *visible_entity,
                             draw_function: draw_transparent_pbr,
@@ -388,8 +394,8 @@ pub fn queue_material2d_meshes<M: Material2d>(
                             // NOTE: Back-to-front ordering for transparent with ascending sort means far should have the
                             // lowest sort key and getting closer should increase. As we have
                             // -z in front of the camera, the largest distance is -far with values increasing toward the
-                            // camera. As such we can just use mesh_z as the distance
-                            sort_key: FloatOrd(mesh_z),
+                            // camera. As such we can just use the z of the mesh as the distance, after its layer
+                            sort_key: sorting_layers.sort_key(sorting_layer, mesh_translation),
                             // This material is not batched
                             batch_range: None,
                         });
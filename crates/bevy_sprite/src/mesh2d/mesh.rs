@@ -25,6 +25,8 @@ use bevy_render::{
 };
 use bevy_transform::components::GlobalTransform;
 
+use crate::SortingLayer;
+
 /// Component for rendering with meshes in the 2d pipeline, usually with a [2d material](crate::Material2d) such as [`ColorMaterial`](crate::ColorMaterial).
 ///
 /// It wraps a [`Handle<Mesh>`] to differentiate from the 3d pipelines which use the handles directly as components
@@ -128,10 +130,18 @@ bitflags::bitflags! {
 pub fn extract_mesh2d(
     mut commands: Commands,
     mut previous_len: Local<usize>,
-    query: Extract<Query<(Entity, &ComputedVisibility, &GlobalTransform, &Mesh2dHandle)>>,
+    query: Extract<
+        Query<(
+            Entity,
+            &ComputedVisibility,
+            &GlobalTransform,
+            &Mesh2dHandle,
+            Option<&SortingLayer>,
+        )>,
+    >,
 ) {
     let mut values = Vec::with_capacity(*previous_len);
-    for (entity, computed_visibility, transform, handle) in &query {
+    for (entity, computed_visibility, transform, handle, sorting_layer) in &query {
         if !computed_visibility.is_visible() {
             continue;
         }
@@ -145,6 +155,7 @@ pub fn extract_mesh2d(
                     transform,
                     inverse_transpose_model: transform.inverse().transpose(),
                 },
+                sorting_layer.copied().unwrap_or_default(),
             ),
         ));
     }
@@ -1,16 +1,19 @@
-use std::cmp::Ordering;
-
 use crate::{
     texture_atlas::{TextureAtlas, TextureAtlasSprite},
-    Sprite, SPRITE_SHADER_HANDLE,
+    DrawSpriteNormals, Normal2d, SortingLayer, SortingLayers, Sprite, SpriteMaterialPipelines,
+    SpriteNormalMap, SpriteNormalsPipeline, SpriteNormalsPipelineKey, SPRITE_SHADER_HANDLE,
 };
 use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
-use bevy_core_pipeline::{core_2d::Transparent2d, tonemapping::Tonemapping};
+use bevy_core_pipeline::{
+    core_2d::{Transparent2d, Transparent2dSortKey},
+    pixel_perfect::PixelPerfectCamera,
+    tonemapping::Tonemapping,
+};
 use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Mat4, Rect, Vec2, Vec3};
 use bevy_reflect::Uuid;
 use bevy_render::{
     color::Color,
@@ -31,15 +34,20 @@ use bevy_render::{
     Extract,
 };
 use bevy_transform::components::GlobalTransform;
-use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
 
-#[derive(Resource)]
+mod texture_arrays;
+
+pub use texture_arrays::*;
+
+#[derive(Resource, Clone)]
 pub struct SpritePipeline {
-    view_layout: BindGroupLayout,
-    material_layout: BindGroupLayout,
+    pub(crate) view_layout: BindGroupLayout,
+    pub(crate) material_layout: BindGroupLayout,
+    /// The layout of the [`SpriteTextureArrays`], replacing the material layout
+    pub(crate) texture_array_layout: BindGroupLayout,
     pub dummy_white_gpu_image: GpuImage,
 }
 
@@ -87,6 +95,29 @@ impl FromWorld for SpritePipeline {
             ],
             label: Some("sprite_material_layout"),
         });
+
+        let texture_array_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                entries: &[
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            multisampled: false,
+                            sample_type: TextureSampleType::Float { filterable: true },
+                            view_dimension: TextureViewDimension::D2Array,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+                label: Some("sprite_texture_array_layout"),
+            });
         let dummy_white_gpu_image = {
             let image = Image::new_fill(
                 Extent3d::default(),
@@ -137,6 +168,7 @@ impl FromWorld for SpritePipeline {
         SpritePipeline {
             view_layout,
             material_layout,
+            texture_array_layout,
             dummy_white_gpu_image,
         }
     }
@@ -152,6 +184,7 @@ bitflags::bitflags! {
         const HDR                         = (1 << 1);
         const TONEMAP_IN_SHADER           = (1 << 2);
         const DEBAND_DITHER               = (1 << 3);
+        const TEXTURE_ARRAY               = (1 << 4);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
     }
 }
@@ -189,6 +222,15 @@ impl SpritePipelineKey {
             SpritePipelineKey::NONE
         }
     }
+
+    #[inline]
+    pub const fn from_texture_array(texture_array: bool) -> Self {
+        if texture_array {
+            SpritePipelineKey::TEXTURE_ARRAY
+        } else {
+            SpritePipelineKey::NONE
+        }
+    }
 }
 
 impl SpecializedRenderPipeline for SpritePipeline {
@@ -200,11 +242,15 @@ impl SpecializedRenderPipeline for SpritePipeline {
             VertexFormat::Float32x3,
             // uv
             VertexFormat::Float32x2,
+            // layer in the texture array
+            VertexFormat::Uint32,
         ];
 
         if key.contains(SpritePipelineKey::COLORED) {
             // color
             formats.push(VertexFormat::Float32x4);
+            // sharpness of the signed distance field, 0 if the texture isn't one
+            formats.push(VertexFormat::Float32);
         }
 
         let vertex_layout =
@@ -215,6 +261,13 @@ impl SpecializedRenderPipeline for SpritePipeline {
             shader_defs.push("COLORED".into());
         }
 
+        let texture_layout = if key.contains(SpritePipelineKey::TEXTURE_ARRAY) {
+            shader_defs.push("TEXTURE_ARRAY".into());
+            self.texture_array_layout.clone()
+        } else {
+            self.material_layout.clone()
+        };
+
         if key.contains(SpritePipelineKey::TONEMAP_IN_SHADER) {
             shader_defs.push("TONEMAP_IN_SHADER".into());
 
@@ -246,7 +299,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
                     write_mask: ColorWrites::ALL,
                 })],
             }),
-            layout: Some(vec![self.view_layout.clone(), self.material_layout.clone()]),
+            layout: Some(vec![self.view_layout.clone(), texture_layout]),
             primitive: PrimitiveState {
                 front_face: FrontFace::Ccw,
                 cull_mode: None,
@@ -274,14 +327,25 @@ pub struct ExtractedSprite {
     pub color: Color,
     /// Select an area of the texture
     pub rect: Option<Rect>,
+    /// Whether the area of the texture is rotated by 90 degrees clockwise, as in a texture atlas
+    pub rotated: bool,
     /// Change the on-screen size of the sprite
     pub custom_size: Option<Vec2>,
     /// Handle to the `Image` of this sprite
     /// PERF: storing a `HandleId` instead of `Handle<Image>` enables some optimizations (`ExtractedSprite` becomes `Copy` and doesn't need to be dropped)
     pub image_handle_id: HandleId,
+    /// Handle to the normal map of this sprite, if it is lit by 2D lights
+    pub normal_map_handle_id: Option<HandleId>,
+    /// Handle to the `Material2d` of this sprite, set by a [`SpriteMaterialPlugin`](crate::SpriteMaterialPlugin)
+    pub material_handle_id: Option<HandleId>,
     pub flip_x: bool,
     pub flip_y: bool,
     pub anchor: Vec2,
+    /// The sharpness of the edges of the sprite, if the alpha of its texture is a signed distance
+    /// field, as for the glyphs of the texts drawn with `TextRendering::Sdf`
+    pub sdf_sharpness: Option<f32>,
+    /// The key sorting this sprite with the other items of the 2D transparent phase
+    pub sort_key: Transparent2dSortKey,
 }
 
 #[derive(Resource, Default)]
@@ -319,7 +383,11 @@ pub fn extract_sprite_events(
 
 pub fn extract_sprites(
     mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut texture_arrays: ResMut<SpriteTextureArrays>,
+    texture_array_settings: Extract<Res<SpriteTextureArraySettings>>,
+    images: Extract<Res<Assets<Image>>>,
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    sorting_layers: Extract<Res<SortingLayers>>,
     sprite_query: Extract<
         Query<(
             Entity,
@@ -327,6 +395,8 @@ pub fn extract_sprites(
             &Sprite,
             &GlobalTransform,
             &Handle<Image>,
+            Option<&SpriteNormalMap>,
+            Option<&SortingLayer>,
         )>,
     >,
     atlas_query: Extract<
@@ -336,11 +406,15 @@ pub fn extract_sprites(
             &TextureAtlasSprite,
             &GlobalTransform,
             &Handle<TextureAtlas>,
+            Option<&SpriteNormalMap>,
+            Option<&SortingLayer>,
         )>,
     >,
 ) {
     extracted_sprites.sprites.clear();
-    for (entity, visibility, sprite, transform, handle) in sprite_query.iter() {
+    for (entity, visibility, sprite, transform, handle, normal_map, sorting_layer) in
+        sprite_query.iter()
+    {
         if !visibility.is_visible() {
             continue;
         }
@@ -350,15 +424,29 @@ pub fn extract_sprites(
             color: sprite.color,
             transform: *transform,
             rect: sprite.rect,
+            rotated: false,
             // Pass the custom size
             custom_size: sprite.custom_size,
             flip_x: sprite.flip_x,
             flip_y: sprite.flip_y,
             image_handle_id: handle.id(),
+            normal_map_handle_id: normal_map.map(|normal_map| normal_map.0.id()),
+            material_handle_id: None,
             anchor: sprite.anchor.as_vec(),
+            sdf_sharpness: None,
+            sort_key: sorting_layers.sort_key(sorting_layer, transform.translation()),
         });
     }
-    for (entity, visibility, atlas_sprite, transform, texture_atlas_handle) in atlas_query.iter() {
+    for (
+        entity,
+        visibility,
+        atlas_sprite,
+        transform,
+        texture_atlas_handle,
+        normal_map,
+        sorting_layer,
+    ) in atlas_query.iter()
+    {
         if !visibility.is_visible() {
             continue;
         }
@@ -370,15 +458,31 @@ pub fn extract_sprites(
                 transform: *transform,
                 // Select the area in the texture atlas
                 rect,
+                rotated: texture_atlas.is_rotated(atlas_sprite.index),
                 // Pass the custom size
                 custom_size: atlas_sprite.custom_size,
                 flip_x: atlas_sprite.flip_x,
                 flip_y: atlas_sprite.flip_y,
                 image_handle_id: texture_atlas.texture.id(),
+                normal_map_handle_id: normal_map.map(|normal_map| normal_map.0.id()),
+                material_handle_id: None,
                 anchor: atlas_sprite.anchor.as_vec(),
+                sdf_sharpness: None,
+                sort_key: sorting_layers.sort_key(sorting_layer, transform.translation()),
             });
         }
     }
+
+    if texture_array_settings.enabled {
+        let mut last_image_handle_id = None;
+        for extracted_sprite in &extracted_sprites.sprites {
+            // Successive sprites often share their image
+            if last_image_handle_id != Some(extracted_sprite.image_handle_id) {
+                last_image_handle_id = Some(extracted_sprite.image_handle_id);
+                texture_arrays.extract_image(extracted_sprite.image_handle_id, &images);
+            }
+        }
+    }
 }
 
 #[repr(C)]
@@ -386,6 +490,7 @@ pub fn extract_sprites(
 struct SpriteVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
+    pub layer: u32,
 }
 
 #[repr(C)]
@@ -393,7 +498,9 @@ struct SpriteVertex {
 struct ColoredSpriteVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
+    pub layer: u32,
     pub color: [f32; 4],
+    pub sdf_sharpness: f32,
 }
 
 #[derive(Resource)]
@@ -429,15 +536,61 @@ const QUAD_UVS: [Vec2; 4] = [
     Vec2::new(0., 0.),
 ];
 
+/// The pixels of the viewport of a [`PixelPerfectCamera`], onto which its sprites are snapped.
+struct PixelGrid {
+    clip_from_world: Mat4,
+    world_from_clip: Mat4,
+    size: Vec2,
+}
+
+impl PixelGrid {
+    fn new(view: &ExtractedView) -> Self {
+        let clip_from_world = view.projection * view.transform.compute_matrix().inverse();
+        Self {
+            clip_from_world,
+            world_from_clip: clip_from_world.inverse(),
+            size: Vec2::new(view.viewport.z as f32, view.viewport.w as f32),
+        }
+    }
+
+    /// The offset moving a position in the world to the nearest corner of a pixel.
+    fn snap_offset(&self, position: Vec3) -> Vec3 {
+        let clip_position = self.clip_from_world.project_point3(position).truncate();
+        let pixel = (clip_position * 0.5 + 0.5) * self.size;
+        let clip_offset = (pixel.round() - pixel) / self.size * 2.0;
+        self.world_from_clip
+            .transform_vector3(clip_offset.extend(0.0))
+    }
+}
+
+/// The texture of the sprites of a [`SpriteBatch`].
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub(crate) enum SpriteBatchTexture {
+    Image(HandleId),
+    /// The index of a texture array of the [`SpriteTextureArrays`], holding the images of the
+    /// sprites
+    Array(usize),
+}
+
 #[derive(Component, Eq, PartialEq, Copy, Clone)]
 pub struct SpriteBatch {
-    image_handle_id: HandleId,
+    texture: SpriteBatchTexture,
     colored: bool,
+    pub(crate) normal_map_handle_id: Option<HandleId>,
+    pub(crate) material_handle_id: Option<HandleId>,
+}
+
+impl SpriteBatch {
+    /// The index of the pipeline drawing this batch, with vertex colors and a texture array or
+    /// not
+    pub(crate) fn pipeline_index(&self) -> usize {
+        self.colored as usize | (matches!(self.texture, SpriteBatchTexture::Array(_)) as usize) << 1
+    }
 }
 
 #[derive(Resource, Default)]
 pub struct ImageBindGroups {
-    values: HashMap<Handle<Image>, BindGroup>,
+    pub(crate) values: HashMap<Handle<Image>, BindGroup>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -445,22 +598,30 @@ pub fn queue_sprites(
     mut commands: Commands,
     mut view_entities: Local<FixedBitSet>,
     draw_functions: Res<DrawFunctions<Transparent2d>>,
+    normal_draw_functions: Res<DrawFunctions<Normal2d>>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut sprite_meta: ResMut<SpriteMeta>,
     view_uniforms: Res<ViewUniforms>,
     sprite_pipeline: Res<SpritePipeline>,
     mut pipelines: ResMut<SpecializedRenderPipelines<SpritePipeline>>,
+    normals_pipeline: Res<SpriteNormalsPipeline>,
+    mut normals_pipelines: ResMut<SpecializedRenderPipelines<SpriteNormalsPipeline>>,
     pipeline_cache: Res<PipelineCache>,
     mut image_bind_groups: ResMut<ImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
     msaa: Res<Msaa>,
     mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut sprite_material_pipelines: ResMut<SpriteMaterialPipelines>,
+    texture_arrays: Res<SpriteTextureArrays>,
     mut views: Query<(
+        Entity,
         &mut RenderPhase<Transparent2d>,
+        Option<&mut RenderPhase<Normal2d>>,
         &VisibleEntities,
         &ExtractedView,
         Option<&Tonemapping>,
+        Option<&PixelPerfectCamera>,
     )>,
     events: Res<SpriteAssetEvents>,
 ) {
@@ -493,6 +654,7 @@ pub fn queue_sprites(
         }));
 
         let draw_sprite_function = draw_functions.read().id::<DrawSprite>();
+        let draw_sprite_normals_function = normal_draw_functions.read().id::<DrawSpriteNormals>();
 
         // Vertex buffer indices
         let mut index = 0;
@@ -501,22 +663,26 @@ pub fn queue_sprites(
         // FIXME: VisibleEntities is ignored
 
         let extracted_sprites = &mut extracted_sprites.sprites;
-        // Sort sprites by z for correct transparency and then by handle to improve batching
+        // Sort sprites by sorting layer, y and z for correct transparency and then by handles to improve batching
         // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
         extracted_sprites.sort_unstable_by(|a, b| {
-            match a
-                .transform
-                .translation()
-                .z
-                .partial_cmp(&b.transform.translation().z)
-            {
-                Some(Ordering::Equal) | None => a.image_handle_id.cmp(&b.image_handle_id),
-                Some(other) => other,
-            }
+            a.sort_key
+                .cmp(&b.sort_key)
+                .then_with(|| a.image_handle_id.cmp(&b.image_handle_id))
+                .then_with(|| a.material_handle_id.cmp(&b.material_handle_id))
         });
         let image_bind_groups = &mut *image_bind_groups;
 
-        for (mut transparent_phase, visible_entities, view, tonemapping) in &mut views {
+        for (
+            view_entity,
+            mut transparent_phase,
+            mut normal_phase,
+            visible_entities,
+            view,
+            tonemapping,
+            pixel_perfect,
+        ) in &mut views
+        {
             let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
             if let Some(Tonemapping::Enabled { deband_dither }) = tonemapping {
                 if !view.hdr {
@@ -527,16 +693,32 @@ pub fn queue_sprites(
                     }
                 }
             }
-            let pipeline = pipelines.specialize(
-                &pipeline_cache,
-                &sprite_pipeline,
-                view_key | SpritePipelineKey::from_colored(false),
-            );
-            let colored_pipeline = pipelines.specialize(
-                &pipeline_cache,
-                &sprite_pipeline,
-                view_key | SpritePipelineKey::from_colored(true),
-            );
+            // The pipelines drawing the sprites, indexed by `SpriteBatch::pipeline_index`
+            let sprite_pipelines: [CachedRenderPipelineId; 4] = std::array::from_fn(|index| {
+                pipelines.specialize(
+                    &pipeline_cache,
+                    &sprite_pipeline,
+                    view_key
+                        | SpritePipelineKey::from_colored(index & 1 != 0)
+                        | SpritePipelineKey::from_texture_array(index & 2 != 0),
+                )
+            });
+            // The pipelines drawing the normals of the sprites for 2D lighting, indexed by their key
+            let normals_pipeline_ids = normal_phase.is_some().then(|| {
+                let normals_pipeline_ids: [CachedRenderPipelineId; 8] =
+                    std::array::from_fn(|bits| {
+                        normals_pipelines.specialize(
+                            &pipeline_cache,
+                            &normals_pipeline,
+                            SpriteNormalsPipelineKey::from_bits_truncate(bits as u32),
+                        )
+                    });
+                normals_pipeline_ids
+            });
+
+            let pixel_grid = pixel_perfect
+                .filter(|pixel_perfect| pixel_perfect.snap_sprites)
+                .map(|_| PixelGrid::new(view));
 
             view_entities.clear();
             view_entities.extend(visible_entities.entities.iter().map(|e| e.index() as usize));
@@ -544,8 +726,10 @@ pub fn queue_sprites(
 
             // Impossible starting values that will be replaced on the first iteration
             let mut current_batch = SpriteBatch {
-                image_handle_id: HandleId::Id(Uuid::nil(), u64::MAX),
+                texture: SpriteBatchTexture::Image(HandleId::Id(Uuid::nil(), u64::MAX)),
                 colored: false,
+                normal_map_handle_id: None,
+                material_handle_id: None,
             };
             let mut current_batch_entity = Entity::PLACEHOLDER;
             let mut current_image_size = Vec2::ZERO;
@@ -558,22 +742,73 @@ pub fn queue_sprites(
                 if !view_entities.contains(extracted_sprite.entity.index() as usize) {
                     continue;
                 }
+                // Sprites whose normal map isn't loaded yet are lit as if they were flat
+                let normal_map = normal_phase
+                    .as_ref()
+                    .and(extracted_sprite.normal_map_handle_id)
+                    .and_then(|handle_id| {
+                        gpu_images
+                            .get(&Handle::weak(handle_id))
+                            .map(|gpu_image| (handle_id, gpu_image))
+                    });
+                // Sprites whose material isn't prepared yet are skipped
+                let material_pipelines = match extracted_sprite.material_handle_id {
+                    Some(handle_id) => {
+                        match sprite_material_pipelines
+                            .values
+                            .get(&(view_entity, handle_id))
+                        {
+                            Some(material_pipelines) => Some(*material_pipelines),
+                            None => continue,
+                        }
+                    }
+                    None => None,
+                };
+                // Sprites whose image was copied to a texture array are batched with the other
+                // images of the array
+                let (texture, layer) = match texture_arrays
+                    .layers
+                    .get(&extracted_sprite.image_handle_id)
+                {
+                    Some(&(array_index, layer)) => (SpriteBatchTexture::Array(array_index), layer),
+                    None => (
+                        SpriteBatchTexture::Image(extracted_sprite.image_handle_id),
+                        0,
+                    ),
+                };
                 let new_batch = SpriteBatch {
-                    image_handle_id: extracted_sprite.image_handle_id,
-                    colored: extracted_sprite.color != Color::WHITE,
+                    texture,
+                    colored: extracted_sprite.color != Color::WHITE
+                        || extracted_sprite.sdf_sharpness.is_some(),
+                    normal_map_handle_id: normal_map.map(|(handle_id, _)| handle_id),
+                    material_handle_id: extracted_sprite.material_handle_id,
                 };
                 if new_batch != current_batch {
                     // Set-up a new possible batch
-                    if let Some(gpu_image) =
-                        gpu_images.get(&Handle::weak(new_batch.image_handle_id))
-                    {
-                        current_batch = new_batch;
-                        current_image_size = Vec2::new(gpu_image.size.x, gpu_image.size.y);
-                        current_batch_entity = commands.spawn(current_batch).id();
-
+                    let image = match new_batch.texture {
+                        SpriteBatchTexture::Image(handle_id) => {
+                            match gpu_images.get(&Handle::weak(handle_id)) {
+                                Some(gpu_image) => {
+                                    current_image_size =
+                                        Vec2::new(gpu_image.size.x, gpu_image.size.y);
+                                    Some((handle_id, gpu_image))
+                                }
+                                // Skip this item if the texture is not ready
+                                None => continue,
+                            }
+                        }
+                        SpriteBatchTexture::Array(array_index) => {
+                            current_image_size = texture_arrays.arrays[array_index].size.as_vec2();
+                            None
+                        }
+                    };
+                    current_batch = new_batch;
+                    current_batch_entity = commands.spawn(current_batch).id();
+
+                    for (handle_id, gpu_image) in image.into_iter().chain(normal_map) {
                         image_bind_groups
                             .values
-                            .entry(Handle::weak(current_batch.image_handle_id))
+                            .entry(Handle::weak(handle_id))
                             .or_insert_with(|| {
                                 render_device.create_bind_group(&BindGroupDescriptor {
                                     entries: &[
@@ -592,9 +827,6 @@ pub fn queue_sprites(
                                     layout: &sprite_pipeline.material_layout,
                                 })
                             });
-                    } else {
-                        // Skip this item if the texture is not ready
-                        continue;
                     }
                 }
 
@@ -615,9 +847,17 @@ pub fn queue_sprites(
                 if let Some(rect) = extracted_sprite.rect {
                     let rect_size = rect.size();
                     for uv in &mut uvs {
+                        if extracted_sprite.rotated {
+                            // Rotate the UVs by 90 degrees counterclockwise
+                            *uv = Vec2::new(1.0 - uv.y, uv.x);
+                        }
                         *uv = (rect.min + *uv * rect_size) / current_image_size;
                     }
-                    quad_size = rect_size;
+                    quad_size = if extracted_sprite.rotated {
+                        Vec2::new(rect_size.y, rect_size.x)
+                    } else {
+                        rect_size
+                    };
                 }
 
                 // Override the size if a custom one is specified
@@ -626,52 +866,84 @@ pub fn queue_sprites(
                 }
 
                 // Apply size and global transform
-                let positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
-                    extracted_sprite
-                        .transform
-                        .transform_point(
-                            ((quad_pos - extracted_sprite.anchor) * quad_size).extend(0.),
-                        )
-                        .into()
+                let mut positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
+                    extracted_sprite.transform.transform_point(
+                        ((quad_pos - extracted_sprite.anchor) * quad_size).extend(0.),
+                    )
                 });
 
-                // These items will be sorted by depth with other phase items
-                let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
+                // Move the quad for its corner to be on the corner of a pixel
+                if let Some(pixel_grid) = &pixel_grid {
+                    let offset = pixel_grid.snap_offset(positions[0]);
+                    for position in &mut positions {
+                        *position += offset;
+                    }
+                }
+
+                // These items will be sorted by layer and depth with other phase items
+                let sort_key = extracted_sprite.sort_key;
 
                 // Store the vertex data and add the item to the render phase
-                if current_batch.colored {
+                let (item_start, item_end) = if current_batch.colored {
                     for i in QUAD_INDICES {
                         sprite_meta.colored_vertices.push(ColoredSpriteVertex {
-                            position: positions[i],
+                            position: positions[i].into(),
                             uv: uvs[i].into(),
+                            layer,
                             color: extracted_sprite.color.as_linear_rgba_f32(),
+                            sdf_sharpness: extracted_sprite.sdf_sharpness.unwrap_or(0.0),
                         });
                     }
                     let item_start = colored_index;
                     colored_index += QUAD_INDICES.len() as u32;
-                    let item_end = colored_index;
-
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline: colored_pipeline,
-                        entity: current_batch_entity,
-                        sort_key,
-                        batch_range: Some(item_start..item_end),
-                    });
+                    (item_start, colored_index)
                 } else {
                     for i in QUAD_INDICES {
                         sprite_meta.vertices.push(SpriteVertex {
-                            position: positions[i],
+                            position: positions[i].into(),
                             uv: uvs[i].into(),
+                            layer,
                         });
                     }
                     let item_start = index;
                     index += QUAD_INDICES.len() as u32;
-                    let item_end = index;
+                    (item_start, index)
+                };
+
+                // Sprites with a material are drawn with its pipelines
+                let pipeline_index = current_batch.pipeline_index();
+                let (draw_function, pipeline) = match material_pipelines {
+                    Some(material_pipelines) => (
+                        material_pipelines.draw_function,
+                        material_pipelines.pipelines[pipeline_index],
+                    ),
+                    None => (draw_sprite_function, sprite_pipelines[pipeline_index]),
+                };
+                transparent_phase.add(Transparent2d {
+                    draw_function,
+                    pipeline,
+                    entity: current_batch_entity,
+                    sort_key,
+                    batch_range: Some(item_start..item_end),
+                });
 
-                    transparent_phase.add(Transparent2d {
-                        draw_function: draw_sprite_function,
-                        pipeline,
+                // Draw the normals of the sprite with the same vertices, for 2D lighting
+                if let (Some(normal_phase), Some(normals_pipeline_ids)) =
+                    (normal_phase.as_mut(), normals_pipeline_ids)
+                {
+                    let mut normals_key = SpriteNormalsPipelineKey::NONE;
+                    normals_key.set(SpriteNormalsPipelineKey::COLORED, current_batch.colored);
+                    normals_key.set(
+                        SpriteNormalsPipelineKey::NORMAL_MAP,
+                        current_batch.normal_map_handle_id.is_some(),
+                    );
+                    normals_key.set(
+                        SpriteNormalsPipelineKey::TEXTURE_ARRAY,
+                        matches!(current_batch.texture, SpriteBatchTexture::Array(_)),
+                    );
+                    normal_phase.add(Normal2d {
+                        draw_function: draw_sprite_normals_function,
+                        pipeline: normals_pipeline_ids[normals_key.bits() as usize],
                         entity: current_batch_entity,
                         sort_key,
                         batch_range: Some(item_start..item_end),
@@ -686,6 +958,8 @@ pub fn queue_sprites(
             .colored_vertices
             .write_buffer(&render_device, &render_queue);
     }
+    // The pipelines of the materials are queued again every frame
+    sprite_material_pipelines.values.clear();
 }
 
 pub type DrawSprite = (
@@ -718,7 +992,7 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteViewBindGroup<I
 }
 pub struct SetSpriteTextureBindGroup<const I: usize>;
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteTextureBindGroup<I> {
-    type Param = SRes<ImageBindGroups>;
+    type Param = (SRes<ImageBindGroups>, SRes<SpriteTextureArrays>);
     type ViewWorldQuery = ();
     type ItemWorldQuery = Read<SpriteBatch>;
 
@@ -726,19 +1000,20 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteTextureBindGrou
         _item: &P,
         _view: (),
         sprite_batch: &'_ SpriteBatch,
-        image_bind_groups: SystemParamItem<'w, '_, Self::Param>,
+        (image_bind_groups, texture_arrays): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let image_bind_groups = image_bind_groups.into_inner();
-
-        pass.set_bind_group(
-            I,
-            image_bind_groups
+        let bind_group = match sprite_batch.texture {
+            SpriteBatchTexture::Image(handle_id) => image_bind_groups
+                .into_inner()
                 .values
-                .get(&Handle::weak(sprite_batch.image_handle_id))
+                .get(&Handle::weak(handle_id))
                 .unwrap(),
-            &[],
-        );
+            SpriteBatchTexture::Array(array_index) => {
+                &texture_arrays.into_inner().arrays[array_index].bind_group
+            }
+        };
+        pass.set_bind_group(I, bind_group, &[]);
         RenderCommandResult::Success
     }
 }
@@ -2,54 +2,39 @@
 #import bevy_core_pipeline::tonemapping
 #endif
 
-struct View {
-    view_proj: mat4x4<f32>,
-    inverse_view_proj: mat4x4<f32>,
-    view: mat4x4<f32>,
-    inverse_view: mat4x4<f32>,
-    projection: mat4x4<f32>,
-    inverse_projection: mat4x4<f32>,
-    world_position: vec3<f32>,
-    // viewport(x_origin, y_origin, width, height)
-    viewport: vec4<f32>,
-};
-@group(0) @binding(0)
-var<uniform> view: View;
-
-struct VertexOutput {
-    @location(0) uv: vec2<f32>,
-#ifdef COLORED
-    @location(1) color: vec4<f32>,
-#endif
-    @builtin(position) position: vec4<f32>,
-};
+#import bevy_sprite::sprite_bindings
+#import bevy_sprite::sprite_vertex_output
 
 @vertex
 fn vertex(
     @location(0) vertex_position: vec3<f32>,
     @location(1) vertex_uv: vec2<f32>,
+    @location(2) vertex_layer: u32,
 #ifdef COLORED
-    @location(2) vertex_color: vec4<f32>,
+    @location(3) vertex_color: vec4<f32>,
+    @location(4) vertex_sdf_sharpness: f32,
 #endif
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
+    out.layer = vertex_layer;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
 #ifdef COLORED
     out.color = vertex_color;
+    out.sdf_sharpness = vertex_sdf_sharpness;
 #endif
     return out;
 }
 
-@group(1) @binding(0)
-var sprite_texture: texture_2d<f32>;
-@group(1) @binding(1)
-var sprite_sampler: sampler;
-
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = textureSample(sprite_texture, sprite_sampler, in.uv);
+    var color = sample_sprite_texture(in.uv, in.layer);
 #ifdef COLORED
+    // The alpha of a signed distance field is the distance to the edge, smoothed over a pixel of
+    // the screen divided by the sharpness
+    let sdf_width = max(0.5 * fwidth(color.a), 1e-4) / max(in.sdf_sharpness, 1e-4);
+    let sdf_alpha = smoothstep(0.5 - sdf_width, 0.5 + sdf_width, color.a);
+    color.a = select(color.a, sdf_alpha, in.sdf_sharpness > 0.0);
     color = in.color * color;
 #endif
 
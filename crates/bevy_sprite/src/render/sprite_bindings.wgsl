@@ -0,0 +1,33 @@
+#define_import_path bevy_sprite::sprite_bindings
+
+#import bevy_sprite::mesh2d_view_types
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+// With a sprite material, the material is bound to group 1 as with the 2D meshes
+#ifdef SPRITE_MATERIAL
+@group(2) @binding(0)
+#else
+@group(1) @binding(0)
+#endif
+#ifdef TEXTURE_ARRAY
+var sprite_texture: texture_2d_array<f32>;
+#else
+var sprite_texture: texture_2d<f32>;
+#endif
+#ifdef SPRITE_MATERIAL
+@group(2) @binding(1)
+#else
+@group(1) @binding(1)
+#endif
+var sprite_sampler: sampler;
+
+// Samples the texture of the sprite, at `layer` if it is a texture array
+fn sample_sprite_texture(uv: vec2<f32>, layer: u32) -> vec4<f32> {
+#ifdef TEXTURE_ARRAY
+    return textureSample(sprite_texture, sprite_sampler, uv, i32(layer));
+#else
+    return textureSample(sprite_texture, sprite_sampler, uv);
+#endif
+}
@@ -0,0 +1,13 @@
+#define_import_path bevy_sprite::sprite_vertex_output
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+#ifdef COLORED
+    @location(1) color: vec4<f32>,
+    // The sharpness of the signed distance field in the alpha of the texture, 0 if it isn't one
+    @location(3) sdf_sharpness: f32,
+#endif
+    // The layer of the texture of the sprite, if it is a texture array
+    @location(2) @interpolate(flat) layer: u32,
+    @builtin(position) position: vec4<f32>,
+};
@@ -0,0 +1,293 @@
+use crate::{SpriteAssetEvents, SpritePipeline};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_resource::ExtractResource,
+    render_resource::{
+        BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, CommandEncoderDescriptor,
+        Extent3d, ImageCopyTexture, ImageDataLayout, Origin3d, Texture, TextureAspect,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
+        TextureViewDimension,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::{DefaultImageSampler, Image, ImageSampler, TextureFormatPixelInfo},
+};
+use bevy_utils::{HashMap, HashSet};
+use std::num::NonZeroU32;
+
+/// The settings of the batching of sprites with different images in texture arrays.
+///
+/// When enabled, the images of the sprites are copied to the layers of texture arrays, one for
+/// each size and format of image, so that the successive sprites with different images of the
+/// same size and format are drawn in a single batch instead of one batch per image. The images
+/// are kept as well, which takes more GPU memory. The images with a custom sampler, mipmaps or a
+/// compressed format are drawn on their own.
+#[derive(Resource, Clone, Debug, ExtractResource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SpriteTextureArraySettings {
+    /// Whether the images of the sprites are copied to texture arrays. Disabled by default.
+    pub enabled: bool,
+    /// The max number of layers of a texture array, lowered to the limit of the device. Another
+    /// array is added when an array with this number of layers is full.
+    pub max_layers: u32,
+}
+
+impl Default for SpriteTextureArraySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_layers: 256,
+        }
+    }
+}
+
+/// A texture array holding images of the same size and format, one per layer.
+pub struct SpriteTextureArray {
+    pub texture: Texture,
+    pub bind_group: BindGroup,
+    pub size: UVec2,
+    pub format: TextureFormat,
+    layer_count: u32,
+    free_layers: Vec<u32>,
+}
+
+impl SpriteTextureArray {
+    /// The number of layers an array starts with
+    const INITIAL_LAYER_COUNT: u32 = 8;
+
+    fn new(
+        size: UVec2,
+        format: TextureFormat,
+        layer_count: u32,
+        render_device: &RenderDevice,
+        sprite_pipeline: &SpritePipeline,
+        default_sampler: &DefaultImageSampler,
+    ) -> Self {
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("sprite_texture_array"),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: layer_count,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::COPY_SRC,
+        });
+        let texture_view = texture.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&texture_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(default_sampler),
+                },
+            ],
+            label: Some("sprite_texture_array_bind_group"),
+            layout: &sprite_pipeline.texture_array_layout,
+        });
+        Self {
+            texture,
+            bind_group,
+            size,
+            format,
+            layer_count,
+            free_layers: (0..layer_count).rev().collect(),
+        }
+    }
+}
+
+/// The texture arrays holding the images of the sprites, with the
+/// [`SpriteTextureArraySettings`].
+#[derive(Resource, Default)]
+pub struct SpriteTextureArrays {
+    pub(crate) arrays: Vec<SpriteTextureArray>,
+    /// The array and the layer of each image copied to an array
+    pub(crate) layers: HashMap<HandleId, (usize, u32)>,
+    /// The images extracted this frame, to copy to an array
+    extracted: HashMap<HandleId, Image>,
+    /// The images which can't be copied to an array
+    excluded: HashSet<HandleId>,
+}
+
+impl SpriteTextureArrays {
+    /// Extracts the image of a sprite, unless it was already copied to an array.
+    pub(crate) fn extract_image(&mut self, handle_id: HandleId, images: &Assets<Image>) {
+        if self.layers.contains_key(&handle_id)
+            || self.excluded.contains(&handle_id)
+            || self.extracted.contains_key(&handle_id)
+        {
+            return;
+        }
+        // The image is extracted once it's loaded
+        let Some(image) = images.get(&Handle::weak(handle_id)) else {
+            return;
+        };
+        let descriptor = &image.texture_descriptor;
+        if descriptor.dimension != TextureDimension::D2
+            || descriptor.size.depth_or_array_layers != 1
+            || descriptor.mip_level_count != 1
+            || image.is_compressed()
+            || image.data.len()
+                != (descriptor.size.width * descriptor.size.height) as usize
+                    * descriptor.format.pixel_size()
+            || !matches!(image.sampler_descriptor, ImageSampler::Default)
+        {
+            self.excluded.insert(handle_id);
+            return;
+        }
+        self.extracted.insert(handle_id, image.clone());
+    }
+
+    /// Frees the layer of an image, if it was copied to an array.
+    fn remove(&mut self, handle_id: HandleId) {
+        if let Some((array_index, layer)) = self.layers.remove(&handle_id) {
+            self.arrays[array_index].free_layers.push(layer);
+        }
+        self.excluded.remove(&handle_id);
+        self.extracted.remove(&handle_id);
+    }
+}
+
+/// Copies the images extracted by [`extract_sprites`](crate::extract_sprites) to the layers of
+/// the [`SpriteTextureArrays`], growing them or adding arrays when they are full.
+pub fn prepare_sprite_texture_arrays(
+    settings: Res<SpriteTextureArraySettings>,
+    mut texture_arrays: ResMut<SpriteTextureArrays>,
+    events: Res<SpriteAssetEvents>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    sprite_pipeline: Res<SpritePipeline>,
+    default_sampler: Res<DefaultImageSampler>,
+) {
+    if !settings.enabled {
+        if !texture_arrays.arrays.is_empty() {
+            *texture_arrays = SpriteTextureArrays::default();
+        }
+        return;
+    }
+
+    // The modified images are copied again the next time they are extracted
+    for event in &events.images {
+        match event {
+            AssetEvent::Created { .. } => {}
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                texture_arrays.remove(handle.id());
+            }
+        }
+    }
+
+    let max_layers = settings
+        .max_layers
+        .min(render_device.limits().max_texture_array_layers)
+        .max(1);
+    let texture_arrays = &mut *texture_arrays;
+    for (handle_id, image) in texture_arrays.extracted.drain() {
+        let size = UVec2::new(
+            image.texture_descriptor.size.width,
+            image.texture_descriptor.size.height,
+        );
+        let format = image.texture_descriptor.format;
+        let array_index = texture_arrays.arrays.iter().position(|array| {
+            array.size == size
+                && array.format == format
+                && (!array.free_layers.is_empty() || array.layer_count < max_layers)
+        });
+        let array_index = match array_index {
+            Some(array_index) => array_index,
+            None => {
+                texture_arrays.arrays.push(SpriteTextureArray::new(
+                    size,
+                    format,
+                    SpriteTextureArray::INITIAL_LAYER_COUNT.min(max_layers),
+                    &render_device,
+                    &sprite_pipeline,
+                    &default_sampler,
+                ));
+                texture_arrays.arrays.len() - 1
+            }
+        };
+
+        let array = &mut texture_arrays.arrays[array_index];
+        if array.free_layers.is_empty() {
+            // Double the layers of the array, keeping the images it already holds
+            let layer_count = (array.layer_count * 2).min(max_layers);
+            let grown_array = SpriteTextureArray::new(
+                size,
+                format,
+                layer_count,
+                &render_device,
+                &sprite_pipeline,
+                &default_sampler,
+            );
+            let mut command_encoder =
+                render_device.create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some("sprite_texture_array_grow"),
+                });
+            command_encoder.copy_texture_to_texture(
+                ImageCopyTexture {
+                    texture: &array.texture,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                ImageCopyTexture {
+                    texture: &grown_array.texture,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: array.layer_count,
+                },
+            );
+            render_queue.submit([command_encoder.finish()]);
+            *array = SpriteTextureArray {
+                free_layers: (array.layer_count..layer_count).rev().collect(),
+                ..grown_array
+            };
+        }
+
+        let layer = array.free_layers.pop().unwrap();
+        render_queue.write_texture(
+            ImageCopyTexture {
+                texture: &array.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer,
+                },
+                aspect: TextureAspect::All,
+            },
+            &image.data,
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(size.x * format.pixel_size() as u32),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        texture_arrays
+            .layers
+            .insert(handle_id, (array_index, layer));
+    }
+}
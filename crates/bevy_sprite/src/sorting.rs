@@ -0,0 +1,74 @@
+use bevy_core_pipeline::core_2d::Transparent2dSortKey;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::extract_resource::ExtractResource;
+use bevy_utils::{FloatOrd, HashSet};
+
+/// The sorting layer of a sprite, a text or a 2D mesh, drawn over the ones in lower layers and
+/// under the ones in higher layers, whatever their position on the Z axis.
+///
+/// Without this component, an entity is at order 0 in layer 0. Use [`SortingLayers`] to sort the
+/// entities of a layer by their position on the Y axis.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Default)]
+pub struct SortingLayer {
+    /// The layer of the entity.
+    pub layer: i32,
+    /// The order of the entity in its layer. The entities with a higher order are drawn over the
+    /// ones with a lower order in the same layer, whatever their position.
+    pub order: i32,
+}
+
+impl SortingLayer {
+    /// The entity is at order 0 in `layer`.
+    pub fn new(layer: i32) -> Self {
+        Self { layer, order: 0 }
+    }
+
+    /// The entity is at `order` in `layer`.
+    pub fn with_order(layer: i32, order: i32) -> Self {
+        Self { layer, order }
+    }
+}
+
+/// The settings of the [`SortingLayer`]s.
+#[derive(Resource, Clone, Debug, Default, ExtractResource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SortingLayers {
+    /// The layers whose entities are sorted by their position on the Y axis, after their order in
+    /// the layer: the lower ones are drawn over the higher ones, as in top-down games.
+    pub y_sorted: HashSet<i32>,
+}
+
+impl SortingLayers {
+    /// Sets whether the entities of `layer` are sorted by their position on the Y axis.
+    pub fn set_y_sorted(&mut self, layer: i32, y_sorted: bool) -> &mut Self {
+        if y_sorted {
+            self.y_sorted.insert(layer);
+        } else {
+            self.y_sorted.remove(&layer);
+        }
+        self
+    }
+
+    /// The key sorting an entity in the [`Transparent2d`](bevy_core_pipeline::core_2d::Transparent2d)
+    /// phase, from its sorting layer and its translation.
+    pub fn sort_key(
+        &self,
+        sorting_layer: Option<&SortingLayer>,
+        translation: Vec3,
+    ) -> Transparent2dSortKey {
+        let sorting_layer = sorting_layer.copied().unwrap_or_default();
+        Transparent2dSortKey {
+            layer: sorting_layer.layer,
+            order: sorting_layer.order,
+            y: if self.y_sorted.contains(&sorting_layer.layer) {
+                FloatOrd(-translation.y)
+            } else {
+                FloatOrd(0.0)
+            },
+            z: FloatOrd(translation.z),
+        }
+    }
+}
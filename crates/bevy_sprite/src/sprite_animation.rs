@@ -0,0 +1,363 @@
+use crate::TextureAtlasSprite;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect, TypeUuid};
+use bevy_time::Time;
+use bevy_utils::HashMap;
+
+/// How a [`SpriteAnimationClip`] goes on after its last frame.
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpriteAnimationLoopMode {
+    /// Stays on the last frame.
+    Once,
+    /// Starts again from the first frame.
+    Loop,
+    /// Plays the frames backward to the first one, then forward again, and so on.
+    PingPong,
+}
+
+impl Default for SpriteAnimationLoopMode {
+    fn default() -> Self {
+        SpriteAnimationLoopMode::Loop
+    }
+}
+
+/// A frame of a [`SpriteAnimationClip`].
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct SpriteAnimationFrame {
+    /// The index of the frame in the [`TextureAtlas`](crate::TextureAtlas) of the sprite.
+    pub index: usize,
+    /// How long the frame is shown, in seconds.
+    pub duration: f32,
+    /// The names of the [`SpriteAnimationEvent`]s sent when the frame is shown.
+    pub events: Vec<String>,
+}
+
+/// A sequence of frames of a [`SpriteAnimation`].
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub struct SpriteAnimationClip {
+    /// The frames of the clip, in order.
+    pub frames: Vec<SpriteAnimationFrame>,
+    /// How the clip goes on after its last frame.
+    pub loop_mode: SpriteAnimationLoopMode,
+}
+
+impl SpriteAnimationClip {
+    /// Creates a looping clip showing the textures at `indices` in the texture atlas, each for
+    /// `frame_duration` seconds.
+    pub fn from_indices(indices: impl IntoIterator<Item = usize>, frame_duration: f32) -> Self {
+        Self {
+            frames: indices
+                .into_iter()
+                .map(|index| SpriteAnimationFrame {
+                    index,
+                    duration: frame_duration,
+                    events: Vec::new(),
+                })
+                .collect(),
+            loop_mode: SpriteAnimationLoopMode::Loop,
+        }
+    }
+
+    /// Sets how the clip goes on after its last frame.
+    #[must_use]
+    pub fn with_loop_mode(mut self, loop_mode: SpriteAnimationLoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    /// Adds an event sent when the frame at `frame` is shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clip has no frame at `frame`.
+    #[must_use]
+    pub fn with_event(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.frames[frame].events.push(name.into());
+        self
+    }
+
+    /// The time it takes to show every frame once, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
+
+/// Named [`SpriteAnimationClip`]s for the [`TextureAtlasSprite`] of an entity, played by a
+/// [`SpriteAnimationPlayer`].
+#[derive(Reflect, FromReflect, Clone, TypeUuid, Debug, Default)]
+#[uuid = "c332e36d-e7e7-4feb-be21-24f23caf1cfc"]
+pub struct SpriteAnimation {
+    /// The clips of the animation, by name.
+    pub clips: HashMap<String, SpriteAnimationClip>,
+}
+
+impl SpriteAnimation {
+    /// Adds a clip to the animation, replacing any clip with the same name.
+    #[must_use]
+    pub fn with_clip(mut self, name: impl Into<String>, clip: SpriteAnimationClip) -> Self {
+        self.clips.insert(name.into(), clip);
+        self
+    }
+
+    /// The clip named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&SpriteAnimationClip> {
+        self.clips.get(name)
+    }
+}
+
+/// Plays a clip of a [`SpriteAnimation`], setting the index of the [`TextureAtlasSprite`] of the
+/// entity to the index of its current frame.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct SpriteAnimationPlayer {
+    animation: Handle<SpriteAnimation>,
+    clip: String,
+    frame: usize,
+    /// How long the current frame has been shown, in seconds
+    frame_elapsed: f32,
+    /// Whether the frames are played backward, in [`SpriteAnimationLoopMode::PingPong`]
+    backward: bool,
+    /// Whether the current frame was shown yet, the clip has just started otherwise
+    started: bool,
+    finished: bool,
+    paused: bool,
+    speed: f32,
+}
+
+impl Default for SpriteAnimationPlayer {
+    fn default() -> Self {
+        Self {
+            animation: Handle::default(),
+            clip: String::new(),
+            frame: 0,
+            frame_elapsed: 0.0,
+            backward: false,
+            started: false,
+            finished: false,
+            paused: false,
+            speed: 1.0,
+        }
+    }
+}
+
+impl SpriteAnimationPlayer {
+    /// Creates a player playing the clip named `clip` of `animation`.
+    pub fn new(animation: Handle<SpriteAnimation>, clip: impl Into<String>) -> Self {
+        Self {
+            animation,
+            clip: clip.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Plays the clip named `clip` from its start, unless it is already playing.
+    pub fn play(&mut self, clip: impl Into<String>) -> &mut Self {
+        let clip = clip.into();
+        if self.clip != clip {
+            self.clip = clip;
+            self.restart();
+        }
+        self
+    }
+
+    /// Plays the current clip again from its start.
+    pub fn restart(&mut self) -> &mut Self {
+        self.frame = 0;
+        self.frame_elapsed = 0.0;
+        self.backward = false;
+        self.started = false;
+        self.finished = false;
+        self
+    }
+
+    /// Sets the animation whose clips are played, and plays the clip named `clip` from its start.
+    pub fn set_animation(
+        &mut self,
+        animation: Handle<SpriteAnimation>,
+        clip: impl Into<String>,
+    ) -> &mut Self {
+        self.animation = animation;
+        self.clip = clip.into();
+        self.restart()
+    }
+
+    /// The animation whose clips are played.
+    pub fn animation(&self) -> &Handle<SpriteAnimation> {
+        &self.animation
+    }
+
+    /// The name of the clip being played.
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+
+    /// The index of the current frame in the clip.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Whether the clip has reached its last frame, with [`SpriteAnimationLoopMode::Once`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Pause the animation.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the animation.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the animation paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Speed of the animation playback.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the speed of the animation playback, which can't be negative.
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Advances the playback of `clip` by `delta` seconds, calling `show_frame` with each frame
+    /// shown in the meantime.
+    fn advance(
+        &mut self,
+        clip: &SpriteAnimationClip,
+        delta: f32,
+        mut show_frame: impl FnMut(&SpriteAnimationFrame),
+    ) {
+        if clip.frames.is_empty() {
+            return;
+        }
+        if !self.started || self.frame >= clip.frames.len() {
+            // The clip has just started, or was changed to a shorter one
+            self.frame = self.frame.min(clip.frames.len() - 1);
+            self.started = true;
+            show_frame(&clip.frames[self.frame]);
+        }
+        // Without a duration, the frames would be skipped over endlessly
+        if self.finished || clip.duration() <= 0.0 {
+            return;
+        }
+
+        let last = clip.frames.len() - 1;
+        self.frame_elapsed += delta * self.speed;
+        while self.frame_elapsed >= clip.frames[self.frame].duration {
+            self.frame_elapsed -= clip.frames[self.frame].duration;
+            self.frame = match clip.loop_mode {
+                SpriteAnimationLoopMode::Once if self.frame == last => {
+                    self.finished = true;
+                    self.frame_elapsed = 0.0;
+                    return;
+                }
+                SpriteAnimationLoopMode::Loop if self.frame == last => 0,
+                SpriteAnimationLoopMode::PingPong if last == 0 => 0,
+                SpriteAnimationLoopMode::PingPong if self.backward => {
+                    self.backward = self.frame > 1;
+                    self.frame - 1
+                }
+                SpriteAnimationLoopMode::PingPong if self.frame == last => {
+                    self.backward = true;
+                    self.frame - 1
+                }
+                _ => self.frame + 1,
+            };
+            show_frame(&clip.frames[self.frame]);
+        }
+    }
+}
+
+/// Event sent when a [`SpriteAnimationPlayer`] shows a frame with events.
+#[derive(Clone, Debug)]
+pub struct SpriteAnimationEvent {
+    /// The entity with the [`SpriteAnimationPlayer`].
+    pub entity: Entity,
+    /// The name of the clip being played.
+    pub clip: String,
+    /// The name of the event.
+    pub name: String,
+}
+
+/// System that plays the [`SpriteAnimationPlayer`]s, and sends the [`SpriteAnimationEvent`]s of
+/// the frames they show.
+pub fn animate_sprites(
+    time: Res<Time>,
+    animations: Res<Assets<SpriteAnimation>>,
+    mut players: Query<(Entity, &mut SpriteAnimationPlayer, &mut TextureAtlasSprite)>,
+    mut animation_events: EventWriter<SpriteAnimationEvent>,
+) {
+    for (entity, mut player, mut sprite) in &mut players {
+        if player.paused {
+            continue;
+        }
+        let Some((clip_name, clip)) = animations
+            .get(&player.animation)
+            .and_then(|animation| animation.clips.get_key_value(&player.clip))
+        else {
+            continue;
+        };
+
+        let mut index = None;
+        player.advance(clip, time.delta_seconds(), |frame| {
+            index = Some(frame.index);
+            animation_events.send_batch(frame.events.iter().map(|name| SpriteAnimationEvent {
+                entity,
+                clip: clip_name.clone(),
+                name: name.clone(),
+            }));
+        });
+        // Only mark the sprite as changed when its frame does
+        if let Some(index) = index {
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The indices of the frames shown by a player playing `clip` for `steps` steps of `delta`
+    /// seconds.
+    fn shown_frames(clip: &SpriteAnimationClip, steps: usize, delta: f32) -> Vec<usize> {
+        let mut player = SpriteAnimationPlayer::new(Handle::default(), "clip");
+        let mut shown = Vec::new();
+        for _ in 0..steps {
+            player.advance(clip, delta, |frame| shown.push(frame.index));
+        }
+        shown
+    }
+
+    #[test]
+    fn loop_modes() {
+        let clip = SpriteAnimationClip::from_indices([3, 4, 5], 1.0);
+        assert_eq!(shown_frames(&clip, 5, 1.0), vec![3, 4, 5, 3, 4, 5]);
+
+        let clip = clip.with_loop_mode(SpriteAnimationLoopMode::Once);
+        assert_eq!(shown_frames(&clip, 5, 1.0), vec![3, 4, 5]);
+
+        let clip = clip.with_loop_mode(SpriteAnimationLoopMode::PingPong);
+        assert_eq!(shown_frames(&clip, 6, 1.0), vec![3, 4, 5, 4, 3, 4, 5]);
+    }
+
+    #[test]
+    fn frame_durations() {
+        let mut clip = SpriteAnimationClip::from_indices([0, 1], 0.5);
+        clip.frames[1].duration = 2.0;
+        // Several frames are skipped over in a long step, and a frame lasts several short ones
+        assert_eq!(shown_frames(&clip, 1, 3.0), vec![0, 1, 0, 1]);
+        assert_eq!(shown_frames(&clip, 5, 0.5), vec![0, 1, 0]);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{
+    queue_sprites, DrawSpriteBatch, ExtractedSprites, Material2d, Material2dPipeline,
+    Material2dPlugin, RenderMaterials2d, SetSpriteTextureBindGroup, SetSpriteViewBindGroup,
+    SpriteBatch, SpritePipeline, SpritePipelineKey,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::{Handle, HandleId};
+use bevy_core_pipeline::{core_2d::Transparent2d, tonemapping::Tonemapping};
+use bevy_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_render::{
+    render_phase::{
+        AddRenderCommand, DrawFunctionId, DrawFunctions, PhaseItem, RenderCommand,
+        RenderCommandResult, RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::{
+        BindGroupLayout, CachedRenderPipelineId, PipelineCache, RenderPipelineDescriptor, Shader,
+        SpecializedRenderPipeline, SpecializedRenderPipelines,
+    },
+    view::{ExtractedView, Msaa},
+    RenderApp, RenderStage,
+};
+use bevy_utils::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Adds the render logic drawing the sprites, texture atlas sprites and 2D texts with a
+/// [`Handle<M>`] with the shaders of the given [`Material2d`], instead of the default sprite
+/// shader. Adds the [`Material2dPlugin<M>`] too, if it wasn't added before.
+///
+/// The sprites keep their vertices, texture and color, and the successive sprites sharing the
+/// same material and texture are still drawn in a single batch. The material is bound to group 1
+/// and the texture of the sprite to group 2: the shaders of the material can import
+/// `bevy_sprite::sprite_bindings` for the view and the texture of the sprite, and
+/// `bevy_sprite::sprite_vertex_output` for the `VertexOutput` of the default sprite vertex
+/// shader. With the [`SpriteTextureArraySettings`](crate::SpriteTextureArraySettings), the
+/// texture may be a texture array: `sample_sprite_texture` samples it either way.
+///
+/// As the sprites have no mesh, [`Material2d::specialize`] isn't called for them.
+pub struct SpriteMaterialPlugin<M: Material2d>(PhantomData<M>);
+
+impl<M: Material2d> Default for SpriteMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: Material2d> Plugin for SpriteMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<Material2dPlugin<M>>() {
+            app.add_plugin(Material2dPlugin::<M>::default());
+        }
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent2d, DrawSpriteMaterial<M>>()
+                .init_resource::<SpriteMaterialPipeline<M>>()
+                .init_resource::<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>()
+                .add_system_to_stage(
+                    RenderStage::Queue,
+                    queue_sprite_materials::<M>.before(queue_sprites),
+                );
+        }
+    }
+}
+
+/// Render pipeline data for the sprites with a given [`Material2d`]
+#[derive(Resource)]
+pub struct SpriteMaterialPipeline<M: Material2d> {
+    pub sprite_pipeline: SpritePipeline,
+    pub material2d_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+impl<M: Material2d> FromWorld for SpriteMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        // The prepared materials are bound with the layout of the 2D mesh pipeline
+        let material2d_pipeline = world.resource::<Material2dPipeline<M>>();
+        SpriteMaterialPipeline {
+            sprite_pipeline: world.resource::<SpritePipeline>().clone(),
+            material2d_layout: material2d_pipeline.material2d_layout.clone(),
+            vertex_shader: material2d_pipeline.vertex_shader.clone(),
+            fragment_shader: material2d_pipeline.fragment_shader.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+pub struct SpriteMaterialKey<M: Material2d> {
+    pub sprite_key: SpritePipelineKey,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: Material2d> Eq for SpriteMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: Material2d> PartialEq for SpriteMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sprite_key == other.sprite_key && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: Material2d> Clone for SpriteMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sprite_key: self.sprite_key,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: Material2d> Hash for SpriteMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.sprite_key.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+impl<M: Material2d> SpecializedRenderPipeline for SpriteMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = SpriteMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.sprite_pipeline.specialize(key.sprite_key);
+        descriptor.vertex.shader_defs.push("SPRITE_MATERIAL".into());
+        if let Some(vertex_shader) = &self.vertex_shader {
+            descriptor.vertex.shader = vertex_shader.clone();
+        }
+
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader_defs.push("SPRITE_MATERIAL".into());
+        if let Some(fragment_shader) = &self.fragment_shader {
+            fragment.shader = fragment_shader.clone();
+        }
+        let texture_layout = if key.sprite_key.contains(SpritePipelineKey::TEXTURE_ARRAY) {
+            &self.sprite_pipeline.texture_array_layout
+        } else {
+            &self.sprite_pipeline.material_layout
+        };
+        descriptor.layout = Some(vec![
+            self.sprite_pipeline.view_layout.clone(),
+            self.material2d_layout.clone(),
+            texture_layout.clone(),
+        ]);
+        descriptor.label = Some("sprite_material_pipeline".into());
+        descriptor
+    }
+}
+
+/// The draw function and the pipelines, with and without vertex colors and a texture array,
+/// drawing the sprites with a material in a view.
+#[derive(Clone, Copy)]
+pub struct SpriteMaterialPipelineIds {
+    pub draw_function: DrawFunctionId,
+    pub pipelines: [CachedRenderPipelineId; 4],
+}
+
+/// The pipelines of the materials of the sprites in each view, queued every frame by the
+/// [`SpriteMaterialPlugin`]s for [`queue_sprites`].
+#[derive(Resource, Default)]
+pub struct SpriteMaterialPipelines {
+    pub(crate) values: HashMap<(Entity, HandleId), SpriteMaterialPipelineIds>,
+}
+
+/// Sets the materials of the sprites with a [`Handle<M>`], and queues their pipelines in each
+/// view for [`queue_sprites`] to draw and batch them.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_sprite_materials<M: Material2d>(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    sprite_material_pipeline: Res<SpriteMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    render_materials: Res<RenderMaterials2d<M>>,
+    mut sprite_material_pipelines: ResMut<SpriteMaterialPipelines>,
+    mut extracted_sprites: ResMut<ExtractedSprites>,
+    material_handles: Query<&Handle<M>>,
+    views: Query<(Entity, &ExtractedView, Option<&Tonemapping>), With<RenderPhase<Transparent2d>>>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if material_handles.is_empty() {
+        return;
+    }
+
+    // The material handles were extracted to the entities of the sprites
+    let mut sprite_materials = HashSet::default();
+    for extracted_sprite in &mut extracted_sprites.sprites {
+        if let Ok(material_handle) = material_handles.get(extracted_sprite.entity) {
+            extracted_sprite.material_handle_id = Some(material_handle.id());
+            sprite_materials.insert(material_handle);
+        }
+    }
+
+    let draw_function = draw_functions.read().id::<DrawSpriteMaterial<M>>();
+    let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples);
+    for (view_entity, view, tonemapping) in &views {
+        let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
+        if let Some(Tonemapping::Enabled { deband_dither }) = tonemapping {
+            if !view.hdr {
+                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
+
+                if *deband_dither {
+                    view_key |= SpritePipelineKey::DEBAND_DITHER;
+                }
+            }
+        }
+
+        for material_handle in &sprite_materials {
+            // The sprites are skipped until their material is prepared
+            let Some(material) = render_materials.get(*material_handle) else {
+                continue;
+            };
+            // Indexed like the pipelines of `queue_sprites`
+            let pipelines = std::array::from_fn(|index| {
+                pipelines.specialize(
+                    &pipeline_cache,
+                    &sprite_material_pipeline,
+                    SpriteMaterialKey {
+                        sprite_key: view_key
+                            | SpritePipelineKey::from_colored(index & 1 != 0)
+                            | SpritePipelineKey::from_texture_array(index & 2 != 0),
+                        bind_group_data: material.key.clone(),
+                    },
+                )
+            });
+            sprite_material_pipelines.values.insert(
+                (view_entity, material_handle.id()),
+                SpriteMaterialPipelineIds {
+                    draw_function,
+                    pipelines,
+                },
+            );
+        }
+    }
+}
+
+pub type DrawSpriteMaterial<M> = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteMaterialBindGroup<M, 1>,
+    SetSpriteTextureBindGroup<2>,
+    DrawSpriteBatch,
+);
+
+pub struct SetSpriteMaterialBindGroup<M: Material2d, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: Material2d, const I: usize> RenderCommand<P>
+    for SetSpriteMaterialBindGroup<M, I>
+{
+    type Param = SRes<RenderMaterials2d<M>>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<SpriteBatch>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        sprite_batch: &'_ SpriteBatch,
+        materials: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(material2d) = sprite_batch
+            .material_handle_id
+            .and_then(|handle_id| materials.into_inner().get(&Handle::weak(handle_id)))
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &material2d.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,744 @@
+use super::{AnimationTag, TagDirection};
+use crate::{SpriteAnimation, TextureAtlas};
+use bevy_asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy_math::{Rect, Vec2};
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::{Image, ImageSamplerSettings},
+};
+use flate2::read::ZlibDecoder;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use thiserror::Error;
+
+/// Loads the Aseprite files, `.aseprite` and `.ase`, as [`TextureAtlas`]es.
+///
+/// The visible layers of each frame are blended into a texture of the atlas, at the index of the
+/// frame. The image of the atlas is labeled `Image`, and the tags of the file are loaded as the
+/// clips of a [`SpriteAnimation`] labeled `Animation`, so that
+/// `asset_server.load("player.aseprite#Animation")` can be played by a
+/// [`SpriteAnimationPlayer`](crate::SpriteAnimationPlayer) on the atlas.
+///
+/// The layers are blended with the normal blend mode, whatever their blend mode, and the tilemap
+/// layers are ignored.
+#[derive(Default)]
+pub struct AsepriteLoader;
+
+/// Settings of the [`AsepriteLoader`], read from the settings file of an Aseprite file.
+///
+/// For example, `player.aseprite.meta` could contain `(sampler: Nearest)`. See
+/// [`LoadContext::settings`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AsepriteLoaderSettings {
+    /// The sampler of the image of the atlas.
+    pub sampler: ImageSamplerSettings,
+}
+
+/// An error that occurs when loading an Aseprite file.
+#[derive(Error, Debug)]
+pub enum AsepriteError {
+    #[error("invalid Aseprite file: wrong magic number")]
+    InvalidMagicNumber,
+    #[error("invalid Aseprite file: unexpected end of data")]
+    UnexpectedEnd,
+    #[error("unsupported color depth of {0} bits per pixel")]
+    UnsupportedColorDepth(u16),
+    #[error("invalid cel image: {0}")]
+    InvalidCelImage(#[from] std::io::Error),
+    #[error("the tag {0} refers to missing frames")]
+    InvalidTag(String),
+}
+
+impl AssetLoader for AsepriteLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+        Box::pin(async move {
+            let settings: AsepriteLoaderSettings = load_context.settings()?;
+            let file = AsepriteFile::parse(bytes)?;
+
+            let durations: Vec<f32> = file
+                .frames
+                .iter()
+                .map(|frame| frame.duration as f32 / 1000.0)
+                .collect();
+            let mut animation = SpriteAnimation::default();
+            for tag in &file.tags {
+                let clip = tag
+                    .clip(&durations)
+                    .ok_or_else(|| AsepriteError::InvalidTag(tag.name.clone()))?;
+                animation.clips.insert(tag.name.clone(), clip);
+            }
+
+            let (mut image, textures) = file.sprite_sheet();
+            image.sampler_descriptor = settings.sampler.into();
+            let size = Vec2::new(
+                image.texture_descriptor.size.width as f32,
+                image.texture_descriptor.size.height as f32,
+            );
+            let image = load_context.set_labeled_asset("Image", LoadedAsset::new(image));
+            let mut atlas = TextureAtlas::new_empty(image, size);
+            for texture in textures {
+                atlas.add_texture(texture);
+            }
+
+            load_context.set_labeled_asset("Animation", LoadedAsset::new(animation));
+            load_context.set_default_asset(LoadedAsset::new(atlas));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite", "ase"]
+    }
+}
+
+const FILE_MAGIC_NUMBER: u16 = 0xA5E0;
+const FRAME_MAGIC_NUMBER: u16 = 0xF1FA;
+
+const OLD_PALETTE_CHUNK: u16 = 0x0004;
+const OLD_PALETTE_64_CHUNK: u16 = 0x0011;
+const LAYER_CHUNK: u16 = 0x2004;
+const CEL_CHUNK: u16 = 0x2005;
+const TAGS_CHUNK: u16 = 0x2018;
+const PALETTE_CHUNK: u16 = 0x2019;
+
+/// Reads the little-endian values of an Aseprite file
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AsepriteError> {
+        if len > self.bytes.len() {
+            return Err(AsepriteError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    /// Takes a chunk of data starting with its size as a DWORD, including the size itself
+    fn take_sized(&mut self) -> Result<Reader<'a>, AsepriteError> {
+        let size = self.dword()? as usize;
+        let bytes = self.take(size.checked_sub(4).ok_or(AsepriteError::UnexpectedEnd)?)?;
+        Ok(Reader { bytes })
+    }
+
+    fn byte(&mut self) -> Result<u8, AsepriteError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn word(&mut self) -> Result<u16, AsepriteError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn short(&mut self) -> Result<i16, AsepriteError> {
+        Ok(i16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn dword(&mut self) -> Result<u32, AsepriteError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, AsepriteError> {
+        let len = self.word()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorDepth {
+    Rgba,
+    Grayscale,
+    Indexed,
+}
+
+impl ColorDepth {
+    fn pixel_size(self) -> usize {
+        match self {
+            ColorDepth::Rgba => 4,
+            ColorDepth::Grayscale => 2,
+            ColorDepth::Indexed => 1,
+        }
+    }
+}
+
+struct Layer {
+    /// Whether the cels of the layer are drawn: it is a visible image layer, in visible groups
+    drawn: bool,
+    opacity: u8,
+    /// Whether the layer is the background, which isn't transparent in indexed files
+    background: bool,
+}
+
+enum CelImage {
+    Pixels {
+        width: usize,
+        height: usize,
+        /// The pixels of the image, in the color depth of the file
+        data: Vec<u8>,
+    },
+    /// The image of the cel of the same layer in another frame
+    Linked(usize),
+}
+
+struct Cel {
+    layer: usize,
+    x: i32,
+    y: i32,
+    opacity: u8,
+    z_index: i32,
+    image: CelImage,
+}
+
+struct Frame {
+    /// The duration of the frame, in milliseconds
+    duration: u16,
+    cels: Vec<Cel>,
+}
+
+/// The data of an Aseprite file, as described in
+/// <https://github.com/aseprite/aseprite/blob/main/docs/ase-file-specs.md>.
+struct AsepriteFile {
+    width: usize,
+    height: usize,
+    color_depth: ColorDepth,
+    /// The palette index of the transparent color, in indexed files
+    transparent_index: u8,
+    palette: Vec<[u8; 4]>,
+    layers: Vec<Layer>,
+    frames: Vec<Frame>,
+    tags: Vec<AnimationTag>,
+}
+
+impl AsepriteFile {
+    fn parse(bytes: &[u8]) -> Result<Self, AsepriteError> {
+        let mut reader = Reader { bytes };
+        let mut header = Reader {
+            bytes: reader.take(128)?,
+        };
+        let _file_size = header.dword()?;
+        if header.word()? != FILE_MAGIC_NUMBER {
+            return Err(AsepriteError::InvalidMagicNumber);
+        }
+        let frame_count = header.word()?;
+        let width = header.word()? as usize;
+        let height = header.word()? as usize;
+        let color_depth = match header.word()? {
+            32 => ColorDepth::Rgba,
+            16 => ColorDepth::Grayscale,
+            8 => ColorDepth::Indexed,
+            depth => return Err(AsepriteError::UnsupportedColorDepth(depth)),
+        };
+        let layer_opacity = header.dword()? & 1 != 0;
+        // Deprecated speed and reserved DWORDs
+        header.take(10)?;
+        let transparent_index = header.byte()?;
+
+        let mut file = AsepriteFile {
+            width,
+            height,
+            color_depth,
+            transparent_index,
+            palette: Vec::new(),
+            layers: Vec::new(),
+            frames: Vec::with_capacity(frame_count as usize),
+            tags: Vec::new(),
+        };
+        // The visibility of the groups containing the current layer, by child level
+        let mut groups_visible = Vec::new();
+        // The palette of files written by older versions of Aseprite
+        let mut old_palette = Vec::new();
+        let mut has_palette = false;
+
+        for _ in 0..frame_count {
+            let mut frame_reader = reader.take_sized()?;
+            if frame_reader.word()? != FRAME_MAGIC_NUMBER {
+                return Err(AsepriteError::InvalidMagicNumber);
+            }
+            let old_chunk_count = frame_reader.word()?;
+            let duration = frame_reader.word()?;
+            frame_reader.take(2)?;
+            let chunk_count = match frame_reader.dword()? {
+                0 => old_chunk_count as u32,
+                chunk_count => chunk_count,
+            };
+
+            let mut frame = Frame {
+                duration,
+                cels: Vec::new(),
+            };
+            for _ in 0..chunk_count {
+                let mut chunk = frame_reader.take_sized()?;
+                match chunk.word()? {
+                    OLD_PALETTE_CHUNK => read_old_palette(&mut chunk, &mut old_palette, 255)?,
+                    OLD_PALETTE_64_CHUNK => read_old_palette(&mut chunk, &mut old_palette, 63)?,
+                    PALETTE_CHUNK => {
+                        has_palette = true;
+                        let size = chunk.dword()? as usize;
+                        let first = chunk.dword()? as usize;
+                        let last = chunk.dword()? as usize;
+                        chunk.take(8)?;
+                        file.palette.resize(size.max(last + 1), [0; 4]);
+                        for index in first..=last {
+                            let flags = chunk.word()?;
+                            let color = chunk.take(4)?;
+                            file.palette[index] = [color[0], color[1], color[2], color[3]];
+                            if flags & 1 != 0 {
+                                chunk.string()?;
+                            }
+                        }
+                    }
+                    LAYER_CHUNK => {
+                        let flags = chunk.word()?;
+                        let layer_type = chunk.word()?;
+                        let child_level = chunk.word()? as usize;
+                        // Ignored default size and blend mode
+                        chunk.take(6)?;
+                        let opacity = chunk.byte()?;
+
+                        groups_visible.truncate(child_level);
+                        let visible = flags & 1 != 0 && groups_visible.iter().all(|&v| v);
+                        groups_visible.push(visible);
+                        // Only the visible image layers are drawn, not the reference layers
+                        file.layers.push(Layer {
+                            drawn: visible && layer_type == 0 && flags & 64 == 0,
+                            opacity: if layer_opacity { opacity } else { 255 },
+                            background: flags & 8 != 0,
+                        });
+                    }
+                    CEL_CHUNK => {
+                        let layer = chunk.word()? as usize;
+                        let x = chunk.short()? as i32;
+                        let y = chunk.short()? as i32;
+                        let opacity = chunk.byte()?;
+                        let cel_type = chunk.word()?;
+                        let z_index = chunk.short()? as i32;
+                        chunk.take(5)?;
+                        let image = match cel_type {
+                            0 | 2 => {
+                                let width = chunk.word()? as usize;
+                                let height = chunk.word()? as usize;
+                                let len = width * height * color_depth.pixel_size();
+                                let data = if cel_type == 0 {
+                                    chunk.take(len)?.to_vec()
+                                } else {
+                                    let mut data = Vec::with_capacity(len);
+                                    ZlibDecoder::new(chunk.bytes).read_to_end(&mut data)?;
+                                    data
+                                };
+                                if data.len() < len {
+                                    return Err(AsepriteError::UnexpectedEnd);
+                                }
+                                CelImage::Pixels {
+                                    width,
+                                    height,
+                                    data,
+                                }
+                            }
+                            1 => CelImage::Linked(chunk.word()? as usize),
+                            // Tilemap cels aren't drawn
+                            _ => continue,
+                        };
+                        frame.cels.push(Cel {
+                            layer,
+                            x,
+                            y,
+                            opacity,
+                            z_index,
+                            image,
+                        });
+                    }
+                    TAGS_CHUNK => {
+                        let tag_count = chunk.word()?;
+                        chunk.take(8)?;
+                        for _ in 0..tag_count {
+                            let from = chunk.word()? as usize;
+                            let to = chunk.word()? as usize;
+                            let direction = match chunk.byte()? {
+                                1 => TagDirection::Reverse,
+                                2 => TagDirection::PingPong,
+                                3 => TagDirection::PingPongReverse,
+                                _ => TagDirection::Forward,
+                            };
+                            let repeat = chunk.word()? as u32;
+                            // Reserved bytes and deprecated color
+                            chunk.take(10)?;
+                            let name = chunk.string()?;
+                            file.tags.push(AnimationTag {
+                                name,
+                                from,
+                                to,
+                                direction,
+                                repeat,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            file.frames.push(frame);
+        }
+
+        if !has_palette {
+            file.palette = old_palette;
+        }
+        Ok(file)
+    }
+
+    /// The color of the pixel at `index` in the image of a cel of `layer`.
+    fn pixel(&self, data: &[u8], index: usize, layer: &Layer) -> [u8; 4] {
+        match self.color_depth {
+            ColorDepth::Rgba => {
+                let pixel = &data[index * 4..index * 4 + 4];
+                [pixel[0], pixel[1], pixel[2], pixel[3]]
+            }
+            ColorDepth::Grayscale => {
+                let (value, alpha) = (data[index * 2], data[index * 2 + 1]);
+                [value, value, value, alpha]
+            }
+            ColorDepth::Indexed => {
+                let palette_index = data[index];
+                if palette_index == self.transparent_index && !layer.background {
+                    [0; 4]
+                } else {
+                    self.palette
+                        .get(palette_index as usize)
+                        .copied()
+                        .unwrap_or([0; 4])
+                }
+            }
+        }
+    }
+
+    /// Blends the visible cels of the frame at `frame_index` into `target`, an RGBA image of the
+    /// size of the file.
+    fn draw_frame(&self, frame_index: usize, target: &mut [u8]) {
+        let mut cels: Vec<&Cel> = self.frames[frame_index].cels.iter().collect();
+        // The z-index of a cel moves it among the cels of the other layers
+        cels.sort_by_key(|cel| (cel.layer as i32 + cel.z_index, cel.z_index));
+
+        for cel in cels {
+            let Some(layer) = self.layers.get(cel.layer).filter(|layer| layer.drawn) else {
+                continue;
+            };
+            let image = match cel.image {
+                CelImage::Linked(linked_frame) => self.frames.get(linked_frame).and_then(|frame| {
+                    frame
+                        .cels
+                        .iter()
+                        .find(|linked_cel| linked_cel.layer == cel.layer)
+                        .map(|linked_cel| &linked_cel.image)
+                }),
+                _ => Some(&cel.image),
+            };
+            let Some(&CelImage::Pixels {
+                width,
+                height,
+                ref data,
+            }) = image
+            else {
+                continue;
+            };
+
+            let opacity = (cel.opacity as u32 * layer.opacity as u32 / 255) as u8;
+            for y in 0..height {
+                let target_y = cel.y + y as i32;
+                if target_y < 0 || target_y >= self.height as i32 {
+                    continue;
+                }
+                for x in 0..width {
+                    let target_x = cel.x + x as i32;
+                    if target_x < 0 || target_x >= self.width as i32 {
+                        continue;
+                    }
+                    let color = self.pixel(data, y * width + x, layer);
+                    let target_index = (target_y as usize * self.width + target_x as usize) * 4;
+                    blend(&mut target[target_index..target_index + 4], color, opacity);
+                }
+            }
+        }
+    }
+
+    /// Draws the frames in a grid in an image, and returns it with the area of each frame.
+    fn sprite_sheet(&self) -> (Image, Vec<Rect>) {
+        let frame_count = self.frames.len().max(1);
+        let columns = (frame_count as f32).sqrt().ceil() as usize;
+        let rows = (frame_count as f32 / columns as f32).ceil() as usize;
+        let sheet_width = self.width * columns;
+
+        let mut data = vec![0; sheet_width * self.height * rows * 4];
+        let mut frame_data = vec![0; self.width * self.height * 4];
+        let mut textures = Vec::with_capacity(self.frames.len());
+        for frame_index in 0..self.frames.len() {
+            frame_data.fill(0);
+            self.draw_frame(frame_index, &mut frame_data);
+
+            let (column, row) = (frame_index % columns, frame_index / columns);
+            for (y, frame_row) in frame_data.chunks_exact(self.width * 4).enumerate() {
+                let start = ((row * self.height + y) * sheet_width + column * self.width) * 4;
+                data[start..start + frame_row.len()].copy_from_slice(frame_row);
+            }
+            textures.push(Rect::new(
+                (column * self.width) as f32,
+                (row * self.height) as f32,
+                ((column + 1) * self.width) as f32,
+                ((row + 1) * self.height) as f32,
+            ));
+        }
+
+        let image = Image::new(
+            Extent3d {
+                width: sheet_width as u32,
+                height: (self.height * rows) as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        (image, textures)
+    }
+}
+
+/// Reads a palette chunk of older versions of Aseprite, whose color components go up to `max`.
+fn read_old_palette(
+    chunk: &mut Reader,
+    palette: &mut Vec<[u8; 4]>,
+    max: u32,
+) -> Result<(), AsepriteError> {
+    let packet_count = chunk.word()?;
+    let mut index = 0;
+    for _ in 0..packet_count {
+        index += chunk.byte()? as usize;
+        let color_count = match chunk.byte()? {
+            0 => 256,
+            color_count => color_count as usize,
+        };
+        for _ in 0..color_count {
+            let color = chunk.take(3)?;
+            if index >= palette.len() {
+                palette.resize(index + 1, [0; 4]);
+            }
+            palette[index] = [
+                (color[0] as u32 * 255 / max) as u8,
+                (color[1] as u32 * 255 / max) as u8,
+                (color[2] as u32 * 255 / max) as u8,
+                255,
+            ];
+            index += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Blends `color` with `opacity` over the `target` color, with the normal blend mode.
+fn blend(target: &mut [u8], color: [u8; 4], opacity: u8) {
+    let alpha = color[3] as f32 / 255.0 * opacity as f32 / 255.0;
+    if alpha <= 0.0 {
+        return;
+    }
+    let target_alpha = target[3] as f32 / 255.0;
+    let blended_alpha = alpha + target_alpha * (1.0 - alpha);
+    for channel in 0..3 {
+        let blended = (color[channel] as f32 * alpha
+            + target[channel] as f32 * target_alpha * (1.0 - alpha))
+            / blended_alpha;
+        target[channel] = blended.round() as u8;
+    }
+    target[3] = (blended_alpha * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes the little-endian values of an Aseprite file
+    #[derive(Default)]
+    struct Writer {
+        bytes: Vec<u8>,
+    }
+
+    impl Writer {
+        fn word(&mut self, value: u16) -> &mut Self {
+            self.bytes.extend(value.to_le_bytes());
+            self
+        }
+
+        fn dword(&mut self, value: u32) -> &mut Self {
+            self.bytes.extend(value.to_le_bytes());
+            self
+        }
+
+        fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+            self.bytes.extend(bytes);
+            self
+        }
+
+        fn string(&mut self, value: &str) -> &mut Self {
+            self.word(value.len() as u16).bytes(value.as_bytes())
+        }
+
+        /// Writes `data` prefixed by its size as a DWORD
+        fn sized(&mut self, data: &[u8]) -> &mut Self {
+            self.dword(data.len() as u32 + 4).bytes(data)
+        }
+    }
+
+    fn chunk(chunk_type: u16, data: &Writer) -> Vec<u8> {
+        let mut chunk = Writer::default();
+        chunk.word(chunk_type).bytes(&data.bytes);
+        chunk.bytes
+    }
+
+    fn layer_chunk(flags: u16, opacity: u8) -> Vec<u8> {
+        let mut layer = Writer::default();
+        layer
+            .word(flags)
+            .word(0)
+            .word(0)
+            .bytes(&[0; 6])
+            .bytes(&[opacity, 0, 0, 0])
+            .string("layer");
+        chunk(LAYER_CHUNK, &layer)
+    }
+
+    /// A raw cel of `layer` at (`x`, `y`), one pixel wide, with the RGBA `pixels`
+    fn cel_chunk(layer: u16, x: i16, y: i16, pixels: &[[u8; 4]]) -> Vec<u8> {
+        let mut cel = Writer::default();
+        cel.word(layer)
+            .word(x as u16)
+            .word(y as u16)
+            .bytes(&[255])
+            .word(0)
+            .word(0)
+            .bytes(&[0; 5])
+            .word(1)
+            .word(pixels.len() as u16);
+        for pixel in pixels {
+            cel.bytes(pixel);
+        }
+        chunk(CEL_CHUNK, &cel)
+    }
+
+    fn linked_cel_chunk(layer: u16, frame: u16) -> Vec<u8> {
+        let mut cel = Writer::default();
+        cel.word(layer)
+            .dword(0)
+            .bytes(&[255])
+            .word(1)
+            .word(0)
+            .bytes(&[0; 5])
+            .word(frame);
+        chunk(CEL_CHUNK, &cel)
+    }
+
+    fn file(width: u16, height: u16, frames: &[(u16, Vec<Vec<u8>>)]) -> Vec<u8> {
+        let mut file = Writer::default();
+        file.dword(0)
+            .word(FILE_MAGIC_NUMBER)
+            .word(frames.len() as u16)
+            .word(width)
+            .word(height)
+            .word(32)
+            .dword(1)
+            .bytes(&[0; 110]);
+        for (duration, chunks) in frames {
+            let mut frame = Writer::default();
+            frame
+                .word(FRAME_MAGIC_NUMBER)
+                .word(chunks.len() as u16)
+                .word(*duration)
+                .bytes(&[0; 2])
+                .dword(chunks.len() as u32);
+            for chunk in chunks {
+                frame.sized(chunk);
+            }
+            file.sized(&frame.bytes);
+        }
+        file.bytes
+    }
+
+    #[test]
+    fn parse_and_draw_frames() {
+        let mut tags = Writer::default();
+        tags.word(1)
+            .bytes(&[0; 8])
+            .word(0)
+            .word(1)
+            .bytes(&[2])
+            .word(0)
+            .bytes(&[0; 10])
+            .string("walk");
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let bytes = file(
+            2,
+            2,
+            &[
+                (
+                    100,
+                    vec![
+                        layer_chunk(1, 255),
+                        // Hidden layer
+                        layer_chunk(0, 255),
+                        // Half transparent layer
+                        layer_chunk(1, 128),
+                        chunk(TAGS_CHUNK, &tags),
+                        cel_chunk(0, 0, 0, &[red, red]),
+                        cel_chunk(1, 1, 0, &[blue, blue]),
+                        cel_chunk(2, 1, 1, &[blue, blue]),
+                    ],
+                ),
+                (250, vec![linked_cel_chunk(0, 0)]),
+            ],
+        );
+
+        let file = AsepriteFile::parse(&bytes).unwrap();
+        assert_eq!(file.frames.len(), 2);
+        assert_eq!(file.frames[1].duration, 250);
+        assert_eq!(
+            file.tags,
+            vec![AnimationTag {
+                name: "walk".to_string(),
+                from: 0,
+                to: 1,
+                direction: TagDirection::PingPong,
+                repeat: 0,
+            }]
+        );
+
+        let mut frame = vec![0; 2 * 2 * 4];
+        file.draw_frame(0, &mut frame);
+        assert_eq!(
+            frame,
+            [red, [0; 4], red, [0, 0, 255, 128]].concat(),
+            "the hidden layer and the cel out of the frame aren't drawn"
+        );
+
+        let (image, textures) = file.sprite_sheet();
+        assert_eq!(image.texture_descriptor.size.width, 4);
+        assert_eq!(image.texture_descriptor.size.height, 2);
+        assert_eq!(textures[1], Rect::new(2.0, 0.0, 4.0, 2.0));
+        // The linked cel is drawn in the second frame
+        assert_eq!(&image.data[8..12], &red);
+    }
+
+    #[test]
+    fn invalid_file() {
+        assert!(matches!(
+            AsepriteFile::parse(&[0; 128]),
+            Err(AsepriteError::InvalidMagicNumber)
+        ));
+        let mut bytes = file(1, 1, &[(100, vec![cel_chunk(0, 0, 0, &[[0; 4]])])]);
+        bytes.truncate(bytes.len() - 1);
+        assert!(matches!(
+            AsepriteFile::parse(&bytes),
+            Err(AsepriteError::UnexpectedEnd)
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(feature = "aseprite")]
+mod aseprite;
+#[cfg(feature = "texture_packer")]
+mod texture_packer;
+
+#[cfg(feature = "aseprite")]
+pub use aseprite::*;
+#[cfg(feature = "texture_packer")]
+pub use texture_packer::*;
+
+use crate::{SpriteAnimationClip, SpriteAnimationFrame, SpriteAnimationLoopMode};
+use serde::Deserialize;
+
+/// The direction in which an [`AnimationTag`] plays its frames.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TagDirection {
+    #[default]
+    Forward,
+    Reverse,
+    #[serde(rename = "pingpong")]
+    PingPong,
+    #[serde(rename = "pingpong_reverse")]
+    PingPongReverse,
+}
+
+/// A named range of frames of a sprite sheet, as exported by Aseprite, loaded as a
+/// [`SpriteAnimationClip`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AnimationTag {
+    pub name: String,
+    /// The first frame of the tag
+    pub from: usize,
+    /// The last frame of the tag, included
+    pub to: usize,
+    pub direction: TagDirection,
+    /// How many times the frames are played, endlessly if 0. With ping-pong, going forward and
+    /// going backward are played once each.
+    pub repeat: u32,
+}
+
+impl AnimationTag {
+    /// The clip playing the frames of the tag, from the durations of the frames of the sprite
+    /// sheet in seconds, or `None` if the tag refers to missing frames.
+    pub fn clip(&self, durations: &[f32]) -> Option<SpriteAnimationClip> {
+        if self.from > self.to || self.to >= durations.len() {
+            return None;
+        }
+        let mut indices: Vec<usize> = (self.from..=self.to).collect();
+        if matches!(
+            self.direction,
+            TagDirection::Reverse | TagDirection::PingPongReverse
+        ) {
+            indices.reverse();
+        }
+        let ping_pong = matches!(
+            self.direction,
+            TagDirection::PingPong | TagDirection::PingPongReverse
+        );
+
+        let (indices, loop_mode) = if self.repeat == 0 {
+            let loop_mode = if ping_pong {
+                SpriteAnimationLoopMode::PingPong
+            } else {
+                SpriteAnimationLoopMode::Loop
+            };
+            (indices, loop_mode)
+        } else {
+            // The frames are repeated in the clip, which is then played once
+            let mut repeated = Vec::with_capacity(indices.len() * self.repeat as usize);
+            for pass in 0..self.repeat {
+                if ping_pong && pass % 2 == 1 {
+                    repeated.extend(indices.iter().rev().skip(1));
+                } else if ping_pong && pass > 0 {
+                    repeated.extend(indices.iter().skip(1));
+                } else {
+                    repeated.extend(&indices);
+                }
+            }
+            (repeated, SpriteAnimationLoopMode::Once)
+        };
+
+        Some(SpriteAnimationClip {
+            frames: indices
+                .into_iter()
+                .map(|index| SpriteAnimationFrame {
+                    index,
+                    duration: durations[index],
+                    events: Vec::new(),
+                })
+                .collect(),
+            loop_mode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(direction: TagDirection, repeat: u32) -> AnimationTag {
+        AnimationTag {
+            name: "tag".to_string(),
+            from: 1,
+            to: 3,
+            direction,
+            repeat,
+        }
+    }
+
+    fn indices(clip: &SpriteAnimationClip) -> Vec<usize> {
+        clip.frames.iter().map(|frame| frame.index).collect()
+    }
+
+    #[test]
+    fn tag_clips() {
+        let durations = [0.1, 0.2, 0.3, 0.4, 0.5];
+
+        let clip = tag(TagDirection::Forward, 0).clip(&durations).unwrap();
+        assert_eq!(indices(&clip), vec![1, 2, 3]);
+        assert_eq!(clip.loop_mode, SpriteAnimationLoopMode::Loop);
+        assert_eq!(clip.frames[2].duration, 0.4);
+
+        let clip = tag(TagDirection::Reverse, 2).clip(&durations).unwrap();
+        assert_eq!(indices(&clip), vec![3, 2, 1, 3, 2, 1]);
+        assert_eq!(clip.loop_mode, SpriteAnimationLoopMode::Once);
+
+        let clip = tag(TagDirection::PingPong, 0).clip(&durations).unwrap();
+        assert_eq!(indices(&clip), vec![1, 2, 3]);
+        assert_eq!(clip.loop_mode, SpriteAnimationLoopMode::PingPong);
+
+        let clip = tag(TagDirection::PingPongReverse, 3)
+            .clip(&durations)
+            .unwrap();
+        assert_eq!(indices(&clip), vec![3, 2, 1, 2, 3, 2, 1]);
+
+        assert!(tag(TagDirection::Forward, 0)
+            .clip(&durations[..3])
+            .is_none());
+    }
+}
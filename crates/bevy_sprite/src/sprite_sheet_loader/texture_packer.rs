@@ -0,0 +1,348 @@
+use super::{AnimationTag, TagDirection};
+use crate::{SpriteAnimation, SpriteAnimationClip, TextureAtlas};
+use bevy_asset::{AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset};
+use bevy_math::{Rect, Vec2};
+use bevy_render::texture::Image;
+use bevy_utils::HashMap;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
+use thiserror::Error;
+
+/// Loads the sprite sheets in the JSON format of TexturePacker, `.atlas.json`, as
+/// [`TextureAtlas`]es.
+///
+/// Both the "JSON (Hash)" and "JSON (Array)" formats are supported, which are also the formats
+/// of the sprite sheets exported by Aseprite. The image of the atlas is loaded from the path in
+/// the `meta` of the sprite sheet, relative to the sprite sheet.
+///
+/// The animations of the sprite sheet are loaded as the clips of a [`SpriteAnimation`] labeled
+/// `Animation`, so that `asset_server.load("player.atlas.json#Animation")` can be played by a
+/// [`SpriteAnimationPlayer`](crate::SpriteAnimationPlayer) on the atlas. These are the
+/// `animations` listing the names of their frames, as exported by TexturePacker, and the
+/// `frameTags` with the durations of the frames, as exported by Aseprite.
+///
+/// The trimmed frames are loaded without their offset in the original sprite: export the sprite
+/// sheets without trimming to keep the frames of an animation aligned.
+#[derive(Default)]
+pub struct TexturePackerLoader;
+
+/// Settings of the [`TexturePackerLoader`], read from the settings file of a sprite sheet.
+///
+/// For example, `player.atlas.json.meta` could contain `(frame_duration: 0.05)`. See
+/// [`LoadContext::settings`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct TexturePackerLoaderSettings {
+    /// The duration of the frames without a duration in the sprite sheet, in seconds.
+    pub frame_duration: f32,
+}
+
+impl Default for TexturePackerLoaderSettings {
+    fn default() -> Self {
+        Self {
+            frame_duration: 0.1,
+        }
+    }
+}
+
+/// An error that occurs when loading a TexturePacker sprite sheet.
+#[derive(Error, Debug)]
+pub enum TexturePackerError {
+    #[error("invalid sprite sheet: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("the animation {animation} refers to the missing frame {frame}")]
+    MissingFrame { animation: String, frame: String },
+    #[error("the tag {0} refers to missing frames")]
+    InvalidTag(String),
+}
+
+impl AssetLoader for TexturePackerLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+        Box::pin(async move {
+            let settings: TexturePackerLoaderSettings = load_context.settings()?;
+            let sprite_sheet: SpriteSheet = serde_json::from_slice(bytes)?;
+            let animation = sprite_sheet.animation(settings.frame_duration)?;
+
+            let image_path = load_context
+                .path()
+                .parent()
+                .unwrap()
+                .join(&sprite_sheet.meta.image);
+            let image_path = AssetPath::new(image_path, None);
+            let atlas = sprite_sheet.texture_atlas(load_context.get_handle(image_path.clone()));
+
+            load_context.set_labeled_asset("Animation", LoadedAsset::new(animation));
+            load_context.set_default_asset(LoadedAsset::new(atlas).with_dependency(image_path));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas.json"]
+    }
+}
+
+#[derive(Deserialize)]
+struct SpriteSheet {
+    frames: Frames,
+    meta: Meta,
+    /// The names of the frames of each animation, as exported by TexturePacker
+    #[serde(default)]
+    animations: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct Frame {
+    /// The name of the frame, in the "JSON (Array)" format
+    #[serde(default)]
+    filename: Option<String>,
+    frame: FrameRect,
+    #[serde(default)]
+    rotated: bool,
+    /// The duration of the frame in milliseconds, as exported by Aseprite
+    #[serde(default)]
+    duration: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct FrameRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct Meta {
+    image: String,
+    size: Size,
+    /// The tags of the frames, as exported by Aseprite
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<FrameTag>,
+}
+
+#[derive(Deserialize)]
+struct Size {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct FrameTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: TagDirection,
+    #[serde(default)]
+    repeat: Repeat,
+}
+
+/// How many times the frames of a tag are played, written as a string by Aseprite
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum Repeat {
+    #[default]
+    Endless,
+    Count(u32),
+    String(String),
+}
+
+impl Repeat {
+    fn count(&self) -> u32 {
+        match self {
+            Repeat::Endless => 0,
+            Repeat::Count(count) => *count,
+            Repeat::String(count) => count.parse().unwrap_or(0),
+        }
+    }
+}
+
+/// The frames of a sprite sheet with their names, in their order in the sprite sheet
+struct Frames(Vec<(String, Frame)>);
+
+impl<'de> Deserialize<'de> for Frames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = Frames;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array or a map of frames")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(frame) = seq.next_element::<Frame>()? {
+                    frames.push((frame.filename.clone().unwrap_or_default(), frame));
+                }
+                Ok(Frames(frames))
+            }
+
+            // The order of the map is kept, unlike with a `HashMap`
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(entry) = map.next_entry::<String, Frame>()? {
+                    frames.push(entry);
+                }
+                Ok(Frames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+impl SpriteSheet {
+    fn texture_atlas(&self, texture: Handle<Image>) -> TextureAtlas {
+        let mut atlas =
+            TextureAtlas::new_empty(texture, Vec2::new(self.meta.size.w, self.meta.size.h));
+        for (index, (_, frame)) in self.frames.0.iter().enumerate() {
+            let FrameRect { x, y, w, h } = frame.frame;
+            // The rotated frames are as high in the atlas as they are wide
+            let (w, h) = if frame.rotated { (h, w) } else { (w, h) };
+            atlas.add_texture(Rect::new(x, y, x + w, y + h));
+            if frame.rotated {
+                atlas.rotated_textures.insert(index);
+            }
+        }
+        atlas
+    }
+
+    fn animation(&self, frame_duration: f32) -> Result<SpriteAnimation, TexturePackerError> {
+        let durations: Vec<f32> = self
+            .frames
+            .0
+            .iter()
+            .map(|(_, frame)| {
+                frame
+                    .duration
+                    .map_or(frame_duration, |duration| duration / 1000.0)
+            })
+            .collect();
+
+        let mut animation = SpriteAnimation::default();
+        for (name, frame_names) in &self.animations {
+            let indices = frame_names
+                .iter()
+                .map(|frame_name| {
+                    self.frames
+                        .0
+                        .iter()
+                        .position(|(name, _)| name == frame_name)
+                        .ok_or_else(|| TexturePackerError::MissingFrame {
+                            animation: name.clone(),
+                            frame: frame_name.clone(),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut clip = SpriteAnimationClip::from_indices(indices, frame_duration);
+            for frame in &mut clip.frames {
+                frame.duration = durations[frame.index];
+            }
+            animation.clips.insert(name.clone(), clip);
+        }
+        for frame_tag in &self.meta.frame_tags {
+            let tag = AnimationTag {
+                name: frame_tag.name.clone(),
+                from: frame_tag.from,
+                to: frame_tag.to,
+                direction: frame_tag.direction,
+                repeat: frame_tag.repeat.count(),
+            };
+            let clip = tag
+                .clip(&durations)
+                .ok_or_else(|| TexturePackerError::InvalidTag(tag.name.clone()))?;
+            animation.clips.insert(tag.name, clip);
+        }
+        Ok(animation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpriteAnimationLoopMode;
+
+    #[test]
+    fn texture_packer_hash() {
+        let sprite_sheet: SpriteSheet = serde_json::from_str(
+            r#"{
+                "frames": {
+                    "run_2.png": { "frame": { "x": 0, "y": 0, "w": 16, "h": 32 }, "rotated": true },
+                    "run_1.png": { "frame": { "x": 32, "y": 0, "w": 16, "h": 32 }, "rotated": false }
+                },
+                "animations": { "run": ["run_1.png", "run_2.png"] },
+                "meta": { "image": "player.png", "size": { "w": 64, "h": 32 } }
+            }"#,
+        )
+        .unwrap();
+
+        let atlas = sprite_sheet.texture_atlas(Default::default());
+        assert_eq!(atlas.textures[0], Rect::new(0.0, 0.0, 32.0, 16.0));
+        assert_eq!(atlas.textures[1], Rect::new(32.0, 0.0, 48.0, 32.0));
+        assert!(atlas.is_rotated(0));
+        assert!(!atlas.is_rotated(1));
+
+        let animation = sprite_sheet.animation(0.2).unwrap();
+        let run = animation.get("run").unwrap();
+        assert_eq!(
+            run.frames
+                .iter()
+                .map(|frame| (frame.index, frame.duration))
+                .collect::<Vec<_>>(),
+            vec![(1, 0.2), (0, 0.2)]
+        );
+    }
+
+    #[test]
+    fn aseprite_array() {
+        let sprite_sheet: SpriteSheet = serde_json::from_str(
+            r#"{
+                "frames": [
+                    { "filename": "0", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "duration": 100 },
+                    { "filename": "1", "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "duration": 200 },
+                    { "filename": "2", "frame": { "x": 16, "y": 0, "w": 8, "h": 8 }, "duration": 300 }
+                ],
+                "meta": {
+                    "image": "player.png",
+                    "size": { "w": 24, "h": 8 },
+                    "frameTags": [
+                        { "name": "idle", "from": 0, "to": 1, "direction": "pingpong" },
+                        { "name": "jump", "from": 1, "to": 2, "direction": "reverse", "repeat": "1" }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let animation = sprite_sheet.animation(0.1).unwrap();
+        let idle = animation.get("idle").unwrap();
+        assert_eq!(idle.loop_mode, SpriteAnimationLoopMode::PingPong);
+        assert_eq!(idle.frames[1].duration, 0.2);
+        let jump = animation.get("jump").unwrap();
+        assert_eq!(jump.loop_mode, SpriteAnimationLoopMode::Once);
+        assert_eq!(jump.frames[0].index, 2);
+
+        let sprite_sheet: SpriteSheet = serde_json::from_str(
+            r#"{
+                "frames": [],
+                "animations": { "run": ["run_1.png"] },
+                "meta": { "image": "player.png", "size": { "w": 0, "h": 0 } }
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            sprite_sheet.animation(0.1),
+            Err(TexturePackerError::MissingFrame { .. })
+        ));
+    }
+}
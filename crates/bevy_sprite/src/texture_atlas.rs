@@ -4,7 +4,7 @@ use bevy_ecs::component::Component;
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 use bevy_render::{color::Color, texture::Image};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 
 /// An atlas containing multiple textures (like a spritesheet or a tilemap).
 /// [Example usage animating sprite.](https://github.com/bevyengine/bevy/blob/latest/examples/2d/sprite_sheet.rs)
@@ -20,6 +20,9 @@ pub struct TextureAtlas {
     /// The specific areas of the atlas where each texture can be found
     pub textures: Vec<Rect>,
     pub texture_handles: Option<HashMap<Handle<Image>, usize>>,
+    /// The indices of the textures rotated by 90 degrees clockwise in the atlas, to pack them
+    /// more tightly. Their area of the atlas is as high as they are wide.
+    pub rotated_textures: HashSet<usize>,
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
@@ -65,6 +68,7 @@ impl TextureAtlas {
             size: dimensions,
             texture_handles: None,
             textures: Vec::new(),
+            rotated_textures: HashSet::default(),
         }
     }
 
@@ -113,6 +117,7 @@ impl TextureAtlas {
             textures: sprites,
             texture,
             texture_handles: None,
+            rotated_textures: HashSet::default(),
         }
     }
 
@@ -142,4 +147,9 @@ impl TextureAtlas {
             .as_ref()
             .and_then(|texture_handles| texture_handles.get(texture).cloned())
     }
+
+    /// Whether the texture at `index` is rotated by 90 degrees clockwise in the atlas.
+    pub fn is_rotated(&self, index: usize) -> bool {
+        self.rotated_textures.contains(&index)
+    }
 }
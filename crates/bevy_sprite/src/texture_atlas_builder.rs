@@ -5,14 +5,14 @@ use bevy_render::{
     render_resource::{Extent3d, TextureDimension, TextureFormat},
     texture::{Image, TextureFormatPixelInfo},
 };
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use rectangle_pack::{
     contains_smallest_box, pack_rects, volume_heuristic, GroupedRectsToPlace, PackedLocation,
     RectToInsert, TargetBin,
 };
 use thiserror::Error;
 
-use crate::texture_atlas::TextureAtlas;
+use crate::{texture_atlas::TextureAtlas, IncrementalTextureAtlasBuilder};
 
 #[derive(Debug, Error)]
 pub enum TextureAtlasBuilderError {
@@ -20,6 +20,8 @@ pub enum TextureAtlasBuilderError {
     NotEnoughSpace,
     #[error("added a texture with the wrong format in an atlas")]
     WrongFormat,
+    #[error("added a texture that isn't loaded in an atlas")]
+    NotLoaded,
 }
 
 #[derive(Debug)]
@@ -30,6 +32,8 @@ pub struct TextureAtlasBuilder {
     /// The grouped rects which must be placed with a key value pair of a
     /// texture handle to an index.
     rects_to_place: GroupedRectsToPlace<Handle<Image>>,
+    /// The textures added to the builder, in order.
+    textures: Vec<Handle<Image>>,
     /// The initial atlas size in pixels.
     initial_size: Vec2,
     /// The absolute maximum size of the texture atlas in pixels.
@@ -38,16 +42,23 @@ pub struct TextureAtlasBuilder {
     format: TextureFormat,
     /// Enable automatic format conversion for textures if they are not in the atlas format.
     auto_format_conversion: bool,
+    /// The space in pixels between the textures in the atlas.
+    padding: u32,
+    /// Whether the textures can be rotated to fit in the atlas, in incremental mode.
+    allow_rotation: bool,
 }
 
 impl Default for TextureAtlasBuilder {
     fn default() -> Self {
         Self {
             rects_to_place: GroupedRectsToPlace::new(),
+            textures: Vec::new(),
             initial_size: Vec2::new(256., 256.),
             max_size: Vec2::new(2048., 2048.),
             format: TextureFormat::Rgba8UnormSrgb,
             auto_format_conversion: true,
+            padding: 0,
+            allow_rotation: false,
         }
     }
 }
@@ -79,14 +90,29 @@ impl TextureAtlasBuilder {
         self
     }
 
+    /// Sets the space in pixels between the textures in the atlas, to keep the pixels of the
+    /// neighbors of a texture from bleeding into it when it is filtered.
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Control whether the textures can be rotated by 90 degrees to fit in the atlas, see
+    /// [`TextureAtlas::is_rotated`]. Only used by [`TextureAtlasBuilder::finish_incremental`].
+    pub fn allow_rotation(mut self, allow_rotation: bool) -> Self {
+        self.allow_rotation = allow_rotation;
+        self
+    }
+
     /// Adds a texture to be copied to the texture atlas.
     pub fn add_texture(&mut self, texture_handle: Handle<Image>, texture: &Image) {
+        self.textures.push(texture_handle.clone());
         self.rects_to_place.push_rect(
             texture_handle,
             None,
             RectToInsert::new(
-                texture.texture_descriptor.size.width,
-                texture.texture_descriptor.size.height,
+                texture.texture_descriptor.size.width + self.padding,
+                texture.texture_descriptor.size.height + self.padding,
                 1,
             ),
         );
@@ -97,8 +123,8 @@ impl TextureAtlasBuilder {
         texture: &Image,
         packed_location: &PackedLocation,
     ) {
-        let rect_width = packed_location.width() as usize;
-        let rect_height = packed_location.height() as usize;
+        let rect_width = texture.texture_descriptor.size.width as usize;
+        let rect_height = texture.texture_descriptor.size.height as usize;
         let rect_x = packed_location.x() as usize;
         let rect_y = packed_location.y() as usize;
         let atlas_width = atlas_texture.texture_descriptor.size.width as usize;
@@ -211,11 +237,7 @@ impl TextureAtlasBuilder {
         for (texture_handle, (_, packed_location)) in rect_placements.packed_locations().iter() {
             let texture = textures.get(texture_handle).unwrap();
             let min = Vec2::new(packed_location.x() as f32, packed_location.y() as f32);
-            let max = min
-                + Vec2::new(
-                    packed_location.width() as f32,
-                    packed_location.height() as f32,
-                );
+            let max = min + texture.size();
             texture_handles.insert(texture_handle.clone_weak(), texture_rects.len());
             texture_rects.push(Rect { min, max });
             if texture.texture_descriptor.format != self.format && !self.auto_format_conversion {
@@ -235,6 +257,46 @@ impl TextureAtlasBuilder {
             texture: textures.add(atlas_texture),
             textures: texture_rects,
             texture_handles: Some(texture_handles),
+            rotated_textures: HashSet::default(),
         })
     }
+
+    /// Consumes the builder and returns an [`IncrementalTextureAtlasBuilder`], which packs
+    /// textures one at a time into atlases that are updated in place, for textures loaded at
+    /// runtime.
+    ///
+    /// The textures added to this builder are packed first, the largest first, into an atlas of
+    /// the initial size which grows up to the max size, after which more atlases are added.
+    ///
+    /// # Errors
+    ///
+    /// If a texture isn't loaded, is larger than the max size or has the wrong format, an error
+    /// will be returned.
+    pub fn finish_incremental(
+        self,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+    ) -> Result<IncrementalTextureAtlasBuilder, TextureAtlasBuilderError> {
+        let mut texture_handles = self.textures;
+        texture_handles.sort_by_cached_key(|texture_handle| {
+            std::cmp::Reverse(textures.get(texture_handle).map_or(0, |texture| {
+                texture.texture_descriptor.size.width * texture.texture_descriptor.size.height
+            }))
+        });
+
+        let mut builder = IncrementalTextureAtlasBuilder {
+            pages: Vec::new(),
+            initial_size: self.initial_size,
+            max_size: self.max_size,
+            format: self.format,
+            auto_format_conversion: self.auto_format_conversion,
+            padding: self.padding,
+            allow_rotation: self.allow_rotation,
+            placements: HashMap::default(),
+        };
+        for texture_handle in &texture_handles {
+            builder.add_texture(texture_handle, textures, texture_atlases)?;
+        }
+        Ok(builder)
+    }
 }
@@ -8,3 +8,19 @@ pub enum TextError {
     #[error("failed to add glyph to newly-created atlas {0:?}")]
     FailedToAddGlyph(GlyphId),
 }
+
+/// An error in the markup of a rich text, see
+/// [`RichTextBuilder::markup`](crate::RichTextBuilder::markup).
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum TextMarkupError {
+    #[error("unknown markup tag `{0}`")]
+    UnknownTag(String),
+    #[error("invalid value `{value}` for markup tag `{tag}`")]
+    InvalidValue { tag: String, value: String },
+    #[error("closing markup tag `{0}` does not match the last opened tag")]
+    UnexpectedClosingTag(String),
+    #[error("markup tag `{0}` is never closed")]
+    UnclosedTag(String),
+    #[error("markup tag starting at byte {0} is never terminated")]
+    UnterminatedTag(usize),
+}
@@ -1,21 +1,37 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use ab_glyph::{Font as _, FontArc, FontVec, InvalidFont, OutlinedGlyph};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     render_resource::{Extent3d, TextureDimension, TextureFormat},
     texture::Image,
 };
 
-#[derive(Debug, TypeUuid)]
+#[derive(Debug, Clone, TypeUuid)]
 #[uuid = "97059ac6-c9ba-4da9-95b6-bed82c3ce198"]
 pub struct Font {
     pub font: FontArc,
+    /// The index of the font in its data, which can be a collection of fonts.
+    pub face_index: u32,
 }
 
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
-        let font = FontVec::try_from_vec(font_data)?;
+        Self::try_from_bytes_and_index(font_data, 0)
+    }
+
+    /// Loads the font at the given index of a font collection, such as a `.ttc` file.
+    pub fn try_from_bytes_and_index(
+        font_data: Vec<u8>,
+        face_index: u32,
+    ) -> Result<Self, InvalidFont> {
+        let font = FontVec::try_from_vec_and_index(font_data, face_index)?;
         let font = FontArc::new(font);
-        Ok(Font { font })
+        Ok(Font { font, face_index })
+    }
+
+    /// Returns whether the font has a glyph for the character, instead of drawing a placeholder
+    /// for it.
+    pub fn has_glyph(&self, character: char) -> bool {
+        self.font.glyph_id(character).0 != 0
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
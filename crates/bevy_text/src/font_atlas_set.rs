@@ -1,9 +1,9 @@
-use crate::{error::TextError, Font, FontAtlas};
-use ab_glyph::{GlyphId, OutlinedGlyph, Point};
+use crate::{error::TextError, sdf_glyph_image, Font, FontAtlas, SubpixelOffset};
+use ab_glyph::{FontArc, GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
 use bevy_reflect::TypeUuid;
-use bevy_render::texture::Image;
+use bevy_render::texture::{Image, ImageSampler};
 use bevy_sprite::TextureAtlas;
 use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
@@ -14,6 +14,8 @@ type FontSizeKey = FloatOrd;
 #[uuid = "73ba778b-b6b5-4f45-982d-d21b6b86ace2"]
 pub struct FontAtlasSet {
     font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    /// The atlases of the signed distance fields of the glyphs, shared by all the font sizes.
+    sdf_font_atlases: Vec<FontAtlas>,
     // TODO unused, remove
     #[allow(dead_code)]
     queue: Vec<FontSizeKey>,
@@ -29,6 +31,7 @@ impl Default for FontAtlasSet {
     fn default() -> Self {
         FontAtlasSet {
             font_atlases: HashMap::with_capacity_and_hasher(1, Default::default()),
+            sdf_font_atlases: Vec::new(),
             queue: Vec::new(),
         }
     }
@@ -71,38 +74,16 @@ impl FontAtlasSet {
             });
 
         let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
-        let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
-            atlas.add_glyph(
-                textures,
-                texture_atlases,
-                glyph_id,
-                glyph_position.into(),
-                &glyph_texture,
-            )
-        };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
-            // Find the largest dimension of the glyph, either its width or its height
-            let glyph_max_size: u32 = glyph_texture
-                .texture_descriptor
-                .size
-                .height
-                .max(glyph_texture.texture_descriptor.size.width);
-            // Pick the higher  of 512 or the smallest power of 2 greater than glyph_max_size
-            let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512) as f32;
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                Vec2::new(containing, containing),
-            ));
-            if !font_atlases.last_mut().unwrap().add_glyph(
-                textures,
-                texture_atlases,
-                glyph_id,
-                glyph_position.into(),
-                &glyph_texture,
-            ) {
-                return Err(TextError::FailedToAddGlyph(glyph_id));
-            }
+        if !add_glyph_texture(
+            font_atlases,
+            textures,
+            texture_atlases,
+            glyph_id,
+            glyph_position.into(),
+            &glyph_texture,
+            false,
+        ) {
+            return Err(TextError::FailedToAddGlyph(glyph_id));
         }
 
         Ok(self
@@ -133,7 +114,98 @@ impl FontAtlasSet {
             })
     }
 
+    /// Adds the signed distance field of a glyph, rasterized at [`SDF_FONT_SIZE`], to the atlases
+    /// drawing the glyph at any size.
+    pub fn add_sdf_glyph_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: &mut Assets<Image>,
+        font: &FontArc,
+        glyph_id: GlyphId,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        let glyph_texture =
+            sdf_glyph_image(font, glyph_id).ok_or(TextError::FailedToAddGlyph(glyph_id))?;
+        if !add_glyph_texture(
+            &mut self.sdf_font_atlases,
+            textures,
+            texture_atlases,
+            glyph_id,
+            SubpixelOffset::from(Point::default()),
+            &glyph_texture,
+            true,
+        ) {
+            return Err(TextError::FailedToAddGlyph(glyph_id));
+        }
+
+        Ok(self.get_sdf_glyph_atlas_info(glyph_id).unwrap())
+    }
+
+    pub fn get_sdf_glyph_atlas_info(&self, glyph_id: GlyphId) -> Option<GlyphAtlasInfo> {
+        self.sdf_font_atlases.iter().find_map(|atlas| {
+            atlas
+                .get_glyph_index(glyph_id, SubpixelOffset::from(Point::default()))
+                .map(|glyph_index| GlyphAtlasInfo {
+                    texture_atlas: atlas.texture_atlas.clone_weak(),
+                    glyph_index,
+                })
+        })
+    }
+
     pub fn num_font_atlases(&self) -> usize {
         self.font_atlases.len()
     }
 }
+
+/// Adds the texture of a glyph to the first of the atlases with room for it, or to a new atlas
+/// large enough for it.
+///
+/// The atlases of signed distance fields are sampled linearly, to interpolate the distances.
+fn add_glyph_texture(
+    font_atlases: &mut Vec<FontAtlas>,
+    textures: &mut Assets<Image>,
+    texture_atlases: &mut Assets<TextureAtlas>,
+    glyph_id: GlyphId,
+    subpixel_offset: SubpixelOffset,
+    glyph_texture: &Image,
+    sdf: bool,
+) -> bool {
+    let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
+        atlas.add_glyph(
+            textures,
+            texture_atlases,
+            glyph_id,
+            subpixel_offset,
+            glyph_texture,
+        )
+    };
+    if font_atlases.iter_mut().any(add_char_to_font_atlas) {
+        return true;
+    }
+
+    // Find the largest dimension of the glyph, either its width or its height
+    let glyph_max_size: u32 = glyph_texture
+        .texture_descriptor
+        .size
+        .height
+        .max(glyph_texture.texture_descriptor.size.width);
+    // Pick the higher  of 512 or the smallest power of 2 greater than glyph_max_size
+    let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512) as f32;
+    let mut font_atlas =
+        FontAtlas::new(textures, texture_atlases, Vec2::new(containing, containing));
+    if sdf {
+        let texture_atlas = texture_atlases.get(&font_atlas.texture_atlas).unwrap();
+        textures
+            .get_mut(&texture_atlas.texture)
+            .unwrap()
+            .sampler_descriptor = ImageSampler::linear();
+    }
+    let added = font_atlas.add_glyph(
+        textures,
+        texture_atlases,
+        glyph_id,
+        subpixel_offset,
+        glyph_texture,
+    );
+    font_atlases.push(font_atlas);
+    added
+}
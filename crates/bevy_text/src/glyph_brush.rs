@@ -9,8 +9,8 @@ use glyph_brush_layout::{
 };
 
 use crate::{
-    error::TextError, Font, FontAtlasSet, FontAtlasWarning, GlyphAtlasInfo, TextAlignment,
-    TextSettings, YAxisOrientation,
+    error::TextError, sdf_glyph_rect, Font, FontAtlasSet, FontAtlasWarning, GlyphAtlasInfo,
+    TextAlignment, TextSettings, YAxisOrientation,
 };
 
 pub struct GlyphBrush {
@@ -46,11 +46,14 @@ impl GlyphBrush {
         Ok(section_glyphs)
     }
 
+    /// Adds the glyphs to the font atlases and positions them, drawing the glyphs of the sections
+    /// flagged in `sdf_sections` from the atlases of signed distance fields.
     #[allow(clippy::too_many_arguments)]
     pub fn process_glyphs(
         &self,
         glyphs: Vec<SectionGlyph>,
         sections: &[SectionText],
+        sdf_sections: &[bool],
         font_atlas_set_storage: &mut Assets<FontAtlasSet>,
         fonts: &Assets<Font>,
         texture_atlases: &mut Assets<TextureAtlas>,
@@ -105,48 +108,80 @@ impl GlyphBrush {
             let glyph_position = glyph.position;
             let adjust = GlyphPlacementAdjuster::new(&mut glyph);
             let section_data = sections_data[sg.section_index];
-            if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
-                let bounds = outlined_glyph.px_bounds();
-                let handle_font_atlas: Handle<FontAtlasSet> = section_data.0.cast_weak();
-                let font_atlas_set = font_atlas_set_storage
-                    .get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
-
-                let atlas_info = font_atlas_set
+            let font = &section_data.1.font;
+            let sdf = sdf_sections[sg.section_index];
+            // The signed distance fields of the glyphs are padded, and scaled to the glyphs
+            let (bounds, outlined_glyph) = if sdf {
+                (sdf_glyph_rect(font, &glyph), None)
+            } else {
+                let outlined_glyph = font.outline_glyph(glyph);
+                (
+                    outlined_glyph
+                        .as_ref()
+                        .map(|outlined_glyph| outlined_glyph.px_bounds()),
+                    outlined_glyph,
+                )
+            };
+            let Some(bounds) = bounds else {
+                continue;
+            };
+
+            let handle_font_atlas: Handle<FontAtlasSet> = section_data.0.cast_weak();
+            let font_atlas_set =
+                font_atlas_set_storage.get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
+
+            let atlas_info = match outlined_glyph {
+                Some(outlined_glyph) => font_atlas_set
                     .get_glyph_atlas_info(section_data.2, glyph_id, glyph_position)
                     .map(Ok)
                     .unwrap_or_else(|| {
                         font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
-                    })?;
-
-                if !text_settings.allow_dynamic_font_size
-                    && !font_atlas_warning.warned
-                    && font_atlas_set.num_font_atlases() > text_settings.max_font_atlases.get()
-                {
-                    warn!("warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer.", text_settings.max_font_atlases.get());
-                    font_atlas_warning.warned = true;
-                }
+                    })?,
+                None => font_atlas_set
+                    .get_sdf_glyph_atlas_info(glyph_id)
+                    .map(Ok)
+                    .unwrap_or_else(|| {
+                        font_atlas_set.add_sdf_glyph_to_atlas(
+                            texture_atlases,
+                            textures,
+                            font,
+                            glyph_id,
+                        )
+                    })?,
+            };
+
+            if !text_settings.allow_dynamic_font_size
+                && !font_atlas_warning.warned
+                && font_atlas_set.num_font_atlases() > text_settings.max_font_atlases.get()
+            {
+                warn!("warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer.", text_settings.max_font_atlases.get());
+                font_atlas_warning.warned = true;
+            }
 
+            let size = if sdf {
+                Vec2::new(bounds.width(), bounds.height())
+            } else {
                 let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
                 let glyph_rect = texture_atlas.textures[atlas_info.glyph_index];
-                let size = Vec2::new(glyph_rect.width(), glyph_rect.height());
+                Vec2::new(glyph_rect.width(), glyph_rect.height())
+            };
 
-                let x = bounds.min.x + size.x / 2.0 - min_x;
+            let x = bounds.min.x + size.x / 2.0 - min_x;
 
-                let y = match y_axis_orientation {
-                    YAxisOrientation::BottomToTop => max_y - bounds.max.y + size.y / 2.0,
-                    YAxisOrientation::TopToBottom => bounds.min.y + size.y / 2.0 - min_y,
-                };
+            let y = match y_axis_orientation {
+                YAxisOrientation::BottomToTop => max_y - bounds.max.y + size.y / 2.0,
+                YAxisOrientation::TopToBottom => bounds.min.y + size.y / 2.0 - min_y,
+            };
 
-                let position = adjust.position(Vec2::new(x, y));
+            let position = adjust.position(Vec2::new(x, y));
 
-                positioned_glyphs.push(PositionedGlyph {
-                    position,
-                    size,
-                    atlas_info,
-                    section_index: sg.section_index,
-                    byte_index,
-                });
-            }
+            positioned_glyphs.push(PositionedGlyph {
+                position,
+                size,
+                atlas_info,
+                section_index: sg.section_index,
+                byte_index,
+            });
         }
         Ok(positioned_glyphs)
     }
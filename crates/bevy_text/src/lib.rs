@@ -4,9 +4,16 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
+mod measure;
 mod pipeline;
+mod rich_text;
+mod sdf;
+mod shaping;
+#[cfg(feature = "system_fonts")]
+mod system_fonts;
 mod text;
 mod text2d;
+mod text3d;
 
 pub use error::*;
 pub use font::*;
@@ -14,19 +21,28 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
+pub use measure::*;
 pub use pipeline::*;
+pub use rich_text::*;
+pub use sdf::*;
+#[cfg(feature = "system_fonts")]
+pub use system_fonts::*;
 pub use text::*;
 pub use text2d::*;
+pub use text3d::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, Text, Text2dBundle, TextAlignment, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, FontFamily, RichTextBuilder, Text, Text2dBundle, Text3d, TextAlignment, TextError,
+        TextOutline, TextRendering, TextSection, TextShadow, TextSpanStyle, TextStyle,
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Handle};
 use bevy_ecs::{schedule::IntoSystemDescriptor, system::Resource};
-use bevy_render::{camera::CameraUpdateSystem, RenderApp, RenderStage};
+use bevy_render::{camera::CameraUpdateSystem, view::VisibilitySystems, RenderApp, RenderStage};
 use bevy_sprite::SpriteSystem;
 use bevy_window::ModifiesWindows;
 use std::num::NonZeroUsize;
@@ -74,8 +90,15 @@ impl Plugin for TextPlugin {
             .register_type::<TextSection>()
             .register_type::<Vec<TextSection>>()
             .register_type::<TextStyle>()
+            .register_type::<Vec<Handle<Font>>>()
+            .register_type::<TextShadow>()
+            .register_type::<Option<TextShadow>>()
+            .register_type::<TextOutline>()
+            .register_type::<Option<TextOutline>>()
+            .register_type::<TextRendering>()
             .register_type::<Text>()
             .register_type::<TextAlignment>()
+            .register_type::<Text3d>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<TextSettings>()
             .init_resource::<FontAtlasWarning>()
@@ -89,8 +112,15 @@ impl Plugin for TextPlugin {
                     // will only ever observe its own render target, and `update_text2d_layout`
                     // will never modify a pre-existing `Image` asset.
                     .ambiguous_with(CameraUpdateSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_text3d_meshes.before(VisibilitySystems::CalculateBounds),
             );
 
+        #[cfg(feature = "system_fonts")]
+        app.init_resource::<SystemFonts>();
+
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_system_to_stage(
                 RenderStage::Extract,
@@ -0,0 +1,301 @@
+use ab_glyph::{Font as _, FontArc, PxScale, ScaleFont as _};
+use bevy_asset::{Assets, HandleId};
+use bevy_math::{Rect, Vec2};
+use glyph_brush_layout::{
+    FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph, SectionText,
+};
+use std::ops::Range;
+
+use crate::{
+    font_runs, scale_value,
+    shaping::{needs_shaping, shape_text},
+    Font, TextAlignment, TextError, TextSection,
+};
+
+/// The sections of a text with their fonts, to lay it out and measure it for any bounds without
+/// rasterizing its glyphs.
+///
+/// The text is measured in physical pixels, at the scale factor it is created with.
+#[derive(Clone, Debug)]
+pub struct TextMeasureInfo {
+    fonts: Vec<Font>,
+    runs: Vec<MeasuredRun>,
+    needs_shaping: bool,
+    alignment: TextAlignment,
+    /// The size of the text wrapped at each of its break opportunities, its narrowest layout
+    pub min_width_content_size: Vec2,
+    /// The size of the text only wrapped at its line breaks, its widest layout
+    pub max_width_content_size: Vec2,
+}
+
+/// A run of characters of a section drawn with the same font, like in
+/// [`TextPipeline::queue_text`](crate::TextPipeline::queue_text).
+#[derive(Clone, Debug)]
+struct MeasuredRun {
+    section_index: usize,
+    /// The index of the first byte of the run in its section
+    byte_offset: usize,
+    text: String,
+    /// The index of the font of the run in [`TextMeasureInfo::fonts`]
+    font_index: usize,
+    scale: PxScale,
+}
+
+/// The layout of a text measured by [`TextMeasureInfo::measure`], in physical pixels relative to
+/// the top left corner of the text, with the Y axis pointing down.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextMeasurement {
+    /// The size of the text, from the ascent of its first line to the descent of its last one
+    pub size: Vec2,
+    /// The lines of the text, from top to bottom
+    pub lines: Vec<TextLineBox>,
+    /// The glyphs of the text, line by line from left to right
+    pub glyphs: Vec<GlyphMetrics>,
+}
+
+/// A line of a [`TextMeasurement`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLineBox {
+    /// The box of the line, from the left of its first glyph to the advance of its last one and
+    /// from its highest ascent to its lowest descent
+    pub rect: Rect,
+    /// The position of the baseline of the line from the top of the text
+    pub baseline: f32,
+    /// The range of the glyphs of the line in [`TextMeasurement::glyphs`]
+    pub glyphs: Range<usize>,
+}
+
+/// A glyph of a [`TextMeasurement`], whitespace included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphMetrics {
+    /// The index of the section of the glyph in the text
+    pub section_index: usize,
+    /// The index of the first byte of the characters of the glyph in its section
+    pub byte_index: usize,
+    /// The box of the glyph, from its origin to its advance and from the ascent to the descent of
+    /// its font
+    pub rect: Rect,
+}
+
+impl TextMeasureInfo {
+    /// Gathers the fonts of the sections, and measures the narrowest and widest layouts of the
+    /// text. Fails with [`TextError::NoSuchFont`] if a font of the sections isn't loaded yet.
+    pub fn from_sections(
+        fonts: &Assets<Font>,
+        sections: &[TextSection],
+        scale_factor: f64,
+        alignment: TextAlignment,
+    ) -> Result<Self, TextError> {
+        let mut font_ids: Vec<HandleId> = Vec::new();
+        let mut measure_fonts = Vec::new();
+        let mut runs = Vec::new();
+        for (section_index, section) in sections.iter().enumerate() {
+            let section_fonts = std::iter::once(&section.style.font)
+                .chain(&section.style.fallback_fonts)
+                .map(|handle| {
+                    let font = fonts.get(handle).ok_or(TextError::NoSuchFont);
+                    font.map(|font| (handle.id(), font))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let font_size = scale_value(section.style.font_size, scale_factor);
+
+            for run in font_runs(&section.value, section_fonts.len(), |index, character| {
+                section_fonts[index].1.has_glyph(character)
+            }) {
+                let (font_id, font) = section_fonts[run.font_index];
+                let font_index = match font_ids.iter().position(|id| *id == font_id) {
+                    Some(font_index) => font_index,
+                    None => {
+                        font_ids.push(font_id);
+                        measure_fonts.push(font.clone());
+                        measure_fonts.len() - 1
+                    }
+                };
+                runs.push(MeasuredRun {
+                    section_index,
+                    byte_offset: run.range.start,
+                    text: section.value[run.range].to_string(),
+                    font_index,
+                    scale: PxScale::from(font_size),
+                });
+            }
+        }
+
+        let mut info = Self {
+            fonts: measure_fonts,
+            runs,
+            needs_shaping: sections.iter().any(|section| needs_shaping(&section.value)),
+            alignment,
+            min_width_content_size: Vec2::ZERO,
+            max_width_content_size: Vec2::ZERO,
+        };
+        info.min_width_content_size = info.compute_size(Vec2::new(0.0, f32::MAX));
+        info.max_width_content_size = info.compute_size(Vec2::splat(f32::MAX));
+        Ok(info)
+    }
+
+    /// The size of the text laid out in the bounds.
+    pub fn compute_size(&self, bounds: Vec2) -> Vec2 {
+        self.measure(bounds).size
+    }
+
+    /// Lays out the text in the bounds, and returns the boxes of its lines and glyphs.
+    pub fn measure(&self, bounds: Vec2) -> TextMeasurement {
+        let section_texts: Vec<SectionText> = self
+            .runs
+            .iter()
+            .map(|run| SectionText {
+                text: &run.text,
+                scale: run.scale,
+                font_id: FontId(run.font_index),
+            })
+            .collect();
+
+        // Only the texts needing it are shaped, the others are laid out one glyph per character
+        let shaped_glyphs = if self.needs_shaping {
+            let run_fonts: Vec<&Font> = self
+                .runs
+                .iter()
+                .map(|run| &self.fonts[run.font_index])
+                .collect();
+            shape_text(&section_texts, &run_fonts, bounds, self.alignment)
+        } else {
+            None
+        };
+        let section_glyphs = shaped_glyphs.unwrap_or_else(|| {
+            let fonts: Vec<&FontArc> = self.fonts.iter().map(|font| &font.font).collect();
+            let geom = SectionGeometry {
+                bounds: (bounds.x, bounds.y),
+                ..Default::default()
+            };
+            Layout::default()
+                .h_align(self.alignment.into())
+                .calculate_glyphs(&fonts, &geom, &section_texts)
+        });
+
+        self.glyph_metrics(&section_glyphs)
+    }
+
+    /// Gathers the laid out glyphs in lines, relative to the top left corner of the text.
+    fn glyph_metrics(&self, section_glyphs: &[SectionGlyph]) -> TextMeasurement {
+        if section_glyphs.is_empty() {
+            return TextMeasurement::default();
+        }
+
+        let mut glyphs: Vec<GlyphMetrics> = Vec::with_capacity(section_glyphs.len());
+        let mut lines: Vec<TextLineBox> = Vec::new();
+        for section_glyph in section_glyphs {
+            let run = &self.runs[section_glyph.section_index];
+            let font = self.fonts[run.font_index].font.as_scaled(run.scale);
+            let glyph = &section_glyph.glyph;
+            let rect = Rect::new(
+                glyph.position.x,
+                glyph.position.y - font.ascent(),
+                glyph.position.x + font.h_advance(glyph.id),
+                glyph.position.y - font.descent(),
+            );
+            // The glyphs of a line share its baseline
+            match lines.last_mut() {
+                Some(line) if line.baseline == glyph.position.y => {
+                    line.rect = line.rect.union(rect);
+                    line.glyphs.end += 1;
+                }
+                _ => lines.push(TextLineBox {
+                    rect,
+                    baseline: glyph.position.y,
+                    glyphs: glyphs.len()..glyphs.len() + 1,
+                }),
+            }
+            glyphs.push(GlyphMetrics {
+                section_index: run.section_index,
+                byte_index: run.byte_offset + section_glyph.byte_index,
+                rect,
+            });
+        }
+
+        let bounds = lines
+            .iter()
+            .skip(1)
+            .fold(lines[0].rect, |bounds, line| bounds.union(line.rect));
+        let offset = bounds.min;
+        for glyph in &mut glyphs {
+            glyph.rect = offset_rect(glyph.rect, -offset);
+        }
+        for line in &mut lines {
+            line.rect = offset_rect(line.rect, -offset);
+            line.baseline -= offset.y;
+        }
+        TextMeasurement {
+            size: bounds.size(),
+            lines,
+            glyphs,
+        }
+    }
+}
+
+fn offset_rect(rect: Rect, offset: Vec2) -> Rect {
+    Rect {
+        min: rect.min + offset,
+        max: rect.max + offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure_info(text: &str, alignment: TextAlignment) -> (TextMeasureInfo, f32) {
+        let font = Font::try_from_bytes(
+            include_bytes!("../../../assets/fonts/FiraMono-Medium.ttf").to_vec(),
+        )
+        .unwrap();
+        let advance = font.font.as_scaled(20.0).h_advance(font.font.glyph_id('a'));
+        let info = TextMeasureInfo {
+            fonts: vec![font],
+            runs: vec![MeasuredRun {
+                section_index: 0,
+                byte_offset: 0,
+                text: text.to_string(),
+                font_index: 0,
+                scale: PxScale::from(20.0),
+            }],
+            needs_shaping: false,
+            alignment,
+            min_width_content_size: Vec2::ZERO,
+            max_width_content_size: Vec2::ZERO,
+        };
+        (info, advance)
+    }
+
+    #[test]
+    fn measure_lines_and_glyphs() {
+        let (info, advance) = measure_info("ab cd\nef", TextAlignment::Center);
+        let measurement = info.measure(Vec2::splat(f32::MAX));
+        assert_eq!(measurement.lines.len(), 2);
+        assert!((measurement.size.x - 5.0 * advance).abs() < 1e-3);
+        let line_height = measurement.lines[0].rect.height();
+        assert!((measurement.size.y - 2.0 * line_height).abs() < 1e-3);
+
+        // The second line is centered under the first one
+        let second_line = &measurement.lines[1];
+        let second_glyphs = &measurement.glyphs[second_line.glyphs.clone()];
+        assert_eq!(second_glyphs[0].byte_index, 6);
+        assert!((second_glyphs[0].rect.min.x - 1.5 * advance).abs() < 1e-3);
+        assert!((second_line.rect.min.y - line_height).abs() < 1e-3);
+    }
+
+    #[test]
+    fn measure_wrapped_text() {
+        let (info, advance) = measure_info("ab cd ef", TextAlignment::Left);
+        // Wrapped at every opportunity, each word is on its own line
+        let narrowest = info.measure(Vec2::new(0.0, f32::MAX));
+        assert_eq!(narrowest.lines.len(), 3);
+        let widest = info.measure(Vec2::splat(f32::MAX));
+        assert_eq!(widest.lines.len(), 1);
+        assert!((widest.size.x - 8.0 * advance).abs() < 1e-3);
+        // Two words fit in the width of five characters
+        let wrapped = info.measure(Vec2::new(5.0 * advance + 0.1, f32::MAX));
+        assert_eq!(wrapped.lines.len(), 2);
+        assert_eq!(wrapped.glyphs[wrapped.lines[1].glyphs.start].byte_index, 6);
+    }
+}
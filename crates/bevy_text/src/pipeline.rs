@@ -6,12 +6,17 @@ use bevy_math::Vec2;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlas;
 use bevy_utils::HashMap;
+use std::ops::Range;
 
 use glyph_brush_layout::{FontId, SectionText};
 
 use crate::{
-    error::TextError, glyph_brush::GlyphBrush, scale_value, Font, FontAtlasSet, FontAtlasWarning,
-    PositionedGlyph, TextAlignment, TextSection, TextSettings, YAxisOrientation,
+    error::TextError,
+    glyph_brush::GlyphBrush,
+    scale_value,
+    shaping::{needs_shaping, shape_text},
+    Font, FontAtlasSet, FontAtlasWarning, PositionedGlyph, TextAlignment, TextRendering,
+    TextSection, TextSettings, YAxisOrientation,
 };
 
 #[derive(Default, Resource)]
@@ -54,30 +59,53 @@ impl TextPipeline {
         y_axis_orientation: YAxisOrientation,
     ) -> Result<TextLayoutInfo, TextError> {
         let mut scaled_fonts = Vec::new();
-        let sections = sections
-            .iter()
-            .map(|section| {
-                let font = fonts
-                    .get(&section.style.font)
-                    .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
-                let font_size = scale_value(section.style.font_size, scale_factor);
+        // The sections are split in runs of characters sharing a font, among the fallbacks of
+        // their style, laid out as sections of their own
+        let mut runs = Vec::new();
+        let mut run_fonts = Vec::new();
+        let mut run_sdf = Vec::new();
+        let mut section_texts = Vec::new();
+        for (section_index, section) in sections.iter().enumerate() {
+            let section_fonts = std::iter::once(&section.style.font)
+                .chain(&section.style.fallback_fonts)
+                .map(|handle| {
+                    let font = fonts.get(handle).ok_or(TextError::NoSuchFont);
+                    font.map(|font| (handle, font))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let font_size = scale_value(section.style.font_size, scale_factor);
+
+            for run in font_runs(&section.value, section_fonts.len(), |index, character| {
+                section_fonts[index].1.has_glyph(character)
+            }) {
+                let (handle, font) = section_fonts[run.font_index];
+                let font_id = self.get_or_insert_font_id(handle, font);
 
                 scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
 
-                let section = SectionText {
+                section_texts.push(SectionText {
                     font_id,
                     scale: PxScale::from(font_size),
-                    text: &section.value,
-                };
-
-                Ok(section)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+                    text: &section.value[run.range.clone()],
+                });
+                runs.push((section_index, run.range.start));
+                run_fonts.push(font);
+                run_sdf.push(matches!(section.style.rendering, TextRendering::Sdf { .. }));
+            }
+        }
 
-        let section_glyphs = self
-            .brush
-            .compute_glyphs(&sections, bounds, text_alignment)?;
+        // Only the texts needing it are shaped, the others are laid out one glyph per character
+        let shaped_glyphs = if sections.iter().any(|section| needs_shaping(&section.value)) {
+            shape_text(&section_texts, &run_fonts, bounds, text_alignment)
+        } else {
+            None
+        };
+        let section_glyphs = match shaped_glyphs {
+            Some(section_glyphs) => section_glyphs,
+            None => self
+                .brush
+                .compute_glyphs(&section_texts, bounds, text_alignment)?,
+        };
 
         if section_glyphs.is_empty() {
             return Ok(TextLayoutInfo::default());
@@ -99,9 +127,10 @@ impl TextPipeline {
 
         let size = Vec2::new(max_x - min_x, max_y - min_y);
 
-        let glyphs = self.brush.process_glyphs(
+        let mut glyphs = self.brush.process_glyphs(
             section_glyphs,
-            &sections,
+            &section_texts,
+            &run_sdf,
             font_atlas_set_storage,
             fonts,
             texture_atlases,
@@ -111,6 +140,96 @@ impl TextPipeline {
             y_axis_orientation,
         )?;
 
+        // Point the glyphs back to the sections their runs were split from
+        for glyph in &mut glyphs {
+            let (section_index, byte_offset) = runs[glyph.section_index];
+            glyph.section_index = section_index;
+            glyph.byte_index += byte_offset;
+        }
+
         Ok(TextLayoutInfo { glyphs, size })
     }
 }
+
+/// A run of characters of a text drawn with the same font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FontRun {
+    /// The index of the font among the fonts of the text
+    pub font_index: usize,
+    /// The range of the run in the text, in bytes
+    pub range: Range<usize>,
+}
+
+/// Splits a text in runs of characters drawn with the first of `font_count` fonts having a glyph
+/// for them. Whitespace, and characters missing from all the fonts, stay in the current run.
+pub(crate) fn font_runs(
+    text: &str,
+    font_count: usize,
+    has_glyph: impl Fn(usize, char) -> bool,
+) -> Vec<FontRun> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current = 0;
+    if font_count > 1 {
+        for (index, character) in text.char_indices() {
+            if character.is_whitespace() || character.is_control() {
+                continue;
+            }
+            let font_index = (0..font_count).find(|&font_index| has_glyph(font_index, character));
+            match font_index {
+                Some(font_index) if font_index != current => {
+                    if index > start {
+                        runs.push(FontRun {
+                            font_index: current,
+                            range: start..index,
+                        });
+                    }
+                    start = index;
+                    current = font_index;
+                }
+                _ => {}
+            }
+        }
+    }
+    runs.push(FontRun {
+        font_index: current,
+        range: start..text.len(),
+    });
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_font_runs() {
+        // The first font only has latin letters, the second one digits and latin letters, and
+        // the last one everything
+        let has_glyph = |font_index, character: char| match font_index {
+            0 => character.is_ascii_alphabetic(),
+            1 => character.is_ascii_alphanumeric(),
+            _ => true,
+        };
+        let runs = |text| {
+            font_runs(text, 3, has_glyph)
+                .into_iter()
+                .map(|run| (run.font_index, &text[run.range]))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(runs(""), vec![(0, "")]);
+        assert_eq!(runs("abc def"), vec![(0, "abc def")]);
+        assert_eq!(
+            runs("abc 123 \u{4e16}\u{754c}!x"),
+            vec![(0, "abc "), (1, "123 "), (2, "\u{4e16}\u{754c}!"), (0, "x")]
+        );
+        assert_eq!(runs("12 ab"), vec![(1, "12 "), (0, "ab")]);
+        assert_eq!(
+            font_runs("1", 1, has_glyph),
+            vec![FontRun {
+                font_index: 0,
+                range: 0..1
+            }]
+        );
+    }
+}
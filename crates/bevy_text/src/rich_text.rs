@@ -0,0 +1,487 @@
+use bevy_asset::Handle;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_render::color::Color;
+
+use crate::{
+    Font, Text, TextMarkupError, TextOutline, TextRendering, TextSection, TextShadow, TextStyle,
+};
+
+/// The fonts of a family, used to resolve the bold and italic inline styles of rich text.
+///
+/// Variants missing from the family fall back to the closest available one, down to the
+/// `regular` font.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub struct FontFamily {
+    pub regular: Handle<Font>,
+    pub bold: Option<Handle<Font>>,
+    pub italic: Option<Handle<Font>>,
+    pub bold_italic: Option<Handle<Font>>,
+    /// The fonts drawing the characters missing from the variants of the family, see
+    /// [`TextStyle::fallback_fonts`].
+    pub fallbacks: Vec<Handle<Font>>,
+}
+
+impl FontFamily {
+    /// Creates a family only made of its regular font.
+    pub fn new(regular: Handle<Font>) -> Self {
+        Self {
+            regular,
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`FontFamily`] with a bold font.
+    pub fn with_bold(mut self, bold: Handle<Font>) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Returns this [`FontFamily`] with an italic font.
+    pub fn with_italic(mut self, italic: Handle<Font>) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Returns this [`FontFamily`] with a bold and italic font.
+    pub fn with_bold_italic(mut self, bold_italic: Handle<Font>) -> Self {
+        self.bold_italic = Some(bold_italic);
+        self
+    }
+
+    /// Returns this [`FontFamily`] with another fallback font, tried after the previous ones.
+    pub fn with_fallback(mut self, fallback: Handle<Font>) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
+
+    /// The font of the family to use for the given variant.
+    pub fn get(&self, bold: bool, italic: bool) -> &Handle<Font> {
+        match (bold, italic) {
+            (true, true) => self
+                .bold_italic
+                .as_ref()
+                .or(self.bold.as_ref())
+                .or(self.italic.as_ref()),
+            (true, false) => self.bold.as_ref(),
+            (false, true) => self.italic.as_ref(),
+            (false, false) => None,
+        }
+        .unwrap_or(&self.regular)
+    }
+}
+
+/// Inline style changes of a span of rich text, applied on top of the style of the enclosing
+/// span. Fields left to `None` are inherited.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextSpanStyle {
+    /// Replaces the font of the span, instead of picking it from the [`FontFamily`].
+    pub font: Option<Handle<Font>>,
+    pub font_size: Option<f32>,
+    pub color: Option<Color>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub shadow: Option<TextShadow>,
+    pub outline: Option<TextOutline>,
+}
+
+impl TextSpanStyle {
+    /// A span using the bold font of the family.
+    pub fn bold() -> Self {
+        Self {
+            bold: Some(true),
+            ..Default::default()
+        }
+    }
+
+    /// A span using the italic font of the family.
+    pub fn italic() -> Self {
+        Self {
+            italic: Some(true),
+            ..Default::default()
+        }
+    }
+
+    /// A span with another color.
+    pub fn color(color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    /// A span with another font size.
+    pub fn font_size(font_size: f32) -> Self {
+        Self {
+            font_size: Some(font_size),
+            ..Default::default()
+        }
+    }
+
+    /// A span with another font, outside of the family.
+    pub fn font(font: Handle<Font>) -> Self {
+        Self {
+            font: Some(font),
+            ..Default::default()
+        }
+    }
+
+    /// A span with a drop shadow.
+    pub fn shadow(shadow: TextShadow) -> Self {
+        Self {
+            shadow: Some(shadow),
+            ..Default::default()
+        }
+    }
+
+    /// A span with an outline.
+    pub fn outline(outline: TextOutline) -> Self {
+        Self {
+            outline: Some(outline),
+            ..Default::default()
+        }
+    }
+}
+
+/// The resolved style of the current span of a [`RichTextBuilder`].
+#[derive(Clone, Debug)]
+struct SpanState {
+    font: Option<Handle<Font>>,
+    font_size: f32,
+    color: Color,
+    bold: bool,
+    italic: bool,
+    shadow: Option<TextShadow>,
+    outline: Option<TextOutline>,
+}
+
+impl SpanState {
+    fn with(&self, style: TextSpanStyle) -> Self {
+        Self {
+            font: style.font.or_else(|| self.font.clone()),
+            font_size: style.font_size.unwrap_or(self.font_size),
+            color: style.color.unwrap_or(self.color),
+            bold: style.bold.unwrap_or(self.bold),
+            italic: style.italic.unwrap_or(self.italic),
+            shadow: style.shadow.or(self.shadow),
+            outline: style.outline.or(self.outline),
+        }
+    }
+}
+
+/// Builds a [`Text`] from spans of inline styles, pushed with [`push`](Self::push) or parsed from
+/// [`markup`](Self::markup).
+///
+/// Consecutive spans resolving to the same [`TextStyle`] are merged in a single [`TextSection`].
+/// The sections of a text are shaped and wrapped together, so lines can break anywhere across
+/// spans.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_render::color::Color;
+/// # use bevy_text::{Font, FontFamily, RichTextBuilder, TextSpanStyle};
+/// #
+/// # let regular: Handle<Font> = Default::default();
+/// # let bold: Handle<Font> = Default::default();
+/// #
+/// let family = FontFamily::new(regular).with_bold(bold);
+/// let text = RichTextBuilder::new(family, 30.0, Color::WHITE)
+///     .text("A ")
+///     .push(TextSpanStyle::bold())
+///     .text("bold, ")
+///     .span("red", TextSpanStyle::color(Color::RED))
+///     .pop()
+///     .text(" word")
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RichTextBuilder {
+    family: FontFamily,
+    rendering: TextRendering,
+    sections: Vec<TextSection>,
+    stack: Vec<SpanState>,
+}
+
+impl RichTextBuilder {
+    /// Creates a builder whose spans use the fonts of `family`, starting with its regular font
+    /// at the given size and color.
+    pub fn new(family: FontFamily, font_size: f32, color: Color) -> Self {
+        Self {
+            family,
+            rendering: TextRendering::Bitmap,
+            sections: Vec::new(),
+            stack: vec![SpanState {
+                font: None,
+                font_size,
+                color,
+                bold: false,
+                italic: false,
+                shadow: None,
+                outline: None,
+            }],
+        }
+    }
+
+    /// Sets how the glyphs of all the spans are rasterized.
+    pub fn with_rendering(mut self, rendering: TextRendering) -> Self {
+        self.rendering = rendering;
+        self
+    }
+
+    /// Starts a span with the given style changes, until the matching [`pop`](Self::pop).
+    pub fn push(mut self, style: TextSpanStyle) -> Self {
+        let state = self.current().with(style);
+        self.stack.push(state);
+        self
+    }
+
+    /// Ends the last span started by [`push`](Self::push). Does nothing if there is none.
+    pub fn pop(mut self) -> Self {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+        self
+    }
+
+    /// Adds some text in the style of the current span.
+    pub fn text(mut self, value: impl AsRef<str>) -> Self {
+        self.push_text(value.as_ref());
+        self
+    }
+
+    /// Adds some text in its own span.
+    pub fn span(self, value: impl AsRef<str>, style: TextSpanStyle) -> Self {
+        self.push(style).text(value).pop()
+    }
+
+    /// Adds some text with inline styles described by tags, in the style of the current span.
+    ///
+    /// Tags are enclosed in brackets and closed with the same name prefixed by a slash:
+    /// - `[b]bold[/b]` and `[i]italic[/i]` use the variants of the [`FontFamily`],
+    /// - `[color=#ff0000]red[/color]` accepts the hexadecimal colors of [`Color::hex`] and the
+    ///   basic color names such as `red` or `white`,
+    /// - `[size=40]large[/size]` changes the font size.
+    ///
+    /// A literal opening bracket is written `[[`.
+    ///
+    /// ```
+    /// # use bevy_render::color::Color;
+    /// # use bevy_text::{FontFamily, RichTextBuilder};
+    /// #
+    /// let text = RichTextBuilder::new(FontFamily::default(), 30.0, Color::WHITE)
+    ///     .markup("Press [b][color=yellow][[E][/color][/b] to [size=40]jump[/size]")
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(text.sections[1].value, "[E]");
+    /// ```
+    pub fn markup(mut self, markup: &str) -> Result<Self, TextMarkupError> {
+        let base = self.stack.len();
+        let mut tags = Vec::new();
+        let mut text = String::new();
+        let mut rest = markup;
+        while let Some(start) = rest.find('[') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            if let Some(escaped) = rest.strip_prefix('[') {
+                text.push('[');
+                rest = escaped;
+                continue;
+            }
+            let end = rest.find(']').ok_or(TextMarkupError::UnterminatedTag(
+                markup.len() - rest.len() - 1,
+            ))?;
+            let tag = &rest[..end];
+            rest = &rest[end + 1..];
+
+            self.push_text(&std::mem::take(&mut text));
+            if let Some(name) = tag.strip_prefix('/') {
+                if tags.pop() != Some(name) {
+                    return Err(TextMarkupError::UnexpectedClosingTag(name.to_string()));
+                }
+                self.stack.pop();
+            } else {
+                let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+                let style = parse_tag(name, value)?;
+                tags.push(name);
+                self = self.push(style);
+            }
+        }
+        if let Some(tag) = tags.pop() {
+            return Err(TextMarkupError::UnclosedTag(tag.to_string()));
+        }
+        debug_assert_eq!(self.stack.len(), base);
+        text.push_str(rest);
+        self.push_text(&text);
+        Ok(self)
+    }
+
+    /// Returns the [`Text`] made of the spans added so far.
+    pub fn build(self) -> Text {
+        Text::from_sections(self.sections)
+    }
+
+    fn current(&self) -> &SpanState {
+        // The base span is never popped
+        self.stack.last().unwrap()
+    }
+
+    fn push_text(&mut self, value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        let state = self.current();
+        let style = TextStyle {
+            font: state
+                .font
+                .clone()
+                .unwrap_or_else(|| self.family.get(state.bold, state.italic).clone()),
+            fallback_fonts: self.family.fallbacks.clone(),
+            font_size: state.font_size,
+            color: state.color,
+            shadow: state.shadow,
+            outline: state.outline,
+            rendering: self.rendering,
+        };
+        match self.sections.last_mut() {
+            Some(section) if section.style == style => section.value.push_str(value),
+            _ => self.sections.push(TextSection::new(value, style)),
+        }
+    }
+}
+
+fn parse_tag(name: &str, value: &str) -> Result<TextSpanStyle, TextMarkupError> {
+    let invalid_value = || TextMarkupError::InvalidValue {
+        tag: name.to_string(),
+        value: value.to_string(),
+    };
+    match name {
+        "b" => Ok(TextSpanStyle::bold()),
+        "i" => Ok(TextSpanStyle::italic()),
+        "color" => parse_color(value)
+            .map(TextSpanStyle::color)
+            .ok_or_else(invalid_value),
+        "size" => value
+            .parse()
+            .ok()
+            .filter(|size: &f32| *size > 0.0)
+            .map(TextSpanStyle::font_size)
+            .ok_or_else(invalid_value),
+        _ => Err(TextMarkupError::UnknownTag(name.to_string())),
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let color = match value {
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        "gray" => Color::GRAY,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "orange" => Color::ORANGE,
+        "purple" => Color::PURPLE,
+        "pink" => Color::PINK,
+        "cyan" => Color::CYAN,
+        _ => return Color::hex(value).ok(),
+    };
+    Some(color)
+}
+
+impl Text {
+    /// Constructs a [`Text`] from markup, see [`RichTextBuilder::markup`].
+    pub fn from_markup(
+        markup: &str,
+        family: FontFamily,
+        font_size: f32,
+        color: Color,
+    ) -> Result<Self, TextMarkupError> {
+        Ok(RichTextBuilder::new(family, font_size, color)
+            .markup(markup)?
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::HandleId;
+    use bevy_reflect::TypeUuid;
+
+    use super::*;
+
+    fn font(id: u64) -> Handle<Font> {
+        Handle::weak(HandleId::new(Font::TYPE_UUID, id))
+    }
+
+    fn family() -> FontFamily {
+        FontFamily::new(font(0)).with_bold(font(1))
+    }
+
+    #[test]
+    fn font_family_fallback() {
+        let family = family();
+        assert_eq!(family.get(false, false), &font(0));
+        assert_eq!(family.get(true, false), &font(1));
+        assert_eq!(family.get(false, true), &font(0));
+        assert_eq!(family.get(true, true), &font(1));
+        let family = family.with_italic(font(2));
+        assert_eq!(family.get(false, true), &font(2));
+        assert_eq!(family.get(true, true), &font(1));
+    }
+
+    #[test]
+    fn markup_spans() {
+        let text = Text::from_markup(
+            "a [b]b [color=red]c[/color][size=20][/size][/b]d [[e]",
+            family(),
+            10.0,
+            Color::WHITE,
+        )
+        .unwrap();
+        let sections: Vec<_> = text
+            .sections
+            .iter()
+            .map(|section| {
+                (
+                    section.value.as_str(),
+                    section.style.font.clone(),
+                    section.style.font_size,
+                    section.style.color,
+                )
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("a ", font(0), 10.0, Color::WHITE),
+                ("b ", font(1), 10.0, Color::WHITE),
+                ("c", font(1), 10.0, Color::RED),
+                ("d [e]", font(0), 10.0, Color::WHITE),
+            ]
+        );
+    }
+
+    #[test]
+    fn markup_errors() {
+        let markup = |markup| Text::from_markup(markup, family(), 10.0, Color::WHITE).map(|_| ());
+        assert_eq!(
+            markup("[b]a"),
+            Err(TextMarkupError::UnclosedTag("b".to_string()))
+        );
+        assert_eq!(
+            markup("[b]a[/i]"),
+            Err(TextMarkupError::UnexpectedClosingTag("i".to_string()))
+        );
+        assert_eq!(
+            markup("[u]a[/u]"),
+            Err(TextMarkupError::UnknownTag("u".to_string()))
+        );
+        assert_eq!(
+            markup("[size=big]a[/size]"),
+            Err(TextMarkupError::InvalidValue {
+                tag: "size".to_string(),
+                value: "big".to_string()
+            })
+        );
+        assert_eq!(markup("a [b"), Err(TextMarkupError::UnterminatedTag(2)));
+    }
+}
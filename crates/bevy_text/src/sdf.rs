@@ -0,0 +1,159 @@
+use ab_glyph::{point, Font as _, FontArc, Glyph, GlyphId, Rect, ScaleFont as _};
+use bevy_math::{UVec2, Vec2};
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+
+use crate::text3d::flatten_curves;
+
+/// The font size the signed distance fields of the glyphs are rasterized at, once for all the
+/// font sizes of the texts drawn with [`TextRendering::Sdf`](crate::TextRendering::Sdf).
+pub const SDF_FONT_SIZE: f32 = 64.0;
+
+/// The distance from the outline of a glyph covered by its signed distance field, in pixels at
+/// [`SDF_FONT_SIZE`], which is also the margin around the glyph in the field.
+pub const SDF_SPREAD: u32 = 8;
+
+/// The rect covered by the signed distance field of a glyph, in pixels at the scale and position
+/// of the glyph, with the Y axis pointing down.
+pub(crate) fn sdf_glyph_rect(font: &FontArc, glyph: &Glyph) -> Option<Rect> {
+    let bounds = font
+        .outline_glyph(glyph.id.with_scale(SDF_FONT_SIZE))?
+        .px_bounds();
+    let scale = glyph.scale.y / SDF_FONT_SIZE;
+    let spread = SDF_SPREAD as f32;
+    Some(Rect {
+        min: point(
+            glyph.position.x + (bounds.min.x - spread) * scale,
+            glyph.position.y + (bounds.min.y - spread) * scale,
+        ),
+        max: point(
+            glyph.position.x + (bounds.max.x + spread) * scale,
+            glyph.position.y + (bounds.max.y + spread) * scale,
+        ),
+    })
+}
+
+/// Rasterizes the signed distance field of a glyph at [`SDF_FONT_SIZE`], in the alpha channel of
+/// a white image: the outline of the glyph is at 0.5, inside of it above and outside below.
+pub(crate) fn sdf_glyph_image(font: &FontArc, glyph_id: GlyphId) -> Option<Image> {
+    let outlined_glyph = font.outline_glyph(glyph_id.with_scale(SDF_FONT_SIZE))?;
+    let bounds = outlined_glyph.px_bounds();
+    let glyph_width = bounds.width() as u32;
+    let glyph_height = bounds.height() as u32;
+    let mut coverage = vec![0.0; (glyph_width * glyph_height) as usize];
+    outlined_glyph.draw(|x, y, v| {
+        coverage[(y * glyph_width + x) as usize] = v;
+    });
+
+    // The contours of the outline in the pixels of the field, with the Y axis pointing down
+    let scale = font.as_scaled(SDF_FONT_SIZE).h_scale_factor();
+    let origin = Vec2::new(bounds.min.x, bounds.min.y) - SDF_SPREAD as f32;
+    let contours: Vec<Vec<Vec2>> = flatten_curves(&font.outline(glyph_id)?.curves)
+        .into_iter()
+        .map(|contour| {
+            contour
+                .into_iter()
+                .map(|point| Vec2::new(point.x, -point.y) * scale - origin)
+                .collect()
+        })
+        .collect();
+
+    let size = UVec2::new(glyph_width, glyph_height) + 2 * SDF_SPREAD;
+    let field = signed_distance_field(&contours, size, |x, y| {
+        let (x, y) = (x.wrapping_sub(SDF_SPREAD), y.wrapping_sub(SDF_SPREAD));
+        x < glyph_width && y < glyph_height && coverage[(y * glyph_width + x) as usize] >= 0.5
+    });
+
+    Some(Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        field
+            .iter()
+            .flat_map(|&alpha| [255, 255, 255, alpha])
+            .collect(),
+        TextureFormat::Rgba8UnormSrgb,
+    ))
+}
+
+/// The signed distances of the centers of the pixels of a field to the contours, mapped from
+/// minus to plus [`SDF_SPREAD`] to the range of a byte.
+fn signed_distance_field(
+    contours: &[Vec<Vec2>],
+    size: UVec2,
+    inside: impl Fn(u32, u32) -> bool,
+) -> Vec<u8> {
+    let spread = SDF_SPREAD as f32;
+    let mut distances = vec![spread; (size.x * size.y) as usize];
+    for contour in contours {
+        for (i, &a) in contour.iter().enumerate() {
+            let b = contour[(i + 1) % contour.len()];
+            // Only the pixels closer than the spread to the edge are affected by it
+            let min = (a.min(b) - spread).floor().max(Vec2::ZERO).as_uvec2();
+            let max = (a.max(b) + spread).ceil().min(size.as_vec2()).as_uvec2();
+            for y in min.y..max.y {
+                for x in min.x..max.x {
+                    let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                    let distance = &mut distances[(y * size.x + x) as usize];
+                    *distance = distance.min(distance_to_segment(center, a, b));
+                }
+            }
+        }
+    }
+
+    distances
+        .iter()
+        .enumerate()
+        .map(|(index, &distance)| {
+            let (x, y) = (index as u32 % size.x, index as u32 / size.x);
+            let distance = if inside(x, y) { distance } else { -distance };
+            ((0.5 + 0.5 * distance / spread).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let edge = b - a;
+    let t = if edge == Vec2::ZERO {
+        0.0
+    } else {
+        ((point - a).dot(edge) / edge.length_squared()).clamp(0.0, 1.0)
+    };
+    point.distance(a + t * edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_distance_field() {
+        // A square from 8 to 24 pixels in a field of 32 pixels
+        let square = vec![
+            Vec2::new(8.0, 8.0),
+            Vec2::new(24.0, 8.0),
+            Vec2::new(24.0, 24.0),
+            Vec2::new(8.0, 24.0),
+        ];
+        let field = signed_distance_field(&[square], UVec2::splat(32), |x, y| {
+            (8..24).contains(&x) && (8..24).contains(&y)
+        });
+        let at = |x: u32, y: u32| field[(y * 32 + x) as usize];
+        // Half a pixel inside and outside of the edges
+        assert_eq!(at(8, 16), 135);
+        assert_eq!(at(7, 16), 120);
+        // The center of the square, and a corner of the field further than the spread from it
+        assert_eq!(at(16, 16), 247);
+        assert_eq!(at(0, 0), 0);
+        assert!(field
+            .iter()
+            .enumerate()
+            .all(|(index, &alpha)| (alpha >= 128)
+                == (index % 32 >= 8 && index % 32 < 24 && index / 32 >= 8 && index / 32 < 24)));
+    }
+}
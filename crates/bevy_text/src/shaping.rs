@@ -0,0 +1,292 @@
+use ab_glyph::{point, Font as _, Glyph, GlyphId, ScaleFont as _};
+use bevy_math::Vec2;
+use glyph_brush_layout::{SectionGlyph, SectionText};
+use rustybuzz::{Direction, UnicodeBuffer};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+use unicode_linebreak::{linebreaks, BreakOpportunity};
+
+use crate::{Font, TextAlignment};
+
+/// Returns whether laying out the text requires shaping its glyphs and reordering its
+/// right-to-left parts, instead of laying out one glyph per character from left to right.
+///
+/// This is the case of the right-to-left scripts, the scripts whose letters change shape with
+/// their neighbors such as Arabic and Devanagari, and the combining marks of any script.
+pub(crate) fn needs_shaping(text: &str) -> bool {
+    text.chars().any(|character| {
+        matches!(
+            bidi_class(character),
+            BidiClass::R
+                | BidiClass::AL
+                | BidiClass::AN
+                | BidiClass::NSM
+                | BidiClass::LRE
+                | BidiClass::LRO
+                | BidiClass::LRI
+                | BidiClass::RLE
+                | BidiClass::RLO
+                | BidiClass::RLI
+                | BidiClass::FSI
+                | BidiClass::PDF
+                | BidiClass::PDI
+        ) || matches!(
+            character as u32,
+            // Indic, Southeast Asian and Tibetan scripts, and the zero width joiners
+            0x0900..=0x0DFF | 0x0E00..=0x0FFF | 0x1000..=0x109F | 0x1780..=0x17FF | 0x200C..=0x200D
+        )
+    })
+}
+
+/// A glyph shaped from the text of a section, in the logical order of the text.
+struct ShapedGlyph {
+    section_index: usize,
+    /// The index of the first byte of the characters of the glyph, in the whole text
+    byte_index: usize,
+    id: GlyphId,
+    advance: f32,
+    offset: Vec2,
+    level: Level,
+    whitespace: bool,
+    control: bool,
+}
+
+/// Lays out the sections of a text like [`GlyphBrush::compute_glyphs`](crate::GlyphBrush::compute_glyphs), with
+/// the glyphs shaped by the fonts and the right-to-left parts of the lines reordered.
+///
+/// `fonts` are the fonts of the sections. Returns `None` if a font can't be shaped with.
+pub(crate) fn shape_text(
+    sections: &[SectionText],
+    fonts: &[&Font],
+    bounds: Vec2,
+    text_alignment: TextAlignment,
+) -> Option<Vec<SectionGlyph>> {
+    let mut text = String::new();
+    let mut section_starts = Vec::with_capacity(sections.len());
+    for section in sections {
+        section_starts.push(text.len());
+        text.push_str(section.text);
+    }
+    let bidi_info = BidiInfo::new(&text, None);
+
+    // Shape the parts of the sections sharing a direction, in the logical order
+    let mut glyphs = Vec::new();
+    for (section_index, section) in sections.iter().enumerate() {
+        let font = fonts[section_index];
+        let face = rustybuzz::Face::from_slice(font.font.font_data(), font.face_index)?;
+        let scale_factor = font.font.as_scaled(section.scale).h_scale_factor();
+
+        let section_end = section_starts[section_index] + section.text.len();
+        let mut start = section_starts[section_index];
+        while start < section_end {
+            let level = bidi_info.levels[start];
+            let end = (start..section_end)
+                .find(|&index| bidi_info.levels[index] != level)
+                .unwrap_or(section_end);
+
+            let mut buffer = UnicodeBuffer::new();
+            buffer.push_str(&text[start..end]);
+            buffer.set_direction(if level.is_rtl() {
+                Direction::RightToLeft
+            } else {
+                Direction::LeftToRight
+            });
+            buffer.guess_segment_properties();
+            let shaped = rustybuzz::shape(&face, &[], buffer);
+
+            let first = glyphs.len();
+            for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+                let byte_index = start + info.cluster as usize;
+                let character = text[byte_index..].chars().next().unwrap_or_default();
+                glyphs.push(ShapedGlyph {
+                    section_index,
+                    byte_index,
+                    id: GlyphId(info.glyph_id as u16),
+                    advance: position.x_advance as f32 * scale_factor,
+                    // The offsets of the shaper point up
+                    offset: Vec2::new(position.x_offset as f32, -position.y_offset as f32)
+                        * scale_factor,
+                    level,
+                    whitespace: character.is_whitespace(),
+                    control: character.is_control(),
+                });
+            }
+            // The right-to-left glyphs come in the visual order
+            if level.is_rtl() {
+                glyphs[first..].reverse();
+            }
+            start = end;
+        }
+    }
+
+    let lines = break_lines(&text, &glyphs, bounds.x);
+
+    let mut section_glyphs = Vec::with_capacity(glyphs.len());
+    let mut baseline = 0.0;
+    for (line_index, line) in lines.iter().enumerate() {
+        let line_glyphs = &glyphs[line.clone()];
+
+        // The whitespace at the end of the line hangs out of it
+        let hanging = line_glyphs
+            .iter()
+            .rev()
+            .take_while(|glyph| glyph.whitespace || glyph.control)
+            .count();
+        let visible = line.start..line.end - hanging;
+        let width: f32 = glyphs[visible.clone()]
+            .iter()
+            .map(|glyph| glyph.advance)
+            .sum();
+
+        let (ascent, descent, line_gap) = line_glyphs
+            .iter()
+            .map(|glyph| glyph.section_index)
+            .chain(line_glyphs.is_empty().then_some(0))
+            .map(|section_index| {
+                let section = &sections[section_index];
+                let font = fonts[section_index].font.as_scaled(section.scale);
+                (font.ascent(), font.descent(), font.line_gap())
+            })
+            .fold((0.0f32, 0.0f32, 0.0f32), |metrics, font_metrics| {
+                (
+                    metrics.0.max(font_metrics.0),
+                    metrics.1.min(font_metrics.1),
+                    metrics.2.max(font_metrics.2),
+                )
+            });
+        if line_index > 0 {
+            baseline += line_gap;
+        }
+        baseline += ascent;
+
+        let levels: Vec<u8> = glyphs[visible.clone()]
+            .iter()
+            .map(|glyph| glyph.level.number())
+            .collect();
+        let mut x = match text_alignment {
+            TextAlignment::Left => 0.0,
+            TextAlignment::Center => -width / 2.0,
+            TextAlignment::Right => -width,
+        };
+        for index in visual_order(&levels) {
+            let glyph = &glyphs[visible.start + index];
+            if !glyph.control {
+                let section = &sections[glyph.section_index];
+                section_glyphs.push(SectionGlyph {
+                    section_index: glyph.section_index,
+                    byte_index: glyph.byte_index - section_starts[glyph.section_index],
+                    glyph: Glyph {
+                        id: glyph.id,
+                        scale: section.scale,
+                        position: point(x + glyph.offset.x, baseline + glyph.offset.y),
+                    },
+                    font_id: section.font_id,
+                });
+            }
+            x += glyph.advance;
+        }
+
+        baseline -= descent;
+    }
+    Some(section_glyphs)
+}
+
+/// Splits the glyphs of the text in lines, at its mandatory line breaks and at the last break
+/// opportunity before the glyphs exceeding the width.
+fn break_lines(text: &str, glyphs: &[ShapedGlyph], width: f32) -> Vec<std::ops::Range<usize>> {
+    let mut opportunities = linebreaks(text).peekable();
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_width = 0.0;
+    let mut last_opportunity = None;
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let mut mandatory = false;
+        let mut allowed = false;
+        while let Some((_, opportunity)) =
+            opportunities.next_if(|(byte_index, _)| *byte_index <= glyph.byte_index)
+        {
+            match opportunity {
+                BreakOpportunity::Mandatory => mandatory = true,
+                BreakOpportunity::Allowed => allowed = true,
+            }
+        }
+
+        if index > line_start {
+            if mandatory {
+                lines.push(line_start..index);
+                line_start = index;
+                line_width = 0.0;
+                last_opportunity = None;
+            } else if allowed {
+                last_opportunity = Some(index);
+            }
+        }
+
+        if !glyph.whitespace && line_width + glyph.advance > width {
+            if let Some(opportunity) = last_opportunity.take() {
+                lines.push(line_start..opportunity);
+                line_start = opportunity;
+                line_width = glyphs[opportunity..index]
+                    .iter()
+                    .map(|glyph| glyph.advance)
+                    .sum();
+            }
+        }
+        line_width += glyph.advance;
+    }
+    lines.push(line_start..glyphs.len());
+    lines
+}
+
+/// The visual order of the items of a line from their bidirectional embedding levels, with the
+/// items of each level reversed over the lower levels, following the rule L2 of the Unicode
+/// Bidirectional Algorithm.
+fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let mut levels = levels.to_vec();
+    let highest = levels.iter().copied().max().unwrap_or(0);
+    let Some(lowest_odd) = levels.iter().copied().filter(|level| level % 2 == 1).min() else {
+        return order;
+    };
+    for level in (lowest_odd..=highest).rev() {
+        let mut start = 0;
+        while start < levels.len() {
+            if levels[start] < level {
+                start += 1;
+                continue;
+            }
+            let end = (start..levels.len())
+                .find(|&index| levels[index] < level)
+                .unwrap_or(levels.len());
+            order[start..end].reverse();
+            levels[start..end].reverse();
+            start = end;
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaping_detection() {
+        assert!(!needs_shaping("Hello, world! 123"));
+        assert!(!needs_shaping("世界"));
+        assert!(needs_shaping("Hello, שלום"));
+        assert!(needs_shaping("مرحبا"));
+        assert!(needs_shaping("नमस्ते"));
+        assert!(needs_shaping("e\u{301}"));
+    }
+
+    #[test]
+    fn bidi_visual_order() {
+        assert_eq!(visual_order(&[]), Vec::<usize>::new());
+        assert_eq!(visual_order(&[0, 0, 0]), vec![0, 1, 2]);
+        assert_eq!(visual_order(&[1, 1, 1]), vec![2, 1, 0]);
+        // Some right-to-left words in a left-to-right line
+        assert_eq!(visual_order(&[0, 1, 1, 0, 1]), vec![0, 2, 1, 3, 4]);
+        // Some left-to-right numbers in a right-to-left line
+        assert_eq!(visual_order(&[1, 1, 2, 2, 1]), vec![4, 2, 3, 1, 0]);
+    }
+}
@@ -0,0 +1,129 @@
+use ab_glyph::{Font as _, FontRef};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::system::Resource;
+use bevy_utils::{tracing::warn, HashMap};
+use fontdb::{Database, Family, Query, Stretch, Style, Weight, ID};
+
+use crate::Font;
+
+/// The generic families of fonts, resolved to installed fonts by [`SystemFonts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GenericFontFamily {
+    Serif,
+    SansSerif,
+    Monospace,
+    Cursive,
+    Fantasy,
+}
+
+/// Loads the fonts installed on the system as [`Font`] assets, to use them as the fallback fonts
+/// of a [`TextStyle`](crate::TextStyle).
+///
+/// The installed fonts are listed the first time one is loaded, which can take a moment, and each
+/// one is loaded once. Only the fonts made of outlines can be drawn: color emoji fonts made of
+/// bitmaps are skipped.
+///
+/// ```no_run
+/// # use bevy_asset::Assets;
+/// # use bevy_text::{Font, SystemFonts, TextStyle};
+/// #
+/// # let mut system_fonts = SystemFonts::default();
+/// # let mut fonts = Assets::<Font>::default();
+/// # let mut style = TextStyle::default();
+/// // Draw the Japanese characters missing from the font of the text with an installed font
+/// if let Some(font) = system_fonts.load_family("Noto Sans CJK JP", &mut fonts) {
+///     style.fallback_fonts.push(font);
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct SystemFonts {
+    database: Option<Database>,
+    loaded: HashMap<ID, Handle<Font>>,
+}
+
+impl SystemFonts {
+    /// Loads the regular font of an installed family, such as `"DejaVu Sans"`.
+    pub fn load_family(&mut self, family: &str, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        self.load_query(Family::Name(family), fonts)
+    }
+
+    /// Loads the font the system uses for a generic family.
+    pub fn load_generic(
+        &mut self,
+        family: GenericFontFamily,
+        fonts: &mut Assets<Font>,
+    ) -> Option<Handle<Font>> {
+        let family = match family {
+            GenericFontFamily::Serif => Family::Serif,
+            GenericFontFamily::SansSerif => Family::SansSerif,
+            GenericFontFamily::Monospace => Family::Monospace,
+            GenericFontFamily::Cursive => Family::Cursive,
+            GenericFontFamily::Fantasy => Family::Fantasy,
+        };
+        self.load_query(family, fonts)
+    }
+
+    /// Loads the first installed font with a glyph for the character.
+    ///
+    /// This reads the installed fonts until one is found, so it is better called once for each
+    /// script, when setting up the fallback fonts of the texts.
+    pub fn load_for_char(
+        &mut self,
+        character: char,
+        fonts: &mut Assets<Font>,
+    ) -> Option<Handle<Font>> {
+        if let Some(handle) = self.loaded.values().find_map(|handle| {
+            let font = fonts.get(handle)?;
+            font.has_glyph(character).then(|| handle.clone())
+        }) {
+            return Some(handle);
+        }
+        let database = self.database();
+        let id = database.faces().iter().map(|face| face.id).find(|&id| {
+            database
+                .with_face_data(id, |data, index| {
+                    FontRef::try_from_slice_and_index(data, index)
+                        .map_or(false, |font| font.glyph_id(character).0 != 0)
+                })
+                .unwrap_or(false)
+        })?;
+        self.load(id, fonts)
+    }
+
+    fn database(&mut self) -> &Database {
+        self.database.get_or_insert_with(|| {
+            let mut database = Database::new();
+            database.load_system_fonts();
+            database
+        })
+    }
+
+    fn load_query(&mut self, family: Family, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        let id = self.database().query(&Query {
+            families: &[family],
+            weight: Weight::NORMAL,
+            stretch: Stretch::Normal,
+            style: Style::Normal,
+        })?;
+        self.load(id, fonts)
+    }
+
+    fn load(&mut self, id: ID, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        if let Some(handle) = self.loaded.get(&id) {
+            return Some(handle.clone());
+        }
+        let font = self.database().with_face_data(id, |data, index| {
+            Font::try_from_bytes_and_index(data.to_vec(), index)
+        })?;
+        let font = match font {
+            Ok(font) => font,
+            Err(error) => {
+                warn!("Failed to load the system font {id:?}: {error}");
+                return None;
+            }
+        };
+        let handle = fonts.add(font);
+        self.loaded.insert(id, handle.clone());
+        Some(handle)
+    }
+}
@@ -1,9 +1,11 @@
 use bevy_asset::Handle;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::{prelude::*, FromReflect};
 use bevy_render::color::Color;
 use bevy_utils::default;
 use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
 
 use crate::Font;
 
@@ -43,6 +45,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// );
     ///
@@ -52,6 +55,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// ) // You can still add an alignment.
     /// .with_alignment(TextAlignment::Center);
@@ -79,6 +83,7 @@ impl Text {
     ///             font: font_handle.clone(),
     ///             font_size: 60.0,
     ///             color: Color::BLUE,
+    ///             ..Default::default()
     ///         },
     ///     ),
     ///     TextSection::new(
@@ -87,6 +92,7 @@ impl Text {
     ///             font: font_handle,
     ///             font_size: 60.0,
     ///             color: Color::RED,
+    ///             ..Default::default()
     ///         },
     ///     ),
     /// ]);
@@ -154,19 +160,176 @@ impl From<TextAlignment> for glyph_brush_layout::HorizontalAlign {
     }
 }
 
-#[derive(Clone, Debug, Reflect, FromReflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, FromReflect)]
 pub struct TextStyle {
     pub font: Handle<Font>,
+    /// The fonts drawing the characters missing from `font`, tried in order.
+    pub fallback_fonts: Vec<Handle<Font>>,
     pub font_size: f32,
     pub color: Color,
+    /// A drop shadow drawn under the glyphs, and under their outline.
+    pub shadow: Option<TextShadow>,
+    /// An outline drawn around the glyphs.
+    pub outline: Option<TextOutline>,
+    /// How the glyphs are rasterized.
+    pub rendering: TextRendering,
 }
 
 impl Default for TextStyle {
     fn default() -> Self {
         Self {
             font: Default::default(),
+            fallback_fonts: Vec::new(),
             font_size: 12.0,
             color: Color::WHITE,
+            shadow: None,
+            outline: None,
+            rendering: TextRendering::Bitmap,
         }
     }
 }
+
+impl TextStyle {
+    /// The color of the copies of the glyphs drawn in the given layer, if the layer is drawn.
+    pub fn layer_color(&self, layer: TextLayer) -> Option<Color> {
+        match layer {
+            TextLayer::Shadow => self.shadow.map(|shadow| shadow.color),
+            TextLayer::Outline => self.outline.map(|outline| outline.color),
+            TextLayer::Glyph => Some(self.color),
+        }
+    }
+
+    /// The offsets of the copies of each glyph drawn in the given layer, in logical pixels with
+    /// the Y axis pointing down.
+    ///
+    /// The outline is drawn as copies of the glyphs around them, which suits outlines of a few
+    /// pixels.
+    pub fn layer_offsets(&self, layer: TextLayer) -> impl Iterator<Item = Vec2> {
+        let outline_width = self.outline.map_or(0.0, |outline| outline.width);
+        let (offset, radius) = match layer {
+            TextLayer::Shadow => (
+                self.shadow.map_or(Vec2::ZERO, |shadow| shadow.offset),
+                outline_width,
+            ),
+            TextLayer::Outline => (Vec2::ZERO, outline_width),
+            TextLayer::Glyph => (Vec2::ZERO, 0.0),
+        };
+        // Enough copies for them to be less than about two pixels apart
+        let copies = if radius > 0.0 {
+            (radius * 4.0).ceil().clamp(8.0, 32.0) as u32
+        } else {
+            1
+        };
+        (0..copies).map(move |i| offset + radius * Vec2::from_angle(TAU * i as f32 / copies as f32))
+    }
+}
+
+/// A drop shadow drawn under the glyphs of a [`TextSection`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+pub struct TextShadow {
+    /// The offset of the shadow from the glyphs, in logical pixels to the right and down.
+    pub offset: Vec2,
+    pub color: Color,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::splat(2.0),
+            color: Color::rgba(0.0, 0.0, 0.0, 0.75),
+        }
+    }
+}
+
+/// An outline drawn around the glyphs of a [`TextSection`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+pub struct TextOutline {
+    /// The width of the outline, in logical pixels.
+    pub width: f32,
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// How the glyphs of a [`TextSection`] are rasterized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, FromReflect)]
+pub enum TextRendering {
+    /// The glyphs are rasterized in bitmaps for each font size, matching the pixels of the screen
+    /// but blurry once scaled or rotated.
+    #[default]
+    Bitmap,
+    /// The glyphs are rasterized once in signed distance fields, drawn for any font size and
+    /// staying crisp when scaled, rotated or placed in the world.
+    ///
+    /// The `sharpness` divides the width of the anti-aliased edges of the glyphs: 1 spreads them
+    /// over a pixel, higher values sharpen them and lower values soften them.
+    Sdf { sharpness: f32 },
+}
+
+impl TextRendering {
+    /// Signed distance fields with edges anti-aliased over a pixel.
+    pub const SDF: TextRendering = TextRendering::Sdf { sharpness: 1.0 };
+}
+
+/// The layers of copies of its glyphs drawn for a text, see [`TextStyle::layer_offsets`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextLayer {
+    Shadow,
+    Outline,
+    Glyph,
+}
+
+impl TextLayer {
+    /// All the layers, from the bottom one to the top one.
+    pub const ALL: [TextLayer; 3] = [TextLayer::Shadow, TextLayer::Outline, TextLayer::Glyph];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layer_offsets() {
+        let style = TextStyle::default();
+        assert_eq!(style.layer_color(TextLayer::Shadow), None);
+        assert_eq!(style.layer_color(TextLayer::Outline), None);
+        assert_eq!(style.layer_color(TextLayer::Glyph), Some(Color::WHITE));
+        assert_eq!(
+            style.layer_offsets(TextLayer::Glyph).collect::<Vec<_>>(),
+            vec![Vec2::ZERO]
+        );
+
+        let style = TextStyle {
+            shadow: Some(TextShadow {
+                offset: Vec2::new(3.0, 4.0),
+                ..default()
+            }),
+            outline: Some(TextOutline {
+                width: 2.0,
+                ..default()
+            }),
+            ..default()
+        };
+        let outline: Vec<_> = style.layer_offsets(TextLayer::Outline).collect();
+        assert_eq!(outline.len(), 8);
+        assert!(outline
+            .iter()
+            .all(|offset| (offset.length() - 2.0).abs() < 1e-5));
+        let shadow: Vec<_> = style.layer_offsets(TextLayer::Shadow).collect();
+        assert_eq!(shadow.len(), 8);
+        assert!(shadow
+            .iter()
+            .all(|offset| (offset.distance(Vec2::new(3.0, 4.0)) - 2.0).abs() < 1e-5));
+        assert_eq!(
+            style.layer_offsets(TextLayer::Glyph).collect::<Vec<_>>(),
+            vec![Vec2::ZERO]
+        );
+    }
+}
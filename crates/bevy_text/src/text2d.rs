@@ -12,19 +12,20 @@ use bevy_ecs::{
 use bevy_math::{Vec2, Vec3};
 use bevy_reflect::Reflect;
 use bevy_render::{
-    prelude::Color,
     texture::Image,
     view::{ComputedVisibility, Visibility},
     Extract,
 };
-use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, TextureAtlas};
+use bevy_sprite::{
+    Anchor, ExtractedSprite, ExtractedSprites, SortingLayer, SortingLayers, TextureAtlas,
+};
 use bevy_transform::prelude::{GlobalTransform, Transform};
-use bevy_utils::HashSet;
+use bevy_utils::{FloatOrd, HashSet};
 use bevy_window::{PrimaryWindow, Window, WindowScaleFactorChanged};
 
 use crate::{
-    Font, FontAtlasSet, FontAtlasWarning, Text, TextError, TextLayoutInfo, TextPipeline,
-    TextSettings, YAxisOrientation,
+    Font, FontAtlasSet, FontAtlasWarning, Text, TextError, TextLayer, TextLayoutInfo, TextPipeline,
+    TextRendering, TextSettings, YAxisOrientation,
 };
 
 /// The maximum width and height of text. The text will wrap according to the specified size.
@@ -71,6 +72,7 @@ pub fn extract_text2d_sprite(
     mut extracted_sprites: ResMut<ExtractedSprites>,
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
     windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    sorting_layers: Extract<Res<SortingLayers>>,
     text2d_query: Extract<
         Query<(
             Entity,
@@ -79,6 +81,7 @@ pub fn extract_text2d_sprite(
             &TextLayoutInfo,
             &Anchor,
             &GlobalTransform,
+            Option<&SortingLayer>,
         )>,
     >,
 ) {
@@ -88,51 +91,80 @@ pub fn extract_text2d_sprite(
         .map(|window| window.resolution.scale_factor() as f32)
         .unwrap_or(1.0);
 
-    for (entity, computed_visibility, text, text_layout_info, anchor, text_transform) in
-        text2d_query.iter()
+    for (
+        entity,
+        computed_visibility,
+        text,
+        text_layout_info,
+        anchor,
+        text_transform,
+        sorting_layer,
+    ) in text2d_query.iter()
     {
         if !computed_visibility.is_visible() {
             continue;
         }
 
+        // The glyphs are sorted together, from the position of the text
+        let sort_key = sorting_layers.sort_key(sorting_layer, text_transform.translation());
+
         let text_glyphs = &text_layout_info.glyphs;
         let text_anchor = anchor.as_vec() * Vec2::new(1., -1.) - 0.5;
         let alignment_offset = text_layout_info.size * text_anchor;
-        let mut color = Color::WHITE;
-        let mut current_section = usize::MAX;
-        for text_glyph in text_glyphs {
-            if text_glyph.section_index != current_section {
-                color = text.sections[text_glyph.section_index]
-                    .style
-                    .color
-                    .as_rgba_linear();
-                current_section = text_glyph.section_index;
+        for (layer_index, layer) in TextLayer::ALL.into_iter().enumerate() {
+            // The shadows and the outlines are sorted just below the glyphs
+            let mut sort_key = sort_key;
+            let steps = (TextLayer::ALL.len() - 1 - layer_index) as u32;
+            sort_key.z = FloatOrd(float_below(sort_key.z.0, steps));
+
+            for text_glyph in text_glyphs {
+                let style = &text.sections[text_glyph.section_index].style;
+                let Some(color) = style.layer_color(layer) else {
+                    continue;
+                };
+                let color = color.as_rgba_linear();
+                let atlas = texture_atlases
+                    .get(&text_glyph.atlas_info.texture_atlas)
+                    .unwrap();
+                let handle = atlas.texture.clone_weak();
+                let index = text_glyph.atlas_info.glyph_index;
+                let rect = Some(atlas.textures[index]);
+                // The signed distance fields are rasterized at a single size, and scaled to the
+                // size of their glyph
+                let (custom_size, sdf_sharpness) = match style.rendering {
+                    TextRendering::Sdf { sharpness } => (Some(text_glyph.size), Some(sharpness)),
+                    TextRendering::Bitmap => (None, None),
+                };
+
+                for offset in style.layer_offsets(layer) {
+                    // The offsets point down, and the Y axis of the glyphs up
+                    let offset = Vec2::new(offset.x, -offset.y) * scale_factor;
+                    let glyph_transform = Transform::from_translation(
+                        (alignment_offset + text_glyph.position + offset).extend(0.),
+                    );
+
+                    let transform = *text_transform
+                        * GlobalTransform::from_scale(Vec3::splat(scale_factor.recip()))
+                        * glyph_transform;
+
+                    extracted_sprites.sprites.push(ExtractedSprite {
+                        entity,
+                        transform,
+                        color,
+                        rect,
+                        rotated: false,
+                        custom_size,
+                        image_handle_id: handle.id(),
+                        normal_map_handle_id: None,
+                        material_handle_id: None,
+                        flip_x: false,
+                        flip_y: false,
+                        anchor: Anchor::Center.as_vec(),
+                        sdf_sharpness,
+                        sort_key,
+                    });
+                }
             }
-            let atlas = texture_atlases
-                .get(&text_glyph.atlas_info.texture_atlas)
-                .unwrap();
-            let handle = atlas.texture.clone_weak();
-            let index = text_glyph.atlas_info.glyph_index;
-            let rect = Some(atlas.textures[index]);
-
-            let glyph_transform =
-                Transform::from_translation((alignment_offset + text_glyph.position).extend(0.));
-
-            let transform = *text_transform
-                * GlobalTransform::from_scale(Vec3::splat(scale_factor.recip()))
-                * glyph_transform;
-
-            extracted_sprites.sprites.push(ExtractedSprite {
-                entity,
-                transform,
-                color,
-                rect,
-                custom_size: None,
-                image_handle_id: handle.id(),
-                flip_x: false,
-                flip_y: false,
-                anchor: Anchor::Center.as_vec(),
-            });
         }
     }
 }
@@ -216,3 +248,17 @@ pub fn update_text2d_layout(
 pub fn scale_value(value: f32, factor: f64) -> f32 {
     (value as f64 * factor) as f32
 }
+
+/// The float `steps` representable values below `value`.
+fn float_below(value: f32, steps: u32) -> f32 {
+    if steps == 0 {
+        value
+    } else if value > 0.0 {
+        f32::from_bits(value.to_bits().saturating_sub(steps))
+    } else if value < 0.0 {
+        f32::from_bits(value.to_bits() + steps)
+    } else {
+        // Below both zeros
+        f32::from_bits((-0.0f32).to_bits() + steps)
+    }
+}
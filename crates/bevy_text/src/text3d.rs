@@ -0,0 +1,562 @@
+use ab_glyph::{Font as _, OutlineCurve, Point, PxScale, ScaleFont as _};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::Component,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res, ResMut},
+    world::Ref,
+};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_render::{
+    mesh::{Indices, Mesh},
+    primitives::Aabb,
+    render_resource::PrimitiveTopology,
+};
+use bevy_utils::HashSet;
+use glyph_brush_layout::{FontId, GlyphPositioner, Layout, SectionGeometry, SectionText};
+
+use crate::{
+    shaping::{needs_shaping, shape_text},
+    Font, TextAlignment,
+};
+
+/// The number of segments the curves of the outlines of the glyphs are split in.
+const CURVE_SEGMENTS: usize = 8;
+
+/// A text drawn in the world as a mesh of extruded glyphs.
+///
+/// The mesh is built in the `Handle<Mesh>` of the entity, which is spawned with a material like
+/// any other mesh:
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::system::Commands;
+/// # use bevy_render::mesh::Mesh;
+/// # use bevy_text::{Font, Text3d};
+/// #
+/// # fn setup(mut commands: Commands, font: Handle<Font>) {
+/// commands.spawn((
+///     // A bundle with a material, such as a `PbrBundle`
+///     Handle::<Mesh>::default(),
+///     Text3d {
+///         value: "Welcome".to_string(),
+///         font,
+///         font_size: 2.0,
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+/// ```
+///
+/// The text faces the Z axis, and its lines are centered vertically around the origin of the
+/// entity, horizontally placed by its alignment like a [`Text2dBundle`](crate::Text2dBundle).
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Text3d {
+    pub value: String,
+    pub font: Handle<Font>,
+    /// The height of a line of the text, in world units.
+    pub font_size: f32,
+    /// The depth of the extruded glyphs along the Z axis, in world units.
+    pub depth: f32,
+    pub alignment: TextAlignment,
+}
+
+impl Default for Text3d {
+    fn default() -> Self {
+        Self {
+            value: String::new(),
+            font: Default::default(),
+            font_size: 1.0,
+            depth: 0.1,
+            alignment: TextAlignment::Center,
+        }
+    }
+}
+
+impl Text3d {
+    /// Builds the mesh of the text with its font, with positions, normals and UVs mapping the
+    /// front and back faces of the text to the whole texture.
+    pub fn mesh(&self, font: &Font) -> Mesh {
+        let section = SectionText {
+            text: &self.value,
+            scale: PxScale::from(self.font_size),
+            font_id: FontId(0),
+        };
+        let bounds = Vec2::splat(f32::MAX);
+        let shaped_glyphs = if needs_shaping(&self.value) {
+            shape_text(
+                std::slice::from_ref(&section),
+                &[font],
+                bounds,
+                self.alignment,
+            )
+        } else {
+            None
+        };
+        let section_glyphs = shaped_glyphs.unwrap_or_else(|| {
+            Layout::default()
+                .h_align(self.alignment.into())
+                .calculate_glyphs(
+                    &[font.font.clone()],
+                    &SectionGeometry {
+                        bounds: (bounds.x, bounds.y),
+                        ..Default::default()
+                    },
+                    std::slice::from_ref(&section),
+                )
+        });
+
+        // The glyphs are laid out with the Y axis pointing down, from the top of the first line
+        let scaled_font = font.font.as_scaled(section.scale);
+        let scale = scaled_font.h_scale_factor();
+        let center_y = section_glyphs
+            .iter()
+            .map(|section_glyph| section_glyph.glyph.position.y)
+            .fold(None, |range: Option<(f32, f32)>, y| {
+                Some(range.map_or((y, y), |(min, max)| (min.min(y), max.max(y))))
+            })
+            .map_or(0.0, |(min, max)| {
+                (min - scaled_font.ascent() + max - scaled_font.descent()) / 2.0
+            });
+
+        let mut contours = Vec::new();
+        for section_glyph in &section_glyphs {
+            let Some(outline) = font.font.outline(section_glyph.glyph.id) else {
+                continue;
+            };
+            let position = section_glyph.glyph.position;
+            let origin = Vec2::new(position.x, center_y - position.y);
+            contours.extend(flatten_curves(&outline.curves).into_iter().map(|contour| {
+                contour
+                    .into_iter()
+                    .map(|point| origin + point * scale)
+                    .collect::<Vec<_>>()
+            }));
+        }
+        extrude(&mut contours, self.depth)
+    }
+}
+
+/// Builds the meshes of the [`Text3d`] entities, when their text changes or their font is loaded.
+pub fn update_text3d_meshes(
+    // Text items which should be reprocessed again, generally when the font hasn't loaded yet.
+    mut queue: Local<HashSet<Entity>>,
+    fonts: Res<Assets<Font>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut text_query: Query<(Entity, Ref<Text3d>, &mut Handle<Mesh>, Option<&mut Aabb>)>,
+) {
+    for (entity, text, mut mesh, aabb) in &mut text_query {
+        if text.is_changed() || queue.remove(&entity) {
+            let Some(font) = fonts.get(&text.font) else {
+                queue.insert(entity);
+                continue;
+            };
+            let text_mesh = text.mesh(font);
+            // The bounds are only computed for the meshes without any
+            if let (Some(mut aabb), Some(text_aabb)) = (aabb, text_mesh.compute_aabb()) {
+                *aabb = text_aabb;
+            }
+            *mesh = meshes.add(text_mesh);
+        }
+    }
+}
+
+/// Splits the curves of the outline of a glyph in the polygons of its contours, without the
+/// point closing them.
+pub(crate) fn flatten_curves(curves: &[OutlineCurve]) -> Vec<Vec<Vec2>> {
+    let vec2 = |point: Point| Vec2::new(point.x, point.y);
+    let mut contours: Vec<Vec<Vec2>> = Vec::new();
+    let mut end = None;
+    for curve in curves {
+        let (start, points): (_, Vec<_>) = match *curve {
+            OutlineCurve::Line(p0, p1) => (vec2(p0), vec![vec2(p1)]),
+            OutlineCurve::Quad(p0, p1, p2) => {
+                let [p0, p1, p2] = [p0, p1, p2].map(vec2);
+                let points = (1..=CURVE_SEGMENTS).map(|i| {
+                    let t = i as f32 / CURVE_SEGMENTS as f32;
+                    p0.lerp(p1, t).lerp(p1.lerp(p2, t), t)
+                });
+                (p0, points.collect())
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                let [p0, p1, p2, p3] = [p0, p1, p2, p3].map(vec2);
+                let points = (1..=CURVE_SEGMENTS).map(|i| {
+                    let t = i as f32 / CURVE_SEGMENTS as f32;
+                    let (q0, q1, q2) = (p0.lerp(p1, t), p1.lerp(p2, t), p2.lerp(p3, t));
+                    q0.lerp(q1, t).lerp(q1.lerp(q2, t), t)
+                });
+                (p0, points.collect())
+            }
+        };
+        if end != Some(start) {
+            contours.push(vec![start]);
+        }
+        end = points.last().copied();
+        if let Some(contour) = contours.last_mut() {
+            contour.extend(points);
+        }
+    }
+
+    for contour in &mut contours {
+        contour.dedup();
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+    }
+    contours.retain(|contour| contour.len() >= 3);
+    contours
+}
+
+/// Builds the mesh of the extrusion of the polygons of the glyphs, filled with the non-zero rule.
+///
+/// The contours are reversed as needed to wind counterclockwise around the filled areas.
+fn extrude(contours: &mut [Vec<Vec2>], depth: f32) -> Mesh {
+    let parents = orient_contours(contours);
+    let points: Vec<Vec2> = contours.iter().flatten().copied().collect();
+    let mut starts = Vec::with_capacity(contours.len());
+    let mut start = 0;
+    for contour in contours.iter() {
+        starts.push(start);
+        start += contour.len() as u32;
+    }
+    let ring = |contour: usize| starts[contour]..starts[contour] + contours[contour].len() as u32;
+
+    let mut face_indices = Vec::new();
+    for (contour, parent) in parents.iter().enumerate() {
+        if parent.is_some() {
+            continue;
+        }
+        let mut polygon: Vec<u32> = ring(contour).collect();
+        let mut holes: Vec<usize> = (0..contours.len())
+            .filter(|&hole| parents[hole] == Some(contour))
+            .collect();
+        // From the rightmost hole, so bridges are never crossed by the next ones
+        holes.sort_by(|&a, &b| max_x(&contours[b]).total_cmp(&max_x(&contours[a])));
+        for hole in holes {
+            bridge_hole(&points, &mut polygon, &ring(hole).collect::<Vec<_>>());
+        }
+        triangulate(&points, polygon, &mut face_indices);
+    }
+
+    let (min, max) = points.iter().fold(
+        (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+        |(min, max), &point| (min.min(point), max.max(point)),
+    );
+    let size = (max - min).max(Vec2::splat(f32::EPSILON));
+    let uv = |point: Vec2| [(point.x - min.x) / size.x, (max.y - point.y) / size.y];
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    for (z, normal) in [(depth / 2.0, 1.0), (-depth / 2.0, -1.0)] {
+        let offset = positions.len() as u32;
+        positions.extend(points.iter().map(|point| [point.x, point.y, z]));
+        normals.extend(points.iter().map(|_| [0.0, 0.0, normal]));
+        uvs.extend(points.iter().map(|&point| uv(point)));
+        for triangle in face_indices.chunks_exact(3) {
+            if normal > 0.0 {
+                indices.extend(triangle.iter().map(|index| offset + index));
+            } else {
+                indices.extend(triangle.iter().rev().map(|index| offset + index));
+            }
+        }
+    }
+    for contour in contours.iter() {
+        for (i, &a) in contour.iter().enumerate() {
+            let b = contour[(i + 1) % contour.len()];
+            // The filled area is on the left of the edge
+            let normal = (b - a).perp().normalize_or_zero() * -1.0;
+            let offset = positions.len() as u32;
+            for (point, z) in [
+                (a, -depth / 2.0),
+                (b, -depth / 2.0),
+                (b, depth / 2.0),
+                (a, depth / 2.0),
+            ] {
+                positions.push([point.x, point.y, z]);
+                normals.push([normal.x, normal.y, 0.0]);
+                uvs.push(uv(point));
+            }
+            indices.extend([0, 1, 2, 0, 2, 3].map(|index| offset + index));
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// Finds the contour each hole of the polygons is cut in, and reverses the contours to wind
+/// counterclockwise around the polygons and clockwise around their holes.
+///
+/// The contours inside an odd number of others are holes, in the smallest contour around them.
+fn orient_contours(contours: &mut [Vec<Vec2>]) -> Vec<Option<usize>> {
+    let depths: Vec<usize> = (0..contours.len())
+        .map(|contour| {
+            (0..contours.len())
+                .filter(|&other| {
+                    other != contour && contains(&contours[other], contours[contour][0])
+                })
+                .count()
+        })
+        .collect();
+    let parents: Vec<Option<usize>> = (0..contours.len())
+        .map(|contour| {
+            if depths[contour] % 2 != 1 {
+                return None;
+            }
+            (0..contours.len())
+                .filter(|&other| {
+                    depths[other] + 1 == depths[contour]
+                        && contains(&contours[other], contours[contour][0])
+                })
+                .min_by(|&a, &b| {
+                    signed_area(&contours[a])
+                        .abs()
+                        .total_cmp(&signed_area(&contours[b]).abs())
+                })
+        })
+        .collect();
+    for (contour, parent) in contours.iter_mut().zip(&parents) {
+        if (signed_area(contour) > 0.0) == parent.is_some() {
+            contour.reverse();
+        }
+    }
+    parents
+}
+
+/// Cuts a clockwise hole in a counterclockwise polygon, with a bridge going from the rightmost
+/// point of the hole to a point of the polygon it can see, and back.
+fn bridge_hole(points: &[Vec2], polygon: &mut Vec<u32>, hole: &[u32]) {
+    let Some(hole_start) = (0..hole.len()).max_by(|&a, &b| {
+        points[hole[a] as usize]
+            .x
+            .total_cmp(&points[hole[b] as usize].x)
+    }) else {
+        return;
+    };
+    let m = points[hole[hole_start] as usize];
+    let point = |i: usize| points[polygon[i % polygon.len()] as usize];
+
+    // The closest edge of the polygon on the right of the hole, and its rightmost end
+    let mut bridge: Option<(f32, usize)> = None;
+    for i in 0..polygon.len() {
+        let (a, b) = (point(i), point(i + 1));
+        if a.y == b.y || m.y < a.y.min(b.y) || m.y > a.y.max(b.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        if x >= m.x && x < bridge.map_or(f32::INFINITY, |(bridge_x, _)| bridge_x) {
+            bridge = Some((
+                x,
+                if a.x > b.x {
+                    i
+                } else {
+                    (i + 1) % polygon.len()
+                },
+            ));
+        }
+    }
+    let Some((x, mut end)) = bridge else {
+        return;
+    };
+
+    // The points of the polygon inside the triangle between the hole, the edge and its end can
+    // hide the end: the one closest to the direction of the edge is visible instead
+    let hit = Vec2::new(x, m.y);
+    let mut best = (f32::INFINITY, f32::INFINITY);
+    for i in 0..polygon.len() {
+        let p = point(i);
+        let inside = if point(end).y < m.y {
+            in_triangle(m, point(end), hit, p)
+        } else {
+            in_triangle(m, hit, point(end), p)
+        };
+        if p == m || !(i == end || inside) || !locally_inside(&point, i, polygon.len(), m) {
+            continue;
+        }
+        let key = (
+            (p.y - m.y).abs() / (p.x - m.x).max(f32::EPSILON),
+            p.distance(m),
+        );
+        if key < best {
+            best = key;
+            end = i;
+        }
+    }
+
+    let bridged: Vec<u32> = hole[hole_start..]
+        .iter()
+        .chain(&hole[..=hole_start])
+        .chain(std::iter::once(&polygon[end]))
+        .copied()
+        .collect();
+    polygon.splice(end + 1..end + 1, bridged);
+}
+
+/// Triangulates a counterclockwise polygon by clipping its ears.
+fn triangulate(points: &[Vec2], mut polygon: Vec<u32>, indices: &mut Vec<u32>) {
+    let point = |index: u32| points[index as usize];
+    let mut i = 0;
+    let mut stalled = 0;
+    while polygon.len() >= 3 {
+        let n = polygon.len();
+        let (prev, current, next) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        let (a, b, c) = (point(prev), point(current), point(next));
+        let convex = cross(a, b, c) > 0.0;
+        let ear = convex
+            && polygon.iter().all(|&other| {
+                let p = point(other);
+                p == a || p == b || p == c || !in_triangle(a, b, c, p)
+            });
+        // Degenerate points and left over polygons are clipped when no ear is found
+        if ear || stalled >= n || n == 3 {
+            if convex {
+                indices.extend([prev, current, next]);
+            }
+            polygon.remove(i);
+            i = (i + n - 2) % (n - 1).max(1);
+            stalled = 0;
+        } else {
+            i = (i + 1) % n;
+            stalled += 1;
+        }
+    }
+}
+
+/// Whether the direction from the `i`-th point of a counterclockwise polygon to `target` goes
+/// inside of the polygon.
+fn locally_inside(point: &impl Fn(usize) -> Vec2, i: usize, len: usize, target: Vec2) -> bool {
+    let (prev, current, next) = (point(i + len - 1), point(i), point(i + 1));
+    let (to_prev, to_next, direction) = (prev - current, next - current, target - current);
+    if cross(prev, current, next) >= 0.0 {
+        to_next.perp_dot(direction) >= 0.0 && direction.perp_dot(to_prev) >= 0.0
+    } else {
+        to_prev.perp_dot(direction) <= 0.0 || direction.perp_dot(to_next) <= 0.0
+    }
+}
+
+/// The cross product of the edges `ab` and `bc`, positive when turning left.
+fn cross(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (b - a).perp_dot(c - b)
+}
+
+/// Whether the point is in the counterclockwise triangle, or on its edges.
+fn in_triangle(a: Vec2, b: Vec2, c: Vec2, p: Vec2) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Whether the point is inside the polygon, with the even-odd rule.
+fn contains(polygon: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// The area of the polygon, positive when it winds counterclockwise.
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    (0..polygon.len())
+        .map(|i| polygon[i].perp_dot(polygon[(i + 1) % polygon.len()]))
+        .sum::<f32>()
+        / 2.0
+}
+
+fn max_x(polygon: &[Vec2]) -> f32 {
+    polygon.iter().map(|point| point.x).fold(f32::MIN, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ab_glyph::point;
+
+    fn square(center: Vec2, half_size: f32) -> Vec<Vec2> {
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| center + Vec2::new(x, y) * half_size)
+            .to_vec()
+    }
+
+    fn triangles_area(points: &[Vec2], indices: &[u32]) -> f32 {
+        indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| points[triangle[i] as usize]);
+                let area = cross(a, b, c) / 2.0;
+                assert!(area > 0.0, "clockwise triangle {a} {b} {c}");
+                area
+            })
+            .sum()
+    }
+
+    #[test]
+    fn flatten_glyph_curves() {
+        let contours = flatten_curves(&[
+            OutlineCurve::Line(point(0.0, 0.0), point(2.0, 0.0)),
+            OutlineCurve::Quad(point(2.0, 0.0), point(2.0, 2.0), point(0.0, 2.0)),
+            OutlineCurve::Line(point(0.0, 2.0), point(0.0, 0.0)),
+            OutlineCurve::Line(point(5.0, 0.0), point(6.0, 0.0)),
+            OutlineCurve::Line(point(6.0, 0.0), point(6.0, 1.0)),
+            OutlineCurve::Line(point(6.0, 1.0), point(5.0, 0.0)),
+        ]);
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[0].len(), 2 + CURVE_SEGMENTS);
+        assert_eq!(contours[0][0], Vec2::ZERO);
+        assert_eq!(contours[0][CURVE_SEGMENTS + 1], Vec2::new(0.0, 2.0));
+        assert_eq!(
+            contours[1],
+            vec![
+                Vec2::new(5.0, 0.0),
+                Vec2::new(6.0, 0.0),
+                Vec2::new(6.0, 1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn triangulate_polygons_with_holes() {
+        // Two squares with square holes, one of them wound the wrong way, and a square in a hole
+        let mut contours = vec![
+            square(Vec2::ZERO, 4.0),
+            square(Vec2::new(-2.0, 0.0), 1.0),
+            square(Vec2::new(2.0, 0.0), 1.0),
+            square(Vec2::new(10.0, 0.0), 4.0),
+            square(Vec2::new(10.0, 0.0), 2.0),
+            square(Vec2::new(10.0, 0.0), 1.0),
+        ];
+        contours[1].reverse();
+        contours[3].reverse();
+        let parents = orient_contours(&mut contours);
+        assert_eq!(parents, vec![None, Some(0), Some(0), None, Some(3), None]);
+        assert!(contours
+            .iter()
+            .zip(&parents)
+            .all(|(contour, parent)| (signed_area(contour) > 0.0) == parent.is_none()));
+
+        let mesh = extrude(&mut contours, 1.0);
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("missing indices");
+        };
+        let points: Vec<Vec2> = contours.iter().flatten().copied().collect();
+        // The front and back faces, and two triangles for each edge of the sides
+        let face_len = (indices.len() - points.len() * 6) / 2;
+        let face = &indices[..face_len];
+        assert!(indices[face_len..]
+            .iter()
+            .all(|&index| index as usize >= points.len()));
+        // The front faces cover the polygons without their holes
+        let area = (64.0 - 4.0 - 4.0) + (64.0 - 16.0) + 4.0;
+        assert!((triangles_area(&points, face) - area).abs() < 1e-4);
+    }
+}
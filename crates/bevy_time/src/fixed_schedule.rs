@@ -0,0 +1,339 @@
+use crate::Time;
+use bevy_ecs::{
+    schedule_v3::{BoxedScheduleLabel, ScheduleLabel, WorldExt},
+    system::Resource,
+    world::World,
+};
+use bevy_utils::Duration;
+use std::ops::Deref;
+
+/// The settings and accumulated time of a fixed-rate [`Schedule`](bevy_ecs::schedule_v3::Schedule)
+/// registered in [`FixedSchedules`].
+#[derive(Debug, Clone)]
+pub struct FixedScheduleState {
+    period: Duration,
+    accumulator: Duration,
+    max_steps_per_update: Option<u32>,
+    steps_this_update: u32,
+}
+
+impl FixedScheduleState {
+    /// Creates a [`FixedScheduleState`] that runs its schedule once every `period`.
+    pub fn new(period: Duration) -> Self {
+        assert!(
+            !period.is_zero(),
+            "the period of a fixed schedule must be positive"
+        );
+        Self {
+            period,
+            accumulator: Duration::ZERO,
+            max_steps_per_update: None,
+            steps_this_update: 0,
+        }
+    }
+
+    /// Creates a [`FixedScheduleState`] that runs its schedule `rate` times per second.
+    pub fn steps_per_second(rate: f64) -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / rate))
+    }
+
+    /// Limits how many times the schedule may run during a single app update to catch up.
+    ///
+    /// If more time has accumulated than that, the excess is dropped instead of being carried
+    /// over, which keeps a slow frame from causing an ever growing backlog of steps.
+    #[must_use]
+    pub fn with_max_steps_per_update(mut self, max_steps: u32) -> Self {
+        self.max_steps_per_update = Some(max_steps);
+        self
+    }
+
+    /// The amount of time each step takes.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Sets the amount of time each step takes.
+    pub fn set_period(&mut self, period: Duration) {
+        assert!(
+            !period.is_zero(),
+            "the period of a fixed schedule must be positive"
+        );
+        self.period = period;
+    }
+
+    /// The maximum number of steps per app update, if any.
+    pub fn max_steps_per_update(&self) -> Option<u32> {
+        self.max_steps_per_update
+    }
+
+    /// The amount of time accumulated towards the next step.
+    ///
+    /// Once all steps of an update have run, this is the overstep: the time that has passed
+    /// since the last step but isn't enough for another one yet.
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// The accumulated time as a fraction of the period, between `0.0` and `1.0` once all
+    /// steps of an update have run. Useful for interpolating between the last two steps.
+    pub fn overstep_percentage(&self) -> f64 {
+        self.accumulator.as_secs_f64() / self.period.as_secs_f64()
+    }
+
+    /// The number of times the schedule ran during the current (or last) app update.
+    pub fn steps_this_update(&self) -> u32 {
+        self.steps_this_update
+    }
+
+    /// Consumes one period from the accumulator if there is enough time for another step.
+    fn expend(&mut self) -> bool {
+        if self.accumulator < self.period {
+            return false;
+        }
+        if self.max_steps_per_update == Some(self.steps_this_update) {
+            let remainder = self.accumulator.as_nanos() % self.period.as_nanos();
+            self.accumulator = Duration::from_nanos(remainder as u64);
+            return false;
+        }
+        self.accumulator -= self.period;
+        self.steps_this_update += 1;
+        true
+    }
+}
+
+/// A resource that holds every fixed-rate schedule that [`run_fixed_schedules`] should run,
+/// each with its own period and accumulator.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule_v3::{Schedule, ScheduleLabel, Schedules}};
+/// # use bevy_time::{FixedScheduleState, FixedSchedules};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Physics;
+///
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Ai;
+///
+/// let mut world = World::new();
+/// let mut schedules = Schedules::new();
+/// schedules.insert(Physics, Schedule::new());
+/// schedules.insert(Ai, Schedule::new());
+/// world.insert_resource(schedules);
+///
+/// let mut fixed_schedules = FixedSchedules::default();
+/// fixed_schedules.insert(
+///     Physics,
+///     FixedScheduleState::steps_per_second(60.0).with_max_steps_per_update(4),
+/// );
+/// fixed_schedules.insert(Ai, FixedScheduleState::steps_per_second(10.0));
+/// world.insert_resource(fixed_schedules);
+/// ```
+#[derive(Resource, Default)]
+pub struct FixedSchedules {
+    schedules: Vec<(BoxedScheduleLabel, FixedScheduleState)>,
+}
+
+impl FixedSchedules {
+    /// Registers the schedule with the given `label` to run at a fixed rate.
+    ///
+    /// If the schedule was already registered, its state is replaced and the old one is returned.
+    pub fn insert(
+        &mut self,
+        label: impl ScheduleLabel,
+        state: FixedScheduleState,
+    ) -> Option<FixedScheduleState> {
+        if let Some(existing) = self.get_mut(&label) {
+            return Some(std::mem::replace(existing, state));
+        }
+        self.schedules.push((Box::new(label), state));
+        None
+    }
+
+    /// Stops running the schedule with the given `label` at a fixed rate.
+    pub fn remove(&mut self, label: &dyn ScheduleLabel) -> Option<FixedScheduleState> {
+        let index = self
+            .schedules
+            .iter()
+            .position(|(other, _)| &**other == label)?;
+        Some(self.schedules.remove(index).1)
+    }
+
+    /// Returns the [`FixedScheduleState`] of the schedule with the given `label`.
+    pub fn get(&self, label: &dyn ScheduleLabel) -> Option<&FixedScheduleState> {
+        self.schedules
+            .iter()
+            .find(|(other, _)| &**other == label)
+            .map(|(_, state)| state)
+    }
+
+    /// Returns the [`FixedScheduleState`] of the schedule with the given `label` mutably.
+    pub fn get_mut(&mut self, label: &dyn ScheduleLabel) -> Option<&mut FixedScheduleState> {
+        self.schedules
+            .iter_mut()
+            .find(|(other, _)| &**other == label)
+            .map(|(_, state)| state)
+    }
+
+    /// Iterates over all registered schedules in the order they run.
+    pub fn iter(&self) -> impl Iterator<Item = (&dyn ScheduleLabel, &FixedScheduleState)> {
+        self.schedules
+            .iter()
+            .map(|(label, state)| (&**label, state))
+    }
+}
+
+/// The [`FixedScheduleState`] of the fixed-rate schedule that is currently running.
+///
+/// This resource only exists while [`run_fixed_schedules`] runs a schedule, so systems in
+/// different fixed-rate schedules can read their own period.
+#[derive(Resource, Debug)]
+pub struct FixedTime {
+    label: BoxedScheduleLabel,
+    state: FixedScheduleState,
+}
+
+impl FixedTime {
+    /// The label of the schedule that is currently running.
+    pub fn label(&self) -> &dyn ScheduleLabel {
+        &*self.label
+    }
+}
+
+impl Deref for FixedTime {
+    type Target = FixedScheduleState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+/// An exclusive system that advances every schedule in [`FixedSchedules`] by the [`Time`] delta,
+/// and runs each of them as many times as their period fits into their accumulated time.
+///
+/// The schedules are run one after the other: all steps of the first registered schedule run
+/// before the steps of the second one.
+pub fn run_fixed_schedules(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let labels = {
+        let mut fixed_schedules = world.resource_mut::<FixedSchedules>();
+        fixed_schedules
+            .schedules
+            .iter_mut()
+            .map(|(label, state)| {
+                state.accumulator += delta;
+                state.steps_this_update = 0;
+                label.clone()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for label in labels {
+        while let Some(state) = next_step(world, &*label) {
+            world.insert_resource(FixedTime {
+                label: label.clone(),
+                state,
+            });
+            world.run_schedule_ref(&*label);
+        }
+    }
+    world.remove_resource::<FixedTime>();
+}
+
+/// Returns the state to run the next step of the schedule with, if there is time for one.
+fn next_step(world: &mut World, label: &dyn ScheduleLabel) -> Option<FixedScheduleState> {
+    let mut fixed_schedules = world.resource_mut::<FixedSchedules>();
+    let state = fixed_schedules.get_mut(label)?;
+    state.expend().then(|| state.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{
+        prelude::*,
+        schedule_v3::{Schedule, Schedules},
+    };
+    use bevy_utils::Instant;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Fast;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Slow;
+
+    #[derive(Resource, Default)]
+    struct Runs(Vec<(&'static str, Duration)>);
+
+    fn record(name: &'static str) -> impl FnMut(Res<FixedTime>, ResMut<Runs>) {
+        move |fixed_time, mut runs| runs.0.push((name, fixed_time.period()))
+    }
+
+    #[test]
+    fn independent_fixed_schedules() {
+        let mut world = World::new();
+        let instant = Instant::now();
+        let mut time = Time::new(instant);
+        time.update_with_instant(instant);
+        world.insert_resource(time);
+        world.init_resource::<Runs>();
+
+        let mut schedules = Schedules::new();
+        let mut fast = Schedule::new();
+        fast.add_system(record("fast"));
+        schedules.insert(Fast, fast);
+        let mut slow = Schedule::new();
+        slow.add_system(record("slow"));
+        schedules.insert(Slow, slow);
+        world.insert_resource(schedules);
+
+        let mut fixed_schedules = FixedSchedules::default();
+        fixed_schedules.insert(
+            Fast,
+            FixedScheduleState::new(Duration::from_millis(100)).with_max_steps_per_update(3),
+        );
+        fixed_schedules.insert(Slow, FixedScheduleState::new(Duration::from_millis(250)));
+        world.insert_resource(fixed_schedules);
+
+        let advance = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(instant + Duration::from_millis(millis));
+            run_fixed_schedules(world);
+            std::mem::take(&mut world.resource_mut::<Runs>().0)
+        };
+
+        assert_eq!(
+            advance(&mut world, 260),
+            vec![
+                ("fast", Duration::from_millis(100)),
+                ("fast", Duration::from_millis(100)),
+                ("slow", Duration::from_millis(250)),
+            ]
+        );
+        assert!(!world.contains_resource::<FixedTime>());
+
+        let fixed_schedules = world.resource::<FixedSchedules>();
+        assert_eq!(fixed_schedules.get(&Fast).unwrap().steps_this_update(), 2);
+        assert_eq!(
+            fixed_schedules.get(&Fast).unwrap().accumulator(),
+            Duration::from_millis(60)
+        );
+        assert_eq!(
+            fixed_schedules.get(&Slow).unwrap().accumulator(),
+            Duration::from_millis(10)
+        );
+
+        // a long frame only catches up 3 steps of the fast schedule and drops the rest
+        let runs = advance(&mut world, 1000);
+        assert_eq!(runs.iter().filter(|(name, _)| *name == "fast").count(), 3);
+        assert_eq!(runs.iter().filter(|(name, _)| *name == "slow").count(), 3);
+        let fixed_schedules = world.resource::<FixedSchedules>();
+        assert_eq!(
+            fixed_schedules.get(&Fast).unwrap().accumulator(),
+            Duration::from_millis(0)
+        );
+        assert_eq!(
+            fixed_schedules.get(&Slow).unwrap().accumulator(),
+            Duration::from_millis(0)
+        );
+    }
+}
@@ -1,9 +1,11 @@
+mod fixed_schedule;
 mod fixed_timestep;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
+pub use fixed_schedule::*;
 pub use fixed_timestep::*;
 pub use stopwatch::*;
 pub use time::*;
@@ -36,12 +38,15 @@ impl Plugin for TimePlugin {
         app.init_resource::<Time>()
             .init_resource::<TimeUpdateStrategy>()
             .init_resource::<FixedTimesteps>()
+            .init_resource::<FixedSchedules>()
             .register_type::<Timer>()
             .register_type::<Time>()
             .register_type::<Stopwatch>()
             // time system is added as an "exclusive system" to ensure it runs before other systems
             // in CoreStage::First
-            .add_system_to_stage(CoreStage::First, time_system.at_start().label(TimeSystem));
+            .add_system_to_stage(CoreStage::First, time_system.at_start().label(TimeSystem))
+            // fixed-rate schedules run before any regular update systems
+            .add_system_to_stage(CoreStage::Update, run_fixed_schedules.at_start());
     }
 }
 
@@ -1,12 +1,16 @@
+use taffy::style_helpers;
+
 use crate::{
-    AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, JustifyContent,
-    PositionType, Size, Style, UiRect, Val,
+    AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, GridTrack, GridTrackRepetition, JustifyContent, MaxTrackSizingFunction,
+    MinTrackSizingFunction, PositionType, RepeatedGridTrack, Size, Style, UiRect, Val,
 };
 
-pub fn from_rect(
+pub fn from_rect<T>(
     scale_factor: f64,
     rect: UiRect,
-) -> taffy::geometry::Rect<taffy::style::Dimension> {
+    from_val: impl Fn(f64, Val) -> T,
+) -> taffy::geometry::Rect<T> {
     taffy::geometry::Rect {
         left: from_val(scale_factor, rect.left),
         right: from_val(scale_factor, rect.right),
@@ -35,17 +39,19 @@ pub fn from_val_size(
 pub fn from_style(scale_factor: f64, value: &Style) -> taffy::style::Style {
     taffy::style::Style {
         display: value.display.into(),
-        position_type: value.position_type.into(),
+        position: value.position_type.into(),
         flex_direction: value.flex_direction.into(),
         flex_wrap: value.flex_wrap.into(),
-        align_items: value.align_items.into(),
+        align_items: Some(value.align_items.into()),
         align_self: value.align_self.into(),
-        align_content: value.align_content.into(),
-        justify_content: value.justify_content.into(),
-        position: from_rect(scale_factor, value.position),
-        margin: from_rect(scale_factor, value.margin),
-        padding: from_rect(scale_factor, value.padding),
-        border: from_rect(scale_factor, value.border),
+        justify_items: None,
+        justify_self: None,
+        align_content: Some(value.align_content.into()),
+        justify_content: Some(value.justify_content.into()),
+        inset: from_rect(scale_factor, value.position, from_val_inset),
+        margin: from_rect(scale_factor, value.margin, from_val_margin),
+        padding: from_rect(scale_factor, value.padding, from_val_length_percentage),
+        border: from_rect(scale_factor, value.border, from_val_length_percentage),
         flex_grow: value.flex_grow,
         flex_shrink: value.flex_shrink,
         flex_basis: from_val(scale_factor, value.flex_basis),
@@ -53,7 +59,17 @@ pub fn from_style(scale_factor: f64, value: &Style) -> taffy::style::Style {
         min_size: from_val_size(scale_factor, value.min_size),
         max_size: from_val_size(scale_factor, value.max_size),
         aspect_ratio: value.aspect_ratio,
-        gap: from_val_size(scale_factor, value.gap),
+        gap: taffy::geometry::Size {
+            width: from_val_length_percentage(scale_factor, value.gap.width),
+            height: from_val_length_percentage(scale_factor, value.gap.height),
+        },
+        grid_auto_flow: value.grid_auto_flow.into(),
+        grid_template_rows: from_repeated_tracks(scale_factor, &value.grid_template_rows),
+        grid_template_columns: from_repeated_tracks(scale_factor, &value.grid_template_columns),
+        grid_auto_rows: from_tracks(scale_factor, &value.grid_auto_rows),
+        grid_auto_columns: from_tracks(scale_factor, &value.grid_auto_columns),
+        grid_row: value.grid_row.into(),
+        grid_column: value.grid_column.into(),
     }
 }
 
@@ -66,12 +82,177 @@ pub fn val_to_f32(scale_factor: f64, val: Val) -> f32 {
     }
 }
 
+/// Converts a size, where [`Val::Undefined`] is [`Val::Auto`].
 pub fn from_val(scale_factor: f64, val: Val) -> taffy::style::Dimension {
     match val {
-        Val::Auto => taffy::style::Dimension::Auto,
+        Val::Auto | Val::Undefined => taffy::style::Dimension::Auto,
         Val::Percent(value) => taffy::style::Dimension::Percent(value / 100.0),
         Val::Px(value) => taffy::style::Dimension::Points((scale_factor * value as f64) as f32),
-        Val::Undefined => taffy::style::Dimension::Undefined,
+    }
+}
+
+/// Converts a length that can't be automatic, where [`Val::Undefined`] and [`Val::Auto`] are
+/// zero.
+pub fn from_val_length_percentage(scale_factor: f64, val: Val) -> taffy::style::LengthPercentage {
+    match val {
+        Val::Auto | Val::Undefined => taffy::style::LengthPercentage::Points(0.0),
+        Val::Percent(value) => taffy::style::LengthPercentage::Percent(value / 100.0),
+        Val::Px(value) => {
+            taffy::style::LengthPercentage::Points((scale_factor * value as f64) as f32)
+        }
+    }
+}
+
+/// Converts a margin, where [`Val::Undefined`] is zero.
+pub fn from_val_margin(scale_factor: f64, val: Val) -> taffy::style::LengthPercentageAuto {
+    match val {
+        Val::Auto => taffy::style::LengthPercentageAuto::Auto,
+        Val::Undefined => taffy::style::LengthPercentageAuto::Points(0.0),
+        Val::Percent(value) => taffy::style::LengthPercentageAuto::Percent(value / 100.0),
+        Val::Px(value) => {
+            taffy::style::LengthPercentageAuto::Points((scale_factor * value as f64) as f32)
+        }
+    }
+}
+
+/// Converts a position, where [`Val::Undefined`] is [`Val::Auto`].
+pub fn from_val_inset(scale_factor: f64, val: Val) -> taffy::style::LengthPercentageAuto {
+    match val {
+        Val::Auto | Val::Undefined => taffy::style::LengthPercentageAuto::Auto,
+        val => from_val_margin(scale_factor, val),
+    }
+}
+
+fn from_min_track_sizing_function(
+    scale_factor: f64,
+    value: MinTrackSizingFunction,
+) -> taffy::style::MinTrackSizingFunction {
+    match value {
+        MinTrackSizingFunction::Px(value) => taffy::style::MinTrackSizingFunction::Fixed(
+            from_val_length_percentage(scale_factor, Val::Px(value)),
+        ),
+        MinTrackSizingFunction::Percent(value) => taffy::style::MinTrackSizingFunction::Fixed(
+            from_val_length_percentage(scale_factor, Val::Percent(value)),
+        ),
+        MinTrackSizingFunction::MinContent => taffy::style::MinTrackSizingFunction::MinContent,
+        MinTrackSizingFunction::MaxContent => taffy::style::MinTrackSizingFunction::MaxContent,
+        MinTrackSizingFunction::Auto => taffy::style::MinTrackSizingFunction::Auto,
+    }
+}
+
+fn from_max_track_sizing_function(
+    scale_factor: f64,
+    value: MaxTrackSizingFunction,
+) -> taffy::style::MaxTrackSizingFunction {
+    match value {
+        MaxTrackSizingFunction::Px(value) => taffy::style::MaxTrackSizingFunction::Fixed(
+            from_val_length_percentage(scale_factor, Val::Px(value)),
+        ),
+        MaxTrackSizingFunction::Percent(value) => taffy::style::MaxTrackSizingFunction::Fixed(
+            from_val_length_percentage(scale_factor, Val::Percent(value)),
+        ),
+        MaxTrackSizingFunction::MinContent => taffy::style::MaxTrackSizingFunction::MinContent,
+        MaxTrackSizingFunction::MaxContent => taffy::style::MaxTrackSizingFunction::MaxContent,
+        MaxTrackSizingFunction::FitContentPx(value) => {
+            taffy::style::MaxTrackSizingFunction::FitContent(from_val_length_percentage(
+                scale_factor,
+                Val::Px(value),
+            ))
+        }
+        MaxTrackSizingFunction::FitContentPercent(value) => {
+            taffy::style::MaxTrackSizingFunction::FitContent(from_val_length_percentage(
+                scale_factor,
+                Val::Percent(value),
+            ))
+        }
+        MaxTrackSizingFunction::Auto => taffy::style::MaxTrackSizingFunction::Auto,
+        MaxTrackSizingFunction::Fraction(value) => {
+            taffy::style::MaxTrackSizingFunction::Flex(value)
+        }
+    }
+}
+
+fn from_track(scale_factor: f64, track: GridTrack) -> taffy::style::NonRepeatedTrackSizingFunction {
+    style_helpers::minmax(
+        from_min_track_sizing_function(scale_factor, track.min_sizing_function),
+        from_max_track_sizing_function(scale_factor, track.max_sizing_function),
+    )
+}
+
+pub fn from_tracks(
+    scale_factor: f64,
+    tracks: &[GridTrack],
+) -> Vec<taffy::style::NonRepeatedTrackSizingFunction> {
+    tracks
+        .iter()
+        .map(|track| from_track(scale_factor, *track))
+        .collect()
+}
+
+pub fn from_repeated_tracks(
+    scale_factor: f64,
+    tracks: &[RepeatedGridTrack],
+) -> Vec<taffy::style::TrackSizingFunction> {
+    tracks
+        .iter()
+        .map(
+            |repeated| match (repeated.repetition, repeated.tracks.as_slice()) {
+                (GridTrackRepetition::Count(1), [track]) => {
+                    taffy::style::TrackSizingFunction::Single(from_track(scale_factor, *track))
+                }
+                (repetition, tracks) => {
+                    style_helpers::repeat(repetition, from_tracks(scale_factor, tracks))
+                }
+            },
+        )
+        .collect()
+}
+
+impl From<GridTrackRepetition> for taffy::style::GridTrackRepetition {
+    fn from(value: GridTrackRepetition) -> Self {
+        match value {
+            GridTrackRepetition::Count(count) => taffy::style::GridTrackRepetition::Count(count),
+            GridTrackRepetition::AutoFill => taffy::style::GridTrackRepetition::AutoFill,
+            GridTrackRepetition::AutoFit => taffy::style::GridTrackRepetition::AutoFit,
+        }
+    }
+}
+
+impl From<GridPlacement> for taffy::geometry::Line<taffy::style::GridPlacement> {
+    fn from(value: GridPlacement) -> Self {
+        // Line 0 is invalid in CSS, so it is ignored like a missing line
+        let start = value.start.filter(|line| *line != 0);
+        let end = value.end.filter(|line| *line != 0);
+        let span = taffy::style::GridPlacement::Span(value.span.unwrap_or(1).max(1));
+        match (start, end) {
+            (Some(start), Some(end)) => taffy::geometry::Line {
+                start: style_helpers::line(start),
+                end: style_helpers::line(end),
+            },
+            (Some(start), None) => taffy::geometry::Line {
+                start: style_helpers::line(start),
+                end: span,
+            },
+            (None, Some(end)) => taffy::geometry::Line {
+                start: span,
+                end: style_helpers::line(end),
+            },
+            (None, None) => taffy::geometry::Line {
+                start: span,
+                end: taffy::style::GridPlacement::Auto,
+            },
+        }
+    }
+}
+
+impl From<GridAutoFlow> for taffy::style::GridAutoFlow {
+    fn from(value: GridAutoFlow) -> Self {
+        match value {
+            GridAutoFlow::Row => taffy::style::GridAutoFlow::Row,
+            GridAutoFlow::Column => taffy::style::GridAutoFlow::Column,
+            GridAutoFlow::RowDense => taffy::style::GridAutoFlow::RowDense,
+            GridAutoFlow::ColumnDense => taffy::style::GridAutoFlow::ColumnDense,
+        }
     }
 }
 
@@ -87,15 +268,15 @@ impl From<AlignItems> for taffy::style::AlignItems {
     }
 }
 
-impl From<AlignSelf> for taffy::style::AlignSelf {
+impl From<AlignSelf> for Option<taffy::style::AlignSelf> {
     fn from(value: AlignSelf) -> Self {
         match value {
-            AlignSelf::Auto => taffy::style::AlignSelf::Auto,
-            AlignSelf::FlexStart => taffy::style::AlignSelf::FlexStart,
-            AlignSelf::FlexEnd => taffy::style::AlignSelf::FlexEnd,
-            AlignSelf::Center => taffy::style::AlignSelf::Center,
-            AlignSelf::Baseline => taffy::style::AlignSelf::Baseline,
-            AlignSelf::Stretch => taffy::style::AlignSelf::Stretch,
+            AlignSelf::Auto => None,
+            AlignSelf::FlexStart => taffy::style::AlignSelf::FlexStart.into(),
+            AlignSelf::FlexEnd => taffy::style::AlignSelf::FlexEnd.into(),
+            AlignSelf::Center => taffy::style::AlignSelf::Center.into(),
+            AlignSelf::Baseline => taffy::style::AlignSelf::Baseline.into(),
+            AlignSelf::Stretch => taffy::style::AlignSelf::Stretch.into(),
         }
     }
 }
@@ -117,6 +298,7 @@ impl From<Display> for taffy::style::Display {
     fn from(value: Display) -> Self {
         match value {
             Display::Flex => taffy::style::Display::Flex,
+            Display::Grid => taffy::style::Display::Grid,
             Display::None => taffy::style::Display::None,
         }
     }
@@ -146,11 +328,11 @@ impl From<JustifyContent> for taffy::style::JustifyContent {
     }
 }
 
-impl From<PositionType> for taffy::style::PositionType {
+impl From<PositionType> for taffy::style::Position {
     fn from(value: PositionType) -> Self {
         match value {
-            PositionType::Relative => taffy::style::PositionType::Relative,
-            PositionType::Absolute => taffy::style::PositionType::Absolute,
+            PositionType::Relative => taffy::style::Position::Relative,
+            PositionType::Absolute => taffy::style::Position::Absolute,
         }
     }
 }
@@ -164,3 +346,83 @@ impl From<FlexWrap> for taffy::style::FlexWrap {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use taffy::{geometry::Line, style::GridPlacement as TaffyGridPlacement};
+
+    fn into_taffy(placement: GridPlacement) -> Line<TaffyGridPlacement> {
+        placement.into()
+    }
+
+    #[test]
+    fn grid_placement_into_taffy() {
+        assert_eq!(
+            into_taffy(GridPlacement::auto()),
+            Line {
+                start: TaffyGridPlacement::Span(1),
+                end: TaffyGridPlacement::Auto,
+            }
+        );
+        assert_eq!(
+            into_taffy(GridPlacement::start_span(2, 3)),
+            Line {
+                start: style_helpers::line(2),
+                end: TaffyGridPlacement::Span(3),
+            }
+        );
+        assert_eq!(
+            into_taffy(GridPlacement::end_span(-1, 2)),
+            Line {
+                start: TaffyGridPlacement::Span(2),
+                end: style_helpers::line(-1),
+            }
+        );
+        assert_eq!(
+            into_taffy(GridPlacement {
+                start: Some(1),
+                span: Some(5),
+                end: Some(3),
+            }),
+            Line {
+                start: style_helpers::line(1),
+                end: style_helpers::line(3),
+            }
+        );
+        // Line 0 is ignored
+        assert_eq!(
+            into_taffy(GridPlacement::start(0)),
+            into_taffy(GridPlacement::auto())
+        );
+    }
+
+    #[test]
+    fn repeated_tracks_into_taffy() {
+        let tracks = from_repeated_tracks(
+            1.0,
+            &[GridTrack::px(10.0).into(), RepeatedGridTrack::fr(3, 1.0)],
+        );
+
+        assert_eq!(
+            tracks,
+            vec![
+                taffy::style::TrackSizingFunction::Single(style_helpers::minmax(
+                    taffy::style::MinTrackSizingFunction::Fixed(
+                        taffy::style::LengthPercentage::Points(10.0)
+                    ),
+                    taffy::style::MaxTrackSizingFunction::Fixed(
+                        taffy::style::LengthPercentage::Points(10.0)
+                    ),
+                )),
+                style_helpers::repeat(
+                    3u16,
+                    vec![style_helpers::minmax(
+                        taffy::style::MinTrackSizingFunction::Auto,
+                        taffy::style::MaxTrackSizingFunction::Flex(1.0),
+                    )],
+                ),
+            ]
+        );
+    }
+}
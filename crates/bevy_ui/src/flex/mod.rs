@@ -1,6 +1,6 @@
 mod convert;
 
-use crate::{CalculatedSize, Node, Style, UiScale};
+use crate::{ContentSize, Measure, Node, Overflow, ScrollPosition, Style, UiScale, Val, WorldUi};
 use bevy_ecs::{
     change_detection::DetectChanges,
     entity::Entity,
@@ -14,7 +14,7 @@ use bevy_math::Vec2;
 use bevy_transform::components::Transform;
 use bevy_utils::HashMap;
 use bevy_window::{PrimaryWindow, Window, WindowResolution, WindowScaleFactorChanged};
-use std::fmt;
+use std::{fmt, sync::Arc};
 use taffy::{
     prelude::{AvailableSpace, Size},
     Taffy,
@@ -24,6 +24,7 @@ use taffy::{
 pub struct FlexSurface {
     entity_to_taffy: HashMap<Entity, taffy::node::Node>,
     window_nodes: HashMap<Entity, taffy::node::Node>,
+    world_ui_nodes: HashMap<Entity, taffy::node::Node>,
     taffy: Taffy,
 }
 
@@ -45,6 +46,7 @@ impl fmt::Debug for FlexSurface {
         f.debug_struct("FlexSurface")
             .field("entity_to_taffy", &self.entity_to_taffy)
             .field("window_nodes", &self.window_nodes)
+            .field("world_ui_nodes", &self.world_ui_nodes)
             .finish()
     }
 }
@@ -54,6 +56,7 @@ impl Default for FlexSurface {
         Self {
             entity_to_taffy: Default::default(),
             window_nodes: Default::default(),
+            world_ui_nodes: Default::default(),
             taffy: Taffy::new(),
         }
     }
@@ -71,6 +74,8 @@ impl FlexSurface {
 
         if !added {
             self.taffy.set_style(*taffy_node, taffy_style).unwrap();
+            // The node may have been measured by the content it no longer has
+            self.taffy.set_measure(*taffy_node, None).unwrap();
         }
     }
 
@@ -78,34 +83,28 @@ impl FlexSurface {
         &mut self,
         entity: Entity,
         style: &Style,
-        calculated_size: CalculatedSize,
+        measure: Arc<dyn Measure>,
         scale_factor: f64,
     ) {
-        let taffy = &mut self.taffy;
         let taffy_style = convert::from_style(scale_factor, style);
+        // The layout is computed in physical pixels, and the content measured in logical pixels
         let measure = taffy::node::MeasureFunc::Boxed(Box::new(
-            move |constraints: Size<Option<f32>>, _available: Size<AvailableSpace>| {
-                let mut size = convert::from_f32_size(scale_factor, calculated_size.size);
-                match (constraints.width, constraints.height) {
-                    (None, None) => {}
-                    (Some(width), None) => {
-                        if calculated_size.preserve_aspect_ratio {
-                            size.height = width * size.height / size.width;
-                        }
-                        size.width = width;
-                    }
-                    (None, Some(height)) => {
-                        if calculated_size.preserve_aspect_ratio {
-                            size.width = height * size.width / size.height;
-                        }
-                        size.height = height;
-                    }
-                    (Some(width), Some(height)) => {
-                        size.width = width;
-                        size.height = height;
-                    }
+            move |constraints: Size<Option<f32>>, available: Size<AvailableSpace>| {
+                let to_logical = |value: f32| (value as f64 / scale_factor) as f32;
+                let available_to_logical = |space: AvailableSpace| match space {
+                    AvailableSpace::Definite(value) => AvailableSpace::Definite(to_logical(value)),
+                    space => space,
+                };
+                let size = measure.measure(
+                    constraints.width.map(to_logical),
+                    constraints.height.map(to_logical),
+                    available_to_logical(available.width),
+                    available_to_logical(available.height),
+                );
+                Size {
+                    width: (size.x as f64 * scale_factor) as f32,
+                    height: (size.y as f64 * scale_factor) as f32,
                 }
-                size
             },
         ));
 
@@ -113,7 +112,10 @@ impl FlexSurface {
             self.taffy.set_style(*taffy_node, taffy_style).unwrap();
             self.taffy.set_measure(*taffy_node, Some(measure)).unwrap();
         } else {
-            let taffy_node = taffy.new_leaf(taffy_style).unwrap();
+            let taffy_node = self
+                .taffy
+                .new_leaf_with_measure(taffy_style, measure)
+                .unwrap();
             self.entity_to_taffy.insert(entity, taffy_node);
         }
     }
@@ -181,8 +183,46 @@ without UI components as a child of an entity with UI components, results may be
         self.taffy.set_children(*taffy_node, &child_nodes).unwrap();
     }
 
+    /// Lays out the root nodes with a [`WorldUi`] in their own area of the given physical size,
+    /// instead of in a window, and removes the areas of the other nodes.
+    pub fn update_world_uis(&mut self, roots: impl Iterator<Item = (Entity, Vec2)>) {
+        let mut world_ui_nodes = HashMap::default();
+        for (root, size) in roots {
+            let Some(root_node) = self.entity_to_taffy.get(&root) else {
+                continue;
+            };
+            let taffy_style = taffy::style::Style {
+                size: taffy::geometry::Size {
+                    width: taffy::style::Dimension::Points(size.x),
+                    height: taffy::style::Dimension::Points(size.y),
+                },
+                ..Default::default()
+            };
+            let taffy = &mut self.taffy;
+            let node = match self.world_ui_nodes.remove(&root) {
+                Some(node) => {
+                    taffy.set_style(node, taffy_style).unwrap();
+                    node
+                }
+                None => taffy.new_leaf(taffy_style).unwrap(),
+            };
+            taffy.set_children(node, &[*root_node]).unwrap();
+            world_ui_nodes.insert(root, node);
+        }
+
+        // the remaining nodes are the areas of the roots without a `WorldUi` anymore
+        for node in self.world_ui_nodes.values() {
+            self.taffy.remove(*node).unwrap();
+        }
+        self.world_ui_nodes = world_ui_nodes;
+    }
+
     pub fn compute_window_layouts(&mut self) {
-        for window_node in self.window_nodes.values() {
+        for window_node in self
+            .window_nodes
+            .values()
+            .chain(self.world_ui_nodes.values())
+        {
             self.taffy
                 .compute_layout(*window_node, Size::MAX_CONTENT)
                 .unwrap();
@@ -198,6 +238,12 @@ without UI components as a child of an entity with UI components, results may be
         }
     }
 
+    /// The layout of the entity, or `None` if it is not a UI node
+    fn get_layout_if_exists(&self, entity: Entity) -> Option<&taffy::layout::Layout> {
+        let taffy_node = self.entity_to_taffy.get(&entity)?;
+        self.taffy.layout(*taffy_node).ok()
+    }
+
     pub fn get_layout(&self, entity: Entity) -> Result<&taffy::layout::Layout, FlexError> {
         if let Some(taffy_node) = self.entity_to_taffy.get(&entity) {
             self.taffy
@@ -226,16 +272,23 @@ pub fn flex_node_system(
     ui_scale: Res<UiScale>,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut flex_surface: ResMut<FlexSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    node_query: Query<(Entity, &Style, Option<&CalculatedSize>), (With<Node>, Changed<Style>)>,
-    full_node_query: Query<(Entity, &Style, Option<&CalculatedSize>), With<Node>>,
-    changed_size_query: Query<
-        (Entity, &Style, &CalculatedSize),
-        (With<Node>, Changed<CalculatedSize>),
-    >,
+    root_node_query: Query<(Entity, Option<&WorldUi>), (With<Node>, Without<Parent>)>,
+    node_query: Query<(Entity, &Style, Option<&ContentSize>), (With<Node>, Changed<Style>)>,
+    full_node_query: Query<(Entity, &Style, Option<&ContentSize>), With<Node>>,
+    changed_size_query: Query<(Entity, &Style, &ContentSize), (With<Node>, Changed<ContentSize>)>,
     children_query: Query<(Entity, &Children), (With<Node>, Changed<Children>)>,
     removed_children: RemovedComponents<Children>,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
+    mut scroll_query: Query<
+        (
+            Entity,
+            &Style,
+            Option<&Children>,
+            Option<&Parent>,
+            &mut ScrollPosition,
+        ),
+        With<Node>,
+    >,
     removed_nodes: RemovedComponents<Node>,
 ) {
     // assume one window for time being...
@@ -257,15 +310,14 @@ pub fn flex_node_system(
     fn update_changed<F: ReadOnlyWorldQuery>(
         flex_surface: &mut FlexSurface,
         scaling_factor: f64,
-        query: Query<(Entity, &Style, Option<&CalculatedSize>), F>,
+        query: Query<(Entity, &Style, Option<&ContentSize>), F>,
     ) {
         // update changed nodes
-        for (entity, style, calculated_size) in &query {
+        for (entity, style, content_size) in &query {
             // TODO: remove node from old hierarchy if its root has changed
-            if let Some(calculated_size) = calculated_size {
-                flex_surface.upsert_leaf(entity, style, *calculated_size, scaling_factor);
-            } else {
-                flex_surface.upsert_node(entity, style, scaling_factor);
+            match content_size.and_then(|content_size| content_size.measure.clone()) {
+                Some(measure) => flex_surface.upsert_leaf(entity, style, measure, scaling_factor),
+                None => flex_surface.upsert_node(entity, style, scaling_factor),
             }
         }
     }
@@ -276,15 +328,28 @@ pub fn flex_node_system(
         update_changed(&mut flex_surface, scale_factor, node_query);
     }
 
-    for (entity, style, calculated_size) in &changed_size_query {
-        flex_surface.upsert_leaf(entity, style, *calculated_size, scale_factor);
+    for (entity, style, content_size) in &changed_size_query {
+        if let Some(measure) = content_size.measure.clone() {
+            flex_surface.upsert_leaf(entity, style, measure, scale_factor);
+        }
     }
 
     // clean up removed nodes
     flex_surface.remove_entities(&removed_nodes);
 
     // update window children (for now assuming all Nodes live in the primary window)
-    flex_surface.set_window_children(primary_window_entity, root_node_query.iter());
+    flex_surface.set_window_children(
+        primary_window_entity,
+        root_node_query
+            .iter()
+            .filter(|(_, world_ui)| world_ui.is_none())
+            .map(|(entity, _)| entity),
+    );
+
+    // the roots in the world are laid out in their own area instead of the window
+    flex_surface.update_world_uis(root_node_query.iter().filter_map(|(entity, world_ui)| {
+        world_ui.map(|world_ui| (entity, world_ui.size * logical_to_physical_factor as f32))
+    }));
 
     // update and remove children
     for entity in &removed_children {
@@ -301,6 +366,51 @@ pub fn flex_node_system(
 
     let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
 
+    // clamp the scroll positions to the content of the nodes, the extent of their children and
+    // their padding
+    let mut scroll_offsets = HashMap::default();
+    for (entity, style, children, parent, mut scroll_position) in &mut scroll_query {
+        let Some(layout) = flex_surface.get_layout_if_exists(entity) else {
+            continue;
+        };
+        let content_end = children
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| flex_surface.get_layout_if_exists(*child))
+            .fold(Vec2::ZERO, |end, child| {
+                end.max(Vec2::new(
+                    child.location.x + child.size.width,
+                    child.location.y + child.size.height,
+                ))
+            });
+        // Like the layout, percentages of the padding and the border are relative to the width
+        // of the parent
+        let parent_width = parent
+            .and_then(|parent| flex_surface.get_layout_if_exists(parent.get()))
+            .map_or(0.0, |parent| parent.size.width);
+        let resolve = |val: Val| match val {
+            Val::Px(value) => (scale_factor * value as f64) as f32,
+            Val::Percent(percent) => parent_width * percent / 100.0,
+            Val::Auto | Val::Undefined => 0.0,
+        };
+        let padding_end = Vec2::new(
+            resolve(style.padding.right) + resolve(style.border.right),
+            resolve(style.padding.bottom) + resolve(style.border.bottom),
+        );
+        let size = Vec2::new(layout.size.width, layout.size.height);
+        let max_offset = (content_end + padding_end - size).max(Vec2::ZERO);
+        let max_offset = Vec2::new(to_logical(max_offset.x), to_logical(max_offset.y));
+        let offset = scroll_position.offset.clamp(Vec2::ZERO, max_offset);
+        // only trigger change detection when the new values are different
+        if scroll_position.offset != offset || scroll_position.max_offset != max_offset {
+            scroll_position.offset = offset;
+            scroll_position.max_offset = max_offset;
+        }
+        if style.overflow == Overflow::Scroll {
+            scroll_offsets.insert(entity, offset);
+        }
+    }
+
     // PERF: try doing this incrementally
     for (entity, mut node, mut transform, parent) in &mut node_transform_query {
         let layout = flex_surface.get_layout(entity).unwrap();
@@ -320,6 +430,11 @@ pub fn flex_node_system(
                 new_position.x -= to_logical(parent_layout.size.width / 2.0);
                 new_position.y -= to_logical(parent_layout.size.height / 2.0);
             }
+            // the children of scrolled nodes are moved by the scroll offset
+            if let Some(offset) = scroll_offsets.get(&**parent) {
+                new_position.x -= offset.x;
+                new_position.y -= offset.y;
+            }
         }
         // only trigger change detection when the new value is different
         if transform.translation != new_position {
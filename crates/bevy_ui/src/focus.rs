@@ -1,4 +1,5 @@
-use crate::{camera_config::UiCameraConfig, CalculatedClip, Node, UiStack};
+use crate::{camera_config::UiCameraConfig, CalculatedClip, Node, UiStack, WorldUiStacks};
+use bevy_core_pipeline::core_3d::Camera3d;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
@@ -129,14 +130,19 @@ pub struct NodeQuery {
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
 /// Entities with a hidden [`ComputedVisibility`] are always treated as released.
+///
+/// The nodes of the [`WorldUiStacks`] are interacted with by the ray of the cursor from the first
+/// active 3D camera, after the nodes on the screen and from the closest tree to the furthest.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
     camera: Query<(&Camera, Option<&UiCameraConfig>)>,
+    world_camera: Query<(&Camera, &GlobalTransform, Option<&UiCameraConfig>), With<Camera3d>>,
     windows: Query<&Window>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
     ui_stack: Res<UiStack>,
+    world_ui_stacks: Res<WorldUiStacks>,
     mut node_query: Query<NodeQuery>,
     primary_window: Query<Entity, With<PrimaryWindow>>,
 ) {
@@ -189,15 +195,70 @@ pub fn ui_focus_system(
         })
         .or_else(|| touches_input.first_pressed_position());
 
+    // the ray of the cursor through the viewport of the first active 3D camera
+    let cursor_ray = world_camera
+        .iter()
+        .filter(|(camera, _, camera_ui)| camera.is_active && !is_ui_disabled(*camera_ui))
+        .find_map(|(camera, camera_transform, _)| {
+            let window_ref = match camera.target.normalize(primary_window) {
+                Some(NormalizedRenderTarget::Window(window_ref)) => window_ref,
+                _ => return None,
+            };
+            let cursor_position = windows
+                .get(window_ref.entity())
+                .ok()
+                .and_then(|window| {
+                    window.cursor.position.map(|mut cursor_pos| {
+                        cursor_pos.y = window.height() as f64 - cursor_pos.y;
+                        cursor_pos.as_vec2()
+                    })
+                })
+                .or_else(|| touches_input.first_pressed_position())?;
+            // the viewport positions of the camera have their origin at the bottom left corner
+            let (viewport_min, viewport_max) = camera.logical_viewport_rect()?;
+            let viewport_position = Vec2::new(
+                cursor_position.x - viewport_min.x,
+                viewport_max.y - cursor_position.y,
+            );
+            camera.viewport_to_world(camera_transform, viewport_position)
+        });
+
+    // the world-space UI hit by the cursor ray, from the closest to the furthest, then the
+    // other ones without cursor position
+    let mut world_ui_hits = world_ui_stacks
+        .stacks
+        .iter()
+        .map(|stack| {
+            let hit = cursor_ray.and_then(|ray| stack.ray_hit(ray));
+            (stack, hit)
+        })
+        .collect::<Vec<_>>();
+    world_ui_hits.sort_by(|(_, a), (_, b)| {
+        let a = a.map_or(f32::INFINITY, |(_, distance)| distance);
+        let b = b.map_or(f32::INFINITY, |(_, distance)| distance);
+        a.total_cmp(&b)
+    });
+
+    // the nodes on the screen are interacted with first, then the ones in the world, each with
+    // the cursor position in the space of their tree
+    let stacks = std::iter::once((ui_stack.uinodes.as_slice(), cursor_position)).chain(
+        world_ui_hits
+            .iter()
+            .map(|(stack, hit)| (stack.uinodes.as_slice(), hit.map(|(position, _)| position))),
+    );
+
     // prepare an iterator that contains all the nodes that have the cursor in their rect,
     // from the top node to the bottom one. this will also reset the interaction to `None`
     // for all nodes encountered that are no longer hovered.
-    let mut moused_over_nodes = ui_stack
-        .uinodes
-        .iter()
-        // reverse the iterator to traverse the tree from closest nodes to furthest
-        .rev()
-        .filter_map(|entity| {
+    let mut moused_over_nodes = stacks
+        .flat_map(|(uinodes, cursor_position)| {
+            uinodes
+                .iter()
+                // reverse the iterator to traverse the tree from closest nodes to furthest
+                .rev()
+                .map(move |entity| (entity, cursor_position))
+        })
+        .filter_map(|(entity, cursor_position)| {
             if let Ok(node) = node_query.get_mut(*entity) {
                 // Nodes that are not rendered should not be interactable
                 if let Some(computed_visibility) = node.computed_visibility {
@@ -235,7 +296,11 @@ pub fn ui_focus_system(
                     normalized: relative_cursor_position,
                 };
 
-                let contains_cursor = relative_cursor_position_component.mouse_over();
+                // The clipped parts of the node are not interacted with
+                let contains_cursor = relative_cursor_position_component.mouse_over()
+                    && node.calculated_clip.map_or(true, |clip| {
+                        cursor_position.map_or(false, |position| clip.contains(position))
+                    });
 
                 // Save the relative cursor position to the correct component
                 if let Some(mut node_relative_cursor_position_component) =
@@ -5,9 +5,14 @@
 mod flex;
 mod focus;
 mod geometry;
+mod measurement;
+mod navigation;
 mod render;
+mod scroll;
 mod stack;
+mod transition;
 mod ui_node;
+mod world_ui;
 
 pub mod camera_config;
 pub mod node_bundles;
@@ -18,20 +23,35 @@ use bevy_render::{camera::CameraUpdateSystem, extract_component::ExtractComponen
 pub use flex::*;
 pub use focus::*;
 pub use geometry::*;
+pub use measurement::*;
+pub use navigation::*;
 pub use render::*;
+pub use scroll::*;
+pub use transition::*;
 pub use ui_node::*;
+pub use world_ui::*;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        camera_config::*, geometry::*, node_bundles::*, ui_node::*, widget::Button, Interaction,
-        UiScale,
+        camera_config::*,
+        geometry::*,
+        node_bundles::*,
+        ui_node::*,
+        widget::{
+            Button, FocusedTextInput, ScrollAxis, Scrollbar, TextInput, TextInputChanged,
+            TextInputSubmitted,
+        },
+        EaseFunction, FocusChanged, FocusOutline, Interaction, ScrollPosition, TabIndex,
+        UiAnimator, UiExit, UiFocus, UiMaterial, UiMaterialPlugin, UiProperty, UiScale,
+        UiStateScoped, UiTransitions, UiTween, WorldUi, WorldUiOrientation,
     };
 }
 
 use bevy_app::prelude::*;
 use bevy_ecs::{
+    entity::Entity,
     schedule::{IntoSystemDescriptor, SystemLabel},
     system::Resource,
 };
@@ -57,6 +77,16 @@ pub enum UiSystem {
     Focus,
     /// After this label, the [`UiStack`] resource has been updated
     Stack,
+    /// After this label, the [`ScrollPosition`] of UI nodes have been updated by input for this
+    /// frame
+    Scroll,
+    /// After this label, the [`UiFocus`] has been moved by input for this frame
+    Navigation,
+    /// After this label, the transforms of the [`WorldUiStacks`] have been updated
+    WorldUi,
+    /// After this label, the properties animated by the [`UiAnimator`]s have been updated for this
+    /// frame
+    Animation,
 }
 
 /// The current scale of the UI.
@@ -81,18 +111,42 @@ impl Plugin for UiPlugin {
             .init_resource::<FlexSurface>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
+            .init_resource::<WorldUiStacks>()
+            .init_resource::<UiFocus>()
+            .init_resource::<widget::FocusedTextInput>()
+            .init_resource::<widget::TextInputClipboard>()
+            .add_event::<FocusChanged>()
+            .add_event::<widget::TextInputChanged>()
+            .add_event::<widget::TextInputSubmitted>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
-            .register_type::<CalculatedSize>()
+            .register_type::<ContentSize>()
             .register_type::<Direction>()
             .register_type::<Display>()
+            .register_type::<EaseFunction>()
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
+            .register_type::<FocusOutline>()
             .register_type::<FocusPolicy>()
+            .register_type::<GridAutoFlow>()
+            .register_type::<GridPlacement>()
+            .register_type::<GridTrack>()
+            .register_type::<GridTrackRepetition>()
+            .register_type::<MaxTrackSizingFunction>()
+            .register_type::<MinTrackSizingFunction>()
+            .register_type::<RepeatedGridTrack>()
+            .register_type::<ScrollPosition>()
+            .register_type::<TabIndex>()
+            // NOTE: used by Style::grid_*
+            .register_type::<Option<i16>>()
+            .register_type::<Option<u16>>()
+            .register_type::<Vec<GridTrack>>()
+            .register_type::<Vec<RepeatedGridTrack>>()
             .register_type::<Interaction>()
             .register_type::<JustifyContent>()
             .register_type::<Node>()
+            .register_type::<Opacity>()
             // NOTE: used by Style::aspect_ratio
             .register_type::<Option<f32>>()
             .register_type::<Overflow>()
@@ -101,19 +155,75 @@ impl Plugin for UiPlugin {
             .register_type::<UiRect>()
             .register_type::<Style>()
             .register_type::<BackgroundColor>()
+            .register_type::<BorderColor>()
+            .register_type::<BorderRadius>()
+            .register_type::<BackgroundGradient>()
+            .register_type::<BorderGradient>()
+            .register_type::<BoxShadow>()
+            .register_type::<ColorStop>()
+            .register_type::<Gradient>()
+            .register_type::<GradientShape>()
+            .register_type::<Vec<ColorStop>>()
             .register_type::<UiImage>()
+            .register_type::<UiImageSize>()
             .register_type::<Val>()
             .register_type::<widget::Button>()
+            .register_type::<widget::ScrollAxis>()
+            .register_type::<widget::Scrollbar>()
+            .register_type::<widget::TextInput>()
+            .register_type::<WorldUi>()
+            .register_type::<WorldUiOrientation>()
+            .register_type::<ZIndex>()
+            // NOTE: used by WorldUi::anchor
+            .register_type::<Option<Entity>>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 ui_focus_system.label(UiSystem::Focus).after(InputSystem),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_scroll_system
+                    .label(UiSystem::Scroll)
+                    .after(InputSystem)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::update_scrollbar_system.after(UiSystem::Scroll),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_navigation_system
+                    .label(UiSystem::Navigation)
+                    .after(InputSystem)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                focus_changed_system.after(UiSystem::Navigation),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, widget::spawn_text_input_parts_system)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::text_input_focus_system.after(UiSystem::Navigation),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::text_input_system
+                    .after(InputSystem)
+                    .after(widget::text_input_focus_system),
+            )
             // add these stages to front because these must run before transform update systems
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                widget::text_system
+                widget::measure_text_system
                     .before(UiSystem::Flex)
-                    .after(ModifiesWindows)
+                    .after(ModifiesWindows),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::text_system
+                    .after(UiSystem::Flex)
                     // Potential conflict: `Assets<Image>`
                     // In practice, they run independently since `bevy_render::camera_update_system`
                     // will only ever observe its own render target, and `widget::text_system`
@@ -126,14 +236,27 @@ impl Plugin for UiPlugin {
             )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                widget::update_image_calculated_size_system
+                widget::update_text_input_system.before(widget::measure_text_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::update_image_content_size_system
                     .before(UiSystem::Flex)
                     // Potential conflicts: `Assets<Image>`
-                    // They run independently since `widget::image_node_system` will only ever observe
-                    // its own UiImage, and `widget::text_system` & `bevy_text::update_text2d_layout`
-                    // will never modify a pre-existing `Image` asset.
+                    // They run independently since `widget::update_image_content_size_system` will
+                    // only ever observe its own UiImage, and `widget::text_system` &
+                    // `bevy_text::update_text2d_layout` will never modify a pre-existing `Image` asset.
                     .ambiguous_with(bevy_text::update_text2d_layout)
-                    .ambiguous_with(widget::text_system),
+                    .ambiguous_with(widget::text_system)
+                    // Potential conflict: `ContentSize`
+                    // Images and texts are disjoint, the image system skips the nodes with a `Text`.
+                    .ambiguous_with(widget::measure_text_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                ui_animation_system
+                    .label(UiSystem::Animation)
+                    .before(UiSystem::Flex),
             )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
@@ -146,6 +269,13 @@ impl Plugin for UiPlugin {
                 CoreStage::PostUpdate,
                 ui_stack_system.label(UiSystem::Stack),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_world_ui_system
+                    .label(UiSystem::WorldUi)
+                    .after(UiSystem::Stack)
+                    .after(TransformSystem::TransformPropagate),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 update_clipping_system.after(TransformSystem::TransformPropagate),
@@ -0,0 +1,68 @@
+use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use std::{fmt, sync::Arc};
+pub use taffy::style::AvailableSpace;
+
+/// Sizes the content of a UI node, such as its text or its image, for the space the layout gives
+/// to the node.
+pub trait Measure: Send + Sync + 'static {
+    /// Measures the content in logical pixels, for the `width` and `height` of the node when the
+    /// layout already knows them, and for the space available to the node otherwise.
+    fn measure(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        available_width: AvailableSpace,
+        available_height: AvailableSpace,
+    ) -> Vec2;
+}
+
+/// A [`Measure`] of content with a fixed size, whatever the space available to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FixedMeasure {
+    pub size: Vec2,
+}
+
+impl Measure for FixedMeasure {
+    fn measure(
+        &self,
+        _width: Option<f32>,
+        _height: Option<f32>,
+        _available_width: AvailableSpace,
+        _available_height: AvailableSpace,
+    ) -> Vec2 {
+        self.size
+    }
+}
+
+/// The [`Measure`] of the content of a node, sizing the node in the layout when its size isn't
+/// set by its [`Style`](crate::Style).
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct ContentSize {
+    #[reflect(ignore)]
+    pub(crate) measure: Option<Arc<dyn Measure>>,
+}
+
+impl ContentSize {
+    /// A content size measured by the given [`Measure`].
+    pub fn new(measure: impl Measure) -> Self {
+        Self {
+            measure: Some(Arc::new(measure)),
+        }
+    }
+
+    /// Sets the [`Measure`] of the content, laying out the node again.
+    pub fn set(&mut self, measure: impl Measure) {
+        self.measure = Some(Arc::new(measure));
+    }
+}
+
+impl fmt::Debug for ContentSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ContentSize")
+            .field("measure", &self.measure.is_some())
+            .finish()
+    }
+}
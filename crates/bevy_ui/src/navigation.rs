@@ -0,0 +1,419 @@
+//! This module contains the focus of the UI nodes, moved by the keyboard and gamepads
+
+use crate::{widget::FocusedTextInput, Interaction, Node, UiStack, Val};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    event::EventWriter,
+    prelude::Component,
+    query::ChangeTrackers,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res, ResMut, Resource},
+};
+use bevy_input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    touch::Touches,
+    Axis, Input,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{color::Color, view::ComputedVisibility};
+use bevy_transform::components::GlobalTransform;
+
+/// How far the left stick of a gamepad is tilted to move the focus
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The UI node focused by the keyboard and gamepads.
+///
+/// The focus is moved between the nodes with an [`Interaction`] by [`ui_navigation_system`]:
+/// with `Tab` and `Shift + Tab` in the order of their [`TabIndex`], and with the arrow keys, the
+/// directional pad and the left stick of gamepads to the closest node in that direction. `Enter`,
+/// `Space` and the south button of gamepads press the focused node.
+///
+/// Clicking a node with an [`Interaction`] focuses it, and clicking elsewhere clears the focus.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UiFocus {
+    /// The focused node
+    pub entity: Option<Entity>,
+    /// Whether the focus was moved by the keyboard or a gamepad rather than by clicking, showing
+    /// the [`FocusOutline`] of the focused node
+    pub visible: bool,
+}
+
+/// An event sent when the [`UiFocus`] changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FocusChanged {
+    /// The node that lost the focus
+    pub previous: Option<Entity>,
+    /// The node that gained the focus
+    pub focused: Option<Entity>,
+}
+
+/// The position of a node in the order of the focus with `Tab`.
+///
+/// The nodes are ordered by their tab index, and in the order they are drawn when their indices
+/// are equal. The nodes without a tab index have an index of 0. The nodes with a negative index
+/// can only be focused by clicking them, not by the keyboard or gamepads.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct TabIndex(pub i32);
+
+/// An outline drawn around the node while it is visibly focused by the keyboard or a gamepad
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct FocusOutline {
+    pub color: Color,
+    /// The width of the outline around the node. A [`Val::Percent`] is relative to the smallest
+    /// side of the node.
+    pub width: Val,
+}
+
+impl FocusOutline {
+    pub const DEFAULT: Self = Self {
+        color: Color::WHITE,
+        width: Val::Px(2.0),
+    };
+
+    /// An outline of the given color and width in pixels
+    pub const fn new(color: Color, width: f32) -> Self {
+        Self {
+            color,
+            width: Val::Px(width),
+        }
+    }
+}
+
+impl Default for FocusOutline {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The nodes that can be focused, from the first to the last in the order of [`TabIndex`]
+fn tab_order(ui_stack: &UiStack, node_query: &Query<NavigationQuery>) -> Vec<(Entity, Vec2)> {
+    let mut nodes: Vec<_> = ui_stack
+        .uinodes
+        .iter()
+        .filter_map(|entity| node_query.get(*entity).ok())
+        .filter(|(_, node, _, _, tab_index, visibility)| {
+            node.size() != Vec2::ZERO
+                && tab_index.map_or(true, |tab_index| tab_index.0 >= 0)
+                && visibility.map_or(true, |visibility| visibility.is_visible())
+        })
+        .map(|(entity, _, transform, _, tab_index, _)| {
+            (
+                tab_index.copied().unwrap_or_default(),
+                entity,
+                transform.translation().truncate(),
+            )
+        })
+        .collect();
+    // The sort is stable, keeping the order of the stack for equal indices
+    nodes.sort_by_key(|(tab_index, _, _)| *tab_index);
+    nodes
+        .into_iter()
+        .map(|(_, entity, position)| (entity, position))
+        .collect()
+}
+
+type NavigationQuery = (
+    Entity,
+    &'static Node,
+    &'static GlobalTransform,
+    (&'static mut Interaction, ChangeTrackers<Interaction>),
+    Option<&'static TabIndex>,
+    Option<&'static ComputedVisibility>,
+);
+
+/// The system that moves the [`UiFocus`] with the mouse, the keyboard and the gamepads, and
+/// presses the focused node.
+///
+/// The arrow keys, `Space` and `Enter` don't move or press the focus while a
+/// [`TextInput`](crate::widget::TextInput) is edited.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_navigation_system(
+    mut focus: ResMut<UiFocus>,
+    focused_text_input: Res<FocusedTextInput>,
+    mut previous_stick_direction: Local<Vec2>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    ui_stack: Res<UiStack>,
+    mut node_query: Query<NavigationQuery>,
+) {
+    // clear the focus of the removed nodes
+    if let Some(entity) = focus.entity {
+        if !node_query.contains(entity) {
+            focus.entity = None;
+        }
+    }
+
+    if mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed() {
+        let clicked = node_query
+            .iter()
+            .find(|(_, _, _, (interaction, change_trackers), _, _)| {
+                **interaction == Interaction::Clicked && change_trackers.is_changed()
+            })
+            .map(|(entity, ..)| entity);
+        let new_focus = UiFocus {
+            entity: clicked,
+            visible: false,
+        };
+        // only trigger change detection when the focus changes
+        if *focus != new_focus {
+            *focus = new_focus;
+        }
+        return;
+    }
+
+    let editing_text = focused_text_input.0.is_some();
+    let pressed = |key| !editing_text && keyboard_input.just_pressed(key);
+    let gamepad_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    let gamepad_released = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_released(GamepadButton::new(gamepad, button_type)))
+    };
+
+    // The directions are in the coordinates of the UI, with y pointing down
+    let mut direction = Vec2::ZERO;
+    for (key, button_type, key_direction) in [
+        (KeyCode::Up, GamepadButtonType::DPadUp, Vec2::NEG_Y),
+        (KeyCode::Down, GamepadButtonType::DPadDown, Vec2::Y),
+        (KeyCode::Left, GamepadButtonType::DPadLeft, Vec2::NEG_X),
+        (KeyCode::Right, GamepadButtonType::DPadRight, Vec2::X),
+    ] {
+        if pressed(key) || gamepad_pressed(button_type) {
+            direction = key_direction;
+        }
+    }
+    // The stick moves the focus once each time it is tilted in a new direction
+    let stick_direction = gamepads
+        .iter()
+        .map(|gamepad| stick_direction(&gamepad_axes, gamepad))
+        .find(|direction| *direction != Vec2::ZERO)
+        .unwrap_or(Vec2::ZERO);
+    if stick_direction != *previous_stick_direction && stick_direction != Vec2::ZERO {
+        direction = stick_direction;
+    }
+    *previous_stick_direction = stick_direction;
+
+    let tab = keyboard_input.just_pressed(KeyCode::Tab);
+    if tab || direction != Vec2::ZERO {
+        let order = tab_order(&ui_stack, &node_query);
+        let current = focus
+            .entity
+            .and_then(|entity| order.iter().position(|(node, _)| *node == entity));
+        let new_focus = match current {
+            _ if order.is_empty() => None,
+            None => Some(order[0].0),
+            Some(current) if tab => {
+                let backward = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+                let next = if backward {
+                    current.checked_sub(1).unwrap_or(order.len() - 1)
+                } else {
+                    (current + 1) % order.len()
+                };
+                Some(order[next].0)
+            }
+            Some(current) => {
+                // The closest node in the direction, preferring the nodes aligned with the
+                // current one
+                let (current_entity, current_position) = order[current];
+                let closest = order
+                    .iter()
+                    .filter_map(|(entity, position)| {
+                        let offset = *position - current_position;
+                        let along = offset.dot(direction);
+                        let across = (offset - along * direction).length();
+                        (along > 0.0).then_some((*entity, along + 2.0 * across))
+                    })
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+                Some(closest.map_or(current_entity, |(entity, _)| entity))
+            }
+        };
+        let new_focus = UiFocus {
+            entity: new_focus,
+            visible: true,
+        };
+        if *focus != new_focus {
+            *focus = new_focus;
+        }
+    }
+
+    // Press the focused node like a click
+    let Some(mut interaction) = focus
+        .entity
+        .and_then(|entity| node_query.get_mut(entity).ok())
+        .map(|(_, _, _, (interaction, _), _, _)| interaction)
+    else {
+        return;
+    };
+    let activate_keys = [KeyCode::Return, KeyCode::NumpadEnter, KeyCode::Space];
+    if activate_keys.into_iter().any(pressed) || gamepad_pressed(GamepadButtonType::South) {
+        *interaction = Interaction::Clicked;
+    } else if *interaction == Interaction::Clicked
+        && (keyboard_input.any_just_released(activate_keys)
+            || gamepad_released(GamepadButtonType::South))
+    {
+        *interaction = Interaction::None;
+    }
+}
+
+/// The direction the left stick of the gamepad is tilted in, or zero
+fn stick_direction(gamepad_axes: &Axis<GamepadAxis>, gamepad: Gamepad) -> Vec2 {
+    let axis = |axis_type| {
+        gamepad_axes
+            .get(GamepadAxis::new(gamepad, axis_type))
+            .unwrap_or(0.0)
+    };
+    // The y axis of the stick points up
+    let tilt = Vec2::new(
+        axis(GamepadAxisType::LeftStickX),
+        -axis(GamepadAxisType::LeftStickY),
+    );
+    if tilt.length() < STICK_THRESHOLD {
+        Vec2::ZERO
+    } else if tilt.x.abs() > tilt.y.abs() {
+        Vec2::new(tilt.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, tilt.y.signum())
+    }
+}
+
+/// Sends a [`FocusChanged`] event when the [`UiFocus`] changes
+pub fn focus_changed_system(
+    focus: Res<UiFocus>,
+    mut previous: Local<Option<Entity>>,
+    mut focus_changed_events: EventWriter<FocusChanged>,
+) {
+    if focus.is_changed() && focus.entity != *previous {
+        focus_changed_events.send(FocusChanged {
+            previous: *previous,
+            focused: focus.entity,
+        });
+        *previous = focus.entity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        entity::Entity,
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+    use bevy_input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        keyboard::KeyCode,
+        mouse::MouseButton,
+        touch::Touches,
+        Axis, Input,
+    };
+    use bevy_math::Vec2;
+    use bevy_transform::components::GlobalTransform;
+
+    use crate::{widget::FocusedTextInput, Interaction, Node, TabIndex, UiFocus, UiStack};
+
+    use super::ui_navigation_system;
+
+    fn spawn_node(world: &mut World, x: f32, y: f32, tab_index: i32) -> Entity {
+        world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::splat(10.0),
+                },
+                GlobalTransform::from_xyz(x, y, 0.0),
+                Interaction::None,
+                TabIndex(tab_index),
+            ))
+            .id()
+    }
+
+    fn press(world: &mut World, stage: &mut SystemStage, keys: &[KeyCode]) -> Option<Entity> {
+        let mut keyboard_input = world.resource_mut::<Input<KeyCode>>();
+        keyboard_input.reset_all();
+        for key in keys {
+            keyboard_input.press(*key);
+        }
+        stage.run(world);
+        world.resource::<UiFocus>().entity
+    }
+
+    #[test]
+    fn test_ui_navigation_system() {
+        let mut world = World::default();
+        world.init_resource::<UiFocus>();
+        world.init_resource::<FocusedTextInput>();
+        world.init_resource::<Input<KeyCode>>();
+        world.init_resource::<Input<MouseButton>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<Gamepads>();
+        world.init_resource::<Input<GamepadButton>>();
+        world.init_resource::<Axis<GamepadAxis>>();
+
+        let top_left = spawn_node(&mut world, 0.0, 0.0, 1);
+        let top_right = spawn_node(&mut world, 100.0, 10.0, 2);
+        let bottom_left = spawn_node(&mut world, 0.0, 100.0, 0);
+        let hidden_from_tab = spawn_node(&mut world, 100.0, 100.0, -1);
+        world.insert_resource(UiStack {
+            uinodes: vec![top_left, top_right, bottom_left, hidden_from_tab],
+        });
+
+        let mut stage = SystemStage::single_threaded();
+        stage.add_system(ui_navigation_system);
+
+        // The tab order follows the tab indices
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Tab]),
+            Some(bottom_left)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Tab]),
+            Some(top_left)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Tab]),
+            Some(top_right)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Tab]),
+            Some(bottom_left)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::LShift, KeyCode::Tab]),
+            Some(top_right)
+        );
+        assert!(world.resource::<UiFocus>().visible);
+
+        // The arrows move to the closest node in their direction, or stay in place
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Left]),
+            Some(top_left)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Down]),
+            Some(bottom_left)
+        );
+        assert_eq!(
+            press(&mut world, &mut stage, &[KeyCode::Down]),
+            Some(bottom_left)
+        );
+
+        // The focused node is pressed like a click
+        press(&mut world, &mut stage, &[KeyCode::Return]);
+        assert_eq!(
+            world.get::<Interaction>(bottom_left),
+            Some(&Interaction::Clicked)
+        );
+    }
+}
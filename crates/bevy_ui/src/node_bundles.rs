@@ -1,9 +1,11 @@
 //! This module contains basic node bundles used to build UIs
 
 use crate::{
-    widget::Button, BackgroundColor, CalculatedSize, FocusPolicy, Interaction, Node, Style,
-    UiImage, ZIndex,
+    widget::{Button, TextInput},
+    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node,
+    ScrollPosition, Style, UiImage, UiImageSize, UiMaterial, ZIndex,
 };
+use bevy_asset::Handle;
 use bevy_ecs::bundle::Bundle;
 use bevy_render::{
     prelude::{Color, ComputedVisibility},
@@ -23,8 +25,14 @@ pub struct NodeBundle {
     pub style: Style,
     /// The background color, which serves as a "fill" for this node
     pub background_color: BackgroundColor,
+    /// The color of each side of the border, whose widths are the `border` of the [`Style`]
+    pub border_color: BorderColor,
+    /// The radius of the rounded corners of the node
+    pub border_radius: BorderRadius,
     /// Whether this node should block interaction with lower nodes
     pub focus_policy: FocusPolicy,
+    /// How far the content is scrolled, if the node has [`Overflow::Scroll`](crate::Overflow::Scroll)
+    pub scroll_position: ScrollPosition,
     /// The transform of the node
     ///
     /// This field is automatically managed by the UI layout system.
@@ -48,9 +56,12 @@ impl Default for NodeBundle {
         NodeBundle {
             // Transparent background
             background_color: Color::NONE.into(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
             node: Default::default(),
             style: Default::default(),
             focus_policy: Default::default(),
+            scroll_position: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
             visibility: Default::default(),
@@ -67,12 +78,18 @@ pub struct ImageBundle {
     pub node: Node,
     /// Describes the style including flexbox settings
     pub style: Style,
-    /// The calculated size based on the given image
-    pub calculated_size: CalculatedSize,
+    /// The size of the node measured from its image
+    pub content_size: ContentSize,
+    /// The size of the image in pixels
+    pub image_size: UiImageSize,
     /// The background color, which serves as a "fill" for this node
     ///
     /// Combines with `UiImage` to tint the provided image.
     pub background_color: BackgroundColor,
+    /// The color of each side of the border, whose widths are the `border` of the [`Style`]
+    pub border_color: BorderColor,
+    /// The radius of the rounded corners of the node
+    pub border_radius: BorderRadius,
     /// The image of the node
     pub image: UiImage,
     /// Whether this node should block interaction with lower nodes
@@ -104,8 +121,8 @@ pub struct TextBundle {
     pub style: Style,
     /// Contains the text of the node
     pub text: Text,
-    /// The calculated size based on the given image
-    pub calculated_size: CalculatedSize,
+    /// The size of the node measured from its text, wrapped in the space available to it
+    pub content_size: ContentSize,
     /// Whether this node should block interaction with lower nodes
     pub focus_policy: FocusPolicy,
     /// The transform of the node
@@ -177,6 +194,10 @@ pub struct ButtonBundle {
     ///
     /// When combined with `UiImage`, tints the provided image.
     pub background_color: BackgroundColor,
+    /// The color of each side of the border, whose widths are the `border` of the [`Style`]
+    pub border_color: BorderColor,
+    /// The radius of the rounded corners of the node
+    pub border_radius: BorderRadius,
     /// The image of the node
     pub image: UiImage,
     /// The transform of the node
@@ -206,6 +227,8 @@ impl Default for ButtonBundle {
             style: Default::default(),
             interaction: Default::default(),
             background_color: Default::default(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
             image: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
@@ -215,3 +238,112 @@ impl Default for ButtonBundle {
         }
     }
 }
+
+/// A UI node that is an editable line of text
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// Describes the size of the node
+    pub node: Node,
+    /// The text, its style and the state of its edition
+    pub text_input: TextInput,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// Describes whether and how the text input has been interacted with by the input, focusing
+    /// it when pressed
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The background color, which serves as a "fill" for this node
+    pub background_color: BackgroundColor,
+    /// The color of each side of the border, whose widths are the `border` of the [`Style`]
+    pub border_color: BorderColor,
+    /// The radius of the rounded corners of the node
+    pub border_radius: BorderRadius,
+    /// The image of the node
+    pub image: UiImage,
+    /// The transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `TextInputBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `TextInputBundle`, use the properties of the [`Style`] component.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        Self {
+            focus_policy: FocusPolicy::Block,
+            node: Default::default(),
+            text_input: Default::default(),
+            style: Default::default(),
+            interaction: Default::default(),
+            background_color: Default::default(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            image: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+/// A UI node drawn with a [`UiMaterial`] instead of a background color or image
+#[derive(Bundle, Clone)]
+pub struct MaterialNodeBundle<M: UiMaterial> {
+    /// Describes the size of the node
+    pub node: Node,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// The material drawing the node
+    pub material: Handle<M>,
+    /// The radius of the rounded corners of the node, given to the shader of the material
+    pub border_radius: BorderRadius,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `MaterialNodeBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `MaterialNodeBundle`, use the properties of the [`Style`] component.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl<M: UiMaterial> Default for MaterialNodeBundle<M> {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            style: Default::default(),
+            material: Default::default(),
+            border_radius: Default::default(),
+            focus_policy: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
@@ -1,15 +1,25 @@
 mod pipeline;
 mod render_pass;
+mod ui_material;
 
-use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
+use bevy_core_pipeline::{
+    core_2d::Camera2d,
+    core_3d::{Camera3d, Transparent3d},
+};
 use bevy_window::{PrimaryWindow, Window};
 pub use pipeline::*;
 pub use render_pass::*;
+pub use ui_material::*;
 
-use crate::{prelude::UiCameraConfig, BackgroundColor, CalculatedClip, Node, UiImage, UiStack};
+use crate::{
+    prelude::UiCameraConfig, BackgroundColor, BackgroundGradient, BorderColor, BorderGradient,
+    BorderRadius, BoxShadow, CalculatedClip, FocusOutline, Gradient, GradientShape, Node, Opacity,
+    Style, UiFocus, UiImage, UiScale, UiStack, Val, WorldUiStacks,
+};
 use bevy_app::prelude::*;
-use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleUntyped};
+use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleId, HandleUntyped};
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
 use bevy_math::{Mat4, Rect, UVec4, Vec2, Vec3, Vec4Swizzles};
 use bevy_reflect::TypeUuid;
 use bevy_render::texture::DEFAULT_IMAGE_HANDLE;
@@ -22,16 +32,16 @@ use bevy_render::{
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::Image,
-    view::{ComputedVisibility, ExtractedView, ViewUniforms},
+    view::{ComputedVisibility, ExtractedView, Msaa, ViewUniforms},
     Extract, RenderApp, RenderStage,
 };
 use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
-use bevy_text::{Text, TextLayoutInfo};
+use bevy_text::{Text, TextLayer, TextLayoutInfo, TextRendering};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
-use std::ops::Range;
+use std::{num::NonZeroU32, ops::Range};
 
 pub mod node {
     pub const UI_PASS_DRIVER: &str = "ui_pass_driver";
@@ -49,6 +59,10 @@ pub mod draw_ui_graph {
 
 pub const UI_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13012847047162779583);
+pub const UI_VERTEX_OUTPUT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10123618247720234751);
+pub const UI_MATERIAL_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10074188008040063891);
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderUiSystem {
@@ -56,7 +70,19 @@ pub enum RenderUiSystem {
 }
 
 pub fn build_ui_render(app: &mut App) {
+    load_internal_asset!(
+        app,
+        UI_VERTEX_OUTPUT_SHADER_HANDLE,
+        "ui_vertex_output.wgsl",
+        Shader::from_wgsl
+    );
     load_internal_asset!(app, UI_SHADER_HANDLE, "ui.wgsl", Shader::from_wgsl);
+    load_internal_asset!(
+        app,
+        UI_MATERIAL_SHADER_HANDLE,
+        "ui_material.wgsl",
+        Shader::from_wgsl
+    );
 
     let render_app = match app.get_sub_app_mut(RenderApp) {
         Ok(render_app) => render_app,
@@ -68,9 +94,12 @@ pub fn build_ui_render(app: &mut App) {
         .init_resource::<SpecializedRenderPipelines<UiPipeline>>()
         .init_resource::<UiImageBindGroups>()
         .init_resource::<UiMeta>()
+        .init_resource::<UiGradients>()
+        .init_resource::<UiMaterialPipelines>()
         .init_resource::<ExtractedUiNodes>()
         .init_resource::<DrawFunctions<TransparentUi>>()
         .add_render_command::<TransparentUi, DrawUi>()
+        .add_render_command::<Transparent3d, DrawUi>()
         .add_system_to_stage(
             RenderStage::Extract,
             extract_default_ui_camera_view::<Camera2d>,
@@ -173,20 +202,192 @@ pub struct ExtractedUiNode {
     pub rect: Rect,
     pub image: Handle<Image>,
     pub atlas_size: Option<Vec2>,
-    pub clip: Option<Rect>,
+    /// The clip of the node: its rect clips the nodes aligned with the axes before they are drawn,
+    /// and its shape clips all the nodes exactly in the shader
+    pub clip: Option<CalculatedClip>,
     pub flip_x: bool,
     pub flip_y: bool,
+    /// The radius of the corners of the node: top-left, top-right, bottom-right and bottom-left
+    pub border_radius: [f32; 4],
+    /// The widths of the border of the node: left, top, right and bottom
+    pub border: [f32; 4],
+    pub part: UiNodePart,
+    /// The gradient filling the part instead of its color
+    pub gradient: Option<ExtractedGradient>,
+    /// The [`UiMaterial`] drawing the part instead of the UI shader
+    pub material_handle_id: Option<HandleId>,
+    /// The index of the world-space UI of the node in [`ExtractedUiNodes::world_uis`], or `None`
+    /// if the node is drawn on the screen
+    pub world_ui: Option<usize>,
+}
+
+/// A [`WorldUiStack`](crate::WorldUiStack) placing its nodes in the world.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtractedWorldUi {
+    /// The transform from the logical pixels of the UI to the world
+    pub transform: Mat4,
+    /// The position of the center of the UI in the world, to sort it with the transparent meshes
+    pub center: Vec3,
+}
+
+/// The part of a UI node drawn by an [`ExtractedUiNode`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UiNodePart {
+    /// The image of the node, tinted by its color
+    Background,
+    /// A side of the border of the node, drawn with its color only
+    LeftBorder,
+    TopBorder,
+    RightBorder,
+    BottomBorder,
+    /// The [`BoxShadow`] of the node, drawn with its color only
+    BoxShadow(ExtractedBoxShadow),
+    /// A glyph of a text drawn with [`TextRendering::Sdf`], whose
+    /// image is a signed distance field
+    SdfGlyph {
+        sharpness: f32,
+    },
+}
+
+/// A [`BoxShadow`] resolved for the size of its node, in logical pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtractedBoxShadow {
+    pub offset: Vec2,
+    pub spread_radius: f32,
+    pub blur_radius: f32,
+    pub inset: bool,
+}
+
+impl ExtractedBoxShadow {
+    /// How much the quad of the shadow extends beyond its node on each side, to cover the
+    /// offset, spread and blurred shadow. Inset shadows are drawn inside their node.
+    pub fn margin(&self) -> Vec2 {
+        if self.inset {
+            return Vec2::ZERO;
+        }
+        self.offset.abs() + self.spread_radius.max(0.0) + self.blur_radius
+    }
+}
+
+/// A [`Gradient`] of an [`ExtractedUiNode`], baked into a row of the gradient texture.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtractedGradient {
+    pub shape: GradientShape,
+    /// The index of the gradient in [`ExtractedUiNodes::gradients`]
+    pub index: usize,
+}
+
+impl ExtractedGradient {
+    /// The kind of the gradient in the UI shader, added to the mode of the part, and its
+    /// parameters, the last one being the coordinate of its row in a texture of the given height.
+    fn shader_params(self, rows: u32) -> (u32, [f32; 4]) {
+        let row = (self.index as f32 + 0.5) / rows as f32;
+        match self.shape {
+            GradientShape::Linear { angle } => (1 << 8, [angle, 0.0, 0.0, row]),
+            GradientShape::Radial { center } => (2 << 8, [center.x, center.y, 0.0, row]),
+            GradientShape::Conic {
+                center,
+                start_angle,
+            } => (3 << 8, [center.x, center.y, start_angle, row]),
+        }
+    }
+}
+
+impl UiNodePart {
+    const BORDERS: [UiNodePart; 4] = [
+        UiNodePart::LeftBorder,
+        UiNodePart::TopBorder,
+        UiNodePart::RightBorder,
+        UiNodePart::BottomBorder,
+    ];
+
+    /// The mode of the part in the UI shader, with the unrounded backgrounds drawn without their
+    /// signed distance field.
+    fn shader_mode(self, border_radius: [f32; 4]) -> u32 {
+        match self {
+            UiNodePart::Background if border_radius == [0.0; 4] => 0,
+            UiNodePart::Background => 1,
+            UiNodePart::LeftBorder => 2,
+            UiNodePart::TopBorder => 3,
+            UiNodePart::RightBorder => 4,
+            UiNodePart::BottomBorder => 5,
+            UiNodePart::BoxShadow(ExtractedBoxShadow { inset: false, .. }) => 6,
+            UiNodePart::BoxShadow(ExtractedBoxShadow { inset: true, .. }) => 7,
+            UiNodePart::SdfGlyph { .. } => 8,
+        }
+    }
 }
 
 #[derive(Resource, Default)]
 pub struct ExtractedUiNodes {
     pub uinodes: Vec<ExtractedUiNode>,
+    /// The gradients of the extracted nodes, baked into the gradient texture
+    pub gradients: Vec<Gradient>,
+    /// The world-space UI of the extracted nodes, in the order of the [`WorldUiStacks`]
+    pub world_uis: Vec<ExtractedWorldUi>,
+}
+
+impl ExtractedUiNodes {
+    /// Adds a gradient to be baked into the gradient texture
+    pub fn add_gradient(&mut self, gradient: &Gradient) -> ExtractedGradient {
+        self.gradients.push(gradient.clone());
+        ExtractedGradient {
+            shape: gradient.shape,
+            index: self.gradients.len() - 1,
+        }
+    }
+}
+
+/// The nodes of the [`UiStack`], then of each tree of the [`WorldUiStacks`], with the index of
+/// their world-space UI if any and their index in their stack.
+pub(crate) fn stacked_uinodes<'a>(
+    ui_stack: &'a UiStack,
+    world_ui_stacks: &'a WorldUiStacks,
+) -> impl Iterator<Item = (Option<usize>, usize, Entity)> + 'a {
+    let screen = ui_stack
+        .uinodes
+        .iter()
+        .enumerate()
+        .map(|(stack_index, entity)| (None, stack_index, *entity));
+    let world = world_ui_stacks
+        .stacks
+        .iter()
+        .enumerate()
+        .flat_map(|(world_ui, stack)| {
+            stack
+                .uinodes
+                .iter()
+                .enumerate()
+                .map(move |(stack_index, entity)| (Some(world_ui), stack_index, *entity))
+        });
+    screen.chain(world)
 }
 
+/// The [`Opacity`] of a node multiplied by the opacity of its ancestors.
+pub(crate) fn inherited_opacity(
+    entity: Entity,
+    opacity_query: &Query<(Option<&Opacity>, Option<&Parent>), With<Node>>,
+) -> f32 {
+    let mut opacity = 1.0;
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        let Ok((node_opacity, parent)) = opacity_query.get(entity) else {
+            break;
+        };
+        opacity *= node_opacity.map_or(1.0, |node_opacity| node_opacity.0);
+        current = parent.map(Parent::get);
+    }
+    opacity
+}
+
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 pub fn extract_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     images: Extract<Res<Assets<Image>>>,
     ui_stack: Extract<Res<UiStack>>,
+    world_ui_stacks: Extract<Res<WorldUiStacks>>,
+    ui_scale: Extract<Res<UiScale>>,
     uinode_query: Extract<
         Query<(
             &Node,
@@ -195,13 +396,45 @@ pub fn extract_uinodes(
             Option<&UiImage>,
             &ComputedVisibility,
             Option<&CalculatedClip>,
+            (
+                &Style,
+                Option<&BorderColor>,
+                Option<&BorderRadius>,
+                Option<&BoxShadow>,
+                Option<&Parent>,
+            ),
+            (
+                Option<&BackgroundGradient>,
+                Option<&BorderGradient>,
+                Option<&FocusOutline>,
+            ),
         )>,
     >,
+    parent_query: Extract<Query<&Node>>,
+    opacity_query: Extract<Query<(Option<&Opacity>, Option<&Parent>), With<Node>>>,
+    focus: Extract<Res<UiFocus>>,
 ) {
     extracted_uinodes.uinodes.clear();
-    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
-        if let Ok((uinode, transform, color, maybe_image, visibility, clip)) =
-            uinode_query.get(*entity)
+    extracted_uinodes.gradients.clear();
+    extracted_uinodes.world_uis.clear();
+    extracted_uinodes
+        .world_uis
+        .extend(world_ui_stacks.stacks.iter().map(|stack| ExtractedWorldUi {
+            transform: stack.transform,
+            center: stack.center,
+        }));
+    let scale = ui_scale.scale as f32;
+    for (world_ui, stack_index, entity) in stacked_uinodes(&ui_stack, &world_ui_stacks) {
+        if let Ok((
+            uinode,
+            transform,
+            color,
+            maybe_image,
+            visibility,
+            clip,
+            (style, border_color, border_radius, box_shadow, parent),
+            (background_gradient, border_gradient, focus_outline),
+        )) = uinode_query.get(entity)
         {
             if !visibility.is_visible() {
                 continue;
@@ -216,28 +449,152 @@ pub fn extract_uinodes(
                 continue;
             }
             // Skip completely transparent nodes
-            if color.0.a() == 0.0 {
+            let opacity = inherited_opacity(entity, &opacity_query);
+            if opacity <= 0.0 {
                 continue;
             }
 
-            extracted_uinodes.uinodes.push(ExtractedUiNode {
-                stack_index,
-                transform: transform.compute_matrix(),
-                background_color: color.0,
-                rect: Rect {
-                    min: Vec2::ZERO,
-                    max: uinode.calculated_size,
-                },
-                image,
-                atlas_size: None,
-                clip: clip.map(|clip| clip.clip),
-                flip_x,
-                flip_y,
-            });
+            let border_radius = border_radius
+                .map(|border_radius| border_radius.resolve(uinode.size(), scale))
+                .unwrap_or_default();
+            let parent_width = parent
+                .and_then(|parent| parent_query.get(parent.get()).ok())
+                .map_or(0.0, |parent| parent.size().x);
+            let border = resolve_border(style, parent_width, scale);
+            let border_color = border_color.copied().unwrap_or_default();
+            let border_colors = [
+                border_color.left,
+                border_color.top,
+                border_color.right,
+                border_color.bottom,
+            ];
+
+            // The gradients without stops are not drawn
+            let mut add_gradient = |gradient: &Gradient| {
+                (!gradient.stops.is_empty()).then(|| extracted_uinodes.add_gradient(gradient))
+            };
+            let background_gradient =
+                background_gradient.and_then(|gradient| add_gradient(&gradient.0));
+            let border_gradient = border_gradient.and_then(|gradient| add_gradient(&gradient.0));
+
+            let mut push_part =
+                |part: UiNodePart, color: Color, gradient: Option<ExtractedGradient>| {
+                    let margin = match part {
+                        UiNodePart::BoxShadow(box_shadow) => box_shadow.margin(),
+                        _ => Vec2::ZERO,
+                    };
+                    extracted_uinodes.uinodes.push(ExtractedUiNode {
+                        stack_index,
+                        transform: transform.compute_matrix(),
+                        background_color: color.with_a(color.a() * opacity),
+                        rect: Rect {
+                            min: Vec2::ZERO,
+                            max: uinode.calculated_size + 2.0 * margin,
+                        },
+                        image: image.clone_weak(),
+                        atlas_size: None,
+                        clip: clip.copied(),
+                        flip_x,
+                        flip_y,
+                        border_radius,
+                        border,
+                        part,
+                        gradient,
+                        material_handle_id: None,
+                        world_ui,
+                    });
+                };
+
+            let box_shadow = box_shadow
+                .filter(|box_shadow| box_shadow.color.a() != 0.0)
+                .map(|box_shadow| {
+                    let resolve = |value: Val, size: f32| match value {
+                        Val::Px(value) => value * scale,
+                        Val::Percent(percent) => size * percent / 100.0,
+                        Val::Auto | Val::Undefined => 0.0,
+                    };
+                    let min_size = uinode.size().min_element();
+                    let part = UiNodePart::BoxShadow(ExtractedBoxShadow {
+                        offset: Vec2::new(
+                            resolve(box_shadow.x_offset, uinode.size().x),
+                            resolve(box_shadow.y_offset, uinode.size().y),
+                        ),
+                        spread_radius: resolve(box_shadow.spread_radius, min_size),
+                        blur_radius: resolve(box_shadow.blur_radius, min_size).max(0.0),
+                        inset: box_shadow.inset,
+                    });
+                    (part, box_shadow.color, box_shadow.inset)
+                });
+
+            // The outline of the visibly focused node is drawn around it like a sharp shadow
+            let focus_outline = focus_outline
+                .filter(|_| focus.visible && focus.entity == Some(entity))
+                .map(|focus_outline| {
+                    let width = match focus_outline.width {
+                        Val::Px(width) => width * scale,
+                        Val::Percent(percent) => uinode.size().min_element() * percent / 100.0,
+                        Val::Auto | Val::Undefined => 0.0,
+                    };
+                    let part = UiNodePart::BoxShadow(ExtractedBoxShadow {
+                        offset: Vec2::ZERO,
+                        spread_radius: width,
+                        blur_radius: 0.0,
+                        inset: false,
+                    });
+                    (part, focus_outline.color)
+                });
+
+            // The shadows behind the node are drawn first, and the inset shadows under the border
+            if let Some((part, color)) = focus_outline {
+                push_part(part, color, None);
+            }
+            if let Some((part, color, false)) = box_shadow {
+                push_part(part, color, None);
+            }
+            // Skip completely transparent backgrounds and borders
+            if background_gradient.is_some() {
+                push_part(UiNodePart::Background, Color::WHITE, background_gradient);
+            } else if color.0.a() != 0.0 {
+                push_part(UiNodePart::Background, color.0, None);
+            }
+            if let Some((part, color, true)) = box_shadow {
+                push_part(part, color, None);
+            }
+            for ((part, width), color) in UiNodePart::BORDERS
+                .into_iter()
+                .zip(border)
+                .zip(border_colors)
+            {
+                if width <= 0.0 {
+                    continue;
+                }
+                if border_gradient.is_some() {
+                    push_part(part, Color::WHITE, border_gradient);
+                } else if color.a() != 0.0 {
+                    push_part(part, color, None);
+                }
+            }
         }
     }
 }
 
+/// The widths of the border of a node in logical pixels: left, top, right and bottom.
+///
+/// Like the layout, percentages of the border are relative to the width of the parent.
+pub(crate) fn resolve_border(style: &Style, parent_width: f32, scale: f32) -> [f32; 4] {
+    [
+        style.border.left,
+        style.border.top,
+        style.border.right,
+        style.border.bottom,
+    ]
+    .map(|width| match width {
+        Val::Px(width) => width * scale,
+        Val::Percent(percent) => parent_width * percent / 100.0,
+        Val::Auto | Val::Undefined => 0.0,
+    })
+}
+
 /// The UI camera is "moved back" by this many units (plus the [`UI_CAMERA_TRANSFORM_OFFSET`]) and also has a view
 /// distance of this many units. This ensures that with a left-handed projection,
 /// as ui elements are "stacked on top of each other", they are within the camera's view
@@ -299,6 +656,7 @@ pub fn extract_text_uinodes(
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
     windows: Extract<Query<&Window, With<PrimaryWindow>>>,
     ui_stack: Extract<Res<UiStack>>,
+    world_ui_stacks: Extract<Res<WorldUiStacks>>,
     uinode_query: Extract<
         Query<(
             &Node,
@@ -309,6 +667,7 @@ pub fn extract_text_uinodes(
             Option<&CalculatedClip>,
         )>,
     >,
+    opacity_query: Extract<Query<(Option<&Opacity>, Option<&Parent>), With<Node>>>,
 ) {
     // TODO: Support window-independent UI scale: https://github.com/bevyengine/bevy/issues/5621
     let scale_factor = windows
@@ -316,9 +675,9 @@ pub fn extract_text_uinodes(
         .map(|window| window.resolution.scale_factor() as f32)
         .unwrap_or(1.0);
 
-    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
+    for (world_ui, stack_index, entity) in stacked_uinodes(&ui_stack, &world_ui_stacks) {
         if let Ok((uinode, global_transform, text, text_layout_info, visibility, clip)) =
-            uinode_query.get(*entity)
+            uinode_query.get(entity)
         {
             if !visibility.is_visible() {
                 continue;
@@ -327,45 +686,68 @@ pub fn extract_text_uinodes(
             if uinode.size() == Vec2::ZERO {
                 continue;
             }
+            let opacity = inherited_opacity(entity, &opacity_query);
+            if opacity <= 0.0 {
+                continue;
+            }
             let text_glyphs = &text_layout_info.glyphs;
             let alignment_offset = (uinode.size() / -2.0).extend(0.0);
 
-            let mut color = Color::WHITE;
-            let mut current_section = usize::MAX;
-            for text_glyph in text_glyphs {
-                if text_glyph.section_index != current_section {
-                    color = text.sections[text_glyph.section_index]
-                        .style
-                        .color
-                        .as_rgba_linear();
-                    current_section = text_glyph.section_index;
+            // The shadows of all the glyphs are drawn first, then their outlines, then the
+            // glyphs themselves
+            for layer in TextLayer::ALL {
+                for text_glyph in text_glyphs {
+                    let style = &text.sections[text_glyph.section_index].style;
+                    let Some(color) = style.layer_color(layer) else {
+                        continue;
+                    };
+                    let mut color = color.as_rgba_linear();
+                    color.set_a(color.a() * opacity);
+                    let atlas = texture_atlases
+                        .get(&text_glyph.atlas_info.texture_atlas)
+                        .unwrap();
+                    let index = text_glyph.atlas_info.glyph_index;
+                    let rect = atlas.textures[index];
+                    let atlas_size = Some(atlas.size);
+                    // The signed distance fields are rasterized at a single size, and scaled to
+                    // the size of their glyph
+                    let (part, glyph_scale) = match style.rendering {
+                        TextRendering::Sdf { sharpness } => (
+                            UiNodePart::SdfGlyph { sharpness },
+                            (text_glyph.size / rect.size()).extend(1.0),
+                        ),
+                        TextRendering::Bitmap => (UiNodePart::Background, Vec3::ONE),
+                    };
+
+                    for offset in style.layer_offsets(layer) {
+                        // NOTE: Should match `bevy_text::text2d::extract_text2d_sprite`
+                        let extracted_transform = global_transform.compute_matrix()
+                            * Mat4::from_scale(Vec3::splat(scale_factor.recip()))
+                            * Mat4::from_translation(
+                                alignment_offset * scale_factor
+                                    + (text_glyph.position + offset * scale_factor).extend(0.),
+                            )
+                            * Mat4::from_scale(glyph_scale);
+
+                        extracted_uinodes.uinodes.push(ExtractedUiNode {
+                            stack_index,
+                            transform: extracted_transform,
+                            background_color: color,
+                            rect,
+                            image: atlas.texture.clone_weak(),
+                            atlas_size,
+                            clip: clip.copied(),
+                            flip_x: false,
+                            flip_y: false,
+                            border_radius: [0.0; 4],
+                            border: [0.0; 4],
+                            part,
+                            gradient: None,
+                            material_handle_id: None,
+                            world_ui,
+                        });
+                    }
                 }
-                let atlas = texture_atlases
-                    .get(&text_glyph.atlas_info.texture_atlas)
-                    .unwrap();
-                let texture = atlas.texture.clone_weak();
-                let index = text_glyph.atlas_info.glyph_index;
-                let rect = atlas.textures[index];
-                let atlas_size = Some(atlas.size);
-
-                // NOTE: Should match `bevy_text::text2d::extract_text2d_sprite`
-                let extracted_transform = global_transform.compute_matrix()
-                    * Mat4::from_scale(Vec3::splat(scale_factor.recip()))
-                    * Mat4::from_translation(
-                        alignment_offset * scale_factor + text_glyph.position.extend(0.),
-                    );
-
-                extracted_uinodes.uinodes.push(ExtractedUiNode {
-                    stack_index,
-                    transform: extracted_transform,
-                    background_color: color,
-                    rect,
-                    image: texture,
-                    atlas_size,
-                    clip: clip.map(|clip| clip.clip),
-                    flip_x: false,
-                    flip_y: false,
-                });
             }
         }
     }
@@ -377,6 +759,23 @@ struct UiVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
     pub color: [f32; 4],
+    /// The position of the vertex relative to the center of the node
+    pub point: [f32; 2],
+    pub size: [f32; 2],
+    pub border_radius: [f32; 4],
+    /// The widths of the border, the offset, spread and blur radius of a shadow, or the sharpness
+    /// of a signed distance field glyph
+    pub border: [f32; 4],
+    /// The parameters of the gradient of the node
+    pub gradient: [f32; 4],
+    /// The part of the node drawn in the lowest byte, the kind of its gradient in the next one,
+    /// and whether it is clipped by its clip shape in the next one
+    pub mode: u32,
+    /// The position of the vertex in the space of the shape clipping the node, relative to its
+    /// center
+    pub clip_point: [f32; 2],
+    pub clip_size: [f32; 2],
+    pub clip_radius: [f32; 4],
 }
 
 #[derive(Resource)]
@@ -394,6 +793,104 @@ impl Default for UiMeta {
     }
 }
 
+/// The number of texels of the gradient texture along each gradient
+const GRADIENT_TEXTURE_WIDTH: u32 = 256;
+
+/// The gradients of the UI nodes, each baked into a row of a texture.
+#[derive(Resource, Default)]
+pub struct UiGradients {
+    texture: Option<Texture>,
+    rows: u32,
+    bind_group: Option<BindGroup>,
+}
+
+impl UiGradients {
+    /// Grows the texture to have at least the given number of rows, recreating its bind group
+    fn reserve(&mut self, rows: u32, render_device: &RenderDevice, ui_pipeline: &UiPipeline) {
+        if rows <= self.rows {
+            return;
+        }
+        let rows = rows.next_power_of_two();
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("ui_gradient_texture"),
+            size: Extent3d {
+                width: GRADIENT_TEXTURE_WIDTH,
+                height: rows,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("ui_gradient_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        self.bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("ui_gradient_bind_group"),
+            layout: &ui_pipeline.gradient_layout,
+        }));
+        self.texture = Some(texture);
+        self.rows = rows;
+    }
+
+    /// Bakes the gradients into the first rows of the texture
+    fn write(&self, gradients: &[Gradient], render_queue: &RenderQueue) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        if gradients.is_empty() {
+            return;
+        }
+        let data: Vec<u8> = gradients
+            .iter()
+            .flat_map(|gradient| {
+                (0..GRADIENT_TEXTURE_WIDTH).flat_map(move |texel| {
+                    let position = texel as f32 / (GRADIENT_TEXTURE_WIDTH - 1) as f32;
+                    gradient
+                        .sample(position)
+                        .as_rgba_f32()
+                        .map(|component| (component.clamp(0.0, 1.0) * 255.0).round() as u8)
+                })
+            })
+            .collect();
+        render_queue.write_texture(
+            ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            &data,
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(GRADIENT_TEXTURE_WIDTH * 4),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width: GRADIENT_TEXTURE_WIDTH,
+                height: gradients.len() as u32,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
+
 const QUAD_VERTEX_POSITIONS: [Vec3; 4] = [
     Vec3::new(-0.5, -0.5, 0.0),
     Vec3::new(0.5, -0.5, 0.0),
@@ -403,10 +900,17 @@ const QUAD_VERTEX_POSITIONS: [Vec3; 4] = [
 
 const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
 
+/// The bit of the mode of the vertices clipped by the shape of their clip in the UI shader
+const CLIPPED_SHADER_MODE: u32 = 1 << 16;
+
 #[derive(Component)]
 pub struct UiBatch {
     pub range: Range<u32>,
     pub image: Handle<Image>,
+    /// The [`UiMaterial`] drawing the batch instead of the UI shader
+    pub material_handle_id: Option<HandleId>,
+    /// The world-space UI of the batch, drawn by the 3D cameras instead of on the screen
+    pub world_ui: Option<ExtractedWorldUi>,
     pub z: f32,
 }
 
@@ -414,31 +918,51 @@ pub fn prepare_uinodes(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
+    ui_pipeline: Res<UiPipeline>,
     mut ui_meta: ResMut<UiMeta>,
+    mut ui_gradients: ResMut<UiGradients>,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
 ) {
     ui_meta.vertices.clear();
 
-    // sort by ui stack index, starting from the deepest node
+    // The texture always has a row, for the bind group of the nodes without gradient
+    ui_gradients.reserve(
+        (extracted_uinodes.gradients.len() as u32).max(1),
+        &render_device,
+        &ui_pipeline,
+    );
+    ui_gradients.write(&extracted_uinodes.gradients, &render_queue);
+
+    // sort by ui stack index, starting from the deepest node, with the nodes on the screen first
+    // and then the nodes of each world-space UI
     extracted_uinodes
         .uinodes
-        .sort_by_key(|node| node.stack_index);
+        .sort_by_key(|node| (node.world_ui, node.stack_index));
 
     let mut start = 0;
     let mut end = 0;
     let mut current_batch_handle = Default::default();
+    let mut current_material_handle_id = None;
+    let mut current_world_ui = None;
     let mut last_z = 0.0;
     for extracted_uinode in &extracted_uinodes.uinodes {
-        if current_batch_handle != extracted_uinode.image {
+        if current_batch_handle != extracted_uinode.image
+            || current_material_handle_id != extracted_uinode.material_handle_id
+            || current_world_ui != extracted_uinode.world_ui
+        {
             if start != end {
                 commands.spawn(UiBatch {
                     range: start..end,
                     image: current_batch_handle,
+                    material_handle_id: current_material_handle_id,
+                    world_ui: current_world_ui.map(|index| extracted_uinodes.world_uis[index]),
                     z: last_z,
                 });
                 start = end;
             }
             current_batch_handle = extracted_uinode.image.clone_weak();
+            current_material_handle_id = extracted_uinode.material_handle_id;
+            current_world_ui = extracted_uinode.world_ui;
         }
 
         let uinode_rect = extracted_uinode.rect;
@@ -448,10 +972,15 @@ pub fn prepare_uinodes(
         let positions = QUAD_VERTEX_POSITIONS
             .map(|pos| (extracted_uinode.transform * (pos * rect_size).extend(1.)).xyz());
 
-        // Calculate the effect of clipping
-        // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-        let positions_diff = if let Some(clip) = extracted_uinode.clip {
-            [
+        // The nodes aligned with the axes are clipped to the rect of their clip before being
+        // drawn, the nodes rotated or mirrored by their transform only in the shader
+        let transform = extracted_uinode.transform;
+        let axis_aligned = transform.x_axis[1] == 0.0
+            && transform.y_axis[0] == 0.0
+            && transform.x_axis[0] > 0.0
+            && transform.y_axis[1] > 0.0;
+        let positions_diff = match extracted_uinode.clip {
+            Some(CalculatedClip { clip, .. }) if axis_aligned => [
                 Vec2::new(
                     f32::max(clip.min.x - positions[0].x, 0.),
                     f32::max(clip.min.y - positions[0].y, 0.),
@@ -468,35 +997,51 @@ pub fn prepare_uinodes(
                     f32::max(clip.min.x - positions[3].x, 0.),
                     f32::min(clip.max.y - positions[3].y, 0.),
                 ),
-            ]
-        } else {
-            [Vec2::ZERO; 4]
+            ],
+            _ => [Vec2::ZERO; 4],
         };
 
-        let positions_clipped = [
+        let mut positions_clipped = [
             positions[0] + positions_diff[0].extend(0.),
             positions[1] + positions_diff[1].extend(0.),
             positions[2] + positions_diff[2].extend(0.),
             positions[3] + positions_diff[3].extend(0.),
         ];
 
-        let transformed_rect_size = extracted_uinode.transform.transform_vector3(rect_size);
-
-        // Don't try to cull nodes that have a rotation
-        // In a rotation around the Z-axis, this value is 0.0 for an angle of 0.0 or π
-        // In those two cases, the culling check can proceed normally as corners will be on
-        // horizontal / vertical lines
-        // For all other angles, bypass the culling check
-        // This does not properly handles all rotations on all axis
-        if extracted_uinode.transform.x_axis[1] == 0.0 {
-            // Cull nodes that are completely clipped
-            if positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
-                || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y
-            {
-                continue;
-            }
+        let transformed_rect_size = transform.transform_vector3(rect_size);
+
+        // Cull the nodes aligned with the axes that are completely clipped
+        if axis_aligned
+            && (positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
+                || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y)
+        {
+            continue;
         }
 
+        // The clipped lengths in the space of the node, which can be scaled
+        let positions_diff = if axis_aligned {
+            positions_diff.map(|diff| diff / Vec2::new(transform.x_axis[0], transform.y_axis[1]))
+        } else {
+            positions_diff
+        };
+
+        // The positions in the space of the shape clipping the node, clipping it exactly in the
+        // shader
+        let (clip_points, clip_size, clip_radius, clip_mode) = match extracted_uinode.clip {
+            Some(CalculatedClip { shape, .. }) => (
+                positions_clipped.map(|position| {
+                    shape
+                        .inverse_transform
+                        .transform_point3(position)
+                        .truncate()
+                }),
+                shape.size,
+                shape.border_radius,
+                CLIPPED_SHADER_MODE,
+            ),
+            None => ([Vec2::ZERO; 4], Vec2::ZERO, [0.0; 4], 0),
+        };
+
         let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
         let mut uvs = [
             Vec2::new(
@@ -525,11 +1070,59 @@ pub fn prepare_uinodes(
             uvs = [uvs[3], uvs[2], uvs[1], uvs[0]];
         }
 
+        // The nodes of a world-space UI are clipped in the space of the UI, then placed in the
+        // world
+        if let Some(world_ui) = extracted_uinode.world_ui {
+            let world_transform = extracted_uinodes.world_uis[world_ui].transform;
+            positions_clipped =
+                positions_clipped.map(|position| world_transform.transform_point3(position));
+        }
+
+        // The positions relative to the center of the node, for the rounded corners, the border and
+        // the shadow
+        let points = [
+            QUAD_VERTEX_POSITIONS[0].truncate() * uinode_rect.size() + positions_diff[0],
+            QUAD_VERTEX_POSITIONS[1].truncate() * uinode_rect.size() + positions_diff[1],
+            QUAD_VERTEX_POSITIONS[2].truncate() * uinode_rect.size() + positions_diff[2],
+            QUAD_VERTEX_POSITIONS[3].truncate() * uinode_rect.size() + positions_diff[3],
+        ];
+        let mode = extracted_uinode
+            .part
+            .shader_mode(extracted_uinode.border_radius);
+        let (gradient_mode, gradient) =
+            extracted_uinode.gradient.map_or((0, [0.0; 4]), |gradient| {
+                gradient.shader_params(ui_gradients.rows)
+            });
+        // The shadows are drawn on quads larger than their node, with their parameters instead of
+        // the border, as is the sharpness of the signed distance field glyphs
+        let (node_size, border) = match extracted_uinode.part {
+            UiNodePart::BoxShadow(box_shadow) => (
+                uinode_rect.size() - 2.0 * box_shadow.margin(),
+                [
+                    box_shadow.offset.x,
+                    box_shadow.offset.y,
+                    box_shadow.spread_radius,
+                    box_shadow.blur_radius,
+                ],
+            ),
+            UiNodePart::SdfGlyph { sharpness } => (uinode_rect.size(), [sharpness, 0.0, 0.0, 0.0]),
+            _ => (uinode_rect.size(), extracted_uinode.border),
+        };
+
         for i in QUAD_INDICES {
             ui_meta.vertices.push(UiVertex {
                 position: positions_clipped[i].into(),
                 uv: uvs[i].into(),
                 color: extracted_uinode.background_color.as_linear_rgba_f32(),
+                point: points[i].into(),
+                size: node_size.into(),
+                border_radius: extracted_uinode.border_radius,
+                border,
+                gradient,
+                mode: mode | gradient_mode | clip_mode,
+                clip_point: clip_points[i].into(),
+                clip_size: clip_size.into(),
+                clip_radius,
             });
         }
 
@@ -542,6 +1135,8 @@ pub fn prepare_uinodes(
         commands.spawn(UiBatch {
             range: start..end,
             image: current_batch_handle,
+            material_handle_id: current_material_handle_id,
+            world_ui: current_world_ui.map(|index| extracted_uinodes.world_uis[index]),
             z: last_z,
         });
     }
@@ -557,6 +1152,7 @@ pub struct UiImageBindGroups {
 #[allow(clippy::too_many_arguments)]
 pub fn queue_uinodes(
     draw_functions: Res<DrawFunctions<TransparentUi>>,
+    draw_functions_3d: Res<DrawFunctions<Transparent3d>>,
     render_device: Res<RenderDevice>,
     mut ui_meta: ResMut<UiMeta>,
     view_uniforms: Res<ViewUniforms>,
@@ -565,8 +1161,16 @@ pub fn queue_uinodes(
     pipeline_cache: Res<PipelineCache>,
     mut image_bind_groups: ResMut<UiImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
+    mut ui_material_pipelines: ResMut<UiMaterialPipelines>,
+    msaa: Res<Msaa>,
     ui_batches: Query<(Entity, &UiBatch)>,
-    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
+    mut views: Query<(Entity, &ExtractedView, &mut RenderPhase<TransparentUi>)>,
+    mut world_views: Query<(
+        Entity,
+        &ExtractedView,
+        Option<&UiCameraConfig>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
     events: Res<SpriteAssetEvents>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
@@ -588,41 +1192,109 @@ pub fn queue_uinodes(
             label: Some("ui_view_bind_group"),
             layout: &ui_pipeline.view_layout,
         }));
+        for (_, batch) in &ui_batches {
+            image_bind_groups
+                .values
+                .entry(batch.image.clone_weak())
+                .or_insert_with(|| {
+                    let gpu_image = gpu_images.get(&batch.image).unwrap();
+                    render_device.create_bind_group(&BindGroupDescriptor {
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&gpu_image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(&gpu_image.sampler),
+                            },
+                        ],
+                        label: Some("ui_material_bind_group"),
+                        layout: &ui_pipeline.image_layout,
+                    })
+                });
+        }
+
         let draw_ui_function = draw_functions.read().id::<DrawUi>();
-        for (view, mut transparent_phase) in &mut views {
-            let pipeline = pipelines.specialize(
+        for (view_entity, view, mut transparent_phase) in &mut views {
+            let ui_pipeline_id = pipelines.specialize(
                 &pipeline_cache,
                 &ui_pipeline,
-                UiPipelineKey { hdr: view.hdr },
+                UiPipelineKey {
+                    hdr: view.hdr,
+                    world: false,
+                    msaa_samples: 1,
+                },
             );
             for (entity, batch) in &ui_batches {
-                image_bind_groups
-                    .values
-                    .entry(batch.image.clone_weak())
-                    .or_insert_with(|| {
-                        let gpu_image = gpu_images.get(&batch.image).unwrap();
-                        render_device.create_bind_group(&BindGroupDescriptor {
-                            entries: &[
-                                BindGroupEntry {
-                                    binding: 0,
-                                    resource: BindingResource::TextureView(&gpu_image.texture_view),
-                                },
-                                BindGroupEntry {
-                                    binding: 1,
-                                    resource: BindingResource::Sampler(&gpu_image.sampler),
-                                },
-                            ],
-                            label: Some("ui_material_bind_group"),
-                            layout: &ui_pipeline.image_layout,
-                        })
-                    });
+                if batch.world_ui.is_some() {
+                    continue;
+                }
+                // Batches whose material isn't prepared yet are skipped
+                let (draw_function, pipeline) = match batch.material_handle_id {
+                    Some(handle_id) => {
+                        match ui_material_pipelines
+                            .values
+                            .get(&(view_entity, handle_id, false))
+                        {
+                            Some(material_pipeline) => *material_pipeline,
+                            None => continue,
+                        }
+                    }
+                    None => (draw_ui_function, ui_pipeline_id),
+                };
                 transparent_phase.add(TransparentUi {
-                    draw_function: draw_ui_function,
+                    draw_function,
                     pipeline,
                     entity,
                     sort_key: FloatOrd(batch.z),
                 });
             }
         }
+
+        // The world-space UI is drawn by the 3D cameras with their transparent meshes, sorted by
+        // the distance to its center
+        let draw_ui_function = draw_functions_3d.read().id::<DrawUi>();
+        for (view_entity, view, camera_ui, mut transparent_phase) in &mut world_views {
+            // ignore cameras with disabled ui
+            if matches!(camera_ui, Some(&UiCameraConfig { show_ui: false, .. })) {
+                continue;
+            }
+            let rangefinder = view.rangefinder3d();
+            let ui_pipeline_id = pipelines.specialize(
+                &pipeline_cache,
+                &ui_pipeline,
+                UiPipelineKey {
+                    hdr: view.hdr,
+                    world: true,
+                    msaa_samples: msaa.samples,
+                },
+            );
+            for (entity, batch) in &ui_batches {
+                let Some(world_ui) = batch.world_ui else {
+                    continue;
+                };
+                let (draw_function, pipeline) = match batch.material_handle_id {
+                    Some(handle_id) => {
+                        match ui_material_pipelines
+                            .values
+                            .get(&(view_entity, handle_id, true))
+                        {
+                            Some(material_pipeline) => *material_pipeline,
+                            None => continue,
+                        }
+                    }
+                    None => (draw_ui_function, ui_pipeline_id),
+                };
+                transparent_phase.add(Transparent3d {
+                    distance: rangefinder.distance(&Mat4::from_translation(world_ui.center)),
+                    pipeline,
+                    entity,
+                    draw_function,
+                });
+            }
+        }
     }
+    // The pipelines of the materials are queued again every frame
+    ui_material_pipelines.values.clear();
 }
@@ -6,10 +6,11 @@ use bevy_render::{
     view::{ViewTarget, ViewUniform},
 };
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct UiPipeline {
     pub view_layout: BindGroupLayout,
     pub image_layout: BindGroupLayout,
+    pub gradient_layout: BindGroupLayout,
 }
 
 impl FromWorld for UiPipeline {
@@ -52,9 +53,32 @@ impl FromWorld for UiPipeline {
             label: Some("ui_image_layout"),
         });
 
+        let gradient_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            label: Some("ui_gradient_layout"),
+        });
+
         UiPipeline {
             view_layout,
             image_layout,
+            gradient_layout,
         }
     }
 }
@@ -62,6 +86,9 @@ impl FromWorld for UiPipeline {
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct UiPipelineKey {
     pub hdr: bool,
+    /// Whether the nodes are drawn in the world by a 3D camera, tested against its depth
+    pub world: bool,
+    pub msaa_samples: u32,
 }
 
 impl SpecializedRenderPipeline for UiPipeline {
@@ -77,6 +104,24 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x2,
                 // color
                 VertexFormat::Float32x4,
+                // point
+                VertexFormat::Float32x2,
+                // size
+                VertexFormat::Float32x2,
+                // border_radius
+                VertexFormat::Float32x4,
+                // border
+                VertexFormat::Float32x4,
+                // gradient
+                VertexFormat::Float32x4,
+                // mode
+                VertexFormat::Uint32,
+                // clip_point
+                VertexFormat::Float32x2,
+                // clip_size
+                VertexFormat::Float32x2,
+                // clip_radius
+                VertexFormat::Float32x4,
             ],
         );
         let shader_defs = Vec::new();
@@ -102,7 +147,11 @@ impl SpecializedRenderPipeline for UiPipeline {
                     write_mask: ColorWrites::ALL,
                 })],
             }),
-            layout: Some(vec![self.view_layout.clone(), self.image_layout.clone()]),
+            layout: Some(vec![
+                self.view_layout.clone(),
+                self.image_layout.clone(),
+                self.gradient_layout.clone(),
+            ]),
             primitive: PrimitiveState {
                 front_face: FrontFace::Ccw,
                 cull_mode: None,
@@ -112,9 +161,17 @@ impl SpecializedRenderPipeline for UiPipeline {
                 topology: PrimitiveTopology::TriangleList,
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            // The world-space UI is hidden by the meshes in front of it, without hiding the meshes
+            // behind it like the other transparent meshes
+            depth_stencil: key.world.then(|| DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::Greater,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
             multisample: MultisampleState {
-                count: 1,
+                count: key.msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
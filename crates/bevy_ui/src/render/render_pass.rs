@@ -1,4 +1,4 @@
-use super::{UiBatch, UiImageBindGroups, UiMeta};
+use super::{UiBatch, UiGradients, UiImageBindGroups, UiMeta};
 use crate::{prelude::UiCameraConfig, DefaultCameraView};
 use bevy_ecs::{
     prelude::*,
@@ -55,10 +55,10 @@ impl Node for UiPassNode {
         let input_view_entity = graph.get_input_entity(Self::IN_VIEW)?;
 
         let Ok((transparent_phase, target, camera_ui)) =
-                self.ui_view_query.get_manual(world, input_view_entity)
-             else {
-                return Ok(());
-            };
+            self.ui_view_query.get_manual(world, input_view_entity)
+        else {
+            return Ok(());
+        };
         if transparent_phase.items.is_empty() {
             return Ok(());
         }
@@ -128,6 +128,7 @@ pub type DrawUi = (
     SetItemPipeline,
     SetUiViewBindGroup<0>,
     SetUiTextureBindGroup<1>,
+    SetUiGradientBindGroup<2>,
     DrawUiNode,
 );
 
@@ -171,6 +172,28 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetUiTextureBindGroup<I>
         RenderCommandResult::Success
     }
 }
+pub struct SetUiGradientBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetUiGradientBindGroup<I> {
+    type Param = SRes<UiGradients>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        _entity: (),
+        ui_gradients: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_bind_group(
+            I,
+            ui_gradients.into_inner().bind_group.as_ref().unwrap(),
+            &[],
+        );
+        RenderCommandResult::Success
+    }
+}
 pub struct DrawUiNode;
 impl<P: PhaseItem> RenderCommand<P> for DrawUiNode {
     type Param = SRes<UiMeta>;
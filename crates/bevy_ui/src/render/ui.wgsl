@@ -12,33 +12,190 @@ struct View {
 @group(0) @binding(0)
 var<uniform> view: View;
 
-struct VertexOutput {
-    @location(0) uv: vec2<f32>,
-    @location(1) color: vec4<f32>,
-    @builtin(position) position: vec4<f32>,
-};
+// The modes of the vertices, drawing a part of a node
+let MODE_TEXTURED: u32 = 0u;
+let MODE_ROUNDED: u32 = 1u;
+let MODE_LEFT_BORDER: u32 = 2u;
+let MODE_TOP_BORDER: u32 = 3u;
+let MODE_RIGHT_BORDER: u32 = 4u;
+let MODE_BOTTOM_BORDER: u32 = 5u;
+let MODE_BOX_SHADOW: u32 = 6u;
+let MODE_INSET_BOX_SHADOW: u32 = 7u;
+let MODE_SDF_GLYPH: u32 = 8u;
+// The part of the node is in the lowest byte of the mode, the kind of its gradient in the next
+// one, and whether it is clipped by its clip shape in the next one
+let MODE_PART_MASK: u32 = 255u;
+let MODE_GRADIENT_MASK: u32 = 255u;
+let GRADIENT_NONE: u32 = 0u;
+let GRADIENT_LINEAR: u32 = 1u;
+let GRADIENT_RADIAL: u32 = 2u;
+let GRADIENT_CONIC: u32 = 3u;
+// The number of texels of each gradient in the gradient texture
+let GRADIENT_TEXTURE_WIDTH: f32 = 256.0;
+let PI: f32 = 3.141592653589793;
+
+#import bevy_ui::ui_vertex_output
 
 @vertex
 fn vertex(
     @location(0) vertex_position: vec3<f32>,
     @location(1) vertex_uv: vec2<f32>,
     @location(2) vertex_color: vec4<f32>,
+    @location(3) vertex_node_position: vec2<f32>,
+    @location(4) vertex_size: vec2<f32>,
+    @location(5) vertex_border_radius: vec4<f32>,
+    @location(6) vertex_border: vec4<f32>,
+    @location(7) vertex_gradient: vec4<f32>,
+    @location(8) vertex_mode: u32,
+    @location(9) vertex_clip_point: vec2<f32>,
+    @location(10) vertex_clip_size: vec2<f32>,
+    @location(11) vertex_clip_radius: vec4<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.color = vertex_color;
+    out.node_position = vertex_node_position;
+    out.size = vertex_size;
+    out.border_radius = vertex_border_radius;
+    out.border = vertex_border;
+    out.gradient = vertex_gradient;
+    out.mode = vertex_mode;
+    out.clip_point = vertex_clip_point;
+    out.clip_size = vertex_clip_size;
+    out.clip_radius = vertex_clip_radius;
     return out;
 }
 
+// The side of the border the point is on: 0 for left, 1 for top, 2 for right and 3 for bottom.
+// The corners are split by the lines from the outer corners to the inner corners of the border.
+fn border_side(position: vec2<f32>, size: vec2<f32>, border: vec4<f32>) -> u32 {
+    let distances = vec4<f32>(
+        position.x + 0.5 * size.x,
+        position.y + 0.5 * size.y,
+        0.5 * size.x - position.x,
+        0.5 * size.y - position.y
+    ) / max(border, vec4<f32>(0.0001));
+    // The sides without width are never the closest
+    let relative_distances = select(distances, vec4<f32>(1e20), border <= vec4<f32>(0.0));
+    var side = 0u;
+    var closest = relative_distances.x;
+    if (relative_distances.y < closest) {
+        side = 1u;
+        closest = relative_distances.y;
+    }
+    if (relative_distances.z < closest) {
+        side = 2u;
+        closest = relative_distances.z;
+    }
+    if (relative_distances.w < closest) {
+        side = 3u;
+    }
+    return side;
+}
+
+// The position of a point along a gradient, from 0 at its start to 1 at its end. The angles are
+// clockwise from the top, and the centers are relative to the node, from its top-left corner.
+fn gradient_position(position: vec2<f32>, size: vec2<f32>, kind: u32, params: vec4<f32>) -> f32 {
+    if (kind == GRADIENT_RADIAL) {
+        // The gradient ends at the corner farthest from the center
+        let center = (params.xy - 0.5) * size;
+        let radius = length(abs(center) + 0.5 * size);
+        return length(position - center) / max(radius, 0.0001);
+    }
+    if (kind == GRADIENT_CONIC) {
+        let center = (params.xy - 0.5) * size;
+        let to_point = position - center;
+        return fract((atan2(to_point.x, -to_point.y) - params.z) / (2.0 * PI));
+    }
+    // The linear gradient goes through the center, its ends on the lines through the corners
+    let direction = vec2<f32>(sin(params.x), -cos(params.x));
+    let line_length = abs(size.x * direction.x) + abs(size.y * direction.y);
+    return dot(position, direction) / max(line_length, 0.0001) + 0.5;
+}
+
 @group(1) @binding(0)
 var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var sprite_sampler: sampler;
 
+@group(2) @binding(0)
+var gradient_texture: texture_2d<f32>;
+@group(2) @binding(1)
+var gradient_sampler: sampler;
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = textureSample(sprite_texture, sprite_sampler, in.uv);
-    color = in.color * color;
-    return color;
+    let part = in.mode & MODE_PART_MASK;
+    let gradient_kind = (in.mode >> 8u) & MODE_GRADIENT_MASK;
+
+    // The gradients are sampled at the texels of their row, interpolated between its first and
+    // last texel centers
+    let position = clamp(
+        gradient_position(in.node_position, in.size, gradient_kind, in.gradient),
+        0.0,
+        1.0
+    );
+    let gradient_uv = vec2<f32>(
+        (position * (GRADIENT_TEXTURE_WIDTH - 1.0) + 0.5) / GRADIENT_TEXTURE_WIDTH,
+        in.gradient.w
+    );
+    let gradient = textureSampleLevel(gradient_texture, gradient_sampler, gradient_uv, 0.0);
+    let fill = in.color * select(vec4<f32>(1.0), gradient, gradient_kind != GRADIENT_NONE);
+
+    let texel = textureSample(sprite_texture, sprite_sampler, in.uv);
+    let color = fill * texel;
+
+    // The derivatives of the distances are computed before branching on the mode
+    let outer_coverage = coverage(sd_rounded_box(in.node_position, in.size, in.border_radius));
+    // The inner edge of the border, with the radii of its corners reduced by the widths of the
+    // adjacent sides
+    let inner_size = in.size - in.border.xy - in.border.zw;
+    let inner_center = 0.5 * (in.border.xy - in.border.zw);
+    let inner_radii = max(
+        in.border_radius - max(in.border.xzzx, in.border.yyww),
+        vec4<f32>(0.0)
+    );
+    let inner_coverage = coverage(
+        sd_rounded_box(in.node_position - inner_center, inner_size, inner_radii)
+    );
+
+    // The box of the shadow, offset and grown by its spread radius, or shrunk if inset
+    let spread = select(in.border.z, -in.border.z, part == MODE_INSET_BOX_SHADOW);
+    let shadow_size = max(in.size + 2.0 * spread, vec2<f32>(0.0));
+    let shadow_radii = max(in.border_radius + spread, vec4<f32>(0.0));
+    let shadow_distance =
+        sd_rounded_box(in.node_position - in.border.xy, shadow_size, shadow_radii);
+    // The shadow fades out over twice its blur radius, centered on its edge
+    let shadow_edge = max(in.border.w, max(0.5 * fwidth(shadow_distance), 0.0001));
+    let shadow_alpha = 1.0 - smoothstep(-shadow_edge, shadow_edge, shadow_distance);
+
+    // The alpha of a signed distance field glyph is the distance to its edge, smoothed over a
+    // pixel of the screen divided by the sharpness in the border
+    let sdf_edge = max(0.5 * fwidth(texel.a), 0.0001) / max(in.border.x, 0.0001);
+    let sdf_coverage = smoothstep(0.5 - sdf_edge, 0.5 + sdf_edge, texel.a);
+
+    // The clipped parts of the node are cut, anti-aliasing the edge of the clip shape
+    let clip = clip_coverage(in.mode, in.clip_point, in.clip_size, in.clip_radius);
+
+    if (part == MODE_TEXTURED) {
+        return vec4<f32>(color.rgb, color.a * clip);
+    }
+    if (part == MODE_ROUNDED) {
+        return vec4<f32>(color.rgb, color.a * outer_coverage * clip);
+    }
+    if (part == MODE_SDF_GLYPH) {
+        return vec4<f32>(color.rgb, fill.a * sdf_coverage * clip);
+    }
+    // The shadows are drawn outside of the node, or inside of it if inset
+    if (part == MODE_BOX_SHADOW) {
+        return vec4<f32>(in.color.rgb, in.color.a * shadow_alpha * (1.0 - outer_coverage) * clip);
+    }
+    if (part == MODE_INSET_BOX_SHADOW) {
+        return vec4<f32>(in.color.rgb, in.color.a * (1.0 - shadow_alpha) * outer_coverage * clip);
+    }
+    if (border_side(in.node_position, in.size, in.border) != part - MODE_LEFT_BORDER) {
+        discard;
+    }
+    return vec4<f32>(fill.rgb, fill.a * outer_coverage * (1.0 - inner_coverage) * clip);
 }
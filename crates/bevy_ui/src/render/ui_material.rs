@@ -0,0 +1,633 @@
+use super::{
+    inherited_opacity, queue_uinodes, resolve_border, stacked_uinodes, DrawUiNode, ExtractedUiNode,
+    ExtractedUiNodes, RenderUiSystem, SetUiViewBindGroup, TransparentUi, UiBatch, UiNodePart,
+    UiPipeline, UiPipelineKey, UI_MATERIAL_SHADER_HANDLE,
+};
+use crate::{
+    prelude::UiCameraConfig, BorderRadius, CalculatedClip, Node, Opacity, Style, UiScale, UiStack,
+    WorldUiStacks,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleId};
+use bevy_core_pipeline::core_3d::Transparent3d;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_hierarchy::Parent;
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    color::Color,
+    render_asset::{PrepareAssetLabel, RenderAssets},
+    render_phase::{
+        AddRenderCommand, DrawFunctionId, DrawFunctions, PhaseItem, RenderCommand,
+        RenderCommandResult, RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, CachedRenderPipelineId,
+        OwnedBindingResource, PipelineCache, RenderPipelineDescriptor, Shader, ShaderRef,
+        SpecializedRenderPipeline, SpecializedRenderPipelines,
+    },
+    renderer::RenderDevice,
+    texture::{FallbackImage, Image, DEFAULT_IMAGE_HANDLE},
+    view::{ComputedVisibility, ExtractedView, Msaa},
+    Extract, RenderApp, RenderStage,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Materials are used alongside [`UiMaterialPlugin`] and
+/// [`MaterialNodeBundle`](crate::node_bundles::MaterialNodeBundle) to draw UI nodes with a
+/// [`Handle<M>`] with custom shader logic, instead of their background color or image.
+///
+/// UiMaterials must implement [`AsBindGroup`] to define how data will be transferred to the GPU and bound in shaders.
+/// [`AsBindGroup`] can be derived, which makes generating bindings straightforward. See the [`AsBindGroup`] docs for details.
+///
+/// Materials must also implement [`TypeUuid`] so they can be treated as an [`Asset`](bevy_asset::Asset).
+///
+/// The nodes are drawn in the same pass and order as the other UI nodes, with the vertices of the
+/// UI shader: the shaders of the material can import `bevy_ui::ui_vertex_output` for its
+/// `VertexOutput`, whose `uv` goes from `(0, 0)` at the top left corner of the node to `(1, 1)` at
+/// its bottom right corner. Its `size`, `border_radius` and `border` are the ones of the node in
+/// logical pixels, and its `node_position` the position relative to the center of the node. Its
+/// `color` is white, with the inherited [`Opacity`] of the node as alpha. The material is bound to
+/// group 1.
+///
+/// The import also has the `sd_rounded_box` and `coverage` functions to draw the rounded corners
+/// of the node, and `clip_coverage`, whose result multiplies the alpha of the materials clipped by
+/// the ancestors of their node like the other UI nodes.
+///
+/// # Example
+///
+/// Here is a simple UiMaterial implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
+/// check out the [`AsBindGroup`] documentation.
+/// ```
+/// # use bevy_ui::{node_bundles::MaterialNodeBundle, Size, Style, UiMaterial, Val};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypeUuid;
+/// # use bevy_render::{render_resource::{AsBindGroup, ShaderRef}, color::Color};
+/// # use bevy_asset::Assets;
+///
+/// #[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+/// #[uuid = "f4a5b2c8-6d1e-4b7a-9c3f-2e8d0a1b5c6d"]
+/// pub struct ProgressBarMaterial {
+///     // Uniform bindings must implement `ShaderType`, which will be used to convert the value to
+///     // its shader-compatible equivalent. Most core math types already implement `ShaderType`.
+///     // The fields with the same uniform binding are combined into a single uniform.
+///     #[uniform(0)]
+///     color: Color,
+///     #[uniform(0)]
+///     progress: f32,
+/// }
+///
+/// // All functions on `UiMaterial` have default impls. You only need to implement the
+/// // functions that are relevant for your material.
+/// impl UiMaterial for ProgressBarMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/progress_bar.wgsl".into()
+///     }
+/// }
+///
+/// // Spawn a node using `ProgressBarMaterial`.
+/// fn setup(mut commands: Commands, mut materials: ResMut<Assets<ProgressBarMaterial>>) {
+///     commands.spawn(MaterialNodeBundle {
+///         style: Style {
+///             size: Size::new(Val::Px(200.0), Val::Px(20.0)),
+///             ..Default::default()
+///         },
+///         material: materials.add(ProgressBarMaterial {
+///             color: Color::GREEN,
+///             progress: 0.5,
+///         }),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+/// In WGSL shaders, the material's binding would look like this:
+///
+/// ```wgsl
+/// #import bevy_ui::ui_vertex_output
+///
+/// struct ProgressBarMaterial {
+///     color: vec4<f32>,
+///     progress: f32,
+/// };
+///
+/// @group(1) @binding(0)
+/// var<uniform> material: ProgressBarMaterial;
+///
+/// @fragment
+/// fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+///     return select(vec4<f32>(0.0), material.color, in.uv.x < material.progress);
+/// }
+/// ```
+pub trait UiMaterial: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'static {
+    /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default UI vertex shader
+    /// will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the node
+    /// will be filled with white.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: UiMaterialKey<Self>) {}
+}
+
+/// Adds the necessary ECS resources and render logic to enable drawing the UI nodes with a
+/// [`Handle<M>`] with the given [`UiMaterial`].
+///
+/// The successive nodes sharing the same material are drawn in a single batch.
+pub struct UiMaterialPlugin<M: UiMaterial>(PhantomData<M>);
+
+impl<M: UiMaterial> Default for UiMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: UiMaterial> Plugin for UiMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.add_asset::<M>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<TransparentUi, DrawUiMaterial<M>>()
+                .add_render_command::<Transparent3d, DrawUiMaterial<M>>()
+                .init_resource::<UiMaterialPipeline<M>>()
+                .init_resource::<ExtractedUiMaterials<M>>()
+                .init_resource::<RenderUiMaterials<M>>()
+                .init_resource::<SpecializedRenderPipelines<UiMaterialPipeline<M>>>()
+                .add_system_to_stage(RenderStage::Extract, extract_ui_materials::<M>)
+                .add_system_to_stage(
+                    RenderStage::Extract,
+                    extract_ui_material_nodes::<M>.after(RenderUiSystem::ExtractNode),
+                )
+                .add_system_to_stage(
+                    RenderStage::Prepare,
+                    prepare_ui_materials::<M>.after(PrepareAssetLabel::PreAssetPrepare),
+                )
+                .add_system_to_stage(
+                    RenderStage::Queue,
+                    queue_ui_materials::<M>.before(queue_uinodes),
+                );
+        }
+    }
+}
+
+/// Render pipeline data for a given [`UiMaterial`]
+#[derive(Resource)]
+pub struct UiMaterialPipeline<M: UiMaterial> {
+    pub ui_pipeline: UiPipeline,
+    pub ui_material_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+pub struct UiMaterialKey<M: UiMaterial> {
+    pub ui_key: UiPipelineKey,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: UiMaterial> Eq for UiMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: UiMaterial> PartialEq for UiMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ui_key == other.ui_key && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: UiMaterial> Clone for UiMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ui_key: self.ui_key,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: UiMaterial> Hash for UiMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.ui_key.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+impl<M: UiMaterial> SpecializedRenderPipeline for UiMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = UiMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.ui_pipeline.specialize(key.ui_key);
+        if let Some(vertex_shader) = &self.vertex_shader {
+            descriptor.vertex.shader = vertex_shader.clone();
+        }
+
+        descriptor.fragment.as_mut().unwrap().shader = match &self.fragment_shader {
+            Some(fragment_shader) => fragment_shader.clone(),
+            None => UI_MATERIAL_SHADER_HANDLE.typed(),
+        };
+        descriptor.layout = Some(vec![
+            self.ui_pipeline.view_layout.clone(),
+            self.ui_material_layout.clone(),
+        ]);
+        descriptor.label = Some("ui_material_pipeline".into());
+
+        M::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+impl<M: UiMaterial> FromWorld for UiMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let ui_material_layout = M::bind_group_layout(render_device);
+
+        UiMaterialPipeline {
+            ui_pipeline: world.resource::<UiPipeline>().clone(),
+            ui_material_layout,
+            vertex_shader: match M::vertex_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The draw function and the pipeline drawing the UI nodes of each view and material, on the
+/// screen or in the world, queued every frame by the [`UiMaterialPlugin`]s for
+/// [`queue_uinodes`].
+#[derive(Resource, Default)]
+pub struct UiMaterialPipelines {
+    pub(crate) values: HashMap<(Entity, HandleId, bool), (DrawFunctionId, CachedRenderPipelineId)>,
+}
+
+pub type DrawUiMaterial<M> = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetUiMaterialBindGroup<M, 1>,
+    DrawUiNode,
+);
+
+pub struct SetUiMaterialBindGroup<M: UiMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: UiMaterial, const I: usize> RenderCommand<P>
+    for SetUiMaterialBindGroup<M, I>
+{
+    type Param = SRes<RenderUiMaterials<M>>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<UiBatch>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        batch: &'_ UiBatch,
+        materials: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(material) = batch
+            .material_handle_id
+            .and_then(|handle_id| materials.into_inner().get(&Handle::weak(handle_id)))
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+/// Extracts the visible UI nodes with a [`Handle<M>`], to be drawn with their material like
+/// a background.
+#[allow(clippy::type_complexity)]
+pub fn extract_ui_material_nodes<M: UiMaterial>(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    ui_stack: Extract<Res<UiStack>>,
+    world_ui_stacks: Extract<Res<WorldUiStacks>>,
+    ui_scale: Extract<Res<UiScale>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &Handle<M>,
+            &Style,
+            Option<&BorderRadius>,
+            Option<&Parent>,
+            &ComputedVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+    parent_query: Extract<Query<&Node>>,
+    opacity_query: Extract<Query<(Option<&Opacity>, Option<&Parent>), With<Node>>>,
+) {
+    if uinode_query.is_empty() {
+        return;
+    }
+
+    let scale = ui_scale.scale as f32;
+    for (world_ui, stack_index, entity) in stacked_uinodes(&ui_stack, &world_ui_stacks) {
+        let Ok((uinode, transform, handle, style, border_radius, parent, visibility, clip)) =
+            uinode_query.get(entity)
+        else {
+            continue;
+        };
+        if !visibility.is_visible() {
+            continue;
+        }
+        let opacity = inherited_opacity(entity, &opacity_query);
+        if opacity <= 0.0 {
+            continue;
+        }
+
+        let border_radius = border_radius
+            .map(|border_radius| border_radius.resolve(uinode.size(), scale))
+            .unwrap_or_default();
+        let parent_width = parent
+            .and_then(|parent| parent_query.get(parent.get()).ok())
+            .map_or(0.0, |parent| parent.size().x);
+        extracted_uinodes.uinodes.push(ExtractedUiNode {
+            stack_index,
+            transform: transform.compute_matrix(),
+            background_color: Color::WHITE.with_a(opacity),
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: uinode.size(),
+            },
+            image: DEFAULT_IMAGE_HANDLE.typed().clone_weak(),
+            atlas_size: None,
+            clip: clip.copied(),
+            flip_x: false,
+            flip_y: false,
+            border_radius,
+            border: resolve_border(style, parent_width, scale),
+            part: UiNodePart::Background,
+            gradient: None,
+            material_handle_id: Some(handle.id()),
+            world_ui,
+        });
+    }
+}
+
+/// Queues the pipelines of the [`UiMaterial`]s of the extracted nodes in each view, for
+/// [`queue_uinodes`] to draw their batches.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_ui_materials<M: UiMaterial>(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    draw_functions_3d: Res<DrawFunctions<Transparent3d>>,
+    ui_material_pipeline: Res<UiMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UiMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_materials: Res<RenderUiMaterials<M>>,
+    mut ui_material_pipelines: ResMut<UiMaterialPipelines>,
+    extracted_uinodes: Res<ExtractedUiNodes>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView), With<RenderPhase<TransparentUi>>>,
+    world_views: Query<
+        (Entity, &ExtractedView, Option<&UiCameraConfig>),
+        With<RenderPhase<Transparent3d>>,
+    >,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    // The nodes whose material isn't prepared yet, or is of another type, are skipped
+    let ui_materials: HashSet<Handle<M>> = extracted_uinodes
+        .uinodes
+        .iter()
+        .filter_map(|uinode| uinode.material_handle_id)
+        .map(Handle::weak)
+        .filter(|handle| render_materials.contains_key(handle))
+        .collect();
+    if ui_materials.is_empty() {
+        return;
+    }
+
+    let draw_function = draw_functions.read().id::<DrawUiMaterial<M>>();
+    for (view_entity, view) in &views {
+        for handle in &ui_materials {
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &ui_material_pipeline,
+                UiMaterialKey {
+                    ui_key: UiPipelineKey {
+                        hdr: view.hdr,
+                        world: false,
+                        msaa_samples: 1,
+                    },
+                    bind_group_data: render_materials[handle].key.clone(),
+                },
+            );
+            ui_material_pipelines
+                .values
+                .insert((view_entity, handle.id(), false), (draw_function, pipeline));
+        }
+    }
+
+    let draw_function = draw_functions_3d.read().id::<DrawUiMaterial<M>>();
+    for (view_entity, view, camera_ui) in &world_views {
+        // ignore cameras with disabled ui
+        if matches!(camera_ui, Some(&UiCameraConfig { show_ui: false, .. })) {
+            continue;
+        }
+        for handle in &ui_materials {
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &ui_material_pipeline,
+                UiMaterialKey {
+                    ui_key: UiPipelineKey {
+                        hdr: view.hdr,
+                        world: true,
+                        msaa_samples: msaa.samples,
+                    },
+                    bind_group_data: render_materials[handle].key.clone(),
+                },
+            );
+            ui_material_pipelines
+                .values
+                .insert((view_entity, handle.id(), true), (draw_function, pipeline));
+        }
+    }
+}
+
+/// Data prepared for a [`UiMaterial`] instance.
+pub struct PreparedUiMaterial<T: UiMaterial> {
+    pub bindings: Vec<OwnedBindingResource>,
+    pub bind_group: BindGroup,
+    pub key: T::Data,
+}
+
+#[derive(Resource)]
+struct ExtractedUiMaterials<M: UiMaterial> {
+    extracted: Vec<(Handle<M>, M)>,
+    removed: Vec<Handle<M>>,
+}
+
+impl<M: UiMaterial> Default for ExtractedUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            extracted: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+/// Stores all prepared representations of [`UiMaterial`] assets for as long as they exist.
+#[derive(Resource, Deref, DerefMut)]
+pub struct RenderUiMaterials<T: UiMaterial>(HashMap<Handle<T>, PreparedUiMaterial<T>>);
+
+impl<T: UiMaterial> Default for RenderUiMaterials<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// This system extracts all created or modified assets of the corresponding [`UiMaterial`] type
+/// into the "render world".
+fn extract_ui_materials<M: UiMaterial>(
+    mut commands: Commands,
+    mut events: Extract<EventReader<AssetEvent<M>>>,
+    assets: Extract<Res<Assets<M>>>,
+) {
+    let mut changed_assets = HashSet::default();
+    let mut removed = Vec::new();
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_assets.insert(handle.clone_weak());
+            }
+            AssetEvent::Removed { handle } => {
+                changed_assets.remove(handle);
+                removed.push(handle.clone_weak());
+            }
+        }
+    }
+
+    let mut extracted_assets = Vec::new();
+    for handle in changed_assets.drain() {
+        if let Some(asset) = assets.get(&handle) {
+            extracted_assets.push((handle, asset.clone()));
+        }
+    }
+
+    commands.insert_resource(ExtractedUiMaterials {
+        extracted: extracted_assets,
+        removed,
+    });
+}
+
+/// All [`UiMaterial`] values of a given type that should be prepared next frame.
+pub struct PrepareNextFrameUiMaterials<M: UiMaterial> {
+    assets: Vec<(Handle<M>, M)>,
+}
+
+impl<M: UiMaterial> Default for PrepareNextFrameUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            assets: Default::default(),
+        }
+    }
+}
+
+/// This system prepares all assets of the corresponding [`UiMaterial`] type
+/// which where extracted this frame for the GPU.
+fn prepare_ui_materials<M: UiMaterial>(
+    mut prepare_next_frame: Local<PrepareNextFrameUiMaterials<M>>,
+    mut extracted_assets: ResMut<ExtractedUiMaterials<M>>,
+    mut render_materials: ResMut<RenderUiMaterials<M>>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+    pipeline: Res<UiMaterialPipeline<M>>,
+) {
+    let queued_assets = std::mem::take(&mut prepare_next_frame.assets);
+    for (handle, material) in queued_assets {
+        match prepare_ui_material(
+            &material,
+            &render_device,
+            &images,
+            &fallback_image,
+            &pipeline,
+        ) {
+            Ok(prepared_asset) => {
+                render_materials.insert(handle, prepared_asset);
+            }
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                prepare_next_frame.assets.push((handle, material));
+            }
+        }
+    }
+
+    for removed in std::mem::take(&mut extracted_assets.removed) {
+        render_materials.remove(&removed);
+    }
+
+    for (handle, material) in std::mem::take(&mut extracted_assets.extracted) {
+        match prepare_ui_material(
+            &material,
+            &render_device,
+            &images,
+            &fallback_image,
+            &pipeline,
+        ) {
+            Ok(prepared_asset) => {
+                render_materials.insert(handle, prepared_asset);
+            }
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                prepare_next_frame.assets.push((handle, material));
+            }
+        }
+    }
+}
+
+fn prepare_ui_material<M: UiMaterial>(
+    material: &M,
+    render_device: &RenderDevice,
+    images: &RenderAssets<Image>,
+    fallback_image: &FallbackImage,
+    pipeline: &UiMaterialPipeline<M>,
+) -> Result<PreparedUiMaterial<M>, AsBindGroupError> {
+    let prepared = material.as_bind_group(
+        &pipeline.ui_material_layout,
+        render_device,
+        images,
+        fallback_image,
+    )?;
+    Ok(PreparedUiMaterial {
+        bindings: prepared.bindings,
+        bind_group: prepared.bind_group,
+        key: prepared.data,
+    })
+}
@@ -0,0 +1,9 @@
+#import bevy_ui::ui_vertex_output
+
+// The default fragment shader of the UI materials, filling their node with its vertex color.
+// The vertices of the UI materials are drawn by the vertex shader of `ui.wgsl`.
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let clip = clip_coverage(in.mode, in.clip_point, in.clip_size, in.clip_radius);
+    return vec4<f32>(in.color.rgb, in.color.a * clip);
+}
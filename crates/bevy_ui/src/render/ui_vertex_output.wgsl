@@ -0,0 +1,55 @@
+#define_import_path bevy_ui::ui_vertex_output
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    // The position relative to the center of the node
+    @location(2) node_position: vec2<f32>,
+    @location(3) @interpolate(flat) size: vec2<f32>,
+    // top-left, top-right, bottom-right, bottom-left
+    @location(4) @interpolate(flat) border_radius: vec4<f32>,
+    // left, top, right, bottom, or for shadows offset, spread radius and blur radius
+    @location(5) @interpolate(flat) border: vec4<f32>,
+    // The parameters of the gradient, the last one being the coordinate of its row in the texture
+    @location(6) @interpolate(flat) gradient: vec4<f32>,
+    @location(7) @interpolate(flat) mode: u32,
+    // The position in the space of the shape clipping the node, relative to its center
+    @location(8) clip_point: vec2<f32>,
+    @location(9) @interpolate(flat) clip_size: vec2<f32>,
+    @location(10) @interpolate(flat) clip_radius: vec4<f32>,
+    @builtin(position) position: vec4<f32>,
+};
+
+// The bit of the mode of the nodes clipped by their clip shape
+let MODE_CLIPPED: u32 = 65536u;
+
+// The signed distance from a point to a box centered on the origin with rounded corners,
+// negative inside the box. The y axis points down.
+fn sd_rounded_box(position: vec2<f32>, size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
+    // The radius of the corner closest to the point
+    let left_right = select(corner_radii.xw, corner_radii.yz, position.x > 0.0);
+    let radius = select(left_right.x, left_right.y, position.y > 0.0);
+    let corner_to_point = abs(position) - 0.5 * size + radius;
+    let outside = length(max(corner_to_point, vec2<f32>(0.0)));
+    let inside = min(max(corner_to_point.x, corner_to_point.y), 0.0);
+    return outside + inside - radius;
+}
+
+// The part of a pixel covered by the shape of a signed distance, anti-aliasing its edge
+fn coverage(distance: f32) -> f32 {
+    let pixel_size = max(fwidth(distance), 0.0001);
+    return clamp(0.5 - distance / pixel_size, 0.0, 1.0);
+}
+
+// The part of a pixel of a node left visible by the shape clipping it, with the `mode`,
+// `clip_point`, `clip_size` and `clip_radius` of its `VertexOutput`. It must be called in uniform
+// control flow.
+fn clip_coverage(
+    mode: u32,
+    clip_point: vec2<f32>,
+    clip_size: vec2<f32>,
+    clip_radius: vec4<f32>
+) -> f32 {
+    let clip = coverage(sd_rounded_box(clip_point, clip_size, clip_radius));
+    return select(1.0, clip, (mode & MODE_CLIPPED) != 0u);
+}
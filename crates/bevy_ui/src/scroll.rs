@@ -0,0 +1,126 @@
+use crate::{CalculatedClip, Node, Overflow, Style, UiStack};
+use bevy_ecs::{
+    event::EventReader,
+    prelude::{Component, With},
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::ComputedVisibility;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{PrimaryWindow, Window};
+
+/// The distance scrolled by a line of the mouse wheel, in logical pixels
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+/// The scroll offset of a node with [`Overflow::Scroll`], in logical pixels from the top-left
+/// corner of its content.
+///
+/// The content of the node is the extent of its children and its padding. The offset is clamped
+/// to the content by [`flex_node_system`](crate::flex_node_system), and changed by
+/// [`ui_scroll_system`] with the mouse wheel and by dragging touches.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ScrollPosition {
+    /// How far the content is scrolled to the right and downwards
+    pub offset: Vec2,
+    /// The largest offset, scrolling to the end of the content
+    pub(crate) max_offset: Vec2,
+}
+
+impl ScrollPosition {
+    /// The content scrolled by the given offset
+    pub fn new(offset: Vec2) -> Self {
+        Self {
+            offset,
+            ..Default::default()
+        }
+    }
+
+    /// The largest offset, scrolling to the end of the content, as of the last layout
+    pub fn max_offset(&self) -> Vec2 {
+        self.max_offset
+    }
+}
+
+/// The system that scrolls the nodes with [`Overflow::Scroll`] under the cursor with the mouse
+/// wheel, and under touches by dragging them.
+///
+/// The topmost node that can scroll further in the direction of the input is scrolled, so that
+/// nested scrolled nodes pass the scrolling to their ancestors at the end of their content.
+pub fn ui_scroll_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_stack: Res<UiStack>,
+    mut node_query: Query<(
+        &Node,
+        &GlobalTransform,
+        &Style,
+        &mut ScrollPosition,
+        Option<&CalculatedClip>,
+        Option<&ComputedVisibility>,
+    )>,
+) {
+    let mut scroll = |position: Vec2, delta: Vec2| {
+        // From the top node to the bottom one
+        for entity in ui_stack.uinodes.iter().rev() {
+            let Ok((node, global_transform, style, mut scroll_position, clip, visibility)) =
+                node_query.get_mut(*entity)
+            else {
+                continue;
+            };
+            if style.overflow != Overflow::Scroll
+                || visibility.map_or(false, |visibility| !visibility.is_visible())
+            {
+                continue;
+            }
+            let rect =
+                Rect::from_center_size(global_transform.translation().truncate(), node.size());
+            if !rect.contains(position) || clip.map_or(false, |clip| !clip.contains(position)) {
+                continue;
+            }
+            let offset =
+                (scroll_position.offset + delta).clamp(Vec2::ZERO, scroll_position.max_offset);
+            if offset != scroll_position.offset {
+                scroll_position.offset = offset;
+                return;
+            }
+        }
+    };
+
+    let wheel_delta: Vec2 = mouse_wheel_events
+        .iter()
+        .map(|event| {
+            let delta = Vec2::new(event.x, event.y);
+            match event.unit {
+                MouseScrollUnit::Line => delta * SCROLL_LINE_HEIGHT,
+                MouseScrollUnit::Pixel => delta,
+            }
+        })
+        .sum();
+    if wheel_delta != Vec2::ZERO {
+        let cursor_position = windows.get_single().ok().and_then(|window| {
+            window.cursor.position.map(|mut cursor_position| {
+                cursor_position.y = window.height() as f64 - cursor_position.y;
+                cursor_position.as_vec2()
+            })
+        });
+        if let Some(cursor_position) = cursor_position {
+            // Scrolling the wheel up moves the content down, towards its start
+            scroll(cursor_position, -wheel_delta);
+        }
+    }
+
+    // The content follows the touches, scrolling the node they started on
+    for touch in touches.iter() {
+        if touch.delta() != Vec2::ZERO {
+            scroll(touch.start_position(), -touch.delta());
+        }
+    }
+}
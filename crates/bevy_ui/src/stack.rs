@@ -2,8 +2,9 @@
 
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::prelude::*;
+use bevy_math::{Mat4, Vec3};
 
-use crate::{Node, ZIndex};
+use crate::{Node, WorldUi, WorldUiStack, WorldUiStacks, ZIndex};
 
 /// The current UI stack, which contains all UI nodes ordered by their depth.
 ///
@@ -26,16 +27,44 @@ struct StackingContextEntry {
 }
 
 /// Generates the render stack for UI nodes.
+///
+/// The trees with a [`WorldUi`] on their root get their own stack in the [`WorldUiStacks`],
+/// where the global z-indices only order the nodes of the same tree.
 pub fn ui_stack_system(
     mut ui_stack: ResMut<UiStack>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    mut world_ui_stacks: ResMut<WorldUiStacks>,
+    root_node_query: Query<(Entity, Option<&WorldUi>), (With<Node>, Without<Parent>)>,
     zindex_query: Query<&ZIndex, With<Node>>,
     children_query: Query<&Children>,
 ) {
     let mut global_context = StackingContext::default();
 
+    world_ui_stacks.stacks.clear();
     let mut total_entry_count: usize = 0;
-    for entity in &root_node_query {
+    for (entity, world_ui) in &root_node_query {
+        if world_ui.is_some() {
+            let mut world_ui_context = StackingContext::default();
+            let mut world_ui_entry_count: usize = 0;
+            insert_context_hierarchy(
+                &zindex_query,
+                &children_query,
+                entity,
+                &mut world_ui_context,
+                None,
+                &mut world_ui_entry_count,
+            );
+            let mut uinodes = Vec::with_capacity(world_ui_entry_count);
+            fill_stack_recursively(&mut uinodes, &mut world_ui_context);
+            // the transform is set by `update_world_ui_system`
+            world_ui_stacks.stacks.push(WorldUiStack {
+                root: entity,
+                uinodes,
+                transform: Mat4::IDENTITY,
+                center: Vec3::ZERO,
+            });
+            continue;
+        }
+
         insert_context_hierarchy(
             &zindex_query,
             &children_query,
@@ -111,7 +140,7 @@ mod tests {
     };
     use bevy_hierarchy::BuildChildren;
 
-    use crate::{Node, UiStack, ZIndex};
+    use crate::{Node, UiStack, WorldUiStacks, ZIndex};
 
     use super::ui_stack_system;
 
@@ -136,6 +165,7 @@ mod tests {
     fn test_ui_stack_system() {
         let mut world = World::default();
         world.init_resource::<UiStack>();
+        world.init_resource::<WorldUiStacks>();
 
         let mut queue = CommandQueue::default();
         let mut commands = Commands::new(&mut queue, &world);
@@ -0,0 +1,650 @@
+//! This module contains the tweens animating the style of UI nodes, and the transitions playing
+//! them when the nodes appear, disappear or are interacted with
+
+use crate::{BackgroundColor, Interaction, Opacity, Size, Style, UiRect, Val};
+use bevy_ecs::{
+    change_detection::{DetectChanges, Ref},
+    prelude::*,
+    query::WorldQuery,
+    schedule::{State, StateData},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_render::color::Color;
+use bevy_time::Time;
+use bevy_utils::Duration;
+use std::{f32::consts::PI, mem};
+
+/// An easing curve, mapping the linear progress of a [`UiTween`] to the progress of its value.
+///
+/// See <https://easings.net> for the shape of each curve.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub enum EaseFunction {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExponentialIn,
+    ExponentialOut,
+    ExponentialInOut,
+    /// Pulls back a little before going to the end
+    BackIn,
+    /// Overshoots the end a little before coming back to it
+    BackOut,
+    BackInOut,
+    /// Oscillates around the end before settling on it
+    ElasticOut,
+    /// Bounces on the end like a falling ball
+    BounceOut,
+    /// Jumps to the end in the given number of equal steps
+    Steps(u32),
+}
+
+impl EaseFunction {
+    /// The eased progress for a linear progress between `0.0` and `1.0`.
+    ///
+    /// The eased progress is `0.0` at the start and `1.0` at the end, but can go beyond these
+    /// values in between, like with [`EaseFunction::BackOut`].
+    pub fn ease(self, t: f32) -> f32 {
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EaseFunction::Linear => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => 1.0 - (1.0 - t).powi(2),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(2) / 2.0
+                }
+            }
+            EaseFunction::CubicIn => t.powi(3),
+            EaseFunction::CubicOut => 1.0 - (1.0 - t).powi(3),
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t.powi(3)
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            EaseFunction::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            EaseFunction::SineOut => (t * PI / 2.0).sin(),
+            EaseFunction::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+            EaseFunction::ExponentialIn if t == 0.0 => 0.0,
+            EaseFunction::ExponentialIn => 2f32.powf(10.0 * t - 10.0),
+            EaseFunction::ExponentialOut if t == 1.0 => 1.0,
+            EaseFunction::ExponentialOut => 1.0 - 2f32.powf(-10.0 * t),
+            EaseFunction::ExponentialInOut if t == 0.0 || t == 1.0 => t,
+            EaseFunction::ExponentialInOut => {
+                if t < 0.5 {
+                    2f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    1.0 - 2f32.powf(10.0 - 20.0 * t) / 2.0
+                }
+            }
+            EaseFunction::BackIn => (BACK + 1.0) * t.powi(3) - BACK * t * t,
+            EaseFunction::BackOut => {
+                1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2)
+            }
+            EaseFunction::BackInOut => {
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2)
+                        * ((BACK_IN_OUT + 1.0) * (2.0 * t - 2.0) + BACK_IN_OUT)
+                        + 2.0)
+                        / 2.0
+                }
+            }
+            EaseFunction::ElasticOut if t == 0.0 || t == 1.0 => t,
+            EaseFunction::ElasticOut => {
+                2f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * 2.0 * PI / 3.0).sin() + 1.0
+            }
+            EaseFunction::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                if t < 1.0 / D {
+                    N * t * t
+                } else if t < 2.0 / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+            EaseFunction::Steps(0) => t,
+            EaseFunction::Steps(steps) => (t * steps as f32).floor() / steps as f32,
+        }
+    }
+}
+
+impl Default for EaseFunction {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+/// A property of a UI node animated by a [`UiTween`], with its value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiProperty {
+    /// The [`Style::position`] of the node
+    Position(UiRect),
+    /// The [`Style::size`] of the node
+    Size(Size),
+    /// The [`BackgroundColor`] of the node
+    BackgroundColor(Color),
+    /// The [`Opacity`] of the node and its descendants
+    Opacity(f32),
+}
+
+impl UiProperty {
+    /// Whether both values are of the same property
+    pub fn is_same_property(&self, other: &UiProperty) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    /// The value between this one and the other one at the given progress.
+    ///
+    /// The values in pixels and percentages can't be interpolated with each other, and switch
+    /// from one to the other halfway. The values of other properties are not interpolated and
+    /// the other one is returned.
+    pub fn lerp(&self, other: &UiProperty, t: f32) -> UiProperty {
+        match (self, other) {
+            (UiProperty::Position(from), UiProperty::Position(to)) => {
+                UiProperty::Position(UiRect {
+                    left: lerp_val(from.left, to.left, t),
+                    right: lerp_val(from.right, to.right, t),
+                    top: lerp_val(from.top, to.top, t),
+                    bottom: lerp_val(from.bottom, to.bottom, t),
+                })
+            }
+            (UiProperty::Size(from), UiProperty::Size(to)) => UiProperty::Size(Size {
+                width: lerp_val(from.width, to.width, t),
+                height: lerp_val(from.height, to.height, t),
+            }),
+            (UiProperty::BackgroundColor(from), UiProperty::BackgroundColor(to)) => {
+                let from = from.as_rgba_f32();
+                let to = to.as_rgba_f32();
+                let [r, g, b, a] =
+                    [0, 1, 2, 3].map(|i| (from[i] + (to[i] - from[i]) * t).clamp(0.0, 1.0));
+                UiProperty::BackgroundColor(Color::rgba(r, g, b, a))
+            }
+            (UiProperty::Opacity(from), UiProperty::Opacity(to)) => {
+                UiProperty::Opacity((from + (to - from) * t).clamp(0.0, 1.0))
+            }
+            _ => other.clone(),
+        }
+    }
+}
+
+fn lerp_val(from: Val, to: Val, t: f32) -> Val {
+    match (from, to) {
+        (Val::Px(from), Val::Px(to)) => Val::Px(from + (to - from) * t),
+        (Val::Percent(from), Val::Percent(to)) => Val::Percent(from + (to - from) * t),
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// Animates a property of a UI node to a value, when played by its [`UiAnimator`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiTween {
+    /// The value the property starts from, or its value when the tween starts if `None`
+    pub from: Option<UiProperty>,
+    /// The value the property ends with, which is also the animated property
+    pub to: UiProperty,
+    pub duration: Duration,
+    /// The time waited after the tween is played before it starts
+    pub delay: Duration,
+    pub ease: EaseFunction,
+}
+
+impl UiTween {
+    /// Creates a linear tween from the current value of the property to the given one
+    pub fn new(to: UiProperty, duration: Duration) -> Self {
+        Self {
+            from: None,
+            to,
+            duration,
+            delay: Duration::ZERO,
+            ease: EaseFunction::Linear,
+        }
+    }
+
+    /// Returns this [`UiTween`] starting from the given value
+    pub fn with_from(mut self, from: UiProperty) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Returns this [`UiTween`] starting after the given delay
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns this [`UiTween`] with the given easing curve
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PlayingTween {
+    tween: UiTween,
+    /// The time since the tween was played, including its delay
+    elapsed: Duration,
+    /// The value the tween started from, once it has started
+    from: Option<UiProperty>,
+}
+
+/// Plays the [`UiTween`]s of a UI node, updating its properties every frame in
+/// [`ui_animation_system`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct UiAnimator {
+    tweens: Vec<PlayingTween>,
+}
+
+impl UiAnimator {
+    /// Starts playing a tween, stopping the tween of the same property if any.
+    ///
+    /// Without explicit start value, the tween starts from the value the property has at that
+    /// time, even in the middle of the stopped tween.
+    pub fn play(&mut self, tween: UiTween) {
+        self.tweens
+            .retain(|playing| !playing.tween.to.is_same_property(&tween.to));
+        self.tweens.push(PlayingTween {
+            tween,
+            elapsed: Duration::ZERO,
+            from: None,
+        });
+    }
+
+    /// Stops all the tweens, leaving the properties at their current value
+    pub fn stop(&mut self) {
+        self.tweens.clear();
+    }
+
+    /// Whether some tweens are still playing or waiting for their delay
+    pub fn is_playing(&self) -> bool {
+        !self.tweens.is_empty()
+    }
+}
+
+/// The [`UiTween`]s played by a UI node when it appears, disappears, or its [`Interaction`]
+/// changes, by [`ui_animation_system`].
+///
+/// The tweens are played by the [`UiAnimator`] of the node, which is added if it is missing.
+#[derive(Component, Clone, Debug, Default)]
+pub struct UiTransitions {
+    /// The tweens played when this component is added to the node, usually when it is spawned
+    pub enter: Vec<UiTween>,
+    /// The tweens played when the node gets a [`UiExit`], before it is despawned with its
+    /// descendants
+    pub exit: Vec<UiTween>,
+    /// The tweens played when the [`Interaction`] of the node changes to each value
+    pub interactions: Vec<(Interaction, Vec<UiTween>)>,
+}
+
+impl UiTransitions {
+    /// Returns these [`UiTransitions`] playing the given tween when the node appears
+    pub fn with_enter(mut self, tween: UiTween) -> Self {
+        self.enter.push(tween);
+        self
+    }
+
+    /// Returns these [`UiTransitions`] playing the given tween when the node disappears
+    pub fn with_exit(mut self, tween: UiTween) -> Self {
+        self.exit.push(tween);
+        self
+    }
+
+    /// Returns these [`UiTransitions`] playing the given tween when the [`Interaction`] of the
+    /// node changes to the given value
+    pub fn with_interaction(mut self, interaction: Interaction, tween: UiTween) -> Self {
+        match self
+            .interactions
+            .iter_mut()
+            .find(|(value, _)| *value == interaction)
+        {
+            Some((_, tweens)) => tweens.push(tween),
+            None => self.interactions.push((interaction, vec![tween])),
+        }
+        self
+    }
+
+    /// The tweens played when the [`Interaction`] of the node changes to the given value
+    pub fn interaction_tweens(&self, interaction: Interaction) -> &[UiTween] {
+        self.interactions
+            .iter()
+            .find(|(value, _)| *value == interaction)
+            .map_or(&[], |(_, tweens)| tweens)
+    }
+}
+
+/// Makes a UI node disappear: it plays the exit tweens of its [`UiTransitions`], then is
+/// despawned with its descendants.
+///
+/// A node without exit tweens is despawned right away.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct UiExit;
+
+/// Makes a UI node disappear with a [`UiExit`] when [`State<T>`] leaves the given state.
+///
+/// It is handled by [`ui_state_scoped_system::<T>`](ui_state_scoped_system), which needs to be
+/// added to the app for each state type.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct UiStateScoped<T: StateData>(pub T);
+
+/// Adds a [`UiExit`] to the UI nodes whose [`UiStateScoped`] state isn't the current one.
+pub fn ui_state_scoped_system<T: StateData>(
+    mut commands: Commands,
+    state: Res<State<T>>,
+    scoped_query: Query<(Entity, &UiStateScoped<T>), Without<UiExit>>,
+) {
+    for (entity, scoped) in &scoped_query {
+        if *state.current() != scoped.0 {
+            commands.entity(entity).insert(UiExit);
+        }
+    }
+}
+
+/// Main query for [`ui_animation_system`]
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub struct AnimatedNodeQuery {
+    entity: Entity,
+    animator: Option<&'static mut UiAnimator>,
+    transitions: Option<Ref<'static, UiTransitions>>,
+    interaction: Option<Ref<'static, Interaction>>,
+    exit: Option<Ref<'static, UiExit>>,
+    style: &'static mut Style,
+    background_color: Option<&'static mut BackgroundColor>,
+    opacity: Option<&'static mut Opacity>,
+}
+
+/// The system playing the [`UiTransitions`] and updating the properties animated by the
+/// [`UiAnimator`]s.
+///
+/// It despawns the nodes with a [`UiExit`] once their animator is done.
+#[allow(clippy::type_complexity)]
+pub fn ui_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut node_query: Query<
+        AnimatedNodeQuery,
+        Or<(With<UiAnimator>, With<UiTransitions>, With<UiExit>)>,
+    >,
+) {
+    let delta = time.delta();
+    for mut node in &mut node_query {
+        let mut new_animator = None;
+        let animator = match &mut node.animator {
+            Some(animator) => &mut **animator,
+            None => new_animator.insert(UiAnimator::default()),
+        };
+
+        // the tweens played before this frame advance, the new ones start from the beginning
+        for playing in &mut animator.tweens {
+            playing.elapsed += delta;
+        }
+
+        if let Some(transitions) = &node.transitions {
+            if transitions.is_added() {
+                for tween in &transitions.enter {
+                    animator.play(tween.clone());
+                }
+            }
+            // the interaction of a spawned node isn't a change
+            if let Some(interaction) = &node.interaction {
+                if interaction.is_changed() && !interaction.is_added() {
+                    for tween in transitions.interaction_tweens(**interaction) {
+                        animator.play(tween.clone());
+                    }
+                }
+            }
+            if node.exit.as_ref().map_or(false, |exit| exit.is_added()) {
+                for tween in &transitions.exit {
+                    animator.play(tween.clone());
+                }
+            }
+        }
+
+        animator.tweens.retain_mut(|playing| {
+            if playing.elapsed < playing.tween.delay {
+                return true;
+            }
+            let from = playing.from.get_or_insert_with(|| {
+                playing.tween.from.clone().unwrap_or_else(|| {
+                    current_value(
+                        &playing.tween.to,
+                        &node.style,
+                        node.background_color.as_deref(),
+                        node.opacity.as_deref(),
+                    )
+                })
+            });
+            let elapsed = playing.elapsed - playing.tween.delay;
+            let progress = if elapsed >= playing.tween.duration {
+                1.0
+            } else {
+                elapsed.as_secs_f32() / playing.tween.duration.as_secs_f32()
+            };
+            match from.lerp(&playing.tween.to, playing.tween.ease.ease(progress)) {
+                // only trigger a new layout when the style changes
+                UiProperty::Position(position) => {
+                    if node.style.position != position {
+                        node.style.position = position;
+                    }
+                }
+                UiProperty::Size(size) => {
+                    if node.style.size != size {
+                        node.style.size = size;
+                    }
+                }
+                UiProperty::BackgroundColor(color) => match &mut node.background_color {
+                    Some(background_color) => background_color.0 = color,
+                    None => {
+                        commands.entity(node.entity).insert(BackgroundColor(color));
+                    }
+                },
+                UiProperty::Opacity(opacity) => match &mut node.opacity {
+                    Some(node_opacity) => node_opacity.0 = opacity,
+                    None => {
+                        commands.entity(node.entity).insert(Opacity(opacity));
+                    }
+                },
+            }
+            progress < 1.0
+        });
+
+        let is_playing = animator.is_playing();
+        if let Some(animator) = new_animator.filter(UiAnimator::is_playing) {
+            commands.entity(node.entity).insert(animator);
+        }
+        if node.exit.is_some() && !is_playing {
+            commands.entity(node.entity).despawn_recursive();
+        }
+    }
+}
+
+/// The current value of the property of a UI node
+fn current_value(
+    property: &UiProperty,
+    style: &Style,
+    background_color: Option<&BackgroundColor>,
+    opacity: Option<&Opacity>,
+) -> UiProperty {
+    match property {
+        UiProperty::Position(_) => UiProperty::Position(style.position),
+        UiProperty::Size(_) => UiProperty::Size(style.size),
+        UiProperty::BackgroundColor(_) => {
+            UiProperty::BackgroundColor(background_color.copied().unwrap_or_default().0)
+        }
+        UiProperty::Opacity(_) => UiProperty::Opacity(opacity.copied().unwrap_or_default().0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ui_animation_system, EaseFunction, UiAnimator, UiExit, UiProperty, UiTransitions, UiTween,
+    };
+    use crate::{BackgroundColor, Interaction, Size, Style, Val};
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        world::World,
+    };
+    use bevy_render::color::Color;
+    use bevy_time::Time;
+    use bevy_utils::{Duration, Instant};
+
+    const EASE_FUNCTIONS: [EaseFunction; 19] = [
+        EaseFunction::Linear,
+        EaseFunction::QuadraticIn,
+        EaseFunction::QuadraticOut,
+        EaseFunction::QuadraticInOut,
+        EaseFunction::CubicIn,
+        EaseFunction::CubicOut,
+        EaseFunction::CubicInOut,
+        EaseFunction::SineIn,
+        EaseFunction::SineOut,
+        EaseFunction::SineInOut,
+        EaseFunction::ExponentialIn,
+        EaseFunction::ExponentialOut,
+        EaseFunction::ExponentialInOut,
+        EaseFunction::BackIn,
+        EaseFunction::BackOut,
+        EaseFunction::BackInOut,
+        EaseFunction::ElasticOut,
+        EaseFunction::BounceOut,
+        EaseFunction::Steps(4),
+    ];
+
+    #[test]
+    fn ease_functions() {
+        for ease in EASE_FUNCTIONS {
+            assert!(ease.ease(0.0).abs() < 1e-5, "{ease:?} doesn't start at 0");
+            assert!(
+                (ease.ease(1.0) - 1.0).abs() < 1e-5,
+                "{ease:?} doesn't end at 1"
+            );
+        }
+        assert_eq!(EaseFunction::QuadraticIn.ease(0.5), 0.25);
+        assert_eq!(EaseFunction::QuadraticOut.ease(0.5), 0.75);
+        assert_eq!(EaseFunction::Steps(4).ease(0.6), 0.5);
+        assert!(EaseFunction::BackOut.ease(0.7) > 1.0);
+    }
+
+    #[test]
+    fn lerp_values() {
+        let from = UiProperty::Size(Size::new(Val::Px(100.0), Val::Percent(0.0)));
+        let to = UiProperty::Size(Size::new(Val::Px(200.0), Val::Px(50.0)));
+        assert_eq!(
+            from.lerp(&to, 0.25),
+            UiProperty::Size(Size::new(Val::Px(125.0), Val::Percent(0.0)))
+        );
+        assert_eq!(
+            from.lerp(&to, 0.75),
+            UiProperty::Size(Size::new(Val::Px(175.0), Val::Px(50.0)))
+        );
+        assert_eq!(
+            UiProperty::BackgroundColor(Color::BLACK)
+                .lerp(&UiProperty::BackgroundColor(Color::WHITE), 0.5),
+            UiProperty::BackgroundColor(Color::rgba(0.5, 0.5, 0.5, 1.0))
+        );
+    }
+
+    fn update(world: &mut World, schedule: &mut Schedule, instant: Instant) {
+        world.resource_mut::<Time>().update_with_instant(instant);
+        schedule.run(world);
+    }
+
+    #[test]
+    fn play_transitions() {
+        let mut world = World::new();
+        let start = Instant::now();
+        let mut time = Time::default();
+        time.update_with_instant(start);
+        world.insert_resource(time);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::single_threaded().with_system(ui_animation_system),
+        );
+
+        let second = Duration::from_secs(1);
+        let transitions = UiTransitions::default()
+            .with_enter(
+                UiTween::new(
+                    UiProperty::Size(Size::new(Val::Px(100.0), Val::Px(10.0))),
+                    second,
+                )
+                .with_from(UiProperty::Size(Size::new(Val::Px(0.0), Val::Px(10.0)))),
+            )
+            .with_interaction(
+                Interaction::Hovered,
+                UiTween::new(UiProperty::BackgroundColor(Color::BLACK), second),
+            )
+            .with_exit(UiTween::new(
+                UiProperty::BackgroundColor(Color::WHITE),
+                second,
+            ));
+        let entity = world
+            .spawn((
+                Style::default(),
+                BackgroundColor(Color::WHITE),
+                Interaction::None,
+                transitions,
+            ))
+            .id();
+
+        // the enter tween starts from its start value on the first frame
+        update(&mut world, &mut schedule, start);
+        let size = |world: &World| world.get::<Style>(entity).unwrap().size.width;
+        assert_eq!(size(&world), Val::Px(0.0));
+        assert!(world.get::<UiAnimator>(entity).unwrap().is_playing());
+
+        update(&mut world, &mut schedule, start + second / 2);
+        assert_eq!(size(&world), Val::Px(50.0));
+
+        // the hovered tween starts from the current color
+        *world.get_mut::<Interaction>(entity).unwrap() = Interaction::Hovered;
+        update(&mut world, &mut schedule, start + second);
+        assert_eq!(size(&world), Val::Px(100.0));
+        let color = |world: &World| world.get::<BackgroundColor>(entity).unwrap().0;
+        assert_eq!(color(&world), Color::WHITE);
+
+        update(&mut world, &mut schedule, start + second * 3 / 2);
+        assert_eq!(color(&world), Color::rgba(0.5, 0.5, 0.5, 1.0));
+
+        update(&mut world, &mut schedule, start + second * 2);
+        assert_eq!(color(&world), Color::BLACK);
+        update(&mut world, &mut schedule, start + second * 3);
+        assert!(!world.get::<UiAnimator>(entity).unwrap().is_playing());
+
+        // the node is despawned once its exit tween is done
+        world.entity_mut(entity).insert(UiExit);
+        update(&mut world, &mut schedule, start + second * 4);
+        assert_eq!(color(&world), Color::BLACK);
+        update(&mut world, &mut schedule, start + second * 9 / 2);
+        assert_eq!(color(&world), Color::rgba(0.5, 0.5, 0.5, 1.0));
+        update(&mut world, &mut schedule, start + second * 5);
+        assert!(world.get_entity(entity).is_none());
+    }
+}
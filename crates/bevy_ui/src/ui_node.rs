@@ -1,7 +1,7 @@
 use crate::{Size, UiRect};
 use bevy_asset::Handle;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Mat4, Rect, Vec2};
 use bevy_reflect::prelude::*;
 use bevy_render::{
     color::Color,
@@ -209,11 +209,12 @@ impl Val {
 
 /// Describes the style of a UI node
 ///
-/// It uses the [Flexbox](https://cssreference.io/flexbox/) system.
+/// It uses the [Flexbox](https://cssreference.io/flexbox/) or the
+/// [CSS Grid](https://css-tricks.com/snippets/css/complete-guide-grid/) system.
 #[derive(Component, Clone, PartialEq, Debug, Reflect)]
 #[reflect(Component, Default, PartialEq)]
 pub struct Style {
-    /// Whether to arrange this node and its children with flexbox layout
+    /// Whether to arrange the children of this node with flexbox or grid layout
     ///
     /// If this is set to [`Display::None`], this node will be collapsed.
     pub display: Display,
@@ -258,10 +259,26 @@ pub struct Style {
     pub aspect_ratio: Option<f32>,
     /// How to handle overflow
     pub overflow: Overflow,
-    /// The size of the gutters between the rows and columns of the flexbox layout
+    /// The size of the gutters between the rows and columns of the flexbox or grid layout
     ///
     /// Values of `Size::UNDEFINED` and `Size::AUTO` are treated as zero.
     pub gap: Size,
+    /// How the items without an explicit [`GridPlacement`] are placed in the grid
+    pub grid_auto_flow: GridAutoFlow,
+    /// The size of each row of the grid
+    pub grid_template_rows: Vec<RepeatedGridTrack>,
+    /// The size of each column of the grid
+    pub grid_template_columns: Vec<RepeatedGridTrack>,
+    /// The size of the rows created implicitly, to place items outside of the template rows.
+    /// The sizes are repeated if there are more rows than sizes.
+    pub grid_auto_rows: Vec<GridTrack>,
+    /// The size of the columns created implicitly, to place items outside of the template
+    /// columns. The sizes are repeated if there are more columns than sizes.
+    pub grid_auto_columns: Vec<GridTrack>,
+    /// The rows this node occupies in the grid of its parent
+    pub grid_row: GridPlacement,
+    /// The columns this node occupies in the grid of its parent
+    pub grid_column: GridPlacement,
 }
 
 impl Style {
@@ -288,6 +305,13 @@ impl Style {
         aspect_ratio: None,
         overflow: Overflow::DEFAULT,
         gap: Size::UNDEFINED,
+        grid_auto_flow: GridAutoFlow::DEFAULT,
+        grid_template_rows: Vec::new(),
+        grid_template_columns: Vec::new(),
+        grid_auto_rows: Vec::new(),
+        grid_auto_columns: Vec::new(),
+        grid_row: GridPlacement::DEFAULT,
+        grid_column: GridPlacement::DEFAULT,
     };
 }
 
@@ -407,14 +431,16 @@ impl Default for Direction {
     }
 }
 
-/// Whether to use a Flexbox layout model.
+/// Whether to use a Flexbox or a CSS Grid layout model.
 ///
 /// Part of the [`Style`] component.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect(PartialEq, Serialize, Deserialize)]
 pub enum Display {
-    /// Use Flexbox layout model to determine the position of this [`Node`].
+    /// Use Flexbox layout model to determine the position of the children of this [`Node`].
     Flex,
+    /// Use CSS Grid layout model to determine the position of the children of this [`Node`].
+    Grid,
     /// Use no layout, don't render this node and its children.
     ///
     /// If you want to hide a node and its children,
@@ -492,6 +518,9 @@ pub enum Overflow {
     Visible,
     /// Hide overflowing items
     Hidden,
+    /// Hide overflowing items, and allow scrolling to them by the offset of the
+    /// [`ScrollPosition`](crate::ScrollPosition) of the node
+    Scroll,
 }
 
 impl Overflow {
@@ -548,24 +577,307 @@ impl Default for FlexWrap {
     }
 }
 
-/// The calculated size of the node
-#[derive(Component, Copy, Clone, Debug, Reflect)]
-#[reflect(Component)]
-pub struct CalculatedSize {
-    /// The size of the node
-    pub size: Size,
-    /// Whether to attempt to preserve the aspect ratio when determining the layout for this item
-    pub preserve_aspect_ratio: bool,
+/// Defines how the grid items without an explicit [`GridPlacement`] are placed in the grid
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum GridAutoFlow {
+    /// Fill each row in turn, adding rows as needed
+    Row,
+    /// Fill each column in turn, adding columns as needed
+    Column,
+    /// Like [`GridAutoFlow::Row`], but filling the holes left earlier in the grid by larger items
+    RowDense,
+    /// Like [`GridAutoFlow::Column`], but filling the holes left earlier in the grid by larger
+    /// items
+    ColumnDense,
 }
 
-impl CalculatedSize {
-    const DEFAULT: Self = Self {
-        size: Size::DEFAULT,
-        preserve_aspect_ratio: false,
-    };
+impl GridAutoFlow {
+    pub const DEFAULT: Self = Self::Row;
+}
+
+impl Default for GridAutoFlow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The minimum size of a grid track
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum MinTrackSizingFunction {
+    /// A size in logical pixels
+    Px(f32),
+    /// A percentage of the size of the grid
+    Percent(f32),
+    /// The smallest size of the content of the track without overflowing
+    MinContent,
+    /// The size of the content of the track without wrapping
+    MaxContent,
+    /// The largest minimum size of the items in the track
+    Auto,
+}
+
+/// The maximum size of a grid track
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum MaxTrackSizingFunction {
+    /// A size in logical pixels
+    Px(f32),
+    /// A percentage of the size of the grid
+    Percent(f32),
+    /// The smallest size of the content of the track without overflowing
+    MinContent,
+    /// The size of the content of the track without wrapping
+    MaxContent,
+    /// The size of the content of the track, limited to a size in logical pixels
+    FitContentPx(f32),
+    /// The size of the content of the track, limited to a percentage of the size of the grid
+    FitContentPercent(f32),
+    /// The largest maximum size of the items in the track, the track being stretched by
+    /// [`AlignContent::Stretch`] and [`JustifyContent`]
+    Auto,
+    /// A fraction of the space left by the other tracks, shared with the other fractions in
+    /// proportion to their values, like the `fr` unit of CSS
+    Fraction(f32),
+}
+
+/// The size of a grid track, a row or a column of a grid, between a minimum and a maximum.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct GridTrack {
+    pub min_sizing_function: MinTrackSizingFunction,
+    pub max_sizing_function: MaxTrackSizingFunction,
+}
+
+impl GridTrack {
+    pub const DEFAULT: Self = Self::auto();
+
+    /// A track between the given minimum and maximum sizes, like `minmax()` in CSS
+    pub const fn minmax(min: MinTrackSizingFunction, max: MaxTrackSizingFunction) -> Self {
+        Self {
+            min_sizing_function: min,
+            max_sizing_function: max,
+        }
+    }
+
+    /// A track of a size in logical pixels
+    pub const fn px(value: f32) -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::Px(value),
+            MaxTrackSizingFunction::Px(value),
+        )
+    }
+
+    /// A track of a percentage of the size of the grid
+    pub const fn percent(value: f32) -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::Percent(value),
+            MaxTrackSizingFunction::Percent(value),
+        )
+    }
+
+    /// A track sharing the space left by the other tracks, but at least as large as its content,
+    /// like `1fr` in CSS
+    pub const fn fr(value: f32) -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::Auto,
+            MaxTrackSizingFunction::Fraction(value),
+        )
+    }
+
+    /// A track sharing the space left by the other tracks, even if smaller than its content,
+    /// like `minmax(0, 1fr)` in CSS
+    pub const fn flex(value: f32) -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::Px(0.0),
+            MaxTrackSizingFunction::Fraction(value),
+        )
+    }
+
+    /// A track sized to its content, stretched to fill the grid
+    pub const fn auto() -> Self {
+        Self::minmax(MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto)
+    }
+
+    /// A track of the smallest size of its content without overflowing
+    pub const fn min_content() -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::MinContent,
+            MaxTrackSizingFunction::MinContent,
+        )
+    }
+
+    /// A track of the size of its content without wrapping
+    pub const fn max_content() -> Self {
+        Self::minmax(
+            MinTrackSizingFunction::MaxContent,
+            MaxTrackSizingFunction::MaxContent,
+        )
+    }
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How many times the tracks of a [`RepeatedGridTrack`] are repeated
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum GridTrackRepetition {
+    /// A number of times
+    Count(u16),
+    /// As many times as fit in the grid, like `auto-fill` in CSS
+    AutoFill,
+    /// As many times as fit in the grid, the empty repeated tracks being collapsed, like
+    /// `auto-fit` in CSS
+    AutoFit,
+}
+
+impl From<u16> for GridTrackRepetition {
+    fn from(count: u16) -> Self {
+        Self::Count(count)
+    }
+}
+
+/// A list of grid tracks, repeated, like `repeat()` in CSS.
+///
+/// A single [`GridTrack`] can be converted into a [`RepeatedGridTrack`] repeated once.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Reflect, FromReflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct RepeatedGridTrack {
+    pub repetition: GridTrackRepetition,
+    pub tracks: Vec<GridTrack>,
+}
+
+impl RepeatedGridTrack {
+    /// The tracks repeated
+    pub fn repeat(
+        repetition: impl Into<GridTrackRepetition>,
+        tracks: impl IntoIterator<Item = GridTrack>,
+    ) -> Self {
+        Self {
+            repetition: repetition.into(),
+            tracks: tracks.into_iter().collect(),
+        }
+    }
+
+    /// Tracks of a size in logical pixels, repeated
+    pub fn px(repetition: impl Into<GridTrackRepetition>, value: f32) -> Self {
+        Self::repeat(repetition, [GridTrack::px(value)])
+    }
+
+    /// Tracks of a percentage of the size of the grid, repeated
+    pub fn percent(repetition: impl Into<GridTrackRepetition>, value: f32) -> Self {
+        Self::repeat(repetition, [GridTrack::percent(value)])
+    }
+
+    /// Tracks sharing the space left by the other tracks, repeated. See [`GridTrack::fr`].
+    pub fn fr(repetition: impl Into<GridTrackRepetition>, value: f32) -> Self {
+        Self::repeat(repetition, [GridTrack::fr(value)])
+    }
+
+    /// Tracks sharing the space left by the other tracks, repeated. See [`GridTrack::flex`].
+    pub fn flex(repetition: impl Into<GridTrackRepetition>, value: f32) -> Self {
+        Self::repeat(repetition, [GridTrack::flex(value)])
+    }
+
+    /// Tracks sized to their content, repeated
+    pub fn auto(repetition: impl Into<GridTrackRepetition>) -> Self {
+        Self::repeat(repetition, [GridTrack::auto()])
+    }
+}
+
+impl From<GridTrack> for RepeatedGridTrack {
+    fn from(track: GridTrack) -> Self {
+        Self::repeat(1, [track])
+    }
 }
 
-impl Default for CalculatedSize {
+/// The rows or columns a grid item occupies in the grid of its parent.
+///
+/// The lines between the tracks of the grid are numbered from 1, or from -1 at the end of the
+/// explicit grid going backwards. Line 0 is invalid, and ignored. Without a start or an end line,
+/// the item spans [`GridPlacement::span`] tracks, 1 by default, placed by the
+/// [`GridAutoFlow`] of the grid.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    /// The line the item starts at
+    pub start: Option<i16>,
+    /// The number of tracks the item spans, ignored if both the start and the end are set
+    pub span: Option<u16>,
+    /// The line the item ends at
+    pub end: Option<i16>,
+}
+
+impl GridPlacement {
+    pub const DEFAULT: Self = Self::auto();
+
+    /// Placed automatically, spanning one track
+    pub const fn auto() -> Self {
+        Self {
+            start: None,
+            span: None,
+            end: None,
+        }
+    }
+
+    /// Placed automatically, spanning the given number of tracks
+    pub const fn span(span: u16) -> Self {
+        Self {
+            span: Some(span),
+            ..Self::auto()
+        }
+    }
+
+    /// Starting at the given line, spanning one track
+    pub const fn start(start: i16) -> Self {
+        Self {
+            start: Some(start),
+            ..Self::auto()
+        }
+    }
+
+    /// Ending at the given line, spanning one track
+    pub const fn end(end: i16) -> Self {
+        Self {
+            end: Some(end),
+            ..Self::auto()
+        }
+    }
+
+    /// Starting at the given line, spanning the given number of tracks
+    pub const fn start_span(start: i16, span: u16) -> Self {
+        Self {
+            start: Some(start),
+            span: Some(span),
+            end: None,
+        }
+    }
+
+    /// Between the given lines
+    pub const fn start_end(start: i16, end: i16) -> Self {
+        Self {
+            start: Some(start),
+            span: None,
+            end: Some(end),
+        }
+    }
+
+    /// Ending at the given line, spanning the given number of tracks
+    pub const fn end_span(end: i16, span: u16) -> Self {
+        Self {
+            start: None,
+            span: Some(span),
+            end: Some(end),
+        }
+    }
+}
+
+impl Default for GridPlacement {
     fn default() -> Self {
         Self::DEFAULT
     }
@@ -595,6 +907,319 @@ impl From<Color> for BackgroundColor {
     }
 }
 
+/// The opacity of a UI node and its descendants, multiplying the alpha of all their colors when
+/// they are drawn: backgrounds, borders, shadows, images, text and
+/// [`UiMaterial`](crate::UiMaterial)s.
+///
+/// The opacities of the ancestors of a node are multiplied together, so a whole panel fades with a
+/// single value. It is `1.0` for the nodes without this component, and the nodes with a
+/// resulting opacity of `0.0` are not drawn.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Opacity(pub f32);
+
+impl Opacity {
+    pub const DEFAULT: Self = Self(1.0);
+}
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The color of the border of a UI node, for each of its sides.
+///
+/// The widths of the border are the [`Style::border`] of the node.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct BorderColor {
+    pub left: Color,
+    pub right: Color,
+    pub top: Color,
+    pub bottom: Color,
+}
+
+impl BorderColor {
+    pub const DEFAULT: Self = Self::all(Color::NONE);
+
+    /// A border of the same color on all sides
+    pub const fn all(color: Color) -> Self {
+        Self {
+            left: color,
+            right: color,
+            top: color,
+            bottom: color,
+        }
+    }
+}
+
+impl Default for BorderColor {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl From<Color> for BorderColor {
+    fn from(color: Color) -> Self {
+        Self::all(color)
+    }
+}
+
+/// The radius of each rounded corner of a UI node, rounding its background, image and border.
+///
+/// A [`Val::Percent`] is relative to the smallest side of the node, and the radius of a corner is
+/// at most half of the smallest side of the node.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct BorderRadius {
+    pub top_left: Val,
+    pub top_right: Val,
+    pub bottom_right: Val,
+    pub bottom_left: Val,
+}
+
+impl BorderRadius {
+    pub const DEFAULT: Self = Self::all(Val::Px(0.0));
+
+    /// The same radius for all corners
+    pub const fn all(radius: Val) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// The radius of each corner in pixels
+    pub const fn px(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self {
+            top_left: Val::Px(top_left),
+            top_right: Val::Px(top_right),
+            bottom_right: Val::Px(bottom_right),
+            bottom_left: Val::Px(bottom_left),
+        }
+    }
+
+    /// The radius of each corner of a node of the given size, top-left, top-right, bottom-right
+    /// and bottom-left, with [`Val::Px`] multiplied by the given scale.
+    pub fn resolve(&self, node_size: Vec2, scale: f32) -> [f32; 4] {
+        let min_size = node_size.min_element().max(0.0);
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+        .map(|radius| {
+            let radius = match radius {
+                Val::Px(radius) => radius * scale,
+                Val::Percent(percent) => min_size * percent / 100.0,
+                Val::Auto | Val::Undefined => 0.0,
+            };
+            radius.clamp(0.0, 0.5 * min_size)
+        })
+    }
+}
+
+impl Default for BorderRadius {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl From<Val> for BorderRadius {
+    fn from(radius: Val) -> Self {
+        Self::all(radius)
+    }
+}
+
+/// A shadow of a UI node, following its rounded corners, drawn behind the node or inside it.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct BoxShadow {
+    pub color: Color,
+    /// The horizontal offset of the shadow, to the right. A [`Val::Percent`] is relative to the
+    /// width of the node.
+    pub x_offset: Val,
+    /// The vertical offset of the shadow, downwards. A [`Val::Percent`] is relative to the height
+    /// of the node.
+    pub y_offset: Val,
+    /// How much the shadow is larger than the node on each side, or smaller if negative. A
+    /// [`Val::Percent`] is relative to the smallest side of the node.
+    pub spread_radius: Val,
+    /// The distance over which the edge of the shadow fades out. A [`Val::Percent`] is relative to
+    /// the smallest side of the node.
+    pub blur_radius: Val,
+    /// Whether the shadow is drawn inside the node, over its background, instead of behind it
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    pub const DEFAULT: Self = Self {
+        color: Color::BLACK,
+        x_offset: Val::Px(0.0),
+        y_offset: Val::Px(0.0),
+        spread_radius: Val::Px(0.0),
+        blur_radius: Val::Px(0.0),
+        inset: false,
+    };
+
+    /// A shadow offset by the given pixels, blurred over the given radius in pixels
+    pub const fn new(color: Color, x_offset: f32, y_offset: f32, blur_radius: f32) -> Self {
+        Self {
+            color,
+            x_offset: Val::Px(x_offset),
+            y_offset: Val::Px(y_offset),
+            blur_radius: Val::Px(blur_radius),
+            ..Self::DEFAULT
+        }
+    }
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A color at a position along a [`Gradient`].
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub struct ColorStop {
+    pub color: Color,
+    /// The position of the color along the gradient, from 0 at its start to 1 at its end
+    pub position: f32,
+}
+
+impl ColorStop {
+    pub const fn new(color: Color, position: f32) -> Self {
+        Self { color, position }
+    }
+}
+
+/// How the points of a UI node are placed along a [`Gradient`].
+///
+/// The angles are in radians, clockwise from the top of the node, and the centers are relative to
+/// the node, from `(0, 0)` at its top-left corner to `(1, 1)` at its bottom-right corner.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub enum GradientShape {
+    /// Along a line going in the direction of the angle through the center of the node, its ends
+    /// on the lines through the corners perpendicular to it
+    Linear { angle: f32 },
+    /// Along circles around the center, ending at the corner farthest from it
+    Radial { center: Vec2 },
+    /// Around the center, starting at the angle and going clockwise
+    Conic { center: Vec2, start_angle: f32 },
+}
+
+impl Default for GradientShape {
+    fn default() -> Self {
+        Self::Linear { angle: 0.0 }
+    }
+}
+
+/// A fill of a UI node blending between colors.
+///
+/// The stops should be in increasing order of position. The points before the first stop have
+/// its color, the points after the last stop have its color, and the colors between two stops
+/// are interpolated in sRGB space.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, FromReflect)]
+pub struct Gradient {
+    pub shape: GradientShape,
+    pub stops: Vec<ColorStop>,
+}
+
+impl Gradient {
+    /// A gradient of the given shape between colors evenly spaced along it
+    pub fn new(shape: GradientShape, colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors: Vec<Color> = colors.into_iter().collect();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self {
+            shape,
+            stops: colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| ColorStop::new(color, i as f32 / last))
+                .collect(),
+        }
+    }
+
+    /// A linear gradient going in the direction of the angle, in radians clockwise from the top
+    pub fn linear(angle: f32, colors: impl IntoIterator<Item = Color>) -> Self {
+        Self::new(GradientShape::Linear { angle }, colors)
+    }
+
+    /// A radial gradient around the center of the node
+    pub fn radial(colors: impl IntoIterator<Item = Color>) -> Self {
+        Self::new(
+            GradientShape::Radial {
+                center: Vec2::splat(0.5),
+            },
+            colors,
+        )
+    }
+
+    /// A conic gradient around the center of the node, starting at its top
+    pub fn conic(colors: impl IntoIterator<Item = Color>) -> Self {
+        Self::new(
+            GradientShape::Conic {
+                center: Vec2::splat(0.5),
+                start_angle: 0.0,
+            },
+            colors,
+        )
+    }
+
+    /// The color at a position along the gradient, or [`Color::NONE`] without stops
+    pub fn sample(&self, position: f32) -> Color {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::NONE;
+        };
+        if position <= first.position {
+            return first.color;
+        }
+        for stops in self.stops.windows(2) {
+            let (start, end) = (stops[0], stops[1]);
+            if position < end.position {
+                let t = (position - start.position) / (end.position - start.position);
+                let start = start.color.as_rgba_f32();
+                let end = end.color.as_rgba_f32();
+                let [r, g, b, a] = [0, 1, 2, 3].map(|i| start[i] + (end[i] - start[i]) * t);
+                return Color::rgba(r, g, b, a);
+            }
+        }
+        last.color
+    }
+}
+
+/// A gradient filling the background of a UI node, drawn instead of its [`BackgroundColor`].
+///
+/// When combined with [`UiImage`], tints the provided texture.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct BackgroundGradient(pub Gradient);
+
+impl From<Gradient> for BackgroundGradient {
+    fn from(gradient: Gradient) -> Self {
+        Self(gradient)
+    }
+}
+
+/// A gradient filling the border of a UI node, drawn instead of its [`BorderColor`].
+///
+/// The gradient spans the whole node, so that it continues from one side of the border to the
+/// next.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct BorderGradient(pub Gradient);
+
+impl From<Gradient> for BorderGradient {
+    fn from(gradient: Gradient) -> Self {
+        Self(gradient)
+    }
+}
+
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect)]
 #[reflect(Component, Default)]
@@ -632,12 +1257,70 @@ impl From<Handle<Image>> for UiImage {
     }
 }
 
+/// The size of the texture of a [`UiImage`], in pixels, updated when the texture is loaded
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiImageSize {
+    pub(crate) size: Vec2,
+}
+
+impl UiImageSize {
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+}
+
 /// The calculated clip of the node
-#[derive(Component, Default, Copy, Clone, Debug, Reflect)]
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
 #[reflect(Component)]
 pub struct CalculatedClip {
-    /// The rect of the clip
+    /// The rect of the clip: the intersection of the bounds of all the ancestors clipping their
+    /// content
     pub clip: Rect,
+    /// The exact shape of the nearest ancestor clipping its content, which can be rotated, scaled
+    /// and have rounded corners
+    pub shape: ClipShape,
+}
+
+impl CalculatedClip {
+    /// Whether the point, in logical pixels of the UI, is inside the rect and the shape of the clip
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.clip.contains(point) && self.shape.contains(point)
+    }
+}
+
+/// The shape of a node clipping its content: the rect of the node with its rounded corners, placed
+/// by its [`GlobalTransform`](bevy_transform::components::GlobalTransform).
+#[derive(Default, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct ClipShape {
+    /// The transform from the logical pixels of the UI to the space of the node, with the origin
+    /// at its center
+    pub inverse_transform: Mat4,
+    pub size: Vec2,
+    /// The radius of the corners: top-left, top-right, bottom-right and bottom-left
+    pub border_radius: [f32; 4],
+}
+
+impl ClipShape {
+    /// Whether the point, in logical pixels of the UI, is inside the shape
+    pub fn contains(&self, point: Vec2) -> bool {
+        let point = self
+            .inverse_transform
+            .transform_point3(point.extend(0.0))
+            .truncate();
+        // The radius of the corner closest to the point, like in the UI shader
+        let radius = match (point.x > 0.0, point.y > 0.0) {
+            (false, false) => self.border_radius[0],
+            (true, false) => self.border_radius[1],
+            (true, true) => self.border_radius[2],
+            (false, true) => self.border_radius[3],
+        };
+        let corner_to_point = point.abs() - self.size / 2.0 + radius;
+        let distance = corner_to_point.max(Vec2::ZERO).length()
+            + corner_to_point.max_element().min(0.0)
+            - radius;
+        distance <= 0.0
+    }
 }
 
 /// Indicates that this [`Node`] entity's front-to-back ordering is not controlled solely
@@ -653,7 +1336,8 @@ pub struct CalculatedClip {
 /// [`ZIndex::Local(n)`] and [`ZIndex::Global(n)`] for root nodes.
 ///
 /// Nodes without this component will be treated as if they had a value of [`ZIndex::Local(0)`].
-#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
 pub enum ZIndex {
     /// Indicates the order in which this node should be rendered relative to its siblings.
     Local(i32),
@@ -672,7 +1356,9 @@ impl Default for ZIndex {
 mod tests {
     use crate::ValArithmeticError;
 
-    use super::Val;
+    use super::{BorderRadius, ClipShape, ColorStop, Gradient, GradientShape, Val};
+    use bevy_math::{Mat4, Quat, Vec2, Vec3};
+    use bevy_render::color::Color;
 
     #[test]
     fn val_try_add() {
@@ -831,4 +1517,71 @@ mod tests {
             "the given variant of Val is not evaluateable (non-numeric)"
         );
     }
+
+    #[test]
+    fn border_radius_resolve() {
+        let radius = BorderRadius {
+            top_left: Val::Px(10.),
+            top_right: Val::Percent(25.),
+            bottom_right: Val::Px(100.),
+            bottom_left: Val::Auto,
+        };
+
+        assert_eq!(radius.resolve(Vec2::new(80., 40.), 2.), [20., 10., 20., 0.]);
+    }
+
+    #[test]
+    fn gradient_sample() {
+        let gradient = Gradient {
+            shape: GradientShape::default(),
+            stops: vec![
+                ColorStop::new(Color::rgba(0., 0., 0., 1.), 0.25),
+                ColorStop::new(Color::rgba(1., 0.5, 0., 1.), 0.75),
+                ColorStop::new(Color::rgba(1., 1., 1., 0.), 1.),
+            ],
+        };
+
+        assert_eq!(gradient.sample(0.), Color::rgba(0., 0., 0., 1.));
+        assert_eq!(gradient.sample(0.5), Color::rgba(0.5, 0.25, 0., 1.));
+        assert_eq!(gradient.sample(0.875), Color::rgba(1., 0.75, 0.5, 0.5));
+        assert_eq!(gradient.sample(2.), Color::rgba(1., 1., 1., 0.));
+        assert_eq!(Gradient::default().sample(0.5), Color::NONE);
+    }
+
+    #[test]
+    fn gradient_new_spaces_colors_evenly() {
+        let gradient = Gradient::linear(0., [Color::RED, Color::GREEN, Color::BLUE]);
+        let positions: Vec<f32> = gradient.stops.iter().map(|stop| stop.position).collect();
+
+        assert_eq!(positions, vec![0., 0.5, 1.]);
+    }
+
+    #[test]
+    fn clip_shape_contains() {
+        // a node of 100x50 centered on (200, 100), rotated by a quarter turn
+        let transform = Mat4::from_rotation_translation(
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(200., 100., 0.),
+        );
+        let shape = ClipShape {
+            inverse_transform: transform.inverse(),
+            size: Vec2::new(100., 50.),
+            border_radius: [0., 0., 20., 0.],
+        };
+
+        assert!(shape.contains(Vec2::new(200., 100.)));
+        // the rotated node is 50 wide and 100 high
+        assert!(shape.contains(Vec2::new(220., 140.)));
+        assert!(!shape.contains(Vec2::new(240., 100.)));
+        assert!(!shape.contains(Vec2::new(200., 160.)));
+        // the rounded corner is cut
+        let corner = transform
+            .transform_point3(Vec3::new(49., 24., 0.))
+            .truncate();
+        assert!(!shape.contains(corner));
+        let corner = transform
+            .transform_point3(Vec3::new(-49., 24., 0.))
+            .truncate();
+        assert!(shape.contains(corner));
+    }
 }
@@ -1,27 +1,29 @@
 //! This module contains systems that update the UI when something changes
 
-use crate::{CalculatedClip, Overflow, Style};
+use crate::{BorderRadius, CalculatedClip, ClipShape, Overflow, Style, UiScale};
 
 use super::Node;
 use bevy_ecs::{
     entity::Entity,
     query::{With, Without},
-    system::{Commands, Query},
+    system::{Commands, Query, Res},
 };
 use bevy_hierarchy::{Children, Parent};
-use bevy_math::Rect;
+use bevy_math::{Rect, Vec2};
 use bevy_transform::components::GlobalTransform;
 
 /// Updates clipping for all nodes
 pub fn update_clipping_system(
     mut commands: Commands,
+    ui_scale: Res<UiScale>,
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    mut node_query: Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    mut node_query: Query<ClippedNodeQuery>,
     children_query: Query<&Children>,
 ) {
     for root_node in &root_node_query {
         update_clipping(
             &mut commands,
+            ui_scale.scale as f32,
             &children_query,
             &mut node_query,
             root_node,
@@ -30,14 +32,24 @@ pub fn update_clipping_system(
     }
 }
 
+type ClippedNodeQuery = (
+    &'static Node,
+    &'static GlobalTransform,
+    &'static Style,
+    Option<&'static BorderRadius>,
+    Option<&'static mut CalculatedClip>,
+);
+
 fn update_clipping(
     commands: &mut Commands,
+    scale: f32,
     children_query: &Query<&Children>,
-    node_query: &mut Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    node_query: &mut Query<ClippedNodeQuery>,
     entity: Entity,
-    clip: Option<Rect>,
+    clip: Option<CalculatedClip>,
 ) {
-    let (node, global_transform, style, calculated_clip) = node_query.get_mut(entity).unwrap();
+    let (node, global_transform, style, border_radius, calculated_clip) =
+        node_query.get_mut(entity).unwrap();
     // Update this node's CalculatedClip component
     match (clip, calculated_clip) {
         (None, None) => {}
@@ -45,28 +57,60 @@ fn update_clipping(
             commands.entity(entity).remove::<CalculatedClip>();
         }
         (Some(clip), None) => {
-            commands.entity(entity).insert(CalculatedClip { clip });
+            commands.entity(entity).insert(clip);
         }
         (Some(clip), Some(mut old_clip)) => {
-            if old_clip.clip != clip {
-                *old_clip = CalculatedClip { clip };
+            if *old_clip != clip {
+                *old_clip = clip;
             }
         }
     }
 
-    // Calculate new clip for its children
+    // Calculate new clip for its children: the bounds of the transformed node intersected with
+    // the current clip, and the exact shape of the node
     let children_clip = match style.overflow {
         Overflow::Visible => clip,
-        Overflow::Hidden => {
-            let node_center = global_transform.translation().truncate();
-            let node_rect = Rect::from_center_size(node_center, node.calculated_size);
-            Some(clip.map_or(node_rect, |c| c.intersect(node_rect)))
+        Overflow::Hidden | Overflow::Scroll => {
+            let transform = global_transform.compute_matrix();
+            let size = node.size();
+            let corners = [
+                Vec2::new(-0.5, -0.5),
+                Vec2::new(0.5, -0.5),
+                Vec2::new(0.5, 0.5),
+                Vec2::new(-0.5, 0.5),
+            ]
+            .map(|corner| {
+                transform
+                    .transform_point3((corner * size).extend(0.0))
+                    .truncate()
+            });
+            let node_rect = Rect {
+                min: corners.into_iter().reduce(Vec2::min).unwrap(),
+                max: corners.into_iter().reduce(Vec2::max).unwrap(),
+            };
+            Some(CalculatedClip {
+                clip: clip.map_or(node_rect, |clip| clip.clip.intersect(node_rect)),
+                shape: ClipShape {
+                    inverse_transform: transform.inverse(),
+                    size,
+                    border_radius: border_radius
+                        .map(|border_radius| border_radius.resolve(size, scale))
+                        .unwrap_or_default(),
+                },
+            })
         }
     };
 
     if let Ok(children) = children_query.get(entity) {
         for child in children.iter().cloned() {
-            update_clipping(commands, children_query, node_query, child, children_clip);
+            update_clipping(
+                commands,
+                scale,
+                children_query,
+                node_query,
+                child,
+                children_clip,
+            );
         }
     }
 }
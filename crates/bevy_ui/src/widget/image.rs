@@ -1,27 +1,52 @@
-use crate::{CalculatedSize, Size, UiImage, Val};
+use crate::{AvailableSpace, ContentSize, Measure, UiImage, UiImageSize};
 use bevy_asset::Assets;
 use bevy_ecs::{
     query::Without,
     system::{Query, Res},
 };
+use bevy_math::Vec2;
 use bevy_render::texture::Image;
 use bevy_text::Text;
 
-/// Updates calculated size of the node based on the image provided
-pub fn update_image_calculated_size_system(
+/// The [`Measure`] of an image, keeping its aspect ratio when the layout sets one of its sides.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageMeasure {
+    /// The size of the texture of the image
+    pub size: Vec2,
+}
+
+impl Measure for ImageMeasure {
+    fn measure(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        _available_width: AvailableSpace,
+        _available_height: AvailableSpace,
+    ) -> Vec2 {
+        match (width, height) {
+            (None, None) => self.size,
+            (Some(width), None) => Vec2::new(width, width * self.size.y / self.size.x),
+            (None, Some(height)) => Vec2::new(height * self.size.x / self.size.y, height),
+            (Some(width), Some(height)) => Vec2::new(width, height),
+        }
+    }
+}
+
+/// Updates the content size of the nodes from the size of their image
+pub fn update_image_content_size_system(
     textures: Res<Assets<Image>>,
-    mut query: Query<(&mut CalculatedSize, &UiImage), Without<Text>>,
+    mut query: Query<(&mut ContentSize, &mut UiImageSize, &UiImage), Without<Text>>,
 ) {
-    for (mut calculated_size, image) in &mut query {
+    for (mut content_size, mut image_size, image) in &mut query {
         if let Some(texture) = textures.get(&image.texture) {
-            let size = Size {
-                width: Val::Px(texture.texture_descriptor.size.width as f32),
-                height: Val::Px(texture.texture_descriptor.size.height as f32),
-            };
+            let size = Vec2::new(
+                texture.texture_descriptor.size.width as f32,
+                texture.texture_descriptor.size.height as f32,
+            );
             // Update only if size has changed to avoid needless layout calculations
-            if size != calculated_size.size {
-                calculated_size.size = size;
-                calculated_size.preserve_aspect_ratio = true;
+            if size != image_size.size {
+                image_size.size = size;
+                content_size.set(ImageMeasure { size });
             }
         }
     }
@@ -2,8 +2,12 @@
 
 mod button;
 mod image;
+mod scrollbar;
 mod text;
+mod text_input;
 
 pub use button::*;
 pub use image::*;
+pub use scrollbar::*;
 pub use text::*;
+pub use text_input::*;
@@ -0,0 +1,130 @@
+use crate::{Interaction, Node, PositionType, RelativeCursorPosition, ScrollPosition, Style, Val};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    system::Query,
+    world::{FromWorld, World},
+};
+use bevy_hierarchy::Children;
+use bevy_reflect::{FromReflect, Reflect};
+
+/// The axis along which a [`Scrollbar`] scrolls its target
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect, FromReflect)]
+pub enum ScrollAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// A scrollbar showing the part of the content of a scrolled node that is in view.
+///
+/// The first child of the scrollbar is its thumb. It is positioned absolutely by
+/// [`update_scrollbar_system`], with its size and position along the axis of the scrollbar
+/// proportional to the view and the offset of the [`ScrollPosition`] of the target. Its size across
+/// the scrollbar is left to its [`Style`].
+///
+/// If the scrollbar has an [`Interaction`] and a [`RelativeCursorPosition`], pressing it scrolls
+/// the target to center the thumb on the cursor.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, MapEntities, PartialEq)]
+pub struct Scrollbar {
+    /// The scrolled node, with [`Overflow::Scroll`](crate::Overflow::Scroll)
+    pub target: Entity,
+    pub axis: ScrollAxis,
+}
+
+impl FromWorld for Scrollbar {
+    fn from_world(_world: &mut World) -> Self {
+        Self {
+            target: Entity::PLACEHOLDER,
+            axis: ScrollAxis::Vertical,
+        }
+    }
+}
+
+impl MapEntities for Scrollbar {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.target = entity_map.get(self.target)?;
+        Ok(())
+    }
+}
+
+/// Scrolls the targets of the pressed scrollbars, and updates the thumbs of the scrollbars
+pub fn update_scrollbar_system(
+    scrollbar_query: Query<(
+        &Scrollbar,
+        &Children,
+        Option<&Interaction>,
+        Option<&RelativeCursorPosition>,
+    )>,
+    mut target_query: Query<(&Node, &mut ScrollPosition)>,
+    mut thumb_query: Query<&mut Style>,
+) {
+    for (scrollbar, children, interaction, cursor_position) in &scrollbar_query {
+        let Ok((node, mut scroll_position)) = target_query.get_mut(scrollbar.target) else {
+            continue;
+        };
+        let axis = match scrollbar.axis {
+            ScrollAxis::Horizontal => 0,
+            ScrollAxis::Vertical => 1,
+        };
+        let view_size = node.size()[axis];
+        let max_offset = scroll_position.max_offset()[axis];
+        let content_size = view_size + max_offset;
+        // The part of the content in view
+        let thumb_size = if content_size > 0.0 {
+            view_size / content_size
+        } else {
+            1.0
+        };
+
+        if let (Some(Interaction::Clicked), Some(cursor_position)) = (
+            interaction,
+            cursor_position.and_then(|position| position.normalized),
+        ) {
+            if thumb_size < 1.0 {
+                let start = (cursor_position[axis] - 0.5 * thumb_size) / (1.0 - thumb_size);
+                let offset = start.clamp(0.0, 1.0) * max_offset;
+                if scroll_position.offset[axis] != offset {
+                    scroll_position.offset[axis] = offset;
+                }
+            }
+        }
+
+        let Some(mut thumb_style) = children
+            .first()
+            .and_then(|thumb| thumb_query.get_mut(*thumb).ok())
+        else {
+            continue;
+        };
+        let thumb_start = if content_size > 0.0 {
+            scroll_position.offset[axis].clamp(0.0, max_offset) / content_size
+        } else {
+            0.0
+        };
+        let size = Val::Percent(100.0 * thumb_size);
+        let start = Val::Percent(100.0 * thumb_start);
+        let (thumb_size, thumb_start) = match scrollbar.axis {
+            ScrollAxis::Horizontal => (thumb_style.size.width, thumb_style.position.left),
+            ScrollAxis::Vertical => (thumb_style.size.height, thumb_style.position.top),
+        };
+        // Only trigger change detection, and the layout, when the thumb moves
+        if thumb_style.position_type == PositionType::Absolute
+            && thumb_size == size
+            && thumb_start == start
+        {
+            continue;
+        }
+        thumb_style.position_type = PositionType::Absolute;
+        match scrollbar.axis {
+            ScrollAxis::Horizontal => {
+                thumb_style.size.width = size;
+                thumb_style.position.left = start;
+            }
+            ScrollAxis::Vertical => {
+                thumb_style.size.height = size;
+                thumb_style.position.top = start;
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{CalculatedSize, Size, Style, UiScale, Val};
+use crate::{AvailableSpace, ContentSize, Measure, Node, UiScale};
 use bevy_asset::Assets;
 use bevy_ecs::{
     entity::Entity,
@@ -9,8 +9,8 @@ use bevy_math::Vec2;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlas;
 use bevy_text::{
-    Font, FontAtlasSet, FontAtlasWarning, Text, TextError, TextLayoutInfo, TextPipeline,
-    TextSettings, YAxisOrientation,
+    Font, FontAtlasSet, FontAtlasWarning, Text, TextError, TextLayoutInfo, TextMeasureInfo,
+    TextPipeline, TextSettings, YAxisOrientation,
 };
 use bevy_window::{PrimaryWindow, Window};
 
@@ -23,22 +23,107 @@ fn scale_value(value: f32, factor: f64) -> f32 {
     (value as f64 * factor) as f32
 }
 
-/// Defines how `min_size`, `size`, and `max_size` affects the bounds of a text
-/// block.
-pub fn text_constraint(min_size: Val, size: Val, max_size: Val, scale_factor: f64) -> f32 {
-    // Needs support for percentages
-    match (min_size, size, max_size) {
-        (_, _, Val::Px(max)) => scale_value(max, scale_factor),
-        (Val::Px(min), _, _) => scale_value(min, scale_factor),
-        (Val::Undefined, Val::Px(size), Val::Undefined) | (Val::Auto, Val::Px(size), Val::Auto) => {
-            scale_value(size, scale_factor)
+/// The [`Measure`] of the content of a text node, wrapping the text to the space it is given.
+#[derive(Clone, Debug)]
+pub struct TextMeasure {
+    pub info: TextMeasureInfo,
+    /// The scale factor the text is measured at
+    pub scale_factor: f64,
+}
+
+impl Measure for TextMeasure {
+    fn measure(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        available_width: AvailableSpace,
+        _available_height: AvailableSpace,
+    ) -> Vec2 {
+        let min = self.info.min_width_content_size;
+        let max = self.info.max_width_content_size;
+        let x = width
+            .map(|width| scale_value(width, self.scale_factor))
+            .unwrap_or_else(|| match available_width {
+                AvailableSpace::Definite(available) => {
+                    scale_value(available, self.scale_factor).clamp(min.x, max.x)
+                }
+                AvailableSpace::MinContent => min.x,
+                AvailableSpace::MaxContent => max.x,
+            });
+        let y = height
+            .map(|height| scale_value(height, self.scale_factor))
+            .unwrap_or_else(|| self.info.compute_size(Vec2::new(x, f32::MAX)).y);
+        // Rounded up so the text laid out in the node wraps the same way as when it was measured
+        Vec2::new(x, y).ceil() / self.scale_factor as f32
+    }
+}
+
+/// Measures the texts whose sections or scale factor changed, for the layout to size their nodes
+/// to their content.
+pub fn measure_text_system(
+    mut queued_text: Local<QueuedText>,
+    mut last_scale_factor: Local<f64>,
+    fonts: Res<Assets<Font>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut text_queries: ParamSet<(
+        Query<Entity, (Changed<Text>, With<Node>)>,
+        Query<Entity, (With<Text>, With<Node>)>,
+        Query<(&Text, &mut ContentSize)>,
+    )>,
+) {
+    // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(ui_scale.scale);
+
+    #[allow(clippy::float_cmp)]
+    if *last_scale_factor == scale_factor {
+        // Adds all entities where the text has changed to the local queue
+        for entity in text_queries.p0().iter() {
+            queued_text.entities.push(entity);
+        }
+    } else {
+        // If the scale factor has changed, queue all text
+        for entity in text_queries.p1().iter() {
+            queued_text.entities.push(entity);
+        }
+        *last_scale_factor = scale_factor;
+    }
+
+    if queued_text.entities.is_empty() {
+        return;
+    }
+
+    let mut new_queue = Vec::new();
+    let mut query = text_queries.p2();
+    for entity in queued_text.entities.drain(..) {
+        if let Ok((text, mut content_size)) = query.get_mut(entity) {
+            match TextMeasureInfo::from_sections(
+                &fonts,
+                &text.sections,
+                scale_factor,
+                text.alignment,
+            ) {
+                Ok(info) => content_size.set(TextMeasure { info, scale_factor }),
+                Err(TextError::NoSuchFont) => {
+                    // The fonts of the text aren't loaded yet, let's add this entity to the queue
+                    // for further processing
+                    new_queue.push(entity);
+                }
+                Err(e @ TextError::FailedToAddGlyph(_)) => {
+                    panic!("Fatal error when processing text: {e}.");
+                }
+            }
         }
-        _ => f32::MAX,
     }
+
+    queued_text.entities = new_queue;
 }
 
-/// Updates the layout and size information whenever the text or style is changed.
-/// This information is computed by the `TextPipeline` on insertion, then stored.
+/// Lays out the glyphs of the texts whose sections or nodes changed, wrapped to the size the
+/// layout gave to their nodes. This information is computed by the `TextPipeline`, then stored.
 ///
 /// ## World Resources
 ///
@@ -59,14 +144,9 @@ pub fn text_system(
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
     mut text_pipeline: ResMut<TextPipeline>,
     mut text_queries: ParamSet<(
-        Query<Entity, Or<(Changed<Text>, Changed<Style>)>>,
-        Query<Entity, (With<Text>, With<Style>)>,
-        Query<(
-            &Text,
-            &Style,
-            &mut CalculatedSize,
-            Option<&mut TextLayoutInfo>,
-        )>,
+        Query<Entity, (With<Text>, Or<(Changed<Text>, Changed<Node>)>)>,
+        Query<Entity, (With<Text>, With<Node>)>,
+        Query<(&Node, &Text, Option<&mut TextLayoutInfo>)>,
     )>,
 ) {
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
@@ -75,11 +155,9 @@ pub fn text_system(
         .map(|window| window.resolution.scale_factor())
         .unwrap_or(ui_scale.scale);
 
-    let inv_scale_factor = 1. / scale_factor;
-
     #[allow(clippy::float_cmp)]
     if *last_scale_factor == scale_factor {
-        // Adds all entities where the text or the style has changed to the local queue
+        // Adds all entities where the text or the size of the node has changed to the local queue
         for entity in text_queries.p0().iter() {
             queued_text.entities.push(entity);
         }
@@ -99,28 +177,17 @@ pub fn text_system(
     let mut new_queue = Vec::new();
     let mut query = text_queries.p2();
     for entity in queued_text.entities.drain(..) {
-        if let Ok((text, style, mut calculated_size, text_layout_info)) = query.get_mut(entity) {
-            let node_size = Vec2::new(
-                text_constraint(
-                    style.min_size.width,
-                    style.size.width,
-                    style.max_size.width,
-                    scale_factor,
-                ),
-                text_constraint(
-                    style.min_size.height,
-                    style.size.height,
-                    style.max_size.height,
-                    scale_factor,
-                ),
-            );
+        if let Ok((node, text, text_layout_info)) = query.get_mut(entity) {
+            // The text is only wrapped to the width of the node, lines overflowing its height are
+            // still laid out
+            let bounds = Vec2::new(scale_value(node.size().x, scale_factor), f32::MAX);
 
             match text_pipeline.queue_text(
                 &fonts,
                 &text.sections,
                 scale_factor,
                 text.alignment,
-                node_size,
+                bounds,
                 &mut font_atlas_set_storage,
                 &mut texture_atlases,
                 &mut textures,
@@ -136,18 +203,12 @@ pub fn text_system(
                 Err(e @ TextError::FailedToAddGlyph(_)) => {
                     panic!("Fatal error when processing text: {e}.");
                 }
-                Ok(info) => {
-                    calculated_size.size = Size {
-                        width: Val::Px(scale_value(info.size.x, inv_scale_factor)),
-                        height: Val::Px(scale_value(info.size.y, inv_scale_factor)),
-                    };
-                    match text_layout_info {
-                        Some(mut t) => *t = info,
-                        None => {
-                            commands.entity(entity).insert(info);
-                        }
+                Ok(info) => match text_layout_info {
+                    Some(mut t) => *t = info,
+                    None => {
+                        commands.entity(entity).insert(info);
                     }
-                }
+                },
             }
         }
     }
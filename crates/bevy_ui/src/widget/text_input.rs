@@ -0,0 +1,624 @@
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, FocusPolicy, Node, Overflow, PositionType, ScrollPosition, Size, Style,
+    UiFocus, UiRect, Val,
+};
+use ab_glyph::{Font as _, ScaleFont};
+use bevy_asset::Assets;
+use bevy_ecs::{
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    prelude::{Component, With, Without},
+    query::ChangeTrackers,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    ButtonState, Input,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::{Font, Text, TextStyle};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{Ime, PrimaryWindow, ReceivedCharacter, Window};
+use std::ops::Range;
+
+/// The width of the caret of the text inputs, in logical pixels
+const CARET_WIDTH: f32 = 2.0;
+/// The height of the underline of the text composed with an IME, in logical pixels
+const COMPOSITION_UNDERLINE_HEIGHT: f32 = 2.0;
+
+/// An editable single line of text.
+///
+/// The text input is displayed by children spawned by [`spawn_text_input_parts_system`]: its
+/// text, a caret while it is focused and the highlight of its selection. When the text is longer
+/// than the input, it scrolls to keep the caret in view.
+///
+/// The input is edited while it has the [`UiFocus`], focused by clicking it or navigating to it,
+/// which requires an [`Interaction`](crate::Interaction). While focused, it is edited with the
+/// keyboard and the IME of the window, and sends [`TextInputChanged`] and [`TextInputSubmitted`]
+/// events.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextInput {
+    value: String,
+    /// The byte index of the caret in the value
+    cursor: usize,
+    /// The byte index of the end of the selection opposite to the caret
+    anchor: usize,
+    /// The text being composed with an IME, in place of the selection
+    composition: String,
+    /// The style of the text, whose color is also the color of the caret
+    pub style: TextStyle,
+    /// The color of the highlight of the selected text
+    pub selection_color: Color,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new(String::new(), TextStyle::default())
+    }
+}
+
+impl TextInput {
+    /// Creates a text input with the given value, and the caret at its end
+    pub fn new(value: impl Into<String>, style: TextStyle) -> Self {
+        let value = value.into();
+        let cursor = value.len();
+        Self {
+            value,
+            cursor,
+            anchor: cursor,
+            composition: String::new(),
+            style,
+            selection_color: Color::rgba(0.3, 0.5, 1.0, 0.5),
+        }
+    }
+
+    /// Returns this [`TextInput`] with a new selection color
+    pub const fn with_selection_color(mut self, color: Color) -> Self {
+        self.selection_color = color;
+        self
+    }
+
+    /// The text of the input
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text of the input, moving the caret to its end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+        self.anchor = self.cursor;
+    }
+
+    /// The byte index of the caret in the value
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The byte range of the selected text, empty if nothing is selected
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// The selected text
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    /// The text being composed with an IME, empty when not composing
+    pub fn composition(&self) -> &str {
+        &self.composition
+    }
+
+    /// Moves the caret to the character boundary at or before the given byte index, extending the
+    /// selection from the previous position of the caret or clearing it
+    pub fn set_cursor(&mut self, index: usize, extend_selection: bool) {
+        let mut index = index.min(self.value.len());
+        while !self.value.is_char_boundary(index) {
+            index -= 1;
+        }
+        self.cursor = index;
+        if !extend_selection {
+            self.anchor = index;
+        }
+    }
+
+    /// Selects the whole text, with the caret at its end
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.value.len();
+    }
+
+    /// Replaces the selection with the given text, placing the caret after it
+    pub fn insert(&mut self, text: &str) {
+        let selection = self.selection();
+        self.value.replace_range(selection.clone(), text);
+        self.cursor = selection.start + text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Moves the caret to the previous character, or to the start of the selection
+    pub fn move_left(&mut self, extend_selection: bool) {
+        if !extend_selection && self.cursor != self.anchor {
+            self.set_cursor(self.selection().start, false);
+        } else {
+            self.set_cursor(self.previous_boundary(), extend_selection);
+        }
+    }
+
+    /// Moves the caret to the next character, or to the end of the selection
+    pub fn move_right(&mut self, extend_selection: bool) {
+        if !extend_selection && self.cursor != self.anchor {
+            self.set_cursor(self.selection().end, false);
+        } else {
+            self.set_cursor(self.next_boundary(), extend_selection);
+        }
+    }
+
+    /// Deletes the selection, or the character before the caret
+    pub fn delete_backward(&mut self) {
+        if self.cursor == self.anchor {
+            self.anchor = self.previous_boundary();
+        }
+        self.insert("");
+    }
+
+    /// Deletes the selection, or the character after the caret
+    pub fn delete_forward(&mut self) {
+        if self.cursor == self.anchor {
+            self.anchor = self.next_boundary();
+        }
+        self.insert("");
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// The displayed text, with the composed text in place of the selection, and the byte ranges
+    /// in it of the highlight and of the caret
+    fn display(&self) -> (String, Range<usize>, usize) {
+        if self.composition.is_empty() {
+            return (self.value.clone(), self.selection(), self.cursor);
+        }
+        let selection = self.selection();
+        let mut text = self.value.clone();
+        text.replace_range(selection.clone(), &self.composition);
+        let composition = selection.start..selection.start + self.composition.len();
+        (text, composition.clone(), composition.end)
+    }
+}
+
+/// The children of a [`TextInput`] displaying it
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TextInputParts {
+    /// The node scrolled to keep the caret in view, parent of the other parts
+    viewport: Entity,
+    text: Entity,
+    caret: Entity,
+    /// The highlight of the selection, or the underline of the text composed with an IME
+    highlight: Entity,
+}
+
+/// The [`TextInput`] edited by the keyboard, if any, which is the node with the [`UiFocus`]
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FocusedTextInput(pub Option<Entity>);
+
+/// The text copied or cut from the [`TextInput`]s, and pasted into them.
+///
+/// It isn't shared with the clipboard of the system, which apps can synchronize with it.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextInputClipboard(pub String);
+
+/// An event sent when the text of a [`TextInput`] is edited
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextInputChanged {
+    /// The edited text input
+    pub entity: Entity,
+    /// The new text of the input
+    pub value: String,
+}
+
+/// An event sent when the enter key is pressed in a [`TextInput`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextInputSubmitted {
+    /// The submitted text input
+    pub entity: Entity,
+    /// The text of the input
+    pub value: String,
+}
+
+/// Spawns the children displaying the new [`TextInput`]s
+pub fn spawn_text_input_parts_system(
+    mut commands: Commands,
+    text_input_query: Query<(Entity, &TextInput), Without<TextInputParts>>,
+) {
+    for (entity, text_input) in &text_input_query {
+        let text = commands
+            .spawn(TextBundle {
+                text: Text::from_section(text_input.value.clone(), text_input.style.clone()),
+                style: Style {
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let mut spawn_part = |color| {
+            commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    background_color: BackgroundColor(color),
+                    focus_policy: FocusPolicy::Pass,
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                })
+                .id()
+        };
+        let highlight = spawn_part(text_input.selection_color);
+        let caret = spawn_part(text_input.style.color);
+        // The highlight is drawn behind the text, and the caret in front of it
+        let viewport = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_grow: 1.0,
+                    min_size: Size::new(Val::Undefined, Val::Px(text_input.style.font_size)),
+                    overflow: Overflow::Scroll,
+                    ..Default::default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                ..Default::default()
+            })
+            .push_children(&[highlight, text, caret])
+            .id();
+        commands
+            .entity(entity)
+            .insert(TextInputParts {
+                viewport,
+                text,
+                caret,
+                highlight,
+            })
+            .add_child(viewport);
+    }
+}
+
+/// Edits the [`TextInput`] with the [`UiFocus`], or stops editing when the focus moves elsewhere
+pub fn text_input_focus_system(
+    focus: Res<UiFocus>,
+    mut focused: ResMut<FocusedTextInput>,
+    text_input_query: Query<(), With<TextInput>>,
+) {
+    let text_input = focus
+        .entity
+        .filter(|entity| text_input_query.contains(*entity));
+    // only trigger change detection when the edited text input changes
+    if focused.0 != text_input {
+        focused.0 = text_input;
+    }
+}
+
+/// Edits the focused [`TextInput`] with the keyboard and the IME of the window, and enables the
+/// IME while a text input is focused.
+///
+/// The keys with the control modifier, or command on macOS, select all the text (`A`), copy it
+/// (`C`), cut it (`X`) and paste the [`TextInputClipboard`] (`V`).
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_system(
+    focused: Res<FocusedTextInput>,
+    mut clipboard: ResMut<TextInputClipboard>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut character_events: EventReader<ReceivedCharacter>,
+    mut ime_events: EventReader<Ime>,
+    mut changed_events: EventWriter<TextInputChanged>,
+    mut submitted_events: EventWriter<TextInputSubmitted>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut text_input_query: Query<(&mut TextInput, Option<&TextInputParts>)>,
+    caret_query: Query<(&Node, &GlobalTransform)>,
+) {
+    let focused_input = focused
+        .0
+        .and_then(|entity| Some((entity, text_input_query.get_mut(entity).ok()?)));
+
+    if let Ok(mut window) = windows.get_single_mut() {
+        // The IME shows the composed text and its candidates under the caret
+        let ime_position = focused_input
+            .as_ref()
+            .and_then(|(_, (_, parts))| caret_query.get(parts.as_ref()?.caret).ok())
+            .map(|(node, transform)| {
+                let bottom = transform.translation().truncate() + 0.5 * node.size();
+                Vec2::new(bottom.x, window.height() - bottom.y)
+            });
+        // only trigger change detection when the values are different
+        if window.ime_enabled != focused_input.is_some() {
+            window.ime_enabled = focused_input.is_some();
+        }
+        if let Some(ime_position) = ime_position {
+            if window.ime_position != ime_position {
+                window.ime_position = ime_position;
+            }
+        }
+    }
+
+    let Some((entity, (mut text_input, _))) = focused_input else {
+        keyboard_events.clear();
+        character_events.clear();
+        ime_events.clear();
+        return;
+    };
+
+    let value = text_input.value.clone();
+    let command = keyboard_input.any_pressed([
+        KeyCode::LControl,
+        KeyCode::RControl,
+        KeyCode::LWin,
+        KeyCode::RWin,
+    ]);
+    let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+
+    for event in keyboard_events.iter() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match event.key_code {
+            Some(KeyCode::Left) => text_input.move_left(shift),
+            Some(KeyCode::Right) => text_input.move_right(shift),
+            Some(KeyCode::Home) => text_input.set_cursor(0, shift),
+            Some(KeyCode::End) => {
+                let end = text_input.value.len();
+                text_input.set_cursor(end, shift);
+            }
+            Some(KeyCode::Back) => text_input.delete_backward(),
+            Some(KeyCode::Delete) => text_input.delete_forward(),
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                submitted_events.send(TextInputSubmitted {
+                    entity,
+                    value: text_input.value.clone(),
+                });
+            }
+            Some(KeyCode::A) if command => text_input.select_all(),
+            Some(KeyCode::C) if command => clipboard.0 = text_input.selected_text().to_string(),
+            Some(KeyCode::X) if command => {
+                clipboard.0 = text_input.selected_text().to_string();
+                text_input.insert("");
+            }
+            Some(KeyCode::V) if command => text_input.insert(&clipboard.0),
+            _ => {}
+        }
+    }
+
+    for event in character_events.iter() {
+        // The control characters are handled with the keys
+        if !command && !event.char.is_control() {
+            text_input.insert(event.char.encode_utf8(&mut [0; 4]));
+        }
+    }
+
+    for event in ime_events.iter() {
+        match event {
+            Ime::Preedit { value, .. } => text_input.composition = value.clone(),
+            Ime::Commit { value, .. } => {
+                text_input.composition.clear();
+                text_input.insert(value);
+            }
+            Ime::Enabled { .. } | Ime::Disabled { .. } => text_input.composition.clear(),
+        }
+    }
+
+    if text_input.value != value {
+        changed_events.send(TextInputChanged {
+            entity,
+            value: text_input.value.clone(),
+        });
+    }
+}
+
+/// The width of a single line of text, in logical pixels
+fn text_width(font: &Font, font_size: f32, text: &str) -> f32 {
+    let font = font.font.as_scaled(font_size);
+    let mut previous = None;
+    text.chars()
+        .map(|c| {
+            let glyph = font.glyph_id(c);
+            let kerning = previous.map_or(0.0, |previous| font.kern(previous, glyph));
+            previous = Some(glyph);
+            kerning + font.h_advance(glyph)
+        })
+        .sum()
+}
+
+/// Updates the children displaying the [`TextInput`]s, and scrolls the edited text inputs to keep
+/// their caret in view
+pub fn update_text_input_system(
+    fonts: Res<Assets<Font>>,
+    focused: Res<FocusedTextInput>,
+    text_input_query: Query<(
+        Entity,
+        &TextInput,
+        ChangeTrackers<TextInput>,
+        &TextInputParts,
+    )>,
+    mut viewport_query: Query<(&Node, &mut ScrollPosition)>,
+    mut text_query: Query<(&mut Text, &Node)>,
+    mut part_query: Query<(&mut Style, &mut BackgroundColor, &mut Visibility), Without<Text>>,
+) {
+    for (entity, text_input, change_trackers, parts) in &text_input_query {
+        let (display, highlight, caret) = text_input.display();
+        let style = &text_input.style;
+
+        let mut height = style.font_size;
+        if let Ok((mut text, node)) = text_query.get_mut(parts.text) {
+            // only trigger change detection, and the layout of the text, when it changes
+            let is_changed = match &text.sections[..] {
+                [section] => {
+                    section.value != display
+                        || section.style.font != style.font
+                        || section.style.font_size != style.font_size
+                        || section.style.color != style.color
+                }
+                _ => true,
+            };
+            if is_changed {
+                *text = Text::from_section(display.clone(), style.clone());
+            }
+            height = height.max(node.size().y);
+        }
+
+        let width = |range: Range<usize>| {
+            fonts.get(&style.font).map_or(0.0, |font| {
+                text_width(font, style.font_size, &display[range])
+            })
+        };
+        let caret_x = width(0..caret);
+        let highlight_x = width(0..highlight.start);
+        let highlight_width = width(highlight.clone());
+
+        let is_focused = focused.0 == Some(entity);
+        let composing = !text_input.composition.is_empty();
+        let caret_rect = (caret_x, 0.0, CARET_WIDTH, height);
+        let highlight_rect = if composing {
+            (
+                highlight_x,
+                height - COMPOSITION_UNDERLINE_HEIGHT,
+                highlight_width,
+                COMPOSITION_UNDERLINE_HEIGHT,
+            )
+        } else {
+            (highlight_x, 0.0, highlight_width, height)
+        };
+        let highlight_color = if composing {
+            style.color
+        } else {
+            text_input.selection_color
+        };
+        let parts_to_update = [
+            (parts.caret, caret_rect, style.color, is_focused),
+            (
+                parts.highlight,
+                highlight_rect,
+                highlight_color,
+                !highlight.is_empty(),
+            ),
+        ];
+        for (part, (x, y, width, height), color, is_visible) in parts_to_update {
+            let Ok((mut part_style, mut background_color, mut visibility)) =
+                part_query.get_mut(part)
+            else {
+                continue;
+            };
+            let position = UiRect {
+                left: Val::Px(x),
+                top: Val::Px(y),
+                ..Default::default()
+            };
+            let size = Size::new(Val::Px(width), Val::Px(height));
+            // only trigger change detection, and the layout, when the values are different
+            if part_style.position != position || part_style.size != size {
+                part_style.position = position;
+                part_style.size = size;
+            }
+            if background_color.0 != color {
+                background_color.0 = color;
+            }
+            let new_visibility = if is_visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility != new_visibility {
+                *visibility = new_visibility;
+            }
+        }
+
+        // Scroll to the caret when it moves, leaving the input free to be scrolled otherwise
+        if !change_trackers.is_changed() {
+            continue;
+        }
+        if let Ok((node, mut scroll_position)) = viewport_query.get_mut(parts.viewport) {
+            let view_width = node.size().x;
+            let offset = scroll_position.offset.x;
+            let new_offset = if caret_x < offset {
+                caret_x
+            } else if caret_x + CARET_WIDTH > offset + view_width {
+                caret_x + CARET_WIDTH - view_width
+            } else {
+                offset
+            };
+            if new_offset != offset {
+                scroll_position.offset.x = new_offset;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    #[test]
+    fn text_input_editing() {
+        let mut text_input = TextInput::new("héllo", Default::default());
+        assert_eq!(text_input.cursor(), 6);
+
+        text_input.move_left(false);
+        text_input.move_left(true);
+        text_input.move_left(true);
+        text_input.move_left(true);
+        assert_eq!(text_input.selection(), 1..5);
+        assert_eq!(text_input.selected_text(), "éll");
+
+        text_input.insert("ey");
+        assert_eq!(text_input.value(), "heyo");
+        assert_eq!(text_input.cursor(), 3);
+
+        text_input.delete_backward();
+        text_input.delete_forward();
+        assert_eq!(text_input.value(), "he");
+
+        text_input.select_all();
+        text_input.move_left(false);
+        assert_eq!(text_input.cursor(), 0);
+        assert!(text_input.selection().is_empty());
+    }
+
+    #[test]
+    fn text_input_set_cursor_on_char_boundary() {
+        let mut text_input = TextInput::new("é", Default::default());
+        text_input.set_cursor(1, false);
+        assert_eq!(text_input.cursor(), 0);
+        text_input.set_cursor(10, false);
+        assert_eq!(text_input.cursor(), 2);
+    }
+
+    #[test]
+    fn text_input_composition_replaces_selection() {
+        let mut text_input = TextInput::new("abc", Default::default());
+        text_input.set_cursor(1, false);
+        text_input.set_cursor(2, true);
+        text_input.composition = "xyz".to_string();
+        let (display, highlight, caret) = text_input.display();
+        assert_eq!(display, "axyzc");
+        assert_eq!(highlight, 1..4);
+        assert_eq!(caret, 4);
+    }
+}
@@ -0,0 +1,249 @@
+//! This module contains the UI trees drawn in the world instead of on the screen
+
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_math::{Mat4, Ray, Vec2, Vec3};
+use bevy_reflect::{
+    std_traits::ReflectDefault, FromReflect, Reflect, ReflectDeserialize, ReflectSerialize,
+};
+use bevy_render::camera::Camera;
+use bevy_transform::components::{GlobalTransform, Transform};
+use serde::{Deserialize, Serialize};
+
+/// Draws the UI tree of a root node in the world, seen by the 3D cameras with their depth, instead
+/// of on the screen.
+///
+/// The tree is laid out in an area of the given `size`, like in a window. This area is centered
+/// on the `transform` of the UI, relative to the [`GlobalTransform`] of its `anchor` if any, and
+/// faces the positive Z axis of the `transform` or the camera depending on the `orientation`.
+///
+/// The nodes are interacted with by the ray of the cursor from the first active 3D camera, after
+/// the nodes on the screen. The world-space UI isn't hidden by the meshes in front of it for
+/// these interactions.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct WorldUi {
+    /// The size of the area the tree is laid out in, in logical pixels
+    pub size: Vec2,
+    /// The number of logical pixels of the UI in a unit of the world
+    pub pixels_per_unit: f32,
+    /// The entity the UI follows, like a health bar or a name plate above a character
+    pub anchor: Option<Entity>,
+    /// The transform of the center of the UI, relative to the anchor, or to the world without
+    /// anchor
+    pub transform: Transform,
+    /// Whether the UI keeps the orientation of its transform or faces the camera
+    pub orientation: WorldUiOrientation,
+}
+
+impl WorldUi {
+    pub const DEFAULT: Self = Self {
+        size: Vec2::new(100.0, 100.0),
+        pixels_per_unit: 100.0,
+        anchor: None,
+        transform: Transform::IDENTITY,
+        orientation: WorldUiOrientation::Fixed,
+    };
+
+    /// Creates a world-space UI of the given size in logical pixels, and number of logical pixels
+    /// in a unit of the world
+    pub fn new(size: Vec2, pixels_per_unit: f32) -> Self {
+        Self {
+            size,
+            pixels_per_unit,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Returns this [`WorldUi`] following the given entity
+    pub fn with_anchor(mut self, anchor: Entity) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Returns this [`WorldUi`] with the given transform, relative to its anchor if any
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Returns this [`WorldUi`] with the given orientation
+    pub fn with_orientation(mut self, orientation: WorldUiOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// The transform from the logical pixels of the UI, with the origin at the top left corner of
+    /// its area and the y axis pointing down, to the world.
+    ///
+    /// The UI is placed by the [`GlobalTransform`] of its anchor, and faces the camera with the
+    /// given [`GlobalTransform`] if it is a billboard.
+    pub fn compute_matrix(
+        &self,
+        anchor: Option<&GlobalTransform>,
+        camera: Option<&GlobalTransform>,
+    ) -> Mat4 {
+        let mut transform = anchor.copied().unwrap_or_default() * self.transform;
+        if let (WorldUiOrientation::Billboard, Some(camera)) = (self.orientation, camera) {
+            let (scale, _, translation) = transform.to_scale_rotation_translation();
+            let (_, rotation, _) = camera.to_scale_rotation_translation();
+            transform = Transform {
+                translation,
+                rotation,
+                scale,
+            }
+            .into();
+        }
+        transform.compute_matrix()
+            * Mat4::from_scale(Vec3::new(1.0, -1.0, 1.0) / self.pixels_per_unit)
+            * Mat4::from_translation((-self.size / 2.0).extend(0.0))
+    }
+}
+
+impl Default for WorldUi {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The orientation of a [`WorldUi`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect, FromReflect, Serialize, Deserialize)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum WorldUiOrientation {
+    /// The UI faces the positive Z axis of its transform
+    Fixed,
+    /// The UI faces the first active 3D camera, keeping its position and scale
+    Billboard,
+}
+
+impl Default for WorldUiOrientation {
+    fn default() -> Self {
+        Self::Fixed
+    }
+}
+
+/// The UI nodes of each tree with a [`WorldUi`], and the transform placing it in the world.
+#[derive(Debug, Resource, Default)]
+pub struct WorldUiStacks {
+    pub stacks: Vec<WorldUiStack>,
+}
+
+/// The UI nodes of a tree with a [`WorldUi`] ordered by their depth, like in the
+/// [`UiStack`](crate::UiStack), and the transform placing it in the world.
+#[derive(Debug)]
+pub struct WorldUiStack {
+    /// The root node with the [`WorldUi`]
+    pub root: Entity,
+    pub uinodes: Vec<Entity>,
+    /// The transform from the logical pixels of the UI to the world, computed by
+    /// [`WorldUi::compute_matrix`]
+    pub transform: Mat4,
+    /// The position of the center of the UI in the world, to sort the trees by distance
+    pub center: Vec3,
+}
+
+impl WorldUiStack {
+    /// The point of the UI hit by a ray, in the logical pixels of the UI, and the distance to it
+    /// along the ray, if the ray hits the plane of the UI in front of its origin.
+    pub fn ray_hit(&self, ray: Ray) -> Option<(Vec2, f32)> {
+        let inverse = self.transform.inverse();
+        let origin = inverse.transform_point3(ray.origin);
+        let direction = inverse.transform_vector3(ray.direction);
+        // The UI is on the plane z = 0 of its space, where the ray keeps its length
+        if direction.z.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = -origin.z / direction.z;
+        (distance >= 0.0).then(|| ((origin + distance * direction).truncate(), distance))
+    }
+}
+
+/// Updates the transforms of the [`WorldUiStacks`], from the [`WorldUi`] of their root and the
+/// [`GlobalTransform`] of its anchor.
+pub fn update_world_ui_system(
+    mut world_ui_stacks: ResMut<WorldUiStacks>,
+    world_ui_query: Query<&WorldUi>,
+    anchor_query: Query<&GlobalTransform>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) {
+    let camera = camera_query
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, camera_transform)| camera_transform);
+    for stack in &mut world_ui_stacks.stacks {
+        let Ok(world_ui) = world_ui_query.get(stack.root) else {
+            continue;
+        };
+        let anchor = world_ui
+            .anchor
+            .and_then(|anchor| anchor_query.get(anchor).ok());
+        stack.transform = world_ui.compute_matrix(anchor, camera);
+        stack.center = stack
+            .transform
+            .transform_point3((world_ui.size / 2.0).extend(0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WorldUi, WorldUiOrientation, WorldUiStack};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Quat, Ray, Vec2, Vec3};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    #[test]
+    fn world_ui_transform() {
+        let world_ui = WorldUi::new(Vec2::new(200.0, 100.0), 100.0)
+            .with_transform(Transform::from_xyz(0.0, 2.0, 0.0));
+        let anchor = GlobalTransform::from_xyz(5.0, 0.0, 0.0);
+        let transform = world_ui.compute_matrix(Some(&anchor), None);
+
+        // the center of the area is on the transform of the UI, relative to its anchor
+        let center = transform.transform_point3(Vec3::new(100.0, 50.0, 0.0));
+        assert!(center.abs_diff_eq(Vec3::new(5.0, 2.0, 0.0), 1e-5));
+        // the y axis of the UI points down
+        let top_left = transform.transform_point3(Vec3::ZERO);
+        assert!(top_left.abs_diff_eq(Vec3::new(4.0, 2.5, 0.0), 1e-5));
+
+        // a billboard faces the camera, keeping its position
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(10.0, 2.0, 0.0).looking_at(Vec3::new(5.0, 2.0, 0.0), Vec3::Y),
+        );
+        let billboard = world_ui.with_orientation(WorldUiOrientation::Billboard);
+        let transform = billboard.compute_matrix(Some(&anchor), Some(&camera));
+        let center = transform.transform_point3(Vec3::new(100.0, 50.0, 0.0));
+        assert!(center.abs_diff_eq(Vec3::new(5.0, 2.0, 0.0), 1e-5));
+        let normal = transform.transform_vector3(Vec3::Z).normalize();
+        assert!(normal.abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn world_ui_ray_hit() {
+        let world_ui = WorldUi::new(Vec2::new(200.0, 100.0), 100.0).with_transform(
+            Transform::from_xyz(0.0, 0.0, -2.0).with_rotation(Quat::from_rotation_y(0.5)),
+        );
+        let transform = world_ui.compute_matrix(None, None);
+        let stack = WorldUiStack {
+            root: Entity::from_raw(0),
+            uinodes: Vec::new(),
+            transform,
+            center: transform.transform_point3(Vec3::new(100.0, 50.0, 0.0)),
+        };
+
+        let target = transform.transform_point3(Vec3::new(150.0, 25.0, 0.0));
+        let ray = Ray {
+            origin: Vec3::new(0.0, 0.0, 3.0),
+            direction: (target - Vec3::new(0.0, 0.0, 3.0)).normalize(),
+        };
+        let (point, distance) = stack.ray_hit(ray).unwrap();
+        assert!(point.abs_diff_eq(Vec2::new(150.0, 25.0), 1e-3));
+        assert!((distance - target.distance(ray.origin)).abs() < 1e-4);
+
+        // the UI behind the origin of the ray isn't hit
+        let ray = Ray {
+            origin: ray.origin,
+            direction: -ray.direction,
+        };
+        assert_eq!(stack.ray_hit(ray), None);
+    }
+}
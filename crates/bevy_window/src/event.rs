@@ -153,6 +153,46 @@ pub struct ReceivedCharacter {
     pub char: char,
 }
 
+/// An event that is sent when the input method editor (IME) of a window composes or commits text.
+///
+/// It is only sent while [`Window::ime_enabled`](crate::Window::ime_enabled) is set. While the
+/// text is composed, the window doesn't receive the [`ReceivedCharacter`] events of its keys.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum Ime {
+    /// The text being composed changed. An empty value ends the composition.
+    Preedit {
+        /// Window of the IME.
+        window: Entity,
+        /// The text being composed, to display in place of the selection.
+        value: String,
+        /// The byte range of the cursor in the composed text, `None` to hide the cursor.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composed text is committed, to insert in place of the selection.
+    Commit {
+        /// Window of the IME.
+        window: Entity,
+        /// The committed text.
+        value: String,
+    },
+    /// The IME of the window is enabled.
+    Enabled {
+        /// Window of the IME.
+        window: Entity,
+    },
+    /// The IME of the window is disabled.
+    Disabled {
+        /// Window of the IME.
+        window: Entity,
+    },
+}
+
 /// An event that indicates a window has received or lost focus.
 #[derive(Debug, Clone, PartialEq, Eq, Reflect, FromReflect)]
 #[reflect(Debug, PartialEq)]
@@ -79,6 +79,7 @@ impl Plugin for WindowPlugin {
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
             .add_event::<ReceivedCharacter>()
+            .add_event::<Ime>()
             .add_event::<WindowFocused>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
@@ -115,6 +116,7 @@ impl Plugin for WindowPlugin {
             .register_type::<CursorEntered>()
             .register_type::<CursorLeft>()
             .register_type::<ReceivedCharacter>()
+            .register_type::<Ime>()
             .register_type::<WindowFocused>()
             .register_type::<WindowScaleFactorChanged>()
             .register_type::<WindowBackendScaleFactorChanged>()
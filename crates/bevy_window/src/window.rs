@@ -2,7 +2,7 @@ use bevy_ecs::{
     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
     prelude::{Component, ReflectComponent},
 };
-use bevy_math::{DVec2, IVec2};
+use bevy_math::{DVec2, IVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
 
 #[cfg(feature = "serialize")]
@@ -158,6 +158,22 @@ pub struct Window {
     ///
     /// This value has no effect on non-web platforms.
     pub fit_canvas_to_parent: bool,
+    /// Should the window use the input method editor (IME) of the system?
+    ///
+    /// While enabled, the text composed by the IME is received through [`Ime`](crate::Ime)
+    /// events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - iOS / Android / Web: Unsupported.
+    pub ime_enabled: bool,
+    /// Where the IME shows the text being composed and its candidates, in logical pixels from the
+    /// bottom-left corner of the window, like [`Cursor::position`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - iOS / Android / Web: Unsupported.
+    pub ime_position: Vec2,
     /// Stores internal state that isn't directly accessible.
     pub internal: InternalWindowState,
 }
@@ -181,6 +197,8 @@ impl Default for Window {
             always_on_top: false,
             fit_canvas_to_parent: false,
             canvas: None,
+            ime_enabled: false,
+            ime_position: Vec2::ZERO,
         }
     }
 }
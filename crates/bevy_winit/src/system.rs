@@ -13,7 +13,7 @@ use bevy_window::{RawHandleWrapper, Window, WindowClosed, WindowCreated};
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
 use winit::{
-    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     event_loop::EventLoopWindowTarget,
 };
 
@@ -176,6 +176,18 @@ pub(crate) fn changed_window(
                 }
             }
 
+            if window.ime_enabled != previous.ime_enabled {
+                winit_window.set_ime_allowed(window.ime_enabled);
+            }
+
+            if window.ime_position != previous.ime_position {
+                winit_window.set_ime_position(LogicalPosition::new(
+                    window.ime_position.x,
+                    // Flip the coordinate space back to winit's context.
+                    window.height() - window.ime_position.y,
+                ));
+            }
+
             if window.cursor.icon != previous.cursor.icon {
                 winit_window.set_cursor_icon(converters::convert_cursor_icon(window.cursor.icon));
             }
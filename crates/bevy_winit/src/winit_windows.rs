@@ -120,6 +120,7 @@ impl WinitWindows {
         }
 
         winit_window.set_cursor_visible(window.cursor.visible);
+        winit_window.set_ime_allowed(window.ime_enabled);
 
         self.entity_to_winit.insert(entity, winit_window.id());
         self.winit_to_entity.insert(winit_window.id(), entity);
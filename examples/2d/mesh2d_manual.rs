@@ -6,7 +6,7 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    core_pipeline::core_2d::Transparent2d,
+    core_pipeline::core_2d::{Transparent2d, Transparent2dSortKey},
     prelude::*,
     reflect::TypeUuid,
     render::{
@@ -27,7 +27,6 @@ use bevy::{
         DrawMesh2d, Mesh2dHandle, Mesh2dPipeline, Mesh2dPipelineKey, Mesh2dUniform,
         SetMesh2dBindGroup, SetMesh2dViewBindGroup,
     },
-    utils::FloatOrd,
 };
 
 fn main() {
@@ -352,7 +351,7 @@ pub fn queue_colored_mesh2d(
                     pipeline: pipeline_id,
                     // The 2d render items are sorted according to their z value before rendering,
                     // in order to get correct transparency
-                    sort_key: FloatOrd(mesh_z),
+                    sort_key: Transparent2dSortKey::from_z(mesh_z),
                     // This material is not batched
                     batch_range: None,
                 });
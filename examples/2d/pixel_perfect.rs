@@ -1,4 +1,7 @@
 //! Renders a 2D scene containing pixelated bevy logo in a pixel perfect style
+//!
+//! The camera renders at a fixed resolution, upscaled by an integer factor to the window, and the
+//! moving logo is snapped to the pixels of the camera.
 
 use bevy::prelude::*;
 
@@ -17,11 +20,14 @@ enum Direction {
 }
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        Camera2dBundle::default(),
+        PixelPerfectCamera::new(UVec2::new(320, 180)),
+    ));
     commands.spawn((
         SpriteBundle {
             texture: asset_server.load("pixel/bevy_pixel_light.png"),
-            transform: Transform::from_xyz(100., 0., 0.),
+            transform: Transform::from_xyz(50., 0., 0.),
             ..default()
         },
         Direction::Right,
@@ -31,13 +37,13 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 fn sprite_movement(time: Res<Time>, mut sprite_position: Query<(&mut Direction, &mut Transform)>) {
     for (mut logo, mut transform) in &mut sprite_position {
         match *logo {
-            Direction::Right => transform.translation.x += 30. * time.delta_seconds(),
-            Direction::Left => transform.translation.x -= 30. * time.delta_seconds(),
+            Direction::Right => transform.translation.x += 15. * time.delta_seconds(),
+            Direction::Left => transform.translation.x -= 15. * time.delta_seconds(),
         }
 
-        if transform.translation.x > 200. {
+        if transform.translation.x > 100. {
             *logo = Direction::Left;
-        } else if transform.translation.x < -200. {
+        } else if transform.translation.x < -100. {
             *logo = Direction::Right;
         }
     }
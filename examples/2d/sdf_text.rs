@@ -0,0 +1,60 @@
+//! Compares the bitmap glyphs of text with glyphs drawn from signed distance fields, while the
+//! texts are scaled up and rotated.
+//!
+//! The bitmap glyphs are rasterized for the font size of the text, and get blurry once scaled,
+//! while the signed distance fields keep their edges crisp at any scale.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(animate)
+        .run();
+}
+
+#[derive(Component)]
+struct Animated;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn(Camera2dBundle::default());
+
+    for (rendering, label, position) in [
+        (TextRendering::Bitmap, "Bitmap", Vec2::new(-300.0, 100.0)),
+        (TextRendering::SDF, "SDF", Vec2::new(300.0, 100.0)),
+        // A lower sharpness softens the edges of the glyphs
+        (
+            TextRendering::Sdf { sharpness: 0.3 },
+            "Soft SDF",
+            Vec2::new(0.0, -200.0),
+        ),
+    ] {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    label,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                        rendering,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(position.extend(0.0)),
+                ..default()
+            },
+            Animated,
+        ));
+    }
+}
+
+fn animate(time: Res<Time>, mut texts: Query<&mut Transform, With<Animated>>) {
+    let seconds = time.elapsed_seconds();
+    for mut transform in &mut texts {
+        transform.scale = Vec3::splat(2.0 + 3.0 * (seconds * 0.5).sin().abs());
+        transform.rotation = Quat::from_rotation_z(0.3 * (seconds * 0.7).sin());
+    }
+}
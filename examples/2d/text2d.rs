@@ -32,6 +32,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         font,
         font_size: 60.0,
         color: Color::WHITE,
+        ..default()
     };
     let text_alignment = TextAlignment::Center;
     // 2d camera
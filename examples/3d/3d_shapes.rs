@@ -20,7 +20,7 @@ fn main() {
 #[derive(Component)]
 struct Shape;
 
-const X_EXTENT: f32 = 14.5;
+const X_EXTENT: f32 = 22.;
 
 fn setup(
     mut commands: Commands,
@@ -41,6 +41,16 @@ fn setup(
         meshes.add(shape::Cylinder::default().into()),
         meshes.add(shape::Icosphere::default().try_into().unwrap()),
         meshes.add(shape::UVSphere::default().into()),
+        meshes.add(shape::Cone::default().into()),
+        meshes.add(
+            shape::TorusKnot {
+                radius: 0.5,
+                tube_radius: 0.1,
+                ..default()
+            }
+            .into(),
+        ),
+        meshes.add(shape::RoundedBox::default().into()),
     ];
 
     let num_shapes = shapes.len();
@@ -75,13 +85,13 @@ fn setup(
 
     // ground plane
     commands.spawn(PbrBundle {
-        mesh: meshes.add(shape::Plane { size: 50. }.into()),
+        mesh: meshes.add(shape::Plane::from_size(50.).into()),
         material: materials.add(Color::SILVER.into()),
         ..default()
     });
 
     commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 6., 12.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+        transform: Transform::from_xyz(0.0, 7., 16.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
         ..default()
     });
 }
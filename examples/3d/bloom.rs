@@ -82,6 +82,7 @@ fn setup_scene(
                 font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                 font_size: 18.0,
                 color: Color::BLACK,
+                ..default()
             },
         )
         .with_style(Style {
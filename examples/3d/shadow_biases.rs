@@ -113,9 +113,7 @@ fn setup(
 
     // ground plane
     commands.spawn(PbrBundle {
-        mesh: meshes.add(Mesh::from(shape::Plane {
-            size: 2.0 * spawn_plane_depth,
-        })),
+        mesh: meshes.add(Mesh::from(shape::Plane::from_size(2.0 * spawn_plane_depth))),
         material: white_handle,
         ..default()
     });
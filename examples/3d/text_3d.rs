@@ -0,0 +1,90 @@
+//! This example illustrates drawing text in the world as meshes of extruded glyphs, lit and shaded
+//! like any other mesh.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotate_title)
+        .run();
+}
+
+#[derive(Component)]
+struct Title;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane::from_size(10.0))),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // title, its mesh is built once its font is loaded
+    commands.spawn((
+        PbrBundle {
+            material: materials.add(StandardMaterial {
+                base_color: Color::GOLD,
+                metallic: 0.8,
+                perceptual_roughness: 0.3,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 1.5, 0.0),
+            ..default()
+        },
+        Text3d {
+            value: "Bevy".to_string(),
+            font: font.clone(),
+            font_size: 1.5,
+            depth: 0.3,
+            ..default()
+        },
+        Title,
+    ));
+    // sign, with two lines aligned to the left
+    commands.spawn((
+        PbrBundle {
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(-3.0, 0.5, 1.0)
+                .with_rotation(Quat::from_rotation_y(0.5)),
+            ..default()
+        },
+        Text3d {
+            value: "Exit\nthis way".to_string(),
+            font,
+            font_size: 0.4,
+            depth: 0.05,
+            alignment: TextAlignment::Left,
+        },
+    ));
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-1.0, 2.5, 7.0)
+            .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate_title(time: Res<Time>, mut titles: Query<&mut Transform, With<Title>>) {
+    for mut transform in &mut titles {
+        transform.rotation = Quat::from_rotation_y(time.elapsed_seconds().sin() * 0.5);
+    }
+}
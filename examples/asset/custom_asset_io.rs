@@ -44,6 +44,10 @@ impl AssetIo for CustomAssetIo {
         info!("get_metadata({:?})", path);
         self.0.get_metadata(path)
     }
+
+    fn take_changed_paths(&self) -> Vec<PathBuf> {
+        self.0.take_changed_paths()
+    }
 }
 
 /// A plugin used to execute the override of the asset io
@@ -0,0 +1,92 @@
+//! This example illustrates how to mix sounds in audio buses, control the volume of each bus
+//! like from the options menu of a game, and process the sound of the buses with effects.
+//!
+//! Press `Up` and `Down` to change the volume of the music, `M` to mute it, and `S` to mute the
+//! sound effects. Press `L` to muffle the music, and `R` to add reverb to the sound effects.
+
+use bevy::{
+    audio::{LowPassFilter, Reverb},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(SfxTimer(Timer::from_seconds(1.0, TimerMode::Repeating)))
+        .add_startup_system(setup)
+        .add_system(play_sfx)
+        .add_system(control_buses)
+        .add_system(control_effects)
+        .run();
+}
+
+#[derive(Resource)]
+struct SfxTimer(Timer);
+
+#[derive(Resource)]
+struct Effects {
+    low_pass: LowPassFilter,
+    reverb: Reverb,
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    audio_buses: Res<AudioBuses>,
+) {
+    // The effects of the buses share their parameters with the ones kept in the resource
+    let effects = Effects {
+        low_pass: LowPassFilter::new(20_000.0),
+        reverb: Reverb::new(0.8, 0.5, 0.0),
+    };
+    audio_buses.add_effect(AudioBus::Music, effects.low_pass.clone());
+    audio_buses.add_effect(AudioBus::Sfx, effects.reverb.clone());
+    commands.insert_resource(effects);
+
+    audio.play_with_settings(
+        asset_server.load("sounds/Windless Slopes.ogg"),
+        PlaybackSettings::LOOP.with_bus(AudioBus::Music),
+    );
+}
+
+fn play_sfx(
+    time: Res<Time>,
+    mut timer: ResMut<SfxTimer>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        audio.play_with_settings(
+            asset_server.load("sounds/breakout_collision.ogg"),
+            PlaybackSettings::ONCE.with_bus(AudioBus::Sfx),
+        );
+    }
+}
+
+fn control_buses(keyboard_input: Res<Input<KeyCode>>, audio_buses: Res<AudioBuses>) {
+    let music_volume = audio_buses.volume(AudioBus::Music);
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        audio_buses.set_volume(AudioBus::Music, (music_volume + 0.1).min(1.0));
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        audio_buses.set_volume(AudioBus::Music, (music_volume - 0.1).max(0.0));
+    }
+    if keyboard_input.just_pressed(KeyCode::M) {
+        audio_buses.toggle_mute(AudioBus::Music);
+    }
+    if keyboard_input.just_pressed(KeyCode::S) {
+        audio_buses.toggle_mute(AudioBus::Sfx);
+    }
+}
+
+fn control_effects(keyboard_input: Res<Input<KeyCode>>, effects: Res<Effects>) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        let muffled = effects.low_pass.cutoff() < 1_000.0;
+        let cutoff = if muffled { 20_000.0 } else { 400.0 };
+        effects.low_pass.set_cutoff(cutoff);
+    }
+    if keyboard_input.just_pressed(KeyCode::R) {
+        let mix = if effects.reverb.mix() > 0.0 { 0.0 } else { 0.4 };
+        effects.reverb.set_mix(mix);
+    }
+}
@@ -1,6 +1,13 @@
 //! This example illustrates how to load and play an audio file, and control how it's played.
+//!
+//! Press `Space` to pause the music, `+` and `-` to change its volume, `Left` and `Right` to seek
+//! in it, and `L` to loop the next five seconds.
 
-use bevy::{audio::AudioSink, prelude::*};
+use bevy::{
+    audio::{AudioSink, PlaybackFinished},
+    prelude::*,
+};
+use std::time::Duration;
 
 fn main() {
     App::new()
@@ -9,6 +16,9 @@ fn main() {
         .add_system(update_speed)
         .add_system(pause)
         .add_system(volume)
+        .add_system(seek)
+        .add_system(loop_section)
+        .add_system(music_finished)
         .run();
 }
 
@@ -61,3 +71,46 @@ fn volume(
         }
     }
 }
+
+fn seek(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    if let Some(sink) = audio_sinks.get(&music_controller.0) {
+        let step = Duration::from_secs(5);
+        if keyboard_input.just_pressed(KeyCode::Right) {
+            sink.seek(sink.position() + step);
+        } else if keyboard_input.just_pressed(KeyCode::Left) {
+            sink.seek(sink.position().saturating_sub(step));
+        }
+    }
+}
+
+fn loop_section(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        if let Some(sink) = audio_sinks.get(&music_controller.0) {
+            if sink.loop_points().is_some() {
+                sink.clear_loop();
+            } else {
+                let start = sink.position();
+                sink.set_loop(start, Some(start + Duration::from_secs(5)));
+            }
+        }
+    }
+}
+
+fn music_finished(
+    mut finished_events: EventReader<PlaybackFinished>,
+    music_controller: Res<MusicController>,
+) {
+    for event in finished_events.iter() {
+        if event.sink == music_controller.0 {
+            info!("The music finished playing");
+        }
+    }
+}
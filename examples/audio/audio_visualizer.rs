@@ -0,0 +1,89 @@
+//! This example illustrates how to visualize the spectrum and the level of the music, from the
+//! `AudioAnalysis` resource.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(update_bars)
+        .run();
+}
+
+/// The number of bars of the spectrum
+const BARS: usize = 32;
+const BAR_WIDTH: f32 = 20.0;
+const MAX_BAR_HEIGHT: f32 = 400.0;
+
+/// A bar showing the amplitude of the frequencies of the music in a range, in hertz
+#[derive(Component)]
+struct SpectrumBar {
+    low: f32,
+    high: f32,
+}
+
+/// The bar showing the level of the music
+#[derive(Component)]
+struct LevelBar;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    audio.play_with_settings(
+        asset_server.load("sounds/Windless Slopes.ogg"),
+        PlaybackSettings::LOOP.with_bus(AudioBus::Music),
+    );
+    commands.spawn(Camera2dBundle::default());
+
+    // The bars cover frequencies from 40 Hz to 16 kHz, spaced logarithmically like the pitch of
+    // the notes
+    let frequency = |bar: usize| 40.0 * 400f32.powf(bar as f32 / BARS as f32);
+    let left = -(BARS as f32) * BAR_WIDTH / 2.0;
+    for bar in 0..BARS {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::hsl(360.0 * bar as f32 / BARS as f32, 0.8, 0.6),
+                    custom_size: Some(Vec2::new(BAR_WIDTH - 4.0, 1.0)),
+                    anchor: bevy::sprite::Anchor::BottomCenter,
+                    ..default()
+                },
+                transform: Transform::from_xyz(left + bar as f32 * BAR_WIDTH, -200.0, 0.0),
+                ..default()
+            },
+            SpectrumBar {
+                low: frequency(bar),
+                high: frequency(bar + 1),
+            },
+        ));
+    }
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::WHITE,
+                custom_size: Some(Vec2::new(BAR_WIDTH, 1.0)),
+                anchor: bevy::sprite::Anchor::BottomCenter,
+                ..default()
+            },
+            transform: Transform::from_xyz(-left + BAR_WIDTH, -200.0, 0.0),
+            ..default()
+        },
+        LevelBar,
+    ));
+}
+
+fn update_bars(
+    audio_analysis: Res<AudioAnalysis>,
+    mut spectrum_bars: Query<(&SpectrumBar, &mut Sprite), Without<LevelBar>>,
+    mut level_bars: Query<&mut Sprite, With<LevelBar>>,
+) {
+    let music = audio_analysis.bus(AudioBus::Music);
+    for (bar, mut sprite) in &mut spectrum_bars {
+        // The amplitude is shown in decibels, from -60 dB
+        let decibels = 20.0 * music.mean_amplitude(bar.low, bar.high).max(1e-6).log10();
+        let height = ((decibels + 60.0) / 60.0).clamp(0.0, 1.0) * MAX_BAR_HEIGHT;
+        sprite.custom_size = Some(Vec2::new(BAR_WIDTH - 4.0, height.max(1.0)));
+    }
+    for mut sprite in &mut level_bars {
+        sprite.custom_size = Some(Vec2::new(BAR_WIDTH, music.rms * MAX_BAR_HEIGHT));
+    }
+}
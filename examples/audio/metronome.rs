@@ -0,0 +1,58 @@
+//! This example illustrates how to schedule sounds at precise times of the audio clock, to play
+//! them in rhythm regardless of the frame rate.
+//!
+//! Press `Up` and `Down` to change the tempo.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// How far ahead of the audio clock the beats are scheduled, which must be longer than a frame
+const SCHEDULE_AHEAD: Duration = Duration::from_millis(200);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(Metronome {
+            beats_per_minute: 120.0,
+            next_beat: Duration::ZERO,
+        })
+        .add_system(schedule_beats)
+        .add_system(change_tempo)
+        .run();
+}
+
+#[derive(Resource)]
+struct Metronome {
+    beats_per_minute: f64,
+    /// The time of the audio clock of the next beat to schedule
+    next_beat: Duration,
+}
+
+fn schedule_beats(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    audio_buses: Res<AudioBuses>,
+    mut metronome: ResMut<Metronome>,
+) {
+    let clock = audio_buses.clock();
+    // Skips the beats missed while the app was starting
+    metronome.next_beat = metronome.next_beat.max(clock);
+    while metronome.next_beat < clock + SCHEDULE_AHEAD {
+        audio.play_with_settings(
+            asset_server.load("sounds/breakout_collision.ogg"),
+            PlaybackSettings::ONCE.with_start_time(metronome.next_beat),
+        );
+        metronome.next_beat += Duration::from_secs_f64(60.0 / metronome.beats_per_minute);
+    }
+}
+
+fn change_tempo(keyboard_input: Res<Input<KeyCode>>, mut metronome: ResMut<Metronome>) {
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        metronome.beats_per_minute = (metronome.beats_per_minute + 10.0).min(240.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        metronome.beats_per_minute = (metronome.beats_per_minute - 10.0).max(40.0);
+    } else {
+        return;
+    }
+    info!("Tempo: {} beats per minute", metronome.beats_per_minute);
+}
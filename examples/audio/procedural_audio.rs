@@ -0,0 +1,100 @@
+//! This example illustrates how to synthesize a sound on the audio thread with an
+//! `AudioGenerator`, controlled by messages sent from the systems.
+//!
+//! Press the keys from `A` to `K` to play the notes of a scale.
+
+use bevy::{audio::AudioGeneratorSender, prelude::*};
+use std::f32::consts::TAU;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(play_notes)
+        .run();
+}
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// The messages controlling the synthesizer
+enum SynthMessage {
+    NoteOn(f32),
+    NoteOff,
+}
+
+/// A sine wave with a short attack and release, to avoid clicks
+struct Synth {
+    frequency: f32,
+    phase: f32,
+    amplitude: f32,
+    target_amplitude: f32,
+}
+
+impl AudioGenerator for Synth {
+    type Message = SynthMessage;
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn receive(&mut self, message: SynthMessage) {
+        match message {
+            SynthMessage::NoteOn(frequency) => {
+                self.frequency = frequency;
+                self.target_amplitude = 0.2;
+            }
+            SynthMessage::NoteOff => self.target_amplitude = 0.0,
+        }
+    }
+
+    fn generate(&mut self, samples: &mut [f32]) -> bool {
+        for sample in samples {
+            self.amplitude += (self.target_amplitude - self.amplitude) * 0.002;
+            *sample = (self.phase * TAU).sin() * self.amplitude;
+            self.phase = (self.phase + self.frequency / SAMPLE_RATE as f32).fract();
+        }
+        // The synthesizer plays until the end of the app
+        true
+    }
+}
+
+#[derive(Resource)]
+struct SynthController(AudioGeneratorSender<SynthMessage>);
+
+fn setup(mut commands: Commands, audio: Res<Audio>) {
+    let synth = Synth {
+        frequency: 440.0,
+        phase: 0.0,
+        amplitude: 0.0,
+        target_amplitude: 0.0,
+    };
+    let (_sink, sender) = audio.play_generator(synth, PlaybackSettings::ONCE);
+    commands.insert_resource(SynthController(sender));
+}
+
+fn play_notes(keyboard_input: Res<Input<KeyCode>>, synth: Res<SynthController>) {
+    // The notes of the C major scale
+    const NOTES: [(KeyCode, f32); 8] = [
+        (KeyCode::A, 261.63),
+        (KeyCode::S, 293.66),
+        (KeyCode::D, 329.63),
+        (KeyCode::F, 349.23),
+        (KeyCode::G, 392.00),
+        (KeyCode::H, 440.00),
+        (KeyCode::J, 493.88),
+        (KeyCode::K, 523.25),
+    ];
+    for (key, frequency) in NOTES {
+        if keyboard_input.just_pressed(key) {
+            synth.0.send(SynthMessage::NoteOn(frequency));
+        }
+    }
+    let keys = NOTES.map(|(key, _)| key);
+    if keyboard_input.any_just_released(keys) && !keyboard_input.any_pressed(keys) {
+        synth.0.send(SynthMessage::NoteOff);
+    }
+}
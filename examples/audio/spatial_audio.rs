@@ -0,0 +1,96 @@
+//! This example illustrates how to play a sound from an entity, attenuated and panned from its
+//! position relative to the listener, and shifted in pitch by the Doppler effect as it moves.
+//!
+//! Press `H` to toggle the approximation of a head-related transfer function, and `D` to
+//! exaggerate the Doppler effect.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(orbit)
+        .add_system(toggle_hrtf)
+        .add_system(toggle_doppler)
+        .run();
+}
+
+#[derive(Component)]
+struct Orbit;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // The emitter, orbiting around the listener off-center to move towards and away from it
+    let emitter = commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::UVSphere {
+                    radius: 0.2,
+                    ..default()
+                })),
+                material: materials.add(Color::rgb(0.8, 0.2, 0.2).into()),
+                ..default()
+            },
+            AudioEmitter::default(),
+            Orbit,
+        ))
+        .id();
+    audio.play_with_settings(
+        asset_server.load("sounds/Windless Slopes.ogg"),
+        PlaybackSettings::LOOP.with_emitter(emitter),
+    );
+
+    // The listener, facing -Z with its right ear along +X
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 0.3 })),
+            material: materials.add(Color::rgb(0.2, 0.2, 0.8).into()),
+            ..default()
+        },
+        AudioListener::default(),
+    ));
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 6.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(0.0, 4.0, 0.0),
+        ..default()
+    });
+}
+
+fn orbit(time: Res<Time>, mut emitters: Query<&mut Transform, With<Orbit>>) {
+    let angle = time.elapsed_seconds() * 0.5;
+    for mut transform in &mut emitters {
+        transform.translation = Vec3::new(angle.cos(), 0.0, angle.sin()) * 3.0 + Vec3::Z * 2.0;
+    }
+}
+
+fn toggle_hrtf(keyboard_input: Res<Input<KeyCode>>, mut listeners: Query<&mut AudioListener>) {
+    if keyboard_input.just_pressed(KeyCode::H) {
+        for mut listener in &mut listeners {
+            listener.hrtf = !listener.hrtf;
+            info!("HRTF: {}", listener.hrtf);
+        }
+    }
+}
+
+fn toggle_doppler(keyboard_input: Res<Input<KeyCode>>, mut listeners: Query<&mut AudioListener>) {
+    if keyboard_input.just_pressed(KeyCode::D) {
+        for mut listener in &mut listeners {
+            // The emitter is too slow for the Doppler effect to be noticeable without exaggerating it
+            listener.doppler_factor = if listener.doppler_factor > 1.0 {
+                1.0
+            } else {
+                30.0
+            };
+            info!("Doppler factor: {}", listener.doppler_factor);
+        }
+    }
+}
@@ -55,7 +55,7 @@ fn setup_scene(
 ) {
     // plane
     commands.spawn(PbrBundle {
-        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        mesh: meshes.add(Mesh::from(shape::Plane::from_size(5.0))),
         material: materials.add(Color::rgb(0.1, 0.2, 0.1).into()),
         ..default()
     });
@@ -120,6 +120,7 @@ fn setup_scene(
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 30.0,
                         color: Color::BLACK,
+                        ..default()
                     },
                 )
                 .with_text_alignment(TextAlignment::Center),
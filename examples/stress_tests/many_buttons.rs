@@ -112,6 +112,7 @@ fn spawn_button(
                     font,
                     font_size: FONT_SIZE,
                     color: Color::rgb(0.2, 0.2, 0.2),
+                    ..default()
                 },
             ));
         });
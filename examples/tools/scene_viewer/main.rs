@@ -37,6 +37,7 @@ fn main() {
                 asset_folder: std::env::var("CARGO_MANIFEST_DIR")
                     .unwrap_or_else(|_| ".".to_string()),
                 watch_for_changes: true,
+                ..default()
             }),
     )
     .add_plugin(CameraControllerPlugin)
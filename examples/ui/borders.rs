@@ -0,0 +1,73 @@
+//! Demonstrates the borders and rounded corners of UI nodes.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let borders = [
+        // A uniform border
+        (UiRect::all(Val::Px(5.0)), BorderColor::all(Color::WHITE)),
+        // A border with a different color on each side
+        (
+            UiRect::all(Val::Px(10.0)),
+            BorderColor {
+                left: Color::RED,
+                right: Color::GREEN,
+                top: Color::YELLOW,
+                bottom: Color::BLUE,
+            },
+        ),
+        // A border with a different width on each side
+        (
+            UiRect::new(Val::Px(2.0), Val::Px(8.0), Val::Px(14.0), Val::Px(20.0)),
+            BorderColor::all(Color::ORANGE),
+        ),
+        // Only the bottom side
+        (UiRect::bottom(Val::Px(6.0)), BorderColor::all(Color::CYAN)),
+    ];
+    let radii = [
+        BorderRadius::DEFAULT,
+        BorderRadius::all(Val::Px(20.0)),
+        BorderRadius::all(Val::Percent(50.0)),
+        BorderRadius::px(0.0, 30.0, 0.0, 30.0),
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                align_content: AlignContent::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            for border_radius in radii {
+                for (border, border_color) in borders {
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(120.0), Val::Px(120.0)),
+                            margin: UiRect::all(Val::Px(15.0)),
+                            border,
+                            ..default()
+                        },
+                        background_color: Color::rgb(0.35, 0.35, 0.75).into(),
+                        border_color,
+                        border_radius,
+                        ..default()
+                    });
+                }
+            }
+        });
+}
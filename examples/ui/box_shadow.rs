@@ -0,0 +1,70 @@
+//! Demonstrates the shadows of UI nodes, behind them and inset.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.8, 0.8, 0.8)))
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let shadows = [
+        // A sharp shadow
+        BoxShadow::new(Color::rgba(0.0, 0.0, 0.0, 0.8), 10.0, 10.0, 0.0),
+        // A blurred shadow
+        BoxShadow::new(Color::rgba(0.0, 0.0, 0.0, 0.8), 5.0, 10.0, 15.0),
+        // A glow around the node
+        BoxShadow {
+            color: Color::rgba(1.0, 0.8, 0.2, 0.9),
+            spread_radius: Val::Px(5.0),
+            blur_radius: Val::Px(10.0),
+            ..default()
+        },
+        // An inset shadow
+        BoxShadow {
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            x_offset: Val::Px(4.0),
+            y_offset: Val::Px(4.0),
+            blur_radius: Val::Px(8.0),
+            inset: true,
+            ..default()
+        },
+    ];
+    let radii = [BorderRadius::DEFAULT, BorderRadius::all(Val::Px(25.0))];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                align_content: AlignContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for border_radius in radii {
+                for box_shadow in shadows {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(120.0), Val::Px(120.0)),
+                                margin: UiRect::all(Val::Px(30.0)),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            border_radius,
+                            ..default()
+                        },
+                        box_shadow,
+                    ));
+                }
+            }
+        });
+}
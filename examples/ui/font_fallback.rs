@@ -0,0 +1,71 @@
+//! This example illustrates drawing the characters missing from the font of a text with fallback
+//! fonts, here loaded from the fonts installed on the system.
+//!
+//! Run it with `cargo run --example font_fallback --features system_fonts`.
+
+use bevy::prelude::*;
+use bevy::text::{GenericFontFamily, SystemFonts};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+const TEXT: &str = "Bevy 世界 Καλημέρα Привет שלום ∑∞≈ → ✓";
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut system_fonts: ResMut<SystemFonts>,
+    mut fonts: ResMut<Assets<Font>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    // An installed font for each kind of character missing from the font of the text, then the
+    // sans-serif font of the system for anything else
+    let mut fallback_fonts: Vec<Handle<Font>> = ['世', 'П', '∑', '✓']
+        .into_iter()
+        .filter_map(|character| system_fonts.load_for_char(character, &mut fonts))
+        .collect();
+    fallback_fonts.extend(system_fonts.load_generic(GenericFontFamily::SansSerif, &mut fonts));
+    fallback_fonts.dedup();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            // Without fallback fonts, the missing characters are drawn as placeholders
+            parent.spawn(TextBundle::from_section(
+                TEXT,
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 40.0,
+                    color: Color::GRAY,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                TEXT,
+                TextStyle {
+                    font,
+                    fallback_fonts,
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
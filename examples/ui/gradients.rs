@@ -0,0 +1,102 @@
+//! Demonstrates the linear, radial and conic gradients filling the backgrounds and borders of UI
+//! nodes.
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let gradients = [
+        // From left to right
+        Gradient::linear(PI / 2.0, [Color::RED, Color::YELLOW]),
+        // Diagonally, through several colors
+        Gradient::linear(
+            3.0 * PI / 4.0,
+            [Color::BLUE, Color::CYAN, Color::GREEN, Color::YELLOW],
+        ),
+        // With a hard edge between two stops at the same position
+        Gradient {
+            shape: GradientShape::Linear { angle: 0.0 },
+            stops: vec![
+                ColorStop::new(Color::NAVY, 0.0),
+                ColorStop::new(Color::MIDNIGHT_BLUE, 0.5),
+                ColorStop::new(Color::ORANGE, 0.5),
+                ColorStop::new(Color::GOLD, 1.0),
+            ],
+        },
+        // Around the center
+        Gradient::radial([Color::WHITE, Color::PURPLE]),
+        // Around the top-left corner, fading out
+        Gradient {
+            shape: GradientShape::Radial { center: Vec2::ZERO },
+            stops: vec![
+                ColorStop::new(Color::ORANGE_RED, 0.0),
+                ColorStop::new(Color::rgba(1.0, 0.27, 0.0, 0.0), 0.8),
+            ],
+        },
+        // A color wheel
+        Gradient::conic([
+            Color::RED,
+            Color::YELLOW,
+            Color::GREEN,
+            Color::CYAN,
+            Color::BLUE,
+            Color::FUCHSIA,
+            Color::RED,
+        ]),
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                align_content: AlignContent::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            for gradient in gradients {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(160.0), Val::Px(120.0)),
+                            margin: UiRect::all(Val::Px(15.0)),
+                            ..default()
+                        },
+                        border_radius: BorderRadius::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundGradient(gradient),
+                ));
+            }
+
+            // A border going around the node through the colors
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(160.0), Val::Px(120.0)),
+                        margin: UiRect::all(Val::Px(15.0)),
+                        border: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.25, 0.25, 0.25).into(),
+                    border_radius: BorderRadius::all(Val::Px(20.0)),
+                    ..default()
+                },
+                BorderGradient(Gradient::conic([Color::PINK, Color::TEAL, Color::PINK])),
+            ));
+        });
+}
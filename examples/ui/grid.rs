@@ -0,0 +1,104 @@
+//! Demonstrates the CSS Grid layout of UI nodes, laying out a dashboard with a header, a sidebar
+//! and an inventory of items.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Grid,
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                // A sidebar of fixed width, and the main area taking the rest of the width
+                grid_template_columns: vec![
+                    GridTrack::px(200.0).into(),
+                    GridTrack::flex(1.0).into(),
+                ],
+                // A header sized to its content, and the rest of the height
+                grid_template_rows: vec![GridTrack::auto().into(), GridTrack::flex(1.0).into()],
+                ..default()
+            },
+            background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            // The header spans both columns
+            parent.spawn(NodeBundle {
+                style: Style {
+                    grid_column: GridPlacement::span(2),
+                    size: Size::new(Val::Auto, Val::Px(60.0)),
+                    ..default()
+                },
+                background_color: Color::rgb(0.2, 0.3, 0.5).into(),
+                ..default()
+            });
+
+            // The sidebar lists its entries in rows of the same height
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_auto_rows: vec![GridTrack::px(40.0)],
+                        gap: Size::new(Val::Undefined, Val::Px(8.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                    ..default()
+                })
+                .with_children(|sidebar| {
+                    for _ in 0..5 {
+                        sidebar.spawn(NodeBundle {
+                            background_color: Color::rgb(0.3, 0.3, 0.3).into(),
+                            ..default()
+                        });
+                    }
+                });
+
+            // The inventory fills as many columns of slots as fit in its width
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_template_columns: vec![RepeatedGridTrack::px(
+                            GridTrackRepetition::AutoFill,
+                            64.0,
+                        )],
+                        grid_auto_rows: vec![GridTrack::px(64.0)],
+                        grid_auto_flow: GridAutoFlow::RowDense,
+                        gap: Size::new(Val::Px(10.0), Val::Px(10.0)),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|inventory| {
+                    for i in 0..40 {
+                        // Some items are larger, spanning several slots
+                        let (columns, rows) = match i % 11 {
+                            0 => (2, 2),
+                            5 => (2, 1),
+                            _ => (1, 1),
+                        };
+                        inventory.spawn(NodeBundle {
+                            style: Style {
+                                grid_column: GridPlacement::span(columns),
+                                grid_row: GridPlacement::span(rows),
+                                ..default()
+                            },
+                            background_color: Color::hsl(i as f32 * 9.0, 0.6, 0.5).into(),
+                            ..default()
+                        });
+                    }
+                });
+        });
+}
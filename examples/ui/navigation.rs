@@ -0,0 +1,117 @@
+//! This example illustrates moving the focus between buttons with the keyboard and gamepads.
+//!
+//! Use `Tab`, the arrow keys, or the directional pad and the left stick of a gamepad to move the
+//! focus, and `Enter`, `Space` or the south button of a gamepad to press the focused button.
+
+use bevy::{prelude::*, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .add_system(button_system)
+        .add_system(focus_text_system)
+        .run();
+}
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+#[derive(Component)]
+struct FocusText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("Nothing is focused", text_style.clone()),
+                FocusText,
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_template_columns: vec![RepeatedGridTrack::px(3, 150.0)],
+                        grid_template_rows: vec![RepeatedGridTrack::px(3, 65.0)],
+                        gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for i in 1..=9 {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    background_color: NORMAL_BUTTON.into(),
+                                    border_radius: BorderRadius::all(Val::Px(10.0)),
+                                    ..default()
+                                },
+                                FocusOutline::new(Color::WHITE, 3.0),
+                                Name::new(format!("Button {i}")),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    format!("{i}"),
+                                    text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Clicked => PRESSED_BUTTON.into(),
+            Interaction::Hovered | Interaction::None => NORMAL_BUTTON.into(),
+        };
+    }
+}
+
+fn focus_text_system(
+    mut focus_changed_events: EventReader<FocusChanged>,
+    name_query: Query<&Name>,
+    mut text_query: Query<&mut Text, With<FocusText>>,
+) {
+    for event in focus_changed_events.iter() {
+        let name = event
+            .focused
+            .and_then(|entity| name_query.get(entity).ok())
+            .map_or("Nothing", |name| name.as_str());
+        text_query.single_mut().sections[0].value = format!("{name} is focused");
+    }
+}
@@ -0,0 +1,70 @@
+//! This example illustrates text with inline styles, written with markup or with a builder.
+//!
+//! The paragraphs wrap in a fixed width, with line breaks falling in the middle of styled spans.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    // The bold spans use the bold font of the family, the others its regular font
+    let family = FontFamily::new(asset_server.load("fonts/FiraMono-Medium.ttf"))
+        .with_bold(asset_server.load("fonts/FiraSans-Bold.ttf"));
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            // Text parsed from markup, whose tags can be nested
+            let text = Text::from_markup(
+                "Press [b][color=yellow][[E][/color][/b] to open the door, or \
+                [b][color=#ff6060]hold [size=40]SPACE[/size][/color][/b] to break it down. \
+                Broken doors [color=gray]cannot be closed again[/color].",
+                family.clone(),
+                30.0,
+                Color::WHITE,
+            )
+            .unwrap();
+            parent.spawn(TextBundle { text, ..default() }.with_style(paragraph_style()));
+
+            // The same kind of text, built span by span
+            let text = RichTextBuilder::new(family, 30.0, Color::WHITE)
+                .text("The ")
+                .push(TextSpanStyle::bold())
+                .span("ancient key", TextSpanStyle::color(Color::GOLD))
+                .pop()
+                .text(" opens every door of the ")
+                .span("dungeon", TextSpanStyle::font_size(40.0))
+                .text(", but it ")
+                .span(
+                    "breaks after three uses",
+                    TextSpanStyle::color(Color::ORANGE_RED),
+                )
+                .text(".")
+                .build();
+            parent.spawn(TextBundle { text, ..default() }.with_style(paragraph_style()));
+        });
+}
+
+fn paragraph_style() -> Style {
+    Style {
+        max_size: Size::new(Val::Px(600.0), Val::Undefined),
+        ..default()
+    }
+}
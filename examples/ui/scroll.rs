@@ -0,0 +1,105 @@
+//! This example illustrates scrolling the content of UI nodes with the mouse wheel, by dragging
+//! touches, and with scrollbars.
+
+use bevy::{prelude::*, ui::RelativeCursorPosition, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 20.0,
+        color: Color::WHITE,
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(400.0), Val::Px(300.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // The scrolled list
+                    let list = parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                flex_grow: 1.0,
+                                padding: UiRect::all(Val::Px(10.0)),
+                                overflow: Overflow::Scroll,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for i in 0..50 {
+                                parent.spawn(
+                                    TextBundle::from_section(
+                                        format!("Item {i}"),
+                                        text_style.clone(),
+                                    )
+                                    .with_style(Style {
+                                        flex_shrink: 0.0,
+                                        size: Size::new(Val::Undefined, Val::Px(24.0)),
+                                        ..default()
+                                    }),
+                                );
+                            }
+                        })
+                        .id();
+
+                    // The scrollbar of the list, scrolling it when pressed
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(12.0), Val::Percent(100.0)),
+                                    ..default()
+                                },
+                                background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                                ..default()
+                            },
+                            Scrollbar {
+                                target: list,
+                                axis: ScrollAxis::Vertical,
+                            },
+                            RelativeCursorPosition::default(),
+                        ))
+                        .with_children(|parent| {
+                            // The thumb
+                            parent.spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.0), Val::Undefined),
+                                    ..default()
+                                },
+                                background_color: Color::rgb(0.6, 0.6, 0.6).into(),
+                                ..default()
+                            });
+                        });
+                });
+        });
+}
@@ -1,7 +1,8 @@
 //! This example illustrates how to create UI text and update it in a system.
 //!
 //! It displays the current FPS in the top left corner, as well as text that changes color
-//! in the bottom right. For text within a scene, please see the text2d example.
+//! in the bottom right, with an outline and a drop shadow. For text within a scene, please see
+//! the text2d example.
 
 use bevy::{
     diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
@@ -39,6 +40,12 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 font_size: 100.0,
                 color: Color::WHITE,
+                // A drop shadow keeps the text readable over any background
+                shadow: Some(TextShadow {
+                    offset: Vec2::new(4.0, 4.0),
+                    color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+                }),
+                ..default()
             },
         ) // Set the alignment of the Text
         .with_text_alignment(TextAlignment::Center)
@@ -64,12 +71,19 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 60.0,
                     color: Color::WHITE,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                 font_size: 60.0,
                 color: Color::GOLD,
+                // So does an outline
+                outline: Some(TextOutline {
+                    width: 2.0,
+                    color: Color::rgb(0.4, 0.2, 0.0),
+                }),
+                ..default()
             }),
         ]),
         FpsText,
@@ -34,6 +34,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: font.clone(),
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
         )
         .with_style(Style {
@@ -52,6 +53,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font: font.clone(),
                 font_size: 50.0,
                 color: Color::rgb(0.8, 0.2, 0.7),
+                ..default()
             },
         )
         .with_text_alignment(TextAlignment::Center)
@@ -77,6 +79,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::WHITE,
+                    ..default()
                 },
             ),
             TextSection::new(
@@ -85,12 +88,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::RED,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: font.clone(),
                 font_size: 30.0,
                 color: Color::ORANGE_RED,
+                ..default()
             }),
             TextSection::new(
                 " fps, ",
@@ -98,12 +103,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::YELLOW,
+                    ..default()
                 },
             ),
             TextSection::from_style(TextStyle {
                 font: font.clone(),
                 font_size: 30.0,
                 color: Color::GREEN,
+                ..default()
             }),
             TextSection::new(
                 " ms/frame",
@@ -111,6 +118,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: Color::BLUE,
+                    ..default()
                 },
             ),
         ])
@@ -132,6 +140,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font,
                 font_size: 50.0,
                 color: Color::WHITE,
+                ..default()
             },
         )
         .with_style(Style {
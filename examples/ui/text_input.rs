@@ -0,0 +1,84 @@
+//! This example illustrates editing text with text inputs, and reacting to their edition.
+
+use bevy::{prelude::*, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_startup_system(setup)
+        .add_system(show_submitted_text)
+        .add_system(highlight_focused_input)
+        .run();
+}
+
+#[derive(Component)]
+struct SubmittedText;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextInputBundle {
+                text_input: TextInput::new("Click to edit me", text_style.clone()),
+                style: Style {
+                    size: Size::new(Val::Px(400.0), Val::Undefined),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                border_color: Color::GRAY.into(),
+                ..default()
+            });
+            parent.spawn((
+                TextBundle::from_section("Press enter to submit the text", text_style),
+                SubmittedText,
+            ));
+        });
+}
+
+fn show_submitted_text(
+    mut submitted_events: EventReader<TextInputSubmitted>,
+    mut text_query: Query<&mut Text, With<SubmittedText>>,
+) {
+    for event in submitted_events.iter() {
+        text_query.single_mut().sections[0].value = format!("Submitted: {}", event.value);
+    }
+}
+
+fn highlight_focused_input(
+    focused: Res<FocusedTextInput>,
+    mut border_query: Query<(Entity, &mut BorderColor), With<TextInput>>,
+) {
+    if !focused.is_changed() {
+        return;
+    }
+    for (entity, mut border_color) in &mut border_query {
+        *border_color = if focused.0 == Some(entity) {
+            Color::WHITE.into()
+        } else {
+            Color::GRAY.into()
+        };
+    }
+}
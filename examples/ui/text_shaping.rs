@@ -0,0 +1,87 @@
+//! This example illustrates the layout of scripts whose letters join or combine, and of lines
+//! mixing left-to-right and right-to-left text, with fonts installed on the system.
+//!
+//! Run it with `cargo run --example text_shaping --features system_fonts`.
+
+use bevy::prelude::*;
+use bevy::text::SystemFonts;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+const LINES: [&str; 4] = [
+    // Arabic, whose letters join their neighbors
+    "مرحبا بالعالم",
+    // Hebrew, with some left-to-right numbers in the line
+    "שלום עולם 2023",
+    // Devanagari, with its vowel signs combining with the consonants
+    "नमस्ते दुनिया",
+    // A left-to-right sentence with a right-to-left word
+    "The word \"peace\" is שלום in Hebrew",
+];
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut system_fonts: ResMut<SystemFonts>,
+    mut fonts: ResMut<Assets<Font>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mut fallback_fonts: Vec<Handle<Font>> = ['م', 'ש', 'न']
+        .into_iter()
+        .filter_map(|character| system_fonts.load_for_char(character, &mut fonts))
+        .collect();
+    fallback_fonts.dedup();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for line in LINES {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font: font.clone(),
+                        fallback_fonts: fallback_fonts.clone(),
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ));
+            }
+
+            // A right-to-left paragraph wrapped in a fixed width, aligned to the right
+            parent.spawn(
+                TextBundle::from_section(
+                    "هذا نص طويل باللغة العربية يلتف على عدة أسطر داخل عرض ثابت",
+                    TextStyle {
+                        font: font.clone(),
+                        fallback_fonts,
+                        font_size: 30.0,
+                        color: Color::GOLD,
+                        ..default()
+                    },
+                )
+                .with_text_alignment(TextAlignment::Right)
+                .with_style(Style {
+                    max_size: Size::new(Val::Px(400.0), Val::Undefined),
+                    ..default()
+                }),
+            );
+        });
+}
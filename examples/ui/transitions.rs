@@ -0,0 +1,201 @@
+//! This example illustrates animating UI nodes with tweens, played by transitions when the nodes
+//! appear, disappear or are interacted with.
+//!
+//! The menus slide and fade in when their state is entered, and fade out when it is left.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_state(AppState::Main)
+        .add_startup_system(setup)
+        .add_system_set(SystemSet::on_enter(AppState::Main).with_system(spawn_main_menu))
+        .add_system_set(SystemSet::on_enter(AppState::Options).with_system(spawn_options_menu))
+        .add_system(menu_button_system)
+        .add_system(ui_state_scoped_system::<AppState>)
+        .run();
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum AppState {
+    Main,
+    Options,
+}
+
+/// A button switching to the given state when clicked
+#[derive(Component)]
+struct MenuButton(AppState);
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.45);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn spawn_main_menu(commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        commands,
+        &asset_server,
+        AppState::Main,
+        "Main menu",
+        Color::rgb(0.2, 0.3, 0.5),
+        &[("Options", AppState::Options)],
+    );
+}
+
+fn spawn_options_menu(commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        commands,
+        &asset_server,
+        AppState::Options,
+        "Options",
+        Color::rgb(0.5, 0.3, 0.2),
+        &[("Back", AppState::Main)],
+    );
+}
+
+fn spawn_menu(
+    mut commands: Commands,
+    asset_server: &AssetServer,
+    state: AppState,
+    title: &str,
+    color: Color,
+    buttons: &[(&str, AppState)],
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let duration = Duration::from_millis(400);
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            // the menu slides down and fades in when it appears, and fades out when it disappears
+            UiTransitions::default()
+                .with_enter(
+                    UiTween::new(UiProperty::Position(UiRect::top(Val::Px(0.0))), duration)
+                        .with_from(UiProperty::Position(UiRect::top(Val::Px(-80.0))))
+                        .with_ease(EaseFunction::BackOut),
+                )
+                .with_enter(
+                    UiTween::new(UiProperty::Opacity(1.0), duration)
+                        .with_from(UiProperty::Opacity(0.0)),
+                )
+                .with_exit(
+                    UiTween::new(UiProperty::Opacity(0.0), duration / 2)
+                        .with_ease(EaseFunction::QuadraticIn),
+                ),
+            // the menu disappears when its state is left
+            UiStateScoped(state),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(30.0)),
+                        gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                        ..default()
+                    },
+                    background_color: color.into(),
+                    border_radius: BorderRadius::all(Val::Px(15.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        title,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 50.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ));
+                    for (label, target) in buttons {
+                        spawn_button(parent, font.clone(), label, target.clone());
+                    }
+                });
+        });
+}
+
+fn spawn_button(parent: &mut ChildBuilder, font: Handle<Font>, label: &str, target: AppState) {
+    let duration = Duration::from_millis(150);
+    let button_size = Size::new(Val::Px(200.0), Val::Px(65.0));
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: button_size,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                border_radius: BorderRadius::all(Val::Px(10.0)),
+                ..default()
+            },
+            // the button changes its color and grows when hovered, without any custom system
+            UiTransitions::default()
+                .with_interaction(
+                    Interaction::None,
+                    UiTween::new(UiProperty::BackgroundColor(NORMAL_BUTTON), duration),
+                )
+                .with_interaction(
+                    Interaction::None,
+                    UiTween::new(UiProperty::Size(button_size), duration),
+                )
+                .with_interaction(
+                    Interaction::Hovered,
+                    UiTween::new(UiProperty::BackgroundColor(HOVERED_BUTTON), duration),
+                )
+                .with_interaction(
+                    Interaction::Hovered,
+                    UiTween::new(
+                        UiProperty::Size(Size::new(Val::Px(220.0), Val::Px(70.0))),
+                        duration,
+                    )
+                    .with_ease(EaseFunction::BackOut),
+                )
+                .with_interaction(
+                    Interaction::Clicked,
+                    UiTween::new(
+                        UiProperty::BackgroundColor(PRESSED_BUTTON),
+                        Duration::from_millis(50),
+                    ),
+                ),
+            MenuButton(target),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font,
+                    font_size: 35.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn menu_button_system(
+    mut state: ResMut<State<AppState>>,
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+) {
+    for (interaction, menu_button) in &interaction_query {
+        if *interaction == Interaction::Clicked && state.current() != &menu_button.0 {
+            state.set(menu_button.0.clone()).unwrap();
+        }
+    }
+}
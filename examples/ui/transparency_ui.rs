@@ -1,5 +1,6 @@
 //! Demonstrates how to use transparency with UI.
-//! Shows two colored buttons with transparent text.
+//! Shows two colored buttons with transparent text, the second one fading in and out with the
+//! [`Opacity`] of its parent.
 
 use bevy::prelude::*;
 
@@ -8,9 +9,14 @@ fn main() {
         .insert_resource(ClearColor(Color::BLACK))
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup)
+        .add_system(fade)
         .run();
 }
 
+/// Marks the node fading in and out with its descendants
+#[derive(Component)]
+struct Fading;
+
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(Camera2dBundle::default());
 
@@ -46,21 +52,27 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font_size: 40.0,
                             // Alpha channel of the color controls transparency.
                             color: Color::rgba(1.0, 1.0, 1.0, 0.2),
+                            ..default()
                         },
                     ));
                 });
         });
 
     commands
-        .spawn(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(50.0), Val::Percent(100.0)),
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::Center,
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(50.0), Val::Percent(100.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
                 ..default()
             },
-            ..default()
-        })
+            // The opacity multiplies the alpha of the node and all its descendants.
+            Opacity(1.0),
+            Fading,
+        ))
         .with_children(|parent| {
             // Button with a different color,
             // to demonstrate the text looks different due to its transparency.
@@ -83,8 +95,15 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font_size: 40.0,
                             // Alpha channel of the color controls transparency.
                             color: Color::rgba(1.0, 1.0, 1.0, 0.2),
+                            ..default()
                         },
                     ));
                 });
         });
 }
+
+fn fade(time: Res<Time>, mut opacity_query: Query<&mut Opacity, With<Fading>>) {
+    for mut opacity in &mut opacity_query {
+        opacity.0 = (time.elapsed_seconds().sin() + 1.0) / 2.0;
+    }
+}
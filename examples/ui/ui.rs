@@ -1,10 +1,6 @@
 //! This example illustrates the various features of Bevy UI.
 
-use bevy::{
-    input::mouse::{MouseScrollUnit, MouseWheel},
-    prelude::*,
-    winit::WinitSettings,
-};
+use bevy::{prelude::*, winit::WinitSettings};
 
 fn main() {
     App::new()
@@ -12,7 +8,6 @@ fn main() {
         // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
         .insert_resource(WinitSettings::desktop_app())
         .add_startup_system(setup)
-        .add_system(mouse_scroll)
         .run();
 }
 
@@ -62,6 +57,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                         font_size: 30.0,
                                         color: Color::WHITE,
+                                        ..default()
                                     },
                                 )
                                 .with_style(Style {
@@ -92,6 +88,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                 font_size: 25.,
                                 color: Color::WHITE,
+                                ..default()
                             },
                         )
                         .with_style(Style {
@@ -104,14 +101,14 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ..default()
                         }),
                     );
-                    // List with hidden overflow
+                    // List scrolled with the mouse wheel
                     parent
                         .spawn(NodeBundle {
                             style: Style {
                                 flex_direction: FlexDirection::Column,
                                 align_self: AlignSelf::Center,
                                 size: Size::new(Val::Percent(100.0), Val::Percent(50.0)),
-                                overflow: Overflow::Hidden,
+                                overflow: Overflow::Scroll,
                                 ..default()
                             },
                             background_color: Color::rgb(0.10, 0.10, 0.10).into(),
@@ -120,18 +117,16 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         .with_children(|parent| {
                             // Moving panel
                             parent
-                                .spawn((
-                                    NodeBundle {
-                                        style: Style {
-                                            flex_direction: FlexDirection::Column,
-                                            flex_grow: 1.0,
-                                            max_size: Size::UNDEFINED,
-                                            ..default()
-                                        },
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        flex_direction: FlexDirection::Column,
+                                        flex_grow: 1.0,
+                                        flex_shrink: 0.,
+                                        max_size: Size::UNDEFINED,
                                         ..default()
                                     },
-                                    ScrollingList::default(),
-                                ))
+                                    ..default()
+                                })
                                 .with_children(|parent| {
                                     // List items
                                     for i in 0..30 {
@@ -143,6 +138,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                                         .load("fonts/FiraSans-Bold.ttf"),
                                                     font_size: 20.,
                                                     color: Color::WHITE,
+                                                    ..default()
                                                 },
                                             )
                                             .with_style(Style {
@@ -293,32 +289,3 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 });
         });
 }
-
-#[derive(Component, Default)]
-struct ScrollingList {
-    position: f32,
-}
-
-fn mouse_scroll(
-    mut mouse_wheel_events: EventReader<MouseWheel>,
-    mut query_list: Query<(&mut ScrollingList, &mut Style, &Children, &Node)>,
-    query_item: Query<&Node>,
-) {
-    for mouse_wheel_event in mouse_wheel_events.iter() {
-        for (mut scrolling_list, mut style, children, uinode) in &mut query_list {
-            let items_height: f32 = children
-                .iter()
-                .map(|entity| query_item.get(*entity).unwrap().size().y)
-                .sum();
-            let panel_height = uinode.size().y;
-            let max_scroll = (items_height - panel_height).max(0.);
-            let dy = match mouse_wheel_event.unit {
-                MouseScrollUnit::Line => mouse_wheel_event.y * 20.,
-                MouseScrollUnit::Pixel => mouse_wheel_event.y,
-            };
-            scrolling_list.position += dy;
-            scrolling_list.position = scrolling_list.position.clamp(-max_scroll, 0.);
-            style.position.top = Val::Px(scrolling_list.position);
-        }
-    }
-}
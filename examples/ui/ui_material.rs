@@ -0,0 +1,91 @@
+//! This example illustrates drawing UI nodes with a custom shader, through a [`UiMaterial`].
+
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(UiMaterialPlugin::<ProgressBarMaterial>::default())
+        .add_startup_system(setup)
+        .add_system(update_progress)
+        .run();
+}
+
+// This is the struct that will be passed to your shader
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "3b6c0d5e-8f2a-4c1d-9e7b-a5f4d3c2b1a0"]
+struct ProgressBarMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    background_color: Color,
+    /// The filled part of the bar, from 0 to 1
+    #[uniform(0)]
+    progress: f32,
+}
+
+impl UiMaterial for ProgressBarMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/progress_bar.wgsl".into()
+    }
+}
+
+/// How fast a progress bar fills, in full bars per second
+#[derive(Component)]
+struct ProgressSpeed(f32);
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ProgressBarMaterial>>) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(20.0), Val::Px(20.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for (i, color) in [Color::RED, Color::ORANGE, Color::GREEN]
+                .into_iter()
+                .enumerate()
+            {
+                parent.spawn((
+                    MaterialNodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(400.0), Val::Px(30.0)),
+                            ..default()
+                        },
+                        border_radius: BorderRadius::all(Val::Px(15.0)),
+                        material: materials.add(ProgressBarMaterial {
+                            color,
+                            background_color: Color::rgb(0.15, 0.15, 0.15),
+                            progress: 0.0,
+                        }),
+                        ..default()
+                    },
+                    ProgressSpeed(0.1 * (i + 1) as f32),
+                ));
+            }
+        });
+}
+
+fn update_progress(
+    time: Res<Time>,
+    progress_bar_query: Query<(&Handle<ProgressBarMaterial>, &ProgressSpeed)>,
+    mut materials: ResMut<Assets<ProgressBarMaterial>>,
+) {
+    for (handle, speed) in &progress_bar_query {
+        if let Some(material) = materials.get_mut(handle) {
+            material.progress = (time.elapsed_seconds() * speed.0).fract();
+        }
+    }
+}
@@ -32,6 +32,7 @@ fn setup(mut commands: Commands, asset_server: ResMut<AssetServer>) {
         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
         font_size: 16.,
         color: Color::BLACK,
+        ..default()
     };
 
     commands
@@ -0,0 +1,216 @@
+//! This example illustrates drawing UI in the world, with health bars following moving cubes and
+//! a panel standing in the scene.
+//!
+//! Click the button of the panel to heal the cubes.
+
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_cubes)
+        .add_system(orbit_camera)
+        .add_system(update_health_bars)
+        .add_system(heal_button_system)
+        .run();
+}
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+#[derive(Component)]
+struct Health {
+    value: f32,
+    speed: f32,
+}
+
+/// The bar showing the [`Health`] of a cube
+#[derive(Component)]
+struct HealthBar(Entity);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane::from_size(8.0))),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 7.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // cubes with a health bar above them, always facing the camera
+    let cube_mesh = meshes.add(Mesh::from(shape::Cube { size: 0.8 }));
+    for (i, color) in [Color::ORANGE_RED, Color::GOLD, Color::TEAL]
+        .into_iter()
+        .enumerate()
+    {
+        let cube = commands
+            .spawn((
+                PbrBundle {
+                    mesh: cube_mesh.clone(),
+                    material: materials.add(color.into()),
+                    transform: Transform::from_xyz(i as f32 * 2.0 - 2.0, 0.4, 0.0),
+                    ..default()
+                },
+                Health {
+                    value: 1.0,
+                    speed: 0.05 + i as f32 * 0.03,
+                },
+            ))
+            .id();
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                        padding: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+                    border_radius: BorderRadius::all(Val::Px(5.0)),
+                    ..default()
+                },
+                WorldUi::new(Vec2::new(100.0, 16.0), 150.0)
+                    .with_anchor(cube)
+                    .with_transform(Transform::from_xyz(0.0, 0.7, 0.0))
+                    .with_orientation(WorldUiOrientation::Billboard),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                            ..default()
+                        },
+                        background_color: Color::LIME_GREEN.into(),
+                        border_radius: BorderRadius::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    HealthBar(cube),
+                ));
+            });
+    }
+
+    // a panel standing behind the cubes, with a button interacted with through the world
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::SpaceEvenly,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0.1, 0.1, 0.3, 0.8).into(),
+                border_radius: BorderRadius::all(Val::Px(20.0)),
+                ..default()
+            },
+            WorldUi::new(Vec2::new(400.0, 240.0), 100.0)
+                .with_transform(Transform::from_xyz(0.0, 2.0, -2.5)),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "World-space UI",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(150.0), Val::Px(65.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: NORMAL_BUTTON.into(),
+                    border_radius: BorderRadius::all(Val::Px(10.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Heal",
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                            font_size: 30.0,
+                            color: Color::rgb(0.9, 0.9, 0.9),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+fn move_cubes(time: Res<Time>, mut cubes: Query<(&mut Transform, &mut Health)>) {
+    for (mut transform, mut health) in &mut cubes {
+        transform.translation.z = (time.elapsed_seconds() + transform.translation.x).sin();
+        health.value = (health.value - health.speed * time.delta_seconds()).max(0.0);
+    }
+}
+
+fn orbit_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera>>) {
+    let angle = (time.elapsed_seconds() * 0.2).sin() * PI / 6.0;
+    for mut transform in &mut cameras {
+        *transform = Transform::from_xyz(7.0 * angle.sin(), 3.0, 7.0 * angle.cos())
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
+
+fn update_health_bars(
+    health_query: Query<&Health>,
+    mut bar_query: Query<(&HealthBar, &mut Style, &mut BackgroundColor)>,
+) {
+    for (health_bar, mut style, mut color) in &mut bar_query {
+        let Ok(health) = health_query.get(health_bar.0) else {
+            continue;
+        };
+        style.size.width = Val::Percent(health.value * 100.0);
+        *color = Color::rgb(1.0 - health.value, health.value, 0.2).into();
+    }
+}
+
+fn heal_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut health_query: Query<&mut Health>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Clicked => {
+                *color = PRESSED_BUTTON.into();
+                for mut health in &mut health_query {
+                    health.value = 1.0;
+                }
+            }
+            Interaction::Hovered => *color = HOVERED_BUTTON.into(),
+            Interaction::None => *color = NORMAL_BUTTON.into(),
+        }
+    }
+}
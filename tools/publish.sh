@@ -31,6 +31,7 @@ crates=(
     bevy_animation
     bevy_pbr
     bevy_gltf
+    bevy_navmesh
     bevy_scene
     bevy_sprite
     bevy_text